@@ -21,8 +21,9 @@ use aws_sdk_dynamodb::types::{Put, TransactWriteItem};
 use super::audience::AudienceMember;
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
+use super::feed::FeedRef;
 use super::item::{ATTR_PK, Item};
-use super::records::InvitationContextRecord;
+use super::records::{AudienceReason, InvitationContextRecord, TeamMemberRecord};
 
 impl Dao {
     /// Accept an invitation synchronously and atomically. In one transaction:
@@ -66,8 +67,9 @@ impl Dao {
             .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
 
-        // 2 + 3. Link the roster entry, and (match) the accepter's own feed row.
-        let (roster_item, feed_put, match_id) = match &inv.context {
+        // 2 + 3. Link the roster entry, and (match) the accepter's own feed row
+        //        or (team) their membership guard.
+        let (roster_item, extra_put, match_id) = match &inv.context {
             InvitationContextRecord::Match { match_id, .. } => {
                 let (item, starts_at, side_id) = self
                     .linked_match_player_item(
@@ -79,11 +81,11 @@ impl Dao {
                     .await?;
                 let feed_item = self.feed_item(
                     accepting_user_id,
-                    match_id,
-                    &starts_at,
+                    &FeedRef::match_at(match_id, &starts_at),
                     now,
                     &AudienceMember {
                         viewer_side_id: side_id,
+                        reasons: vec![AudienceReason::Participant],
                         ..Default::default()
                     },
                 )?;
@@ -95,15 +97,11 @@ impl Dao {
                 (item, Some(feed_put), Some(match_id.clone()))
             }
             InvitationContextRecord::Team { team_id, .. } => {
-                let item = self
-                    .linked_team_member_item(
-                        team_id,
-                        invitation_id,
-                        accepting_user_id,
-                        responded_at,
-                    )
+                let member = self
+                    .linked_team_member(team_id, invitation_id, accepting_user_id, responded_at)
                     .await?;
-                (item, None, None)
+                let guard = self.member_guard_put(team_id, &member)?;
+                (self.team_member_item(team_id, &member)?, guard, None)
             }
         };
 
@@ -118,12 +116,15 @@ impl Dao {
             .transact_write_items()
             .transact_items(TransactWriteItem::builder().put(put_inv).build())
             .transact_items(TransactWriteItem::builder().put(put_roster).build());
-        if let Some(feed_put) = feed_put {
-            tx = tx.transact_items(TransactWriteItem::builder().put(feed_put).build());
+        if let Some(extra_put) = extra_put {
+            tx = tx.transact_items(TransactWriteItem::builder().put(extra_put).build());
         }
 
         match tx.send().await {
             Ok(_) => Ok(match_id),
+            Err(e) if super::is_transaction_item_conditional_failure(&e, 2) => Err(
+                DaoError::Conflict(format!("user {accepting_user_id} is already a member")),
+            ),
             Err(e) if super::is_transaction_conditional_failure(&e) => {
                 Err(DaoError::NotFound(format!("invitation {invitation_id}")))
             }
@@ -173,15 +174,15 @@ impl Dao {
         ))
     }
 
-    /// Build the linked team-member item (external → user) for `invitation_id`.
-    /// Keeps the stable `membership_id`.
-    async fn linked_team_member_item(
+    /// The team member embedding `invitation_id`, linked (external → user) to
+    /// the accepting user. Keeps the stable `membership_id`.
+    async fn linked_team_member(
         &self,
         team_id: &str,
         invitation_id: &str,
         accepting_user_id: &str,
         responded_at: &str,
-    ) -> DaoResult<Item> {
+    ) -> DaoResult<TeamMemberRecord> {
         let Some(agg) = self.get_team(team_id).await? else {
             return Err(DaoError::NotFound(format!("team {team_id}")));
         };
@@ -202,8 +203,7 @@ impl Dao {
             inv.status = "accepted".to_string();
             inv.responded_at = Some(responded_at.to_string());
         }
-
-        self.team_member_item(team_id, &member)
+        Ok(member)
     }
 
     /// Link the roster entry that embeds `invitation_id` to the accepting user
@@ -239,15 +239,11 @@ impl Dao {
                 self.put_item(item).await
             }
             InvitationContextRecord::Team { team_id, .. } => {
-                let item = self
-                    .linked_team_member_item(
-                        team_id,
-                        invitation_id,
-                        accepting_user_id,
-                        responded_at,
-                    )
+                let member = self
+                    .linked_team_member(team_id, invitation_id, accepting_user_id, responded_at)
                     .await?;
-                self.put_item(item).await
+                self.put_item(self.team_member_item(team_id, &member)?)
+                    .await
             }
         }
     }
@@ -0,0 +1,71 @@
+//! Team announcements (`TEAM#<teamId>` / `ANNOUNCE#<announcementId>`): posted
+//! by a team admin, fanned out to the team's feed audience by the worker, and
+//! read back to hydrate their feed cards.
+
+use std::collections::HashMap;
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::records::TeamAnnouncementRecord;
+
+pub const TYPE_TEAM_ANNOUNCEMENT: &str = "team_announcement";
+
+impl Dao {
+    /// Store a new announcement. Its insert is what starts the fan-out.
+    #[tracing::instrument(skip(self, announcement), fields(team_id = %announcement.team_id))]
+    pub async fn put_team_announcement(
+        &self,
+        announcement: &TeamAnnouncementRecord,
+    ) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::Team(announcement.team_id.clone()),
+            &Sk::Announcement(announcement.id.clone()),
+            TYPE_TEAM_ANNOUNCEMENT,
+            announcement,
+        )?;
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Fetch announcements by `(team_id, announcement_id)` in one batch read,
+    /// keyed by announcement id. A key with no announcement has no entry.
+    #[tracing::instrument(skip(self))]
+    pub async fn batch_get_team_announcements(
+        &self,
+        keys: &[(String, String)],
+    ) -> DaoResult<HashMap<String, TeamAnnouncementRecord>> {
+        let mut seen = std::collections::HashSet::new();
+        let keys: Vec<_> = keys
+            .iter()
+            .filter(|key| seen.insert((*key).clone()))
+            .map(|(team_id, id)| {
+                HashMap::from([
+                    (
+                        ATTR_PK.to_string(),
+                        s(Pk::Team(team_id.clone()).to_string()),
+                    ),
+                    (
+                        ATTR_SK.to_string(),
+                        s(Sk::Announcement(id.clone()).to_string()),
+                    ),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, None).await?;
+        let mut out = HashMap::with_capacity(items.len());
+        for item in items {
+            let record: TeamAnnouncementRecord = from_item(item)?;
+            out.insert(record.id.clone(), record);
+        }
+        Ok(out)
+    }
+}
@@ -1,4 +1,4 @@
-//! Asset operations: create (pending), get, mark uploaded/failed.
+//! Asset operations: create (pending), get, mark uploaded/failed/removed.
 //!
 //! The `pending → uploaded` transition is normally driven by the async storage
 //! event worker, not by request handlers. The presigned upload target is built
@@ -59,6 +59,12 @@ impl Dao {
         self.set_asset_status(asset_id, "failed", None).await
     }
 
+    /// Mark an asset removed by moderation, so it can't be attached again.
+    #[tracing::instrument(skip(self))]
+    pub async fn mark_asset_removed(&self, asset_id: &str) -> DaoResult<()> {
+        self.set_asset_status(asset_id, "removed", None).await
+    }
+
     async fn set_asset_status(
         &self,
         asset_id: &str,
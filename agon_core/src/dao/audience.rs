@@ -9,10 +9,17 @@
 //! Per docs/async-design.md §5/§11 the audience is the union of:
 //! - followers of every **participating user** (players with a linked user id),
 //! - followers of every **involved team** (sides with a team id),
+//! - followers of the **club** owning each involved team, if any,
 //! - the **participants themselves** (so a user's own matches appear in their
 //!   own feed).
 //!
-//! Deduplicated across all three. Feed writes are idempotent on the match id
+//! A participants-only match (see `records::Visibility`) reaches only the
+//! participants themselves — none of the follower sets are walked. A
+//! followers-level match has the same audience as a public one: everyone it
+//! fans out to follows a participant, side team or club, which is exactly
+//! who may see it.
+//!
+//! Deduplicated across all four. Feed writes are idempotent on the match id
 //! anyway, so an accidental duplicate is harmless — dedup just avoids wasted
 //! writes.
 //!
@@ -25,11 +32,22 @@
 //! the match (creation, an invitation acceptance, or a meta update
 //! re-running fan-out), refreshed via `write_feed_items`' full-item overwrite
 //! — see each field's doc comment on `FeedItemRecord` for what triggers that.
+//!
+//! Each viewer also carries the reasons they're in the audience (see
+//! `AudienceReason`) — which participants, teams and clubs they follow, or
+//! that they play — so an unfollow can later tell which of their entries
+//! that follow alone put there.
+//!
+//! Feed entries about a user (a milestone) or a team (an announcement, a new
+//! member) go to that user or team's followers and the user themselves or
+//! the team's members, narrowed the same way by the user or team's
+//! visibility.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::client::Dao;
 use super::error::DaoResult;
+use super::records::{AudienceReason, Visibility};
 
 /// How many follower rows to pull per page while walking a follower list.
 const FOLLOWER_PAGE: u32 = 100;
@@ -57,6 +75,20 @@ pub struct AudienceMember {
     /// `None` for a viewer in the audience only via a follow (they're not
     /// playing) or a participant not yet assigned a side.
     pub viewer_side_id: Option<String>,
+    /// Why the viewer is in the audience — every participant, team and club
+    /// they follow here, and whether they play. Deduplicated.
+    pub reasons: Vec<AudienceReason>,
+}
+
+impl AudienceMember {
+    /// Record `reason`, returning false if it was already recorded.
+    pub fn add_reason(&mut self, reason: AudienceReason) -> bool {
+        if self.reasons.contains(&reason) {
+            return false;
+        }
+        self.reasons.push(reason);
+        true
+    }
 }
 
 impl Dao {
@@ -73,6 +105,7 @@ impl Dao {
         };
 
         let mut audience: HashMap<String, AudienceMember> = HashMap::new();
+        let followers_too = agg.match_.visibility != Visibility::Participants;
 
         // Participating users (players linked to an account) + their followers.
         for player in &agg.players {
@@ -82,21 +115,95 @@ impl Dao {
                 // players" list — you're never your own follower).
                 let entry = audience.entry(user_id.clone()).or_default();
                 entry.viewer_side_id = player.side_id.clone();
-                self.collect_user_followers(user_id, &mut audience).await?;
+                entry.add_reason(AudienceReason::Participant);
+                if followers_too {
+                    self.collect_user_followers(user_id, &mut audience).await?;
+                }
             }
         }
+        if !followers_too {
+            return Ok(audience);
+        }
 
         // Involved teams (sides with a team) → their followers. No specific
         // player to attribute, so they just join the audience.
-        for side in &agg.sides {
-            if let Some(team_id) = &side.team_id {
-                self.collect_team_followers(team_id, &mut audience).await?;
+        let team_ids: Vec<String> = agg
+            .sides
+            .iter()
+            .filter_map(|side| side.team_id.clone())
+            .collect();
+        for team_id in &team_ids {
+            self.collect_team_followers(team_id, &mut audience).await?;
+        }
+
+        // Clubs owning those teams → their followers, once per club (both
+        // sides of a club derby share it).
+        if !team_ids.is_empty() {
+            let teams = self.batch_get_team_metas(&team_ids).await?;
+            let club_ids: HashSet<&str> = teams
+                .values()
+                .filter_map(|team| team.club_id.as_deref())
+                .collect();
+            for club_id in club_ids {
+                self.collect_club_followers(club_id, &mut audience).await?;
             }
         }
 
         Ok(audience)
     }
 
+    /// The audience of a feed entry about `user_id`: them and, unless their
+    /// profile is participants-only, their followers (who each know them, as
+    /// for a match they play in). Empty if the user doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn resolve_user_feed_audience(
+        &self,
+        user_id: &str,
+    ) -> DaoResult<HashMap<String, AudienceMember>> {
+        let Some(user) = self.get_user(user_id).await? else {
+            return Ok(HashMap::new());
+        };
+        let own = AudienceMember {
+            reasons: vec![AudienceReason::Participant],
+            ..Default::default()
+        };
+        let mut audience = HashMap::from([(user_id.to_string(), own)]);
+        if user.visibility != Visibility::Participants {
+            self.collect_user_followers(user_id, &mut audience).await?;
+        }
+        Ok(audience)
+    }
+
+    /// The audience of a feed entry about `team_id`: its current members with
+    /// an account (not still-pending invitees) and, unless the team is
+    /// participants-only, its followers. Empty if the team doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn resolve_team_feed_audience(
+        &self,
+        team_id: &str,
+    ) -> DaoResult<HashMap<String, AudienceMember>> {
+        let Some(team) = self.get_team(team_id).await? else {
+            return Ok(HashMap::new());
+        };
+        let mut audience: HashMap<String, AudienceMember> = team
+            .members
+            .iter()
+            .filter(|m| m.invitation.as_ref().is_none_or(|i| i.status == "accepted"))
+            .filter_map(|m| m.user_id.clone())
+            .map(|id| {
+                let member = AudienceMember {
+                    reasons: vec![AudienceReason::Member(team_id.to_string())],
+                    ..Default::default()
+                };
+                (id, member)
+            })
+            .collect();
+        if team.team.visibility != Visibility::Participants {
+            self.collect_team_followers(team_id, &mut audience).await?;
+        }
+        Ok(audience)
+    }
+
     /// Walk a participating user's followers, adding each to `audience` (as
     /// an audience member) and recording that they follow `user_id`: always
     /// in `known_player_count`, and — capped at `MAX_KNOWN_PLAYERS` — in
@@ -113,7 +220,7 @@ impl Dao {
                 .await?;
             for edge in &page.items {
                 let entry = audience.entry(edge.follower_id.clone()).or_default();
-                if entry.known_player_ids.iter().any(|id| id == user_id) {
+                if !entry.add_reason(AudienceReason::FollowsUser(user_id.to_string())) {
                     continue;
                 }
                 entry.known_player_count += 1;
@@ -130,7 +237,8 @@ impl Dao {
     }
 
     /// Walk a team's followers, adding each to `audience` as an audience
-    /// member (no specific participant to attribute the follow to).
+    /// member (no specific participant to attribute the follow to, just the
+    /// team).
     async fn collect_team_followers(
         &self,
         team_id: &str,
@@ -142,7 +250,36 @@ impl Dao {
                 .list_team_followers(team_id, cursor.as_deref(), FOLLOWER_PAGE)
                 .await?;
             for edge in &page.items {
-                audience.entry(edge.follower_id.clone()).or_default();
+                audience
+                    .entry(edge.follower_id.clone())
+                    .or_default()
+                    .add_reason(AudienceReason::FollowsTeam(team_id.to_string()));
+            }
+            match page.next_cursor {
+                Some(c) => cursor = Some(c),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Walk a club's followers, adding each to `audience` as an audience
+    /// member — same as a team follow, just one level up.
+    async fn collect_club_followers(
+        &self,
+        club_id: &str,
+        audience: &mut HashMap<String, AudienceMember>,
+    ) -> DaoResult<()> {
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .list_club_followers(club_id, cursor.as_deref(), FOLLOWER_PAGE)
+                .await?;
+            for edge in &page.items {
+                audience
+                    .entry(edge.follower_id.clone())
+                    .or_default()
+                    .add_reason(AudienceReason::FollowsClub(club_id.to_string()));
             }
             match page.next_cursor {
                 Some(c) => cursor = Some(c),
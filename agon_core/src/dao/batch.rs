@@ -17,9 +17,10 @@ use super::error::{DaoError, DaoResult};
 use super::item::Item;
 
 /// DynamoDB's hard cap on keys per `BatchGetItem` request. Callers batch a page
-/// at a time, which the service caps (`MAX_PAGE_LIMIT`) well below this, so no
-/// request ever needs splitting.
-pub(super) const BATCH_GET_MAX: usize = 100;
+/// at a time, which the service caps (`MAX_PAGE_LIMIT`) well below this, so
+/// most requests never need splitting; the few that read more than a page
+/// (e.g. a club's whole fixture list) chunk by this.
+pub const BATCH_GET_MAX: usize = 100;
 
 /// DynamoDB's hard cap on items per `BatchWriteItem` request (puts and deletes
 /// alike). Callers chunk their requests into pages of this size.
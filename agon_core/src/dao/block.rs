@@ -0,0 +1,450 @@
+//! Blocking and muting between users.
+//!
+//! A block (`USER#<blocker>` / `BLOCK#<blocked>`) acts both ways: blocking
+//! drops any follow edges, pending follow requests and pending invitations
+//! between the two users, and from then on neither can follow, comment on or
+//! like the other's matches, or invite the other. Checks therefore look under both users'
+//! partitions — directly by key for a known pair, or through the GSI1
+//! projection (`UBLOCKEDBY#<blocked>`) for "everyone I can't interact with".
+//!
+//! A mute (`USER#<muter>` / `MUTE#<muted>`) is one-way and silent: the muted
+//! user's matches leave the muter's feed and their actions stop notifying
+//! the muter, but nothing changes from the muted user's side.
+
+use std::collections::{HashMap, HashSet};
+
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{
+    ATTR_GSI1PK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, item_pk, item_sk, s, to_item,
+};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::{BlockRecord, MuteRecord};
+
+pub const TYPE_BLOCK: &str = "block";
+pub const TYPE_MUTE: &str = "mute";
+
+/// GSI1 partition listing who has blocked a user.
+fn blocked_by_gsi1pk(user_id: &str) -> String {
+    format!("UBLOCKEDBY#{user_id}")
+}
+
+impl Dao {
+    /// Block a user. Idempotent. Also removes the follow edges (with their
+    /// counters), pending follow requests and pending invitations in both
+    /// directions; each of those steps is itself idempotent, so a retry after
+    /// a partial failure finishes the job.
+    #[tracing::instrument(skip(self))]
+    pub async fn block_user(
+        &self,
+        user_id: &str,
+        blocked_user_id: &str,
+        now: &str,
+    ) -> DaoResult<()> {
+        if user_id == blocked_user_id {
+            return Err(DaoError::Conflict("cannot block yourself".into()));
+        }
+        let block = BlockRecord {
+            user_id: user_id.into(),
+            blocked_user_id: blocked_user_id.into(),
+            created_at: now.into(),
+        };
+        let item = ItemBuilder::new(to_item(
+            &Pk::User(user_id.into()),
+            &Sk::Block(blocked_user_id.into()),
+            TYPE_BLOCK,
+            &block,
+        )?)
+        .gsi1(
+            blocked_by_gsi1pk(blocked_user_id),
+            Pk::User(user_id.into()).to_string(),
+        )
+        .build();
+        self.put_if_absent(item).await?;
+
+        self.unfollow_user(user_id, blocked_user_id).await?;
+        self.unfollow_user(blocked_user_id, user_id).await?;
+        for (followee, follower) in [(user_id, blocked_user_id), (blocked_user_id, user_id)] {
+            match self.delete_follow_request(followee, follower).await {
+                Ok(()) | Err(DaoError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+            self.revoke_pending_invitations(followee, follower).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete the invitations `inviter` has sent `invitee` that are still
+    /// pending, as if the inviter had revoked each. Only user-kind invitations
+    /// are in an inbox to find; a token invitation is refused when it's
+    /// answered instead.
+    async fn revoke_pending_invitations(&self, inviter: &str, invitee: &str) -> DaoResult<()> {
+        let mut cursor = None;
+        loop {
+            let page = self
+                .list_user_invitations(invitee, Some("pending"), cursor.as_deref(), 100)
+                .await?;
+            for inv in page
+                .items
+                .iter()
+                .filter(|i| i.invited_by_user_id == inviter)
+            {
+                match self.delete_invitation(&inv.id).await {
+                    Ok(()) | Err(DaoError::NotFound(_)) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            match page.next_cursor {
+                Some(c) => cursor = Some(c),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Lift a block. Idempotent. Follows removed by the block stay removed.
+    #[tracing::instrument(skip(self))]
+    pub async fn unblock_user(&self, user_id: &str, blocked_user_id: &str) -> DaoResult<()> {
+        self.delete_key(Pk::User(user_id.into()), Sk::Block(blocked_user_id.into()))
+            .await
+    }
+
+    /// Mute a user. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn mute_user(&self, user_id: &str, muted_user_id: &str, now: &str) -> DaoResult<()> {
+        if user_id == muted_user_id {
+            return Err(DaoError::Conflict("cannot mute yourself".into()));
+        }
+        let mute = MuteRecord {
+            user_id: user_id.into(),
+            muted_user_id: muted_user_id.into(),
+            created_at: now.into(),
+        };
+        self.put_if_absent(to_item(
+            &Pk::User(user_id.into()),
+            &Sk::Mute(muted_user_id.into()),
+            TYPE_MUTE,
+            &mute,
+        )?)
+        .await
+    }
+
+    /// Unmute a user. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn unmute_user(&self, user_id: &str, muted_user_id: &str) -> DaoResult<()> {
+        self.delete_key(Pk::User(user_id.into()), Sk::Mute(muted_user_id.into()))
+            .await
+    }
+
+    /// The users `user_id` has blocked, cursor-paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_blocked_users(
+        &self,
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<BlockRecord>> {
+        self.query_page(
+            self.own_collection(user_id, Sk::block_prefix()),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// The users `user_id` has muted, cursor-paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_muted_users(
+        &self,
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<MuteRecord>> {
+        self.query_page(
+            self.own_collection(user_id, Sk::mute_prefix()),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Whether either user has blocked the other.
+    #[tracing::instrument(skip(self))]
+    pub async fn is_blocked_between(&self, a: &str, b: &str) -> DaoResult<bool> {
+        Ok(!self.blocked_among(a, &[b.to_string()]).await?.is_empty())
+    }
+
+    /// Of `others`, the users with a block either way with `user_id`. One
+    /// `BatchGetItem` over both directions' keys.
+    #[tracing::instrument(skip(self))]
+    pub async fn blocked_among(
+        &self,
+        user_id: &str,
+        others: &[String],
+    ) -> DaoResult<HashSet<String>> {
+        let mut seen = HashSet::new();
+        let keys: Vec<_> = others
+            .iter()
+            .filter(|id| id.as_str() != user_id && seen.insert(id.as_str()))
+            .flat_map(|other| {
+                [
+                    key(Pk::User(user_id.into()), Sk::Block(other.clone())),
+                    key(Pk::User(other.clone()), Sk::Block(user_id.into())),
+                ]
+            })
+            .collect();
+        if keys.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        // Projecting the key alone is enough: the other user is in the SK of
+        // our own block, or the PK of theirs.
+        let items = self
+            .batch_get_all(keys, Some(&format!("{ATTR_PK}, {ATTR_SK}")))
+            .await?;
+        let mut blocked = HashSet::with_capacity(items.len());
+        for item in items {
+            match (item_pk(&item)?, item_sk(&item)?) {
+                (Pk::User(owner), Sk::Block(other)) if owner == user_id => {
+                    blocked.insert(other);
+                }
+                (Pk::User(other), Sk::Block(_)) => {
+                    blocked.insert(other);
+                }
+                (pk, sk) => {
+                    return Err(DaoError::Malformed(format!(
+                        "unexpected block item {pk} / {sk}"
+                    )));
+                }
+            }
+        }
+        Ok(blocked)
+    }
+
+    /// Whether a notification from `actor_id` should be dropped for
+    /// `recipient_id`: the recipient muted the actor, or either blocked the
+    /// other. One `BatchGetItem`.
+    #[tracing::instrument(skip(self))]
+    pub async fn is_silenced(&self, recipient_id: &str, actor_id: &str) -> DaoResult<bool> {
+        if recipient_id == actor_id {
+            return Ok(false);
+        }
+        let keys = vec![
+            key(Pk::User(recipient_id.into()), Sk::Mute(actor_id.into())),
+            key(Pk::User(recipient_id.into()), Sk::Block(actor_id.into())),
+            key(Pk::User(actor_id.into()), Sk::Block(recipient_id.into())),
+        ];
+        let items = self.batch_get_all(keys, Some(ATTR_PK)).await?;
+        Ok(!items.is_empty())
+    }
+
+    /// Every user `user_id` has a block with, either way — for filtering whole
+    /// lists (comments) rather than checking one pair. Reads both collections
+    /// in full; block lists are short.
+    #[tracing::instrument(skip(self))]
+    pub async fn blocked_user_ids(&self, user_id: &str) -> DaoResult<HashSet<String>> {
+        let mut ids: HashSet<String> = self
+            .query_all::<BlockRecord>(self.own_collection(user_id, Sk::block_prefix()))
+            .await?
+            .into_iter()
+            .map(|b| b.blocked_user_id)
+            .collect();
+        let blocked_by = self
+            .client
+            .query()
+            .table_name(self.table())
+            .index_name("GSI1")
+            .key_condition_expression("#pk = :pk")
+            .expression_attribute_names("#pk", ATTR_GSI1PK)
+            .expression_attribute_values(":pk", s(blocked_by_gsi1pk(user_id)));
+        ids.extend(
+            self.query_all::<BlockRecord>(blocked_by)
+                .await?
+                .into_iter()
+                .map(|b| b.user_id),
+        );
+        Ok(ids)
+    }
+
+    /// Every user whose activity `user_id` shouldn't see in their feed: the
+    /// ones they muted plus everyone they have a block with.
+    #[tracing::instrument(skip(self))]
+    pub async fn hidden_user_ids(&self, user_id: &str) -> DaoResult<HashSet<String>> {
+        let mut ids = self.blocked_user_ids(user_id).await?;
+        ids.extend(
+            self.query_all::<MuteRecord>(self.own_collection(user_id, Sk::mute_prefix()))
+                .await?
+                .into_iter()
+                .map(|m| m.muted_user_id),
+        );
+        Ok(ids)
+    }
+
+    /// A query over one of a user's own `BLOCK#` / `MUTE#` collections.
+    fn own_collection(&self, user_id: &str, sk_prefix: String) -> QueryFluentBuilder {
+        self.client
+            .query()
+            .table_name(self.table())
+            .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":pk", s(Pk::User(user_id.into()).to_string()))
+            .expression_attribute_values(":sk", s(sk_prefix))
+    }
+
+    /// Run `query` to exhaustion.
+    async fn query_all<T: serde::de::DeserializeOwned>(
+        &self,
+        query: QueryFluentBuilder,
+    ) -> DaoResult<Vec<T>> {
+        let mut out = Vec::new();
+        let mut start_key = None;
+        loop {
+            let page = query
+                .clone()
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            for item in page.items.unwrap_or_default() {
+                out.push(from_item(item)?);
+            }
+            match page.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => break,
+            }
+        }
+        Ok(out)
+    }
+
+    /// Put `item` unless it already exists (keeping the original
+    /// `created_at`).
+    async fn put_if_absent(
+        &self,
+        item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
+    ) -> DaoResult<()> {
+        let result = self
+            .client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_put_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    async fn delete_key(&self, pk: Pk, sk: Sk) -> DaoResult<()> {
+        self.client
+            .delete_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(pk.to_string()))
+            .key(ATTR_SK, s(sk.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn key(pk: Pk, sk: Sk) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+    HashMap::from([
+        (ATTR_PK.to_string(), s(pk.to_string())),
+        (ATTR_SK.to_string(), s(sk.to_string())),
+    ])
+}
+
+fn is_put_conditional_failure(err: &SdkError<PutItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), PutItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{InvitationContextRecord, InvitationKindRecord, InvitationRecord};
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+
+    fn invitation(id: &str, from: &str, to: &str, status: &str) -> InvitationRecord {
+        InvitationRecord {
+            id: id.into(),
+            status: status.into(),
+            invited_by_user_id: from.into(),
+            invited_user_id: Some(to.into()),
+            invite_token: None,
+            invitee_email: None,
+            kind: InvitationKindRecord::User {
+                invited_user_id: to.into(),
+            },
+            context: InvitationContextRecord::Team {
+                team_id: "t1".into(),
+                team_name: "Rovers".into(),
+            },
+            invited_at: NOW.into(),
+            responded_at: None,
+            link: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn blocking_revokes_pending_invitations_both_ways() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        for inv in [
+            invitation("sent", "a", "b", "pending"),
+            invitation("received", "b", "a", "pending"),
+            invitation("answered", "a", "b", "accepted"),
+            invitation("elsewhere", "c", "b", "pending"),
+        ] {
+            dao.create_invitation(&inv).await.unwrap();
+        }
+
+        dao.block_user("a", "b", NOW).await.unwrap();
+        assert!(dao.is_blocked_between("b", "a").await.unwrap());
+        for (id, kept) in [
+            ("sent", false),
+            ("received", false),
+            ("answered", true),
+            ("elsewhere", true),
+        ] {
+            let found = dao.get_invitation(id).await.unwrap();
+            assert_eq!(found.is_some(), kept, "{id}");
+        }
+        // Nothing left to revoke: blocking again is a no-op.
+        dao.block_user("a", "b", NOW).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn blocking_drops_follows_both_ways() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        dao.follow_user("a", "b", NOW).await.unwrap();
+        dao.follow_user("b", "a", NOW).await.unwrap();
+        dao.block_user("b", "a", NOW).await.unwrap();
+        assert!(!dao.is_following_user("a", "b").await.unwrap());
+        assert!(!dao.is_following_user("b", "a").await.unwrap());
+        assert_eq!(
+            dao.blocked_among("a", &["b".into(), "c".into()])
+                .await
+                .unwrap(),
+            HashSet::from(["b".to_string()])
+        );
+        assert!(matches!(
+            dao.block_user("a", "a", NOW).await,
+            Err(DaoError::Conflict(_))
+        ));
+    }
+}
@@ -1,11 +1,16 @@
 use aws_sdk_dynamodb::Client;
 
+use super::memory::MemoryTable;
+
 /// Handle to the DynamoDB single table. Cheap to clone (the SDK client is an
 /// `Arc` internally); pass by shared reference or clone freely.
 #[derive(Clone)]
 pub struct Dao {
     pub(super) client: Client,
     pub(super) table: String,
+    /// How long feed entries are kept past their sort time, in days (see
+    /// `FeedItemRecord::ttl`).
+    pub(super) feed_retention_days: u32,
 }
 
 impl Dao {
@@ -14,9 +19,19 @@ impl Dao {
         Self {
             client,
             table: table.into(),
+            feed_retention_days: super::feed::DEFAULT_FEED_RETENTION_DAYS,
         }
     }
 
+    /// Keep feed entries `days` past their sort time, rather than
+    /// [`DEFAULT_FEED_RETENTION_DAYS`](super::feed::DEFAULT_FEED_RETENTION_DAYS).
+    /// Applies to entries written from now on (and those the TTL backfill
+    /// stamps); ones already stamped keep their TTL.
+    pub fn with_feed_retention_days(mut self, days: u32) -> Self {
+        self.feed_retention_days = days;
+        self
+    }
+
     /// Build a DAO using the ambient AWS config (env / profile / IMDS). The
     /// table name typically comes from `AGON_TABLE_NAME`.
     pub async fn from_env(table: impl Into<String>) -> Self {
@@ -24,6 +39,12 @@ impl Dao {
         Self::new(Client::new(&config), table)
     }
 
+    /// Build a DAO over an in-memory table rather than DynamoDB (see
+    /// [`memory`](super::memory)): for tests, and for running offline.
+    pub fn in_memory(memory: &MemoryTable) -> Self {
+        Self::new(memory.client(), memory.name())
+    }
+
     pub(super) fn table(&self) -> &str {
         &self.table
     }
@@ -0,0 +1,398 @@
+//! Club operations: a club groups several teams (`TeamRecord::club_id`) under
+//! one set of club admins, who inherit admin on every team the club owns.
+//!
+//! The club partition (`CLUB#<clubId>`) holds the meta, the admins and the
+//! club's own follower edges (see `follow`). Which teams belong to the club is
+//! recorded on each team's meta and listed through its GSI1 projection
+//! (`CTEAMS#<clubId>`), so a team is in at most one club by construction.
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{Put, TransactWriteItem};
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::records::{ClubAdminRecord, ClubRecord, TeamRecord};
+
+pub const TYPE_CLUB: &str = "club";
+pub const TYPE_CLUB_ADMIN: &str = "club_admin";
+
+/// GSI1 partition listing a club's teams.
+pub(super) fn club_teams_gsi1pk(club_id: &str) -> String {
+    format!("CTEAMS#{club_id}")
+}
+
+impl Dao {
+    /// Create a club and make its creator the first club admin, in one
+    /// transaction. `Conflict` if the club id already exists.
+    #[tracing::instrument(skip(self, club, creator), fields(club_id = %club.id))]
+    pub async fn create_club(&self, club: &ClubRecord, creator: &ClubAdminRecord) -> DaoResult<()> {
+        let put_meta = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(to_item(
+                &Pk::Club(club.id.clone()),
+                &Sk::Meta,
+                TYPE_CLUB,
+                club,
+            )?))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let put_admin = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(club_admin_item(creator)?))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put_meta).build())
+            .transact_items(TransactWriteItem::builder().put(put_admin).build())
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::Conflict(
+                format!("club {} already exists", club.id),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Fetch a club's meta. `None` if absent.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_club(&self, club_id: &str) -> DaoResult<Option<ClubRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Club(club_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => Ok(Some(from_item(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Add (or overwrite) a club admin.
+    #[tracing::instrument(skip(self, admin), fields(club_id = %admin.club_id, user_id = %admin.user_id))]
+    pub async fn put_club_admin(&self, admin: &ClubAdminRecord) -> DaoResult<()> {
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(club_admin_item(admin)?))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Whether `user_id` is an admin of the club. Existence check on the
+    /// `CLUB#<club>` / `CLUBADMIN#<user>` item.
+    #[tracing::instrument(skip(self))]
+    pub async fn is_club_admin(&self, club_id: &str, user_id: &str) -> DaoResult<bool> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Club(club_id.into()).to_string()))
+            .key("SK", s(Sk::ClubAdmin(user_id.into()).to_string()))
+            .projection_expression(ATTR_PK) // existence check only
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(out.item.is_some())
+    }
+
+    /// Put a team under a club. `Conflict` if the team already belongs to a
+    /// club (including this one); `NotFound` if the team doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn attach_team_to_club(&self, team_id: &str, club_id: &str) -> DaoResult<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression("SET club_id = :club, #gsi1pk = :gsi1pk, #gsi1sk = :gsi1sk")
+            .condition_expression("attribute_exists(#pk) AND attribute_not_exists(club_id)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":club", s(club_id))
+            .expression_attribute_values(":gsi1pk", s(club_teams_gsi1pk(club_id)))
+            .expression_attribute_values(":gsi1sk", s(Pk::Team(team_id.into()).to_string()))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => {
+                match self.get_team_meta(team_id).await? {
+                    None => Err(DaoError::NotFound(format!("team {team_id}"))),
+                    Some(_) => Err(DaoError::Conflict(format!(
+                        "team {team_id} already belongs to a club"
+                    ))),
+                }
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Take a team out of a club. `NotFound` if the team isn't in that club.
+    #[tracing::instrument(skip(self))]
+    pub async fn detach_team_from_club(&self, team_id: &str, club_id: &str) -> DaoResult<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression("REMOVE club_id, #gsi1pk, #gsi1sk")
+            .condition_expression("club_id = :club")
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":club", s(club_id))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => Err(DaoError::NotFound(format!(
+                "team {team_id} in club {club_id}"
+            ))),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Every team the club owns, via GSI1 (`CTEAMS#<clubId>`). Clubs run a
+    /// handful of teams, so this reads the whole list rather than paging.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_club_teams(&self, club_id: &str) -> DaoResult<Vec<TeamRecord>> {
+        let mut teams = Vec::new();
+        let mut start_key = None;
+        loop {
+            let out = self
+                .client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(club_teams_gsi1pk(club_id)))
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+            for item in out.items.unwrap_or_default() {
+                teams.push(from_item(item)?);
+            }
+
+            match out.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => break,
+            }
+        }
+        Ok(teams)
+    }
+}
+
+fn club_admin_item(
+    admin: &ClubAdminRecord,
+) -> DaoResult<HashMap<String, aws_sdk_dynamodb::types::AttributeValue>> {
+    to_item(
+        &Pk::Club(admin.club_id.clone()),
+        &Sk::ClubAdmin(admin.user_id.clone()),
+        TYPE_CLUB_ADMIN,
+        admin,
+    )
+}
+
+fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), UpdateItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{
+        AudienceReason, MatchPlayerRecord, MatchRecord, TeamMemberRecord, Visibility,
+    };
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+
+    async fn club(dao: &Dao, club_id: &str) {
+        let club = ClubRecord {
+            id: club_id.into(),
+            name: format!("{club_id} FC"),
+            follower_count: 0,
+            created_at: NOW.into(),
+        };
+        let admin = ClubAdminRecord {
+            club_id: club_id.into(),
+            user_id: "chair".into(),
+            created_at: NOW.into(),
+        };
+        dao.create_club(&club, &admin).await.unwrap();
+    }
+
+    async fn team(dao: &Dao, team_id: &str) {
+        let team = TeamRecord {
+            id: team_id.into(),
+            name: team_id.into(),
+            invite_token: None,
+            follower_count: 0,
+            club_id: None,
+            archived_at: None,
+            visibility: Visibility::Public,
+            created_at: NOW.into(),
+        };
+        let captain = TeamMemberRecord {
+            team_id: team_id.into(),
+            membership_id: format!("{team_id}-captain"),
+            user_id: Some(format!("{team_id}-captain")),
+            display_name: None,
+            role: "admin".into(),
+            invitation: None,
+            created_at: NOW.into(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        };
+        dao.create_team(&team, &captain).await.unwrap();
+    }
+
+    /// A match between `home` and `away` with one player, `u1`.
+    async fn team_match(dao: &Dao, id: &str, home: &str, away: &str, visibility: &str) {
+        let match_: MatchRecord = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": "Derby",
+            "description": "",
+            "match_type": "football",
+            "status": "scheduled",
+            "starts_at": NOW,
+            "sides": {
+                "home": { "side_id": "home", "team_id": home },
+                "away": { "side_id": "away", "team_id": away },
+            },
+            "visibility": visibility,
+            "created_at": NOW,
+        }))
+        .unwrap();
+        let player = MatchPlayerRecord {
+            player_id: "p1".into(),
+            user_id: Some("u1".into()),
+            display_name: None,
+            side_id: Some("home".into()),
+            is_member_of_team: None,
+            invitation: None,
+        };
+        dao.create_match(&match_, &[player]).await.unwrap();
+    }
+
+    fn ids(teams: Vec<TeamRecord>) -> Vec<String> {
+        let mut ids: Vec<String> = teams.into_iter().map(|t| t.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn a_clubs_teams_are_listed_through_its_projection() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        club(&dao, "c1").await;
+        club(&dao, "c2").await;
+        for id in ["t1", "t2", "t3"] {
+            team(&dao, id).await;
+        }
+        dao.attach_team_to_club("t1", "c1").await.unwrap();
+        dao.attach_team_to_club("t2", "c1").await.unwrap();
+        dao.attach_team_to_club("t3", "c2").await.unwrap();
+        assert!(matches!(
+            dao.attach_team_to_club("t1", "c2").await,
+            Err(DaoError::Conflict(_))
+        ));
+        assert!(matches!(
+            dao.attach_team_to_club("t9", "c1").await,
+            Err(DaoError::NotFound(_))
+        ));
+        assert_eq!(ids(dao.list_club_teams("c1").await.unwrap()), ["t1", "t2"]);
+
+        assert!(matches!(
+            dao.detach_team_from_club("t1", "c2").await,
+            Err(DaoError::NotFound(_))
+        ));
+        dao.detach_team_from_club("t1", "c1").await.unwrap();
+        assert_eq!(ids(dao.list_club_teams("c1").await.unwrap()), ["t2"]);
+        assert_eq!(ids(dao.list_club_teams("c2").await.unwrap()), ["t3"]);
+        let t1 = dao.get_team_meta("t1").await.unwrap().unwrap();
+        assert_eq!(t1.club_id, None);
+        assert!(dao.is_club_admin("c1", "chair").await.unwrap());
+        assert!(!dao.is_club_admin("c1", "t1-captain").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn club_follows_are_idempotent_and_counted() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        club(&dao, "c1").await;
+        dao.follow_club("u2", "c1", NOW).await.unwrap();
+        dao.follow_club("u2", "c1", NOW).await.unwrap();
+        dao.follow_club("u3", "c1", NOW).await.unwrap();
+        assert_eq!(dao.get_club("c1").await.unwrap().unwrap().follower_count, 2);
+        assert!(dao.is_following_club("u2", "c1").await.unwrap());
+
+        dao.unfollow_club("u2", "c1").await.unwrap();
+        dao.unfollow_club("u2", "c1").await.unwrap();
+        assert_eq!(dao.get_club("c1").await.unwrap().unwrap().follower_count, 1);
+        assert!(!dao.is_following_club("u2", "c1").await.unwrap());
+        let followers = dao.list_club_followers("c1", None, 10).await.unwrap();
+        let followers: Vec<_> = followers.items.into_iter().map(|f| f.follower_id).collect();
+        assert_eq!(followers, ["u3"]);
+    }
+
+    #[tokio::test]
+    async fn club_followers_see_their_teams_matches() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        club(&dao, "c1").await;
+        for id in ["t1", "t2", "t3", "t4"] {
+            team(&dao, id).await;
+        }
+        dao.attach_team_to_club("t1", "c1").await.unwrap();
+        dao.attach_team_to_club("t2", "c1").await.unwrap();
+        dao.follow_club("fan", "c1", NOW).await.unwrap();
+
+        // A derby between two of the club's teams reaches the fan once.
+        team_match(&dao, "derby", "t1", "t2", "public").await;
+        let audience = dao.resolve_fanout_audience("derby").await.unwrap();
+        assert_eq!(
+            audience["fan"].reasons,
+            [AudienceReason::FollowsClub("c1".into())]
+        );
+        assert_eq!(audience["u1"].reasons, [AudienceReason::Participant]);
+
+        // A team outside the club brings in none of its followers...
+        team_match(&dao, "friendly", "t3", "t4", "public").await;
+        let audience = dao.resolve_fanout_audience("friendly").await.unwrap();
+        assert!(!audience.contains_key("fan"));
+
+        // ...and a participants-only match walks no follower lists at all.
+        team_match(&dao, "private", "t1", "t3", "participants").await;
+        let audience = dao.resolve_fanout_audience("private").await.unwrap();
+        assert_eq!(audience.keys().collect::<Vec<_>>(), ["u1"]);
+    }
+}
@@ -1,28 +1,126 @@
-//! Fan-out feed operations: write feed entries for a set of viewers, and read a
-//! viewer's feed newest-first.
+//! Fan-out feed operations: write feed entries for a set of viewers, read a
+//! viewer's feed newest-first, and store the order of their ranked feed.
 //!
 //! Feed entries are thin pointers (`ref_type`/`ref_id` + `starts_at`), one row
-//! per viewer, living under `UFEED#<viewer>` / `FEED#<starts_at>#<matchId>`.
-//! Writes are idempotent on the sort key: re-fanning-out the same match to the
-//! same viewer overwrites the identical row, so the at-least-once fan-out
-//! workflow can replay a chunk harmlessly.
+//! per viewer, living under `UFEED#<viewer>` / `FEED#<at>#<refId>`. Writes
+//! are idempotent on the sort key: re-fanning-out the same match (or result,
+//! milestone, ...) to the same viewer overwrites the identical row, so the
+//! at-least-once fan-out workflows can replay a chunk harmlessly.
 //!
 //! Writes use `BatchWriteItem` (25 items/request max) so a large audience is
 //! written efficiently; the caller (the fan-out workflow) chunks the audience so
 //! a mid-way failure resumes rather than restarts.
+//!
+//! Every match and result entry is also projected to GSI2 (`MFEED#<matchId>`
+//! / `<at>#<viewerId>`), so all of a match's rows can be found again when its
+//! `starts_at` — and so every row's sort key — changes (a postponement):
+//! fan-out writes the new rows and `prune_stale_feed_items` deletes the old
+//! ones, and likewise a result's rows when the result changes or goes. The
+//! same pass drops the rows of viewers no longer in the audience, when the
+//! caller supplies it (a match narrowed to participants-only). Rows written
+//! before the projection existed get it from a one-off backfill
+//! (`project_match_feed_page`).
+//!
+//! Each entry records why its viewer is in the audience (see
+//! `AudienceReason`), so a new follow can add the followed user's, team's or
+//! club's recent matches to the follower's feed alongside whatever already
+//! put them there (`add_feed_reason`), and an unfollow can take out exactly
+//! the entries that follow alone put there (`remove_feed_reason`).
+//!
+//! Entries don't live forever: each carries a TTL the retention window
+//! (`DEFAULT_FEED_RETENTION_DAYS`, or `Dao::with_feed_retention_days`) past
+//! its sort time, and DynamoDB deletes it some time after that. Until it
+//! does, the reads here skip it. Entries from before retention get their
+//! TTL from a one-off backfill (`stamp_feed_ttl_page`).
 
-use aws_sdk_dynamodb::types::{PutRequest, WriteRequest};
+use std::collections::{HashMap, HashSet};
 
-use super::audience::AudienceMember;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, DeleteRequest, PutRequest, WriteRequest};
+use serde::{Deserialize, Serialize};
+
+use super::audience::{AudienceMember, MAX_KNOWN_PLAYERS};
 use super::batch::BATCH_WRITE_MAX;
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ItemBuilder, to_item};
+use super::item::{
+    ATTR_GSI2PK, ATTR_GSI2SK, ATTR_PK, ATTR_SK, ATTR_TTL, ATTR_TYPE, ItemBuilder, from_item, s,
+    to_item,
+};
 use super::keys::{Pk, Sk};
 use super::page::Page;
-use super::records::FeedItemRecord;
+use super::records::{
+    AudienceReason, FeedItemRecord, FeedRefType, RankedFeedEntryRecord, RankedFeedRecord,
+    Visibility,
+};
 
 pub const TYPE_FEED_ITEM: &str = "feed_item";
+pub const TYPE_RANKED_FEED: &str = "ranked_feed";
+
+/// How long feed entries are kept past their sort time (a match's start), by
+/// default.
+pub const DEFAULT_FEED_RETENTION_DAYS: u32 = 180;
+
+/// Filter for reads that skip entries past their TTL but not yet deleted.
+const NOT_EXPIRED: &str = "(attribute_not_exists(#ttl) OR #ttl > :now)";
+
+/// When an entry sorted at `at` (RFC3339) expires, `retention_days` later,
+/// as the epoch seconds DynamoDB's TTL wants. `None` if `at` doesn't parse —
+/// the entry is then kept.
+fn feed_ttl(at: &str, retention_days: u32) -> Option<i64> {
+    let at = chrono::DateTime::parse_from_rfc3339(at).ok()?;
+    Some((at + chrono::Duration::days(retention_days.into())).timestamp())
+}
+
+/// The current time in epoch seconds, to compare TTLs against.
+fn now_epoch() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Most of a followed user's match ids read when backfilling a new follow.
+/// They come back in id order, not by date, so this bounds the read rather
+/// than picking the recent ones — `follow_backfill_refs` sorts those out.
+const BACKFILL_SCAN: u32 = 500;
+
+/// GSI2 partition holding every viewer's feed entry for one match.
+fn match_feed_gsi2pk(match_id: &str) -> String {
+    format!("MFEED#{match_id}")
+}
+
+/// What a batch of feed entries points at and when they sort — everything
+/// on a [`FeedItemRecord`] that's the same for every viewer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedRef {
+    pub ref_type: FeedRefType,
+    pub ref_id: String,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    /// The sort time, stored as `FeedItemRecord::starts_at`.
+    pub at: String,
+}
+
+impl FeedRef {
+    /// What an existing entry points at.
+    fn of(record: &FeedItemRecord) -> Self {
+        Self {
+            ref_type: record.ref_type,
+            ref_id: record.ref_id.clone(),
+            subject_id: record.subject_id.clone(),
+            at: record.starts_at.clone(),
+        }
+    }
+
+    /// A match, sorted by its start time.
+    pub fn match_at(match_id: &str, starts_at: &str) -> Self {
+        Self {
+            ref_type: FeedRefType::Match,
+            ref_id: match_id.into(),
+            subject_id: None,
+            at: starts_at.into(),
+        }
+    }
+}
 
 /// One viewer's feed entry to write for a match — the viewer id plus their
 /// [`AudienceMember`] data (known-players list / own side, see
@@ -33,9 +131,37 @@ pub struct FeedViewer {
     pub audience: AudienceMember,
 }
 
+/// An existing entry's per-viewer data, to write it back changed.
+fn audience_of(record: &FeedItemRecord) -> AudienceMember {
+    AudienceMember {
+        known_player_ids: record.known_player_ids.clone(),
+        known_player_count: record.known_player_count,
+        viewer_side_id: record.viewer_side_id.clone(),
+        reasons: record.reasons.clone(),
+    }
+}
+
+/// The key of `viewer_id`'s entry for `feed_ref`.
+fn feed_item_key(viewer_id: &str, feed_ref: &FeedRef) -> super::item::Item {
+    HashMap::from([
+        (
+            ATTR_PK.to_string(),
+            s(Pk::UserFeed(viewer_id.into()).to_string()),
+        ),
+        (
+            ATTR_SK.to_string(),
+            s(Sk::Feed {
+                at: feed_ref.at.clone(),
+                ref_id: feed_ref.ref_id.clone(),
+            }
+            .to_string()),
+        ),
+    ])
+}
+
 impl Dao {
-    /// Write one feed entry per viewer for a match, in batches. Idempotent on
-    /// `<starts_at>#<matchId>` (an overwrite is a no-op in effect, and fully
+    /// Write one feed entry per viewer for `feed_ref`, in batches. Idempotent
+    /// on `<at>#<refId>` (an overwrite is a no-op in effect, and fully
     /// replaces the item — including `known_player_ids`/`viewer_side_id` — so
     /// a re-fan-out refreshes it to the audience computation's current
     /// state). Retries any `UnprocessedItems` DynamoDB returns (throttling)
@@ -46,20 +172,13 @@ impl Dao {
     pub async fn write_feed_items(
         &self,
         viewers: &[FeedViewer],
-        match_id: &str,
-        starts_at: &str,
+        feed_ref: &FeedRef,
         now: &str,
     ) -> DaoResult<()> {
         for chunk in viewers.chunks(BATCH_WRITE_MAX) {
             let mut requests = Vec::with_capacity(chunk.len());
             for viewer in chunk {
-                let item = self.feed_item(
-                    &viewer.viewer_id,
-                    match_id,
-                    starts_at,
-                    now,
-                    &viewer.audience,
-                )?;
+                let item = self.feed_item(&viewer.viewer_id, feed_ref, now, &viewer.audience)?;
                 let put = PutRequest::builder()
                     .set_item(Some(item))
                     .build()
@@ -72,44 +191,305 @@ impl Dao {
         Ok(())
     }
 
-    /// Build one viewer's feed-entry item for a match. Idempotent on the sort key
-    /// `<starts_at>#<matchId>`, so writing it again (via fan-out) overwrites the
+    /// Build one viewer's feed-entry item. Idempotent on the sort key
+    /// `<at>#<refId>`, so writing it again (via fan-out) overwrites the
     /// identical row. Shared with the accept / create transactions, which write a
     /// participant's *own* row synchronously (with a default `AudienceMember` —
     /// empty `known_player_ids`, zero `known_player_count` — it's their own
     /// match, not someone they follow playing in it — plus their own `side_id`,
-    /// if assigned).
+    /// if assigned, and the `Participant` reason).
     pub(super) fn feed_item(
         &self,
         viewer_id: &str,
-        match_id: &str,
-        starts_at: &str,
+        feed_ref: &FeedRef,
         now: &str,
         audience: &AudienceMember,
     ) -> DaoResult<super::item::Item> {
         let record = FeedItemRecord {
             viewer_id: viewer_id.into(),
-            ref_type: "match".into(),
-            ref_id: match_id.into(),
-            starts_at: starts_at.into(),
+            ref_type: feed_ref.ref_type,
+            ref_id: feed_ref.ref_id.clone(),
+            subject_id: feed_ref.subject_id.clone(),
+            starts_at: feed_ref.at.clone(),
             created_at: now.into(),
             known_player_ids: audience.known_player_ids.clone(),
             known_player_count: audience.known_player_count,
             viewer_side_id: audience.viewer_side_id.clone(),
+            reasons: audience.reasons.clone(),
+            ttl: feed_ttl(&feed_ref.at, self.feed_retention_days),
         };
-        Ok(ItemBuilder::new(to_item(
+        let builder = ItemBuilder::new(to_item(
             &Pk::UserFeed(viewer_id.into()),
             &Sk::Feed {
-                starts_at: starts_at.into(),
-                match_id: match_id.into(),
+                at: feed_ref.at.clone(),
+                ref_id: feed_ref.ref_id.clone(),
             },
             TYPE_FEED_ITEM,
             &record,
-        )?)
+        )?);
+        Ok(match feed_ref.ref_type {
+            FeedRefType::Match | FeedRefType::Result => builder.gsi2(
+                match_feed_gsi2pk(&feed_ref.ref_id),
+                format!("{}#{viewer_id}", feed_ref.at),
+            ),
+            _ => builder,
+        }
         .build())
     }
 
+    /// Delete every feed entry for a match whose `starts_at` isn't the match's
+    /// current one — the rows a postponement left behind under the old sort
+    /// key — and every result entry not sorted at `confirmed_at`, the current
+    /// result's confirmation (all of them, when there's no result now). Run by
+    /// the fan-out workflow after it writes the current rows, so a viewer
+    /// never sees the match twice (old and new time) for longer than a
+    /// fan-out. With `audience`, also delete the rows of every viewer not in
+    /// it — the rows a visibility change left behind. Idempotent; a no-op when
+    /// nothing changed. Returns how many rows were deleted.
+    #[tracing::instrument(skip(self, audience))]
+    pub async fn prune_stale_feed_items(
+        &self,
+        match_id: &str,
+        starts_at: &str,
+        confirmed_at: Option<&str>,
+        audience: Option<&HashSet<String>>,
+    ) -> DaoResult<u32> {
+        let mut stale = Vec::new();
+        let mut start_key = None;
+        loop {
+            let out = self
+                .client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI2")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI2PK)
+                .expression_attribute_values(":pk", s(match_feed_gsi2pk(match_id)))
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+            for item in out.items.unwrap_or_default() {
+                let record: FeedItemRecord = from_item(item)?;
+                let current = match record.ref_type {
+                    FeedRefType::Result => confirmed_at,
+                    _ => Some(starts_at),
+                };
+                let moved = current != Some(record.starts_at.as_str());
+                let left_audience = audience.is_some_and(|a| !a.contains(&record.viewer_id));
+                if moved || left_audience {
+                    stale.push(record);
+                }
+            }
+
+            match out.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => break,
+            }
+        }
+
+        for chunk in stale.chunks(BATCH_WRITE_MAX) {
+            let mut requests = Vec::with_capacity(chunk.len());
+            for record in chunk {
+                let key = feed_item_key(&record.viewer_id, &FeedRef::of(record));
+                let delete = DeleteRequest::builder()
+                    .set_key(Some(key))
+                    .build()
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                requests.push(WriteRequest::builder().delete_request(delete).build());
+            }
+            self.flush_batch_write(requests).await?;
+        }
+        Ok(stale.len() as u32)
+    }
+
+    /// The recent and upcoming matches `followed` — a user, team or club —
+    /// plays in, for a new follower's feed: those starting from `since` on,
+    /// latest first, at most `limit`. Leaves out cancelled and
+    /// participants-only matches, which fan-out wouldn't show a follower
+    /// either. Empty for a reason that isn't a follow.
+    #[tracing::instrument(skip(self))]
+    pub async fn follow_backfill_refs(
+        &self,
+        followed: &AudienceReason,
+        since: &str,
+        limit: usize,
+    ) -> DaoResult<Vec<FeedRef>> {
+        let match_ids: Vec<String> = match followed {
+            AudienceReason::FollowsUser(user_id) => {
+                let mut ids = Vec::new();
+                let mut cursor: Option<String> = None;
+                while ids.len() < BACKFILL_SCAN as usize {
+                    let page = self
+                        .list_user_match_ids(user_id, cursor.as_deref(), BACKFILL_SCAN)
+                        .await?;
+                    ids.extend(page.items);
+                    match page.next_cursor {
+                        Some(c) => cursor = Some(c),
+                        None => break,
+                    }
+                }
+                ids
+            }
+            AudienceReason::FollowsTeam(team_id) => self
+                .list_team_matches(team_id)
+                .await?
+                .into_iter()
+                .map(|m| m.match_id)
+                .collect(),
+            AudienceReason::FollowsClub(club_id) => {
+                let mut ids = Vec::new();
+                for team in self.list_club_teams(club_id).await? {
+                    let matches = self.list_team_matches(&team.id).await?;
+                    ids.extend(matches.into_iter().map(|m| m.match_id));
+                }
+                ids
+            }
+            AudienceReason::Participant | AudienceReason::Member(_) => Vec::new(),
+        };
+
+        let mut matches: Vec<_> = self
+            .batch_get_match_metas(&match_ids)
+            .await?
+            .into_values()
+            .filter(|m| {
+                m.status != "cancelled"
+                    && m.visibility != Visibility::Participants
+                    && m.starts_at.as_str() >= since
+            })
+            .collect();
+        matches.sort_by(|a, b| b.starts_at.cmp(&a.starts_at));
+        matches.truncate(limit);
+        Ok(matches
+            .iter()
+            .map(|m| FeedRef::match_at(&m.id, &m.starts_at))
+            .collect())
+    }
+
+    /// Put `refs` in `viewer_id`'s feed for `reason` (a follow): write the
+    /// entries they don't have yet, and add `reason` to those they do —
+    /// keeping whatever else put them there. A followed user also joins each
+    /// entry's known players. Returns how many entries were written; those
+    /// already recording `reason` are left as they are, so a replay is a
+    /// no-op.
+    #[tracing::instrument(skip(self, refs))]
+    pub async fn add_feed_reason(
+        &self,
+        viewer_id: &str,
+        reason: &AudienceReason,
+        refs: &[FeedRef],
+        now: &str,
+    ) -> DaoResult<u32> {
+        let keys = refs.iter().map(|r| feed_item_key(viewer_id, r)).collect();
+        let mut existing = HashMap::new();
+        for item in self.batch_get_all(keys, None).await? {
+            let record: FeedItemRecord = from_item(item)?;
+            existing.insert((record.starts_at.clone(), record.ref_id.clone()), record);
+        }
+
+        let mut items = Vec::new();
+        for feed_ref in refs {
+            let current = existing.get(&(feed_ref.at.clone(), feed_ref.ref_id.clone()));
+            let mut audience = current.map(audience_of).unwrap_or_default();
+            if !audience.add_reason(reason.clone()) {
+                continue;
+            }
+            if let AudienceReason::FollowsUser(user_id) = reason {
+                audience.known_player_count += 1;
+                if audience.known_player_ids.len() < MAX_KNOWN_PLAYERS {
+                    audience.known_player_ids.push(user_id.clone());
+                }
+            }
+            let created_at = current.map_or(now, |r| r.created_at.as_str());
+            items.push(self.feed_item(viewer_id, feed_ref, created_at, &audience)?);
+        }
+
+        for chunk in items.chunks(BATCH_WRITE_MAX) {
+            let mut requests = Vec::with_capacity(chunk.len());
+            for item in chunk {
+                let put = PutRequest::builder()
+                    .set_item(Some(item.clone()))
+                    .build()
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                requests.push(WriteRequest::builder().put_request(put).build());
+            }
+            self.flush_batch_write(requests).await?;
+        }
+        Ok(items.len() as u32)
+    }
+
+    /// Take `reason` (a follow that's gone) out of `viewer_id`'s feed: delete
+    /// the entries it was the only reason for, and strip it from the rest —
+    /// along with the unfollowed user from their known players. Entries
+    /// written before reasons were recorded don't list any, so are kept.
+    /// Idempotent. Returns how many entries were deleted or rewritten.
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_feed_reason(
+        &self,
+        viewer_id: &str,
+        reason: &AudienceReason,
+    ) -> DaoResult<u32> {
+        let mut affected = Vec::new();
+        let mut start_key = None;
+        loop {
+            let out = self
+                .client
+                .query()
+                .table_name(self.table())
+                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .filter_expression("contains(reasons, :reason)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_values(":pk", s(Pk::UserFeed(viewer_id.into()).to_string()))
+                .expression_attribute_values(":sk", s(Sk::feed_prefix()))
+                .expression_attribute_values(":reason", s(reason.to_string()))
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+            for item in out.items.unwrap_or_default() {
+                affected.push(from_item::<FeedItemRecord>(item)?);
+            }
+
+            match out.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => break,
+            }
+        }
+
+        for chunk in affected.chunks(BATCH_WRITE_MAX) {
+            let mut requests = Vec::with_capacity(chunk.len());
+            for record in chunk {
+                let feed_ref = FeedRef::of(record);
+                let mut audience = audience_of(record);
+                audience.reasons.retain(|r| r != reason);
+                if audience.reasons.is_empty() {
+                    let delete = DeleteRequest::builder()
+                        .set_key(Some(feed_item_key(viewer_id, &feed_ref)))
+                        .build()
+                        .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                    requests.push(WriteRequest::builder().delete_request(delete).build());
+                    continue;
+                }
+                if let AudienceReason::FollowsUser(user_id) = reason {
+                    audience.known_player_ids.retain(|id| id != user_id);
+                    audience.known_player_count = audience.known_player_count.saturating_sub(1);
+                }
+                let item = self.feed_item(viewer_id, &feed_ref, &record.created_at, &audience)?;
+                let put = PutRequest::builder()
+                    .set_item(Some(item))
+                    .build()
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                requests.push(WriteRequest::builder().put_request(put).build());
+            }
+            self.flush_batch_write(requests).await?;
+        }
+        Ok(affected.len() as u32)
+    }
+
     /// List a viewer's feed newest-first (by match `starts_at`), paginated.
+    /// Expired entries are skipped, and read past to fill the page.
     #[tracing::instrument(skip(self))]
     pub async fn list_feed(
         &self,
@@ -117,19 +497,510 @@ impl Dao {
         cursor: Option<&str>,
         limit: u32,
     ) -> DaoResult<Page<FeedItemRecord>> {
-        use super::item::{ATTR_PK, s};
-        self.query_page(
+        self.query_page_filtered(
             self.client
                 .query()
                 .table_name(self.table())
                 .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .filter_expression(NOT_EXPIRED)
                 .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_names("#ttl", ATTR_TTL)
                 .expression_attribute_values(":pk", s(Pk::UserFeed(viewer_id.into()).to_string()))
                 .expression_attribute_values(":sk", s(Sk::feed_prefix()))
+                .expression_attribute_values(":now", AttributeValue::N(now_epoch().to_string()))
                 .scan_index_forward(false), // newest (latest starts_at) first
             cursor,
             limit,
         )
         .await
     }
+
+    /// Specific feed entries of a viewer's — a page of a
+    /// [`RankedFeedRecord`] — keyed as asked for. Entries since removed (a
+    /// postponed match's old row, or an expired one) are simply absent.
+    #[tracing::instrument(skip(self, entries))]
+    pub async fn batch_get_feed_items(
+        &self,
+        viewer_id: &str,
+        entries: &[RankedFeedEntryRecord],
+    ) -> DaoResult<HashMap<RankedFeedEntryRecord, FeedItemRecord>> {
+        let pk = s(Pk::UserFeed(viewer_id.into()).to_string());
+        let mut seen = HashSet::new();
+        let keys = entries
+            .iter()
+            .filter(|e| seen.insert(*e))
+            .map(|e| {
+                let sk = Sk::Feed {
+                    at: e.starts_at.clone(),
+                    ref_id: e.ref_id.clone(),
+                };
+                HashMap::from([
+                    (ATTR_PK.to_string(), pk.clone()),
+                    (ATTR_SK.to_string(), s(sk.to_string())),
+                ])
+            })
+            .collect();
+        let now = now_epoch();
+        let mut out = HashMap::new();
+        for item in self.batch_get_all(keys, None).await? {
+            let record: FeedItemRecord = from_item(item)?;
+            if !record.is_expired(now) {
+                let key = RankedFeedEntryRecord {
+                    starts_at: record.starts_at.clone(),
+                    ref_id: record.ref_id.clone(),
+                };
+                out.insert(key, record);
+            }
+        }
+        Ok(out)
+    }
+
+    /// One page of the TTL backfill: stamp a TTL on the feed entries written
+    /// before retention existed, as `write_feed_items` would now. Scans the
+    /// table for them, so `limit` caps the items *read* and a page can stamp
+    /// none yet have a cursor to follow. Re-running from the start is safe —
+    /// entries already stamped don't match, so it picks up where it stopped
+    /// — and so is racing a live write: the stamp only lands on an entry
+    /// that still exists without one. Returns the entries stamped.
+    #[tracing::instrument(skip(self))]
+    pub async fn stamp_feed_ttl_page(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<FeedItemRecord>> {
+        let page: Page<FeedItemRecord> = self
+            .scan_page(
+                self.client
+                    .scan()
+                    .table_name(self.table())
+                    .filter_expression(
+                        "begins_with(#pk, :pfx) AND #type = :type AND attribute_not_exists(#ttl)",
+                    )
+                    .expression_attribute_names("#pk", ATTR_PK)
+                    .expression_attribute_names("#type", ATTR_TYPE)
+                    .expression_attribute_names("#ttl", ATTR_TTL)
+                    .expression_attribute_values(":pfx", s(Pk::UserFeed(String::new()).to_string()))
+                    .expression_attribute_values(":type", s(TYPE_FEED_ITEM)),
+                cursor,
+                limit,
+            )
+            .await?;
+
+        let mut stamped = Vec::with_capacity(page.items.len());
+        for mut record in page.items {
+            let Some(ttl) = feed_ttl(&record.starts_at, self.feed_retention_days) else {
+                continue;
+            };
+            let key = feed_item_key(&record.viewer_id, &FeedRef::of(&record));
+            let result = self
+                .client
+                .update_item()
+                .table_name(self.table())
+                .set_key(Some(key))
+                .update_expression("SET #ttl = :ttl")
+                .condition_expression("attribute_exists(#pk) AND attribute_not_exists(#ttl)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_names("#ttl", ATTR_TTL)
+                .expression_attribute_values(":ttl", AttributeValue::N(ttl.to_string()))
+                .send()
+                .await;
+            match result {
+                Ok(_) => {
+                    record.ttl = Some(ttl);
+                    stamped.push(record);
+                }
+                // Deleted, or rewritten with a TTL, since the scan read it.
+                Err(e) if is_update_conditional_failure(&e) => {}
+                Err(e) => return Err(DaoError::Dynamo(e.to_string())),
+            }
+        }
+        Ok(Page {
+            items: stamped,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// One page of the `MFEED#` backfill: project the match and result
+    /// entries written before the GSI2 projection existed, as `feed_item`
+    /// would now, so `prune_stale_feed_items` can find them. Scans like
+    /// `stamp_feed_ttl_page`, so a page can project none yet have a cursor to
+    /// follow, and is as safe to re-run or race with live writes: the
+    /// projection only lands on an entry that still exists without one.
+    /// Returns the entries projected.
+    #[tracing::instrument(skip(self))]
+    pub async fn project_match_feed_page(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<FeedItemRecord>> {
+        let page: Page<FeedItemRecord> = self
+            .scan_page(
+                self.client
+                    .scan()
+                    .table_name(self.table())
+                    .filter_expression(
+                        "begins_with(#pk, :pfx) AND #type = :type \
+                         AND ref_type IN (:match, :result) AND attribute_not_exists(#gsi2pk)",
+                    )
+                    .expression_attribute_names("#pk", ATTR_PK)
+                    .expression_attribute_names("#type", ATTR_TYPE)
+                    .expression_attribute_names("#gsi2pk", ATTR_GSI2PK)
+                    .expression_attribute_values(":pfx", s(Pk::UserFeed(String::new()).to_string()))
+                    .expression_attribute_values(":type", s(TYPE_FEED_ITEM))
+                    .expression_attribute_values(":match", s("match"))
+                    .expression_attribute_values(":result", s("result")),
+                cursor,
+                limit,
+            )
+            .await?;
+
+        let mut projected = Vec::with_capacity(page.items.len());
+        for record in page.items {
+            let key = feed_item_key(&record.viewer_id, &FeedRef::of(&record));
+            let result = self
+                .client
+                .update_item()
+                .table_name(self.table())
+                .set_key(Some(key))
+                .update_expression("SET #gsi2pk = :gsi2pk, #gsi2sk = :gsi2sk")
+                .condition_expression("attribute_exists(#pk) AND attribute_not_exists(#gsi2pk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_names("#gsi2pk", ATTR_GSI2PK)
+                .expression_attribute_names("#gsi2sk", ATTR_GSI2SK)
+                .expression_attribute_values(":gsi2pk", s(match_feed_gsi2pk(&record.ref_id)))
+                .expression_attribute_values(
+                    ":gsi2sk",
+                    s(format!("{}#{}", record.starts_at, record.viewer_id)),
+                )
+                .send()
+                .await;
+            match result {
+                Ok(_) => projected.push(record),
+                // Deleted, or rewritten with the projection, since the scan
+                // read it.
+                Err(e) if is_update_conditional_failure(&e) => {}
+                Err(e) => return Err(DaoError::Dynamo(e.to_string())),
+            }
+        }
+        Ok(Page {
+            items: projected,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// A viewer's stored ranked-feed order, if they've ever asked for one.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_ranked_feed(&self, viewer_id: &str) -> DaoResult<Option<RankedFeedRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(viewer_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::RankedFeed.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        out.item.map(from_item).transpose()
+    }
+
+    /// Replace a viewer's ranked-feed order.
+    #[tracing::instrument(skip(self, ranking), fields(viewer_id = %ranking.viewer_id))]
+    pub async fn put_ranked_feed(&self, ranking: &RankedFeedRecord) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::User(ranking.viewer_id.clone()),
+            &Sk::RankedFeed,
+            TYPE_RANKED_FEED,
+            ranking,
+        )?;
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// A viewer's feed entries for matches starting in `[from, to]` (RFC3339),
+    /// earliest first, at most `limit` of them — unexpired match entries
+    /// only. The digest's "coming up" and "results" sections.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_feed_between(
+        &self,
+        viewer_id: &str,
+        from: &str,
+        to: &str,
+        limit: u32,
+    ) -> DaoResult<Vec<FeedItemRecord>> {
+        let prefix = Sk::feed_prefix();
+        let page = self
+            .query_page_filtered(
+                self.client
+                    .query()
+                    .table_name(self.table())
+                    .key_condition_expression("#pk = :pk AND SK BETWEEN :lo AND :hi")
+                    .filter_expression(format!("ref_type = :match AND {NOT_EXPIRED}"))
+                    .expression_attribute_names("#pk", ATTR_PK)
+                    .expression_attribute_names("#ttl", ATTR_TTL)
+                    .expression_attribute_values(":match", s("match"))
+                    .expression_attribute_values(":now", AttributeValue::N(now_epoch().to_string()))
+                    .expression_attribute_values(
+                        ":pk",
+                        s(Pk::UserFeed(viewer_id.into()).to_string()),
+                    )
+                    .expression_attribute_values(":lo", s(format!("{prefix}{from}")))
+                    // `~` sorts after the `#` that follows the timestamp, so a
+                    // match starting exactly at `to` is included.
+                    .expression_attribute_values(":hi", s(format!("{prefix}{to}~"))),
+                None,
+                limit,
+            )
+            .await?;
+        Ok(page.items)
+    }
+}
+
+fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), UpdateItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+
+    const NOW: &str = "2027-04-01T10:00:00Z";
+    const LATER: &str = "2027-04-08T10:00:00Z";
+    /// When the first result was confirmed — after `NOW`'s kick-off.
+    const CONFIRMED: &str = "2027-04-01T12:00:00Z";
+
+    fn viewers(ids: &[&str]) -> Vec<FeedViewer> {
+        ids.iter()
+            .map(|id| FeedViewer {
+                viewer_id: id.to_string(),
+                audience: AudienceMember::default(),
+            })
+            .collect()
+    }
+
+    fn result_at(match_id: &str, confirmed_at: &str) -> FeedRef {
+        FeedRef {
+            ref_type: FeedRefType::Result,
+            ..FeedRef::match_at(match_id, confirmed_at)
+        }
+    }
+
+    async fn feed(dao: &Dao, viewer_id: &str) -> Vec<(FeedRefType, String)> {
+        let page = dao.list_feed(viewer_id, None, 50).await.unwrap();
+        page.items
+            .into_iter()
+            .map(|r| (r.ref_type, r.starts_at))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn a_ranked_page_fetches_a_match_and_its_result_card_apart() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let a = viewers(&["a"]);
+        dao.write_feed_items(&a, &FeedRef::match_at("m1", NOW), NOW)
+            .await
+            .unwrap();
+        dao.write_feed_items(&a, &result_at("m1", CONFIRMED), NOW)
+            .await
+            .unwrap();
+
+        let key = |at: &str| RankedFeedEntryRecord {
+            starts_at: at.into(),
+            ref_id: "m1".into(),
+        };
+        let keys = [key(CONFIRMED), key(NOW), key(LATER)];
+        let found = dao.batch_get_feed_items("a", &keys).await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[&keys[0]].ref_type, FeedRefType::Result);
+        assert_eq!(found[&keys[1]].ref_type, FeedRefType::Match);
+    }
+
+    #[tokio::test]
+    async fn a_postponement_prunes_the_rows_left_under_the_old_time() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let both = viewers(&["a", "b"]);
+        dao.write_feed_items(&both, &FeedRef::match_at("m1", NOW), NOW)
+            .await
+            .unwrap();
+        dao.write_feed_items(&both[..1], &result_at("m1", CONFIRMED), NOW)
+            .await
+            .unwrap();
+        // Postponed, and the result with it: fan-out writes the new rows.
+        dao.write_feed_items(&both, &FeedRef::match_at("m1", LATER), NOW)
+            .await
+            .unwrap();
+
+        let pruned = dao
+            .prune_stale_feed_items("m1", LATER, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pruned, 3);
+        assert_eq!(feed(&dao, "a").await, [(FeedRefType::Match, LATER.into())]);
+        assert_eq!(feed(&dao, "b").await, [(FeedRefType::Match, LATER.into())]);
+
+        // Narrowed to participants-only: only `a` keeps the match.
+        let keep = HashSet::from(["a".to_string()]);
+        let pruned = dao
+            .prune_stale_feed_items("m1", LATER, None, Some(&keep))
+            .await
+            .unwrap();
+        assert_eq!(pruned, 1);
+        assert!(feed(&dao, "b").await.is_empty());
+        let pruned = dao
+            .prune_stale_feed_items("m1", LATER, None, Some(&keep))
+            .await
+            .unwrap();
+        assert_eq!(pruned, 0);
+    }
+
+    #[tokio::test]
+    async fn rows_from_before_the_projection_are_backfilled_then_pruned() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        for (viewer, feed_ref) in [
+            ("a", FeedRef::match_at("m1", NOW)),
+            ("a", result_at("m1", CONFIRMED)),
+            (
+                "a",
+                FeedRef {
+                    ref_type: FeedRefType::Milestone,
+                    ..FeedRef::match_at("ms1", NOW)
+                },
+            ),
+        ] {
+            let mut item = dao
+                .feed_item(viewer, &feed_ref, NOW, &AudienceMember::default())
+                .unwrap();
+            item.remove(ATTR_GSI2PK);
+            item.remove(ATTR_GSI2SK);
+            dao.client
+                .put_item()
+                .table_name(dao.table())
+                .set_item(Some(item))
+                .send()
+                .await
+                .unwrap();
+        }
+        dao.write_feed_items(&viewers(&["a"]), &FeedRef::match_at("m1", LATER), NOW)
+            .await
+            .unwrap();
+        let pruned = dao
+            .prune_stale_feed_items("m1", LATER, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pruned, 0);
+
+        let mut projected = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = dao
+                .project_match_feed_page(cursor.as_deref(), 2)
+                .await
+                .unwrap();
+            projected.extend(page.items.into_iter().map(|r| r.ref_type));
+            match page.next_cursor {
+                Some(c) => cursor = Some(c),
+                None => break,
+            }
+        }
+        projected.sort_by_key(|t| format!("{t:?}"));
+        assert_eq!(projected, [FeedRefType::Match, FeedRefType::Result]);
+        let again = dao.project_match_feed_page(None, 100).await.unwrap();
+        assert!(again.items.is_empty());
+
+        let pruned = dao
+            .prune_stale_feed_items("m1", LATER, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pruned, 2);
+        let mut left = feed(&dao, "a").await;
+        left.sort_by_key(|(t, _)| format!("{t:?}"));
+        assert_eq!(
+            left,
+            [
+                (FeedRefType::Match, LATER.into()),
+                (FeedRefType::Milestone, NOW.into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_read_past_to_fill_the_page() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let live = [
+            "2027-04-01T10:00:00Z",
+            "2027-02-01T10:00:00Z",
+            "2027-01-01T10:00:00Z",
+        ];
+        for (i, at) in live.iter().enumerate() {
+            dao.write_feed_items(
+                &viewers(&["a"]),
+                &FeedRef::match_at(&format!("m{i}"), at),
+                NOW,
+            )
+            .await
+            .unwrap();
+        }
+        // Between the first two, entries past their TTL that DynamoDB hasn't
+        // deleted yet.
+        for day in 1..=3 {
+            let at = format!("2027-03-0{day}T10:00:00Z");
+            let mut item = dao
+                .feed_item(
+                    "a",
+                    &FeedRef::match_at(&format!("x{day}"), &at),
+                    NOW,
+                    &AudienceMember::default(),
+                )
+                .unwrap();
+            item.insert(ATTR_TTL.into(), AttributeValue::N("1".into()));
+            dao.client
+                .put_item()
+                .table_name(dao.table())
+                .set_item(Some(item))
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let starts = |items: Vec<FeedItemRecord>| -> Vec<String> {
+            items.into_iter().map(|r| r.starts_at).collect()
+        };
+        let first = dao.list_feed("a", None, 2).await.unwrap();
+        assert_eq!(starts(first.items), live[..2]);
+        let rest = dao
+            .list_feed("a", first.next_cursor.as_deref(), 2)
+            .await
+            .unwrap();
+        assert_eq!(starts(rest.items), live[2..]);
+        assert!(rest.next_cursor.is_none());
+
+        let between = dao
+            .list_feed_between("a", "2027-01-01T00:00:00Z", "2027-05-01T00:00:00Z", 3)
+            .await
+            .unwrap();
+        assert_eq!(starts(between), [live[2], live[1], live[0]]);
+    }
+
+    #[test]
+    fn ttl_is_the_retention_window_past_the_sort_time() {
+        let starts = chrono::DateTime::parse_from_rfc3339("2026-07-01T10:00:00Z").unwrap();
+        let expected = (starts + chrono::Duration::days(180)).timestamp();
+        assert_eq!(feed_ttl("2026-07-01T10:00:00Z", 180), Some(expected));
+        assert_eq!(feed_ttl("2026-07-01T10:00:00.000Z", 180), Some(expected));
+        assert_eq!(feed_ttl("not a time", 180), None);
+    }
 }
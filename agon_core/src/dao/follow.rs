@@ -1,19 +1,36 @@
-//! Follow-graph operations (user→user and user→team), with atomic counter
-//! maintenance and cursor-paginated listing.
+//! Follow-graph operations (user→user, user→team and user→club), with atomic
+//! counter maintenance and cursor-paginated listing.
+//!
+//! Following a private profile goes through a follow request instead
+//! (`FOLLOWREQ#` under the followee): the edge and both counters are written
+//! only when the followee approves it, in the same transaction that deletes
+//! the request.
 
 use std::collections::{HashMap, HashSet};
 
-use aws_sdk_dynamodb::types::{Delete, Put, TransactWriteItem, Update};
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::types::{
+    AttributeValue, Delete, DeleteRequest, Put, TransactWriteItem, Update, WriteRequest,
+};
 
+use super::batch::BATCH_WRITE_MAX;
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ATTR_GSI1PK, ATTR_GSI3PK, ATTR_PK, ATTR_SK, ItemBuilder, item_pk, s, to_item};
+use super::item::{
+    ATTR_GSI1PK, ATTR_GSI3PK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, item_pk, s, to_item,
+};
 use super::keys::{Pk, Sk};
 use super::page::Page;
-use super::records::{TeamFollowRecord, UserFollowRecord};
+use super::records::{
+    AudienceReason, ClubFollowRecord, FollowRequestRecord, TeamFollowRecord, UserFollowRecord,
+};
 
 pub const TYPE_USER_FOLLOW: &str = "user_follow";
 pub const TYPE_TEAM_FOLLOW: &str = "team_follow";
+pub const TYPE_CLUB_FOLLOW: &str = "club_follow";
+pub const TYPE_FOLLOW_REQUEST: &str = "follow_request";
 
 impl Dao {
     /// Follow a user. Idempotent: re-following is a no-op that does not
@@ -34,52 +51,12 @@ impl Dao {
             followee_id: followee_id.into(),
             follower_id: follower_id.into(),
             created_at: now.into(),
+            requested_at: None,
         };
-        // Edge lives under the followee; projected to GSI1 for "following" list.
-        let edge_item = ItemBuilder::new(to_item(
-            &Pk::User(followee_id.into()),
-            &Sk::Follower(follower_id.into()),
-            TYPE_USER_FOLLOW,
-            &edge,
-        )?)
-        .gsi1(
-            format!("UFOLLOWING#{follower_id}"),
-            Pk::User(followee_id.into()).to_string(),
-        )
-        .build();
-
-        let put_edge = Put::builder()
-            .table_name(self.table())
-            .set_item(Some(edge_item))
-            .condition_expression("attribute_not_exists(#pk)")
-            .expression_attribute_names("#pk", ATTR_PK)
-            .build()
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-
         let result = self
             .client
             .transact_write_items()
-            .transact_items(TransactWriteItem::builder().put(put_edge).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(counter_delta(
-                        self.table(),
-                        &Pk::User(followee_id.into()),
-                        "follower_count",
-                        1,
-                    )?)
-                    .build(),
-            )
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(counter_delta(
-                        self.table(),
-                        &Pk::User(follower_id.into()),
-                        "following_count",
-                        1,
-                    )?)
-                    .build(),
-            )
+            .set_transact_items(Some(self.user_follow_writes(&edge)?))
             .send()
             .await;
 
@@ -218,6 +195,41 @@ impl Dao {
         Ok(out.item.is_some())
     }
 
+    /// Of `team_ids`, which does `follower_id` follow? The team counterpart of
+    /// [`batch_is_following_users`](Dao::batch_is_following_users): one
+    /// `BatchGetItem` over the `TEAM#<team>` / `FOLLOWER#<follower>` edges.
+    /// Callers must pass at most `BATCH_GET_MAX` ids.
+    #[tracing::instrument(skip(self))]
+    pub async fn batch_is_following_teams(
+        &self,
+        follower_id: &str,
+        team_ids: &[String],
+    ) -> DaoResult<HashSet<String>> {
+        let mut seen = HashSet::new();
+        let keys: Vec<_> = team_ids
+            .iter()
+            .filter(|id| seen.insert((*id).clone()))
+            .map(|team| {
+                HashMap::from([
+                    (ATTR_PK.to_string(), s(Pk::Team(team.clone()).to_string())),
+                    (
+                        ATTR_SK.to_string(),
+                        s(Sk::Follower(follower_id.to_string()).to_string()),
+                    ),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, Some(ATTR_PK)).await?;
+        let mut following = HashSet::with_capacity(items.len());
+        for item in items {
+            if let Pk::Team(team) = item_pk(&item)? {
+                following.insert(team);
+            }
+        }
+        Ok(following)
+    }
+
     /// List a user's followers (the edge records), cursor-paginated.
     #[tracing::instrument(skip(self))]
     pub async fn list_user_followers(
@@ -240,6 +252,249 @@ impl Dao {
         .await
     }
 
+    /// Ask to follow a private profile. Idempotent: asking again while a
+    /// request is pending keeps the original one (and its `created_at`).
+    /// Counters don't move until [`approve_follow_request`].
+    ///
+    /// [`approve_follow_request`]: Dao::approve_follow_request
+    #[tracing::instrument(skip(self))]
+    pub async fn request_follow_user(
+        &self,
+        follower_id: &str,
+        followee_id: &str,
+        now: &str,
+    ) -> DaoResult<()> {
+        if follower_id == followee_id {
+            return Err(DaoError::Conflict("cannot follow yourself".into()));
+        }
+
+        let request = FollowRequestRecord {
+            followee_id: followee_id.into(),
+            follower_id: follower_id.into(),
+            created_at: now.into(),
+        };
+        let result = self
+            .client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(to_item(
+                &Pk::User(followee_id.into()),
+                &Sk::FollowRequest(follower_id.into()),
+                TYPE_FOLLOW_REQUEST,
+                &request,
+            )?))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // Already pending; treat as success (idempotent).
+            Err(e) if is_put_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// True if `follower_id` has a pending request to follow `followee_id`
+    /// (drives `is_follow_requested_by_me`).
+    #[tracing::instrument(skip(self))]
+    pub async fn is_follow_requested(
+        &self,
+        follower_id: &str,
+        followee_id: &str,
+    ) -> DaoResult<bool> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(followee_id.into()).to_string()))
+            .key("SK", s(Sk::FollowRequest(follower_id.into()).to_string()))
+            .projection_expression(ATTR_PK) // existence check only
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(out.item.is_some())
+    }
+
+    /// List the pending requests to follow a user, cursor-paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_follow_requests(
+        &self,
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<FollowRequestRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_values(":pk", s(Pk::User(user_id.into()).to_string()))
+                .expression_attribute_values(":sk", s(Sk::follow_request_prefix())),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Approve a pending follow request. Atomically: deletes the request,
+    /// writes the follow edge (stamped with the request's time) and bumps
+    /// both counters — so an approved request is always exactly one follow.
+    /// `NotFound` if there's no pending request (already answered or
+    /// withdrawn). If the follower already follows (the profile was public
+    /// when they followed), the request is just dropped: there's nothing left
+    /// to approve, and counting the follow again would double it.
+    #[tracing::instrument(skip(self))]
+    pub async fn approve_follow_request(
+        &self,
+        followee_id: &str,
+        follower_id: &str,
+        now: &str,
+    ) -> DaoResult<()> {
+        let not_found = || {
+            DaoError::NotFound(format!(
+                "follow request from {follower_id} to {followee_id}"
+            ))
+        };
+        let Some(request) = self.get_follow_request(followee_id, follower_id).await? else {
+            return Err(not_found());
+        };
+
+        let delete_request = Delete::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(followee_id.into()).to_string()))
+            .key("SK", s(Sk::FollowRequest(follower_id.into()).to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let edge = UserFollowRecord {
+            followee_id: followee_id.into(),
+            follower_id: follower_id.into(),
+            created_at: now.into(),
+            requested_at: Some(request.created_at),
+        };
+        let mut items = vec![TransactWriteItem::builder().delete(delete_request).build()];
+        items.extend(self.user_follow_writes(&edge)?);
+
+        let result = self
+            .client
+            .transact_write_items()
+            .set_transact_items(Some(items))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // The request went (answered or withdrawn) since the read above.
+            Err(e) if super::is_transaction_item_conditional_failure(&e, 0) => Err(not_found()),
+            // Only the edge's guard failed: already following.
+            Err(e) if super::is_transaction_item_conditional_failure(&e, 1) => {
+                match self.delete_follow_request(followee_id, follower_id).await {
+                    Ok(()) | Err(DaoError::NotFound(_)) => Ok(()),
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(not_found()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Delete a pending follow request — the followee denying it, or the
+    /// follower withdrawing it. `NotFound` if there's none pending.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_follow_request(
+        &self,
+        followee_id: &str,
+        follower_id: &str,
+    ) -> DaoResult<()> {
+        let result = self
+            .client
+            .delete_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(followee_id.into()).to_string()))
+            .key("SK", s(Sk::FollowRequest(follower_id.into()).to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_delete_conditional_failure(&e) => Err(DaoError::NotFound(format!(
+                "follow request from {follower_id} to {followee_id}"
+            ))),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    async fn get_follow_request(
+        &self,
+        followee_id: &str,
+        follower_id: &str,
+    ) -> DaoResult<Option<FollowRequestRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(followee_id.into()).to_string()))
+            .key("SK", s(Sk::FollowRequest(follower_id.into()).to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => Ok(Some(from_item(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The writes that make `edge` a follow: the edge itself (guarded on not
+    /// existing yet) and both counter bumps. The edge lives under the
+    /// followee, projected to GSI1 for the follower's "following" list.
+    fn user_follow_writes(&self, edge: &UserFollowRecord) -> DaoResult<Vec<TransactWriteItem>> {
+        let edge_item = ItemBuilder::new(to_item(
+            &Pk::User(edge.followee_id.clone()),
+            &Sk::Follower(edge.follower_id.clone()),
+            TYPE_USER_FOLLOW,
+            edge,
+        )?)
+        .gsi1(
+            format!("UFOLLOWING#{}", edge.follower_id),
+            Pk::User(edge.followee_id.clone()).to_string(),
+        )
+        .build();
+
+        let put_edge = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(edge_item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        Ok(vec![
+            TransactWriteItem::builder().put(put_edge).build(),
+            TransactWriteItem::builder()
+                .update(counter_delta(
+                    self.table(),
+                    &Pk::User(edge.followee_id.clone()),
+                    "follower_count",
+                    1,
+                )?)
+                .build(),
+            TransactWriteItem::builder()
+                .update(counter_delta(
+                    self.table(),
+                    &Pk::User(edge.follower_id.clone()),
+                    "following_count",
+                    1,
+                )?)
+                .build(),
+        ])
+    }
+
     /// List the users a given user follows, via GSI1 (`UFOLLOWING#<id>`).
     #[tracing::instrument(skip(self))]
     pub async fn list_user_following(
@@ -373,6 +628,53 @@ impl Dao {
         .await
     }
 
+    /// Detach every follower from a team (when it's archived). Edges are
+    /// batch-deleted a page at a time, then `follower_count` is set to zero
+    /// once: the team can't be followed while archived, so that's the count
+    /// the edges end at, and a re-run after an interruption lands there too.
+    /// Returns how many edges were removed.
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_all_team_followers(&self, team_id: &str) -> DaoResult<u32> {
+        let mut removed = 0;
+        loop {
+            // Always the first page: the previous one has just been deleted.
+            let page = self.list_team_followers(team_id, None, 100).await?;
+            if page.items.is_empty() {
+                break;
+            }
+            for chunk in page.items.chunks(BATCH_WRITE_MAX) {
+                let mut requests = Vec::with_capacity(chunk.len());
+                for edge in chunk {
+                    let delete = DeleteRequest::builder()
+                        .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+                        .key(
+                            ATTR_SK,
+                            s(Sk::Follower(edge.follower_id.clone()).to_string()),
+                        )
+                        .build()
+                        .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                    requests.push(WriteRequest::builder().delete_request(delete).build());
+                }
+                self.flush_batch_write(requests).await?;
+                removed += chunk.len() as u32;
+            }
+        }
+
+        self.client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .update_expression("SET follower_count = :zero")
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":zero", AttributeValue::N("0".into()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(removed)
+    }
+
     /// List the teams a user follows, via GSI3.
     #[tracing::instrument(skip(self))]
     pub async fn list_followed_teams(
@@ -394,13 +696,174 @@ impl Dao {
         )
         .await
     }
+
+    /// Follow a club. Idempotent. Bumps the club's `follower_count`. Matches
+    /// of every team the club owns fan out to its followers.
+    #[tracing::instrument(skip(self))]
+    pub async fn follow_club(&self, follower_id: &str, club_id: &str, now: &str) -> DaoResult<()> {
+        let edge = ClubFollowRecord {
+            club_id: club_id.into(),
+            follower_id: follower_id.into(),
+            created_at: now.into(),
+        };
+        // Edge under the club; projected to GSI3 for "clubs I follow".
+        let edge_item = ItemBuilder::new(to_item(
+            &Pk::Club(club_id.into()),
+            &Sk::Follower(follower_id.into()),
+            TYPE_CLUB_FOLLOW,
+            &edge,
+        )?)
+        .gsi3(
+            format!("UFOLLOWS_CLUB#{follower_id}"),
+            Pk::Club(club_id.into()).to_string(),
+        )
+        .build();
+
+        let put_edge = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(edge_item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put_edge).build())
+            .transact_items(
+                TransactWriteItem::builder()
+                    .update(counter_delta(
+                        self.table(),
+                        &Pk::Club(club_id.into()),
+                        "follower_count",
+                        1,
+                    )?)
+                    .build(),
+            )
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Unfollow a club. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn unfollow_club(&self, follower_id: &str, club_id: &str) -> DaoResult<()> {
+        let delete_edge = Delete::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Club(club_id.into()).to_string()))
+            .key("SK", s(Sk::Follower(follower_id.into()).to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().delete(delete_edge).build())
+            .transact_items(
+                TransactWriteItem::builder()
+                    .update(counter_delta(
+                        self.table(),
+                        &Pk::Club(club_id.into()),
+                        "follower_count",
+                        -1,
+                    )?)
+                    .build(),
+            )
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Whether `follower_id` follows the club `club_id`. Existence check on the
+    /// `CLUB#<club>` / `FOLLOWER#<follower>` edge.
+    #[tracing::instrument(skip(self))]
+    pub async fn is_following_club(&self, follower_id: &str, club_id: &str) -> DaoResult<bool> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Club(club_id.into()).to_string()))
+            .key("SK", s(Sk::Follower(follower_id.into()).to_string()))
+            .projection_expression(ATTR_PK) // existence check only
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(out.item.is_some())
+    }
+
+    /// Whether `follower_id` has the follow edge `followed` names — of a
+    /// user, team or club. Always false for a reason that isn't a follow.
+    pub async fn is_following(
+        &self,
+        follower_id: &str,
+        followed: &AudienceReason,
+    ) -> DaoResult<bool> {
+        match followed {
+            AudienceReason::FollowsUser(id) => self.is_following_user(follower_id, id).await,
+            AudienceReason::FollowsTeam(id) => self.is_following_team(follower_id, id).await,
+            AudienceReason::FollowsClub(id) => self.is_following_club(follower_id, id).await,
+            AudienceReason::Participant | AudienceReason::Member(_) => Ok(false),
+        }
+    }
+
+    /// List a club's followers, cursor-paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_club_followers(
+        &self,
+        club_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<ClubFollowRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_values(":pk", s(Pk::Club(club_id.into()).to_string()))
+                .expression_attribute_values(":sk", s(Sk::follower_prefix())),
+            cursor,
+            limit,
+        )
+        .await
+    }
+}
+
+fn is_put_conditional_failure(err: &SdkError<PutItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), PutItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+fn is_delete_conditional_failure(err: &SdkError<DeleteItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), DeleteItemError::ConditionalCheckFailedException(_))
+    )
 }
 
 /// Build an `Update` that atomically adds `delta` to a counter on the `#PROFILE`
 /// / `#META` singleton of the given partition. Uses `ADD`, which treats a
 /// missing attribute as 0.
 fn counter_delta(table: &str, pk: &Pk, counter: &str, delta: i64) -> DaoResult<Update> {
-    // The counter lives on the profile item for users, meta item for teams.
+    // The counter lives on the profile item for users, meta item for teams
+    // and clubs.
     let sk = match pk {
         Pk::User(_) => Sk::Profile,
         _ => Sk::Meta,
@@ -411,10 +874,108 @@ fn counter_delta(table: &str, pk: &Pk, counter: &str, delta: i64) -> DaoResult<U
         .key("SK", s(sk.to_string()))
         .update_expression("ADD #c :d")
         .expression_attribute_names("#c", counter)
-        .expression_attribute_values(
-            ":d",
-            aws_sdk_dynamodb::types::AttributeValue::N(delta.to_string()),
-        )
+        .expression_attribute_values(":d", AttributeValue::N(delta.to_string()))
         .build()
         .map_err(|e| DaoError::Dynamo(e.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::UserRecord;
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+    const LATER: &str = "2026-04-02T10:00:00Z";
+
+    async fn users(dao: &Dao, ids: &[&str]) {
+        for id in ids {
+            let user: UserRecord = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "email": format!("{id}@example.com"),
+                "name": id,
+                "created_at": NOW,
+            }))
+            .unwrap();
+            dao.create_user(&format!("sub-{id}"), &user).await.unwrap();
+        }
+    }
+
+    /// `(follower_count, following_count)` of a user.
+    async fn counts(dao: &Dao, id: &str) -> (u64, u64) {
+        let user = dao.get_user(id).await.unwrap().unwrap();
+        (user.follower_count, user.following_count)
+    }
+
+    async fn requests(dao: &Dao, id: &str) -> Vec<String> {
+        let page = dao.list_follow_requests(id, None, 50).await.unwrap();
+        page.items.into_iter().map(|r| r.follower_id).collect()
+    }
+
+    #[tokio::test]
+    async fn an_approved_request_becomes_one_follow() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        users(&dao, &["a", "b"]).await;
+        dao.request_follow_user("a", "b", NOW).await.unwrap();
+        dao.request_follow_user("a", "b", LATER).await.unwrap();
+        assert!(dao.is_follow_requested("a", "b").await.unwrap());
+        assert_eq!(requests(&dao, "b").await, ["a"]);
+        assert_eq!(counts(&dao, "b").await, (0, 0));
+
+        dao.approve_follow_request("b", "a", LATER).await.unwrap();
+        assert!(dao.is_following_user("a", "b").await.unwrap());
+        assert!(!dao.is_follow_requested("a", "b").await.unwrap());
+        assert!(requests(&dao, "b").await.is_empty());
+        assert_eq!(counts(&dao, "b").await, (1, 0));
+        assert_eq!(counts(&dao, "a").await, (0, 1));
+        let edge = dao.list_user_followers("b", None, 50).await.unwrap();
+        assert_eq!(edge.items[0].requested_at.as_deref(), Some(NOW));
+
+        // Approving again finds nothing to approve, and counts nothing.
+        assert!(matches!(
+            dao.approve_follow_request("b", "a", LATER).await,
+            Err(DaoError::NotFound(_))
+        ));
+        assert_eq!(counts(&dao, "b").await, (1, 0));
+    }
+
+    #[tokio::test]
+    async fn denied_and_withdrawn_requests_leave_no_follow() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        users(&dao, &["a", "b", "c"]).await;
+        dao.request_follow_user("a", "b", NOW).await.unwrap();
+        dao.request_follow_user("c", "b", NOW).await.unwrap();
+
+        // `b` denies `a`; `c` withdraws.
+        dao.delete_follow_request("b", "a").await.unwrap();
+        dao.delete_follow_request("b", "c").await.unwrap();
+        assert!(requests(&dao, "b").await.is_empty());
+        assert!(!dao.is_following_user("a", "b").await.unwrap());
+        assert_eq!(counts(&dao, "b").await, (0, 0));
+        assert!(matches!(
+            dao.delete_follow_request("b", "a").await,
+            Err(DaoError::NotFound(_))
+        ));
+        assert!(matches!(
+            dao.approve_follow_request("b", "c", LATER).await,
+            Err(DaoError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_request_from_an_existing_follower_is_dropped_on_approval() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        users(&dao, &["a", "b"]).await;
+        dao.follow_user("a", "b", NOW).await.unwrap();
+        dao.request_follow_user("a", "b", NOW).await.unwrap();
+
+        dao.approve_follow_request("b", "a", LATER).await.unwrap();
+        assert!(requests(&dao, "b").await.is_empty());
+        assert!(dao.is_following_user("a", "b").await.unwrap());
+        assert_eq!(counts(&dao, "b").await, (1, 0));
+        assert_eq!(counts(&dao, "a").await, (0, 1));
+    }
+}
@@ -11,16 +11,17 @@ use super::error::{DaoError, DaoResult};
 use super::item::{ATTR_GSI1PK, ATTR_GSI2PK, ATTR_PK, ItemBuilder, from_item, s, to_item};
 use super::keys::{Pk, Sk};
 use super::page::Page;
-use super::records::InvitationRecord;
+use super::records::{InvitationContextRecord, InvitationRecord};
 
 pub const TYPE_INVITATION: &str = "invitation";
 
 impl Dao {
     /// Build the item map for an invitation, applying the GSI projections:
     /// GSI1 (`UINV#<inviteeUserId>`) if it targets a known user (the inbox,
-    /// sorted `<status>#<invited_at>` so it can be filtered by status), and GSI2
-    /// (`TOKEN#<token>`) if it has a token. Shared by `create_invitation` and the
-    /// accept transaction so the two never diverge on projection rules.
+    /// sorted `<status>#<invited_at>` so it can be filtered by status), or
+    /// `TLINKS#<teamId>` if it's a team invite link; and GSI2 (`TOKEN#<token>`)
+    /// if it has a token. Shared by `create_invitation` and the accept
+    /// transaction so the two never diverge on projection rules.
     pub(super) fn invitation_item(&self, inv: &InvitationRecord) -> DaoResult<super::item::Item> {
         let base = to_item(
             &Pk::Invitation(inv.id.clone()),
@@ -30,7 +31,12 @@ impl Dao {
         )?;
 
         let mut builder = ItemBuilder::new(base);
-        if let Some(uid) = &inv.invited_user_id {
+        if let (Some(_), InvitationContextRecord::Team { team_id, .. }) = (&inv.link, &inv.context)
+        {
+            // A link never has an invitee of its own (redeemers join the team,
+            // the link stays pending), so it can't collide with the inbox.
+            builder = builder.gsi1(format!("TLINKS#{team_id}"), inv.invited_at.clone());
+        } else if let Some(uid) = &inv.invited_user_id {
             builder = builder.gsi1(
                 format!("UINV#{uid}"),
                 format!("{}#{}", inv.status, inv.invited_at),
@@ -26,6 +26,9 @@ pub const ATTR_GSI2PK: &str = "GSI2PK";
 pub const ATTR_GSI2SK: &str = "GSI2SK";
 pub const ATTR_GSI3PK: &str = "GSI3PK";
 pub const ATTR_GSI3SK: &str = "GSI3SK";
+/// The table's TTL attribute (epoch seconds): DynamoDB deletes an item some
+/// time after it passes. Items opt in by setting it.
+pub const ATTR_TTL: &str = "ttl";
 
 /// A single DynamoDB item as an attribute map.
 pub type Item = HashMap<String, AttributeValue>;
@@ -54,8 +54,10 @@ pub enum Pk {
     /// provider's subject so the provider can change without rewriting every
     /// `USER#`/`UFEED#`/`FOLLOWER#` key (only these guards get rewritten).
     AuthGuard(String),
-    /// A team and its members/followers. `TEAM#<tid>`
+    /// A team and its members/followers/announcements. `TEAM#<tid>`
     Team(String),
+    /// A club grouping several teams, with its admins/followers. `CLUB#<cid>`
+    Club(String),
     /// A match and its sides/players/score/likes/top-level comments. `MATCH#<mid>`
     Match(String),
     /// A user's fan-out feed. `UFEED#<viewerUid>`
@@ -64,6 +66,17 @@ pub enum Pk {
     Invitation(String),
     /// An uploadable asset. `ASSET#<assetId>`
     Asset(String),
+    /// A moderation case: every report against one piece of content.
+    /// `REPORT#<caseId>`, the case id derived from the reported target.
+    Report(String),
+    /// A hashtag and the comments and matches carrying it. `TAG#<tag>`, the
+    /// tag lowercased and without its `#`.
+    Tag(String),
+    /// Bookkeeping for one Meilisearch index while it's being rebuilt.
+    /// `SEARCHINDEX#<index>`
+    SearchIndex(String),
+    /// A user's stats milestone. `MILESTONE#<milestoneId>`
+    Milestone(String),
 }
 
 impl Pk {
@@ -79,10 +92,15 @@ impl Pk {
             Pk::EmailGuard(_) => "EMAIL",
             Pk::AuthGuard(_) => "AUTH",
             Pk::Team(_) => "TEAM",
+            Pk::Club(_) => "CLUB",
             Pk::Match(_) => "MATCH",
             Pk::UserFeed(_) => "UFEED",
             Pk::Invitation(_) => "INVITATION",
             Pk::Asset(_) => "ASSET",
+            Pk::Report(_) => "REPORT",
+            Pk::Tag(_) => "TAG",
+            Pk::SearchIndex(_) => "SEARCHINDEX",
+            Pk::Milestone(_) => "MILESTONE",
         }
     }
 
@@ -100,10 +118,15 @@ impl fmt::Display for Pk {
             | Pk::EmailGuard(v)
             | Pk::AuthGuard(v)
             | Pk::Team(v)
+            | Pk::Club(v)
             | Pk::Match(v)
             | Pk::UserFeed(v)
             | Pk::Invitation(v)
-            | Pk::Asset(v) => v,
+            | Pk::Asset(v)
+            | Pk::Report(v)
+            | Pk::Tag(v)
+            | Pk::SearchIndex(v)
+            | Pk::Milestone(v) => v,
         };
         write!(f, "{}{}{}", self.prefix(), DELIMITER, value)
     }
@@ -124,10 +147,15 @@ impl FromStr for Pk {
             "EMAIL" => Ok(Pk::EmailGuard(value.into())),
             "AUTH" => Ok(Pk::AuthGuard(value.into())),
             "TEAM" => Ok(Pk::Team(value.into())),
+            "CLUB" => Ok(Pk::Club(value.into())),
             "MATCH" => Ok(Pk::Match(value.into())),
             "UFEED" => Ok(Pk::UserFeed(value.into())),
             "INVITATION" => Ok(Pk::Invitation(value.into())),
             "ASSET" => Ok(Pk::Asset(value.into())),
+            "REPORT" => Ok(Pk::Report(value.into())),
+            "TAG" => Ok(Pk::Tag(value.into())),
+            "SEARCHINDEX" => Ok(Pk::SearchIndex(value.into())),
+            "MILESTONE" => Ok(Pk::Milestone(value.into())),
             other => Err(KeyError::UnknownPrefix(other.into())),
         }
     }
@@ -148,11 +176,21 @@ pub enum Sk {
     Meta,
     /// Uniqueness guard marker (e.g. under an email guard PK). `#GUARD`
     Guard,
+    /// A user's notification preferences, in the user partition. `#NOTIFPREFS`
+    NotificationPreferences,
+    /// A user's cached follow suggestions, in the user partition. `#SUGGESTIONS`
+    Suggestions,
+    /// A viewer's ranked-feed order, in the user partition. `#RANKEDFEED`
+    RankedFeed,
 
     /// A follower edge (who follows this user/team). `FOLLOWER#<followerUid>`
     Follower(String),
     /// A team membership. `MEMBER#<membershipId>`
     Member(String),
+    /// A user's current membership of a team, in the team's partition.
+    /// `MEMBERGUARD#<userId>` — one per (team, user) while the membership
+    /// lasts, so two racing joins can't both make the user a member.
+    MemberGuard(String),
     /// A match side. `SIDE#<sideId>`
     Side(String),
     /// A match player. `PLAYER#<playerId>`
@@ -164,7 +202,8 @@ pub enum Sk {
     /// `#META` item, which *is* the agreed-or-awaiting-agreement result (see
     /// `ScoreSubmission`'s confirm/dispute flow).
     Score(String),
-    /// A like on a match. `LIKE#<uid>`
+    /// A user's reaction on a match. `LIKE#<uid>` — one per (match, user);
+    /// the name predates reactions, and a like is the 👍 reaction.
     Like(String),
 
     /// A live-scoring event, in append order — the source of truth for live
@@ -199,9 +238,55 @@ pub enum Sk {
     /// re-scores, roster changes and cancellations all self-correct, and
     /// redelivery is a no-op (same state → zero delta).
     StatContribution(String),
-    /// A fan-out feed entry, ordered by match start time. `FEED#<starts_at>#<mid>`
-    /// (only ever listed, never addressed by id — keeps ts in the key).
-    Feed { starts_at: String, match_id: String },
+    /// A request to join a team, in the team's partition. `JOINREQ#<userId>` —
+    /// one per (team, user), so a second request while one is pending is a
+    /// conditional-put conflict rather than a duplicate row.
+    JoinRequest(String),
+    /// A season of a team, in the team's partition. `SEASON#<seasonId>`.
+    Season(String),
+    /// A match one of whose sides is this team, in the team's partition.
+    /// `TMATCH#<matchId>` — the reverse index per-season team stats are
+    /// computed from. Written alongside the match; sides never change team.
+    TeamMatch(String),
+    /// A club admin, in the club's partition. `CLUBADMIN#<userId>` — club
+    /// admins inherit admin on every team the club owns.
+    ClubAdmin(String),
+    /// A pending request to follow a private profile, in the followee's
+    /// partition. `FOLLOWREQ#<followerUid>` — one per (followee, follower);
+    /// approving it swaps it for a `Follower` edge, denying deletes it.
+    FollowRequest(String),
+    /// A block, in the blocker's partition. `BLOCK#<blockedUid>` — blocks act
+    /// both ways, so checks look for the edge under either user.
+    Block(String),
+    /// A mute, in the muting user's partition. `MUTE#<mutedUid>` — one-way and
+    /// never visible to the muted user.
+    Mute(String),
+    /// One user's report, in a moderation case's partition.
+    /// `REPORTER#<reporterUid>` — one per (case, reporter), so reports are
+    /// counted per distinct user.
+    Reporter(String),
+    /// A match carrying a hashtag, in the tag's partition. `TAGMATCH#<matchId>`
+    /// — one per (tag, match), counting the match's comments that use the tag;
+    /// recency ordering is via GSI1 (`TAGMATCHES#<tag>` / `<ts>#<matchId>`).
+    /// (A tagged comment reuses `Comment` in the same partition.)
+    TaggedMatch(String),
+    /// A user's reaction on a comment or reply, in the match partition.
+    /// `CREACT#<commentId>#<uid>` — one per (comment, user), listed per
+    /// comment by `comment_reaction_prefix`.
+    CommentReaction { comment_id: String, user_id: String },
+    /// A push held back by the recipient's quiet hours, in the user partition.
+    /// `DEFERPUSH#<release_at>#<nid>` — ordered by when it may go out, so the
+    /// due ones are one range query (`deferred_push_prefix`).
+    DeferredPush {
+        release_at: String,
+        notification_id: String,
+    },
+    /// A team admin's announcement, in the team partition. `ANNOUNCE#<id>`
+    Announcement(String),
+    /// A fan-out feed entry, ordered by when it sorts (a match's start time;
+    /// see `FeedItemRecord::starts_at`). `FEED#<at>#<refId>` (addressed by id
+    /// only by a ranked-feed page — keeps ts in the key).
+    Feed { at: String, ref_id: String },
 }
 
 impl Sk {
@@ -217,8 +302,12 @@ impl Sk {
             Sk::Profile => "#PROFILE",
             Sk::Meta => "#META",
             Sk::Guard => "#GUARD",
+            Sk::NotificationPreferences => "#NOTIFPREFS",
+            Sk::Suggestions => "#SUGGESTIONS",
+            Sk::RankedFeed => "#RANKEDFEED",
             Sk::Follower(_) => "FOLLOWER",
             Sk::Member(_) => "MEMBER",
+            Sk::MemberGuard(_) => "MEMBERGUARD",
             Sk::Side(_) => "SIDE",
             Sk::Player(_) => "PLAYER",
             Sk::Score(_) => "LIVESCORE",
@@ -230,6 +319,18 @@ impl Sk {
             Sk::Notification(_) => "NOTIF",
             Sk::Device(_) => "DEVICE",
             Sk::StatContribution(_) => "STATCONTRIB",
+            Sk::JoinRequest(_) => "JOINREQ",
+            Sk::Season(_) => "SEASON",
+            Sk::TeamMatch(_) => "TMATCH",
+            Sk::ClubAdmin(_) => "CLUBADMIN",
+            Sk::FollowRequest(_) => "FOLLOWREQ",
+            Sk::Block(_) => "BLOCK",
+            Sk::Mute(_) => "MUTE",
+            Sk::Reporter(_) => "REPORTER",
+            Sk::TaggedMatch(_) => "TAGMATCH",
+            Sk::CommentReaction { .. } => "CREACT",
+            Sk::DeferredPush { .. } => "DEFERPUSH",
+            Sk::Announcement(_) => "ANNOUNCE",
             Sk::Feed { .. } => "FEED",
         }
     }
@@ -250,6 +351,11 @@ impl Sk {
         format!("{}{DELIMITER}", Sk::Follower(String::new()).prefix())
     }
 
+    /// Lists a team's memberships, current and former: `MEMBER#`.
+    pub fn member_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::Member(String::new()).prefix())
+    }
+
     /// Lists a user's registered devices: `DEVICE#`.
     pub fn device_prefix() -> String {
         format!("{}{DELIMITER}", Sk::Device(String::new()).prefix())
@@ -265,7 +371,7 @@ impl Sk {
         format!("{}{DELIMITER}", Sk::Player(String::new()).prefix())
     }
 
-    /// Lists a match's likes: `LIKE#`.
+    /// Lists a match's reactions: `LIKE#`.
     pub fn like_prefix() -> String {
         format!("{}{DELIMITER}", Sk::Like(String::new()).prefix())
     }
@@ -283,13 +389,79 @@ impl Sk {
         )
     }
 
+    /// Lists a team's join requests: `JOINREQ#`.
+    pub fn join_request_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::JoinRequest(String::new()).prefix())
+    }
+
+    /// Lists a team's seasons: `SEASON#`.
+    pub fn season_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::Season(String::new()).prefix())
+    }
+
+    /// Lists the matches a team has played in: `TMATCH#`.
+    pub fn team_match_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::TeamMatch(String::new()).prefix())
+    }
+
+    /// Lists a club's admins: `CLUBADMIN#`.
+    pub fn club_admin_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::ClubAdmin(String::new()).prefix())
+    }
+
+    /// Lists a user's pending follow requests: `FOLLOWREQ#`.
+    pub fn follow_request_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::FollowRequest(String::new()).prefix())
+    }
+
+    /// Lists the users a user has blocked: `BLOCK#`.
+    pub fn block_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::Block(String::new()).prefix())
+    }
+
+    /// Lists the users a user has muted: `MUTE#`.
+    pub fn mute_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::Mute(String::new()).prefix())
+    }
+
+    /// Lists the reports in a moderation case: `REPORTER#`.
+    pub fn reporter_prefix() -> String {
+        format!("{}{DELIMITER}", Sk::Reporter(String::new()).prefix())
+    }
+
+    /// Lists the reactions on one comment or reply: `CREACT#<commentId>#`.
+    /// Ends in the delimiter, so one comment's id can't pick up another's
+    /// that merely starts with it.
+    pub fn comment_reaction_prefix(comment_id: &str) -> String {
+        format!(
+            "{}{DELIMITER}{comment_id}{DELIMITER}",
+            Sk::CommentReaction {
+                comment_id: String::new(),
+                user_id: String::new(),
+            }
+            .prefix()
+        )
+    }
+
+    /// Lists a user's deferred pushes, oldest release first: `DEFERPUSH#`.
+    pub fn deferred_push_prefix() -> String {
+        format!(
+            "{}{DELIMITER}",
+            Sk::DeferredPush {
+                release_at: String::new(),
+                notification_id: String::new(),
+            }
+            .prefix()
+        )
+    }
+
     /// Lists a viewer's feed: `FEED#`.
     pub fn feed_prefix() -> String {
         format!(
             "{}{DELIMITER}",
             Sk::Feed {
-                starts_at: String::new(),
-                match_id: String::new(),
+                at: String::new(),
+                ref_id: String::new(),
             }
             .prefix()
         )
@@ -300,11 +472,17 @@ impl fmt::Display for Sk {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             // Marker keys (the prefix is the whole key).
-            Sk::Profile | Sk::Meta | Sk::Guard => write!(f, "{}", self.prefix()),
+            Sk::Profile
+            | Sk::Meta
+            | Sk::Guard
+            | Sk::NotificationPreferences
+            | Sk::Suggestions
+            | Sk::RankedFeed => write!(f, "{}", self.prefix()),
 
             // Single-value keys.
             Sk::Follower(v)
             | Sk::Member(v)
+            | Sk::MemberGuard(v)
             | Sk::Side(v)
             | Sk::Player(v)
             | Sk::Score(v)
@@ -314,18 +492,36 @@ impl fmt::Display for Sk {
             | Sk::Reply(v)
             | Sk::Notification(v)
             | Sk::Device(v)
-            | Sk::StatContribution(v) => write!(f, "{}{}{}", self.prefix(), DELIMITER, v),
+            | Sk::StatContribution(v)
+            | Sk::JoinRequest(v)
+            | Sk::Season(v)
+            | Sk::TeamMatch(v)
+            | Sk::ClubAdmin(v)
+            | Sk::FollowRequest(v)
+            | Sk::Block(v)
+            | Sk::Mute(v)
+            | Sk::Reporter(v)
+            | Sk::TaggedMatch(v)
+            | Sk::Announcement(v) => write!(f, "{}{}{}", self.prefix(), DELIMITER, v),
 
             // Zero-padded so lexicographic order matches numeric seq order.
             Sk::LiveEvent(seq) => write!(f, "LIVEEVT{DELIMITER}{seq:010}"),
 
-            // Feed entries keep the timestamp in the key (list-only).
-            Sk::Feed {
-                starts_at,
-                match_id,
-            } => {
-                write!(f, "FEED{DELIMITER}{starts_at}{DELIMITER}{match_id}")
-            }
+            Sk::CommentReaction {
+                comment_id,
+                user_id,
+            } => write!(f, "CREACT{DELIMITER}{comment_id}{DELIMITER}{user_id}"),
+
+            Sk::DeferredPush {
+                release_at,
+                notification_id,
+            } => write!(
+                f,
+                "DEFERPUSH{DELIMITER}{release_at}{DELIMITER}{notification_id}"
+            ),
+
+            // Feed entries keep the timestamp in the key, so they list in order.
+            Sk::Feed { at, ref_id } => write!(f, "FEED{DELIMITER}{at}{DELIMITER}{ref_id}"),
         }
     }
 }
@@ -342,6 +538,9 @@ impl FromStr for Sk {
             "#PROFILE" => return Ok(Sk::Profile),
             "#META" => return Ok(Sk::Meta),
             "#GUARD" => return Ok(Sk::Guard),
+            "#NOTIFPREFS" => return Ok(Sk::NotificationPreferences),
+            "#SUGGESTIONS" => return Ok(Sk::Suggestions),
+            "#RANKEDFEED" => return Ok(Sk::RankedFeed),
             _ => {}
         }
 
@@ -359,6 +558,7 @@ impl FromStr for Sk {
         match prefix {
             "FOLLOWER" => Ok(Sk::Follower(rest.into())),
             "MEMBER" => Ok(Sk::Member(rest.into())),
+            "MEMBERGUARD" => Ok(Sk::MemberGuard(rest.into())),
             "SIDE" => Ok(Sk::Side(rest.into())),
             "PLAYER" => Ok(Sk::Player(rest.into())),
             "LIVESCORE" => Ok(Sk::Score(rest.into())),
@@ -373,13 +573,34 @@ impl FromStr for Sk {
             "NOTIF" => Ok(Sk::Notification(rest.into())),
             "DEVICE" => Ok(Sk::Device(rest.into())),
             "STATCONTRIB" => Ok(Sk::StatContribution(rest.into())),
-            "FEED" => {
-                let (starts_at, match_id) = two(rest)?;
-                Ok(Sk::Feed {
-                    starts_at,
-                    match_id,
+            "JOINREQ" => Ok(Sk::JoinRequest(rest.into())),
+            "SEASON" => Ok(Sk::Season(rest.into())),
+            "TMATCH" => Ok(Sk::TeamMatch(rest.into())),
+            "CLUBADMIN" => Ok(Sk::ClubAdmin(rest.into())),
+            "FOLLOWREQ" => Ok(Sk::FollowRequest(rest.into())),
+            "BLOCK" => Ok(Sk::Block(rest.into())),
+            "MUTE" => Ok(Sk::Mute(rest.into())),
+            "REPORTER" => Ok(Sk::Reporter(rest.into())),
+            "TAGMATCH" => Ok(Sk::TaggedMatch(rest.into())),
+            "ANNOUNCE" => Ok(Sk::Announcement(rest.into())),
+            "CREACT" => {
+                let (comment_id, user_id) = two(rest)?;
+                Ok(Sk::CommentReaction {
+                    comment_id,
+                    user_id,
+                })
+            }
+            "DEFERPUSH" => {
+                let (release_at, notification_id) = two(rest)?;
+                Ok(Sk::DeferredPush {
+                    release_at,
+                    notification_id,
                 })
             }
+            "FEED" => {
+                let (at, ref_id) = two(rest)?;
+                Ok(Sk::Feed { at, ref_id })
+            }
             other => Err(KeyError::UnknownPrefix(other.into())),
         }
     }
@@ -408,10 +629,18 @@ mod tests {
         );
         pk_roundtrip(Pk::AuthGuard("sub-abc-123".into()), "AUTH#sub-abc-123");
         pk_roundtrip(Pk::Team("t1".into()), "TEAM#t1");
+        pk_roundtrip(Pk::Club("c1".into()), "CLUB#c1");
         pk_roundtrip(Pk::Match("m1".into()), "MATCH#m1");
         pk_roundtrip(Pk::UserFeed("u1".into()), "UFEED#u1");
         pk_roundtrip(Pk::Invitation("i1".into()), "INVITATION#i1");
         pk_roundtrip(Pk::Asset("a1".into()), "ASSET#a1");
+        pk_roundtrip(Pk::Report("comment-c1".into()), "REPORT#comment-c1");
+        pk_roundtrip(Pk::Tag("derby".into()), "TAG#derby");
+        pk_roundtrip(Pk::SearchIndex("matches".into()), "SEARCHINDEX#matches");
+        pk_roundtrip(
+            Pk::Milestone("u1.tennis.wins.10".into()),
+            "MILESTONE#u1.tennis.wins.10",
+        );
     }
 
     #[test]
@@ -427,6 +656,9 @@ mod tests {
         sk_roundtrip(Sk::Profile, "#PROFILE");
         sk_roundtrip(Sk::Meta, "#META");
         sk_roundtrip(Sk::Guard, "#GUARD");
+        sk_roundtrip(Sk::NotificationPreferences, "#NOTIFPREFS");
+        sk_roundtrip(Sk::Suggestions, "#SUGGESTIONS");
+        sk_roundtrip(Sk::RankedFeed, "#RANKEDFEED");
     }
 
     #[test]
@@ -447,6 +679,7 @@ mod tests {
     fn sk_single_value_variants_roundtrip() {
         sk_roundtrip(Sk::Follower("u2".into()), "FOLLOWER#u2");
         sk_roundtrip(Sk::Member("mem1".into()), "MEMBER#mem1");
+        sk_roundtrip(Sk::MemberGuard("u1".into()), "MEMBERGUARD#u1");
         sk_roundtrip(Sk::Side("side_red".into()), "SIDE#side_red");
         sk_roundtrip(Sk::Player("p1".into()), "PLAYER#p1");
         sk_roundtrip(Sk::Score("cricket".into()), "LIVESCORE#cricket");
@@ -458,6 +691,16 @@ mod tests {
         sk_roundtrip(Sk::Notification("n1".into()), "NOTIF#n1");
         sk_roundtrip(Sk::Device("token-abc".into()), "DEVICE#token-abc");
         sk_roundtrip(Sk::StatContribution("u4".into()), "STATCONTRIB#u4");
+        sk_roundtrip(Sk::JoinRequest("u5".into()), "JOINREQ#u5");
+        sk_roundtrip(Sk::Season("s1".into()), "SEASON#s1");
+        sk_roundtrip(Sk::TeamMatch("m6".into()), "TMATCH#m6");
+        sk_roundtrip(Sk::ClubAdmin("u7".into()), "CLUBADMIN#u7");
+        sk_roundtrip(Sk::FollowRequest("u8".into()), "FOLLOWREQ#u8");
+        sk_roundtrip(Sk::Block("u9".into()), "BLOCK#u9");
+        sk_roundtrip(Sk::Mute("u10".into()), "MUTE#u10");
+        sk_roundtrip(Sk::Reporter("u11".into()), "REPORTER#u11");
+        sk_roundtrip(Sk::TaggedMatch("m12".into()), "TAGMATCH#m12");
+        sk_roundtrip(Sk::Announcement("a13".into()), "ANNOUNCE#a13");
     }
 
     #[test]
@@ -465,13 +708,36 @@ mod tests {
         let ts = "2026-06-01T10:00:00Z";
         sk_roundtrip(
             Sk::Feed {
-                starts_at: ts.into(),
-                match_id: "m1".into(),
+                at: ts.into(),
+                ref_id: "m1".into(),
             },
             "FEED#2026-06-01T10:00:00Z#m1",
         );
     }
 
+    #[test]
+    fn sk_comment_reaction_variant_roundtrips() {
+        sk_roundtrip(
+            Sk::CommentReaction {
+                comment_id: "c1".into(),
+                user_id: "u2".into(),
+            },
+            "CREACT#c1#u2",
+        );
+        assert_eq!(Sk::comment_reaction_prefix("c1"), "CREACT#c1#");
+    }
+
+    #[test]
+    fn sk_deferred_push_variant_roundtrips() {
+        sk_roundtrip(
+            Sk::DeferredPush {
+                release_at: "2025-06-01T07:00:00Z".into(),
+                notification_id: "n1".into(),
+            },
+            "DEFERPUSH#2025-06-01T07:00:00Z#n1",
+        );
+    }
+
     #[test]
     fn errors_are_reported() {
         assert_eq!("".parse::<Pk>(), Err(KeyError::Empty));
@@ -500,11 +766,20 @@ mod tests {
         // query actually needs, unlike the bare `prefix()` a `Display` impl
         // builds off (see `no_range_query_prefix_is_a_prefix_of_another`).
         assert_eq!(Sk::follower_prefix(), "FOLLOWER#");
+        assert_eq!(Sk::member_prefix(), "MEMBER#");
         assert_eq!(Sk::side_prefix(), "SIDE#");
         assert_eq!(Sk::player_prefix(), "PLAYER#");
         assert_eq!(Sk::like_prefix(), "LIKE#");
         assert_eq!(Sk::live_event_prefix(), "LIVEEVT#");
         assert_eq!(Sk::stat_contribution_prefix(), "STATCONTRIB#");
+        assert_eq!(Sk::join_request_prefix(), "JOINREQ#");
+        assert_eq!(Sk::season_prefix(), "SEASON#");
+        assert_eq!(Sk::team_match_prefix(), "TMATCH#");
+        assert_eq!(Sk::club_admin_prefix(), "CLUBADMIN#");
+        assert_eq!(Sk::follow_request_prefix(), "FOLLOWREQ#");
+        assert_eq!(Sk::block_prefix(), "BLOCK#");
+        assert_eq!(Sk::mute_prefix(), "MUTE#");
+        assert_eq!(Sk::reporter_prefix(), "REPORTER#");
         assert_eq!(Sk::feed_prefix(), "FEED#");
     }
 
@@ -521,13 +796,25 @@ mod tests {
         // additions, not just the ones that already have a function.
         let prefixes = [
             Sk::follower_prefix(),
+            Sk::member_prefix(),
             Sk::side_prefix(),
             Sk::player_prefix(),
             Sk::like_prefix(),
             Sk::live_event_prefix(),
             Sk::stat_contribution_prefix(),
+            Sk::join_request_prefix(),
+            Sk::season_prefix(),
+            Sk::team_match_prefix(),
+            Sk::club_admin_prefix(),
+            Sk::follow_request_prefix(),
+            Sk::block_prefix(),
+            Sk::mute_prefix(),
+            Sk::reporter_prefix(),
             Sk::feed_prefix(),
+            Sk::comment_reaction_prefix(""),
+            Sk::deferred_push_prefix(),
             "SCORESUB#".to_string(),
+            "MEMBERGUARD#".to_string(),
         ];
         for (i, a) in prefixes.iter().enumerate() {
             for (j, b) in prefixes.iter().enumerate() {
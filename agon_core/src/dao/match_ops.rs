@@ -1,6 +1,6 @@
 //! Match operations: create (meta + sides + players in one transaction),
-//! get aggregate, update meta, live-scoring score record, and player roster
-//! writes.
+//! get aggregate, update meta, cancel/postpone, live-scoring score record, and
+//! player roster writes.
 
 use std::collections::HashMap;
 
@@ -13,11 +13,14 @@ use aws_sdk_dynamodb::types::{
 use super::audience::AudienceMember;
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ATTR_PK, ATTR_SK, ItemBuilder, from_item, item_pk, s, to_item};
+use super::feed::FeedRef;
+use super::item::{ATTR_GSI1PK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, item_pk, s, to_item};
 use super::keys::{Pk, Sk};
+use super::page::Page;
 use super::records::{
-    ConfirmedScoreRecord, HeaderPhotoRecord, MatchFormatRecord, MatchPlayerRecord, MatchRecord,
-    MatchScoreRecord, MatchSideRecord, PendingScoreRecord, SideRosterMemberRecord,
+    AudienceReason, ConfirmedScoreRecord, HeaderPhotoRecord, MatchCancellationRecord,
+    MatchFormatRecord, MatchPlayerRecord, MatchPostponementRecord, MatchRecord, MatchScoreRecord,
+    MatchSideRecord, PendingScoreRecord, SideRosterMemberRecord, TeamMatchRecord,
 };
 
 pub const TYPE_MATCH: &str = "match";
@@ -25,6 +28,14 @@ pub const TYPE_MATCH_SIDE: &str = "match_side";
 pub const TYPE_MATCH_PLAYER: &str = "match_player";
 pub const TYPE_MATCH_SCORE: &str = "match_score";
 
+/// The base-table key of a match player item, read back from its GSI1
+/// projection to recover which match it belongs to.
+#[derive(serde::Deserialize)]
+struct PlayerItemKey {
+    #[serde(rename = "PK")]
+    pk: String,
+}
+
 /// A side's roster is cached in full (`MatchSideRecord::roster_preview`) only
 /// when it's this small or smaller — enough for 1v1 and doubles/small squads.
 /// Above the cap the preview is left empty and callers fall back to
@@ -69,7 +80,7 @@ fn side_roster(side_id: &str, players: &[MatchPlayerRecord]) -> (u32, Vec<SideRo
 /// more people on first" — that's a read-time, per-viewer decision for
 /// `agon_service` to make); just a stable one so which side renders first
 /// doesn't flip between requests.
-fn sorted_sides(sides: &HashMap<String, MatchSideRecord>) -> Vec<MatchSideRecord> {
+pub(super) fn sorted_sides(sides: &HashMap<String, MatchSideRecord>) -> Vec<MatchSideRecord> {
     let mut sides: Vec<MatchSideRecord> = sides.values().cloned().collect();
     sides.sort_by(|a, b| a.side_id.cmp(&b.side_id));
     sides
@@ -99,6 +110,9 @@ impl Dao {
     /// overwriting whatever the caller set) and players, in a single
     /// transaction. `Conflict` if the match id already exists.
     ///
+    /// Each side with a `team_id` also gets a `TMATCH#` pointer in that team's
+    /// partition, so the team's season stats can find its matches.
+    ///
     /// Note: DynamoDB caps a transaction at 100 items, so a match with a very
     /// large roster would need chunking — not handled here (fine for real
     /// team sizes). Feed fan-out happens asynchronously off the stream, not here.
@@ -139,6 +153,23 @@ impl Dao {
             .transact_write_items()
             .transact_items(TransactWriteItem::builder().put(put_meta).build());
 
+        for side in match_.sides.values() {
+            let Some(team_id) = &side.team_id else {
+                continue;
+            };
+            let pointer = TeamMatchRecord {
+                team_id: team_id.clone(),
+                match_id: match_.id.clone(),
+                side_id: side.side_id.clone(),
+            };
+            let put = Put::builder()
+                .table_name(self.table())
+                .set_item(Some(self.team_match_item(&pointer)?))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            tx = tx.transact_items(TransactWriteItem::builder().put(put).build());
+        }
+
         for player in players {
             let put = Put::builder()
                 .table_name(self.table())
@@ -158,11 +189,11 @@ impl Dao {
             if let (Some(uid), true) = (&player.user_id, joined) {
                 let feed_item = self.feed_item(
                     uid,
-                    &match_.id,
-                    &match_.starts_at,
+                    &FeedRef::match_at(&match_.id, &match_.starts_at),
                     &match_.created_at,
                     &AudienceMember {
                         viewer_side_id: player.side_id.clone(),
+                        reasons: vec![AudienceReason::Participant],
                         ..Default::default()
                     },
                 )?;
@@ -226,14 +257,56 @@ impl Dao {
         }))
     }
 
+    /// The ids of matches a user is (or was) a player in, via their player
+    /// items' GSI1 projection (`UMATCHES#<userId>`). Ordered by match id, not
+    /// by date.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_user_match_ids(
+        &self,
+        user_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<String>> {
+        let page: Page<PlayerItemKey> = self
+            .query_page(
+                self.client
+                    .query()
+                    .table_name(self.table())
+                    .index_name("GSI1")
+                    .key_condition_expression("#pk = :pk")
+                    .projection_expression("#mpk")
+                    .expression_attribute_names("#pk", ATTR_GSI1PK)
+                    .expression_attribute_names("#mpk", ATTR_PK)
+                    .expression_attribute_values(":pk", s(format!("UMATCHES#{user_id}"))),
+                cursor,
+                limit,
+            )
+            .await?;
+        let mut ids = Vec::with_capacity(page.items.len());
+        for item in page.items {
+            match item.pk.parse()? {
+                Pk::Match(id) => ids.push(id),
+                pk => {
+                    return Err(DaoError::Malformed(format!(
+                        "unexpected UMATCHES item under {pk}"
+                    )));
+                }
+            }
+        }
+        Ok(Page {
+            items: ids,
+            next_cursor: page.next_cursor,
+        })
+    }
+
     /// Fetch many matches' meta items by id in one round-trip, keyed by id.
     /// Missing ids are simply absent from the map (mirrors [`get_match`]
     /// returning `None`), and duplicate ids collapse.
     ///
     /// Each meta item is an exact-key point read, so this collapses what would
     /// be N `GetItem`s into one `BatchGetItem` (see [`batch_get_all`] for the
-    /// unprocessed-key retry). Callers must pass at most `BATCH_GET_MAX` ids —
-    /// every current caller hydrates a single, service-capped page.
+    /// unprocessed-key retry). More than `BATCH_GET_MAX` ids (a team's whole
+    /// match history, say) take one request per `BATCH_GET_MAX`.
     ///
     /// [`batch_get_all`]: Dao::batch_get_all
     #[tracing::instrument(skip(self))]
@@ -416,9 +489,9 @@ impl Dao {
     }
 
     /// Update a match's mutable meta fields. Any `Some` field is written; `name`,
-    /// `description`, `status`, `starts_at`, `location` (Some(None) clears it),
-    /// and the resolved `confirmed_score`/`pending_score` blobs. `NotFound` if
-    /// the match is absent.
+    /// `description`, `status`, `starts_at`, `venue` and `league` (Some(None)
+    /// clears those two), and the resolved `confirmed_score`/`pending_score`
+    /// blobs. `NotFound` if the match is absent.
     ///
     /// `side_names` renames one or more sides in the same `UpdateItem` call as
     /// everything else here — both target the same item, so folding it in
@@ -438,6 +511,10 @@ impl Dao {
         description: Option<&str>,
         status: Option<&str>,
         starts_at: Option<&str>,
+        // Set (`Some(Some(_))`), clear (`Some(None)`) or leave the venue and
+        // league.
+        venue: Option<Option<&str>>,
+        league: Option<Option<&str>>,
         confirmed_score: Option<ConfirmedScoreRecord>,
         pending_score: Option<Option<PendingScoreRecord>>,
         // Replace the header photos, in order. `None` leaves them unchanged;
@@ -479,6 +556,16 @@ impl Dao {
         if let Some(v) = starts_at {
             set_str("starts_at", "starts", v, &mut set, &mut names, &mut values);
         }
+        for (field, value) in [("venue", venue), ("league", league)] {
+            match value {
+                Some(Some(v)) => set_str(field, field, v, &mut set, &mut names, &mut values),
+                Some(None) => {
+                    remove.push(format!("#{field}"));
+                    names.insert(format!("#{field}"), field.to_string());
+                }
+                None => {}
+            }
+        }
         if let Some(cs) = confirmed_score {
             set.push("#cs = :cs".into());
             names.insert("#cs".into(), "confirmed_score".into());
@@ -579,6 +666,122 @@ impl Dao {
         }
     }
 
+    /// Cancel a match: set `status = "cancelled"`, record `cancellation`, and
+    /// drop any pending score (there's nothing left to confirm). A confirmed
+    /// score is kept for the record; the stats handler backs its contribution
+    /// out because the match is cancelled. `NotFound` if the match doesn't
+    /// exist, `Conflict` if it's already cancelled.
+    #[tracing::instrument(skip(self, cancellation))]
+    pub async fn cancel_match(
+        &self,
+        match_id: &str,
+        cancellation: &MatchCancellationRecord,
+    ) -> DaoResult<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression("SET #status = :cancelled, cancellation = :c REMOVE pending_score")
+            .condition_expression("attribute_exists(#pk) AND #status <> :cancelled")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":cancelled", s("cancelled"))
+            .expression_attribute_values(":c", to_attr(cancellation)?)
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => {
+                if self.match_exists(match_id).await? {
+                    Err(DaoError::Conflict(format!(
+                        "match {match_id} is already cancelled"
+                    )))
+                } else {
+                    Err(DaoError::NotFound(format!("match {match_id}")))
+                }
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Move a match to `postponement.to_starts_at`, appending the entry to
+    /// `postponements` and putting the match back to "scheduled". A pending
+    /// score is dropped. Roster, invitations and everything else in the match
+    /// partition are keyed by match id, not start time, so they carry over
+    /// untouched; only the feed rows (keyed by `starts_at`) need rewriting,
+    /// which the fan-out this `#META` write triggers takes care of.
+    ///
+    /// Guarded on `starts_at` still being `postponement.from_starts_at`, so two
+    /// concurrent postponements can't both apply. `NotFound` if the match
+    /// doesn't exist; `Conflict` if it's cancelled, already has a confirmed
+    /// score, or its start time changed underneath the caller.
+    #[tracing::instrument(skip(self, postponement))]
+    pub async fn postpone_match(
+        &self,
+        match_id: &str,
+        postponement: &MatchPostponementRecord,
+    ) -> DaoResult<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression(
+                "SET starts_at = :to, #status = :scheduled, \
+                 postponements = list_append(if_not_exists(postponements, :empty), :p) \
+                 REMOVE pending_score",
+            )
+            .condition_expression(
+                "attribute_exists(#pk) AND #status <> :cancelled \
+                 AND starts_at = :from AND attribute_not_exists(confirmed_score)",
+            )
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":to", s(&postponement.to_starts_at))
+            .expression_attribute_values(":from", s(&postponement.from_starts_at))
+            .expression_attribute_values(":scheduled", s("scheduled"))
+            .expression_attribute_values(":cancelled", s("cancelled"))
+            .expression_attribute_values(":empty", AttributeValue::L(Vec::new()))
+            .expression_attribute_values(":p", to_attr(&vec![postponement])?)
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => {
+                if self.match_exists(match_id).await? {
+                    Err(DaoError::Conflict(format!(
+                        "match {match_id} can't be postponed from {}",
+                        postponement.from_starts_at
+                    )))
+                } else {
+                    Err(DaoError::NotFound(format!("match {match_id}")))
+                }
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Existence check on a match's `#META`, for telling a failed guard's
+    /// `NotFound` apart from its `Conflict`.
+    async fn match_exists(&self, match_id: &str) -> DaoResult<bool> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .projection_expression(ATTR_PK) // existence check only
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(out.item.is_some())
+    }
+
     /// Add or update a single match player (roster reconciliation / late adds).
     #[tracing::instrument(skip(self, player), fields(player_id = %player.player_id))]
     pub async fn put_match_player(
@@ -800,3 +1003,102 @@ fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
             if matches!(se.err(), UpdateItemError::ConditionalCheckFailedException(_))
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{MatchCancellationRecord, MatchPostponementRecord};
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+    const LATER: &str = "2026-04-08T10:00:00Z";
+
+    async fn create(dao: &Dao, id: &str) {
+        let match_: MatchRecord = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": "Friendly",
+            "description": "",
+            "match_type": "football",
+            "status": "scheduled",
+            "starts_at": NOW,
+            "sides": {},
+            "created_at": NOW,
+        }))
+        .unwrap();
+        dao.create_match(&match_, &[]).await.unwrap();
+    }
+
+    fn cancellation() -> MatchCancellationRecord {
+        MatchCancellationRecord {
+            reason: "waterlogged pitch".into(),
+            by_user_id: "u1".into(),
+            at: NOW.into(),
+        }
+    }
+
+    fn postponement(from: &str, to: &str) -> MatchPostponementRecord {
+        MatchPostponementRecord {
+            from_starts_at: from.into(),
+            to_starts_at: to.into(),
+            reason: "floodlights".into(),
+            by_user_id: "u1".into(),
+            at: NOW.into(),
+        }
+    }
+
+    async fn meta(dao: &Dao, id: &str) -> MatchRecord {
+        dao.get_match(id).await.unwrap().unwrap().match_
+    }
+
+    #[tokio::test]
+    async fn a_match_is_cancelled_once() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        create(&dao, "m1").await;
+        dao.cancel_match("m1", &cancellation()).await.unwrap();
+        let m1 = meta(&dao, "m1").await;
+        assert_eq!(m1.status, "cancelled");
+        assert_eq!(m1.cancellation, Some(cancellation()));
+
+        assert!(matches!(
+            dao.cancel_match("m1", &cancellation()).await,
+            Err(DaoError::Conflict(_))
+        ));
+        assert!(matches!(
+            dao.cancel_match("m9", &cancellation()).await,
+            Err(DaoError::NotFound(_))
+        ));
+        // A cancelled match stays where it was.
+        assert!(matches!(
+            dao.postpone_match("m1", &postponement(NOW, LATER)).await,
+            Err(DaoError::Conflict(_))
+        ));
+        assert_eq!(meta(&dao, "m1").await.starts_at, NOW);
+    }
+
+    #[tokio::test]
+    async fn postponements_move_the_start_and_are_kept_in_order() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        create(&dao, "m1").await;
+        let first = postponement(NOW, LATER);
+        let second = postponement(LATER, "2026-04-15T10:00:00Z");
+        dao.postpone_match("m1", &first).await.unwrap();
+
+        // Postponing from a start time that's since moved loses the race.
+        assert!(matches!(
+            dao.postpone_match("m1", &first).await,
+            Err(DaoError::Conflict(_))
+        ));
+        dao.postpone_match("m1", &second).await.unwrap();
+        let m1 = meta(&dao, "m1").await;
+        assert_eq!(m1.starts_at, "2026-04-15T10:00:00Z");
+        assert_eq!(m1.status, "scheduled");
+        assert_eq!(m1.postponements, [first, second]);
+
+        assert!(matches!(
+            dao.postpone_match("m9", &postponement(NOW, LATER)).await,
+            Err(DaoError::NotFound(_))
+        ));
+    }
+}
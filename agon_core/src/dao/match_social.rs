@@ -1,133 +1,153 @@
-//! Match likes, comments (+ replies, tombstone) and score submissions — the
-//! sub-collections under a match, with atomic counter maintenance.
+//! Match and comment reactions (a like is the 👍 one), comments (+ replies,
+//! tombstone) and score submissions — the sub-collections under a match, with
+//! atomic counter maintenance.
 
 use std::collections::{HashMap, HashSet};
 
+use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
 use aws_sdk_dynamodb::types::{AttributeValue, Delete, Put, TransactWriteItem, Update};
 
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ATTR_GSI1PK, ATTR_PK, ATTR_SK, ItemBuilder, item_pk, s, to_item};
+use super::item::{ATTR_GSI1PK, ATTR_PK, ATTR_SK, Item, ItemBuilder, s, to_item};
 use super::keys::{Pk, Sk};
 use super::page::Page;
-use super::records::{CommentRecord, MatchLikeRecord, ScoreSubmissionRecord};
+use super::records::{
+    CommentMentionRecord, CommentReactionRecord, CommentRecord, MatchLikeRecord, ReactionRecord,
+    ScoreSubmissionRecord, TagCommentRecord,
+};
 
 pub const TYPE_MATCH_LIKE: &str = "match_like";
+pub const TYPE_COMMENT_REACTION: &str = "comment_reaction";
 pub const TYPE_COMMENT: &str = "comment";
 pub const TYPE_REPLY: &str = "reply";
 pub const TYPE_SCORE_SUBMISSION: &str = "score_submission";
 
 impl Dao {
-    // ---- Likes ------------------------------------------------------------
+    // ---- Reactions ----------------------------------------------------------
+    //
+    // One reaction per (user, match) and per (user, comment). A match's lives
+    // in the `LIKE#<uid>` edge likes always used, so a like is just the 👍
+    // reaction. Each reaction has its own counter on the reacted-to item (see
+    // `ReactionRecord::count_attr`); switching reactions moves one unit between
+    // two counters in the same transaction as the edge write. Every write is
+    // conditioned on the reaction it read, so a concurrent change by the same
+    // user wins and the losing call is a no-op.
 
-    /// Like a match. Idempotent; bumps `like_count` on the match meta only when
-    /// the like edge is newly created.
+    /// Set `user_id`'s reaction on a match, replacing any other. Idempotent.
     #[tracing::instrument(skip(self))]
-    pub async fn like_match(&self, match_id: &str, user_id: &str, now: &str) -> DaoResult<()> {
-        let like = MatchLikeRecord {
+    pub async fn react_to_match(
+        &self,
+        match_id: &str,
+        user_id: &str,
+        reaction: ReactionRecord,
+        now: &str,
+    ) -> DaoResult<()> {
+        let previous = self
+            .get_match_reaction(match_id, user_id)
+            .await?
+            .map(|r| r.reaction);
+        if previous == Some(reaction) {
+            return Ok(());
+        }
+        let record = MatchLikeRecord {
             match_id: match_id.into(),
             user_id: user_id.into(),
+            reaction,
             created_at: now.into(),
         };
         let item = to_item(
             &Pk::Match(match_id.into()),
             &Sk::Like(user_id.into()),
             TYPE_MATCH_LIKE,
-            &like,
+            &record,
         )?;
+        self.put_reaction(
+            item,
+            previous,
+            reaction,
+            Pk::Match(match_id.into()),
+            Sk::Meta,
+        )
+        .await
+    }
 
-        let put = Put::builder()
-            .table_name(self.table())
-            .set_item(Some(item))
-            .condition_expression("attribute_not_exists(#pk)")
-            .expression_attribute_names("#pk", ATTR_PK)
-            .build()
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-
-        let result = self
-            .client
-            .transact_write_items()
-            .transact_items(TransactWriteItem::builder().put(put).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(self.match_counter(match_id, "like_count", 1)?)
-                    .build(),
-            )
-            .send()
-            .await;
+    /// Like a match: react with 👍, replacing any other reaction. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn like_match(&self, match_id: &str, user_id: &str, now: &str) -> DaoResult<()> {
+        self.react_to_match(match_id, user_id, ReactionRecord::Like, now)
+            .await
+    }
 
-        match result {
-            Ok(_) => Ok(()),
-            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()), // already liked
-            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+    /// Remove `user_id`'s reaction on a match — any reaction, or only
+    /// `only`'s. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn unreact_to_match(
+        &self,
+        match_id: &str,
+        user_id: &str,
+        only: Option<ReactionRecord>,
+    ) -> DaoResult<()> {
+        let Some(previous) = self.get_match_reaction(match_id, user_id).await? else {
+            return Ok(());
+        };
+        if only.is_some_and(|r| r != previous.reaction) {
+            return Ok(());
         }
+        self.delete_reaction(
+            Sk::Like(user_id.into()),
+            previous.reaction,
+            Pk::Match(match_id.into()),
+            Sk::Meta,
+        )
+        .await
     }
 
-    /// Unlike a match. Idempotent; only decrements when a like actually existed.
+    /// Unlike a match. Idempotent; leaves any other reaction in place.
     #[tracing::instrument(skip(self))]
     pub async fn unlike_match(&self, match_id: &str, user_id: &str) -> DaoResult<()> {
-        let delete = Delete::builder()
-            .table_name(self.table())
-            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
-            .key("SK", s(Sk::Like(user_id.into()).to_string()))
-            .condition_expression("attribute_exists(#pk)")
-            .expression_attribute_names("#pk", ATTR_PK)
-            .build()
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-
-        let result = self
-            .client
-            .transact_write_items()
-            .transact_items(TransactWriteItem::builder().delete(delete).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(self.match_counter(match_id, "like_count", -1)?)
-                    .build(),
-            )
-            .send()
-            .await;
-
-        match result {
-            Ok(_) => Ok(()),
-            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()), // wasn't liked
-            Err(e) => Err(DaoError::Dynamo(e.to_string())),
-        }
+        self.unreact_to_match(match_id, user_id, Some(ReactionRecord::Like))
+            .await
     }
 
-    /// Whether the given user has liked the match (drives `i_liked`).
+    /// The given user's reaction on the match, if any (drives `my_reaction`
+    /// and `i_liked`). Strongly consistent, since the reaction writes condition
+    /// on what it returns.
     #[tracing::instrument(skip(self))]
-    pub async fn has_liked_match(&self, match_id: &str, user_id: &str) -> DaoResult<bool> {
+    pub async fn get_match_reaction(
+        &self,
+        match_id: &str,
+        user_id: &str,
+    ) -> DaoResult<Option<MatchLikeRecord>> {
         let out = self
             .client
             .get_item()
             .table_name(self.table())
             .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
-            .key("SK", s(Sk::Like(user_id.into()).to_string()))
-            .projection_expression(ATTR_PK)
+            .key(ATTR_SK, s(Sk::Like(user_id.into()).to_string()))
+            .consistent_read(true)
             .send()
             .await
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-        Ok(out.item.is_some())
+        out.item.map(super::item::from_item).transpose()
     }
 
-    /// Of `match_ids`, which has `user_id` liked? Returns the subset as a set,
-    /// for populating `i_liked` across a page of matches (e.g. a feed page) in
-    /// one round-trip instead of one `has_liked_match` call each.
+    /// Of `match_ids`, which has `user_id` reacted to, and how? For populating
+    /// `my_reaction` across a page of matches (e.g. a feed page) in one
+    /// round-trip instead of one `get_match_reaction` call each.
     ///
-    /// Each like edge is an exact-key existence check (`MATCH#<id>` /
-    /// `LIKE#<uid>`), so these collapse into a single `BatchGetItem` (see
-    /// [`batch_get_all`] for the unprocessed-key retry); we project just the
-    /// `PK` since we only need existence, then recover which match ids came
-    /// back. Callers must pass at most `BATCH_GET_MAX` ids — a single,
-    /// service-capped page.
+    /// Each reaction edge is an exact-key lookup (`MATCH#<id>` / `LIKE#<uid>`),
+    /// so these collapse into a single `BatchGetItem` (see [`batch_get_all`]
+    /// for the unprocessed-key retry). Callers must pass at most
+    /// `BATCH_GET_MAX` ids — a single, service-capped page.
     ///
     /// [`batch_get_all`]: Dao::batch_get_all
     #[tracing::instrument(skip(self))]
-    pub async fn batch_has_liked_matches(
+    pub async fn batch_get_match_reactions(
         &self,
         match_ids: &[String],
         user_id: &str,
-    ) -> DaoResult<HashSet<String>> {
+    ) -> DaoResult<HashMap<String, ReactionRecord>> {
         let mut seen = HashSet::new();
         let keys: Vec<_> = match_ids
             .iter()
@@ -146,38 +166,316 @@ impl Dao {
             })
             .collect();
 
-        let items = self.batch_get_all(keys, Some(ATTR_PK)).await?;
-        let mut liked = HashSet::with_capacity(items.len());
-        for item in items {
-            if let Pk::Match(match_id) = item_pk(&item)? {
-                liked.insert(match_id);
-            }
-        }
-        Ok(liked)
+        let items = self.batch_get_all(keys, None).await?;
+        items
+            .into_iter()
+            .map(|item| {
+                let record: MatchLikeRecord = super::item::from_item(item)?;
+                Ok((record.match_id, record.reaction))
+            })
+            .collect()
     }
 
-    /// List users who liked a match, cursor-paginated.
+    /// List who reacted to a match — everyone, or only those who reacted with
+    /// `reaction` — cursor-paginated. Filtered pages can come back short (or
+    /// empty) while `next_cursor` is still set.
     #[tracing::instrument(skip(self))]
-    pub async fn list_match_likes(
+    pub async fn list_match_reactions(
         &self,
         match_id: &str,
+        reaction: Option<ReactionRecord>,
         cursor: Option<&str>,
         limit: u32,
     ) -> DaoResult<Page<MatchLikeRecord>> {
-        self.query_page(
-            self.client
-                .query()
-                .table_name(self.table())
-                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
-                .expression_attribute_names("#pk", ATTR_PK)
-                .expression_attribute_values(":pk", s(Pk::Match(match_id.into()).to_string()))
-                .expression_attribute_values(":sk", s(Sk::like_prefix())),
-            cursor,
-            limit,
+        let query = self
+            .client
+            .query()
+            .table_name(self.table())
+            .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":pk", s(Pk::Match(match_id.into()).to_string()))
+            .expression_attribute_values(":sk", s(Sk::like_prefix()));
+        self.query_page(reaction_filter(query, reaction), cursor, limit)
+            .await
+    }
+
+    /// Set `user_id`'s reaction on a comment or reply, replacing any other.
+    /// Idempotent.
+    #[tracing::instrument(
+        skip(self, comment),
+        fields(match_id = %comment.match_id, comment_id = %comment.comment_id)
+    )]
+    pub async fn react_to_comment(
+        &self,
+        comment: &CommentRecord,
+        user_id: &str,
+        reaction: ReactionRecord,
+        now: &str,
+    ) -> DaoResult<()> {
+        let previous = self
+            .get_comment_reaction(&comment.match_id, &comment.comment_id, user_id)
+            .await?
+            .map(|r| r.reaction);
+        if previous == Some(reaction) {
+            return Ok(());
+        }
+        let record = CommentReactionRecord {
+            match_id: comment.match_id.clone(),
+            comment_id: comment.comment_id.clone(),
+            user_id: user_id.into(),
+            reaction,
+            created_at: now.into(),
+        };
+        let item = to_item(
+            &Pk::Match(comment.match_id.clone()),
+            &Sk::CommentReaction {
+                comment_id: comment.comment_id.clone(),
+                user_id: user_id.into(),
+            },
+            TYPE_COMMENT_REACTION,
+            &record,
+        )?;
+        self.put_reaction(
+            item,
+            previous,
+            reaction,
+            Pk::Match(comment.match_id.clone()),
+            comment_sk(comment),
+        )
+        .await
+    }
+
+    /// Remove `user_id`'s reaction on a comment or reply. Idempotent.
+    #[tracing::instrument(
+        skip(self, comment),
+        fields(match_id = %comment.match_id, comment_id = %comment.comment_id)
+    )]
+    pub async fn unreact_to_comment(
+        &self,
+        comment: &CommentRecord,
+        user_id: &str,
+    ) -> DaoResult<()> {
+        let Some(previous) = self
+            .get_comment_reaction(&comment.match_id, &comment.comment_id, user_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        self.delete_reaction(
+            Sk::CommentReaction {
+                comment_id: comment.comment_id.clone(),
+                user_id: user_id.into(),
+            },
+            previous.reaction,
+            Pk::Match(comment.match_id.clone()),
+            comment_sk(comment),
         )
         .await
     }
 
+    /// The given user's reaction on a comment or reply, if any. Strongly
+    /// consistent, like `get_match_reaction`.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_comment_reaction(
+        &self,
+        match_id: &str,
+        comment_id: &str,
+        user_id: &str,
+    ) -> DaoResult<Option<CommentReactionRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key(
+                ATTR_SK,
+                s(Sk::CommentReaction {
+                    comment_id: comment_id.into(),
+                    user_id: user_id.into(),
+                }
+                .to_string()),
+            )
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        out.item.map(super::item::from_item).transpose()
+    }
+
+    /// `user_id`'s reactions on `comments`, keyed by comment id, in one
+    /// `BatchGetItem`. Callers must pass at most `BATCH_GET_MAX` comments.
+    #[tracing::instrument(skip(self, comments))]
+    pub async fn batch_get_comment_reactions(
+        &self,
+        comments: &[CommentRecord],
+        user_id: &str,
+    ) -> DaoResult<HashMap<String, ReactionRecord>> {
+        let mut seen = HashSet::new();
+        let keys: Vec<_> = comments
+            .iter()
+            .filter(|c| seen.insert(c.comment_id.clone()))
+            .map(|c| {
+                HashMap::from([
+                    (
+                        ATTR_PK.to_string(),
+                        s(Pk::Match(c.match_id.clone()).to_string()),
+                    ),
+                    (
+                        ATTR_SK.to_string(),
+                        s(Sk::CommentReaction {
+                            comment_id: c.comment_id.clone(),
+                            user_id: user_id.to_string(),
+                        }
+                        .to_string()),
+                    ),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, None).await?;
+        items
+            .into_iter()
+            .map(|item| {
+                let record: CommentReactionRecord = super::item::from_item(item)?;
+                Ok((record.comment_id, record.reaction))
+            })
+            .collect()
+    }
+
+    /// List who reacted to a comment or reply — everyone, or only those who
+    /// reacted with `reaction` — cursor-paginated. As with
+    /// `list_match_reactions`, filtered pages can come back short.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_comment_reactions(
+        &self,
+        match_id: &str,
+        comment_id: &str,
+        reaction: Option<ReactionRecord>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<CommentReactionRecord>> {
+        let query = self
+            .client
+            .query()
+            .table_name(self.table())
+            .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":pk", s(Pk::Match(match_id.into()).to_string()))
+            .expression_attribute_values(":sk", s(Sk::comment_reaction_prefix(comment_id)));
+        self.query_page(reaction_filter(query, reaction), cursor, limit)
+            .await
+    }
+
+    /// Write a reaction edge over `previous` (what the caller just read) and
+    /// move the counts on the reacted-to item (`counted_pk` / `counted_sk`)
+    /// to match, in one transaction. The counter update requires the item to
+    /// exist, so a reaction racing its comment's deletion doesn't leave a
+    /// stray counter-only item behind.
+    async fn put_reaction(
+        &self,
+        item: Item,
+        previous: Option<ReactionRecord>,
+        reaction: ReactionRecord,
+        counted_pk: Pk,
+        counted_sk: Sk,
+    ) -> DaoResult<()> {
+        let mut put = Put::builder().table_name(self.table()).set_item(Some(item));
+        put = match previous {
+            None => put
+                .condition_expression("attribute_not_exists(#pk)")
+                .expression_attribute_names("#pk", ATTR_PK),
+            Some(previous) => put
+                .condition_expression(previous_reaction_condition(previous))
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_names("#r", "reaction")
+                .expression_attribute_values(":prev", s(previous.as_str())),
+        };
+        let put = put.build().map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let mut counts = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(counted_pk.to_string()))
+            .key(ATTR_SK, s(counted_sk.to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#new", reaction.count_attr())
+            .expression_attribute_values(":one", AttributeValue::N("1".into()));
+        counts = match previous {
+            None => counts.update_expression("ADD #new :one"),
+            Some(previous) => counts
+                .update_expression("ADD #new :one, #old :minus")
+                .expression_attribute_names("#old", previous.count_attr())
+                .expression_attribute_values(":minus", AttributeValue::N("-1".into())),
+        };
+        let counts = counts
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .transact_items(TransactWriteItem::builder().update(counts).build())
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // Changed concurrently, or the target is gone.
+            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Delete the reaction edge at `edge_sk` (in the counted item's
+    /// partition) if it's still `previous`, decrementing that reaction's
+    /// count in the same transaction.
+    async fn delete_reaction(
+        &self,
+        edge_sk: Sk,
+        previous: ReactionRecord,
+        counted_pk: Pk,
+        counted_sk: Sk,
+    ) -> DaoResult<()> {
+        let delete = Delete::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(counted_pk.to_string()))
+            .key(ATTR_SK, s(edge_sk.to_string()))
+            .condition_expression(previous_reaction_condition(previous))
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#r", "reaction")
+            .expression_attribute_values(":prev", s(previous.as_str()))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let counts = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(counted_pk.to_string()))
+            .key(ATTR_SK, s(counted_sk.to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .update_expression("ADD #c :minus")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#c", previous.count_attr())
+            .expression_attribute_values(":minus", AttributeValue::N("-1".into()))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().delete(delete).build())
+            .transact_items(TransactWriteItem::builder().update(counts).build())
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // Changed or removed concurrently, or the target is gone.
+            Err(e) if super::is_transaction_conditional_failure(&e) => Ok(()),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
     // ---- Comments & replies ----------------------------------------------
     //
     // Comments/replies are addressed by id (base SK `COMMENT#<id>` /
@@ -208,14 +506,16 @@ impl Dao {
             .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
 
+        let mut writes = vec![
+            TransactWriteItem::builder().put(put).build(),
+            TransactWriteItem::builder()
+                .update(self.match_counter(&comment.match_id, "comment_count", 1)?)
+                .build(),
+        ];
+        writes.extend(self.tag_comment_writes(comment, &comment.tags, &comment.created_at)?);
         self.client
             .transact_write_items()
-            .transact_items(TransactWriteItem::builder().put(put).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(self.match_counter(&comment.match_id, "comment_count", 1)?)
-                    .build(),
-            )
+            .set_transact_items(Some(writes))
             .send()
             .await
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
@@ -269,15 +569,17 @@ impl Dao {
             .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
 
+        let mut writes = vec![
+            TransactWriteItem::builder().put(put_reply).build(),
+            TransactWriteItem::builder().update(bump_parent).build(),
+            TransactWriteItem::builder()
+                .update(self.match_counter(&reply.match_id, "comment_count", 1)?)
+                .build(),
+        ];
+        writes.extend(self.tag_comment_writes(reply, &reply.tags, &reply.created_at)?);
         self.client
             .transact_write_items()
-            .transact_items(TransactWriteItem::builder().put(put_reply).build())
-            .transact_items(TransactWriteItem::builder().update(bump_parent).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(self.match_counter(&reply.match_id, "comment_count", 1)?)
-                    .build(),
-            )
+            .set_transact_items(Some(writes))
             .send()
             .await
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
@@ -378,50 +680,190 @@ impl Dao {
         }
     }
 
-    /// Edit a top-level comment's text (addressed by id). Sets `edited_at`.
-    #[tracing::instrument(skip(self))]
+    /// Fetch the comments and replies a page of tag-index rows points at, in
+    /// one `BatchGetItem`, keyed by comment id. Rows whose comment is gone are
+    /// simply absent from the map.
+    #[tracing::instrument(skip(self, rows))]
+    pub async fn batch_get_tagged_comments(
+        &self,
+        rows: &[TagCommentRecord],
+    ) -> DaoResult<HashMap<String, CommentRecord>> {
+        let mut seen = HashSet::new();
+        let keys: Vec<_> = rows
+            .iter()
+            .filter(|r| seen.insert(r.comment_id.clone()))
+            .map(|r| {
+                let sk = match r.parent_id {
+                    Some(_) => Sk::Reply(r.comment_id.clone()),
+                    None => Sk::Comment(r.comment_id.clone()),
+                };
+                HashMap::from([
+                    (
+                        ATTR_PK.to_string(),
+                        s(Pk::Match(r.match_id.clone()).to_string()),
+                    ),
+                    (ATTR_SK.to_string(), s(sk.to_string())),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, None).await?;
+        let mut out = HashMap::with_capacity(items.len());
+        for item in items {
+            let record: CommentRecord = super::item::from_item(item)?;
+            out.insert(record.comment_id.clone(), record);
+        }
+        Ok(out)
+    }
+
+    /// Edit a comment's or reply's text, replacing its resolved mentions and
+    /// tags. Sets `edited_at`. Tags dropped from `existing` leave the tag index
+    /// and new ones join it, in the same transaction.
+    #[tracing::instrument(
+        skip(self, existing, text, mentions),
+        fields(match_id = %existing.match_id, comment_id = %existing.comment_id)
+    )]
     pub async fn edit_comment(
         &self,
-        match_id: &str,
-        comment_id: &str,
+        existing: &CommentRecord,
         text: &str,
+        mentions: &[CommentMentionRecord],
+        tags: &[String],
         edited_at: &str,
     ) -> DaoResult<()> {
-        self.client
-            .update_item()
+        let sk = match existing.parent_id {
+            Some(_) => Sk::Reply(existing.comment_id.clone()),
+            None => Sk::Comment(existing.comment_id.clone()),
+        };
+        // Empty lists are removed rather than stored, matching how the record
+        // serializes them on create.
+        let mut set = vec!["#t = :t", "edited_at = :e"];
+        let mut remove = Vec::new();
+        let mut update = Update::builder()
             .table_name(self.table())
-            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
-            .key("SK", s(Sk::Comment(comment_id.into()).to_string()))
-            .update_expression("SET #t = :t, edited_at = :e")
+            .key(ATTR_PK, s(Pk::Match(existing.match_id.clone()).to_string()))
+            .key("SK", s(sk.to_string()))
             .condition_expression("attribute_exists(#pk)")
             .expression_attribute_names("#t", "text")
             .expression_attribute_names("#pk", ATTR_PK)
             .expression_attribute_values(":t", s(text))
-            .expression_attribute_values(":e", s(edited_at))
+            .expression_attribute_values(":e", s(edited_at));
+        if mentions.is_empty() {
+            remove.push("mentions");
+        } else {
+            set.push("mentions = :mn");
+            update = update
+                .expression_attribute_values(":mn", serde_dynamo::to_attribute_value(mentions)?);
+        }
+        if tags.is_empty() {
+            remove.push("tags");
+        } else {
+            set.push("tags = :tg");
+            update =
+                update.expression_attribute_values(":tg", serde_dynamo::to_attribute_value(tags)?);
+        }
+        let mut expression = format!("SET {}", set.join(", "));
+        if !remove.is_empty() {
+            expression.push_str(&format!(" REMOVE {}", remove.join(", ")));
+        }
+        let update = update
+            .update_expression(expression)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let added: Vec<String> = tags
+            .iter()
+            .filter(|t| !existing.tags.contains(t))
+            .cloned()
+            .collect();
+        let dropped: Vec<String> = existing
+            .tags
+            .iter()
+            .filter(|t| !tags.contains(t))
+            .cloned()
+            .collect();
+        let mut writes = vec![TransactWriteItem::builder().update(update).build()];
+        writes.extend(self.tag_comment_writes(existing, &added, edited_at)?);
+        writes.extend(self.untag_comment_writes(
+            &existing.match_id,
+            &existing.comment_id,
+            &dropped,
+        )?);
+
+        match self
+            .client
+            .transact_write_items()
+            .set_transact_items(Some(writes))
             .send()
             .await
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-        Ok(())
+        {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => {
+                Err(DaoError::NotFound(format!(
+                    "comment {} on match {}",
+                    existing.comment_id, existing.match_id
+                )))
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
     }
 
     /// Tombstone a top-level comment that has replies (addressed by id): clear
-    /// author/text, set `deleted_at`, keep the row. Does not touch counts. Use
+    /// author/text/mentions/tags, set `deleted_at`, keep the row, and take it
+    /// out of the index for each of its `tags`. Does not touch counts. Use
     /// `delete_comment_hard` for reply-less comments.
     #[tracing::instrument(skip(self))]
     pub async fn tombstone_comment(
         &self,
         match_id: &str,
         comment_id: &str,
+        tags: &[String],
         deleted_at: &str,
     ) -> DaoResult<()> {
-        self.client
-            .update_item()
+        let update = Update::builder()
             .table_name(self.table())
             .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
             .key("SK", s(Sk::Comment(comment_id.into()).to_string()))
-            .update_expression("SET deleted_at = :d REMOVE #t, author_user_id")
+            .update_expression("SET deleted_at = :d REMOVE #t, author_user_id, mentions, tags")
             .expression_attribute_names("#t", "text")
             .expression_attribute_values(":d", s(deleted_at))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let mut writes = vec![TransactWriteItem::builder().update(update).build()];
+        writes.extend(self.untag_comment_writes(match_id, comment_id, tags)?);
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(writes))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Tombstone a reply the same way: clear author/text/mentions/tags, set
+    /// `deleted_at`, keep the row (and the parent's `reply_count`).
+    #[tracing::instrument(skip(self))]
+    pub async fn tombstone_reply(
+        &self,
+        match_id: &str,
+        reply_id: &str,
+        tags: &[String],
+        deleted_at: &str,
+    ) -> DaoResult<()> {
+        let update = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key("SK", s(Sk::Reply(reply_id.into()).to_string()))
+            .update_expression("SET deleted_at = :d REMOVE #t, author_user_id, mentions, tags")
+            .expression_attribute_names("#t", "text")
+            .expression_attribute_values(":d", s(deleted_at))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let mut writes = vec![TransactWriteItem::builder().update(update).build()];
+        writes.extend(self.untag_comment_writes(match_id, reply_id, tags)?);
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(writes))
             .send()
             .await
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
@@ -429,12 +871,17 @@ impl Dao {
     }
 
     /// Hard-delete a reply-less top-level comment (addressed by id); decrements
-    /// `comment_count`. Delete-by-id, not a toggle, so errors with
-    /// `DaoError::NotFound` if the comment doesn't exist — previously this had
-    /// no existence guard at all, so a bogus id would have decremented the
-    /// counter regardless.
+    /// `comment_count` and drops it from the index for each of its `tags`.
+    /// Delete-by-id, not a toggle, so errors with `DaoError::NotFound` if the
+    /// comment doesn't exist — previously this had no existence guard at all,
+    /// so a bogus id would have decremented the counter regardless.
     #[tracing::instrument(skip(self))]
-    pub async fn delete_comment_hard(&self, match_id: &str, comment_id: &str) -> DaoResult<()> {
+    pub async fn delete_comment_hard(
+        &self,
+        match_id: &str,
+        comment_id: &str,
+        tags: &[String],
+    ) -> DaoResult<()> {
         let delete = Delete::builder()
             .table_name(self.table())
             .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
@@ -444,15 +891,17 @@ impl Dao {
             .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
 
+        let mut writes = vec![
+            TransactWriteItem::builder().delete(delete).build(),
+            TransactWriteItem::builder()
+                .update(self.match_counter(match_id, "comment_count", -1)?)
+                .build(),
+        ];
+        writes.extend(self.untag_comment_writes(match_id, comment_id, tags)?);
         let result = self
             .client
             .transact_write_items()
-            .transact_items(TransactWriteItem::builder().delete(delete).build())
-            .transact_items(
-                TransactWriteItem::builder()
-                    .update(self.match_counter(match_id, "comment_count", -1)?)
-                    .build(),
-            )
+            .set_transact_items(Some(writes))
             .send()
             .await;
 
@@ -563,3 +1012,40 @@ impl Dao {
             .map_err(|e| DaoError::Dynamo(e.to_string()))
     }
 }
+
+/// The key of the comment or reply item itself.
+fn comment_sk(comment: &CommentRecord) -> Sk {
+    match comment.parent_id {
+        Some(_) => Sk::Reply(comment.comment_id.clone()),
+        None => Sk::Comment(comment.comment_id.clone()),
+    }
+}
+
+/// Condition that a reaction edge still holds `previous`. An edge without a
+/// `reaction` attribute predates reactions and is a 👍.
+fn previous_reaction_condition(previous: ReactionRecord) -> &'static str {
+    match previous {
+        ReactionRecord::Like => {
+            "attribute_exists(#pk) AND (attribute_not_exists(#r) OR #r = :prev)"
+        }
+        _ => "attribute_exists(#pk) AND #r = :prev",
+    }
+}
+
+/// Narrow a reaction listing to `reaction`, if given.
+fn reaction_filter(
+    query: QueryFluentBuilder,
+    reaction: Option<ReactionRecord>,
+) -> QueryFluentBuilder {
+    let Some(reaction) = reaction else {
+        return query;
+    };
+    let filter = match reaction {
+        ReactionRecord::Like => "attribute_not_exists(#r) OR #r = :r",
+        _ => "#r = :r",
+    };
+    query
+        .filter_expression(filter)
+        .expression_attribute_names("#r", "reaction")
+        .expression_attribute_values(":r", s(reaction.as_str()))
+}
@@ -0,0 +1,1442 @@
+//! Expressions for the in-memory table: parsing and evaluating the condition /
+//! filter / key-condition, update and projection expressions the DAO sends,
+//! against a single item.
+//!
+//! `#name` and `:value` placeholders resolve while parsing, against the
+//! request's `ExpressionAttributeNames` / `ExpressionAttributeValues`. The
+//! [`Scope`] tracks which ones were used, because DynamoDB rejects a request
+//! that supplies a placeholder its expressions never mention. Names written
+//! inline are checked against DynamoDB's reserved words for the same reason:
+//! an expression that only works in memory is worse than useless.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::dao::item::Item;
+
+/// A parse or evaluation failure, worded like DynamoDB's `ValidationException`.
+pub(super) type ExprResult<T> = Result<T, String>;
+
+/// The placeholders one request supplies, and which of them its expressions
+/// have used so far.
+pub(super) struct Scope {
+    names: HashMap<String, String>,
+    values: HashMap<String, AttributeValue>,
+    used_names: HashSet<String>,
+    used_values: HashSet<String>,
+}
+
+impl Scope {
+    pub(super) fn new(
+        names: HashMap<String, String>,
+        values: HashMap<String, AttributeValue>,
+    ) -> Self {
+        Self {
+            names,
+            values,
+            used_names: HashSet::new(),
+            used_values: HashSet::new(),
+        }
+    }
+
+    fn name(&mut self, placeholder: &str) -> ExprResult<String> {
+        let name = self.names.get(placeholder).cloned().ok_or_else(|| {
+            format!(
+                "An expression attribute name used in the document path is not defined; attribute name: {placeholder}"
+            )
+        })?;
+        self.used_names.insert(placeholder.to_string());
+        Ok(name)
+    }
+
+    fn value(&mut self, placeholder: &str) -> ExprResult<AttributeValue> {
+        let value = self.values.get(placeholder).cloned().ok_or_else(|| {
+            format!(
+                "An expression attribute value used in expression is not defined; attribute value: {placeholder}"
+            )
+        })?;
+        self.used_values.insert(placeholder.to_string());
+        Ok(value)
+    }
+
+    /// Fail if a supplied placeholder went unused, as DynamoDB does. Call once
+    /// every expression in the request has been parsed.
+    pub(super) fn finish(&self) -> ExprResult<()> {
+        let mut unused: Vec<&String> = self
+            .names
+            .keys()
+            .filter(|k| !self.used_names.contains(*k))
+            .collect();
+        if !unused.is_empty() {
+            unused.sort();
+            return Err(format!(
+                "Value provided in ExpressionAttributeNames unused in expressions: keys: {{{}}}",
+                join(&unused)
+            ));
+        }
+        let mut unused: Vec<&String> = self
+            .values
+            .keys()
+            .filter(|k| !self.used_values.contains(*k))
+            .collect();
+        if !unused.is_empty() {
+            unused.sort();
+            return Err(format!(
+                "Value provided in ExpressionAttributeValues unused in expressions: keys: {{{}}}",
+                join(&unused)
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn join(keys: &[&String]) -> String {
+    keys.iter()
+        .map(|k| k.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// ── Document paths ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathElem {
+    Attr(String),
+    Index(usize),
+}
+
+/// A document path: a top-level attribute, then map keys and list indexes
+/// (`stats.#sport.wins`, `header_photos[2].hidden_at`).
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Path(Vec<PathElem>);
+
+impl Path {
+    /// The top-level attribute the path starts at.
+    pub(super) fn top(&self) -> &str {
+        match &self.0[0] {
+            PathElem::Attr(name) => name,
+            PathElem::Index(_) => unreachable!("paths start at an attribute name"),
+        }
+    }
+
+    fn get<'a>(&self, item: &'a Item) -> Option<&'a AttributeValue> {
+        let mut value = item.get(self.top())?;
+        for elem in &self.0[1..] {
+            value = child(value, elem)?;
+        }
+        Some(value)
+    }
+
+    /// Write `value` at the path. Every level above the last must already
+    /// exist with the right shape; an index past the end of a list appends.
+    fn set(&self, item: &mut Item, value: AttributeValue) -> ExprResult<()> {
+        let Some((last, parents)) = self.0[1..].split_last() else {
+            item.insert(self.top().to_string(), value);
+            return Ok(());
+        };
+        let invalid = || {
+            "The document path provided in the update expression is invalid for update".to_string()
+        };
+        let mut parent = item.get_mut(self.top()).ok_or_else(invalid)?;
+        for elem in parents {
+            parent = child_mut(parent, elem).ok_or_else(invalid)?;
+        }
+        match (parent, last) {
+            (AttributeValue::M(map), PathElem::Attr(name)) => {
+                map.insert(name.clone(), value);
+            }
+            (AttributeValue::L(list), PathElem::Index(index)) => {
+                if *index < list.len() {
+                    list[*index] = value;
+                } else {
+                    list.push(value);
+                }
+            }
+            _ => return Err(invalid()),
+        }
+        Ok(())
+    }
+
+    /// Remove whatever is at the path; a path that doesn't resolve is a no-op.
+    fn remove(&self, item: &mut Item) {
+        let Some((last, parents)) = self.0[1..].split_last() else {
+            item.remove(self.top());
+            return;
+        };
+        let Some(mut parent) = item.get_mut(self.top()) else {
+            return;
+        };
+        for elem in parents {
+            match child_mut(parent, elem) {
+                Some(next) => parent = next,
+                None => return,
+            }
+        }
+        match (parent, last) {
+            (AttributeValue::M(map), PathElem::Attr(name)) => {
+                map.remove(name);
+            }
+            (AttributeValue::L(list), PathElem::Index(index)) if *index < list.len() => {
+                list.remove(*index);
+            }
+            _ => {}
+        }
+    }
+
+    /// True if one path is a prefix of the other (`a` and `a.b`), which
+    /// DynamoDB won't let one update expression touch both of.
+    fn overlaps(&self, other: &Path) -> bool {
+        let n = self.0.len().min(other.0.len());
+        self.0[..n] == other.0[..n]
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let elems: Vec<String> = self
+            .0
+            .iter()
+            .map(|e| match e {
+                PathElem::Attr(name) => name.clone(),
+                PathElem::Index(i) => format!("[{i}]"),
+            })
+            .collect();
+        write!(f, "[{}]", elems.join(", "))
+    }
+}
+
+fn child<'a>(value: &'a AttributeValue, elem: &PathElem) -> Option<&'a AttributeValue> {
+    match (value, elem) {
+        (AttributeValue::M(map), PathElem::Attr(name)) => map.get(name),
+        (AttributeValue::L(list), PathElem::Index(index)) => list.get(*index),
+        _ => None,
+    }
+}
+
+fn child_mut<'a>(value: &'a mut AttributeValue, elem: &PathElem) -> Option<&'a mut AttributeValue> {
+    match (value, elem) {
+        (AttributeValue::M(map), PathElem::Attr(name)) => map.get_mut(name),
+        (AttributeValue::L(list), PathElem::Index(index)) => list.get_mut(*index),
+        _ => None,
+    }
+}
+
+// ── Tokens and the shared parser ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Word(String),
+    Name(String),
+    Value(String),
+    Index(usize),
+    LParen,
+    RParen,
+    Comma,
+    Dot,
+    Cmp(CmpOp),
+    Plus,
+    Minus,
+}
+
+fn tokenize(expr: &str) -> ExprResult<Vec<Tok>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    let word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let tok = match c {
+            '#' | ':' => {
+                i += 1;
+                while i < chars.len() && word_char(chars[i]) {
+                    i += 1;
+                }
+                if i == start + 1 {
+                    return Err(format!("Invalid syntax: bare '{c}' in expression: {expr}"));
+                }
+                let text: String = chars[start..i].iter().collect();
+                if c == '#' {
+                    Tok::Name(text)
+                } else {
+                    Tok::Value(text)
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while i < chars.len() && word_char(chars[i]) {
+                    i += 1;
+                }
+                Tok::Word(chars[start..i].iter().collect())
+            }
+            '[' => {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let digits: String = chars[start + 1..i].iter().collect();
+                if i >= chars.len() || chars[i] != ']' || digits.is_empty() {
+                    return Err(format!(
+                        "Invalid syntax: bad list index in expression: {expr}"
+                    ));
+                }
+                i += 1;
+                Tok::Index(
+                    digits
+                        .parse()
+                        .map_err(|_| format!("Invalid list index in expression: {expr}"))?,
+                )
+            }
+            _ => {
+                let next = chars.get(i + 1).copied();
+                let (tok, len) = match (c, next) {
+                    ('(', _) => (Tok::LParen, 1),
+                    (')', _) => (Tok::RParen, 1),
+                    (',', _) => (Tok::Comma, 1),
+                    ('.', _) => (Tok::Dot, 1),
+                    ('+', _) => (Tok::Plus, 1),
+                    ('-', _) => (Tok::Minus, 1),
+                    ('=', _) => (Tok::Cmp(CmpOp::Eq), 1),
+                    ('<', Some('>')) => (Tok::Cmp(CmpOp::Ne), 2),
+                    ('<', Some('=')) => (Tok::Cmp(CmpOp::Le), 2),
+                    ('<', _) => (Tok::Cmp(CmpOp::Lt), 1),
+                    ('>', Some('=')) => (Tok::Cmp(CmpOp::Ge), 2),
+                    ('>', _) => (Tok::Cmp(CmpOp::Gt), 1),
+                    _ => {
+                        return Err(format!(
+                            "Invalid syntax: unexpected character '{c}' in expression: {expr}"
+                        ));
+                    }
+                };
+                i += len;
+                tok
+            }
+        };
+        toks.push(tok);
+    }
+    Ok(toks)
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    toks: Vec<Tok>,
+    pos: usize,
+    scope: &'a mut Scope,
+}
+
+impl<'a> Parser<'a> {
+    fn new(expr: &'a str, scope: &'a mut Scope) -> ExprResult<Self> {
+        let toks = tokenize(expr)?;
+        if toks.is_empty() {
+            return Err("Invalid expression: The expression can not be empty".into());
+        }
+        Ok(Self {
+            expr,
+            toks,
+            pos: 0,
+            scope,
+        })
+    }
+
+    fn syntax(&self) -> String {
+        match self.toks.get(self.pos) {
+            Some(tok) => format!(
+                "Invalid syntax: unexpected token {tok:?} in expression: {}",
+                self.expr
+            ),
+            None => format!(
+                "Invalid syntax: unexpected end of expression: {}",
+                self.expr
+            ),
+        }
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Tok> {
+        self.toks.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.toks.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect(&mut self, tok: Tok) -> ExprResult<()> {
+        if self.peek() == Some(&tok) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.syntax())
+        }
+    }
+
+    fn eat(&mut self, tok: &Tok) -> bool {
+        if self.peek() == Some(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.at_keyword(keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// A function call's name, if the next tokens are `name(`.
+    fn at_function(&self) -> Option<String> {
+        match (self.peek(), self.peek_at(1)) {
+            (Some(Tok::Word(w)), Some(Tok::LParen)) => Some(w.to_ascii_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn end(&self) -> ExprResult<()> {
+        if self.pos == self.toks.len() {
+            Ok(())
+        } else {
+            Err(self.syntax())
+        }
+    }
+
+    fn attribute_name(&mut self) -> ExprResult<String> {
+        match self.next() {
+            Some(Tok::Word(word)) => {
+                if is_reserved(&word) {
+                    return Err(format!(
+                        "Attribute name is a reserved keyword; reserved keyword: {word}"
+                    ));
+                }
+                Ok(word)
+            }
+            Some(Tok::Name(placeholder)) => self.scope.name(&placeholder),
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax())
+            }
+        }
+    }
+
+    fn path(&mut self) -> ExprResult<Path> {
+        let mut elems = vec![PathElem::Attr(self.attribute_name()?)];
+        loop {
+            match self.peek() {
+                Some(Tok::Dot) => {
+                    self.pos += 1;
+                    elems.push(PathElem::Attr(self.attribute_name()?));
+                }
+                Some(Tok::Index(index)) => {
+                    elems.push(PathElem::Index(*index));
+                    self.pos += 1;
+                }
+                _ => return Ok(Path(elems)),
+            }
+        }
+    }
+
+    fn value(&mut self) -> ExprResult<AttributeValue> {
+        match self.next() {
+            Some(Tok::Value(placeholder)) => self.scope.value(&placeholder),
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax())
+            }
+        }
+    }
+}
+
+// ── Conditions (condition, filter and key-condition expressions) ──────────
+
+/// An operand of a comparison or function.
+#[derive(Debug, Clone)]
+pub(super) enum Operand {
+    Path(Path),
+    Value(AttributeValue),
+    Size(Path),
+}
+
+impl Operand {
+    fn resolve(&self, item: &Item) -> Option<AttributeValue> {
+        match self {
+            Operand::Path(path) => path.get(item).cloned(),
+            Operand::Value(value) => Some(value.clone()),
+            Operand::Size(path) => size(path.get(item)?).map(|n| AttributeValue::N(n.to_string())),
+        }
+    }
+
+    fn top(&self) -> Option<&str> {
+        match self {
+            Operand::Path(path) | Operand::Size(path) => Some(path.top()),
+            Operand::Value(_) => None,
+        }
+    }
+}
+
+/// A parsed condition, filter or key-condition expression.
+#[derive(Debug, Clone)]
+pub(super) enum Condition {
+    Compare(Operand, CmpOp, Operand),
+    Between(Operand, Operand, Operand),
+    In(Operand, Vec<Operand>),
+    Exists(Path),
+    NotExists(Path),
+    Type(Path, String),
+    BeginsWith(Operand, Operand),
+    Contains(Operand, Operand),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+/// Parse a condition (or filter) expression.
+pub(super) fn parse_condition(expr: &str, scope: &mut Scope) -> ExprResult<Condition> {
+    let mut parser = Parser::new(expr, scope)?;
+    let condition = parser.or()?;
+    parser.end()?;
+    Ok(condition)
+}
+
+/// Parse a Query's key-condition expression and check it has the only shape
+/// DynamoDB allows: equality on the partition key `hash`, optionally AND one
+/// condition on the sort key `range`.
+pub(super) fn parse_key_condition(
+    expr: &str,
+    scope: &mut Scope,
+    hash: &str,
+    range: &str,
+) -> ExprResult<Condition> {
+    let condition = parse_condition(expr, scope)?;
+    let terms = match &condition {
+        Condition::And(a, b) => vec![a.as_ref(), b.as_ref()],
+        other => vec![other],
+    };
+    let is_key = |operand: &Operand, key: &str| matches!(operand, Operand::Path(p) if p.0 == [PathElem::Attr(key.to_string())]);
+    let is_value = |operand: &Operand| matches!(operand, Operand::Value(_));
+    let mut has_hash = false;
+    for term in terms {
+        let ok = match term {
+            Condition::Compare(left, CmpOp::Eq, right) if is_key(left, hash) && is_value(right) => {
+                if has_hash {
+                    false
+                } else {
+                    has_hash = true;
+                    true
+                }
+            }
+            Condition::Compare(left, op, right) => {
+                *op != CmpOp::Ne && is_key(left, range) && is_value(right)
+            }
+            Condition::Between(operand, lo, hi) => {
+                is_key(operand, range) && is_value(lo) && is_value(hi)
+            }
+            Condition::BeginsWith(operand, prefix) => is_key(operand, range) && is_value(prefix),
+            _ => false,
+        };
+        if !ok {
+            return Err(format!("Query key condition not supported: {expr}"));
+        }
+    }
+    if !has_hash {
+        return Err(format!("Query condition missed key schema element: {hash}"));
+    }
+    Ok(condition)
+}
+
+impl Parser<'_> {
+    fn or(&mut self) -> ExprResult<Condition> {
+        let mut left = self.and()?;
+        while self.eat_keyword("OR") {
+            let right = self.and()?;
+            left = Condition::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> ExprResult<Condition> {
+        let mut left = self.not()?;
+        while self.eat_keyword("AND") {
+            let right = self.not()?;
+            left = Condition::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> ExprResult<Condition> {
+        if self.eat_keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> ExprResult<Condition> {
+        if self.eat(&Tok::LParen) {
+            let condition = self.or()?;
+            self.expect(Tok::RParen)?;
+            return Ok(condition);
+        }
+        if let Some(function) = self.at_function()
+            && function != "size"
+        {
+            return self.function(&function);
+        }
+        let left = self.operand()?;
+        match self.next() {
+            Some(Tok::Cmp(op)) => Ok(Condition::Compare(left, op, self.operand()?)),
+            Some(Tok::Word(w)) if w.eq_ignore_ascii_case("BETWEEN") => {
+                let lo = self.operand()?;
+                if !self.eat_keyword("AND") {
+                    return Err(self.syntax());
+                }
+                let hi = self.operand()?;
+                Ok(Condition::Between(left, lo, hi))
+            }
+            Some(Tok::Word(w)) if w.eq_ignore_ascii_case("IN") => {
+                self.expect(Tok::LParen)?;
+                let mut options = vec![self.operand()?];
+                while self.eat(&Tok::Comma) {
+                    options.push(self.operand()?);
+                }
+                self.expect(Tok::RParen)?;
+                Ok(Condition::In(left, options))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax())
+            }
+        }
+    }
+
+    fn function(&mut self, name: &str) -> ExprResult<Condition> {
+        self.pos += 2; // name and `(`
+        let condition = match name {
+            "attribute_exists" => Condition::Exists(self.path()?),
+            "attribute_not_exists" => Condition::NotExists(self.path()?),
+            "attribute_type" => {
+                let path = self.path()?;
+                self.expect(Tok::Comma)?;
+                let type_code = match self.value()? {
+                    AttributeValue::S(code) if TYPE_CODES.contains(&code.as_str()) => code,
+                    _ => {
+                        return Err("Invalid attribute type name found in type descriptor".into());
+                    }
+                };
+                Condition::Type(path, type_code)
+            }
+            "begins_with" => {
+                let operand = self.operand()?;
+                self.expect(Tok::Comma)?;
+                Condition::BeginsWith(operand, self.operand()?)
+            }
+            "contains" => {
+                let operand = self.operand()?;
+                self.expect(Tok::Comma)?;
+                Condition::Contains(operand, self.operand()?)
+            }
+            other => {
+                return Err(format!("Invalid function name; function: {other}"));
+            }
+        };
+        self.expect(Tok::RParen)?;
+        Ok(condition)
+    }
+
+    fn operand(&mut self) -> ExprResult<Operand> {
+        if self.at_function().as_deref() == Some("size") {
+            self.pos += 2;
+            let path = self.path()?;
+            self.expect(Tok::RParen)?;
+            return Ok(Operand::Size(path));
+        }
+        if matches!(self.peek(), Some(Tok::Value(_))) {
+            return Ok(Operand::Value(self.value()?));
+        }
+        Ok(Operand::Path(self.path()?))
+    }
+}
+
+const TYPE_CODES: [&str; 10] = ["S", "SS", "N", "NS", "B", "BS", "BOOL", "NULL", "L", "M"];
+
+impl Condition {
+    /// Evaluate against `item` (an empty map for an item that doesn't exist).
+    /// A comparison with a missing operand is false, as in DynamoDB.
+    pub(super) fn eval(&self, item: &Item) -> bool {
+        match self {
+            Condition::Compare(left, op, right) => {
+                let (Some(a), Some(b)) = (left.resolve(item), right.resolve(item)) else {
+                    return false;
+                };
+                match op {
+                    CmpOp::Eq => values_equal(&a, &b),
+                    CmpOp::Ne => !values_equal(&a, &b),
+                    _ => match compare_values(&a, &b) {
+                        Some(ord) => match op {
+                            CmpOp::Lt => ord == Ordering::Less,
+                            CmpOp::Le => ord != Ordering::Greater,
+                            CmpOp::Gt => ord == Ordering::Greater,
+                            CmpOp::Ge => ord != Ordering::Less,
+                            CmpOp::Eq | CmpOp::Ne => unreachable!(),
+                        },
+                        None => false,
+                    },
+                }
+            }
+            Condition::Between(operand, lo, hi) => {
+                let (Some(v), Some(lo), Some(hi)) =
+                    (operand.resolve(item), lo.resolve(item), hi.resolve(item))
+                else {
+                    return false;
+                };
+                compare_values(&lo, &v).is_some_and(|o| o != Ordering::Greater)
+                    && compare_values(&v, &hi).is_some_and(|o| o != Ordering::Greater)
+            }
+            Condition::In(operand, options) => {
+                let Some(v) = operand.resolve(item) else {
+                    return false;
+                };
+                options
+                    .iter()
+                    .filter_map(|o| o.resolve(item))
+                    .any(|o| values_equal(&v, &o))
+            }
+            Condition::Exists(path) => path.get(item).is_some(),
+            Condition::NotExists(path) => path.get(item).is_none(),
+            Condition::Type(path, code) => path.get(item).is_some_and(|v| type_code(v) == code),
+            Condition::BeginsWith(operand, prefix) => {
+                match (operand.resolve(item), prefix.resolve(item)) {
+                    (Some(AttributeValue::S(s)), Some(AttributeValue::S(p))) => s.starts_with(&p),
+                    (Some(AttributeValue::B(b)), Some(AttributeValue::B(p))) => {
+                        b.as_ref().starts_with(p.as_ref())
+                    }
+                    _ => false,
+                }
+            }
+            Condition::Contains(operand, needle) => {
+                match (operand.resolve(item), needle.resolve(item)) {
+                    (Some(AttributeValue::S(s)), Some(AttributeValue::S(n))) => s.contains(&n),
+                    (Some(AttributeValue::B(b)), Some(AttributeValue::B(n))) => {
+                        let (b, n) = (b.as_ref(), n.as_ref());
+                        n.is_empty() || b.windows(n.len()).any(|w| w == n)
+                    }
+                    (Some(AttributeValue::Ss(set)), Some(AttributeValue::S(n))) => set.contains(&n),
+                    (Some(AttributeValue::Ns(set)), Some(AttributeValue::N(n))) => {
+                        set.iter().any(|x| num_cmp(x, &n) == Some(Ordering::Equal))
+                    }
+                    (Some(AttributeValue::Bs(set)), Some(AttributeValue::B(n))) => set.contains(&n),
+                    (Some(AttributeValue::L(list)), Some(n)) => {
+                        list.iter().any(|x| values_equal(x, &n))
+                    }
+                    _ => false,
+                }
+            }
+            Condition::And(a, b) => a.eval(item) && b.eval(item),
+            Condition::Or(a, b) => a.eval(item) || b.eval(item),
+            Condition::Not(c) => !c.eval(item),
+        }
+    }
+
+    /// The top-level attributes the condition reads.
+    pub(super) fn attributes(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_attributes(&mut out);
+        out
+    }
+
+    fn collect_attributes<'a>(&'a self, out: &mut Vec<&'a str>) {
+        let mut operands = |ops: &[&'a Operand]| out.extend(ops.iter().filter_map(|o| o.top()));
+        match self {
+            Condition::Compare(a, _, b)
+            | Condition::BeginsWith(a, b)
+            | Condition::Contains(a, b) => operands(&[a, b]),
+            Condition::Between(a, b, c) => operands(&[a, b, c]),
+            Condition::In(a, options) => {
+                operands(&[a]);
+                out.extend(options.iter().filter_map(|o| o.top()));
+            }
+            Condition::Exists(path) | Condition::NotExists(path) | Condition::Type(path, _) => {
+                out.push(path.top())
+            }
+            Condition::And(a, b) | Condition::Or(a, b) => {
+                a.collect_attributes(out);
+                b.collect_attributes(out);
+            }
+            Condition::Not(c) => c.collect_attributes(out),
+        }
+    }
+}
+
+// ── Update expressions ─────────────────────────────────────────────────────
+
+/// The right-hand side of a `SET` action.
+#[derive(Debug, Clone)]
+enum SetValue {
+    Path(Path),
+    Value(AttributeValue),
+    IfNotExists(Path, Box<SetValue>),
+    ListAppend(Box<SetValue>, Box<SetValue>),
+    Plus(Box<SetValue>, Box<SetValue>),
+    Minus(Box<SetValue>, Box<SetValue>),
+}
+
+#[derive(Debug, Clone)]
+enum UpdateAction {
+    Set(Path, SetValue),
+    Remove(Path),
+    Add(Path, AttributeValue),
+    Delete(Path, AttributeValue),
+}
+
+impl UpdateAction {
+    fn path(&self) -> &Path {
+        match self {
+            UpdateAction::Set(path, _)
+            | UpdateAction::Remove(path)
+            | UpdateAction::Add(path, _)
+            | UpdateAction::Delete(path, _) => path,
+        }
+    }
+}
+
+/// A parsed update expression.
+#[derive(Debug, Clone)]
+pub(super) struct Update(Vec<UpdateAction>);
+
+/// Parse an update expression: any of the `SET`, `REMOVE`, `ADD` and
+/// `DELETE` clauses, each at most once, in any order.
+pub(super) fn parse_update(expr: &str, scope: &mut Scope) -> ExprResult<Update> {
+    let mut parser = Parser::new(expr, scope)?;
+    let mut actions = Vec::new();
+    let mut seen = HashSet::new();
+    while parser.peek().is_some() {
+        let clause = match parser.next() {
+            Some(Tok::Word(w)) => w.to_ascii_uppercase(),
+            _ => {
+                parser.pos -= 1;
+                return Err(parser.syntax());
+            }
+        };
+        if !matches!(clause.as_str(), "SET" | "REMOVE" | "ADD" | "DELETE") {
+            parser.pos -= 1;
+            return Err(parser.syntax());
+        }
+        if !seen.insert(clause.clone()) {
+            return Err(format!(
+                "Invalid UpdateExpression: The \"{clause}\" section can only be used once in an update expression"
+            ));
+        }
+        loop {
+            let path = parser.path()?;
+            let action = match clause.as_str() {
+                "SET" => {
+                    parser.expect(Tok::Cmp(CmpOp::Eq))?;
+                    UpdateAction::Set(path, parser.set_value()?)
+                }
+                "REMOVE" => UpdateAction::Remove(path),
+                "ADD" => {
+                    let value = parser.value()?;
+                    if !matches!(
+                        value,
+                        AttributeValue::N(_)
+                            | AttributeValue::Ss(_)
+                            | AttributeValue::Ns(_)
+                            | AttributeValue::Bs(_)
+                    ) {
+                        return Err(
+                            "Invalid UpdateExpression: Incorrect operand type for operator or function; operator: ADD".into(),
+                        );
+                    }
+                    UpdateAction::Add(path, value)
+                }
+                _ => {
+                    let value = parser.value()?;
+                    if !matches!(
+                        value,
+                        AttributeValue::Ss(_) | AttributeValue::Ns(_) | AttributeValue::Bs(_)
+                    ) {
+                        return Err(
+                            "Invalid UpdateExpression: Incorrect operand type for operator or function; operator: DELETE".into(),
+                        );
+                    }
+                    UpdateAction::Delete(path, value)
+                }
+            };
+            actions.push(action);
+            if !parser.eat(&Tok::Comma) {
+                break;
+            }
+        }
+    }
+    for (i, a) in actions.iter().enumerate() {
+        for b in &actions[i + 1..] {
+            if a.path().overlaps(b.path()) {
+                return Err(format!(
+                    "Invalid UpdateExpression: Two document paths overlap with each other; must remove or rewrite one of these paths; path one: {}, path two: {}",
+                    a.path(),
+                    b.path()
+                ));
+            }
+        }
+    }
+    Ok(Update(actions))
+}
+
+impl Parser<'_> {
+    fn set_value(&mut self) -> ExprResult<SetValue> {
+        let left = self.set_operand()?;
+        if self.eat(&Tok::Plus) {
+            return Ok(SetValue::Plus(
+                Box::new(left),
+                Box::new(self.set_operand()?),
+            ));
+        }
+        if self.eat(&Tok::Minus) {
+            return Ok(SetValue::Minus(
+                Box::new(left),
+                Box::new(self.set_operand()?),
+            ));
+        }
+        Ok(left)
+    }
+
+    fn set_operand(&mut self) -> ExprResult<SetValue> {
+        match self.at_function().as_deref() {
+            Some("if_not_exists") => {
+                self.pos += 2;
+                let path = self.path()?;
+                self.expect(Tok::Comma)?;
+                let fallback = self.set_value()?;
+                self.expect(Tok::RParen)?;
+                Ok(SetValue::IfNotExists(path, Box::new(fallback)))
+            }
+            Some("list_append") => {
+                self.pos += 2;
+                let a = self.set_value()?;
+                self.expect(Tok::Comma)?;
+                let b = self.set_value()?;
+                self.expect(Tok::RParen)?;
+                Ok(SetValue::ListAppend(Box::new(a), Box::new(b)))
+            }
+            Some(other) => Err(format!(
+                "Invalid UpdateExpression: Invalid function name; function: {other}"
+            )),
+            None if matches!(self.peek(), Some(Tok::Value(_))) => {
+                Ok(SetValue::Value(self.value()?))
+            }
+            None => Ok(SetValue::Path(self.path()?)),
+        }
+    }
+}
+
+impl SetValue {
+    fn eval(&self, item: &Item) -> ExprResult<AttributeValue> {
+        match self {
+            SetValue::Path(path) => path.get(item).cloned().ok_or_else(|| {
+                "The provided expression refers to an attribute that does not exist in the item"
+                    .to_string()
+            }),
+            SetValue::Value(value) => Ok(value.clone()),
+            SetValue::IfNotExists(path, fallback) => match path.get(item) {
+                Some(value) => Ok(value.clone()),
+                None => fallback.eval(item),
+            },
+            SetValue::ListAppend(a, b) => match (a.eval(item)?, b.eval(item)?) {
+                (AttributeValue::L(mut a), AttributeValue::L(b)) => {
+                    a.extend(b);
+                    Ok(AttributeValue::L(a))
+                }
+                _ => Err(
+                    "Invalid UpdateExpression: Incorrect operand type for operator or function; operator or function: list_append".into(),
+                ),
+            },
+            SetValue::Plus(a, b) | SetValue::Minus(a, b) => {
+                let op = if matches!(self, SetValue::Plus(..)) { '+' } else { '-' };
+                match (a.eval(item)?, b.eval(item)?) {
+                    (AttributeValue::N(a), AttributeValue::N(b)) => {
+                        Ok(AttributeValue::N(num_arith(&a, &b, op == '-')?))
+                    }
+                    _ => Err(format!(
+                        "Invalid UpdateExpression: Incorrect operand type for operator or function; operator or function: {op}"
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl Update {
+    /// True if any action writes the top-level attribute `name`.
+    pub(super) fn touches(&self, name: &str) -> bool {
+        self.0.iter().any(|a| a.path().top() == name)
+    }
+
+    /// The item after the update. Every right-hand side reads `item` as it
+    /// was before any action applied, as DynamoDB evaluates them.
+    pub(super) fn apply(&self, item: &Item) -> ExprResult<Item> {
+        let mut out = item.clone();
+        for action in &self.0 {
+            match action {
+                UpdateAction::Set(path, value) => path.set(&mut out, value.eval(item)?)?,
+                UpdateAction::Remove(path) => path.remove(&mut out),
+                UpdateAction::Add(path, value) => {
+                    let sum = match (path.get(item), value) {
+                        (None, value) => value.clone(),
+                        (Some(AttributeValue::N(a)), AttributeValue::N(b)) => {
+                            AttributeValue::N(num_arith(a, b, false)?)
+                        }
+                        (Some(AttributeValue::Ss(a)), AttributeValue::Ss(b)) => {
+                            AttributeValue::Ss(union(a, b))
+                        }
+                        (Some(AttributeValue::Ns(a)), AttributeValue::Ns(b)) => {
+                            AttributeValue::Ns(union(a, b))
+                        }
+                        (Some(AttributeValue::Bs(a)), AttributeValue::Bs(b)) => {
+                            AttributeValue::Bs(union(a, b))
+                        }
+                        _ => {
+                            return Err(
+                                "An operand in the update expression has an incorrect data type"
+                                    .into(),
+                            );
+                        }
+                    };
+                    path.set(&mut out, sum)?;
+                }
+                UpdateAction::Delete(path, value) => {
+                    let rest = match (path.get(item), value) {
+                        (None, _) => continue,
+                        (Some(AttributeValue::Ss(a)), AttributeValue::Ss(b)) => {
+                            difference(a, b).map(AttributeValue::Ss)
+                        }
+                        (Some(AttributeValue::Ns(a)), AttributeValue::Ns(b)) => {
+                            difference(a, b).map(AttributeValue::Ns)
+                        }
+                        (Some(AttributeValue::Bs(a)), AttributeValue::Bs(b)) => {
+                            difference(a, b).map(AttributeValue::Bs)
+                        }
+                        _ => {
+                            return Err(
+                                "An operand in the update expression has an incorrect data type"
+                                    .into(),
+                            );
+                        }
+                    };
+                    match rest {
+                        Some(rest) => path.set(&mut out, rest)?,
+                        None => path.remove(&mut out),
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn union<T: Clone + PartialEq>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut out = a.to_vec();
+    for x in b {
+        if !out.contains(x) {
+            out.push(x.clone());
+        }
+    }
+    out
+}
+
+/// `a` without `b`'s members, or `None` if nothing's left (DynamoDB has no
+/// empty sets, so the attribute goes).
+fn difference<T: Clone + PartialEq>(a: &[T], b: &[T]) -> Option<Vec<T>> {
+    let rest: Vec<T> = a.iter().filter(|x| !b.contains(x)).cloned().collect();
+    (!rest.is_empty()).then_some(rest)
+}
+
+// ── Projections ────────────────────────────────────────────────────────────
+
+/// A parsed projection expression.
+#[derive(Debug, Clone)]
+pub(super) struct Projection(Vec<Path>);
+
+pub(super) fn parse_projection(expr: &str, scope: &mut Scope) -> ExprResult<Projection> {
+    let mut parser = Parser::new(expr, scope)?;
+    let mut paths = vec![parser.path()?];
+    while parser.eat(&Tok::Comma) {
+        paths.push(parser.path()?);
+    }
+    parser.end()?;
+    Ok(Projection(paths))
+}
+
+impl Projection {
+    /// Just the projected parts of `item`, nested the way they were.
+    pub(super) fn apply(&self, item: &Item) -> Item {
+        let mut out = Item::new();
+        for path in &self.0 {
+            let Some(value) = path.get(item) else {
+                continue;
+            };
+            let rest = &path.0[1..];
+            let Some(first) = rest.first() else {
+                out.insert(path.top().to_string(), value.clone());
+                continue;
+            };
+            let slot = out
+                .entry(path.top().to_string())
+                .or_insert_with(|| container_for(first));
+            insert_projected(slot, rest, value.clone());
+        }
+        out
+    }
+}
+
+fn container_for(elem: &PathElem) -> AttributeValue {
+    match elem {
+        PathElem::Attr(_) => AttributeValue::M(HashMap::new()),
+        PathElem::Index(_) => AttributeValue::L(Vec::new()),
+    }
+}
+
+fn insert_projected(slot: &mut AttributeValue, path: &[PathElem], value: AttributeValue) {
+    let (head, rest) = path.split_first().expect("non-empty projected path");
+    let next = match (slot, head) {
+        (AttributeValue::M(map), PathElem::Attr(name)) => {
+            if rest.is_empty() {
+                map.insert(name.clone(), value);
+                return;
+            }
+            map.entry(name.clone())
+                .or_insert_with(|| container_for(&rest[0]))
+        }
+        (AttributeValue::L(list), PathElem::Index(_)) => {
+            if rest.is_empty() {
+                list.push(value);
+                return;
+            }
+            list.push(container_for(&rest[0]));
+            list.last_mut().expect("just pushed")
+        }
+        _ => return,
+    };
+    insert_projected(next, rest, value)
+}
+
+// ── Values ─────────────────────────────────────────────────────────────────
+
+fn type_code(value: &AttributeValue) -> &'static str {
+    match value {
+        AttributeValue::S(_) => "S",
+        AttributeValue::Ss(_) => "SS",
+        AttributeValue::N(_) => "N",
+        AttributeValue::Ns(_) => "NS",
+        AttributeValue::B(_) => "B",
+        AttributeValue::Bs(_) => "BS",
+        AttributeValue::Bool(_) => "BOOL",
+        AttributeValue::Null(_) => "NULL",
+        AttributeValue::L(_) => "L",
+        AttributeValue::M(_) => "M",
+        _ => "",
+    }
+}
+
+fn size(value: &AttributeValue) -> Option<usize> {
+    match value {
+        AttributeValue::S(s) => Some(s.chars().count()),
+        AttributeValue::B(b) => Some(b.as_ref().len()),
+        AttributeValue::Ss(set) => Some(set.len()),
+        AttributeValue::Ns(set) => Some(set.len()),
+        AttributeValue::Bs(set) => Some(set.len()),
+        AttributeValue::L(list) => Some(list.len()),
+        AttributeValue::M(map) => Some(map.len()),
+        _ => None,
+    }
+}
+
+/// DynamoDB equality: numbers compare by value, sets ignore order, and
+/// values of different types are never equal.
+pub(super) fn values_equal(a: &AttributeValue, b: &AttributeValue) -> bool {
+    match (a, b) {
+        (AttributeValue::N(x), AttributeValue::N(y)) => num_cmp(x, y) == Some(Ordering::Equal),
+        (AttributeValue::Ss(x), AttributeValue::Ss(y)) => {
+            x.len() == y.len() && x.iter().all(|v| y.contains(v))
+        }
+        (AttributeValue::Ns(x), AttributeValue::Ns(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|v| y.iter().any(|w| num_cmp(v, w) == Some(Ordering::Equal)))
+        }
+        (AttributeValue::Bs(x), AttributeValue::Bs(y)) => {
+            x.len() == y.len() && x.iter().all(|v| y.contains(v))
+        }
+        (AttributeValue::L(x), AttributeValue::L(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(v, w)| values_equal(v, w))
+        }
+        (AttributeValue::M(x), AttributeValue::M(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| values_equal(v, w)))
+        }
+        (AttributeValue::S(_), AttributeValue::S(_))
+        | (AttributeValue::B(_), AttributeValue::B(_))
+        | (AttributeValue::Bool(_), AttributeValue::Bool(_))
+        | (AttributeValue::Null(_), AttributeValue::Null(_)) => a == b,
+        _ => false,
+    }
+}
+
+/// Ordering for `<`, `BETWEEN` and friends: numbers by value, strings and
+/// binary bytewise. `None` for any other pairing, which compares false.
+fn compare_values(a: &AttributeValue, b: &AttributeValue) -> Option<Ordering> {
+    match (a, b) {
+        (AttributeValue::N(x), AttributeValue::N(y)) => num_cmp(x, y),
+        (AttributeValue::S(x), AttributeValue::S(y)) => Some(x.as_bytes().cmp(y.as_bytes())),
+        (AttributeValue::B(x), AttributeValue::B(y)) => Some(x.as_ref().cmp(y.as_ref())),
+        _ => None,
+    }
+}
+
+/// Compare two DynamoDB numbers: exactly when both are integers (every
+/// counter and timestamp the DAO stores), as floats otherwise.
+fn num_cmp(a: &str, b: &str) -> Option<Ordering> {
+    match (a.parse::<i128>(), b.parse::<i128>()) {
+        (Ok(x), Ok(y)) => Some(x.cmp(&y)),
+        _ => a.parse::<f64>().ok()?.partial_cmp(&b.parse::<f64>().ok()?),
+    }
+}
+
+fn num_arith(a: &str, b: &str, subtract: bool) -> ExprResult<String> {
+    let invalid = || format!("A value provided cannot be converted into a number: {a} / {b}");
+    if let (Ok(x), Ok(y)) = (a.parse::<i128>(), b.parse::<i128>()) {
+        let sum = if subtract {
+            x.checked_sub(y)
+        } else {
+            x.checked_add(y)
+        };
+        return sum
+            .map(|n| n.to_string())
+            .ok_or_else(|| "Number overflow. Attempting to store a number with magnitude larger than supported range".to_string());
+    }
+    let x: f64 = a.parse().map_err(|_| invalid())?;
+    let y: f64 = b.parse().map_err(|_| invalid())?;
+    Ok(if subtract { x - y } else { x + y }.to_string())
+}
+
+// ── Reserved words ─────────────────────────────────────────────────────────
+
+/// True if `word` is one of DynamoDB's reserved words, which can only be
+/// used in an expression through a `#name` placeholder.
+fn is_reserved(word: &str) -> bool {
+    RESERVED_WORDS
+        .binary_search(&word.to_ascii_uppercase().as_str())
+        .is_ok()
+}
+
+/// DynamoDB's reserved words, sorted for [`is_reserved`]'s binary search.
+#[rustfmt::skip]
+const RESERVED_WORDS: &[&str] = &[
+    "ABORT", "ABSOLUTE", "ACTION", "ADD", "AFTER", "AGENT", "AGGREGATE", "ALL", "ALLOCATE", "ALTER",
+    "ANALYZE", "AND", "ANY", "ARCHIVE", "ARE", "ARRAY", "AS", "ASC", "ASCII", "ASENSITIVE",
+    "ASSERTION", "ASYMMETRIC", "AT", "ATOMIC", "ATTACH", "ATTRIBUTE", "AUTH", "AUTHORIZATION",
+    "AUTHORIZE", "AUTO", "AVG", "BACK", "BACKUP", "BASE", "BATCH", "BEFORE", "BEGIN", "BETWEEN",
+    "BIGINT", "BINARY", "BIT", "BLOB", "BLOCK", "BOOLEAN", "BOTH", "BREADTH", "BUCKET", "BULK",
+    "BY", "BYTE", "CALL", "CALLED", "CALLING", "CAPACITY", "CASCADE", "CASCADED", "CASE", "CAST",
+    "CATALOG", "CHAR", "CHARACTER", "CHECK", "CLASS", "CLOB", "CLOSE", "CLUSTER", "CLUSTERED",
+    "CLUSTERING", "CLUSTERS", "COALESCE", "COLLATE", "COLLATION", "COLLECTION", "COLUMN", "COLUMNS",
+    "COMBINE", "COMMENT", "COMMIT", "COMPACT", "COMPILE", "COMPRESS", "CONDITION", "CONFLICT",
+    "CONNECT", "CONNECTION", "CONSISTENCY", "CONSISTENT", "CONSTRAINT", "CONSTRAINTS",
+    "CONSTRUCTOR", "CONSUMED", "CONTINUE", "CONVERT", "COPY", "CORRESPONDING", "COUNT", "COUNTER",
+    "CREATE", "CROSS", "CUBE", "CURRENT", "CURSOR", "CYCLE", "DATA", "DATABASE", "DATE", "DATETIME",
+    "DAY", "DEALLOCATE", "DEC", "DECIMAL", "DECLARE", "DEFAULT", "DEFERRABLE", "DEFERRED", "DEFINE",
+    "DEFINED", "DEFINITION", "DELETE", "DELIMITED", "DEPTH", "DEREF", "DESC", "DESCRIBE",
+    "DESCRIPTOR", "DETACH", "DETERMINISTIC", "DIAGNOSTICS", "DIRECTORIES", "DISABLE", "DISCONNECT",
+    "DISTINCT", "DISTRIBUTE", "DO", "DOMAIN", "DOUBLE", "DROP", "DUMP", "DURATION", "DYNAMIC",
+    "EACH", "ELEMENT", "ELSE", "ELSEIF", "EMPTY", "ENABLE", "END", "EQUAL", "EQUALS", "ERROR",
+    "ESCAPE", "ESCAPED", "EVAL", "EVALUATE", "EXCEEDED", "EXCEPT", "EXCEPTION", "EXCEPTIONS",
+    "EXCLUSIVE", "EXEC", "EXECUTE", "EXISTS", "EXIT", "EXPLAIN", "EXPLODE", "EXPORT", "EXPRESSION",
+    "EXTENDED", "EXTERNAL", "EXTRACT", "FAIL", "FALSE", "FAMILY", "FETCH", "FIELDS", "FILE",
+    "FILTER", "FILTERING", "FINAL", "FINISH", "FIRST", "FIXED", "FLATTERN", "FLOAT", "FOR", "FORCE",
+    "FOREIGN", "FORMAT", "FORWARD", "FOUND", "FREE", "FROM", "FULL", "FUNCTION", "FUNCTIONS",
+    "GENERAL", "GENERATE", "GET", "GLOB", "GLOBAL", "GO", "GOTO", "GRANT", "GREATER", "GROUP",
+    "GROUPING", "HANDLER", "HASH", "HAVE", "HAVING", "HEAP", "HIDDEN", "HOLD", "HOUR", "IDENTIFIED",
+    "IDENTITY", "IF", "IGNORE", "IMMEDIATE", "IMPORT", "IN", "INCLUDING", "INCLUSIVE", "INCREMENT",
+    "INCREMENTAL", "INDEX", "INDEXED", "INDEXES", "INDICATOR", "INFINITE", "INITIALLY", "INLINE",
+    "INNER", "INNTER", "INOUT", "INPUT", "INSENSITIVE", "INSERT", "INSTEAD", "INT", "INTEGER",
+    "INTERSECT", "INTERVAL", "INTO", "INVALIDATE", "IS", "ISOLATION", "ITEM", "ITEMS", "ITERATE",
+    "JOIN", "KEY", "KEYS", "LAG", "LANGUAGE", "LARGE", "LAST", "LATERAL", "LEAD", "LEADING",
+    "LEAVE", "LEFT", "LENGTH", "LESS", "LEVEL", "LIKE", "LIMIT", "LIMITED", "LINES", "LIST", "LOAD",
+    "LOCAL", "LOCALTIME", "LOCALTIMESTAMP", "LOCATION", "LOCATOR", "LOCK", "LOCKS", "LOG", "LOGED",
+    "LONG", "LOOP", "LOWER", "MAP", "MATCH", "MATERIALIZED", "MAX", "MAXLEN", "MEMBER", "MERGE",
+    "METHOD", "METRICS", "MIN", "MINUS", "MINUTE", "MISSING", "MOD", "MODE", "MODIFIES", "MODIFY",
+    "MODULE", "MONTH", "MULTI", "MULTISET", "NAME", "NAMES", "NATIONAL", "NATURAL", "NCHAR",
+    "NCLOB", "NEW", "NEXT", "NO", "NONE", "NOT", "NULL", "NULLIF", "NUMBER", "NUMERIC", "OBJECT",
+    "OF", "OFFLINE", "OFFSET", "OLD", "ON", "ONLINE", "ONLY", "OPAQUE", "OPEN", "OPERATOR",
+    "OPTION", "OR", "ORDER", "ORDINALITY", "OTHER", "OTHERS", "OUT", "OUTER", "OUTPUT", "OVER",
+    "OVERLAPS", "OVERRIDE", "OWNER", "PAD", "PARALLEL", "PARAMETER", "PARAMETERS", "PARTIAL",
+    "PARTITION", "PARTITIONED", "PARTITIONS", "PATH", "PERCENT", "PERCENTILE", "PERMISSION",
+    "PERMISSIONS", "PIPE", "PIPELINED", "PLAN", "POOL", "POSITION", "PRECISION", "PREPARE",
+    "PRESERVE", "PRIMARY", "PRIOR", "PRIVATE", "PRIVILEGES", "PROCEDURE", "PROCESSED", "PROJECT",
+    "PROJECTION", "PROPERTY", "PROVISIONING", "PUBLIC", "PUT", "QUERY", "QUIT", "QUORUM", "RAISE",
+    "RANDOM", "RANGE", "RANK", "RAW", "READ", "READS", "REAL", "REBUILD", "RECORD", "RECURSIVE",
+    "REDUCE", "REF", "REFERENCE", "REFERENCES", "REFERENCING", "REGEXP", "REGION", "REINDEX",
+    "RELATIVE", "RELEASE", "REMAINDER", "RENAME", "REPEAT", "REPLACE", "REQUEST", "RESET",
+    "RESIGNAL", "RESOURCE", "RESPONSE", "RESTORE", "RESTRICT", "RESULT", "RETURN", "RETURNING",
+    "RETURNS", "REVERSE", "REVOKE", "RIGHT", "ROLE", "ROLES", "ROLLBACK", "ROLLUP", "ROUTINE",
+    "ROW", "ROWS", "RULE", "RULES", "SAMPLE", "SATISFIES", "SAVE", "SAVEPOINT", "SCAN", "SCHEMA",
+    "SCOPE", "SCROLL", "SEARCH", "SECOND", "SECTION", "SEGMENT", "SEGMENTS", "SELECT", "SELF",
+    "SEMI", "SENSITIVE", "SEPARATE", "SEQUENCE", "SERIALIZABLE", "SESSION", "SET", "SETS", "SHARD",
+    "SHARE", "SHARED", "SHORT", "SHOW", "SIGNAL", "SIMILAR", "SIZE", "SKEWED", "SMALLINT",
+    "SNAPSHOT", "SOME", "SOURCE", "SPACE", "SPACES", "SPARSE", "SPECIFIC", "SPECIFICTYPE", "SPLIT",
+    "SQL", "SQLCODE", "SQLERROR", "SQLEXCEPTION", "SQLSTATE", "SQLWARNING", "START", "STATE",
+    "STATIC", "STATUS", "STORAGE", "STORE", "STORED", "STREAM", "STRING", "STRUCT", "STYLE", "SUB",
+    "SUBMULTISET", "SUBPARTITION", "SUBSTRING", "SUBTYPE", "SUM", "SUPER", "SYMMETRIC", "SYNONYM",
+    "SYSTEM", "TABLE", "TABLESAMPLE", "TEMP", "TEMPORARY", "TERMINATED", "TEXT", "THAN", "THEN",
+    "THROUGHPUT", "TIME", "TIMESTAMP", "TIMEZONE", "TINYINT", "TO", "TOKEN", "TOTAL", "TOUCH",
+    "TRAILING", "TRANSACTION", "TRANSFORM", "TRANSLATE", "TRANSLATION", "TREAT", "TRIGGER", "TRIM",
+    "TRUE", "TRUNCATE", "TTL", "TUPLE", "TYPE", "UNDER", "UNDO", "UNION", "UNIQUE", "UNIT",
+    "UNKNOWN", "UNLOGGED", "UNNEST", "UNPROCESSED", "UNSIGNED", "UNTIL", "UPDATE", "UPPER", "URL",
+    "USAGE", "USE", "USER", "USERS", "USING", "UUID", "VACUUM", "VALUE", "VALUED", "VALUES",
+    "VARCHAR", "VARIABLE", "VARIANCE", "VARINT", "VARYING", "VIEW", "VIEWS", "VIRTUAL", "VOID",
+    "WAIT", "WHEN", "WHENEVER", "WHERE", "WHILE", "WINDOW", "WITH", "WITHIN", "WITHOUT", "WORK",
+    "WRAPPED", "WRITE", "YEAR", "ZONE",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(v: &str) -> AttributeValue {
+        AttributeValue::S(v.into())
+    }
+
+    fn n(v: i64) -> AttributeValue {
+        AttributeValue::N(v.to_string())
+    }
+
+    fn scope(names: &[(&str, &str)], values: &[(&str, AttributeValue)]) -> Scope {
+        Scope::new(
+            names
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn reserved_words_are_sorted() {
+        assert!(RESERVED_WORDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(is_reserved("status") && !is_reserved("played"));
+    }
+
+    #[test]
+    fn conditions_follow_dynamodb_semantics() {
+        let item: Item = [
+            ("status".to_string(), s("pending")),
+            ("count".to_string(), n(10)),
+        ]
+        .into();
+        let mut sc = scope(
+            &[("#status", "status"), ("#c", "count")],
+            &[
+                (":pending", s("pending")),
+                (":nine", AttributeValue::N("9.0".into())),
+            ],
+        );
+        let cond = parse_condition(
+            "attribute_exists(#status) AND (#status = :pending OR NOT #c > :nine) AND #c > :nine AND attribute_not_exists(left_at)",
+            &mut sc,
+        )
+        .unwrap();
+        sc.finish().unwrap();
+        assert!(cond.eval(&item));
+        // A comparison against a missing attribute is false either way round.
+        let mut sc = scope(&[], &[(":v", s("x"))]);
+        assert!(
+            !parse_condition("nowhere <> :v", &mut sc)
+                .unwrap()
+                .eval(&item)
+        );
+        // Reserved words must go through a placeholder.
+        let mut sc = scope(&[], &[(":v", s("x"))]);
+        assert!(parse_condition("status = :v", &mut sc).is_err());
+        // Unused placeholders are rejected.
+        let mut sc = scope(&[("#unused", "x")], &[(":v", s("x"))]);
+        parse_condition("played = :v", &mut sc).unwrap();
+        assert!(sc.finish().is_err());
+    }
+
+    #[test]
+    fn updates_read_the_original_item() {
+        let item: Item = [
+            ("live_seq".to_string(), n(1)),
+            ("stats".to_string(), AttributeValue::M(HashMap::new())),
+            ("gone".to_string(), s("x")),
+        ]
+        .into();
+        let mut sc = scope(
+            &[("#sport", "tennis")],
+            &[
+                (":one", n(1)),
+                (":empty", AttributeValue::M(HashMap::new())),
+                (":tags", AttributeValue::Ss(vec!["a".into()])),
+            ],
+        );
+        let update = parse_update(
+            "SET live_seq = live_seq + :one, stats.#sport = if_not_exists(stats.#sport, :empty) REMOVE gone ADD tags :tags, wins :one",
+            &mut sc,
+        )
+        .unwrap();
+        sc.finish().unwrap();
+        let out = update.apply(&item).unwrap();
+        assert_eq!(out["live_seq"], n(2));
+        assert_eq!(out["wins"], n(1));
+        assert!(!out.contains_key("gone"));
+        let AttributeValue::M(stats) = &out["stats"] else {
+            panic!("stats is a map");
+        };
+        assert!(stats.contains_key("tennis"));
+        // A nested write under a missing parent is invalid.
+        let mut sc = scope(&[], &[(":one", n(1))]);
+        let update = parse_update("ADD nowhere.wins :one", &mut sc).unwrap();
+        assert!(update.apply(&item).is_err());
+        // Overlapping paths are rejected.
+        let mut sc = scope(&[], &[(":one", n(1))]);
+        assert!(parse_update("SET stats = :one REMOVE stats.wins", &mut sc).is_err());
+    }
+
+    #[test]
+    fn key_conditions_must_pin_the_partition_key() {
+        let mut sc = scope(&[("#pk", "PK")], &[(":pk", s("U#1")), (":sk", s("F#"))]);
+        assert!(
+            parse_key_condition("#pk = :pk AND begins_with(SK, :sk)", &mut sc, "PK", "SK").is_ok()
+        );
+        let mut sc = scope(&[], &[(":sk", s("F#"))]);
+        assert!(parse_key_condition("begins_with(SK, :sk)", &mut sc, "PK", "SK").is_err());
+    }
+}
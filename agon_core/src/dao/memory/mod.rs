@@ -0,0 +1,1294 @@
+//! An in-memory stand-in for the DynamoDB table, for tests and offline local
+//! development.
+//!
+//! It plugs in *under* the SDK rather than beside the DAO: [`MemoryTable`] is
+//! an SDK `HttpClient` that answers DynamoDB's JSON protocol in-process, so
+//! `Dao::in_memory` hands every DAO method the same `aws_sdk_dynamodb::Client`
+//! it always has — same request builders, same error types — and no method
+//! needs a second implementation. What it emulates is what the DAO uses:
+//!
+//! - `GetItem` / `PutItem` / `UpdateItem` / `DeleteItem`, with condition
+//!   expressions failing as `ConditionalCheckFailedException`.
+//! - `TransactWriteItems`, all-or-nothing, cancelling with per-item
+//!   `CancellationReasons` like the real thing.
+//! - `Query` (the base table and the three GSIs, which are sparse, as in
+//!   `agon_infra`) and `Scan`, paginated by `Limit` and the 1 MB page cap
+//!   through `LastEvaluatedKey`, with filter and projection expressions.
+//! - `BatchGetItem` / `BatchWriteItem`, which never leave anything
+//!   unprocessed.
+//!
+//! It validates the way DynamoDB does where getting it wrong would let a bug
+//! through: undefined or unused placeholders, reserved words, key schema and
+//! item size, repeated keys in a batch or transaction. Expressions are in
+//! [`expr`].
+//!
+//! Every write that changes an item is also appended to a stream, in the
+//! shape of the table's `NEW_AND_OLD_IMAGES` stream (unchanged writes leave
+//! no record, as there); [`MemoryTable::take_stream`] drains it, so worker
+//! handlers can be driven end to end without SQS.
+
+mod expr;
+mod wire;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::config::http::{HttpRequest, HttpResponse};
+use aws_sdk_dynamodb::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use self::expr::{Condition, Projection, Scope, Update};
+use super::item::{
+    ATTR_GSI1PK, ATTR_GSI1SK, ATTR_GSI2PK, ATTR_GSI2SK, ATTR_GSI3PK, ATTR_GSI3SK, ATTR_PK, ATTR_SK,
+    ATTR_TTL, Item,
+};
+
+/// DynamoDB's per-item size limit.
+const MAX_ITEM_BYTES: usize = 400 * 1024;
+/// The data a single Query or Scan page reads before it stops and hands back
+/// a `LastEvaluatedKey`.
+const MAX_PAGE_BYTES: usize = 1024 * 1024;
+const MAX_BATCH_GET: usize = 100;
+const MAX_BATCH_WRITE: usize = 25;
+const MAX_TRANSACT_ITEMS: usize = 100;
+
+/// The table's GSIs, as `agon_infra` declares them: name, partition key, sort
+/// key. All project every attribute.
+const INDEXES: [(&str, &str, &str); 3] = [
+    ("GSI1", ATTR_GSI1PK, ATTR_GSI1SK),
+    ("GSI2", ATTR_GSI2PK, ATTR_GSI2SK),
+    ("GSI3", ATTR_GSI3PK, ATTR_GSI3SK),
+];
+
+/// A base-table primary key: `(PK, SK)`.
+type TableKey = (String, String);
+
+/// The kind of change a stream record captures, as the stream's `eventName`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEventKind {
+    Insert,
+    Modify,
+    Remove,
+}
+
+impl StreamEventKind {
+    /// The stream's name for it (`INSERT` / `MODIFY` / `REMOVE`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StreamEventKind::Insert => "INSERT",
+            StreamEventKind::Modify => "MODIFY",
+            StreamEventKind::Remove => "REMOVE",
+        }
+    }
+}
+
+/// One change to the table, as the `NEW_AND_OLD_IMAGES` stream carries it:
+/// an insert has no old image, a remove no new one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamRecord {
+    pub kind: StreamEventKind,
+    pub pk: String,
+    pub sk: String,
+    pub old_image: Option<Item>,
+    pub new_image: Option<Item>,
+}
+
+/// An in-memory table answering the DynamoDB API. Cheap to clone; clones
+/// share the same items and stream.
+#[derive(Clone)]
+pub struct MemoryTable {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    name: String,
+    items: BTreeMap<TableKey, Item>,
+    stream: Vec<StreamRecord>,
+}
+
+impl fmt::Debug for MemoryTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MemoryTable")
+            .field("name", &state.name)
+            .field("items", &state.items.len())
+            .finish()
+    }
+}
+
+impl MemoryTable {
+    /// An empty table called `name`. Requests naming any other table fail
+    /// with `ResourceNotFoundException`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                name: name.into(),
+                items: BTreeMap::new(),
+                stream: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.lock().name.clone()
+    }
+
+    /// An SDK client whose every request this table answers. Static
+    /// credentials and a fixed region, so nothing reads the AWS environment.
+    pub fn client(&self) -> Client {
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("local"))
+            .credentials_provider(Credentials::new("memory", "memory", None, None, "memory"))
+            .http_client(self.clone())
+            .build();
+        Client::from_conf(config)
+    }
+
+    /// Drain the stream: every change since the last call, oldest first.
+    pub fn take_stream(&self) -> Vec<StreamRecord> {
+        std::mem::take(&mut self.lock().stream)
+    }
+
+    /// Delete every item whose `ttl` (epoch seconds) is at or before `now`,
+    /// as DynamoDB's TTL sweeper eventually does, leaving a `REMOVE` on the
+    /// stream for each. Returns how many went.
+    pub fn expire(&self, now: i64) -> usize {
+        let mut state = self.lock();
+        let expired: Vec<TableKey> = state
+            .items
+            .iter()
+            .filter(|(_, item)| match item.get(ATTR_TTL) {
+                Some(AttributeValue::N(ttl)) => ttl.parse::<f64>().is_ok_and(|t| t <= now as f64),
+                _ => false,
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            state.commit(key.clone(), None);
+        }
+        expired.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panic mid-request can't leave a half-applied write (each is a
+        // single map operation), so a poisoned lock's state is still good.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn handle(&self, request: &HttpRequest) -> Result<Value, Fault> {
+        let operation = request
+            .headers()
+            .get("x-amz-target")
+            .and_then(|t| t.strip_prefix("DynamoDB_20120810."))
+            .ok_or_else(|| Fault::UnknownOperation("missing x-amz-target".into()))?
+            .to_string();
+        let body = request.body().bytes().unwrap_or_default();
+        let mut state = self.lock();
+        match operation.as_str() {
+            "GetItem" => state.get_item(parse(body)?),
+            "PutItem" => state.write_one(parse(body)?, WriteKind::Put),
+            "UpdateItem" => state.write_one(parse(body)?, WriteKind::Update),
+            "DeleteItem" => state.write_one(parse(body)?, WriteKind::Delete),
+            "Query" => state.query(parse(body)?),
+            "Scan" => state.scan(parse(body)?),
+            "BatchGetItem" => state.batch_get(parse(body)?),
+            "BatchWriteItem" => state.batch_write(parse(body)?),
+            "TransactWriteItems" => state.transact_write(parse(body)?),
+            other => Err(Fault::UnknownOperation(other.to_string())),
+        }
+    }
+}
+
+impl HttpConnector for MemoryTable {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let (status, body) = match self.handle(&request) {
+            Ok(body) => (200, body),
+            Err(fault) => (400, fault.to_json()),
+        };
+        let mut response = HttpResponse::new(
+            StatusCode::try_from(status).expect("valid status code"),
+            SdkBody::from(body.to_string()),
+        );
+        response
+            .headers_mut()
+            .insert("content-type", "application/x-amz-json-1.0");
+        HttpConnectorFuture::ready(Ok(response))
+    }
+}
+
+impl HttpClient for MemoryTable {
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(self.clone())
+    }
+}
+
+// ── Errors ─────────────────────────────────────────────────────────────────
+
+/// A request the table refused, returned as the matching DynamoDB error.
+#[derive(Debug)]
+enum Fault {
+    Validation(String),
+    ConditionalCheckFailed,
+    /// One reason per transaction item: the failure code, or `None` for the
+    /// items that were fine.
+    TransactionCanceled(Vec<Option<&'static str>>),
+    ResourceNotFound(String),
+    UnknownOperation(String),
+    Serialization(String),
+}
+
+impl From<String> for Fault {
+    fn from(message: String) -> Self {
+        Fault::Validation(message)
+    }
+}
+
+impl Fault {
+    fn to_json(&self) -> Value {
+        const DYNAMODB: &str = "com.amazonaws.dynamodb.v20120810#";
+        const CORAL: &str = "com.amazon.coral.service#";
+        match self {
+            Fault::Validation(message) => {
+                json!({ "__type": format!("{CORAL}ValidationException"), "message": message })
+            }
+            Fault::ConditionalCheckFailed => json!({
+                "__type": format!("{DYNAMODB}ConditionalCheckFailedException"),
+                "message": "The conditional request failed",
+            }),
+            Fault::TransactionCanceled(reasons) => {
+                let codes: Vec<&str> = reasons.iter().map(|r| r.unwrap_or("None")).collect();
+                json!({
+                    "__type": format!("{DYNAMODB}TransactionCanceledException"),
+                    "Message": format!(
+                        "Transaction cancelled, please refer cancellation reasons for specific reasons [{}]",
+                        codes.join(", ")
+                    ),
+                    "CancellationReasons": reasons
+                        .iter()
+                        .map(|r| match r {
+                            Some(code) => json!({ "Code": code, "Message": "The conditional request failed" }),
+                            None => json!({ "Code": "None" }),
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            }
+            Fault::ResourceNotFound(table) => json!({
+                "__type": format!("{DYNAMODB}ResourceNotFoundException"),
+                "message": format!("Requested resource not found: Table: {table} not found"),
+            }),
+            Fault::UnknownOperation(operation) => json!({
+                "__type": format!("{CORAL}UnknownOperationException"),
+                "message": format!("The in-memory table does not support {operation}"),
+            }),
+            Fault::Serialization(message) => json!({
+                "__type": format!("{CORAL}SerializationException"),
+                "message": message,
+            }),
+        }
+    }
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(body: &[u8]) -> Result<T, Fault> {
+    serde_json::from_slice(body).map_err(|e| Fault::Serialization(e.to_string()))
+}
+
+// ── Request shapes ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct Placeholders {
+    expression_attribute_names: HashMap<String, String>,
+    expression_attribute_values: HashMap<String, Value>,
+}
+
+impl Placeholders {
+    fn scope(&self) -> Result<Scope, Fault> {
+        let values = self
+            .expression_attribute_values
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), wire::value_from_json(v)?)))
+            .collect::<Result<_, String>>()?;
+        Ok(Scope::new(self.expression_attribute_names.clone(), values))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetItemInput {
+    table_name: String,
+    key: Value,
+    projection_expression: Option<String>,
+    #[serde(flatten)]
+    placeholders: Placeholders,
+}
+
+/// A single-item write: `PutItem` / `UpdateItem` / `DeleteItem`, or one
+/// element of a transaction (a `ConditionCheck` too).
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WriteInput {
+    table_name: String,
+    key: Option<Value>,
+    item: Option<Value>,
+    condition_expression: Option<String>,
+    update_expression: Option<String>,
+    return_values: Option<String>,
+    #[serde(flatten)]
+    placeholders: Placeholders,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ReadInput {
+    table_name: String,
+    index_name: Option<String>,
+    key_condition_expression: Option<String>,
+    filter_expression: Option<String>,
+    projection_expression: Option<String>,
+    scan_index_forward: Option<bool>,
+    limit: Option<i64>,
+    exclusive_start_key: Option<Value>,
+    select: Option<String>,
+    consistent_read: Option<bool>,
+    #[serde(flatten)]
+    placeholders: Placeholders,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BatchGetInput {
+    request_items: HashMap<String, KeysAndAttributes>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KeysAndAttributes {
+    keys: Vec<Value>,
+    projection_expression: Option<String>,
+    #[serde(flatten)]
+    placeholders: Placeholders,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BatchWriteInput {
+    request_items: HashMap<String, Vec<WriteRequest>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WriteRequest {
+    put_request: Option<PutRequest>,
+    delete_request: Option<DeleteRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PutRequest {
+    item: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeleteRequest {
+    key: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TransactWriteInput {
+    transact_items: Vec<TransactItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TransactItem {
+    condition_check: Option<WriteInput>,
+    put: Option<WriteInput>,
+    update: Option<WriteInput>,
+    delete: Option<WriteInput>,
+}
+
+// ── Keys and validation ────────────────────────────────────────────────────
+
+/// The `(PK, SK)` of a request's `Key`, which must be exactly those two
+/// string attributes.
+fn table_key(key: &Value) -> Result<TableKey, Fault> {
+    let key = wire::item_from_json(key)?;
+    let mismatch =
+        || Fault::Validation("The provided key element does not match the schema".into());
+    if key.len() != 2 {
+        return Err(mismatch());
+    }
+    match (key.get(ATTR_PK), key.get(ATTR_SK)) {
+        (Some(AttributeValue::S(pk)), Some(AttributeValue::S(sk))) => {
+            if pk.is_empty() || sk.is_empty() {
+                return Err(Fault::Validation(
+                    "One or more parameter values are not valid. The AttributeValue for a key attribute cannot contain an empty string value.".into(),
+                ));
+            }
+            Ok((pk.clone(), sk.clone()))
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+fn key_item((pk, sk): &TableKey) -> Item {
+    [
+        (ATTR_PK.to_string(), AttributeValue::S(pk.clone())),
+        (ATTR_SK.to_string(), AttributeValue::S(sk.clone())),
+    ]
+    .into()
+}
+
+/// Check an item is one the table would store, returning its key: string
+/// table keys, string GSI keys where present, and under the size limit.
+fn validate_item(item: &Item) -> Result<TableKey, Fault> {
+    let key = match (item.get(ATTR_PK), item.get(ATTR_SK)) {
+        (Some(AttributeValue::S(pk)), Some(AttributeValue::S(sk)))
+            if !pk.is_empty() && !sk.is_empty() =>
+        {
+            (pk.clone(), sk.clone())
+        }
+        _ => {
+            return Err(Fault::Validation(
+                "One or more parameter values were invalid: Missing the key PK or SK in the item, or it is not a non-empty string".into(),
+            ));
+        }
+    };
+    for (index, hash, range) in INDEXES {
+        for attr in [hash, range] {
+            match item.get(attr) {
+                None => {}
+                Some(AttributeValue::S(v)) if !v.is_empty() => {}
+                Some(_) => {
+                    return Err(Fault::Validation(format!(
+                        "One or more parameter values were invalid: Type mismatch or empty value for Index Key {attr} Expected: S IndexName: {index}"
+                    )));
+                }
+            }
+        }
+    }
+    if wire::item_size(item) > MAX_ITEM_BYTES {
+        return Err(Fault::Validation(
+            "Item size has exceeded the maximum allowed size".into(),
+        ));
+    }
+    Ok(key)
+}
+
+/// The key schema a read runs against: the table's, or a GSI's.
+struct Schema {
+    hash: &'static str,
+    range: &'static str,
+    index: bool,
+}
+
+fn schema(index_name: Option<&str>) -> Result<Schema, Fault> {
+    let Some(name) = index_name else {
+        return Ok(Schema {
+            hash: ATTR_PK,
+            range: ATTR_SK,
+            index: false,
+        });
+    };
+    INDEXES
+        .iter()
+        .find(|(n, _, _)| *n == name)
+        .map(|(_, hash, range)| Schema {
+            hash,
+            range,
+            index: true,
+        })
+        .ok_or_else(|| {
+            Fault::Validation(format!(
+                "The table does not have the specified index: {name}"
+            ))
+        })
+}
+
+/// Where an item sorts within a read: its index key, then its table key
+/// (which breaks ties within a GSI). `None` if the item isn't in the index.
+type Position = (String, String, String, String);
+
+fn position(item: &Item, schema: &Schema) -> Option<Position> {
+    let s = |attr: &str| match item.get(attr) {
+        Some(AttributeValue::S(v)) => Some(v.clone()),
+        _ => None,
+    };
+    Some((s(schema.hash)?, s(schema.range)?, s(ATTR_PK)?, s(ATTR_SK)?))
+}
+
+/// The key attributes a `LastEvaluatedKey` carries for an item.
+fn last_key(item: &Item, schema: &Schema) -> Item {
+    let mut attrs = vec![ATTR_PK, ATTR_SK];
+    if schema.index {
+        attrs.extend([schema.hash, schema.range]);
+    }
+    attrs
+        .into_iter()
+        .filter_map(|a| Some((a.to_string(), item.get(a)?.clone())))
+        .collect()
+}
+
+// ── Operations ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriteKind {
+    Put,
+    Update,
+    Delete,
+    Check,
+}
+
+/// A parsed, validated single-item write, ready to check and apply.
+struct PreparedWrite {
+    key: TableKey,
+    condition: Option<Condition>,
+    action: Action,
+    return_values: Option<String>,
+}
+
+enum Action {
+    Put(Item),
+    Update(Option<Update>),
+    Delete,
+    Check,
+}
+
+impl PreparedWrite {
+    fn passes(&self, existing: Option<&Item>) -> bool {
+        let empty = Item::new();
+        self.condition
+            .as_ref()
+            .is_none_or(|c| c.eval(existing.unwrap_or(&empty)))
+    }
+
+    /// The item once this write applies to `existing` (`None` = deleted).
+    fn apply(&self, existing: Option<&Item>) -> Result<Option<Item>, Fault> {
+        Ok(match &self.action {
+            Action::Put(item) => Some(item.clone()),
+            Action::Update(update) => {
+                let base = existing.cloned().unwrap_or_else(|| key_item(&self.key));
+                let item = match update {
+                    Some(update) => update.apply(&base)?,
+                    None => base,
+                };
+                validate_item(&item)?;
+                Some(item)
+            }
+            Action::Delete => None,
+            Action::Check => existing.cloned(),
+        })
+    }
+}
+
+impl State {
+    fn check_table(&self, table: &str) -> Result<(), Fault> {
+        if table == self.name {
+            Ok(())
+        } else {
+            Err(Fault::ResourceNotFound(table.to_string()))
+        }
+    }
+
+    /// Store (or delete) one item and stream the change, if it is one.
+    fn commit(&mut self, key: TableKey, new: Option<Item>) {
+        let old = match &new {
+            Some(item) => self.items.insert(key.clone(), item.clone()),
+            None => self.items.remove(&key),
+        };
+        let kind = match (&old, &new) {
+            (None, None) => return,
+            (Some(old), Some(new)) if old == new => return,
+            (None, Some(_)) => StreamEventKind::Insert,
+            (Some(_), Some(_)) => StreamEventKind::Modify,
+            (Some(_), None) => StreamEventKind::Remove,
+        };
+        let (pk, sk) = key;
+        self.stream.push(StreamRecord {
+            kind,
+            pk,
+            sk,
+            old_image: old,
+            new_image: new,
+        });
+    }
+
+    fn get_item(&self, input: GetItemInput) -> Result<Value, Fault> {
+        self.check_table(&input.table_name)?;
+        let key = table_key(&input.key)?;
+        let mut scope = input.placeholders.scope()?;
+        let projection = input
+            .projection_expression
+            .as_deref()
+            .map(|p| expr::parse_projection(p, &mut scope))
+            .transpose()?;
+        scope.finish()?;
+        Ok(match self.items.get(&key) {
+            Some(item) => {
+                json!({ "Item": wire::item_to_json(&project(item, projection.as_ref())) })
+            }
+            None => json!({}),
+        })
+    }
+
+    fn prepare(&self, input: WriteInput, kind: WriteKind) -> Result<PreparedWrite, Fault> {
+        self.check_table(&input.table_name)?;
+        let mut scope = input.placeholders.scope()?;
+        let condition = input
+            .condition_expression
+            .as_deref()
+            .map(|c| expr::parse_condition(c, &mut scope))
+            .transpose()?;
+        let key_of = |key: &Option<Value>| {
+            table_key(key.as_ref().ok_or_else(|| {
+                Fault::Validation("The provided key element does not match the schema".into())
+            })?)
+        };
+        let (key, action) = match kind {
+            WriteKind::Put => {
+                let item = wire::item_from_json(input.item.as_ref().ok_or_else(|| {
+                    Fault::Validation("1 validation error detected: Item must not be null".into())
+                })?)?;
+                (validate_item(&item)?, Action::Put(item))
+            }
+            WriteKind::Update => {
+                let update = input
+                    .update_expression
+                    .as_deref()
+                    .map(|u| expr::parse_update(u, &mut scope))
+                    .transpose()?;
+                if let Some(attr) = [ATTR_PK, ATTR_SK]
+                    .into_iter()
+                    .find(|a| update.as_ref().is_some_and(|u| u.touches(a)))
+                {
+                    return Err(Fault::Validation(format!(
+                        "One or more parameter values were invalid: Cannot update attribute {attr}. This attribute is part of the key"
+                    )));
+                }
+                (key_of(&input.key)?, Action::Update(update))
+            }
+            WriteKind::Delete => (key_of(&input.key)?, Action::Delete),
+            WriteKind::Check => {
+                if condition.is_none() {
+                    return Err(Fault::Validation(
+                        "1 validation error detected: ConditionExpression must not be null".into(),
+                    ));
+                }
+                (key_of(&input.key)?, Action::Check)
+            }
+        };
+        scope.finish()?;
+        Ok(PreparedWrite {
+            key,
+            condition,
+            action,
+            return_values: input.return_values,
+        })
+    }
+
+    fn write_one(&mut self, input: WriteInput, kind: WriteKind) -> Result<Value, Fault> {
+        let write = self.prepare(input, kind)?;
+        let existing = self.items.get(&write.key).cloned();
+        if !write.passes(existing.as_ref()) {
+            return Err(Fault::ConditionalCheckFailed);
+        }
+        let new = write.apply(existing.as_ref())?;
+        let returned = match write.return_values.as_deref() {
+            None | Some("NONE") => None,
+            Some("ALL_OLD") => existing,
+            Some("ALL_NEW") if kind == WriteKind::Update => new.clone(),
+            Some(other) => {
+                return Err(Fault::Validation(format!(
+                    "ReturnValues {other} is not supported by the in-memory table for this operation"
+                )));
+            }
+        };
+        self.commit(write.key, new);
+        Ok(match returned {
+            Some(item) => json!({ "Attributes": wire::item_to_json(&item) }),
+            None => json!({}),
+        })
+    }
+
+    fn transact_write(&mut self, input: TransactWriteInput) -> Result<Value, Fault> {
+        if input.transact_items.is_empty() || input.transact_items.len() > MAX_TRANSACT_ITEMS {
+            return Err(Fault::Validation(format!(
+                "1 validation error detected: Value at 'transactItems' failed to satisfy constraint: Member must have length between 1 and {MAX_TRANSACT_ITEMS}"
+            )));
+        }
+        let mut writes = Vec::with_capacity(input.transact_items.len());
+        for item in input.transact_items {
+            let (write, kind) = match item {
+                TransactItem {
+                    condition_check: Some(w),
+                    put: None,
+                    update: None,
+                    delete: None,
+                } => (w, WriteKind::Check),
+                TransactItem {
+                    condition_check: None,
+                    put: Some(w),
+                    update: None,
+                    delete: None,
+                } => (w, WriteKind::Put),
+                TransactItem {
+                    condition_check: None,
+                    put: None,
+                    update: Some(w),
+                    delete: None,
+                } => (w, WriteKind::Update),
+                TransactItem {
+                    condition_check: None,
+                    put: None,
+                    update: None,
+                    delete: Some(w),
+                } => (w, WriteKind::Delete),
+                _ => {
+                    return Err(Fault::Validation(
+                        "TransactItems can only contain one of Check, Put, Update or Delete".into(),
+                    ));
+                }
+            };
+            writes.push(self.prepare(write, kind)?);
+        }
+        let mut keys = HashSet::new();
+        if !writes.iter().all(|w| keys.insert(&w.key)) {
+            return Err(Fault::Validation(
+                "Transaction request cannot include multiple operations on one item".into(),
+            ));
+        }
+
+        let reasons: Vec<Option<&'static str>> = writes
+            .iter()
+            .map(|w| (!w.passes(self.items.get(&w.key))).then_some("ConditionalCheckFailed"))
+            .collect();
+        if reasons.iter().any(Option::is_some) {
+            return Err(Fault::TransactionCanceled(reasons));
+        }
+        // No item appears twice, so each write sees the state from before the
+        // transaction, and nothing commits unless every write can.
+        let results = writes
+            .iter()
+            .map(|w| w.apply(self.items.get(&w.key)))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (write, new) in writes.into_iter().zip(results) {
+            if !matches!(write.action, Action::Check) {
+                self.commit(write.key, new);
+            }
+        }
+        Ok(json!({}))
+    }
+
+    fn query(&self, input: ReadInput) -> Result<Value, Fault> {
+        let schema = schema(input.index_name.as_deref())?;
+        let mut scope = input.placeholders.scope()?;
+        let key_condition = expr::parse_key_condition(
+            input.key_condition_expression.as_deref().ok_or_else(|| {
+                Fault::Validation(
+                    "Either the KeyConditions or KeyConditionExpression parameter must be specified in the request.".into(),
+                )
+            })?,
+            &mut scope,
+            schema.hash,
+            schema.range,
+        )?;
+        let filter = input
+            .filter_expression
+            .as_deref()
+            .map(|f| expr::parse_condition(f, &mut scope))
+            .transpose()?;
+        if let Some(key) = filter.as_ref().and_then(|f| {
+            f.attributes()
+                .into_iter()
+                .find(|a| *a == schema.hash || *a == schema.range)
+        }) {
+            return Err(Fault::Validation(format!(
+                "Filter Expression can only contain non-primary key attributes: Primary key attribute: {key}"
+            )));
+        }
+        self.read(input, scope, schema, Some(key_condition), filter)
+    }
+
+    fn scan(&self, input: ReadInput) -> Result<Value, Fault> {
+        let schema = schema(input.index_name.as_deref())?;
+        let mut scope = input.placeholders.scope()?;
+        let filter = input
+            .filter_expression
+            .as_deref()
+            .map(|f| expr::parse_condition(f, &mut scope))
+            .transpose()?;
+        self.read(input, scope, schema, None, filter)
+    }
+
+    /// The shared half of Query and Scan: walk the table or index in key
+    /// order from the start key, stop at `Limit` items or the page size cap,
+    /// then filter and project what was read.
+    fn read(
+        &self,
+        input: ReadInput,
+        mut scope: Scope,
+        schema: Schema,
+        key_condition: Option<Condition>,
+        filter: Option<Condition>,
+    ) -> Result<Value, Fault> {
+        self.check_table(&input.table_name)?;
+        if schema.index && input.consistent_read == Some(true) {
+            return Err(Fault::Validation(
+                "Consistent reads are not supported on global secondary indexes".into(),
+            ));
+        }
+        let projection = input
+            .projection_expression
+            .as_deref()
+            .map(|p| expr::parse_projection(p, &mut scope))
+            .transpose()?;
+        scope.finish()?;
+        let count_only = match input.select.as_deref() {
+            None | Some("ALL_ATTRIBUTES") | Some("ALL_PROJECTED_ATTRIBUTES") => false,
+            Some("COUNT") => true,
+            Some(other) => {
+                return Err(Fault::Validation(format!(
+                    "Select {other} is not supported by the in-memory table"
+                )));
+            }
+        };
+        let limit = match input.limit {
+            Some(n) if n < 1 => {
+                return Err(Fault::Validation(
+                    "1 validation error detected: Value at 'limit' failed to satisfy constraint: Member must have value greater than or equal to 1".into(),
+                ));
+            }
+            Some(n) => Some(n as usize),
+            None => None,
+        };
+        let forward = input.scan_index_forward.unwrap_or(true);
+        let start = input
+            .exclusive_start_key
+            .as_ref()
+            .map(|k| {
+                let k = wire::item_from_json(k)?;
+                position(&k, &schema)
+                    .ok_or_else(|| Fault::Validation("The provided starting key is invalid".into()))
+            })
+            .transpose()?;
+
+        let mut rows: Vec<(Position, &Item)> = self
+            .items
+            .values()
+            .filter_map(|item| Some((position(item, &schema)?, item)))
+            .filter(|(_, item)| key_condition.as_ref().is_none_or(|c| c.eval(item)))
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        if !forward {
+            rows.reverse();
+        }
+        let skip = match &start {
+            Some(start) => rows
+                .iter()
+                .take_while(|(p, _)| if forward { p <= start } else { p >= start })
+                .count(),
+            None => 0,
+        };
+
+        let mut scanned = Vec::new();
+        let mut bytes = 0;
+        let full = |scanned: &Vec<&Item>, bytes: usize| {
+            limit.is_some_and(|l| scanned.len() >= l) || bytes >= MAX_PAGE_BYTES
+        };
+        for (_, item) in &rows[skip..] {
+            if full(&scanned, bytes) {
+                break;
+            }
+            bytes += wire::item_size(item);
+            scanned.push(*item);
+        }
+        // DynamoDB hands back a key whenever a full page cut the read short,
+        // even if nothing follows; the caller's next page is then empty.
+        let stopped = full(&scanned, bytes);
+
+        let matched: Vec<&Item> = scanned
+            .iter()
+            .copied()
+            .filter(|item| filter.as_ref().is_none_or(|f| f.eval(item)))
+            .collect();
+        let mut out = json!({
+            "Count": matched.len(),
+            "ScannedCount": scanned.len(),
+        });
+        if !count_only {
+            out["Items"] = Value::Array(
+                matched
+                    .iter()
+                    .map(|item| wire::item_to_json(&project(item, projection.as_ref())))
+                    .collect(),
+            );
+        }
+        if stopped && let Some(last) = scanned.last() {
+            out["LastEvaluatedKey"] = wire::item_to_json(&last_key(last, &schema));
+        }
+        Ok(out)
+    }
+
+    fn batch_get(&self, input: BatchGetInput) -> Result<Value, Fault> {
+        let total: usize = input.request_items.values().map(|r| r.keys.len()).sum();
+        if total > MAX_BATCH_GET {
+            return Err(Fault::Validation(
+                "Too many items requested for the BatchGetItem call".into(),
+            ));
+        }
+        let mut responses = serde_json::Map::new();
+        for (table, request) in input.request_items {
+            self.check_table(&table)?;
+            let mut scope = request.placeholders.scope()?;
+            let projection = request
+                .projection_expression
+                .as_deref()
+                .map(|p| expr::parse_projection(p, &mut scope))
+                .transpose()?;
+            scope.finish()?;
+            let keys = request
+                .keys
+                .iter()
+                .map(table_key)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut seen = HashSet::new();
+            if !keys.iter().all(|k| seen.insert(k)) {
+                return Err(Fault::Validation(
+                    "Provided list of item keys contains duplicates".into(),
+                ));
+            }
+            let items: Vec<Value> = keys
+                .iter()
+                .filter_map(|k| self.items.get(k))
+                .map(|item| wire::item_to_json(&project(item, projection.as_ref())))
+                .collect();
+            responses.insert(table, Value::Array(items));
+        }
+        Ok(json!({ "Responses": responses, "UnprocessedKeys": {} }))
+    }
+
+    fn batch_write(&mut self, input: BatchWriteInput) -> Result<Value, Fault> {
+        let total: usize = input.request_items.values().map(Vec::len).sum();
+        if total == 0 || total > MAX_BATCH_WRITE {
+            return Err(Fault::Validation(format!(
+                "1 validation error detected: Value at 'requestItems' failed to satisfy constraint: Member must have length between 1 and {MAX_BATCH_WRITE}"
+            )));
+        }
+        let mut writes = Vec::with_capacity(total);
+        for (table, requests) in input.request_items {
+            self.check_table(&table)?;
+            for request in requests {
+                writes.push(match (request.put_request, request.delete_request) {
+                    (Some(put), None) => {
+                        let item = wire::item_from_json(&put.item)?;
+                        (validate_item(&item)?, Some(item))
+                    }
+                    (None, Some(delete)) => (table_key(&delete.key)?, None),
+                    _ => {
+                        return Err(Fault::Validation(
+                            "A WriteRequest must contain exactly one of PutRequest or DeleteRequest".into(),
+                        ));
+                    }
+                });
+            }
+        }
+        let mut seen = HashSet::new();
+        if !writes.iter().all(|(k, _)| seen.insert(k.clone())) {
+            return Err(Fault::Validation(
+                "Provided list of item keys contains duplicates".into(),
+            ));
+        }
+        for (key, item) in writes {
+            self.commit(key, item);
+        }
+        Ok(json!({ "UnprocessedItems": {} }))
+    }
+}
+
+fn project(item: &Item, projection: Option<&Projection>) -> Item {
+    match projection {
+        Some(projection) => projection.apply(item),
+        None => item.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::types::{Put, TransactWriteItem, Update as TransactUpdate};
+
+    use super::*;
+    use crate::dao::is_transaction_conditional_failure;
+    use crate::dao::keys::{Pk, Sk};
+    use crate::dao::records::UserRecord;
+    use crate::dao::{Dao, DaoError};
+
+    fn s(v: &str) -> AttributeValue {
+        AttributeValue::S(v.into())
+    }
+
+    fn row(pk: &str, sk: &str, extra: &[(&str, AttributeValue)]) -> Item {
+        let mut item = key_item(&(pk.into(), sk.into()));
+        item.extend(extra.iter().map(|(k, v)| (k.to_string(), v.clone())));
+        item
+    }
+
+    #[tokio::test]
+    async fn conditional_writes_fail_like_dynamodb() {
+        let table = MemoryTable::new("agon");
+        let client = table.client();
+        let put = || {
+            client
+                .put_item()
+                .table_name("agon")
+                .set_item(Some(row("USER#u1", "#META", &[])))
+                .condition_expression("attribute_not_exists(#pk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+        };
+        put().send().await.unwrap();
+        let err = put().send().await.unwrap_err();
+        assert!(
+            err.as_service_error()
+                .is_some_and(|e| e.is_conditional_check_failed_exception())
+        );
+
+        let err = client
+            .get_item()
+            .table_name("elsewhere")
+            .key(ATTR_PK, s("USER#u1"))
+            .key(ATTR_SK, s("#META"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(
+            err.as_service_error()
+                .is_some_and(|e| e.is_resource_not_found_exception())
+        );
+    }
+
+    #[tokio::test]
+    async fn transactions_are_all_or_nothing() {
+        let table = MemoryTable::new("agon");
+        let client = table.client();
+        client
+            .put_item()
+            .table_name("agon")
+            .set_item(Some(row(
+                "TEAM#t1",
+                "#META",
+                &[("member_count", AttributeValue::N("1".into()))],
+            )))
+            .send()
+            .await
+            .unwrap();
+        table.take_stream();
+
+        let transact = |member: &str| {
+            let put = Put::builder()
+                .table_name("agon")
+                .set_item(Some(row("TEAM#t1", member, &[])))
+                .condition_expression("attribute_not_exists(#pk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .build()
+                .unwrap();
+            let bump = TransactUpdate::builder()
+                .table_name("agon")
+                .key(ATTR_PK, s("TEAM#t1"))
+                .key(ATTR_SK, s("#META"))
+                .update_expression("ADD member_count :one")
+                .expression_attribute_values(":one", AttributeValue::N("1".into()))
+                .build()
+                .unwrap();
+            client
+                .transact_write_items()
+                .transact_items(TransactWriteItem::builder().put(put).build())
+                .transact_items(TransactWriteItem::builder().update(bump).build())
+        };
+        transact("MEMBER#u2").send().await.unwrap();
+        let err = transact("MEMBER#u2").send().await.unwrap_err();
+        assert!(is_transaction_conditional_failure(&err));
+
+        // Only the first transaction's two writes happened.
+        let stream = table.take_stream();
+        let kinds: Vec<StreamEventKind> = stream.iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, [StreamEventKind::Insert, StreamEventKind::Modify]);
+        assert_eq!(
+            stream[1].new_image.as_ref().unwrap()["member_count"],
+            AttributeValue::N("2".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn queries_page_through_sparse_indexes() {
+        let table = MemoryTable::new("agon");
+        let client = table.client();
+        for i in 0..5 {
+            let mut extra = vec![];
+            // Only the even rows project into GSI1.
+            if i % 2 == 0 {
+                extra.push((ATTR_GSI1PK, s("CLUB#c1")));
+                extra.push((ATTR_GSI1SK, s(&format!("TEAM#{i}"))));
+            }
+            client
+                .put_item()
+                .table_name("agon")
+                .set_item(Some(row("TEAM", &format!("T#{i}"), &extra)))
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut start = None;
+        loop {
+            let out = client
+                .query()
+                .table_name("agon")
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk AND begins_with(GSI1SK, :sk)")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s("CLUB#c1"))
+                .expression_attribute_values(":sk", s("TEAM#"))
+                .scan_index_forward(false)
+                .limit(2)
+                .set_exclusive_start_key(start)
+                .send()
+                .await
+                .unwrap();
+            seen.extend(
+                out.items()
+                    .iter()
+                    .map(|i| i[ATTR_SK].as_s().unwrap().clone()),
+            );
+            start = out.last_evaluated_key;
+            if start.is_none() {
+                break;
+            }
+        }
+        assert_eq!(seen, ["T#4", "T#2", "T#0"]);
+
+        let err = client
+            .query()
+            .table_name("agon")
+            .key_condition_expression("begins_with(SK, :sk)")
+            .expression_attribute_values(":sk", s("T#"))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_service_error().and_then(|e| e.meta().code()),
+            Some("ValidationException")
+        );
+    }
+
+    #[tokio::test]
+    async fn expiry_removes_items_past_their_ttl() {
+        let table = MemoryTable::new("agon");
+        let client = table.client();
+        for (sk, ttl) in [("F#old", "100"), ("F#new", "300")] {
+            client
+                .put_item()
+                .table_name("agon")
+                .set_item(Some(row(
+                    "FEED#u1",
+                    sk,
+                    &[(ATTR_TTL, AttributeValue::N(ttl.into()))],
+                )))
+                .send()
+                .await
+                .unwrap();
+        }
+        table.take_stream();
+        assert_eq!(table.expire(200), 1);
+        let stream = table.take_stream();
+        assert_eq!(stream.len(), 1);
+        assert_eq!(
+            (stream[0].kind, stream[0].sk.as_str()),
+            (StreamEventKind::Remove, "F#old")
+        );
+    }
+
+    fn user(id: &str) -> UserRecord {
+        serde_json::from_value(json!({
+            "id": id,
+            "email": format!("{id}@example.com"),
+            "name": id,
+            "created_at": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn the_dao_runs_against_it_unchanged() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        for id in ["u1", "u2", "u3"] {
+            dao.create_user(&format!("sub-{id}"), &user(id))
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            dao.create_user("sub-u1", &user("u1")).await,
+            Err(DaoError::Conflict(_))
+        ));
+
+        let now = "2026-01-02T00:00:00Z";
+        dao.follow_user("u2", "u1", now).await.unwrap();
+        dao.follow_user("u2", "u1", now).await.unwrap();
+        dao.follow_user("u3", "u1", now).await.unwrap();
+        dao.follow_user("u2", "u3", now).await.unwrap();
+        let u1 = dao.get_user("u1").await.unwrap().unwrap();
+        assert_eq!(u1.follower_count, 2);
+
+        let mut followers = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = dao
+                .list_user_followers("u1", cursor.as_deref(), 1)
+                .await
+                .unwrap();
+            followers.extend(page.items.into_iter().map(|f| f.follower_id));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(followers, ["u2", "u3"]);
+        let following = dao.list_user_following("u2", None, 10).await.unwrap();
+        assert_eq!(following.items.len(), 2);
+
+        dao.unfollow_user("u2", "u1").await.unwrap();
+        dao.unfollow_user("u2", "u1").await.unwrap();
+        let u1 = dao.get_user("u1").await.unwrap().unwrap();
+        assert_eq!(u1.follower_count, 1);
+        assert!(!dao.is_following_user("u2", "u1").await.unwrap());
+
+        // The edge's insert and removal both made it onto the stream.
+        let edges: Vec<StreamEventKind> = table
+            .take_stream()
+            .into_iter()
+            .filter(|r| {
+                r.pk == Pk::User("u1".into()).to_string()
+                    && r.sk == Sk::Follower("u2".into()).to_string()
+            })
+            .map(|r| r.kind)
+            .collect();
+        assert_eq!(edges, [StreamEventKind::Insert, StreamEventKind::Remove]);
+    }
+}
@@ -0,0 +1,140 @@
+//! DynamoDB's JSON wire shape for attribute values (`{"S": "..."}`,
+//! `{"N": "42"}`, …), in both directions, for the in-memory table's request
+//! and response bodies.
+
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::AttributeValue;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde_json::{Map, Value, json};
+
+use super::expr::ExprResult;
+use crate::dao::item::Item;
+
+pub(super) fn value_from_json(value: &Value) -> ExprResult<AttributeValue> {
+    let invalid = || format!("Supplied AttributeValue is empty or has an invalid shape: {value}");
+    let map = value
+        .as_object()
+        .filter(|m| m.len() == 1)
+        .ok_or_else(invalid)?;
+    let (tag, inner) = map.iter().next().ok_or_else(invalid)?;
+    let string = || inner.as_str().map(str::to_string).ok_or_else(invalid);
+    let strings = || -> ExprResult<Vec<String>> {
+        let list = inner.as_array().ok_or_else(invalid)?;
+        if list.is_empty() {
+            return Err(format!(
+                "One or more parameter values were invalid: An {tag} may not be empty"
+            ));
+        }
+        list.iter()
+            .map(|v| v.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect()
+    };
+    let blob = |s: &str| {
+        BASE64_STANDARD
+            .decode(s)
+            .map(Blob::new)
+            .map_err(|_| invalid())
+    };
+    Ok(match tag.as_str() {
+        "S" => AttributeValue::S(string()?),
+        "N" => AttributeValue::N(number(&string()?)?),
+        "B" => AttributeValue::B(blob(&string()?)?),
+        "BOOL" => AttributeValue::Bool(inner.as_bool().ok_or_else(invalid)?),
+        "NULL" => AttributeValue::Null(inner.as_bool().ok_or_else(invalid)?),
+        "SS" => AttributeValue::Ss(strings()?),
+        "NS" => AttributeValue::Ns(
+            strings()?
+                .iter()
+                .map(|n| number(n))
+                .collect::<ExprResult<_>>()?,
+        ),
+        "BS" => AttributeValue::Bs(
+            strings()?
+                .iter()
+                .map(|b| blob(b))
+                .collect::<ExprResult<_>>()?,
+        ),
+        "L" => AttributeValue::L(
+            inner
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(value_from_json)
+                .collect::<ExprResult<_>>()?,
+        ),
+        "M" => AttributeValue::M(item_from_json(inner)?),
+        _ => return Err(invalid()),
+    })
+}
+
+/// Check a number parses, as DynamoDB does on the way in.
+fn number(n: &str) -> ExprResult<String> {
+    match n.trim().parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(n.trim().to_string()),
+        _ => Err(format!(
+            "A value provided cannot be converted into a number: {n}"
+        )),
+    }
+}
+
+pub(super) fn item_from_json(value: &Value) -> ExprResult<Item> {
+    value
+        .as_object()
+        .ok_or_else(|| format!("Expected an attribute map, got: {value}"))?
+        .iter()
+        .map(|(k, v)| Ok((k.clone(), value_from_json(v)?)))
+        .collect()
+}
+
+pub(super) fn value_to_json(value: &AttributeValue) -> Value {
+    let blob = |b: &Blob| Value::String(BASE64_STANDARD.encode(b.as_ref()));
+    match value {
+        AttributeValue::S(s) => json!({ "S": s }),
+        AttributeValue::N(n) => json!({ "N": n }),
+        AttributeValue::B(b) => json!({ "B": blob(b) }),
+        AttributeValue::Bool(b) => json!({ "BOOL": b }),
+        AttributeValue::Null(b) => json!({ "NULL": b }),
+        AttributeValue::Ss(set) => json!({ "SS": set }),
+        AttributeValue::Ns(set) => json!({ "NS": set }),
+        AttributeValue::Bs(set) => json!({ "BS": set.iter().map(blob).collect::<Vec<_>>() }),
+        AttributeValue::L(list) => {
+            json!({ "L": list.iter().map(value_to_json).collect::<Vec<_>>() })
+        }
+        AttributeValue::M(map) => json!({ "M": item_to_json(map) }),
+        _ => json!({ "NULL": true }),
+    }
+}
+
+pub(super) fn item_to_json(item: &Item) -> Value {
+    Value::Object(
+        item.iter()
+            .map(|(k, v)| (k.clone(), value_to_json(v)))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+/// An item's size as DynamoDB bills and limits it: attribute names plus
+/// values, roughly byte for byte.
+pub(super) fn item_size(item: &Item) -> usize {
+    item.iter().map(|(k, v)| k.len() + value_size(v)).sum()
+}
+
+fn value_size(value: &AttributeValue) -> usize {
+    match value {
+        AttributeValue::S(s) => s.len(),
+        AttributeValue::N(n) => n.len().div_ceil(2) + 1,
+        AttributeValue::B(b) => b.as_ref().len(),
+        AttributeValue::Ss(set) => set.iter().map(String::len).sum(),
+        AttributeValue::Ns(set) => set.iter().map(|n| n.len().div_ceil(2) + 1).sum(),
+        AttributeValue::Bs(set) => set.iter().map(|b| b.as_ref().len()).sum(),
+        AttributeValue::L(list) => 3 + list.iter().map(|v| 1 + value_size(v)).sum::<usize>(),
+        AttributeValue::M(map) => {
+            3 + map
+                .iter()
+                .map(|(k, v)| 1 + k.len() + value_size(v))
+                .sum::<usize>()
+        }
+        _ => 1,
+    }
+}
@@ -0,0 +1,95 @@
+//! Stats milestones (`MILESTONE#<id>` / `#META`): recorded once each by the
+//! worker when it sees a user's stats cross a threshold (see
+//! `agon_core::milestones`), and read back to hydrate their feed cards.
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::records::MilestoneRecord;
+
+pub const TYPE_MILESTONE: &str = "milestone";
+
+impl Dao {
+    /// Record a milestone unless it already is, returning the stored record —
+    /// so a milestone detected twice (a redelivery, or stats dipping under a
+    /// threshold and climbing back) keeps its first `reached_at`.
+    #[tracing::instrument(skip(self, milestone), fields(milestone_id = %milestone.id))]
+    pub async fn record_milestone(
+        &self,
+        milestone: &MilestoneRecord,
+    ) -> DaoResult<MilestoneRecord> {
+        let item = to_item(
+            &Pk::Milestone(milestone.id.clone()),
+            &Sk::Meta,
+            TYPE_MILESTONE,
+            milestone,
+        )?;
+        let result = self
+            .client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(milestone.clone()),
+            Err(SdkError::ServiceError(se))
+                if matches!(se.err(), PutItemError::ConditionalCheckFailedException(_)) =>
+            {
+                let out = self
+                    .client
+                    .get_item()
+                    .table_name(self.table())
+                    .key(ATTR_PK, s(Pk::Milestone(milestone.id.clone()).to_string()))
+                    .key(ATTR_SK, s(Sk::Meta.to_string()))
+                    .consistent_read(true)
+                    .send()
+                    .await
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                out.item.map(from_item).transpose()?.ok_or_else(|| {
+                    DaoError::Malformed(format!("milestone {} missing after write", milestone.id))
+                })
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Fetch milestones by id in one batch read, keyed by id. An id with no
+    /// milestone has no entry.
+    #[tracing::instrument(skip(self))]
+    pub async fn batch_get_milestones(
+        &self,
+        ids: &[String],
+    ) -> DaoResult<HashMap<String, MilestoneRecord>> {
+        let mut seen = std::collections::HashSet::new();
+        let keys: Vec<_> = ids
+            .iter()
+            .filter(|id| seen.insert((*id).clone()))
+            .map(|id| {
+                HashMap::from([
+                    (
+                        ATTR_PK.to_string(),
+                        s(Pk::Milestone(id.clone()).to_string()),
+                    ),
+                    (ATTR_SK.to_string(), s(Sk::Meta.to_string())),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, None).await?;
+        let mut out = HashMap::with_capacity(items.len());
+        for item in items {
+            let record: MilestoneRecord = from_item(item)?;
+            out.insert(record.id.clone(), record);
+        }
+        Ok(out)
+    }
+}
@@ -17,13 +17,17 @@ pub mod client;
 pub mod error;
 pub mod item;
 pub mod keys;
+pub mod memory;
 pub mod page;
 pub mod records;
 
 pub mod accept;
+pub mod announcement;
 pub mod asset;
 pub mod audience;
 pub mod batch;
+pub mod block;
+pub mod club;
 pub mod device;
 pub mod feed;
 pub mod follow;
@@ -31,10 +35,19 @@ pub mod invitation;
 pub mod live_score_ops;
 pub mod match_ops;
 pub mod match_social;
+pub mod milestone;
 pub mod notification;
+pub mod notification_preferences;
+pub mod report;
+pub mod search_index;
+pub mod season;
 pub mod stats;
+pub mod suggestion;
+pub mod tag;
 pub mod team;
+pub mod team_join;
 pub mod user;
+pub mod visibility;
 
 use aws_sdk_dynamodb::error::SdkError;
 use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
@@ -55,6 +68,27 @@ pub(crate) fn is_transaction_conditional_failure(err: &SdkError<TransactWriteIte
     }
 }
 
+/// Whether the transact-item at `index` (0-based, matching the order items
+/// were added via `transact_items`) failed its own `ConditionExpression`, for
+/// a cancelled `TransactWriteItems`. `false` for any other kind of failure
+/// (network, throttling, ...) — those should propagate as real errors, not
+/// get misread as "the condition failed".
+pub(crate) fn is_transaction_item_conditional_failure(
+    err: &SdkError<TransactWriteItemsError>,
+    index: usize,
+) -> bool {
+    match err {
+        SdkError::ServiceError(se) => match se.err() {
+            TransactWriteItemsError::TransactionCanceledException(e) => {
+                e.cancellation_reasons().get(index).and_then(|r| r.code())
+                    == Some("ConditionalCheckFailed")
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 // Re-exported for the API layer once wired in; unused within the crate for now.
 #[allow(unused_imports)]
 pub use client::Dao;
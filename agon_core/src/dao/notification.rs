@@ -1,21 +1,26 @@
-//! Notification operations: write, list (newest first), unread count, mark read.
+//! Notification operations: write, group, list (newest first), unread count,
+//! mark read.
 //!
 //! Notifications live under the user partition (`USER#<uid>` / `NOTIF#<ts>#<id>`).
 //! Writes are normally produced by the async stream worker, not synchronously in
 //! request handlers. The unread badge count is a counter on the user's profile
 //! item, kept in step with the notifications' `is_read` flags.
 
-use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem, Update};
+use aws_sdk_dynamodb::types::{AttributeValue, Put, Select, TransactWriteItem, Update};
 
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ATTR_GSI1PK, ATTR_PK, ItemBuilder, s, to_item};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, s, to_item};
 use super::keys::{Pk, Sk};
 use super::page::Page;
 use super::records::NotificationRecord;
 
 pub const TYPE_NOTIFICATION: &str = "notification";
 
+/// How many times `group_notification` re-reads and retries when a concurrent
+/// write to the same group beats it.
+const GROUP_WRITE_ATTEMPTS: usize = 5;
+
 impl Dao {
     /// Append a notification for a user and bump their `unread_count`, atomically.
     ///
@@ -31,25 +36,7 @@ impl Dao {
         fields(user_id = %notif.user_id, notification_id = %notif.id)
     )]
     pub async fn create_notification(&self, notif: &NotificationRecord) -> DaoResult<()> {
-        let item = ItemBuilder::new(to_item(
-            &Pk::User(notif.user_id.clone()),
-            &Sk::Notification(notif.id.clone()),
-            TYPE_NOTIFICATION,
-            notif,
-        )?)
-        .gsi1(
-            format!("UNOTIFS#{}", notif.user_id),
-            format!("{}#{}", notif.created_at, notif.id),
-        )
-        .build();
-        let put = Put::builder()
-            .table_name(self.table())
-            .set_item(Some(item))
-            .condition_expression("attribute_not_exists(#pk)")
-            .expression_attribute_names("#pk", ATTR_PK)
-            .build()
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-
+        let put = self.new_notification_put(notif)?;
         let result = self
             .client
             .transact_write_items()
@@ -70,6 +57,87 @@ impl Dao {
         }
     }
 
+    /// Fold `notif`'s actor into the grouped notification with its id — a
+    /// "Sam and 12 others reacted to Sunday Derby" row — creating it for the
+    /// first actor.
+    ///
+    /// Joining an existing group replaces its `kind` with `notif`'s (so the
+    /// row shows the latest actor), appends the actor to `actor_ids` and moves
+    /// it to the top of the feed. `unread_count` stays one per unread row: a
+    /// new group bumps it, as does a read group coming back as unread; an
+    /// unread group joining another actor leaves it alone.
+    ///
+    /// **Idempotent per actor**: one already in `actor_ids` is a no-op. Each
+    /// write is conditioned on the state it read, and re-read on a clash.
+    #[tracing::instrument(
+        skip(self, notif),
+        fields(user_id = %notif.user_id, notification_id = %notif.id)
+    )]
+    pub async fn group_notification(&self, notif: &NotificationRecord) -> DaoResult<()> {
+        let actor = notif.kind.actor_user_id();
+        for _ in 0..GROUP_WRITE_ATTEMPTS {
+            let existing = self.get_notification(&notif.user_id, &notif.id).await?;
+            let writes = match existing {
+                Some(group) if group.actor_ids.iter().any(|id| id == actor) => return Ok(()),
+                Some(group) => self.join_group_writes(notif, actor, group.is_read)?,
+                None => {
+                    let first = NotificationRecord {
+                        actor_ids: vec![actor.to_string()],
+                        ..notif.clone()
+                    };
+                    vec![
+                        TransactWriteItem::builder()
+                            .put(self.new_notification_put(&first)?)
+                            .build(),
+                        TransactWriteItem::builder()
+                            .update(self.unread_delta(&notif.user_id, 1)?)
+                            .build(),
+                    ]
+                }
+            };
+            let result = self
+                .client
+                .transact_write_items()
+                .set_transact_items(Some(writes))
+                .send()
+                .await;
+            match result {
+                Ok(_) => return Ok(()),
+                // Someone else wrote the group since we read it; look again.
+                Err(e) if super::is_transaction_conditional_failure(&e) => continue,
+                Err(e) => return Err(DaoError::Dynamo(e.to_string())),
+            }
+        }
+        Err(DaoError::Conflict(format!(
+            "notification {} kept changing",
+            notif.id
+        )))
+    }
+
+    /// A single notification by id. Strongly consistent, since
+    /// `group_notification` conditions its write on what this returns.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> DaoResult<Option<NotificationRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(user_id.into()).to_string()))
+            .key(
+                ATTR_SK,
+                s(Sk::Notification(notification_id.into()).to_string()),
+            )
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        out.item.map(from_item).transpose()
+    }
+
     /// List a user's notifications, newest first, via GSI1.
     #[tracing::instrument(skip(self))]
     pub async fn list_notifications(
@@ -93,6 +161,39 @@ impl Dao {
         .await
     }
 
+    /// How many notifications a user has had since `since` (RFC3339) — new
+    /// ones and groups a new actor joined, read or not. For the digest's
+    /// activity line; reads only the count, not the rows.
+    #[tracing::instrument(skip(self))]
+    pub async fn count_notifications_since(&self, user_id: &str, since: &str) -> DaoResult<u64> {
+        let query = self
+            .client
+            .query()
+            .table_name(self.table())
+            .index_name("GSI1")
+            .key_condition_expression("#pk = :pk AND #sk > :since")
+            .expression_attribute_names("#pk", ATTR_GSI1PK)
+            .expression_attribute_names("#sk", ATTR_GSI1SK)
+            .expression_attribute_values(":pk", s(format!("UNOTIFS#{user_id}")))
+            .expression_attribute_values(":since", s(since))
+            .select(Select::Count);
+        let mut count = 0;
+        let mut start_key = None;
+        loop {
+            let page = query
+                .clone()
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            count += u64::try_from(page.count).unwrap_or_default();
+            match page.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => return Ok(count),
+            }
+        }
+    }
+
     /// The unread notification count for the bell badge (read off the profile
     /// counter). Zero if the user or counter is absent.
     #[tracing::instrument(skip(self))]
@@ -183,6 +284,69 @@ impl Dao {
         Ok(())
     }
 
+    /// A `Put` creating `notif`, guarded against it already existing.
+    fn new_notification_put(&self, notif: &NotificationRecord) -> DaoResult<Put> {
+        let item = ItemBuilder::new(to_item(
+            &Pk::User(notif.user_id.clone()),
+            &Sk::Notification(notif.id.clone()),
+            TYPE_NOTIFICATION,
+            notif,
+        )?)
+        .gsi1(
+            format!("UNOTIFS#{}", notif.user_id),
+            format!("{}#{}", notif.created_at, notif.id),
+        )
+        .build();
+        Put::builder()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+
+    /// Transaction items adding `actor` to the existing group `notif.id`,
+    /// which was read as `was_read`. Coming back unread from read also bumps
+    /// `unread_count`.
+    fn join_group_writes(
+        &self,
+        notif: &NotificationRecord,
+        actor: &str,
+        was_read: bool,
+    ) -> DaoResult<Vec<TransactWriteItem>> {
+        let kind: AttributeValue = serde_dynamo::to_attribute_value(&notif.kind)?;
+        let join = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(notif.user_id.clone()).to_string()))
+            .key(ATTR_SK, s(Sk::Notification(notif.id.clone()).to_string()))
+            .update_expression(
+                "SET #kind = :kind, created_at = :ts, #g1sk = :g1sk, is_read = :f, \
+                 actor_ids = list_append(actor_ids, :actors)",
+            )
+            .condition_expression("is_read = :was AND NOT contains(actor_ids, :actor)")
+            .expression_attribute_names("#kind", "kind")
+            .expression_attribute_names("#g1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":kind", kind)
+            .expression_attribute_values(":ts", s(notif.created_at.as_str()))
+            .expression_attribute_values(":g1sk", s(format!("{}#{}", notif.created_at, notif.id)))
+            .expression_attribute_values(":f", AttributeValue::Bool(false))
+            .expression_attribute_values(":was", AttributeValue::Bool(was_read))
+            .expression_attribute_values(":actors", AttributeValue::L(vec![s(actor)]))
+            .expression_attribute_values(":actor", s(actor))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let mut writes = vec![TransactWriteItem::builder().update(join).build()];
+        if was_read {
+            writes.push(
+                TransactWriteItem::builder()
+                    .update(self.unread_delta(&notif.user_id, 1)?)
+                    .build(),
+            );
+        }
+        Ok(writes)
+    }
+
     /// An `Update` adding `delta` to the profile's `unread_count`.
     fn unread_delta(&self, user_id: &str, delta: i64) -> DaoResult<Update> {
         Update::builder()
@@ -195,3 +359,113 @@ impl Dao {
             .map_err(|e| DaoError::Dynamo(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::NotificationKindRecord;
+
+    fn follow(actor: &str, at: &str) -> NotificationRecord {
+        NotificationRecord {
+            id: "followers-u1".into(),
+            user_id: "u1".into(),
+            is_read: false,
+            created_at: at.into(),
+            kind: NotificationKindRecord::Follow {
+                actor_user_id: actor.into(),
+            },
+            actor_ids: Vec::new(),
+        }
+    }
+
+    async fn unread(dao: &Dao) -> u64 {
+        dao.unread_notification_count("u1").await.unwrap()
+    }
+
+    async fn actors(dao: &Dao) -> Vec<String> {
+        let group = dao.get_notification("u1", "followers-u1").await.unwrap();
+        group.unwrap().actor_ids
+    }
+
+    #[tokio::test]
+    async fn a_group_counts_once_while_unread() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+
+        dao.group_notification(&follow("a", "2026-04-01T10:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        // Joining an unread group adds the actor, not a count.
+        dao.group_notification(&follow("b", "2026-04-01T11:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        // The same actor again (a redelivered event) is a no-op.
+        dao.group_notification(&follow("b", "2026-04-01T12:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        assert_eq!(actors(&dao).await, ["a", "b"]);
+        let group = dao
+            .get_notification("u1", "followers-u1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(group.created_at, "2026-04-01T11:00:00Z");
+
+        // Read, then joined: unread again, and counted again.
+        dao.mark_notification_read("u1", "followers-u1")
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 0);
+        dao.group_notification(&follow("c", "2026-04-02T10:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        let group = dao
+            .get_notification("u1", "followers-u1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!group.is_read);
+        assert_eq!(group.actor_ids, ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn a_join_from_a_stale_read_is_retried_without_double_counting() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        dao.group_notification(&follow("a", "2026-04-01T10:00:00Z"))
+            .await
+            .unwrap();
+        dao.mark_notification_read("u1", "followers-u1")
+            .await
+            .unwrap();
+
+        // A join that read the group as read, losing to one that got there
+        // first: its condition fails and nothing moves.
+        let late = follow("c", "2026-04-02T11:00:00Z");
+        let stale = dao.join_group_writes(&late, "c", true).unwrap();
+        dao.group_notification(&follow("b", "2026-04-02T10:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        let result = dao
+            .client
+            .transact_write_items()
+            .set_transact_items(Some(stale))
+            .send()
+            .await;
+        assert!(crate::dao::is_transaction_conditional_failure(
+            &result.unwrap_err()
+        ));
+        assert_eq!(unread(&dao).await, 1);
+
+        // Retried from a fresh read, it joins the now-unread group for free.
+        dao.group_notification(&late).await.unwrap();
+        assert_eq!(unread(&dao).await, 1);
+        assert_eq!(actors(&dao).await, ["a", "b", "c"]);
+    }
+}
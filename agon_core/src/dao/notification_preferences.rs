@@ -0,0 +1,193 @@
+//! Notification preferences and the pushes they defer.
+//!
+//! Both live under the user partition: the preferences as a single
+//! `#NOTIFPREFS` item (absent = the defaults; projected to GSI1 as
+//! `DIGEST#<frequency>` while subscribed to a digest), and pushes held back by quiet
+//! hours as `DEFERPUSH#<release_at>#<nid>` items, ordered by when they may go
+//! out. The worker writes the latter and, once quiet hours end, sends and
+//! deletes them (see the worker's `ReleaseDeferredPushes` workflow).
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_GSI1PK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::{DeferredPushRecord, DigestFrequencyRecord, NotificationPreferencesRecord};
+
+pub const TYPE_NOTIFICATION_PREFERENCES: &str = "notification_preferences";
+pub const TYPE_DEFERRED_PUSH: &str = "deferred_push";
+
+/// Page size when collecting due deferred pushes. A night's worth for one
+/// user is normally a single page; `list_due_deferred_pushes` follows the
+/// cursor regardless.
+const DEFERRED_PUSH_PAGE: u32 = 100;
+
+/// GSI1 partition listing the users subscribed to a digest frequency.
+fn digest_gsi1pk(frequency: DigestFrequencyRecord) -> String {
+    format!("DIGEST#{}", frequency.as_str())
+}
+
+impl Dao {
+    /// A user's notification preferences, or the defaults if they never saved
+    /// any.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_notification_preferences(
+        &self,
+        user_id: &str,
+    ) -> DaoResult<NotificationPreferencesRecord> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(user_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::NotificationPreferences.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => from_item(item),
+            None => Ok(NotificationPreferencesRecord {
+                user_id: user_id.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Replace a user's notification preferences wholesale. Switching the
+    /// digest off drops the GSI1 projection with the rest of the old item.
+    #[tracing::instrument(skip(self, prefs), fields(user_id = %prefs.user_id))]
+    pub async fn put_notification_preferences(
+        &self,
+        prefs: &NotificationPreferencesRecord,
+    ) -> DaoResult<()> {
+        let mut item = ItemBuilder::new(to_item(
+            &Pk::User(prefs.user_id.clone()),
+            &Sk::NotificationPreferences,
+            TYPE_NOTIFICATION_PREFERENCES,
+            prefs,
+        )?);
+        if prefs.digest != DigestFrequencyRecord::Off {
+            item = item.gsi1(
+                digest_gsi1pk(prefs.digest),
+                Pk::User(prefs.user_id.clone()).to_string(),
+            );
+        }
+        let item = item.build();
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// One page of the users subscribed to a digest, as their preferences.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_digest_subscribers(
+        &self,
+        frequency: DigestFrequencyRecord,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<NotificationPreferencesRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(digest_gsi1pk(frequency))),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Hold a push until `push.release_at`. A plain put: the key is the
+    /// notification's, so a redelivery (or a grouped notification pushing
+    /// again) overwrites rather than queueing a second copy.
+    #[tracing::instrument(
+        skip(self, push),
+        fields(user_id = %push.user_id, notification_id = %push.notification_id)
+    )]
+    pub async fn defer_push(&self, push: &DeferredPushRecord) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::User(push.user_id.clone()),
+            &Sk::DeferredPush {
+                release_at: push.release_at.clone(),
+                notification_id: push.notification_id.clone(),
+            },
+            TYPE_DEFERRED_PUSH,
+            push,
+        )?;
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Every deferred push for a user released at or before `until`, oldest
+    /// first.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_due_deferred_pushes(
+        &self,
+        user_id: &str,
+        until: &str,
+    ) -> DaoResult<Vec<DeferredPushRecord>> {
+        let prefix = Sk::deferred_push_prefix();
+        // `~` sorts after the `#` that follows the timestamp, so this upper
+        // bound takes in every push released exactly at `until`.
+        let upper = format!("{prefix}{until}~");
+        let mut due = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .query_page(
+                    self.client
+                        .query()
+                        .table_name(self.table())
+                        .key_condition_expression("#pk = :pk AND SK BETWEEN :lo AND :hi")
+                        .expression_attribute_names("#pk", ATTR_PK)
+                        .expression_attribute_values(":pk", s(Pk::User(user_id.into()).to_string()))
+                        .expression_attribute_values(":lo", s(prefix.clone()))
+                        .expression_attribute_values(":hi", s(upper.clone())),
+                    cursor.as_deref(),
+                    DEFERRED_PUSH_PAGE,
+                )
+                .await?;
+            due.extend(page.items);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(due),
+            }
+        }
+    }
+
+    /// Drop a deferred push once sent. Idempotent: deleting one that's
+    /// already gone is fine.
+    #[tracing::instrument(skip(self, push), fields(user_id = %push.user_id))]
+    pub async fn delete_deferred_push(&self, push: &DeferredPushRecord) -> DaoResult<()> {
+        self.client
+            .delete_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(push.user_id.clone()).to_string()))
+            .key(
+                ATTR_SK,
+                s(Sk::DeferredPush {
+                    release_at: push.release_at.clone(),
+                    notification_id: push.notification_id.clone(),
+                }
+                .to_string()),
+            )
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+}
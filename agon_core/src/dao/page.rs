@@ -1,4 +1,4 @@
-//! Cursor pagination for Query operations.
+//! Cursor pagination for Query (and the odd Scan) operations.
 //!
 //! A cursor is DynamoDB's `LastEvaluatedKey` (an attribute map) serialized to
 //! JSON and base64url-encoded — opaque to clients, decoded back into the
@@ -7,6 +7,7 @@
 use std::collections::HashMap;
 
 use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
+use aws_sdk_dynamodb::operation::scan::builders::ScanFluentBuilder;
 use aws_sdk_dynamodb::types::AttributeValue;
 use base64::{Engine, prelude::BASE64_URL_SAFE};
 use serde::de::DeserializeOwned;
@@ -57,6 +58,67 @@ impl Dao {
 
         Ok(Page { items, next_cursor })
     }
+
+    /// [`query_page`](Self::query_page) for a query with a filter expression.
+    /// DynamoDB applies `Limit` before the filter, so one round-trip can come
+    /// back short (even empty) with more to read; this keeps reading until
+    /// `limit` items pass or the results run out. Each read asks only for
+    /// what's still missing, so the cursor never skips an item.
+    #[tracing::instrument(skip(self))]
+    pub(super) async fn query_page_filtered<T: DeserializeOwned>(
+        &self,
+        query: QueryFluentBuilder,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<T>> {
+        let mut page = self.query_page(query.clone(), cursor, limit).await?;
+        while page.items.len() < limit as usize {
+            let Some(cursor) = page.next_cursor.take() else {
+                break;
+            };
+            let missing = limit - page.items.len() as u32;
+            let next: Page<T> = self
+                .query_page(query.clone(), Some(&cursor), missing)
+                .await?;
+            page.items.extend(next.items);
+            page.next_cursor = next.next_cursor;
+        }
+        Ok(page)
+    }
+
+    /// [`query_page`](Self::query_page) for a prepared Scan. `limit` caps the
+    /// items *read*, before any filter expression, so a page can come back
+    /// short (even empty) with a cursor still to follow.
+    #[tracing::instrument(skip(self))]
+    pub(super) async fn scan_page<T: DeserializeOwned>(
+        &self,
+        scan: ScanFluentBuilder,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<T>> {
+        let start_key = match cursor {
+            Some(c) => Some(decode_cursor(c)?),
+            None => None,
+        };
+
+        let out = scan
+            .limit(limit as i32)
+            .set_exclusive_start_key(start_key)
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let items = out
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .map(from_item)
+            .collect::<DaoResult<Vec<T>>>()?;
+
+        let next_cursor = out.last_evaluated_key.map(encode_cursor).transpose()?;
+
+        Ok(Page { items, next_cursor })
+    }
 }
 
 /// Encode a `LastEvaluatedKey` map into an opaque cursor string.
@@ -28,6 +28,41 @@ pub struct LocationRecord {
 pub struct HeaderPhotoRecord {
     pub asset_id: String,
     pub url: String,
+    /// Set while the photo is hidden pending moderation review (see
+    /// `ReportCaseRecord`); hidden photos are left out of every read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<String>,
+}
+
+/// Who may see a match, team or profile. Stored on `MatchRecord`,
+/// `TeamRecord` and `UserRecord`; absent on records written before it
+/// existed, which read back as `Public`.
+///
+/// - `Public` — anyone.
+/// - `Followers` — the participants (a match's creator and linked players, a
+///   team's members, a profile's owner) plus anyone following one of them, or
+///   for a match, one of its side teams or their clubs.
+/// - `Participants` — the participants only.
+///
+/// The checks themselves live in `dao::visibility`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Followers,
+    Participants,
+}
+
+impl Visibility {
+    /// The stored/indexed tag, matching the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Followers => "followers",
+            Visibility::Participants => "participants",
+        }
+    }
 }
 
 /// A match score. Tagged union mirroring the sport's scoring shape.
@@ -199,6 +234,11 @@ pub struct ConfirmedScoreRecord {
     pub score: ScoreRecord,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub winner_side_id: Option<String>,
+    /// When the last side confirmed it — the sort time of the match's result
+    /// feed entries. Absent on scores confirmed before it was recorded, which
+    /// get no result entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmed_at: Option<String>,
 }
 
 /// A submitted score awaiting confirmation, with per-side confirmations so far.
@@ -288,6 +328,17 @@ pub struct AuthGuardRecord {
     pub user_id: String,
 }
 
+/// `TEAM#<teamId>` / `MEMBERGUARD#<userId>` — the one-current-membership
+/// guard for a user on a team.
+///
+/// Written with every membership that links a user, under a conditional put
+/// on its key, and deleted when that membership ends; it records the
+/// `membership_id` it stands for so only that membership's end releases it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberGuardRecord {
+    pub membership_id: String,
+}
+
 /// `USER#<id>` / `#PROFILE` — the user profile item.
 ///
 /// Counts are denormalized and maintained via atomic `ADD` (see follow ops).
@@ -311,6 +362,27 @@ pub struct UserRecord {
     pub unread_count: u64,
     #[serde(default)]
     pub stats: HashMap<String, UserSportStatsRecord>,
+    /// Who may see the profile's stats and follow lists, and find it in
+    /// search. The name and image stay visible wherever the user appears
+    /// (a match roster, a comment).
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// May read the moderation queue and resolve reports. Granted out of
+    /// band; nothing in the API sets it.
+    #[serde(default)]
+    pub is_moderator: bool,
+    /// Set when a moderator suspends the account: it can still read, but no
+    /// longer post, comment, like, follow or invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspended_at: Option<String>,
+    /// Set while the profile is hidden by moderation: to everyone but its
+    /// owner it reads like a participants-only profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<String>,
+    /// Set while the profile image is hidden by moderation; the image is
+    /// left out of every read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_image_hidden_at: Option<String>,
     pub created_at: String,
 }
 
@@ -324,6 +396,47 @@ pub struct UserFollowRecord {
     /// The user doing the following.
     pub follower_id: String,
     pub created_at: String,
+    /// When the follower asked, if this edge came from an approved follow
+    /// request (the followee's profile was private). Tells the notifier to
+    /// tell the follower about the approval rather than the followee about a
+    /// new follower.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_at: Option<String>,
+}
+
+/// `USER#<followeeId>` / `FOLLOWREQ#<followerId>` — a pending request to follow
+/// a private profile (one whose `visibility` isn't `public`). Approval swaps it
+/// for a `UserFollowRecord`; denial deletes it. Follower counts don't move
+/// until then.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FollowRequestRecord {
+    /// The user being asked.
+    pub followee_id: String,
+    /// The user asking to follow.
+    pub follower_id: String,
+    pub created_at: String,
+}
+
+/// `USER#<userId>` / `BLOCK#<blockedUserId>` — `user_id` blocked
+/// `blocked_user_id`. Acts both ways: neither can follow, comment on or like
+/// the other's matches, or invite the other. Projected into GSI1
+/// (`UBLOCKEDBY#<blockedUserId>`) so a user's blocks can be checked from
+/// either side.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlockRecord {
+    pub user_id: String,
+    pub blocked_user_id: String,
+    pub created_at: String,
+}
+
+/// `USER#<userId>` / `MUTE#<mutedUserId>` — `user_id` muted
+/// `muted_user_id`: their matches drop out of `user_id`'s feed and their
+/// actions stop notifying `user_id`. One-way and silent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MuteRecord {
+    pub user_id: String,
+    pub muted_user_id: String,
+    pub created_at: String,
 }
 
 /// `TEAM#<teamId>` / `FOLLOWER#<userId>` — a user→team follow edge. Projected
@@ -335,8 +448,18 @@ pub struct TeamFollowRecord {
     pub created_at: String,
 }
 
+/// `CLUB#<clubId>` / `FOLLOWER#<userId>` — a user→club follow edge. Projected
+/// into GSI3 (`UFOLLOWS_CLUB#<userId>`) for "clubs I follow".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClubFollowRecord {
+    pub club_id: String,
+    pub follower_id: String,
+    pub created_at: String,
+}
+
 /// `TEAM#<teamId>` / `#META` — team metadata. `follower_count` is denormalized
-/// and maintained by the follow ops.
+/// and maintained by the follow ops. A team owned by a club is projected into
+/// GSI1 (`CTEAMS#<clubId>`) so the club can list its teams.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TeamRecord {
     pub id: String,
@@ -345,6 +468,37 @@ pub struct TeamRecord {
     pub invite_token: Option<String>,
     #[serde(default)]
     pub follower_count: u64,
+    /// The club this team belongs to, if any. Club admins inherit admin on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub club_id: Option<String>,
+    /// When the team was archived. An archived team keeps its meta (so past
+    /// matches still show its name) but is unlisted and can't be followed or
+    /// joined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<String>,
+    /// Who may see the team, its squad, seasons and followers.
+    #[serde(default)]
+    pub visibility: Visibility,
+    pub created_at: String,
+}
+
+/// `CLUB#<clubId>` / `#META` — a club grouping several teams (e.g. a 1st XI,
+/// 2nd XI and juniors). `follower_count` counts the club's own followers,
+/// maintained by the follow ops; the teams' followers stay on the teams.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClubRecord {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub follower_count: u64,
+    pub created_at: String,
+}
+
+/// `CLUB#<clubId>` / `CLUBADMIN#<userId>` — a club admin.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClubAdminRecord {
+    pub club_id: String,
+    pub user_id: String,
     pub created_at: String,
 }
 
@@ -352,7 +506,11 @@ pub struct TeamRecord {
 /// shared membership shape (user or external, with optional invitation) as
 /// opaque JSON the API layer interprets, plus the team-specific role. Projected
 /// into GSI1 (`UTEAMS#<userId>`) for "my teams" — only for members with a
-/// resolved `user_id`.
+/// resolved, current `user_id`.
+///
+/// Removing a member doesn't delete the item: it sets `left_at` and drops the
+/// GSI1 projection, so the squad history (who played when, in what role)
+/// survives. Rejoining creates a new membership.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TeamMemberRecord {
     /// The team this membership belongs to. Lets "my teams" (the GSI1 query over
@@ -372,6 +530,48 @@ pub struct TeamMemberRecord {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub invitation: Option<EmbeddedInvitationRecord>,
     pub created_at: String,
+    /// When the member left (or was removed from) the team. None while current.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left_at: Option<String>,
+    /// Roles this membership held before its current `role`, oldest first —
+    /// so a past season shows the role held then, not today's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_roles: Vec<PreviousRoleRecord>,
+}
+
+/// A role a team membership held until `until` (when it changed).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PreviousRoleRecord {
+    /// `admin` | `member`.
+    pub role: String,
+    pub until: String,
+}
+
+/// `TEAM#<teamId>` / `SEASON#<seasonId>` — a named date range on a team.
+/// Squads and stats are derived per season from membership dates and the
+/// team's matches; nothing is copied into the season itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamSeasonRecord {
+    pub id: String,
+    pub team_id: String,
+    /// e.g. "2024" or "Spring 2025".
+    pub name: String,
+    pub starts_at: String,
+    /// Exclusive. None while the season is open-ended (the current one).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<String>,
+    pub created_at: String,
+}
+
+/// `TEAM#<teamId>` / `TMATCH#<matchId>` — the team played (or is playing) in
+/// this match as `side_id`. Written in `create_match`'s transaction, one per
+/// side with a `team_id`. Only a pointer: dates and results are read from the
+/// match itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamMatchRecord {
+    pub team_id: String,
+    pub match_id: String,
+    pub side_id: String,
 }
 
 /// `MATCH#<matchId>` / `#META` — match metadata + resolved scores + social
@@ -400,6 +600,13 @@ pub struct MatchRecord {
     pub starts_at: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub location: Option<LocationRecord>,
+    /// Where it's played, by name (e.g. "Hackney Marshes, pitch 4").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    /// The league or competition it's part of, if any — free text, the same
+    /// for every match in it, so search can filter and count by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub league: Option<String>,
     /// This match's sides, keyed by `side_id` — a DynamoDB map, not a list,
     /// so a single side's `player_count`/`roster_preview` can be updated in
     /// place by key (`Dao::refresh_side_roster_previews`) without needing to
@@ -418,10 +625,20 @@ pub struct MatchRecord {
     /// A score awaiting confirmation. None if none.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pending_score: Option<PendingScoreRecord>,
-    // Denormalized social counts, maintained via atomic ADD.
+    // Denormalized social counts, maintained via atomic ADD. `like_count` is
+    // the 👍 reaction's count; the other reactions have one counter each
+    // (see `ReactionRecord::count_attr`).
     #[serde(default)]
     pub like_count: u64,
     #[serde(default)]
+    pub fire_count: u64,
+    #[serde(default)]
+    pub clap_count: u64,
+    #[serde(default)]
+    pub laugh_count: u64,
+    #[serde(default)]
+    pub wow_count: u64,
+    #[serde(default)]
     pub comment_count: u64,
     /// The seq of the last appended `LIVEEVT#` (0 = no live events yet).
     /// Doubles as the optimistic-concurrency + ordering gate for
@@ -437,9 +654,47 @@ pub struct MatchRecord {
     /// configured.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub format: Option<MatchFormatRecord>,
+    /// Why and by whom the match was cancelled. Set together with
+    /// `status = "cancelled"` by `Dao::cancel_match`; `None` for a match
+    /// that was never cancelled (or was marked cancelled through a plain
+    /// status edit, which records no reason).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancellation: Option<MatchCancellationRecord>,
+    /// Every time the match was moved to a new `starts_at`, oldest first
+    /// (appended by `Dao::postpone_match`). `#[serde(default)]` for records
+    /// written before this field existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub postponements: Vec<MatchPostponementRecord>,
+    /// Who may see the match. Also caps the fan-out audience: a
+    /// participants-only match reaches no follower's feed.
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Set while the match is hidden by moderation: only its participants
+    /// can see it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<String>,
     pub created_at: String,
 }
 
+/// Embedded in `MatchRecord::cancellation`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchCancellationRecord {
+    pub reason: String,
+    pub by_user_id: String,
+    pub at: String,
+}
+
+/// One entry of `MatchRecord::postponements`: the match moved from
+/// `from_starts_at` to `to_starts_at`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchPostponementRecord {
+    pub from_starts_at: String,
+    pub to_starts_at: String,
+    pub reason: String,
+    pub by_user_id: String,
+    pub at: String,
+}
+
 /// Mirrors `agon_service::match_format::MatchFormat`, sport-first
 /// discriminated like `LiveEventPayloadRecord` — a typed DAO enum rather
 /// than opaque JSON, so a variant added on the API side and forgotten here
@@ -915,14 +1170,112 @@ pub struct ScoreSubmissionRecord {
     pub responses: Vec<ScoreResponseRecord>,
 }
 
-/// `MATCH#<matchId>` / `LIKE#<userId>` — a like on a match.
+/// `MATCH#<matchId>` / `LIKE#<userId>` — a user's reaction on a match. Named
+/// for the likes it started out as: an edge written before reactions existed
+/// has no `reaction` and reads back as 👍.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MatchLikeRecord {
     pub match_id: String,
     pub user_id: String,
+    #[serde(default)]
+    pub reaction: ReactionRecord,
+    /// When the reaction was first made or last changed.
+    pub created_at: String,
+}
+
+/// `MATCH#<matchId>` / `CREACT#<commentId>#<userId>` — a user's reaction on a
+/// comment or reply.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommentReactionRecord {
+    pub match_id: String,
+    pub comment_id: String,
+    pub user_id: String,
+    pub reaction: ReactionRecord,
+    /// When the reaction was first made or last changed.
     pub created_at: String,
 }
 
+/// The fixed set of reactions on a match or comment. `Like` is 👍 — what a
+/// like has always been.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionRecord {
+    #[default]
+    Like,
+    Fire,
+    Clap,
+    Laugh,
+    Wow,
+}
+
+impl ReactionRecord {
+    pub const ALL: [ReactionRecord; 5] = [
+        ReactionRecord::Like,
+        ReactionRecord::Fire,
+        ReactionRecord::Clap,
+        ReactionRecord::Laugh,
+        ReactionRecord::Wow,
+    ];
+
+    /// As stored (the serde name), for condition and filter expressions.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReactionRecord::Like => "like",
+            ReactionRecord::Fire => "fire",
+            ReactionRecord::Clap => "clap",
+            ReactionRecord::Laugh => "laugh",
+            ReactionRecord::Wow => "wow",
+        }
+    }
+
+    /// The counter attribute on the reacted-to match or comment item.
+    pub fn count_attr(self) -> &'static str {
+        match self {
+            ReactionRecord::Like => "like_count",
+            ReactionRecord::Fire => "fire_count",
+            ReactionRecord::Clap => "clap_count",
+            ReactionRecord::Laugh => "laugh_count",
+            ReactionRecord::Wow => "wow_count",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            ReactionRecord::Like => "👍",
+            ReactionRecord::Fire => "🔥",
+            ReactionRecord::Clap => "👏",
+            ReactionRecord::Laugh => "😂",
+            ReactionRecord::Wow => "😮",
+        }
+    }
+}
+
+impl MatchRecord {
+    /// The denormalized count of `reaction`.
+    pub fn reaction_count(&self, reaction: ReactionRecord) -> u64 {
+        match reaction {
+            ReactionRecord::Like => self.like_count,
+            ReactionRecord::Fire => self.fire_count,
+            ReactionRecord::Clap => self.clap_count,
+            ReactionRecord::Laugh => self.laugh_count,
+            ReactionRecord::Wow => self.wow_count,
+        }
+    }
+}
+
+impl CommentRecord {
+    /// The denormalized count of `reaction`.
+    pub fn reaction_count(&self, reaction: ReactionRecord) -> u64 {
+        match reaction {
+            ReactionRecord::Like => self.like_count,
+            ReactionRecord::Fire => self.fire_count,
+            ReactionRecord::Clap => self.clap_count,
+            ReactionRecord::Laugh => self.laugh_count,
+            ReactionRecord::Wow => self.wow_count,
+        }
+    }
+}
+
 /// A comment on a match. The base item lives in the match partition, addressed
 /// by id — a top-level comment (`MATCH#<matchId>` / `COMMENT#<cid>`) or a reply
 /// (`MATCH#<matchId>` / `REPLY#<rid>`); time-ordered listing is via GSI1
@@ -945,8 +1298,68 @@ pub struct CommentRecord {
     pub edited_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<String>,
+    /// Set while the comment is hidden pending moderation review; everyone
+    /// but its author sees it as a tombstone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<String>,
     #[serde(default)]
     pub reply_count: u64,
+    // Per-reaction counts, maintained via atomic ADD as on `MatchRecord`.
+    #[serde(default)]
+    pub like_count: u64,
+    #[serde(default)]
+    pub fire_count: u64,
+    #[serde(default)]
+    pub clap_count: u64,
+    #[serde(default)]
+    pub laugh_count: u64,
+    #[serde(default)]
+    pub wow_count: u64,
+    /// The `@`-mentions in `text`, resolved to user ids when the comment was
+    /// written or last edited — so a rename doesn't break them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<CommentMentionRecord>,
+    /// The distinct `#tags` in `text`, lowercased and without the `#`. Each
+    /// one is indexed under `TAG#<tag>` (see `TagCommentRecord`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// One `@`-mention in a comment: who it resolved to, and where the `@handle`
+/// token sits in the text (byte offsets), so a client can render the user's
+/// current name in its place.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommentMentionRecord {
+    pub user_id: String,
+    pub start: u32,
+    pub len: u32,
+}
+
+/// `TAG#<tag>` / `COMMENT#<cid>` — a comment or reply carrying a hashtag.
+/// Projects to GSI1 (`TAGCOMMENTS#<tag>` / `<created_at>#<cid>`) so a tag's
+/// comments list newest first. Written and removed alongside the comment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagCommentRecord {
+    pub tag: String,
+    pub match_id: String,
+    pub comment_id: String,
+    /// The top-level comment for a reply; `None` for a top-level comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    pub created_at: String,
+}
+
+/// `TAG#<tag>` / `TAGMATCH#<mid>` — a match whose comments use a hashtag.
+/// Projects to GSI1 (`TAGMATCHES#<tag>` / `<last_tagged_at>#<mid>`) so a tag's
+/// matches list by most recent use. `comment_count` drops as tagged comments
+/// are edited or deleted; a row at zero is left in place and skipped on read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagMatchRecord {
+    pub tag: String,
+    pub match_id: String,
+    #[serde(default)]
+    pub comment_count: i64,
+    pub last_tagged_at: String,
 }
 
 /// `INVITATION#<invId>` / `#META` — a standalone invitation entity.
@@ -955,6 +1368,12 @@ pub struct CommentRecord {
 /// and to GSI2 (`TOKEN#<token>`) for token-kind invitations. `kind` and
 /// `context` are opaque JSON owned by the API layer (the `InvitationKind` /
 /// `InvitationContext` unions).
+///
+/// A token invitation with `link` set is a reusable team invite link rather
+/// than a one-off invite: it stays `pending` for its whole life (each
+/// redemption bumps `link.use_count` instead of accepting it), and projects to
+/// GSI1 as `TLINKS#<teamId>` (sort `<invited_at>`) so a team can list its
+/// links.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct InvitationRecord {
     pub id: String,
@@ -969,12 +1388,71 @@ pub struct InvitationRecord {
     /// The bearer token, for a token/external invitation (drives token lookup).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub invite_token: Option<String>,
+    /// Where to email a token invitation's link, if the inviter gave an
+    /// address. The worker sends it when the invitation is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitee_email: Option<String>,
     pub kind: InvitationKindRecord,
     /// What the invitation is to (match/team).
     pub context: InvitationContextRecord,
     pub invited_at: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub responded_at: Option<String>,
+    /// Present only on a multi-use team invite link (see above).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<InviteLinkRecord>,
+}
+
+/// The multi-use settings of a team invite link, embedded on its
+/// `InvitationRecord`. Expiry and the use limit are enforced by the redeem
+/// transaction's condition, so concurrent redemptions can't overshoot
+/// `max_uses`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InviteLinkRecord {
+    /// Admin-facing label (e.g. "Freshers' fair 2026").
+    pub name: String,
+    /// RFC3339; None never expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// None is unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+    /// Redemptions so far — a join, or a join request when approval is
+    /// required (a later denial doesn't hand the use back).
+    #[serde(default)]
+    pub use_count: u32,
+    /// Role a redeemer joins with: `admin` | `member`.
+    pub default_role: String,
+    /// True => redeeming files a join request for an admin to approve instead
+    /// of joining straight away.
+    #[serde(default)]
+    pub requires_approval: bool,
+}
+
+/// `TEAM#<teamId>` / `JOINREQ#<userId>` — a user's request to join a team,
+/// either asked for directly or filed by redeeming an approval-gated invite
+/// link. Re-requesting after a denial overwrites the row with a fresh `id`, so
+/// each request gets its own notifications. Projected into GSI1 as
+/// `TJOINREQS#<teamId>` (sort `<requested_at>#<userId>`) while pending.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JoinRequestRecord {
+    /// Unique per request (not per user), for deterministic notification ids.
+    pub id: String,
+    pub team_id: String,
+    pub user_id: String,
+    /// "pending" | "approved" | "denied".
+    pub status: String,
+    /// Role granted on approval: `admin` | `member`.
+    pub role: String,
+    /// The invite link this request came through, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via_invitation_id: Option<String>,
+    pub requested_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responded_at: Option<String>,
+    /// The admin who approved/denied it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responded_by_user_id: Option<String>,
 }
 
 /// `USER#<uid>` / `NOTIF#<ts>#<nid>` — a notification for a user.
@@ -987,8 +1465,24 @@ pub struct NotificationRecord {
     pub id: String,
     pub user_id: String,
     pub is_read: bool,
+    /// When the notification was created or, for a grouped one, last joined
+    /// by a new actor.
     pub created_at: String,
+    /// The latest event. On a grouped notification, `kind`'s actor is the
+    /// most recent one to join.
     pub kind: NotificationKindRecord,
+    /// Everyone a grouped notification stands for, in the order they joined
+    /// (see `Dao::group_notification`); empty on an ungrouped one. Doubles as
+    /// the check that a redelivered event doesn't count its actor twice.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actor_ids: Vec<String>,
+}
+
+impl NotificationRecord {
+    /// How many people the notification stands for: 1 unless it's grouped.
+    pub fn actor_count(&self) -> usize {
+        self.actor_ids.len().max(1)
+    }
 }
 
 /// The kind of notification. Mirrors the API's `NotificationKind` union but is
@@ -1017,6 +1511,18 @@ pub enum NotificationKindRecord {
     Follow {
         actor_user_id: String,
     },
+    /// Someone asked to follow your private profile. `actor_user_id` is the
+    /// requester (whose id addresses the request to approve or deny).
+    FollowRequest {
+        actor_user_id: String,
+    },
+    /// Your request to follow a private profile was approved. `actor_user_id`
+    /// is the user you now follow.
+    FollowRequestApproved {
+        actor_user_id: String,
+    },
+    /// Written before reactions replaced likes; kept so old rows still read.
+    /// New likes arrive as `Reaction`.
     Like {
         actor_user_id: String,
         match_id: String,
@@ -1058,6 +1564,356 @@ pub enum NotificationKindRecord {
         match_name: String,
         submission_id: String,
     },
+    /// Someone asked to join a team you admin. Sent to every admin;
+    /// `actor_user_id` is the requester (whose id addresses the request).
+    TeamJoinRequest {
+        actor_user_id: String,
+        team_id: String,
+        team_name: String,
+        request_id: String,
+    },
+    /// Your request to join a team was approved. `actor_user_id` is the admin
+    /// who approved it.
+    TeamJoinApproved {
+        actor_user_id: String,
+        team_id: String,
+        team_name: String,
+    },
+    /// A match you play in or follow was cancelled. `actor_user_id` is the
+    /// organizer who cancelled it.
+    MatchCancelled {
+        actor_user_id: String,
+        match_id: String,
+        match_name: String,
+        reason: String,
+    },
+    /// A match you play in or follow was moved to `starts_at`.
+    /// `actor_user_id` is the organizer who postponed it.
+    MatchPostponed {
+        actor_user_id: String,
+        match_id: String,
+        match_name: String,
+        reason: String,
+        starts_at: String,
+    },
+    /// Someone `@`-mentioned you in a comment or reply. `comment_id` is that
+    /// comment's own id; `parent_comment_id` is set for a reply.
+    Mention {
+        actor_user_id: String,
+        match_id: String,
+        comment_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_comment_id: Option<String>,
+        preview: String,
+    },
+    /// Someone reacted to your match, or to your comment on it (`comment_id`
+    /// set). Grouped per match: `actor_user_id` is the latest to react.
+    Reaction {
+        actor_user_id: String,
+        match_id: String,
+        match_name: String,
+        reaction: ReactionRecord,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        comment_id: Option<String>,
+    },
+}
+
+impl NotificationKindRecord {
+    /// The user whose action triggered the notification.
+    pub fn actor_user_id(&self) -> &str {
+        match self {
+            NotificationKindRecord::MatchInvitation { actor_user_id, .. }
+            | NotificationKindRecord::TeamInvitation { actor_user_id, .. }
+            | NotificationKindRecord::InvitationAccepted { actor_user_id, .. }
+            | NotificationKindRecord::Follow { actor_user_id }
+            | NotificationKindRecord::FollowRequest { actor_user_id }
+            | NotificationKindRecord::FollowRequestApproved { actor_user_id }
+            | NotificationKindRecord::Like { actor_user_id, .. }
+            | NotificationKindRecord::Comment { actor_user_id, .. }
+            | NotificationKindRecord::Reply { actor_user_id, .. }
+            | NotificationKindRecord::ScoreSubmitted { actor_user_id, .. }
+            | NotificationKindRecord::ScoreConfirmed { actor_user_id, .. }
+            | NotificationKindRecord::TeamJoinRequest { actor_user_id, .. }
+            | NotificationKindRecord::TeamJoinApproved { actor_user_id, .. }
+            | NotificationKindRecord::MatchCancelled { actor_user_id, .. }
+            | NotificationKindRecord::MatchPostponed { actor_user_id, .. }
+            | NotificationKindRecord::Mention { actor_user_id, .. }
+            | NotificationKindRecord::Reaction { actor_user_id, .. } => actor_user_id,
+        }
+    }
+
+    /// Which kind of notification this is, without its payload — what
+    /// per-kind preferences are keyed by.
+    pub fn notification_type(&self) -> NotificationTypeRecord {
+        match self {
+            NotificationKindRecord::MatchInvitation { .. } => {
+                NotificationTypeRecord::MatchInvitation
+            }
+            NotificationKindRecord::TeamInvitation { .. } => NotificationTypeRecord::TeamInvitation,
+            NotificationKindRecord::InvitationAccepted { .. } => {
+                NotificationTypeRecord::InvitationAccepted
+            }
+            NotificationKindRecord::Follow { .. } => NotificationTypeRecord::Follow,
+            NotificationKindRecord::FollowRequest { .. } => NotificationTypeRecord::FollowRequest,
+            NotificationKindRecord::FollowRequestApproved { .. } => {
+                NotificationTypeRecord::FollowRequestApproved
+            }
+            NotificationKindRecord::Like { .. } => NotificationTypeRecord::Like,
+            NotificationKindRecord::Comment { .. } => NotificationTypeRecord::Comment,
+            NotificationKindRecord::Reply { .. } => NotificationTypeRecord::Reply,
+            NotificationKindRecord::ScoreSubmitted { .. } => NotificationTypeRecord::ScoreSubmitted,
+            NotificationKindRecord::ScoreConfirmed { .. } => NotificationTypeRecord::ScoreConfirmed,
+            NotificationKindRecord::TeamJoinRequest { .. } => {
+                NotificationTypeRecord::TeamJoinRequest
+            }
+            NotificationKindRecord::TeamJoinApproved { .. } => {
+                NotificationTypeRecord::TeamJoinApproved
+            }
+            NotificationKindRecord::MatchCancelled { .. } => NotificationTypeRecord::MatchCancelled,
+            NotificationKindRecord::MatchPostponed { .. } => NotificationTypeRecord::MatchPostponed,
+            NotificationKindRecord::Mention { .. } => NotificationTypeRecord::Mention,
+            NotificationKindRecord::Reaction { .. } => NotificationTypeRecord::Reaction,
+        }
+    }
+
+    /// The match the notification is about, if any.
+    pub fn match_id(&self) -> Option<&str> {
+        match self {
+            NotificationKindRecord::MatchInvitation { match_id, .. }
+            | NotificationKindRecord::Like { match_id, .. }
+            | NotificationKindRecord::Comment { match_id, .. }
+            | NotificationKindRecord::Reply { match_id, .. }
+            | NotificationKindRecord::ScoreSubmitted { match_id, .. }
+            | NotificationKindRecord::ScoreConfirmed { match_id, .. }
+            | NotificationKindRecord::MatchCancelled { match_id, .. }
+            | NotificationKindRecord::MatchPostponed { match_id, .. }
+            | NotificationKindRecord::Mention { match_id, .. }
+            | NotificationKindRecord::Reaction { match_id, .. } => Some(match_id),
+            NotificationKindRecord::InvitationAccepted {
+                context: InvitationContextRecord::Match { match_id, .. },
+                ..
+            } => Some(match_id),
+            _ => None,
+        }
+    }
+
+    /// The team the notification is about, if any. Match notifications name
+    /// no team here; their sides' teams are on the match itself.
+    pub fn team_id(&self) -> Option<&str> {
+        match self {
+            NotificationKindRecord::TeamInvitation { team_id, .. }
+            | NotificationKindRecord::TeamJoinRequest { team_id, .. }
+            | NotificationKindRecord::TeamJoinApproved { team_id, .. } => Some(team_id),
+            NotificationKindRecord::InvitationAccepted {
+                context: InvitationContextRecord::Team { team_id, .. },
+                ..
+            } => Some(team_id),
+            _ => None,
+        }
+    }
+}
+
+/// A `NotificationKindRecord` variant without its payload.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationTypeRecord {
+    MatchInvitation,
+    TeamInvitation,
+    InvitationAccepted,
+    Follow,
+    FollowRequest,
+    FollowRequestApproved,
+    Like,
+    Comment,
+    Reply,
+    ScoreSubmitted,
+    ScoreConfirmed,
+    TeamJoinRequest,
+    TeamJoinApproved,
+    MatchCancelled,
+    MatchPostponed,
+    Mention,
+    Reaction,
+}
+
+impl NotificationTypeRecord {
+    /// Whether this kind is emailed when the user hasn't said either way:
+    /// invitations and schedule changes — what someone who never opens the
+    /// app still needs to hear about — but not social chatter.
+    pub fn emails_by_default(self) -> bool {
+        matches!(
+            self,
+            NotificationTypeRecord::MatchInvitation
+                | NotificationTypeRecord::TeamInvitation
+                | NotificationTypeRecord::MatchCancelled
+                | NotificationTypeRecord::MatchPostponed
+        )
+    }
+}
+
+/// `USER#<uid>` / `#NOTIFPREFS` — what a user wants to be notified about, and
+/// how. A user without one gets the default: every kind in-app and pushed,
+/// the `emails_by_default` kinds emailed, nothing muted, no quiet hours, no
+/// digest.
+///
+/// Push and email ride on the in-app notification (the worker sends them in
+/// reaction to the `NotificationRecord` being written), so a kind switched
+/// off in-app gets neither, whatever its other toggles say.
+///
+/// A user subscribed to a digest is projected to GSI1 (`DIGEST#<frequency>`,
+/// sort `<uid>`), which is how the digest workflow finds its recipients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NotificationPreferencesRecord {
+    pub user_id: String,
+    /// Per-kind overrides. A kind not listed is on for both channels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<NotificationChannelsRecord>,
+    /// Teams whose notifications are dropped: the team's own (invitations,
+    /// join requests) and those about any match one of its sides plays in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted_team_ids: Vec<String>,
+    /// Matches whose notifications are dropped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted_match_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHoursRecord>,
+    #[serde(default)]
+    pub digest: DigestFrequencyRecord,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+impl NotificationPreferencesRecord {
+    /// The toggles for one kind (in-app and push on unless overridden;
+    /// email per the kind's default).
+    pub fn channels_for(&self, kind: NotificationTypeRecord) -> NotificationChannelsRecord {
+        self.channels
+            .iter()
+            .find(|c| c.kind == kind)
+            .copied()
+            .unwrap_or(NotificationChannelsRecord {
+                kind,
+                in_app: true,
+                push: true,
+                email: None,
+            })
+    }
+
+    /// Whether `kind` should land in the bell at all, judged on what the kind
+    /// itself carries. Mutes on the teams playing a match need the match's
+    /// sides, so the caller checks those with `mutes_team`.
+    pub fn allows_in_app(&self, kind: &NotificationKindRecord) -> bool {
+        self.channels_for(kind.notification_type()).in_app
+            && !kind.match_id().is_some_and(|id| self.mutes_match(id))
+            && !kind.team_id().is_some_and(|id| self.mutes_team(id))
+    }
+
+    /// Whether a notification that was delivered in-app should also push.
+    pub fn allows_push(&self, kind: &NotificationKindRecord) -> bool {
+        self.channels_for(kind.notification_type()).push
+    }
+
+    /// Whether a notification that was delivered in-app should also be
+    /// emailed.
+    pub fn allows_email(&self, kind: &NotificationKindRecord) -> bool {
+        let kind = kind.notification_type();
+        self.channels_for(kind)
+            .email
+            .unwrap_or_else(|| kind.emails_by_default())
+    }
+
+    pub fn mutes_match(&self, match_id: &str) -> bool {
+        self.muted_match_ids.iter().any(|id| id == match_id)
+    }
+
+    pub fn mutes_team(&self, team_id: &str) -> bool {
+        self.muted_team_ids.iter().any(|id| id == team_id)
+    }
+}
+
+/// One kind's in-app, push and email toggles.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct NotificationChannelsRecord {
+    pub kind: NotificationTypeRecord,
+    pub in_app: bool,
+    pub push: bool,
+    /// None = the kind's default (`emails_by_default`). Also what rows
+    /// written before email existed read as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+}
+
+/// How often a user is emailed a digest of upcoming matches, results and
+/// activity.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequencyRecord {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequencyRecord {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DigestFrequencyRecord::Off => "off",
+            DigestFrequencyRecord::Daily => "daily",
+            DigestFrequencyRecord::Weekly => "weekly",
+        }
+    }
+}
+
+/// A daily window in which pushes are held back, in the user's local time.
+///
+/// The time zone is a fixed UTC offset rather than a zone name — the client
+/// sends its current offset and re-saves when it changes (daylight saving,
+/// travel). `start_minute == end_minute` is an empty window; `start_minute >
+/// end_minute` wraps past midnight (22:00–07:00).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct QuietHoursRecord {
+    /// Minutes after local midnight, `0..1440`.
+    pub start_minute: u32,
+    /// Minutes after local midnight, `0..1440`; exclusive.
+    pub end_minute: u32,
+    /// The user's offset from UTC, in minutes (e.g. `-300` for UTC-5).
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietHoursRecord {
+    /// If `at` falls within quiet hours, when they end; `None` otherwise.
+    pub fn ends_after(
+        &self,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::Timelike;
+
+        const DAY: i64 = 24 * 60 * 60;
+        let local = at + chrono::Duration::minutes(self.utc_offset_minutes.into());
+        let now = i64::from(local.num_seconds_from_midnight());
+        let (start, end) = (
+            i64::from(self.start_minute) * 60,
+            i64::from(self.end_minute) * 60,
+        );
+        let quiet = if start <= end {
+            start <= now && now < end
+        } else {
+            now >= start || now < end
+        };
+        quiet.then(|| at + chrono::Duration::seconds((end - now).rem_euclid(DAY)))
+    }
+}
+
+/// `USER#<uid>` / `DEFERPUSH#<release_at>#<nid>` — a push that arrived in the
+/// recipient's quiet hours, held until `release_at` (when they end). The copy
+/// is rendered at deferral time, so it reads as it would have then.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeferredPushRecord {
+    pub user_id: String,
+    pub notification_id: String,
+    pub title: String,
+    pub body: String,
+    pub release_at: String,
 }
 
 /// The client platform a registered push token belongs to. Distinguishes how
@@ -1078,9 +1934,9 @@ pub enum DevicePlatform {
 /// same token (e.g. on every app open) is a plain upsert — no separate id
 /// layer, no conditional guard needed.
 ///
-/// Future: a per-user (or per-user + notification kind / per-followed team)
-/// mute preference would be looked up in the worker's push handler right
-/// before the send loop — additive, doesn't change this record shape.
+/// Whether a push is sent at all is the user's `NotificationPreferencesRecord`,
+/// checked in the worker's push handler before the send loop — not a
+/// per-device setting.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DeviceRecord {
     pub user_id: String,
@@ -1091,7 +1947,8 @@ pub struct DeviceRecord {
 
 /// `ASSET#<assetId>` / `#META` — an uploadable asset.
 ///
-/// `status` is "pending" | "uploaded" | "failed". `url` is set once uploaded.
+/// `status` is "pending" | "uploaded" | "failed" | "removed" (taken down by
+/// moderation). `url` is set once uploaded.
 /// The presigned upload target is generated on read, not stored.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AssetRecord {
@@ -1107,7 +1964,7 @@ pub struct AssetRecord {
     /// before this field existed — treated as "no length constraint".
     #[serde(default)]
     pub content_length: i64,
-    /// "pending" | "uploaded" | "failed".
+    /// "pending" | "uploaded" | "failed" | "removed".
     pub status: String,
     /// Storage object key, needed to generate presigned URLs / read the object.
     pub storage_key: String,
@@ -1117,24 +1974,111 @@ pub struct AssetRecord {
     pub created_at: String,
 }
 
-/// `UFEED#<viewerId>` / `FEED#<starts_at>#<matchId>` — a fan-out feed entry.
+/// What a feed entry points at.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedRefType {
+    /// A match, sorted by its start time. `ref_id` is the match id.
+    #[default]
+    Match,
+    /// A match's confirmed result, sorted by when it was confirmed. `ref_id`
+    /// is the match id.
+    Result,
+    /// A user reaching a stats milestone. `ref_id` is the [`MilestoneRecord`]
+    /// id.
+    Milestone,
+    /// A team admin's announcement. `ref_id` is the announcement id, and
+    /// `subject_id` the team.
+    TeamAnnouncement,
+    /// Someone joining a team. `ref_id` is the membership id, and
+    /// `subject_id` the team.
+    MemberJoined,
+}
+
+/// Why a viewer is in a feed entry's audience. Recorded on the entry so an
+/// unfollow can find, and remove, the entries that follow alone put there.
+/// Stored as a string — `participant`, `member#<teamId>`, `user#<userId>`,
+/// `team#<teamId>` or `club#<clubId>` — so a query can filter a viewer's
+/// entries on one with `contains`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum AudienceReason {
+    /// The viewer plays in the match, or the entry is about them.
+    Participant,
+    /// The viewer is a member of the team the entry is about.
+    Member(String),
+    /// The viewer follows this user.
+    FollowsUser(String),
+    /// The viewer follows this team.
+    FollowsTeam(String),
+    /// The viewer follows this club.
+    FollowsClub(String),
+}
+
+impl std::fmt::Display for AudienceReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudienceReason::Participant => f.write_str("participant"),
+            AudienceReason::Member(id) => write!(f, "member#{id}"),
+            AudienceReason::FollowsUser(id) => write!(f, "user#{id}"),
+            AudienceReason::FollowsTeam(id) => write!(f, "team#{id}"),
+            AudienceReason::FollowsClub(id) => write!(f, "club#{id}"),
+        }
+    }
+}
+
+impl From<AudienceReason> for String {
+    fn from(reason: AudienceReason) -> Self {
+        reason.to_string()
+    }
+}
+
+impl TryFrom<String> for AudienceReason {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "participant" {
+            return Ok(AudienceReason::Participant);
+        }
+        let (kind, id) = value
+            .split_once('#')
+            .ok_or_else(|| format!("malformed audience reason `{value}`"))?;
+        let id = id.to_string();
+        match kind {
+            "member" => Ok(AudienceReason::Member(id)),
+            "user" => Ok(AudienceReason::FollowsUser(id)),
+            "team" => Ok(AudienceReason::FollowsTeam(id)),
+            "club" => Ok(AudienceReason::FollowsClub(id)),
+            _ => Err(format!("unknown audience reason `{value}`")),
+        }
+    }
+}
+
+/// `UFEED#<viewerId>` / `FEED#<at>#<refId>` — a fan-out feed entry.
 ///
 /// A **thin pointer**: it names what to show (`ref_type` + `ref_id`) and carries
 /// only the sort key material (`starts_at`), not a denormalized copy of the
-/// referenced entity. The read path hydrates the real match from its own item,
-/// so feed entries never go stale. Written by the fan-out workflow, one row per
-/// viewer, idempotent on `<starts_at>#<matchId>`.
+/// referenced entity. The read path hydrates the real match (or milestone,
+/// announcement, membership) from its own item, so feed entries never go
+/// stale. Written by the fan-out workflows, one row per viewer, idempotent on
+/// `<at>#<refId>`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FeedItemRecord {
     /// The viewer whose feed this entry belongs to.
     pub viewer_id: String,
-    /// What kind of thing this points at (currently always "match").
-    pub ref_type: String,
-    /// The id of the referenced entity (the match id).
+    /// What kind of thing this points at.
+    pub ref_type: FeedRefType,
+    /// The id of the referenced entity (see [`FeedRefType`]).
     pub ref_id: String,
-    /// Start time of the referenced match — the feed's sort key material.
+    /// The partition the referenced entity lives in, when `ref_id` alone
+    /// doesn't say: the team, for an announcement or a new member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_id: Option<String>,
+    /// The feed's sort key material: the referenced match's start time, or
+    /// for anything else, when it happened. Named for the original (match
+    /// only) entries.
     pub starts_at: String,
-    /// When this feed entry was written (for debugging / potential TTL).
+    /// When this feed entry was written (for debugging).
     pub created_at: String,
     /// Up to `MAX_KNOWN_PLAYERS` user ids of this match's participants that the
     /// viewer follows — "people you know are playing", denormalized at fan-out
@@ -1162,6 +2106,191 @@ pub struct FeedItemRecord {
     /// this field existed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub viewer_side_id: Option<String>,
+    /// Every reason the viewer is in this entry's audience, as of the last
+    /// write. An unfollow deletes the entries whose only reason was that
+    /// follow, and strips it from the rest. Empty on entries written before
+    /// this field existed, which an unfollow therefore leaves alone.
+    #[serde(default)]
+    pub reasons: Vec<AudienceReason>,
+    /// When DynamoDB may delete this entry (its TTL attribute, epoch
+    /// seconds): the feed retention window past `starts_at`. Deletion lags
+    /// expiry by up to a couple of days, so reads skip expired entries
+    /// themselves (see [`FeedItemRecord::is_expired`]). `None` on entries
+    /// written before retention, until the one-off backfill stamps them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<i64>,
+}
+
+impl FeedItemRecord {
+    /// Whether this entry is past its TTL at `now` (epoch seconds) — expired,
+    /// whether or not DynamoDB has got round to deleting it.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.ttl.is_some_and(|ttl| ttl <= now)
+    }
+}
+
+/// `USER#<viewerId>` / `#RANKEDFEED` — the order of a viewer's ranked feed,
+/// as of its first page (see `agon_core::feed_rank`). Later pages read it
+/// back instead of re-ranking, so pagination is stable. Replaced whole each
+/// time the first page is requested.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RankedFeedRecord {
+    pub viewer_id: String,
+    /// Unique to this ranking; cursors carry it, so one from a ranking since
+    /// replaced is rejected. Empty on rankings stored before it existed,
+    /// which no cursor matches.
+    #[serde(default)]
+    pub ranking_id: String,
+    /// When the entries were ranked.
+    pub ranked_at: String,
+    /// Best first. Each is the key of one of the viewer's feed entries.
+    pub entries: Vec<RankedFeedEntryRecord>,
+}
+
+/// The key of one feed entry in a [`RankedFeedRecord`]: its
+/// `FEED#<at>#<refId>` sort key material.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RankedFeedEntryRecord {
+    pub starts_at: String,
+    /// The entry's `ref_id` — a match's own card and its result card share
+    /// it, so only together with `starts_at` does it name one entry.
+    #[serde(alias = "match_id")]
+    pub ref_id: String,
+}
+
+/// Which stat a [`MilestoneRecord`] counts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneStat {
+    MatchesPlayed,
+    Wins,
+}
+
+impl MilestoneStat {
+    /// The stored tag, matching the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MilestoneStat::MatchesPlayed => "matches_played",
+            MilestoneStat::Wins => "wins",
+        }
+    }
+}
+
+/// `MILESTONE#<id>` / `#META` — a user reaching a round number of matches
+/// played or won in a sport (see `agon_core::milestones`). The id is
+/// `<userId>.<sport>.<stat>.<count>`, so each milestone is recorded once
+/// however often it's detected, keeping its `reached_at` — and so its feed
+/// entries' sort key — stable.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MilestoneRecord {
+    pub id: String,
+    pub user_id: String,
+    /// Sport tag, as on `MatchRecord::match_type`.
+    pub sport: String,
+    pub stat: MilestoneStat,
+    pub count: u64,
+    pub reached_at: String,
+}
+
+impl MilestoneRecord {
+    /// The id of `user_id` reaching `count` of `stat` in `sport`.
+    pub fn id_for(user_id: &str, sport: &str, stat: MilestoneStat, count: u64) -> String {
+        format!("{user_id}.{sport}.{}.{count}", stat.as_str())
+    }
+}
+
+/// `TEAM#<teamId>` / `ANNOUNCE#<announcementId>` — a post by a team admin,
+/// fanned out to the team's members and followers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamAnnouncementRecord {
+    pub id: String,
+    pub team_id: String,
+    pub author_id: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+/// What a moderation case is about. A header photo or profile image is
+/// reported as an `Asset`, with the match it's attached to for a header photo.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportTargetRecord {
+    Comment {
+        match_id: String,
+        comment_id: String,
+        /// The parent comment, for a reply.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_id: Option<String>,
+    },
+    Match {
+        match_id: String,
+    },
+    User {
+        user_id: String,
+    },
+    Asset {
+        asset_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        match_id: Option<String>,
+    },
+}
+
+impl ReportTargetRecord {
+    /// The case id for this target: one case per reported thing, however
+    /// many users report it.
+    pub fn case_id(&self) -> String {
+        match self {
+            ReportTargetRecord::Comment { comment_id, .. } => format!("comment-{comment_id}"),
+            ReportTargetRecord::Match { match_id } => format!("match-{match_id}"),
+            ReportTargetRecord::User { user_id } => format!("user-{user_id}"),
+            ReportTargetRecord::Asset { asset_id, .. } => format!("asset-{asset_id}"),
+        }
+    }
+}
+
+/// `REPORT#<caseId>` / `#META` — a moderation case. Created by the first
+/// report against its target and counted up by each further one. Projected
+/// into GSI1 (`REPORTS#pending`, sorted by `pending_since`) while it awaits
+/// review — the moderation queue; resolving it drops it from the queue.
+///
+/// `status` is "pending" | "dismissed" | "actioned". `report_count` counts
+/// the distinct users who reported the target since the case was last
+/// resolved, so a new report against a dismissed target reopens the case
+/// from zero.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportCaseRecord {
+    pub id: String,
+    pub target: ReportTargetRecord,
+    pub status: String,
+    #[serde(default)]
+    pub report_count: u32,
+    /// When the case last entered the queue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_since: Option<String>,
+    /// When the target was automatically hidden for having too many reports.
+    /// Cleared on resolution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<String>,
+    pub last_reported_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by_user_id: Option<String>,
+    /// What the moderator did: "dismiss" | "remove" | "suspend".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+}
+
+/// `REPORT#<caseId>` / `REPORTER#<reporterId>` — one user's report in a case.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportRecord {
+    pub case_id: String,
+    pub reporter_user_id: String,
+    /// "spam" | "harassment" | "inappropriate" | "other".
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    pub created_at: String,
 }
 
 /// Aggregate stats for one sport, stored inline on `UserRecord::stats` keyed
@@ -1191,6 +2320,58 @@ pub struct StatContributionRecord {
     pub won: u64,
 }
 
+/// `SEARCHINDEX#<index>` / `#META` — present while that Meilisearch index is
+/// being rebuilt into its shadow. The stream indexer checks for it so writes
+/// made during a rebuild land in the shadow too, not only in the index about
+/// to be swapped out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchReindexRecord {
+    pub index: String,
+    pub started_at: String,
+    /// The Meilisearch task swapping the shadow in, once enqueued — a retried
+    /// finish waits on it instead of swapping back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap_task_uid: Option<u32>,
+}
+
+/// `USER#<userId>` / `#SUGGESTIONS` — the people and teams to suggest a user
+/// follow, best first. Computed in bulk by the worker's periodic
+/// `RefreshSuggestions` job (see `agon_core::suggestions`) and replaced
+/// wholesale each run, so reading them is one `GetItem`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SuggestionsRecord {
+    pub user_id: String,
+    pub computed_at: String,
+    #[serde(default)]
+    pub users: Vec<SuggestedUserRecord>,
+    #[serde(default)]
+    pub teams: Vec<SuggestedTeamRecord>,
+}
+
+/// One suggested user, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SuggestedUserRecord {
+    pub user_id: String,
+    /// Matches the two have both played in, of the recent ones looked at.
+    #[serde(default)]
+    pub shared_matches: u32,
+    /// People the user follows who follow this one.
+    #[serde(default)]
+    pub mutual_follows: u32,
+}
+
+/// One suggested team, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SuggestedTeamRecord {
+    pub team_id: String,
+    /// Recent matches of the user's that this team played in, on either side.
+    #[serde(default)]
+    pub shared_matches: u32,
+    /// People the user follows who follow this team.
+    #[serde(default)]
+    pub mutual_follows: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1258,4 +2439,175 @@ mod tests {
             _ => panic!("expected football"),
         }
     }
+    /// Follow edges written before follow requests existed read as plain
+    /// follows, so the notifier keeps announcing them to the followee.
+    #[test]
+    fn follow_edge_without_requested_at_is_a_plain_follow() {
+        let edge_av = AttributeValue::M(HashMap::from([
+            ("followee_id".to_string(), AttributeValue::S("u1".into())),
+            ("follower_id".to_string(), AttributeValue::S("u2".into())),
+            (
+                "created_at".to_string(),
+                AttributeValue::S("2026-01-01T00:00:00Z".into()),
+            ),
+        ]));
+        let edge: UserFollowRecord = serde_dynamo::from_attribute_value(edge_av).unwrap();
+        assert_eq!(edge.requested_at, None);
+    }
+
+    /// Like edges written before reactions existed read as 👍 reactions, and
+    /// a reaction's stored name is what the conditional writes compare.
+    #[test]
+    fn like_edge_without_reaction_is_a_thumbs_up() {
+        let edge_av = AttributeValue::M(HashMap::from([
+            ("match_id".to_string(), AttributeValue::S("m1".into())),
+            ("user_id".to_string(), AttributeValue::S("u1".into())),
+            (
+                "created_at".to_string(),
+                AttributeValue::S("2026-01-01T00:00:00Z".into()),
+            ),
+        ]));
+        let edge: MatchLikeRecord = serde_dynamo::from_attribute_value(edge_av).unwrap();
+        assert_eq!(edge.reaction, ReactionRecord::Like);
+
+        for reaction in ReactionRecord::ALL {
+            let av: AttributeValue = serde_dynamo::to_attribute_value(reaction).unwrap();
+            assert_eq!(av, AttributeValue::S(reaction.as_str().into()));
+        }
+    }
+
+    /// Reports against the same target land in the same case, whichever
+    /// reply or header photo context they carry.
+    #[test]
+    fn report_case_id_is_per_target() {
+        let reply = ReportTargetRecord::Comment {
+            match_id: "m1".into(),
+            comment_id: "r1".into(),
+            parent_id: Some("c1".into()),
+        };
+        assert_eq!(reply.case_id(), "comment-r1");
+        let header = ReportTargetRecord::Asset {
+            asset_id: "a1".into(),
+            match_id: Some("m1".into()),
+        };
+        assert_eq!(header.case_id(), "asset-a1");
+        assert_eq!(
+            ReportTargetRecord::User {
+                user_id: "u1".into()
+            }
+            .case_id(),
+            "user-u1"
+        );
+    }
+
+    fn utc(s: &str) -> chrono::DateTime<chrono::Utc> {
+        s.parse().unwrap()
+    }
+
+    /// A window past midnight, in a zone behind UTC: 22:00–07:00 at UTC-5
+    /// is 03:00–12:00 UTC.
+    #[test]
+    fn quiet_hours_wrap_midnight_in_the_users_zone() {
+        let quiet = QuietHoursRecord {
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+            utc_offset_minutes: -300,
+        };
+        let end = Some(utc("2025-06-02T12:00:00Z"));
+        assert_eq!(quiet.ends_after(utc("2025-06-02T03:00:00Z")), end);
+        assert_eq!(quiet.ends_after(utc("2025-06-02T11:59:30Z")), end);
+        assert_eq!(quiet.ends_after(utc("2025-06-02T12:00:00Z")), None);
+        assert_eq!(quiet.ends_after(utc("2025-06-02T02:59:59Z")), None);
+    }
+
+    #[test]
+    fn quiet_hours_within_one_day_and_empty() {
+        let quiet = QuietHoursRecord {
+            start_minute: 13 * 60,
+            end_minute: 14 * 60 + 30,
+            utc_offset_minutes: 60,
+        };
+        assert_eq!(
+            quiet.ends_after(utc("2025-06-02T12:10:00Z")),
+            Some(utc("2025-06-02T13:30:00Z"))
+        );
+        assert_eq!(quiet.ends_after(utc("2025-06-02T13:30:00Z")), None);
+        let empty = QuietHoursRecord {
+            start_minute: 600,
+            end_minute: 600,
+            utc_offset_minutes: 0,
+        };
+        assert_eq!(empty.ends_after(utc("2025-06-02T10:00:00Z")), None);
+    }
+
+    /// Unlisted kinds are on (email per kind); mutes apply to the match or team a kind names.
+    #[test]
+    fn preferences_default_on_and_apply_mutes() {
+        let prefs = NotificationPreferencesRecord {
+            user_id: "u1".into(),
+            channels: vec![NotificationChannelsRecord {
+                kind: NotificationTypeRecord::Follow,
+                in_app: true,
+                push: false,
+                email: Some(true),
+            }],
+            muted_match_ids: vec!["m1".into()],
+            muted_team_ids: vec!["t1".into()],
+            ..Default::default()
+        };
+        let follow = NotificationKindRecord::Follow {
+            actor_user_id: "a".into(),
+        };
+        assert!(prefs.allows_in_app(&follow));
+        assert!(!prefs.allows_push(&follow));
+        assert!(prefs.allows_email(&follow));
+
+        let comment = |match_id: &str| NotificationKindRecord::Comment {
+            actor_user_id: "a".into(),
+            match_id: match_id.into(),
+            comment_id: "c1".into(),
+            preview: String::new(),
+        };
+        assert!(!prefs.allows_in_app(&comment("m1")));
+        assert!(prefs.allows_in_app(&comment("m2")));
+        assert!(prefs.allows_push(&comment("m2")));
+        // Email follows the kind's default unless overridden.
+        assert!(!prefs.allows_email(&comment("m2")));
+        assert!(prefs.allows_email(&NotificationKindRecord::MatchCancelled {
+            actor_user_id: "a".into(),
+            match_id: "m2".into(),
+            match_name: "Derby".into(),
+            reason: String::new(),
+        }));
+
+        let accepted = NotificationKindRecord::InvitationAccepted {
+            actor_user_id: "a".into(),
+            invitation_id: "i1".into(),
+            context: InvitationContextRecord::Team {
+                team_id: "t1".into(),
+                team_name: "Rovers".into(),
+            },
+        };
+        assert!(!prefs.allows_in_app(&accepted));
+    }
+
+    /// Audience reasons are stored as plain strings, so a feed query can
+    /// filter on one, and read back as the same reason.
+    #[test]
+    fn audience_reasons_round_trip_as_strings() {
+        let reasons = [
+            AudienceReason::Participant,
+            AudienceReason::Member("t1".into()),
+            AudienceReason::FollowsUser("u1".into()),
+            AudienceReason::FollowsTeam("t1".into()),
+            AudienceReason::FollowsClub("c1".into()),
+        ];
+        for reason in reasons {
+            let av = serde_dynamo::to_attribute_value::<_, AttributeValue>(&reason).unwrap();
+            assert_eq!(av, AttributeValue::S(reason.to_string()));
+            let back: AudienceReason = serde_dynamo::from_attribute_value(av).unwrap();
+            assert_eq!(back, reason);
+        }
+        assert!(AudienceReason::try_from("league#l1".to_string()).is_err());
+    }
 }
@@ -0,0 +1,588 @@
+//! Content reports and the moderation queue.
+//!
+//! Every report against one target — a comment, match, user or asset — lands
+//! in the same case (`REPORT#<caseId>`, id from `ReportTargetRecord::case_id`):
+//! a `#META` item counting the reports, plus one `REPORTER#<uid>` item per
+//! reporting user, so a user reporting the same thing twice is a conflict
+//! rather than a second count. A pending case is projected into GSI1
+//! (`REPORTS#pending`), oldest first — the queue moderators work through.
+//!
+//! Resolving a case drops it from the queue and zeroes its count; the
+//! reporter items stay, so the same users can't reopen a dismissed case, but
+//! a report from anyone else does.
+//!
+//! The moderation actions themselves — hiding and unhiding content, removing
+//! a header photo, suspending a user — live here too. Hiding is a `hidden_at`
+//! stamp on the content's own item, which every read path already has in hand.
+
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem, Update};
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, ATTR_SK, ATTR_TYPE, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::{MatchRecord, ReportCaseRecord, ReportRecord, ReportTargetRecord};
+
+pub const TYPE_REPORT_CASE: &str = "report_case";
+pub const TYPE_REPORT: &str = "report";
+
+/// GSI1 partition holding the cases awaiting review.
+const PENDING_QUEUE_GSI1PK: &str = "REPORTS#pending";
+
+impl Dao {
+    /// File `report` against `target`, opening the case or adding to it.
+    /// `Conflict` if this user already reported the target. Returns the case
+    /// as it stands after the report, so the caller can decide whether it has
+    /// crossed the auto-hide threshold.
+    #[tracing::instrument(skip(self, report), fields(reporter = %report.reporter_user_id))]
+    pub async fn file_report(
+        &self,
+        target: &ReportTargetRecord,
+        report: &ReportRecord,
+    ) -> DaoResult<ReportCaseRecord> {
+        let case_id = target.case_id();
+        let pk = Pk::Report(case_id.clone());
+
+        let put = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(to_item(
+                &pk,
+                &Sk::Reporter(report.reporter_user_id.clone()),
+                TYPE_REPORT,
+                report,
+            )?))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        // `pending_since` is read before the update applies, so a case
+        // reopened after resolution (which removes it) re-enters the queue at
+        // the back.
+        let count = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(pk.to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .update_expression(
+                "SET id = :id, #target = if_not_exists(#target, :target), #type = :type, \
+                 #status = :pending, pending_since = if_not_exists(pending_since, :now), \
+                 last_reported_at = :now, #gsi1pk = :queue, \
+                 #gsi1sk = if_not_exists(pending_since, :now) \
+                 ADD report_count :one",
+            )
+            .expression_attribute_names("#target", "target")
+            .expression_attribute_names("#type", ATTR_TYPE)
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":id", s(case_id.clone()))
+            .expression_attribute_values(":target", serde_dynamo::to_attribute_value(target)?)
+            .expression_attribute_values(":type", s(TYPE_REPORT_CASE))
+            .expression_attribute_values(":pending", s("pending"))
+            .expression_attribute_values(":now", s(report.created_at.clone()))
+            .expression_attribute_values(":queue", s(PENDING_QUEUE_GSI1PK))
+            .expression_attribute_values(":one", AttributeValue::N("1".into()))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .transact_items(TransactWriteItem::builder().update(count).build())
+            .send()
+            .await;
+        match result {
+            Ok(_) => {}
+            Err(e) if super::is_transaction_conditional_failure(&e) => {
+                return Err(DaoError::Conflict("already reported".into()));
+            }
+            Err(e) => return Err(DaoError::Dynamo(e.to_string())),
+        }
+
+        self.get_report_case(&case_id).await?.ok_or_else(|| {
+            DaoError::Malformed(format!("report case {case_id} missing after write"))
+        })
+    }
+
+    /// Fetch a case by id. `None` if absent. Strongly consistent, so a case
+    /// read straight after a report or resolution reflects it.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_report_case(&self, case_id: &str) -> DaoResult<Option<ReportCaseRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Report(case_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => Ok(Some(from_item(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The moderation queue: cases awaiting review, oldest first.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_pending_report_cases(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<ReportCaseRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(PENDING_QUEUE_GSI1PK)),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// The individual reports in a case, cursor-paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_case_reports(
+        &self,
+        case_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<ReportRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_values(":pk", s(Pk::Report(case_id.into()).to_string()))
+                .expression_attribute_values(":sk", s(Sk::reporter_prefix())),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Record that a pending case's target was hidden automatically. Returns
+    /// false if it already was, or the case is no longer pending.
+    #[tracing::instrument(skip(self))]
+    pub async fn mark_report_case_hidden(&self, case_id: &str, now: &str) -> DaoResult<bool> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Report(case_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .update_expression("SET hidden_at = :now")
+            .condition_expression("#status = :pending AND attribute_not_exists(hidden_at)")
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":pending", s("pending"))
+            .expression_attribute_values(":now", s(now))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if is_update_conditional_failure(&e) => Ok(false),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Close a pending case with the moderator's `resolution` ("dismiss",
+    /// "remove" or "suspend"), taking it off the queue. `Conflict` if it isn't
+    /// pending (already resolved, or no such case).
+    #[tracing::instrument(skip(self))]
+    pub async fn resolve_report_case(
+        &self,
+        case_id: &str,
+        resolution: &str,
+        moderator_id: &str,
+        now: &str,
+    ) -> DaoResult<()> {
+        let status = if resolution == "dismiss" {
+            "dismissed"
+        } else {
+            "actioned"
+        };
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Report(case_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .update_expression(
+                "SET #status = :status, resolution = :resolution, \
+                 resolved_by_user_id = :moderator, resolved_at = :now, report_count = :zero \
+                 REMOVE pending_since, hidden_at, #gsi1pk, #gsi1sk",
+            )
+            .condition_expression("#status = :pending")
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":status", s(status))
+            .expression_attribute_values(":resolution", s(resolution))
+            .expression_attribute_values(":moderator", s(moderator_id))
+            .expression_attribute_values(":now", s(now))
+            .expression_attribute_values(":zero", AttributeValue::N("0".into()))
+            .expression_attribute_values(":pending", s("pending"))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => Err(DaoError::Conflict(format!(
+                "report case {case_id} is not pending"
+            ))),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    // ---- Moderation actions ---------------------------------------------
+
+    /// Hide (`Some(now)`) or unhide (`None`) a comment or, with `is_reply`, a
+    /// reply. `NotFound` if it doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_comment_hidden(
+        &self,
+        match_id: &str,
+        comment_id: &str,
+        is_reply: bool,
+        hidden_at: Option<&str>,
+    ) -> DaoResult<()> {
+        let sk = if is_reply {
+            Sk::Reply(comment_id.into())
+        } else {
+            Sk::Comment(comment_id.into())
+        };
+        self.set_timestamp(Pk::Match(match_id.into()), sk, "hidden_at", hidden_at)
+            .await
+    }
+
+    /// Hide or unhide a match. `NotFound` if it doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_match_hidden(&self, match_id: &str, hidden_at: Option<&str>) -> DaoResult<()> {
+        self.set_timestamp(Pk::Match(match_id.into()), Sk::Meta, "hidden_at", hidden_at)
+            .await
+    }
+
+    /// Hide or unhide a profile. `NotFound` if the user doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_user_hidden(&self, user_id: &str, hidden_at: Option<&str>) -> DaoResult<()> {
+        self.set_timestamp(
+            Pk::User(user_id.into()),
+            Sk::Profile,
+            "hidden_at",
+            hidden_at,
+        )
+        .await
+    }
+
+    /// Hide or unhide a user's profile image. `NotFound` if the user doesn't
+    /// exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_profile_image_hidden(
+        &self,
+        user_id: &str,
+        hidden_at: Option<&str>,
+    ) -> DaoResult<()> {
+        self.set_timestamp(
+            Pk::User(user_id.into()),
+            Sk::Profile,
+            "profile_image_hidden_at",
+            hidden_at,
+        )
+        .await
+    }
+
+    /// Suspend a user. `NotFound` if the user doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn suspend_user(&self, user_id: &str, now: &str) -> DaoResult<()> {
+        self.set_timestamp(
+            Pk::User(user_id.into()),
+            Sk::Profile,
+            "suspended_at",
+            Some(now),
+        )
+        .await
+    }
+
+    /// Hide or unhide one of a match's header photos. A photo no longer on
+    /// the match is left alone.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_header_photo_hidden(
+        &self,
+        match_id: &str,
+        asset_id: &str,
+        hidden_at: Option<&str>,
+    ) -> DaoResult<()> {
+        let Some(index) = self.header_photo_index(match_id, asset_id).await? else {
+            return Ok(());
+        };
+        let update = self.header_photo_update(match_id, asset_id, index);
+        let update = match hidden_at {
+            Some(at) => update
+                .update_expression(format!("SET header_photos[{index}].hidden_at = :at"))
+                .expression_attribute_values(":at", s(at)),
+            None => update.update_expression(format!("REMOVE header_photos[{index}].hidden_at")),
+        };
+        match update.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => Err(DaoError::Conflict(format!(
+                "header photos of match {match_id} changed; retry"
+            ))),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Take a header photo off a match. A photo no longer on the match is
+    /// left alone.
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_header_photo(&self, match_id: &str, asset_id: &str) -> DaoResult<()> {
+        let Some(index) = self.header_photo_index(match_id, asset_id).await? else {
+            return Ok(());
+        };
+        let result = self
+            .header_photo_update(match_id, asset_id, index)
+            .update_expression(format!("REMOVE header_photos[{index}]"))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => Err(DaoError::Conflict(format!(
+                "header photos of match {match_id} changed; retry"
+            ))),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Where `asset_id` sits in the match's header photos, if it's there.
+    async fn header_photo_index(&self, match_id: &str, asset_id: &str) -> DaoResult<Option<usize>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let Some(item) = out.item else {
+            return Err(DaoError::NotFound(format!("match {match_id}")));
+        };
+        let record: MatchRecord = from_item(item)?;
+        Ok(record
+            .header_photos
+            .iter()
+            .position(|photo| photo.asset_id == asset_id))
+    }
+
+    /// An update to the header photo at `index`, guarded on it still being
+    /// `asset_id` (an edit may have reordered the list since it was read).
+    fn header_photo_update(
+        &self,
+        match_id: &str,
+        asset_id: &str,
+        index: usize,
+    ) -> aws_sdk_dynamodb::operation::update_item::builders::UpdateItemFluentBuilder {
+        self.client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Match(match_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .condition_expression(format!("header_photos[{index}].asset_id = :asset"))
+            .expression_attribute_values(":asset", s(asset_id))
+    }
+
+    /// Set (`Some`) or remove (`None`) a timestamp attribute on an existing
+    /// item.
+    async fn set_timestamp(
+        &self,
+        pk: Pk,
+        sk: Sk,
+        attr: &str,
+        value: Option<&str>,
+    ) -> DaoResult<()> {
+        let update = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(pk.to_string()))
+            .key(ATTR_SK, s(sk.to_string()))
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#attr", attr);
+        let update = match value {
+            Some(v) => update
+                .update_expression("SET #attr = :v")
+                .expression_attribute_values(":v", s(v)),
+            None => update.update_expression("REMOVE #attr"),
+        };
+        match update.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => {
+                Err(DaoError::NotFound(format!("{pk} / {sk}")))
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+}
+
+fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), UpdateItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::UserRecord;
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+    /// What the API hides a target at (`AUTO_HIDE_REPORT_THRESHOLD`).
+    const THRESHOLD: u32 = 3;
+
+    fn target(user_id: &str) -> ReportTargetRecord {
+        ReportTargetRecord::User {
+            user_id: user_id.into(),
+        }
+    }
+
+    fn report(target: &ReportTargetRecord, reporter: &str) -> ReportRecord {
+        ReportRecord {
+            case_id: target.case_id(),
+            reporter_user_id: reporter.into(),
+            reason: "spam".into(),
+            details: None,
+            created_at: NOW.into(),
+        }
+    }
+
+    async fn pending(dao: &Dao) -> Vec<String> {
+        let page = dao.list_pending_report_cases(None, 50).await.unwrap();
+        page.items.into_iter().map(|c| c.id).collect()
+    }
+
+    #[tokio::test]
+    async fn each_reporter_counts_once() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let spammer = target("u1");
+
+        let case = dao
+            .file_report(&spammer, &report(&spammer, "a"))
+            .await
+            .unwrap();
+        assert_eq!((case.status.as_str(), case.report_count), ("pending", 1));
+        assert!(matches!(
+            dao.file_report(&spammer, &report(&spammer, "a")).await,
+            Err(DaoError::Conflict(_))
+        ));
+        let case = dao
+            .file_report(&spammer, &report(&spammer, "b"))
+            .await
+            .unwrap();
+        assert_eq!(case.report_count, 2);
+        assert_eq!(case.pending_since.as_deref(), Some(NOW));
+
+        let reports = dao.list_case_reports(&case.id, None, 50).await.unwrap();
+        let mut reporters: Vec<_> = reports
+            .items
+            .into_iter()
+            .map(|r| r.reporter_user_id)
+            .collect();
+        reporters.sort();
+        assert_eq!(reporters, ["a", "b"]);
+        assert_eq!(pending(&dao).await, [case.id]);
+    }
+
+    #[tokio::test]
+    async fn a_case_at_the_threshold_is_hidden_once() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let user: UserRecord = serde_json::from_value(serde_json::json!({
+            "id": "u1",
+            "email": "u1@example.com",
+            "name": "u1",
+            "created_at": NOW,
+        }))
+        .unwrap();
+        dao.create_user("sub-u1", &user).await.unwrap();
+        let spammer = target("u1");
+
+        let mut case = None;
+        for reporter in ["a", "b", "c"] {
+            case = Some(
+                dao.file_report(&spammer, &report(&spammer, reporter))
+                    .await
+                    .unwrap(),
+            );
+        }
+        let case = case.unwrap();
+        assert!(case.report_count >= THRESHOLD && case.hidden_at.is_none());
+        dao.set_user_hidden("u1", Some(NOW)).await.unwrap();
+        assert!(dao.mark_report_case_hidden(&case.id, NOW).await.unwrap());
+        // A fourth report finds it already hidden.
+        assert!(!dao.mark_report_case_hidden(&case.id, NOW).await.unwrap());
+        let user = dao.get_user("u1").await.unwrap().unwrap();
+        assert_eq!(user.hidden_at.as_deref(), Some(NOW));
+        let case = dao.get_report_case(&case.id).await.unwrap().unwrap();
+        assert_eq!(case.hidden_at.as_deref(), Some(NOW));
+
+        // Nothing to hide that isn't there.
+        assert!(matches!(
+            dao.set_user_hidden("nobody", Some(NOW)).await,
+            Err(DaoError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolving_takes_a_case_off_the_queue() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let spammer = target("u1");
+        let case = dao
+            .file_report(&spammer, &report(&spammer, "a"))
+            .await
+            .unwrap();
+        dao.mark_report_case_hidden(&case.id, NOW).await.unwrap();
+
+        dao.resolve_report_case(&case.id, "dismiss", "mod", NOW)
+            .await
+            .unwrap();
+        assert!(pending(&dao).await.is_empty());
+        let resolved = dao.get_report_case(&case.id).await.unwrap().unwrap();
+        assert_eq!(resolved.status, "dismissed");
+        assert_eq!(resolved.report_count, 0);
+        assert_eq!(resolved.hidden_at, None);
+        assert!(matches!(
+            dao.resolve_report_case(&case.id, "remove", "mod", NOW)
+                .await,
+            Err(DaoError::Conflict(_))
+        ));
+
+        // The same reporter can't reopen it; someone else can.
+        assert!(matches!(
+            dao.file_report(&spammer, &report(&spammer, "a")).await,
+            Err(DaoError::Conflict(_))
+        ));
+        let reopened = dao
+            .file_report(&spammer, &report(&spammer, "b"))
+            .await
+            .unwrap();
+        assert_eq!(
+            (reopened.status.as_str(), reopened.report_count),
+            ("pending", 1)
+        );
+        assert_eq!(pending(&dao).await, [case.id]);
+    }
+}
@@ -0,0 +1,132 @@
+//! What a full search reindex needs from the table: the ids of every indexed
+//! entity, and a `SEARCHINDEX#<index>` marker while a rebuild is running.
+//!
+//! The id listings are Scans — the one place this DAO reads the whole table.
+//! They're for the worker's reindex job only, which walks them a page at a
+//! time; nothing on a request path should call them.
+
+use serde::Deserialize;
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::SearchReindexRecord;
+
+pub const TYPE_SEARCH_REINDEX: &str = "search_reindex";
+
+/// Every entity item carries its own id as a plain attribute; the scans
+/// project just that.
+#[derive(Deserialize)]
+struct IdOnly {
+    id: String,
+}
+
+impl Dao {
+    /// One page of user ids (every `USER#` / `#PROFILE` item), in table order.
+    #[tracing::instrument(skip(self))]
+    pub async fn scan_user_ids(&self, cursor: Option<&str>, limit: u32) -> DaoResult<Page<String>> {
+        self.scan_entity_ids(Pk::User(String::new()), Sk::Profile, cursor, limit)
+            .await
+    }
+
+    /// One page of team ids (every `TEAM#` / `#META` item), in table order.
+    /// Archived teams are included; the caller decides what to index.
+    #[tracing::instrument(skip(self))]
+    pub async fn scan_team_ids(&self, cursor: Option<&str>, limit: u32) -> DaoResult<Page<String>> {
+        self.scan_entity_ids(Pk::Team(String::new()), Sk::Meta, cursor, limit)
+            .await
+    }
+
+    /// One page of match ids (every `MATCH#` / `#META` item), in table order.
+    #[tracing::instrument(skip(self))]
+    pub async fn scan_match_ids(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<String>> {
+        self.scan_entity_ids(Pk::Match(String::new()), Sk::Meta, cursor, limit)
+            .await
+    }
+
+    /// `pk_prefix` is an empty-valued `Pk`, whose string form (`USER#`) is the
+    /// prefix every key of that kind shares.
+    async fn scan_entity_ids(
+        &self,
+        pk_prefix: Pk,
+        sk: Sk,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<String>> {
+        let page: Page<IdOnly> = self
+            .scan_page(
+                self.client
+                    .scan()
+                    .table_name(self.table())
+                    .filter_expression("begins_with(#pk, :pfx) AND #sk = :sk")
+                    .projection_expression("#id")
+                    .expression_attribute_names("#pk", ATTR_PK)
+                    .expression_attribute_names("#sk", ATTR_SK)
+                    .expression_attribute_names("#id", "id")
+                    .expression_attribute_values(":pfx", s(pk_prefix.to_string()))
+                    .expression_attribute_values(":sk", s(sk.to_string())),
+                cursor,
+                limit,
+            )
+            .await?;
+        Ok(Page {
+            items: page.items.into_iter().map(|i| i.id).collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// Mark `reindex.index` as being rebuilt, or update the marker. A plain
+    /// put, so a resumed rebuild re-marking it is harmless.
+    #[tracing::instrument(skip(self))]
+    pub async fn put_search_reindex(&self, reindex: &SearchReindexRecord) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::SearchIndex(reindex.index.clone()),
+            &Sk::Meta,
+            TYPE_SEARCH_REINDEX,
+            reindex,
+        )?;
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+
+    /// The running rebuild of `index`, if any.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_search_reindex(&self, index: &str) -> DaoResult<Option<SearchReindexRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::SearchIndex(index.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        out.item.map(from_item).transpose()
+    }
+
+    /// Clear the rebuild marker. Idempotent.
+    #[tracing::instrument(skip(self))]
+    pub async fn end_search_reindex(&self, index: &str) -> DaoResult<()> {
+        self.client
+            .delete_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::SearchIndex(index.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Meta.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,741 @@
+//! Team seasons and the squad/stats views derived from them.
+//!
+//! A season is just a named date range on a team (`SEASON#<seasonId>`). Its
+//! squad is every membership whose joined/left dates overlap the range (so
+//! removing a member doesn't rewrite history), and its stats are computed from
+//! the team's matches (`TMATCH#<matchId>` pointers) starting inside the range.
+//! Nothing is materialized per season: edits to a season's dates, late score
+//! confirmations and roster fixes all show up on the next read.
+//!
+//! Pointers are written with the match; matches created before pointers
+//! existed get theirs from a re-runnable backfill
+//! (`backfill_team_match_pointers_page`).
+
+use std::collections::{BTreeMap, HashMap};
+
+use aws_sdk_dynamodb::types::{AttributeValue, Put, PutRequest, TransactWriteItem, WriteRequest};
+
+use super::batch::BATCH_WRITE_MAX;
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, ATTR_TYPE, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::match_ops::{MatchAggregate, TYPE_MATCH, sorted_sides};
+use super::page::Page;
+use super::records::{
+    MatchRecord, ScoreRecord, TeamMatchRecord, TeamMemberRecord, TeamSeasonRecord,
+};
+
+pub const TYPE_SEASON: &str = "team_season";
+pub const TYPE_TEAM_MATCH: &str = "team_match";
+
+impl Dao {
+    /// Create a season. `Conflict` if the season id already exists.
+    #[tracing::instrument(skip(self, season), fields(team_id = %season.team_id, season_id = %season.id))]
+    pub async fn create_team_season(&self, season: &TeamSeasonRecord) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::Team(season.team_id.clone()),
+            &Sk::Season(season.id.clone()),
+            TYPE_SEASON,
+            season,
+        )?;
+        let put = Put::builder()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::Conflict(
+                format!("season {} already exists", season.id),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Fetch one season. `None` if absent.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_team_season(
+        &self,
+        team_id: &str,
+        season_id: &str,
+    ) -> DaoResult<Option<TeamSeasonRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Season(season_id.into()).to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => Ok(Some(from_item(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// All of a team's seasons, most recent first (by `starts_at`). A team
+    /// has a handful at most, so this isn't paginated.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_team_seasons(&self, team_id: &str) -> DaoResult<Vec<TeamSeasonRecord>> {
+        let mut seasons: Vec<TeamSeasonRecord> = self
+            .query_team_collection(team_id, &Sk::season_prefix())
+            .await?;
+        seasons.sort_by(|a, b| b.starts_at.cmp(&a.starts_at));
+        Ok(seasons)
+    }
+
+    /// Every match the team has had a side in (pointers only — hydrate with
+    /// `get_match`).
+    #[tracing::instrument(skip(self))]
+    pub async fn list_team_matches(&self, team_id: &str) -> DaoResult<Vec<TeamMatchRecord>> {
+        self.query_team_collection(team_id, &Sk::team_match_prefix())
+            .await
+    }
+
+    /// The team's matches that count toward `season`'s stats — starting in it,
+    /// with a confirmed score — each with the side the team played as. The
+    /// matches' metas are batch-read and filtered first, so only a counted
+    /// match costs a roster query.
+    #[tracing::instrument(skip(self, season), fields(season_id = %season.id))]
+    pub async fn list_season_matches(
+        &self,
+        team_id: &str,
+        season: &TeamSeasonRecord,
+    ) -> DaoResult<Vec<(String, MatchAggregate)>> {
+        let pointers = self.list_team_matches(team_id).await?;
+        let match_ids: Vec<String> = pointers.iter().map(|p| p.match_id.clone()).collect();
+        let mut metas = self.batch_get_match_metas(&match_ids).await?;
+
+        let mut matches = Vec::new();
+        for pointer in pointers {
+            let Some(match_) = metas.remove(&pointer.match_id) else {
+                continue;
+            };
+            if match_.confirmed_score.is_none() || !match_in_season(&match_.starts_at, season) {
+                continue;
+            }
+            let players = self
+                .query_match_collection(&match_.id, &Sk::player_prefix())
+                .await?;
+            let sides = sorted_sides(&match_.sides);
+            matches.push((
+                pointer.side_id,
+                MatchAggregate {
+                    match_,
+                    sides,
+                    players,
+                },
+            ));
+        }
+        Ok(matches)
+    }
+
+    /// One page of the `TMATCH#` pointer backfill: write the pointers for the
+    /// team sides of matches created before `create_match` wrote them. Scans
+    /// the table for match metas, so `limit` caps the items *read* and a page
+    /// can write none yet have a cursor to follow. Pointers are derived from
+    /// sides that never change team, so re-writing one is a no-op and the
+    /// whole backfill is safe to re-run from the start. Returns the pointers
+    /// written.
+    #[tracing::instrument(skip(self))]
+    pub async fn backfill_team_match_pointers_page(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<TeamMatchRecord>> {
+        let page: Page<MatchRecord> = self
+            .scan_page(
+                self.client
+                    .scan()
+                    .table_name(self.table())
+                    .filter_expression("#type = :type AND #sk = :meta")
+                    .expression_attribute_names("#type", ATTR_TYPE)
+                    .expression_attribute_names("#sk", ATTR_SK)
+                    .expression_attribute_values(":type", s(TYPE_MATCH))
+                    .expression_attribute_values(":meta", s(Sk::Meta.to_string())),
+                cursor,
+                limit,
+            )
+            .await?;
+
+        let pointers: Vec<TeamMatchRecord> = page
+            .items
+            .iter()
+            .flat_map(|match_| {
+                match_.sides.values().filter_map(|side| {
+                    Some(TeamMatchRecord {
+                        team_id: side.team_id.clone()?,
+                        match_id: match_.id.clone(),
+                        side_id: side.side_id.clone(),
+                    })
+                })
+            })
+            .collect();
+        for chunk in pointers.chunks(BATCH_WRITE_MAX) {
+            let mut requests = Vec::with_capacity(chunk.len());
+            for pointer in chunk {
+                let put = PutRequest::builder()
+                    .set_item(Some(self.team_match_item(pointer)?))
+                    .build()
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+                requests.push(WriteRequest::builder().put_request(put).build());
+            }
+            self.flush_batch_write(requests).await?;
+        }
+        Ok(Page {
+            items: pointers,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// Build the `TMATCH#` pointer item for one team side of a match.
+    pub(super) fn team_match_item(
+        &self,
+        record: &TeamMatchRecord,
+    ) -> DaoResult<HashMap<String, AttributeValue>> {
+        to_item(
+            &Pk::Team(record.team_id.clone()),
+            &Sk::TeamMatch(record.match_id.clone()),
+            TYPE_TEAM_MATCH,
+            record,
+        )
+    }
+
+    /// Read every item in a team's partition whose SK starts with `sk_prefix`,
+    /// draining all query pages. Mirrors `query_match_collection`.
+    pub(super) async fn query_team_collection<T: serde::de::DeserializeOwned>(
+        &self,
+        team_id: &str,
+        sk_prefix: &str,
+    ) -> DaoResult<Vec<T>> {
+        let pk = Pk::Team(team_id.into()).to_string();
+        let mut items = Vec::new();
+        let mut start_key = None;
+        loop {
+            let out = self
+                .client
+                .query()
+                .table_name(self.table())
+                .key_condition_expression("#pk = :pk AND begins_with(SK, :sk)")
+                .expression_attribute_names("#pk", ATTR_PK)
+                .expression_attribute_values(":pk", s(pk.clone()))
+                .expression_attribute_values(":sk", s(sk_prefix))
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+            for item in out.items.unwrap_or_default() {
+                items.push(from_item(item)?);
+            }
+
+            match out.last_evaluated_key {
+                Some(k) => start_key = Some(k),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// When a membership actually started: on acceptance for an invited member
+/// (`None` while the invitation is pending or was declined — they never
+/// joined), otherwise when it was created.
+pub fn member_joined_at(member: &TeamMemberRecord) -> Option<&str> {
+    match &member.invitation {
+        None => Some(member.created_at.as_str()),
+        Some(inv) if inv.status == "accepted" => inv
+            .responded_at
+            .as_deref()
+            .or(Some(member.created_at.as_str())),
+        Some(_) => None,
+    }
+}
+
+/// Whether a membership overlaps the season: joined before it ended and
+/// didn't leave before it started.
+pub fn member_in_season(member: &TeamMemberRecord, season: &TeamSeasonRecord) -> bool {
+    let Some(joined_at) = member_joined_at(member) else {
+        return false;
+    };
+    let joined_before_end = season.ends_at.as_deref().is_none_or(|end| joined_at < end);
+    let left_after_start = member
+        .left_at
+        .as_deref()
+        .is_none_or(|left| left > season.starts_at.as_str());
+    joined_before_end && left_after_start
+}
+
+/// The role a member held in a season: the role in effect at the last moment
+/// they were in it (just before the season's end, or when they left if
+/// earlier — both are exclusive bounds).
+pub fn role_in_season<'a>(member: &'a TeamMemberRecord, season: &TeamSeasonRecord) -> &'a str {
+    let at = match (season.ends_at.as_deref(), member.left_at.as_deref()) {
+        (Some(end), Some(left)) => Some(end.min(left)),
+        (end, left) => end.or(left),
+    };
+    let Some(at) = at else {
+        return &member.role;
+    };
+    member
+        .previous_roles
+        .iter()
+        .find(|r| r.until.as_str() >= at)
+        .map_or(member.role.as_str(), |r| r.role.as_str())
+}
+
+/// Whether a match starting at `starts_at` falls in the season.
+pub fn match_in_season(starts_at: &str, season: &TeamSeasonRecord) -> bool {
+    starts_at >= season.starts_at.as_str()
+        && season.ends_at.as_deref().is_none_or(|end| starts_at < end)
+}
+
+/// Who a season stat line is about: a linked user, or (for an external player
+/// who never linked an account) the name they were entered under.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SeasonPlayerKey {
+    User(String),
+    External(String),
+}
+
+/// One player's line in a season's stats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonPlayerStats {
+    pub player: SeasonPlayerKey,
+    pub appearances: u32,
+    /// Goals (football), points (netball) or runs (cricket).
+    pub scored: u32,
+}
+
+/// A team's record and per-player totals for one season.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeasonStats {
+    pub played: u32,
+    pub won: u32,
+    pub drawn: u32,
+    pub lost: u32,
+    /// Top scorers first, then by appearances.
+    pub players: Vec<SeasonPlayerStats>,
+}
+
+/// Compute a team's season stats from its matches, each paired with the side
+/// the team played as. Only matches starting in the season with a confirmed
+/// score count — same rule as per-user stats, so an unagreed result never
+/// shows up as a loss. A player appears if they were on the team's side and
+/// actually played (no invitation, or an accepted one).
+pub fn season_stats(season: &TeamSeasonRecord, matches: &[(&str, &MatchAggregate)]) -> SeasonStats {
+    let mut stats = SeasonStats::default();
+    let mut players: BTreeMap<SeasonPlayerKey, (u32, u32)> = BTreeMap::new();
+
+    for (side_id, agg) in matches {
+        if !match_in_season(&agg.match_.starts_at, season) {
+            continue;
+        }
+        let Some(confirmed) = &agg.match_.confirmed_score else {
+            continue;
+        };
+        stats.played += 1;
+        match confirmed.winner_side_id.as_deref() {
+            Some(winner) if winner == *side_id => stats.won += 1,
+            Some(_) => stats.lost += 1,
+            None => stats.drawn += 1,
+        }
+
+        // player_id → key, for this match's players on our side.
+        let mut ours: BTreeMap<&str, SeasonPlayerKey> = BTreeMap::new();
+        for p in &agg.players {
+            if p.side_id.as_deref() != Some(*side_id) {
+                continue;
+            }
+            let played = p.invitation.as_ref().is_none_or(|i| i.status == "accepted");
+            let key = match (&p.user_id, &p.display_name) {
+                (Some(uid), _) => SeasonPlayerKey::User(uid.clone()),
+                (None, Some(name)) => SeasonPlayerKey::External(name.clone()),
+                (None, None) => continue,
+            };
+            if played {
+                players.entry(key.clone()).or_default().0 += 1;
+            }
+            ours.insert(&p.player_id, key);
+        }
+
+        for (player_id, points) in scored_by_player(&confirmed.score, side_id) {
+            if let Some(key) = ours.get(player_id) {
+                players.entry(key.clone()).or_default().1 += points;
+            }
+        }
+    }
+
+    stats.players = players
+        .into_iter()
+        .map(|(player, (appearances, scored))| SeasonPlayerStats {
+            player,
+            appearances,
+            scored,
+        })
+        .collect();
+    stats
+        .players
+        .sort_by_key(|p| std::cmp::Reverse((p.scored, p.appearances)));
+    stats
+}
+
+/// `(player_id, points)` for everything `side_id` scored in a confirmed score
+/// with per-player detail. Own goals aren't credited to anyone; scores without
+/// player detail (simple/sets, manual results) yield nothing.
+fn scored_by_player<'a>(score: &'a ScoreRecord, side_id: &str) -> Vec<(&'a str, u32)> {
+    match score {
+        ScoreRecord::Football { goals, .. } => goals
+            .iter()
+            .flatten()
+            .filter(|g| g.side_id == side_id && !g.own_goal)
+            .filter_map(|g| g.scorer_player_id.as_deref().map(|p| (p, 1)))
+            .collect(),
+        ScoreRecord::Netball { goals, .. } => goals
+            .iter()
+            .flatten()
+            .filter(|g| g.side_id == side_id)
+            .filter_map(|g| {
+                g.scorer_player_id
+                    .as_deref()
+                    .map(|p| (p, if g.two_points { 2 } else { 1 }))
+            })
+            .collect(),
+        ScoreRecord::Cricket { innings, .. } => innings
+            .iter()
+            .filter(|i| i.batting_side_id == side_id)
+            .flat_map(|i| i.batting.iter().flatten())
+            .map(|b| (b.player_id.as_str(), b.runs))
+            .collect(),
+        ScoreRecord::Simple { .. } | ScoreRecord::Sets { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{
+        ConfirmedScoreRecord, EmbeddedInvitationRecord, FootballGoalEventRecord,
+        InvitationKindRecord, MatchPlayerRecord, MatchSideRecord, PreviousRoleRecord,
+    };
+
+    fn season(starts_at: &str, ends_at: Option<&str>) -> TeamSeasonRecord {
+        TeamSeasonRecord {
+            id: "s1".into(),
+            team_id: "t1".into(),
+            name: "2024".into(),
+            starts_at: starts_at.into(),
+            ends_at: ends_at.map(Into::into),
+            created_at: "2024-01-01T00:00:00.000Z".into(),
+        }
+    }
+
+    fn member(created_at: &str, left_at: Option<&str>) -> TeamMemberRecord {
+        TeamMemberRecord {
+            team_id: "t1".into(),
+            membership_id: "m1".into(),
+            user_id: Some("u1".into()),
+            display_name: None,
+            role: "member".into(),
+            invitation: None,
+            created_at: created_at.into(),
+            left_at: left_at.map(Into::into),
+            previous_roles: Vec::new(),
+        }
+    }
+
+    fn player(player_id: &str, user_id: &str, side_id: &str) -> MatchPlayerRecord {
+        MatchPlayerRecord {
+            player_id: player_id.into(),
+            user_id: Some(user_id.into()),
+            display_name: None,
+            side_id: Some(side_id.into()),
+            is_member_of_team: None,
+            invitation: None,
+        }
+    }
+
+    fn football_match(
+        starts_at: &str,
+        winner: Option<&str>,
+        goals: Vec<(&str, &str, bool)>,
+        players: Vec<MatchPlayerRecord>,
+    ) -> MatchAggregate {
+        let goals = goals
+            .into_iter()
+            .map(|(side_id, scorer, own_goal)| FootballGoalEventRecord {
+                side_id: side_id.into(),
+                scorer_player_id: Some(scorer.into()),
+                assist_player_id: None,
+                own_goal,
+                penalty: false,
+                minute: None,
+                occurred_at: None,
+            })
+            .collect();
+        MatchAggregate {
+            match_: MatchRecord {
+                id: "m".into(),
+                created_by_user_id: "u1".into(),
+                name: "Fixture".into(),
+                description: String::new(),
+                match_type: "football".into(),
+                status: "completed".into(),
+                starts_at: starts_at.into(),
+                location: None,
+                venue: None,
+                league: None,
+                sides: HashMap::new(),
+                header_photos: Vec::new(),
+                confirmed_score: Some(ConfirmedScoreRecord {
+                    score: ScoreRecord::Football {
+                        score: HashMap::new(),
+                        goals: Some(goals),
+                        cards: None,
+                        substitutions: None,
+                        period: None,
+                        period_times: None,
+                        penalty_shootout: None,
+                        penalty_shootout_score: None,
+                    },
+                    winner_side_id: winner.map(Into::into),
+                    confirmed_at: None,
+                }),
+                pending_score: None,
+                like_count: 0,
+                fire_count: 0,
+                clap_count: 0,
+                laugh_count: 0,
+                wow_count: 0,
+                comment_count: 0,
+                live_seq: 0,
+                format: None,
+                cancellation: None,
+                postponements: Vec::new(),
+                visibility: Default::default(),
+                hidden_at: None,
+                created_at: starts_at.into(),
+            },
+            sides: Vec::new(),
+            players,
+        }
+    }
+
+    #[test]
+    fn member_overlap_uses_joined_and_left_dates() {
+        let s = season("2024-01-01", Some("2025-01-01"));
+        assert!(member_in_season(&member("2023-06-01", None), &s));
+        assert!(member_in_season(
+            &member("2023-06-01", Some("2024-03-01")),
+            &s
+        ));
+        assert!(!member_in_season(
+            &member("2023-06-01", Some("2023-12-01")),
+            &s
+        ));
+        assert!(!member_in_season(&member("2025-02-01", None), &s));
+        // Left exactly as the season started: never part of it.
+        assert!(!member_in_season(
+            &member("2023-06-01", Some("2024-01-01")),
+            &s
+        ));
+    }
+
+    #[test]
+    fn pending_invitee_never_joined() {
+        let mut m = member("2024-02-01", None);
+        m.invitation = Some(EmbeddedInvitationRecord {
+            id: "i1".into(),
+            status: "pending".into(),
+            invited_by_user_id: "u2".into(),
+            invited_at: "2024-02-01".into(),
+            responded_at: None,
+            kind: InvitationKindRecord::User {
+                invited_user_id: "u1".into(),
+            },
+        });
+        assert!(!member_in_season(&m, &season("2024-01-01", None)));
+    }
+
+    #[test]
+    fn role_is_the_one_held_at_season_end() {
+        let mut m = member("2023-01-01", None);
+        m.role = "admin".into();
+        m.previous_roles = vec![PreviousRoleRecord {
+            role: "member".into(),
+            until: "2025-03-01".into(),
+        }];
+        assert_eq!(
+            role_in_season(&m, &season("2024-01-01", Some("2025-01-01"))),
+            "member"
+        );
+        assert_eq!(role_in_season(&m, &season("2025-01-01", None)), "admin");
+    }
+
+    #[test]
+    fn stats_count_record_scorers_and_appearances() {
+        let s = season("2024-01-01", Some("2025-01-01"));
+        let win = football_match(
+            "2024-03-01",
+            Some("a"),
+            vec![("a", "p1", false), ("a", "p1", false), ("b", "p3", true)],
+            vec![
+                player("p1", "u1", "a"),
+                player("p2", "u2", "a"),
+                player("p3", "u3", "b"),
+            ],
+        );
+        let draw = football_match(
+            "2024-04-01",
+            None,
+            vec![("a", "q2", false)],
+            vec![player("q1", "u1", "a"), player("q2", "u2", "a")],
+        );
+        let out_of_season = football_match(
+            "2025-02-01",
+            Some("b"),
+            vec![],
+            vec![player("r1", "u1", "a")],
+        );
+        let stats = season_stats(&s, &[("a", &win), ("a", &draw), ("a", &out_of_season)]);
+
+        assert_eq!(
+            (stats.played, stats.won, stats.drawn, stats.lost),
+            (2, 1, 1, 0)
+        );
+        assert_eq!(
+            stats.players,
+            vec![
+                SeasonPlayerStats {
+                    player: SeasonPlayerKey::User("u1".into()),
+                    appearances: 2,
+                    scored: 2,
+                },
+                SeasonPlayerStats {
+                    player: SeasonPlayerKey::User("u2".into()),
+                    appearances: 2,
+                    scored: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unconfirmed_match_does_not_count() {
+        let mut m = football_match(
+            "2024-03-01",
+            Some("a"),
+            vec![],
+            vec![player("p1", "u1", "a")],
+        );
+        m.match_.confirmed_score = None;
+        let stats = season_stats(&season("2024-01-01", None), &[("a", &m)]);
+        assert_eq!(stats, SeasonStats::default());
+    }
+
+    /// `football_match` as match `id`, with side `red` played by team `t1`
+    /// and `blue` by nobody in particular.
+    fn team_match(id: &str, starts_at: &str, confirmed: bool) -> MatchAggregate {
+        let mut agg = football_match(
+            starts_at,
+            Some("red"),
+            vec![("red", "p1", false)],
+            vec![player("p1", "u1", "red")],
+        );
+        agg.match_.id = id.into();
+        if !confirmed {
+            agg.match_.confirmed_score = None;
+        }
+        for (side_id, team_id) in [("red", Some("t1")), ("blue", None)] {
+            agg.match_.sides.insert(
+                side_id.into(),
+                MatchSideRecord {
+                    side_id: side_id.into(),
+                    team_id: team_id.map(Into::into),
+                    name: None,
+                    player_count: 0,
+                    roster_preview: Vec::new(),
+                },
+            );
+        }
+        agg
+    }
+
+    async fn season_match_ids(dao: &Dao, s: &TeamSeasonRecord) -> Vec<String> {
+        let mut ids: Vec<String> = dao
+            .list_season_matches("t1", s)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(side_id, agg)| {
+                assert_eq!(side_id, "red");
+                assert_eq!(agg.players.len(), 1);
+                agg.match_.id
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn backfilled_pointers_bring_old_matches_into_season_stats() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        for (id, starts_at, confirmed) in [
+            ("m-old", "2024-03-01T10:00:00Z", true),
+            ("m-new", "2024-06-01T10:00:00Z", true),
+            ("m-unconfirmed", "2024-07-01T10:00:00Z", false),
+            ("m-next-season", "2025-03-01T10:00:00Z", true),
+        ] {
+            let agg = team_match(id, starts_at, confirmed);
+            dao.create_match(&agg.match_, &agg.players).await.unwrap();
+        }
+        // `m-old` predates the pointers.
+        table
+            .client()
+            .delete_item()
+            .table_name("agon")
+            .key(ATTR_PK, s(Pk::Team("t1".into()).to_string()))
+            .key(ATTR_SK, s(Sk::TeamMatch("m-old".into()).to_string()))
+            .send()
+            .await
+            .unwrap();
+
+        let s2024 = season("2024-01-01T00:00:00Z", Some("2025-01-01T00:00:00Z"));
+        assert_eq!(season_match_ids(&dao, &s2024).await, ["m-new"]);
+
+        for _ in 0..2 {
+            let mut written = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = dao
+                    .backfill_team_match_pointers_page(cursor.as_deref(), 2)
+                    .await
+                    .unwrap();
+                written.extend(page.items.into_iter().map(|p| p.match_id));
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            written.sort();
+            assert_eq!(
+                written,
+                ["m-new", "m-next-season", "m-old", "m-unconfirmed"]
+            );
+        }
+        assert_eq!(season_match_ids(&dao, &s2024).await, ["m-new", "m-old"]);
+        assert_eq!(dao.list_team_matches("t1").await.unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,47 @@
+//! A user's cached follow suggestions: one `#SUGGESTIONS` item in the user
+//! partition, written by the worker's periodic refresh and read whole by
+//! `GET /suggestions`.
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::records::SuggestionsRecord;
+
+pub const TYPE_SUGGESTIONS: &str = "suggestions";
+
+impl Dao {
+    /// A user's suggestions, or `None` if none have been computed yet.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_suggestions(&self, user_id: &str) -> DaoResult<Option<SuggestionsRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::User(user_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::Suggestions.to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        out.item.map(from_item).transpose()
+    }
+
+    /// Replace a user's suggestions wholesale.
+    #[tracing::instrument(skip(self, suggestions), fields(user_id = %suggestions.user_id))]
+    pub async fn put_suggestions(&self, suggestions: &SuggestionsRecord) -> DaoResult<()> {
+        let item = to_item(
+            &Pk::User(suggestions.user_id.clone()),
+            &Sk::Suggestions,
+            TYPE_SUGGESTIONS,
+            suggestions,
+        )?;
+        self.client
+            .put_item()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! Hashtag index — `TAG#<tag>` partitions listing the comments (and, through
+//! them, the matches) that use a tag.
+//!
+//! Nothing here writes on its own: the comment create / edit / delete ops in
+//! `match_social` fold these items into their own transactions, so the index
+//! moves together with the comment text it was extracted from.
+
+use aws_sdk_dynamodb::types::{AttributeValue, Delete, Put, TransactWriteItem, Update};
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, ATTR_SK, ATTR_TYPE, ItemBuilder, s, to_item};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::{CommentRecord, TagCommentRecord, TagMatchRecord};
+
+pub const TYPE_TAG_COMMENT: &str = "tag_comment";
+pub const TYPE_TAG_MATCH: &str = "tag_match";
+
+impl Dao {
+    /// Comments and replies carrying `tag`, newest first, via GSI1.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_tag_comments(
+        &self,
+        tag: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<TagCommentRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(format!("TAGCOMMENTS#{tag}")))
+                .scan_index_forward(false),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Matches whose comments use `tag`, most recently tagged first, via GSI1.
+    /// Rows whose `comment_count` has dropped to zero are still returned;
+    /// callers skip them.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_tag_matches(
+        &self,
+        tag: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<TagMatchRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(format!("TAGMATCHES#{tag}")))
+                .scan_index_forward(false),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Transaction items indexing `comment` under each of `tags`: a
+    /// `TagCommentRecord` put, and a bump of the (tag, match) row that also
+    /// moves it to the front of the tag's match list.
+    pub(super) fn tag_comment_writes(
+        &self,
+        comment: &CommentRecord,
+        tags: &[String],
+        tagged_at: &str,
+    ) -> DaoResult<Vec<TransactWriteItem>> {
+        let mut writes = Vec::with_capacity(tags.len() * 2);
+        for tag in tags {
+            let record = TagCommentRecord {
+                tag: tag.clone(),
+                match_id: comment.match_id.clone(),
+                comment_id: comment.comment_id.clone(),
+                parent_id: comment.parent_id.clone(),
+                created_at: comment.created_at.clone(),
+            };
+            let item = ItemBuilder::new(to_item(
+                &Pk::Tag(tag.clone()),
+                &Sk::Comment(comment.comment_id.clone()),
+                TYPE_TAG_COMMENT,
+                &record,
+            )?)
+            .gsi1(
+                format!("TAGCOMMENTS#{tag}"),
+                format!("{}#{}", comment.created_at, comment.comment_id),
+            )
+            .build();
+            let put = Put::builder()
+                .table_name(self.table())
+                .set_item(Some(item))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            writes.push(TransactWriteItem::builder().put(put).build());
+
+            let bump = Update::builder()
+                .table_name(self.table())
+                .key(ATTR_PK, s(Pk::Tag(tag.clone()).to_string()))
+                .key(
+                    ATTR_SK,
+                    s(Sk::TaggedMatch(comment.match_id.clone()).to_string()),
+                )
+                .update_expression(
+                    "SET #type = :type, tag = :tag, match_id = :m, last_tagged_at = :ts, \
+                     #g1pk = :g1pk, #g1sk = :g1sk ADD comment_count :one",
+                )
+                .expression_attribute_names("#type", ATTR_TYPE)
+                .expression_attribute_names("#g1pk", ATTR_GSI1PK)
+                .expression_attribute_names("#g1sk", ATTR_GSI1SK)
+                .expression_attribute_values(":type", s(TYPE_TAG_MATCH))
+                .expression_attribute_values(":tag", s(tag.as_str()))
+                .expression_attribute_values(":m", s(comment.match_id.as_str()))
+                .expression_attribute_values(":ts", s(tagged_at))
+                .expression_attribute_values(":g1pk", s(format!("TAGMATCHES#{tag}")))
+                .expression_attribute_values(
+                    ":g1sk",
+                    s(format!("{tagged_at}#{}", comment.match_id)),
+                )
+                .expression_attribute_values(":one", AttributeValue::N("1".into()))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            writes.push(TransactWriteItem::builder().update(bump).build());
+        }
+        Ok(writes)
+    }
+
+    /// Transaction items taking a comment back out of each of `tags`' index:
+    /// delete its `TagCommentRecord` and decrement the (tag, match) row.
+    pub(super) fn untag_comment_writes(
+        &self,
+        match_id: &str,
+        comment_id: &str,
+        tags: &[String],
+    ) -> DaoResult<Vec<TransactWriteItem>> {
+        let mut writes = Vec::with_capacity(tags.len() * 2);
+        for tag in tags {
+            let delete = Delete::builder()
+                .table_name(self.table())
+                .key(ATTR_PK, s(Pk::Tag(tag.clone()).to_string()))
+                .key(ATTR_SK, s(Sk::Comment(comment_id.into()).to_string()))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            writes.push(TransactWriteItem::builder().delete(delete).build());
+
+            let drop = Update::builder()
+                .table_name(self.table())
+                .key(ATTR_PK, s(Pk::Tag(tag.clone()).to_string()))
+                .key(ATTR_SK, s(Sk::TaggedMatch(match_id.into()).to_string()))
+                .update_expression("ADD comment_count :minus")
+                .expression_attribute_values(":minus", AttributeValue::N("-1".into()))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            writes.push(TransactWriteItem::builder().update(drop).build());
+        }
+        Ok(writes)
+    }
+}
@@ -1,28 +1,47 @@
 //! Team operations: create, get (meta + members aggregate), update, member
-//! add/remove, and "my teams".
+//! add/remove (removal keeps the membership as history), admin handover,
+//! archiving, and "my teams".
+//!
+//! A team always keeps at least one admin: removing or demoting an admin runs
+//! in a transaction that also condition-checks another current admin, so two
+//! admins removing each other concurrently can't both succeed.
+//!
+//! A user holds at most one current membership of a team: every membership
+//! that links a user is written alongside a `MEMBERGUARD#<userId>` item under a
+//! conditional put, and ending the membership deletes it.
 
 use std::collections::HashMap;
 
 use aws_sdk_dynamodb::error::SdkError;
-use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
 use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::types::{
+    AttributeValue, ConditionCheck, Delete, Put, TransactWriteItem, Update,
+};
 
 use super::client::Dao;
 use super::error::{DaoError, DaoResult};
-use super::item::{ATTR_GSI1PK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, item_sk, s, to_item};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, ATTR_SK, ItemBuilder, from_item, s, to_item};
 use super::keys::{Pk, Sk};
 use super::page::Page;
-use super::records::{TeamMemberRecord, TeamRecord};
+use super::records::{MemberGuardRecord, PreviousRoleRecord, TeamMemberRecord, TeamRecord};
 
 pub const TYPE_TEAM: &str = "team";
 pub const TYPE_TEAM_MEMBER: &str = "team_member";
+pub const TYPE_MEMBER_GUARD: &str = "member_guard";
+
+/// The membership role that can manage a team.
+pub const ROLE_ADMIN: &str = "admin";
+/// The default membership role.
+pub const ROLE_MEMBER: &str = "member";
 
 /// A team plus its members, assembled from one item-collection query.
 #[derive(Debug)]
 pub struct TeamAggregate {
     pub team: TeamRecord,
+    /// Current members only.
     pub members: Vec<TeamMemberRecord>,
+    /// Memberships that have ended (`left_at` set), for squad history.
+    pub former_members: Vec<TeamMemberRecord>,
 }
 
 impl Dao {
@@ -35,9 +54,7 @@ impl Dao {
         team: &TeamRecord,
         creator: &TeamMemberRecord,
     ) -> DaoResult<()> {
-        use aws_sdk_dynamodb::types::{Put, TransactWriteItem};
-
-        let meta_item = to_item(&Pk::Team(team.id.clone()), &Sk::Meta, TYPE_TEAM, team)?;
+        let meta_item = team_meta_item(team)?;
         let member_item = self.team_member_item(&team.id, creator)?;
 
         let put_meta = Put::builder()
@@ -54,13 +71,15 @@ impl Dao {
             .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
 
-        let result = self
+        let mut tx = self
             .client
             .transact_write_items()
             .transact_items(TransactWriteItem::builder().put(put_meta).build())
-            .transact_items(TransactWriteItem::builder().put(put_member).build())
-            .send()
-            .await;
+            .transact_items(TransactWriteItem::builder().put(put_member).build());
+        if let Some(guard) = self.member_guard_put(&team.id, creator)? {
+            tx = tx.transact_items(TransactWriteItem::builder().put(guard).build());
+        }
+        let result = tx.send().await;
 
         match result {
             Ok(_) => Ok(()),
@@ -121,35 +140,25 @@ impl Dao {
         Ok(out)
     }
 
-    /// Fetch the full team aggregate (meta + all members) in a single query on
-    /// the `TEAM#<id>` partition, splitting the collection by SK prefix.
+    /// Fetch the full team aggregate: the meta, then every membership via a
+    /// `MEMBER#` range query drained page by page — so the follower edges,
+    /// seasons and match pointers sharing the partition are never read.
     /// `None` if the team's meta item is absent.
     #[tracing::instrument(skip(self))]
     pub async fn get_team(&self, team_id: &str) -> DaoResult<Option<TeamAggregate>> {
-        let out = self
-            .client
-            .query()
-            .table_name(self.table())
-            .key_condition_expression("#pk = :pk")
-            .expression_attribute_names("#pk", ATTR_PK)
-            .expression_attribute_values(":pk", s(Pk::Team(team_id.into()).to_string()))
-            .send()
-            .await
-            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-
-        let mut team: Option<TeamRecord> = None;
-        let mut members: Vec<TeamMemberRecord> = Vec::new();
-
-        for item in out.items.unwrap_or_default() {
-            match item_sk(&item)? {
-                Sk::Meta => team = Some(from_item(item)?),
-                Sk::Member(_) => members.push(from_item(item)?),
-                // Follower edges also live here; ignore for the aggregate.
-                _ => {}
-            }
-        }
-
-        Ok(team.map(|team| TeamAggregate { team, members }))
+        let Some(team) = self.get_team_meta(team_id).await? else {
+            return Ok(None);
+        };
+        let (former_members, members): (Vec<TeamMemberRecord>, Vec<TeamMemberRecord>) = self
+            .query_team_collection(team_id, &Sk::member_prefix())
+            .await?
+            .into_iter()
+            .partition(|m: &TeamMemberRecord| m.left_at.is_some());
+        Ok(Some(TeamAggregate {
+            team,
+            members,
+            former_members,
+        }))
     }
 
     /// Update a team's mutable fields (currently just `name`). `NotFound` if the
@@ -182,41 +191,229 @@ impl Dao {
     }
 
     /// Add (or overwrite) a single team member. Used for both single adds and
-    /// the fan-out of a bulk invite (call per member).
+    /// the fan-out of a bulk invite (call per member). A member linked to a
+    /// user is written with its membership guard, so `Conflict` if that user
+    /// is already a current member.
     #[tracing::instrument(skip(self, member), fields(membership_id = %member.membership_id))]
     pub async fn put_team_member(&self, team_id: &str, member: &TeamMemberRecord) -> DaoResult<()> {
-        let item = self.team_member_item(team_id, member)?;
-        self.client
-            .put_item()
+        let put = Put::builder()
             .table_name(self.table())
-            .set_item(Some(item))
-            .send()
-            .await
+            .set_item(Some(self.team_member_item(team_id, member)?))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        let mut tx = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build());
+        if let Some(guard) = self.member_guard_put(team_id, member)? {
+            tx = tx.transact_items(TransactWriteItem::builder().put(guard).build());
+        }
+
+        match tx.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::Conflict(
+                format!("user is already a member of team {team_id}"),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Fetch memberships by `(team_id, membership_id)` in one batch read,
+    /// keyed by membership id — current and former alike. A key with no
+    /// membership has no entry.
+    #[tracing::instrument(skip(self))]
+    pub async fn batch_get_team_members(
+        &self,
+        keys: &[(String, String)],
+    ) -> DaoResult<HashMap<String, TeamMemberRecord>> {
+        let mut seen = std::collections::HashSet::new();
+        let keys: Vec<_> = keys
+            .iter()
+            .filter(|key| seen.insert((*key).clone()))
+            .map(|(team_id, membership_id)| {
+                HashMap::from([
+                    (
+                        ATTR_PK.to_string(),
+                        s(Pk::Team(team_id.clone()).to_string()),
+                    ),
+                    (
+                        ATTR_SK.to_string(),
+                        s(Sk::Member(membership_id.clone()).to_string()),
+                    ),
+                ])
+            })
+            .collect();
+
+        let items = self.batch_get_all(keys, None).await?;
+        let mut out = HashMap::with_capacity(items.len());
+        for item in items {
+            let record: TeamMemberRecord = from_item(item)?;
+            out.insert(record.membership_id.clone(), record);
+        }
+        Ok(out)
+    }
+
+    /// Remove a member from a team by membership id. The membership is ended
+    /// (`left_at`) rather than deleted, and dropped from the member's "my
+    /// teams" projection, so it stays in the team's squad history. Errors with
+    /// `DaoError::NotFound` if the membership doesn't exist or has already
+    /// ended, rather than silently succeeding, and with `DaoError::Conflict`
+    /// if it's the team's last admin (or the team changed underneath us).
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_team_member(
+        &self,
+        team_id: &str,
+        membership_id: &str,
+        left_at: &str,
+    ) -> DaoResult<()> {
+        let not_found =
+            || DaoError::NotFound(format!("membership {membership_id} on team {team_id}"));
+        let agg = self.get_team(team_id).await?.ok_or_else(not_found)?;
+        let member = agg
+            .members
+            .iter()
+            .find(|m| m.membership_id == membership_id)
+            .ok_or_else(not_found)?;
+
+        let end = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key("SK", s(Sk::Member(membership_id.into()).to_string()))
+            .update_expression("SET left_at = :left_at REMOVE #gsi1pk, #gsi1sk")
+            .condition_expression(
+                "attribute_exists(#pk) AND attribute_not_exists(left_at) AND #role = :role",
+            )
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_names("#role", "role")
+            .expression_attribute_values(":left_at", s(left_at))
+            .expression_attribute_values(":role", s(member.role.clone()))
+            .build()
             .map_err(|e| DaoError::Dynamo(e.to_string()))?;
-        Ok(())
+
+        let mut tx = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(end).build());
+        if let Some(user_id) = &member.user_id {
+            tx = tx.transact_items(
+                TransactWriteItem::builder()
+                    .delete(self.member_guard_delete(team_id, user_id, membership_id)?)
+                    .build(),
+            );
+        }
+        if member.role == ROLE_ADMIN {
+            tx = tx.transact_items(
+                TransactWriteItem::builder()
+                    .condition_check(self.other_admin_check(&agg, membership_id)?)
+                    .build(),
+            );
+        }
+
+        match tx.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::Conflict(
+                format!("team {team_id} changed while removing {membership_id}; retry"),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Hand admin from one member to another: `to` becomes an admin and `from`
+    /// steps down to a plain member, each with the old role kept in
+    /// `previous_roles`. Both must be current members, `from` an admin and `to`
+    /// a linked user. `Conflict` if either changed since read.
+    #[tracing::instrument(skip(self))]
+    pub async fn transfer_team_admin(
+        &self,
+        team_id: &str,
+        from_membership_id: &str,
+        to_membership_id: &str,
+        at: &str,
+    ) -> DaoResult<()> {
+        let agg = self
+            .get_team(team_id)
+            .await?
+            .ok_or_else(|| DaoError::NotFound(format!("team {team_id}")))?;
+        let find = |id: &str| {
+            agg.members
+                .iter()
+                .find(|m| m.membership_id == id)
+                .ok_or_else(|| DaoError::NotFound(format!("membership {id} on team {team_id}")))
+        };
+        let from = find(from_membership_id)?;
+        let to = find(to_membership_id)?;
+        if from.role != ROLE_ADMIN {
+            return Err(DaoError::Conflict(format!(
+                "membership {from_membership_id} is not an admin"
+            )));
+        }
+        if to.user_id.is_none() || from_membership_id == to_membership_id {
+            return Err(DaoError::Conflict(format!(
+                "membership {to_membership_id} can't take over as admin"
+            )));
+        }
+
+        let mut tx = self.client.transact_write_items();
+        // `to` is written even if already an admin, so the guard also covers
+        // it being removed concurrently.
+        for (member, role) in [(to, ROLE_ADMIN), (from, ROLE_MEMBER)] {
+            let mut changed = member.clone();
+            if member.role != role {
+                changed.previous_roles.push(PreviousRoleRecord {
+                    role: member.role.clone(),
+                    until: at.to_string(),
+                });
+                changed.role = role.to_string();
+            }
+            tx = tx.transact_items(
+                TransactWriteItem::builder()
+                    .put(self.guarded_member_put(team_id, member, &changed)?)
+                    .build(),
+            );
+        }
+
+        match tx.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::Conflict(
+                format!("team {team_id} changed during the handover; retry"),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
     }
 
-    /// Remove a member from a team by membership id. Delete-by-id, not a
-    /// toggle, so errors with `DaoError::NotFound` if the membership doesn't
-    /// exist rather than silently succeeding.
+    /// Archive a team: it stops being listed or searchable and can't be
+    /// followed or joined, but its meta stays, so past matches referencing it
+    /// keep rendering its name. Also takes it out of its club. Followers are
+    /// detached separately (`remove_all_team_followers`). `NotFound` if the
+    /// team doesn't exist, `Conflict` if it's already archived.
     #[tracing::instrument(skip(self))]
-    pub async fn remove_team_member(&self, team_id: &str, membership_id: &str) -> DaoResult<()> {
+    pub async fn archive_team(&self, team_id: &str, at: &str) -> DaoResult<()> {
         let result = self
             .client
-            .delete_item()
+            .update_item()
             .table_name(self.table())
             .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
-            .key("SK", s(Sk::Member(membership_id.into()).to_string()))
-            .condition_expression("attribute_exists(#pk)")
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression("SET archived_at = :at REMOVE club_id, #gsi1pk, #gsi1sk")
+            .condition_expression("attribute_exists(#pk) AND attribute_not_exists(archived_at)")
             .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":at", s(at))
             .send()
             .await;
-
         match result {
             Ok(_) => Ok(()),
-            Err(e) if is_delete_conditional_failure(&e) => Err(DaoError::NotFound(format!(
-                "membership {membership_id} on team {team_id}"
-            ))),
+            Err(e) if is_update_conditional_failure(&e) => {
+                match self.get_team_meta(team_id).await? {
+                    None => Err(DaoError::NotFound(format!("team {team_id}"))),
+                    Some(_) => Err(DaoError::Conflict(format!(
+                        "team {team_id} is already archived"
+                    ))),
+                }
+            }
             Err(e) => Err(DaoError::Dynamo(e.to_string())),
         }
     }
@@ -243,9 +440,60 @@ impl Dao {
         .await
     }
 
+    /// A condition check that some current admin other than
+    /// `excluding_membership_id` is still an admin — what keeps a team from
+    /// losing its last one. `Conflict` if the aggregate has no such admin.
+    fn other_admin_check(
+        &self,
+        agg: &TeamAggregate,
+        excluding_membership_id: &str,
+    ) -> DaoResult<ConditionCheck> {
+        let Some(other) = agg.members.iter().find(|m| {
+            m.role == ROLE_ADMIN
+                && m.user_id.is_some()
+                && m.membership_id != excluding_membership_id
+        }) else {
+            return Err(DaoError::Conflict(format!(
+                "team {} must keep at least one admin",
+                agg.team.id
+            )));
+        };
+        ConditionCheck::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(agg.team.id.clone()).to_string()))
+            .key("SK", s(Sk::Member(other.membership_id.clone()).to_string()))
+            .condition_expression("#role = :admin AND attribute_not_exists(left_at)")
+            .expression_attribute_names("#role", "role")
+            .expression_attribute_values(":admin", s(ROLE_ADMIN))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+
+    /// A put overwriting `current` with `changed`, conditional on the stored
+    /// membership still having `current`'s role and not having ended.
+    fn guarded_member_put(
+        &self,
+        team_id: &str,
+        current: &TeamMemberRecord,
+        changed: &TeamMemberRecord,
+    ) -> DaoResult<Put> {
+        Put::builder()
+            .table_name(self.table())
+            .set_item(Some(self.team_member_item(team_id, changed)?))
+            .condition_expression(
+                "attribute_exists(#pk) AND attribute_not_exists(left_at) AND #role = :role",
+            )
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#role", "role")
+            .expression_attribute_values(":role", s(current.role.clone()))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+
     /// Build a team-member item, projecting members with a linked user into GSI1
     /// (`UTEAMS#<userId>`) so the user can list their teams. External-only
-    /// members (no `user_id`) are not projected — they don't have a "my teams".
+    /// members (no `user_id`) are not projected — they don't have a "my teams" —
+    /// and neither are former members.
     pub(super) fn team_member_item(
         &self,
         team_id: &str,
@@ -258,16 +506,82 @@ impl Dao {
             member,
         )?;
         let item = match &member.user_id {
-            Some(uid) => ItemBuilder::new(base)
+            Some(uid) if member.left_at.is_none() => ItemBuilder::new(base)
                 .gsi1(
                     format!("UTEAMS#{uid}"),
                     Pk::Team(team_id.into()).to_string(),
                 )
                 .build(),
-            None => base,
+            _ => base,
         };
         Ok(item)
     }
+
+    /// The conditional put of a current, user-linked membership's guard:
+    /// fails if the user already holds a different current membership of this
+    /// team (re-writing the same membership's is fine). `None` for an external
+    /// or ended membership, which holds no guard.
+    pub(super) fn member_guard_put(
+        &self,
+        team_id: &str,
+        member: &TeamMemberRecord,
+    ) -> DaoResult<Option<Put>> {
+        let Some(user_id) = member.user_id.as_ref().filter(|_| member.left_at.is_none()) else {
+            return Ok(None);
+        };
+        let item = to_item(
+            &Pk::Team(team_id.into()),
+            &Sk::MemberGuard(user_id.clone()),
+            TYPE_MEMBER_GUARD,
+            &MemberGuardRecord {
+                membership_id: member.membership_id.clone(),
+            },
+        )?;
+        Put::builder()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk) OR membership_id = :mid")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":mid", s(member.membership_id.clone()))
+            .build()
+            .map(Some)
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+
+    /// Release `membership_id`'s guard for `user_id`. Guarded on the guard
+    /// being that membership's, so ending one membership can't free another's;
+    /// a membership from before guards existed has none, which is fine too.
+    fn member_guard_delete(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        membership_id: &str,
+    ) -> DaoResult<Delete> {
+        Delete::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key(ATTR_SK, s(Sk::MemberGuard(user_id.into()).to_string()))
+            .condition_expression("attribute_not_exists(#pk) OR membership_id = :mid")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":mid", s(membership_id))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+}
+
+/// Build a team's meta item, projecting a club-owned team into GSI1
+/// (`CTEAMS#<clubId>`) so the club can list its teams.
+fn team_meta_item(team: &TeamRecord) -> DaoResult<HashMap<String, AttributeValue>> {
+    let base = to_item(&Pk::Team(team.id.clone()), &Sk::Meta, TYPE_TEAM, team)?;
+    Ok(match &team.club_id {
+        Some(club_id) => ItemBuilder::new(base)
+            .gsi1(
+                super::club::club_teams_gsi1pk(club_id),
+                Pk::Team(team.id.clone()).to_string(),
+            )
+            .build(),
+        None => base,
+    })
 }
 
 fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
@@ -278,10 +592,157 @@ fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
     )
 }
 
-fn is_delete_conditional_failure(err: &SdkError<DeleteItemError>) -> bool {
-    matches!(
-        err,
-        SdkError::ServiceError(se)
-            if matches!(se.err(), DeleteItemError::ConditionalCheckFailedException(_))
-    )
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{ClubAdminRecord, ClubRecord, Visibility};
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+    const LATER: &str = "2026-05-01T10:00:00Z";
+
+    fn member(membership_id: &str, role: &str) -> TeamMemberRecord {
+        TeamMemberRecord {
+            team_id: "t1".into(),
+            membership_id: membership_id.into(),
+            user_id: Some(format!("user-{membership_id}")),
+            display_name: None,
+            role: role.into(),
+            invitation: None,
+            created_at: NOW.into(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        }
+    }
+
+    /// Team `t1` with `m1` as its admin and `m2` as a plain member.
+    async fn team(dao: &Dao) {
+        let team = TeamRecord {
+            id: "t1".into(),
+            name: "Rovers".into(),
+            invite_token: None,
+            follower_count: 0,
+            club_id: None,
+            archived_at: None,
+            visibility: Visibility::Public,
+            created_at: NOW.into(),
+        };
+        dao.create_team(&team, &member("m1", ROLE_ADMIN))
+            .await
+            .unwrap();
+        dao.put_team_member("t1", &member("m2", ROLE_MEMBER))
+            .await
+            .unwrap();
+    }
+
+    fn find<'a>(members: &'a [TeamMemberRecord], id: &str) -> &'a TeamMemberRecord {
+        members.iter().find(|m| m.membership_id == id).unwrap()
+    }
+
+    #[tokio::test]
+    async fn the_last_admin_cannot_leave() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        team(&dao).await;
+        assert!(matches!(
+            dao.remove_team_member("t1", "m1", LATER).await,
+            Err(DaoError::Conflict(_))
+        ));
+
+        // A plain member can, and only once.
+        dao.remove_team_member("t1", "m2", LATER).await.unwrap();
+        assert!(matches!(
+            dao.remove_team_member("t1", "m2", LATER).await,
+            Err(DaoError::NotFound(_))
+        ));
+        let agg = dao.get_team("t1").await.unwrap().unwrap();
+        assert_eq!(agg.members.len(), 1);
+        assert_eq!(
+            find(&agg.former_members, "m2").left_at.as_deref(),
+            Some(LATER)
+        );
+    }
+
+    #[tokio::test]
+    async fn handing_over_admin_keeps_previous_roles() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        team(&dao).await;
+        dao.transfer_team_admin("t1", "m1", "m2", LATER)
+            .await
+            .unwrap();
+
+        let agg = dao.get_team("t1").await.unwrap().unwrap();
+        let (from, to) = (find(&agg.members, "m1"), find(&agg.members, "m2"));
+        assert_eq!(from.role, ROLE_MEMBER);
+        assert_eq!(
+            from.previous_roles,
+            [PreviousRoleRecord {
+                role: ROLE_ADMIN.into(),
+                until: LATER.into(),
+            }]
+        );
+        assert_eq!(to.role, ROLE_ADMIN);
+        assert_eq!(
+            to.previous_roles,
+            [PreviousRoleRecord {
+                role: ROLE_MEMBER.into(),
+                until: LATER.into(),
+            }]
+        );
+
+        // The old admin is now a plain member and can leave; the new one can't.
+        assert!(matches!(
+            dao.transfer_team_admin("t1", "m1", "m2", LATER).await,
+            Err(DaoError::Conflict(_))
+        ));
+        dao.remove_team_member("t1", "m1", LATER).await.unwrap();
+        assert!(matches!(
+            dao.remove_team_member("t1", "m2", LATER).await,
+            Err(DaoError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn archiving_leaves_the_club_and_drops_followers() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        team(&dao).await;
+        let club = ClubRecord {
+            id: "c1".into(),
+            name: "Rovers FC".into(),
+            follower_count: 0,
+            created_at: NOW.into(),
+        };
+        let chair = ClubAdminRecord {
+            club_id: "c1".into(),
+            user_id: "chair".into(),
+            created_at: NOW.into(),
+        };
+        dao.create_club(&club, &chair).await.unwrap();
+        dao.attach_team_to_club("t1", "c1").await.unwrap();
+        for fan in 0..30 {
+            dao.follow_team(&format!("fan{fan}"), "t1", NOW)
+                .await
+                .unwrap();
+        }
+
+        dao.archive_team("t1", LATER).await.unwrap();
+        assert!(matches!(
+            dao.archive_team("t1", LATER).await,
+            Err(DaoError::Conflict(_))
+        ));
+        let meta = dao.get_team_meta("t1").await.unwrap().unwrap();
+        assert_eq!(meta.club_id, None);
+        assert_eq!(meta.archived_at.as_deref(), Some(LATER));
+        assert!(dao.list_club_teams("c1").await.unwrap().is_empty());
+
+        assert_eq!(dao.remove_all_team_followers("t1").await.unwrap(), 30);
+        assert_eq!(dao.remove_all_team_followers("t1").await.unwrap(), 0);
+        let meta = dao.get_team_meta("t1").await.unwrap().unwrap();
+        assert_eq!(meta.follower_count, 0);
+        let followers = dao.list_team_followers("t1", None, 100).await.unwrap();
+        assert!(followers.items.is_empty());
+        assert!(!dao.is_following_team("fan0", "t1").await.unwrap());
+    }
 }
@@ -0,0 +1,542 @@
+//! Ways into a team other than a personal invitation: multi-use invite links
+//! and join requests.
+//!
+//! An invite link is a token-kind `InvitationRecord` carrying an
+//! `InviteLinkRecord` (so it's resolved by the same `get_invitation_by_token`
+//! GSI2 lookup as any token invite). Redeeming one either adds the redeemer as
+//! a member straight away or, if the link requires approval, files a
+//! `JoinRequestRecord` for an admin to approve or deny. A join request can also
+//! be filed directly, without a link.
+//!
+//! Both ways in write the new membership with its guard (see `dao::team`), so
+//! two racing redemptions, or a redemption racing an approval, can't make the
+//! same user a member twice. A pending request is projected into GSI1
+//! (`TJOINREQS#<teamId>`, sort `<requested_at>#<userId>`) until it's answered,
+//! so an admin's queue is a key query rather than a filtered one.
+
+use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem, Update};
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_GSI1PK, ATTR_GSI1SK, ATTR_PK, ItemBuilder, from_item, s, to_item};
+use super::keys::{Pk, Sk};
+use super::page::Page;
+use super::records::{InvitationRecord, JoinRequestRecord, TeamMemberRecord};
+
+pub const TYPE_JOIN_REQUEST: &str = "join_request";
+
+/// What redeeming an invite link produces: a membership (open link) or a join
+/// request (approval-gated link). The caller decides from the link's
+/// `requires_approval` and builds the record.
+#[derive(Debug)]
+pub enum LinkRedemption<'a> {
+    Join(&'a TeamMemberRecord),
+    Request(&'a JoinRequestRecord),
+}
+
+impl Dao {
+    /// List a team's invite links, newest first, via GSI1 (`TLINKS#<teamId>`).
+    /// Includes expired and used-up links; the caller decides how to show them.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_team_invite_links(
+        &self,
+        team_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<InvitationRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(format!("TLINKS#{team_id}")))
+                .scan_index_forward(false),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Redeem an invite link: in one transaction, count the use against the
+    /// link and write the resulting membership or join request. The link's
+    /// update is guarded on it still existing, being `pending` (not revoked),
+    /// unexpired as of `now` and under `max_uses` — so two racing redemptions
+    /// of a one-use link can't both succeed.
+    ///
+    /// `Conflict` if any of those guards fail, the user is already a member
+    /// (for a join), or the user already has a pending request for the team
+    /// (for a request).
+    #[tracing::instrument(skip(self, outcome))]
+    pub async fn redeem_invite_link(
+        &self,
+        invitation_id: &str,
+        now: &str,
+        outcome: LinkRedemption<'_>,
+    ) -> DaoResult<()> {
+        let bump = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Invitation(invitation_id.into()).to_string()))
+            .key("SK", s(Sk::Meta.to_string()))
+            .update_expression("SET #link.use_count = if_not_exists(#link.use_count, :zero) + :one")
+            .condition_expression(
+                "attribute_exists(#pk) AND #status = :pending \
+                 AND (attribute_not_exists(#link.expires_at) OR #link.expires_at > :now) \
+                 AND (attribute_not_exists(#link.max_uses) OR #link.use_count < #link.max_uses)",
+            )
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#link", "link")
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":zero", AttributeValue::N("0".into()))
+            .expression_attribute_values(":one", AttributeValue::N("1".into()))
+            .expression_attribute_values(":pending", s("pending"))
+            .expression_attribute_values(":now", s(now))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let (put, guard) = match outcome {
+            LinkRedemption::Join(member) => (
+                Put::builder()
+                    .table_name(self.table())
+                    .set_item(Some(self.team_member_item(&member.team_id, member)?))
+                    .build()
+                    .map_err(|e| DaoError::Dynamo(e.to_string()))?,
+                self.member_guard_put(&member.team_id, member)?,
+            ),
+            LinkRedemption::Request(request) => (self.put_join_request(request)?, None),
+        };
+
+        let mut tx = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(bump).build())
+            .transact_items(TransactWriteItem::builder().put(put).build());
+        if let Some(guard) = guard {
+            tx = tx.transact_items(TransactWriteItem::builder().put(guard).build());
+        }
+
+        match tx.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_item_conditional_failure(&e, 2) => Err(
+                DaoError::Conflict("you are already a member of this team".into()),
+            ),
+            Err(e) if super::is_transaction_conditional_failure(&e) => {
+                Err(DaoError::Conflict(format!(
+                    "invite link {invitation_id} is revoked, expired or used up, or a join request is already pending"
+                )))
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// File a join request. `Conflict` if the user already has a pending
+    /// request for this team; an earlier approved/denied one is overwritten.
+    #[tracing::instrument(skip(self, request), fields(team_id = %request.team_id, user_id = %request.user_id))]
+    pub async fn create_join_request(&self, request: &JoinRequestRecord) -> DaoResult<()> {
+        let put = self.put_join_request(request)?;
+        let result = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_conditional_failure(&e) => {
+                Err(DaoError::Conflict(format!(
+                    "user {} already has a pending request to join team {}",
+                    request.user_id, request.team_id
+                )))
+            }
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Fetch a user's join request for a team (any status). `None` if absent.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_join_request(
+        &self,
+        team_id: &str,
+        user_id: &str,
+    ) -> DaoResult<Option<JoinRequestRecord>> {
+        let out = self
+            .client
+            .get_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key("SK", s(Sk::JoinRequest(user_id.into()).to_string()))
+            .send()
+            .await
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+        match out.item {
+            Some(item) => Ok(Some(from_item(item)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List a team's pending join requests, oldest first, via GSI1
+    /// (`TJOINREQS#<teamId>`). Only pending requests are projected there, so
+    /// every page is full until the queue runs out.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_pending_join_requests(
+        &self,
+        team_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> DaoResult<Page<JoinRequestRecord>> {
+        self.query_page(
+            self.client
+                .query()
+                .table_name(self.table())
+                .index_name("GSI1")
+                .key_condition_expression("#pk = :pk")
+                .expression_attribute_names("#pk", ATTR_GSI1PK)
+                .expression_attribute_values(":pk", s(pending_join_requests_gsi1pk(team_id))),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Approve or deny a pending join request. On approval, `member` (the new
+    /// membership, built by the caller with the request's role) is written in
+    /// the same transaction as the status change, so an approved request always
+    /// has its membership. `NotFound` if there's no pending request — including
+    /// one another admin already answered — and `Conflict` on approving a user
+    /// who has become a member some other way since.
+    #[tracing::instrument(skip(self, member))]
+    pub async fn respond_to_join_request(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        responded_by_user_id: &str,
+        responded_at: &str,
+        member: Option<&TeamMemberRecord>,
+    ) -> DaoResult<()> {
+        let status = if member.is_some() {
+            "approved"
+        } else {
+            "denied"
+        };
+        let update = Update::builder()
+            .table_name(self.table())
+            .key(ATTR_PK, s(Pk::Team(team_id.into()).to_string()))
+            .key("SK", s(Sk::JoinRequest(user_id.into()).to_string()))
+            .update_expression(
+                "SET #status = :status, responded_at = :responded_at, responded_by_user_id = :by \
+                 REMOVE #gsi1pk, #gsi1sk",
+            )
+            .condition_expression("attribute_exists(#pk) AND #status = :pending")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_names("#gsi1pk", ATTR_GSI1PK)
+            .expression_attribute_names("#gsi1sk", ATTR_GSI1SK)
+            .expression_attribute_values(":status", s(status))
+            .expression_attribute_values(":pending", s("pending"))
+            .expression_attribute_values(":responded_at", s(responded_at))
+            .expression_attribute_values(":by", s(responded_by_user_id))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+
+        let mut tx = self
+            .client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(update).build());
+        if let Some(member) = member {
+            let put = Put::builder()
+                .table_name(self.table())
+                .set_item(Some(self.team_member_item(team_id, member)?))
+                .build()
+                .map_err(|e| DaoError::Dynamo(e.to_string()))?;
+            tx = tx.transact_items(TransactWriteItem::builder().put(put).build());
+            if let Some(guard) = self.member_guard_put(team_id, member)? {
+                tx = tx.transact_items(TransactWriteItem::builder().put(guard).build());
+            }
+        }
+
+        match tx.send().await {
+            Ok(_) => Ok(()),
+            Err(e) if super::is_transaction_item_conditional_failure(&e, 2) => Err(
+                DaoError::Conflict(format!("{user_id} is already a member of team {team_id}")),
+            ),
+            Err(e) if super::is_transaction_conditional_failure(&e) => Err(DaoError::NotFound(
+                format!("pending join request from {user_id} on team {team_id}"),
+            )),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// A guarded put of a join request: succeeds unless a *pending* request
+    /// from the same user already exists. Shared by direct requests and
+    /// approval-gated link redemptions.
+    fn put_join_request(&self, request: &JoinRequestRecord) -> DaoResult<Put> {
+        let item = ItemBuilder::new(to_item(
+            &Pk::Team(request.team_id.clone()),
+            &Sk::JoinRequest(request.user_id.clone()),
+            TYPE_JOIN_REQUEST,
+            request,
+        )?)
+        .gsi1(
+            pending_join_requests_gsi1pk(&request.team_id),
+            format!("{}#{}", request.requested_at, request.user_id),
+        )
+        .build();
+        Put::builder()
+            .table_name(self.table())
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(#pk) OR #status <> :pending")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(":pending", s("pending"))
+            .build()
+            .map_err(|e| DaoError::Dynamo(e.to_string()))
+    }
+}
+
+/// GSI1 partition of a team's pending join requests.
+fn pending_join_requests_gsi1pk(team_id: &str) -> String {
+    format!("TJOINREQS#{team_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::dao::memory::MemoryTable;
+    use crate::dao::records::{
+        InvitationContextRecord, InvitationKindRecord, InviteLinkRecord, TeamRecord,
+    };
+
+    const NOW: &str = "2026-03-01T10:00:00Z";
+
+    /// A fresh membership for `user_id`, with a membership id of its own.
+    fn member(team_id: &str, user_id: &str, role: &str) -> TeamMemberRecord {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        TeamMemberRecord {
+            team_id: team_id.into(),
+            membership_id: format!("mem{}", NEXT.fetch_add(1, Ordering::Relaxed)),
+            user_id: Some(user_id.into()),
+            display_name: None,
+            role: role.into(),
+            invitation: None,
+            created_at: NOW.into(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        }
+    }
+
+    fn request(team_id: &str, user_id: &str, requested_at: &str) -> JoinRequestRecord {
+        JoinRequestRecord {
+            id: format!("req-{user_id}"),
+            team_id: team_id.into(),
+            user_id: user_id.into(),
+            status: "pending".into(),
+            role: "member".into(),
+            via_invitation_id: None,
+            requested_at: requested_at.into(),
+            responded_at: None,
+            responded_by_user_id: None,
+        }
+    }
+
+    /// A team `t1` administered by `admin`, with an invite link `link1`.
+    async fn team_with_link(max_uses: Option<u32>, requires_approval: bool) -> (MemoryTable, Dao) {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let team = TeamRecord {
+            id: "t1".into(),
+            name: "The Aces".into(),
+            invite_token: None,
+            follower_count: 0,
+            club_id: None,
+            archived_at: None,
+            visibility: Default::default(),
+            created_at: NOW.into(),
+        };
+        dao.create_team(&team, &member("t1", "admin", "admin"))
+            .await
+            .unwrap();
+        dao.create_invitation(&InvitationRecord {
+            id: "link1".into(),
+            status: "pending".into(),
+            invited_by_user_id: "admin".into(),
+            invited_user_id: None,
+            invite_token: Some("tok1".into()),
+            invitee_email: None,
+            kind: InvitationKindRecord::Token {
+                invite_token: "tok1".into(),
+            },
+            context: InvitationContextRecord::Team {
+                team_id: "t1".into(),
+                team_name: "The Aces".into(),
+            },
+            invited_at: NOW.into(),
+            responded_at: None,
+            link: Some(InviteLinkRecord {
+                name: "Freshers".into(),
+                expires_at: None,
+                max_uses,
+                use_count: 0,
+                default_role: "member".into(),
+                requires_approval,
+            }),
+        })
+        .await
+        .unwrap();
+        (table, dao)
+    }
+
+    async fn member_ids(dao: &Dao) -> Vec<String> {
+        let mut ids: Vec<String> = dao
+            .get_team("t1")
+            .await
+            .unwrap()
+            .unwrap()
+            .members
+            .into_iter()
+            .filter_map(|m| m.user_id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn racing_redemptions_make_one_membership() {
+        let (_table, dao) = team_with_link(None, false).await;
+        let (a, b) = (member("t1", "u2", "member"), member("t1", "u2", "member"));
+        let (first, second) = tokio::join!(
+            dao.redeem_invite_link("link1", NOW, LinkRedemption::Join(&a)),
+            dao.redeem_invite_link("link1", NOW, LinkRedemption::Join(&b)),
+        );
+        assert!(first.is_ok() != second.is_ok(), "{first:?} / {second:?}");
+        let again = member("t1", "u2", "member");
+        assert!(matches!(
+            dao.redeem_invite_link("link1", NOW, LinkRedemption::Join(&again))
+                .await,
+            Err(DaoError::Conflict(_))
+        ));
+        assert_eq!(member_ids(&dao).await, ["admin", "u2"]);
+    }
+
+    #[tokio::test]
+    async fn a_link_stops_at_its_use_limit() {
+        let (_table, dao) = team_with_link(Some(1), false).await;
+        let (u2, u3) = (member("t1", "u2", "member"), member("t1", "u3", "member"));
+        dao.redeem_invite_link("link1", NOW, LinkRedemption::Join(&u2))
+            .await
+            .unwrap();
+        assert!(matches!(
+            dao.redeem_invite_link("link1", NOW, LinkRedemption::Join(&u3))
+                .await,
+            Err(DaoError::Conflict(_))
+        ));
+        let link = dao.get_invitation("link1").await.unwrap().unwrap().link;
+        assert_eq!(link.unwrap().use_count, 1);
+        assert_eq!(member_ids(&dao).await, ["admin", "u2"]);
+    }
+
+    #[tokio::test]
+    async fn approval_after_joining_another_way_conflicts() {
+        let (_table, dao) = team_with_link(None, false).await;
+        dao.create_join_request(&request("t1", "u2", NOW))
+            .await
+            .unwrap();
+        dao.redeem_invite_link(
+            "link1",
+            NOW,
+            LinkRedemption::Join(&member("t1", "u2", "member")),
+        )
+        .await
+        .unwrap();
+
+        let approved = member("t1", "u2", "member");
+        assert!(matches!(
+            dao.respond_to_join_request("t1", "u2", "admin", NOW, Some(&approved))
+                .await,
+            Err(DaoError::Conflict(_))
+        ));
+        assert_eq!(member_ids(&dao).await, ["admin", "u2"]);
+        let pending = dao.get_join_request("t1", "u2").await.unwrap().unwrap();
+        assert_eq!(pending.status, "pending");
+    }
+
+    #[tokio::test]
+    async fn approving_adds_the_member_once() {
+        let (_table, dao) = team_with_link(None, true).await;
+        dao.redeem_invite_link(
+            "link1",
+            NOW,
+            LinkRedemption::Request(&request("t1", "u2", NOW)),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            dao.create_join_request(&request("t1", "u2", NOW)).await,
+            Err(DaoError::Conflict(_))
+        ));
+
+        let approved = member("t1", "u2", "member");
+        dao.respond_to_join_request("t1", "u2", "admin", NOW, Some(&approved))
+            .await
+            .unwrap();
+        assert!(matches!(
+            dao.respond_to_join_request("t1", "u2", "admin", NOW, Some(&approved))
+                .await,
+            Err(DaoError::NotFound(_))
+        ));
+        assert_eq!(member_ids(&dao).await, ["admin", "u2"]);
+
+        // Leaving frees the user to join again.
+        dao.remove_team_member("t1", &approved.membership_id, NOW)
+            .await
+            .unwrap();
+        dao.redeem_invite_link(
+            "link1",
+            NOW,
+            LinkRedemption::Request(&request("t1", "u2", NOW)),
+        )
+        .await
+        .unwrap();
+        dao.respond_to_join_request(
+            "t1",
+            "u2",
+            "admin",
+            NOW,
+            Some(&member("t1", "u2", "member")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(member_ids(&dao).await, ["admin", "u2"]);
+    }
+
+    #[tokio::test]
+    async fn pending_requests_page_without_gaps() {
+        let (_table, dao) = team_with_link(None, true).await;
+        for (i, user) in ["u2", "u3", "u4", "u5"].iter().enumerate() {
+            let at = format!("2026-03-0{}T10:00:00Z", i + 1);
+            dao.create_join_request(&request("t1", user, &at))
+                .await
+                .unwrap();
+        }
+        dao.respond_to_join_request("t1", "u2", "admin", NOW, None)
+            .await
+            .unwrap();
+        dao.respond_to_join_request(
+            "t1",
+            "u4",
+            "admin",
+            NOW,
+            Some(&member("t1", "u4", "member")),
+        )
+        .await
+        .unwrap();
+
+        // Answered requests don't take up room on the page.
+        let page = dao.list_pending_join_requests("t1", None, 2).await.unwrap();
+        let pending: Vec<_> = page.items.into_iter().map(|r| r.user_id).collect();
+        assert_eq!(pending, ["u3", "u5"]);
+    }
+}
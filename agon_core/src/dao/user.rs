@@ -206,6 +206,8 @@ impl Dao {
                     names.insert("#img".into(), "profile_image_url".into());
                 }
             }
+            // A moderation hide applied to the old image, not the new one.
+            remove_parts.push("profile_image_hidden_at".into());
         }
 
         if set_parts.is_empty() && remove_parts.is_empty() {
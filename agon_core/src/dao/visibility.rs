@@ -0,0 +1,383 @@
+//! Visibility checks and updates for matches, teams and profiles (see
+//! `records::Visibility`).
+//!
+//! Who counts as a participant:
+//! - a match: its creator and every player with a linked account;
+//! - a team: its current members with a linked account, plus the admins of
+//!   the club owning it (who already administer the team);
+//! - a profile: its owner.
+//!
+//! A followers-level record is also visible to anyone following one of its
+//! participants — for a match, additionally anyone following one of its side
+//! teams or those teams' clubs, the same follow edges that put a match in a
+//! feed (see `audience`). Every follow check for one record is a single
+//! `BatchGetItem` over the viewer's candidate `FOLLOWER#` edges.
+//!
+//! An anonymous viewer (`None`) sees public records only.
+//!
+//! A match or profile hidden by moderation (`hidden_at`) is treated as
+//! participants-only whatever its visibility, until a moderator restores it.
+
+use std::collections::{HashMap, HashSet};
+
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+
+use super::client::Dao;
+use super::error::{DaoError, DaoResult};
+use super::item::{ATTR_PK, ATTR_SK, s};
+use super::keys::{Pk, Sk};
+use super::match_ops::{MatchAggregate, MatchSummary};
+use super::records::{UserRecord, Visibility};
+use super::team::TeamAggregate;
+
+/// User ids that count as a match's participants: the creator plus every
+/// linked player.
+pub fn match_participant_ids(agg: &MatchAggregate) -> HashSet<&str> {
+    let mut ids: HashSet<&str> = agg
+        .players
+        .iter()
+        .filter_map(|p| p.user_id.as_deref())
+        .collect();
+    if !agg.match_.created_by_user_id.is_empty() {
+        ids.insert(&agg.match_.created_by_user_id);
+    }
+    ids
+}
+
+/// User ids of a team's current members with a linked account.
+pub fn team_member_ids(agg: &TeamAggregate) -> HashSet<&str> {
+    agg.members
+        .iter()
+        .filter_map(|m| m.user_id.as_deref())
+        .collect()
+}
+
+impl Dao {
+    /// Whether `viewer` may see the match. See the module docs for who that
+    /// is at each level.
+    #[tracing::instrument(skip(self, agg), fields(match_id = %agg.match_.id))]
+    pub async fn can_view_match(
+        &self,
+        agg: &MatchAggregate,
+        viewer: Option<&str>,
+    ) -> DaoResult<bool> {
+        let visibility = if agg.match_.hidden_at.is_some() {
+            Visibility::Participants
+        } else {
+            agg.match_.visibility
+        };
+        if visibility == Visibility::Public {
+            return Ok(true);
+        }
+        let Some(viewer) = viewer else {
+            return Ok(false);
+        };
+        let participants = match_participant_ids(agg);
+        if participants.contains(viewer) {
+            return Ok(true);
+        }
+        if visibility == Visibility::Participants {
+            return Ok(false);
+        }
+
+        let mut followees: Vec<Pk> = participants
+            .iter()
+            .map(|id| Pk::User((*id).to_string()))
+            .collect();
+        let team_ids: Vec<String> = agg
+            .sides
+            .iter()
+            .filter_map(|side| side.team_id.clone())
+            .collect();
+        if !team_ids.is_empty() {
+            let teams = self.batch_get_team_metas(&team_ids).await?;
+            let club_ids: HashSet<&str> = teams
+                .values()
+                .filter_map(|team| team.club_id.as_deref())
+                .collect();
+            followees.extend(club_ids.into_iter().map(|id| Pk::Club(id.to_string())));
+            followees.extend(team_ids.into_iter().map(Pk::Team));
+        }
+        self.follows_any(viewer, followees).await
+    }
+
+    /// Drop the matches `viewer` may not see from a batch of summaries. Public
+    /// matches not hidden by moderation pass straight through; the rest are
+    /// re-read in full (the check needs the roster, which summaries leave
+    /// out), so this stays cheap as long as restricted matches are the
+    /// exception.
+    #[tracing::instrument(skip(self, summaries))]
+    pub async fn retain_visible_matches(
+        &self,
+        summaries: &mut HashMap<String, MatchSummary>,
+        viewer: Option<&str>,
+    ) -> DaoResult<()> {
+        let restricted: Vec<String> = summaries
+            .values()
+            .filter(|s| s.match_.visibility != Visibility::Public || s.match_.hidden_at.is_some())
+            .map(|s| s.match_.id.clone())
+            .collect();
+        for match_id in restricted {
+            let visible = match self.get_match(&match_id).await? {
+                Some(agg) => self.can_view_match(&agg, viewer).await?,
+                None => false,
+            };
+            if !visible {
+                summaries.remove(&match_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `viewer` may see the team, its squad, seasons and followers.
+    #[tracing::instrument(skip(self, agg), fields(team_id = %agg.team.id))]
+    pub async fn can_view_team(
+        &self,
+        agg: &TeamAggregate,
+        viewer: Option<&str>,
+    ) -> DaoResult<bool> {
+        let visibility = agg.team.visibility;
+        if visibility == Visibility::Public {
+            return Ok(true);
+        }
+        let Some(viewer) = viewer else {
+            return Ok(false);
+        };
+        if team_member_ids(agg).contains(viewer) {
+            return Ok(true);
+        }
+        if let Some(club_id) = &agg.team.club_id
+            && self.is_club_admin(club_id, viewer).await?
+        {
+            return Ok(true);
+        }
+        if visibility == Visibility::Participants {
+            return Ok(false);
+        }
+
+        let mut followees = vec![Pk::Team(agg.team.id.clone())];
+        if let Some(club_id) = &agg.team.club_id {
+            followees.push(Pk::Club(club_id.clone()));
+        }
+        self.follows_any(viewer, followees).await
+    }
+
+    /// Whether `viewer` may see the profile's stats and follow lists.
+    #[tracing::instrument(skip(self, user), fields(user_id = %user.id))]
+    pub async fn can_view_user(&self, user: &UserRecord, viewer: Option<&str>) -> DaoResult<bool> {
+        let visibility = if user.hidden_at.is_some() {
+            Visibility::Participants
+        } else {
+            user.visibility
+        };
+        if visibility == Visibility::Public {
+            return Ok(true);
+        }
+        let Some(viewer) = viewer else {
+            return Ok(false);
+        };
+        if viewer == user.id {
+            return Ok(true);
+        }
+        if visibility == Visibility::Participants {
+            return Ok(false);
+        }
+        self.is_following_user(viewer, &user.id).await
+    }
+
+    /// Set a match's visibility. `NotFound` if the match doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_match_visibility(
+        &self,
+        match_id: &str,
+        visibility: Visibility,
+    ) -> DaoResult<()> {
+        self.set_visibility(Pk::Match(match_id.into()), Sk::Meta, visibility)
+            .await
+    }
+
+    /// Set a team's visibility. `NotFound` if the team doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_team_visibility(
+        &self,
+        team_id: &str,
+        visibility: Visibility,
+    ) -> DaoResult<()> {
+        self.set_visibility(Pk::Team(team_id.into()), Sk::Meta, visibility)
+            .await
+    }
+
+    /// Set a profile's visibility. `NotFound` if the user doesn't exist.
+    #[tracing::instrument(skip(self))]
+    pub async fn set_user_visibility(
+        &self,
+        user_id: &str,
+        visibility: Visibility,
+    ) -> DaoResult<()> {
+        self.set_visibility(Pk::User(user_id.into()), Sk::Profile, visibility)
+            .await
+    }
+
+    async fn set_visibility(&self, pk: Pk, sk: Sk, visibility: Visibility) -> DaoResult<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(self.table())
+            .key(ATTR_PK, s(pk.to_string()))
+            .key(ATTR_SK, s(sk.to_string()))
+            .update_expression("SET visibility = :visibility")
+            .condition_expression("attribute_exists(#pk)")
+            .expression_attribute_names("#pk", ATTR_PK)
+            .expression_attribute_values(":visibility", s(visibility.as_str()))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if is_update_conditional_failure(&e) => Err(DaoError::NotFound(pk.to_string())),
+            Err(e) => Err(DaoError::Dynamo(e.to_string())),
+        }
+    }
+
+    /// Whether `viewer` has a `FOLLOWER#` edge under any of `followees`, in
+    /// one batch read.
+    async fn follows_any(&self, viewer: &str, followees: Vec<Pk>) -> DaoResult<bool> {
+        let keys: Vec<_> = followees
+            .into_iter()
+            .map(|pk| {
+                HashMap::from([
+                    (ATTR_PK.to_string(), s(pk.to_string())),
+                    (
+                        ATTR_SK.to_string(),
+                        s(Sk::Follower(viewer.to_string()).to_string()),
+                    ),
+                ])
+            })
+            .collect();
+        if keys.is_empty() {
+            return Ok(false);
+        }
+        let items = self.batch_get_all(keys, Some(ATTR_PK)).await?;
+        Ok(!items.is_empty())
+    }
+}
+
+fn is_update_conditional_failure(err: &SdkError<UpdateItemError>) -> bool {
+    matches!(
+        err,
+        SdkError::ServiceError(se)
+            if matches!(se.err(), UpdateItemError::ConditionalCheckFailedException(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::records::{MatchPlayerRecord, MatchRecord, TeamMemberRecord, TeamRecord};
+
+    fn player(player_id: &str, user_id: Option<&str>) -> MatchPlayerRecord {
+        MatchPlayerRecord {
+            player_id: player_id.into(),
+            user_id: user_id.map(Into::into),
+            display_name: None,
+            side_id: Some("s1".into()),
+            is_member_of_team: None,
+            invitation: None,
+        }
+    }
+
+    fn member(membership_id: &str, user_id: Option<&str>) -> TeamMemberRecord {
+        TeamMemberRecord {
+            team_id: "t".into(),
+            membership_id: membership_id.into(),
+            user_id: user_id.map(Into::into),
+            display_name: None,
+            role: "member".into(),
+            invitation: None,
+            created_at: String::new(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        }
+    }
+
+    fn match_agg(created_by: &str, players: Vec<MatchPlayerRecord>) -> MatchAggregate {
+        MatchAggregate {
+            match_: MatchRecord {
+                id: "m".into(),
+                created_by_user_id: created_by.into(),
+                name: "Fixture".into(),
+                description: String::new(),
+                match_type: "football".into(),
+                status: "scheduled".into(),
+                starts_at: "2026-01-01T00:00:00Z".into(),
+                location: None,
+                venue: None,
+                league: None,
+                sides: HashMap::new(),
+                header_photos: Vec::new(),
+                confirmed_score: None,
+                pending_score: None,
+                like_count: 0,
+                fire_count: 0,
+                clap_count: 0,
+                laugh_count: 0,
+                wow_count: 0,
+                comment_count: 0,
+                live_seq: 0,
+                format: None,
+                cancellation: None,
+                postponements: Vec::new(),
+                visibility: Visibility::Participants,
+                hidden_at: None,
+                created_at: String::new(),
+            },
+            sides: Vec::new(),
+            players,
+        }
+    }
+
+    #[test]
+    fn match_participants_are_creator_and_linked_players() {
+        let agg = match_agg(
+            "organizer",
+            vec![player("p1", Some("u1")), player("p2", None)],
+        );
+        assert_eq!(
+            match_participant_ids(&agg),
+            HashSet::from(["organizer", "u1"])
+        );
+    }
+
+    #[test]
+    fn match_participants_skip_missing_creator() {
+        let agg = match_agg("", vec![player("p1", Some("u1"))]);
+        assert_eq!(match_participant_ids(&agg), HashSet::from(["u1"]));
+    }
+
+    #[test]
+    fn team_members_exclude_externals_and_former_members() {
+        let agg = TeamAggregate {
+            team: TeamRecord {
+                id: "t".into(),
+                name: "Juniors".into(),
+                invite_token: None,
+                follower_count: 0,
+                club_id: None,
+                archived_at: None,
+                visibility: Visibility::Participants,
+                created_at: String::new(),
+            },
+            members: vec![member("m1", Some("u1")), member("m2", None)],
+            former_members: vec![member("m3", Some("u3"))],
+        };
+        assert_eq!(team_member_ids(&agg), HashSet::from(["u1"]));
+    }
+
+    #[test]
+    fn visibility_is_snake_case_and_defaults_to_public() {
+        let v: Visibility = serde_json::from_str("\"participants\"").unwrap();
+        assert_eq!(v, Visibility::Participants);
+        assert_eq!(Visibility::default(), Visibility::Public);
+        assert_eq!(Visibility::Followers.as_str(), "followers");
+    }
+}
@@ -0,0 +1,321 @@
+//! Transactional email over SMTP: one connection per message, with
+//! `STARTTLS` before authenticating when configured.
+//!
+//! Built on lettre's tokio transport (over rustls with the ring provider,
+//! trusting the OS roots, like the FCM client), which owns the protocol and
+//! the MIME rendering; this module only maps our config onto it and its
+//! failures onto [`EmailOutcome`]. Point it at a local sink
+//! (`docker compose up mailpit`, with [`SmtpSecurity::None`]) to see what
+//! would be sent without delivering it.
+//!
+//! Shared by the worker, which emails notifications, token invitations and
+//! digests (see `agon_worker/src/handlers/email.rs` and the digest workflow).
+
+pub mod templates;
+
+use lettre::message::Mailbox;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::extension::ClientId;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::error::EmailError;
+
+pub type EmailResult<T> = Result<T, EmailError>;
+
+/// The name we greet the server with in `EHLO`.
+const HELO_NAME: &str = "agon.local";
+
+/// Permanent reply codes that are about the mailbox rather than the session:
+/// unavailable, not local, name not allowed. lettre doesn't say which command
+/// a refusal answered, so these stand in for "a 5xx to `RCPT TO`"; a refused
+/// login or sender is a 5xx too, but not one of these.
+const MAILBOX_REFUSALS: [u16; 3] = [550, 551, 553];
+
+/// How the connection is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Plaintext throughout — only for a local sink or a relay on a private
+    /// network.
+    None,
+    /// Upgrade with `STARTTLS` before authenticating (submission, port 587).
+    StartTls,
+}
+
+/// Where and as whom to send.
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    /// Username and password, if the server wants them.
+    pub credentials: Option<(String, String)>,
+    /// The `From` address, e.g. `Agon <no-reply@agon.app>`.
+    pub from: String,
+}
+
+/// One message to send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    /// Plain-text body.
+    pub text: String,
+}
+
+/// Outcome of a single send. `Rejected` means the server permanently refused
+/// the recipient (a 5xx to `RCPT TO`) — the address is bad, so retrying won't
+/// help; any other failure is an [`EmailError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailOutcome {
+    Sent,
+    Rejected,
+}
+
+/// An SMTP client. Cheap to clone; each `send` opens its own connection.
+#[derive(Clone)]
+pub struct EmailClient {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailClient {
+    /// Build a client. For [`SmtpSecurity::StartTls`] this loads the OS trust
+    /// roots once, up front; a `from` that doesn't parse fails here too,
+    /// rather than on the first send.
+    pub fn new(config: SmtpConfig) -> EmailResult<Self> {
+        let from = config
+            .from
+            .parse::<Mailbox>()
+            .map_err(|e| EmailError(format!("bad sender {:?}: {e}", config.from)))?;
+        let builder = match config.security {
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                    .map_err(|e| EmailError(format!("TLS config: {e}")))?
+            }
+        };
+        let mut builder = builder
+            .port(config.port)
+            .hello_name(ClientId::Domain(HELO_NAME.into()));
+        if let Some((user, password)) = config.credentials {
+            builder = builder.credentials(Credentials::new(user, password));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+
+    /// Send one message.
+    #[tracing::instrument(skip(self, msg), fields(to = %msg.to))]
+    pub async fn send(&self, msg: &EmailMessage) -> EmailResult<EmailOutcome> {
+        validate_header("recipient", &msg.to)?;
+        validate_header("subject", &msg.subject)?;
+        if !is_plausible_address(&msg.to) {
+            return Ok(EmailOutcome::Rejected);
+        }
+        let Ok(to) = msg.to.parse::<Mailbox>() else {
+            return Ok(EmailOutcome::Rejected);
+        };
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(msg.subject.as_str())
+            .header(ContentType::TEXT_PLAIN)
+            .body(msg.text.clone())
+            .map_err(|e| EmailError(format!("build message: {e}")))?;
+
+        match self.transport.send(message).await {
+            Ok(_) => Ok(EmailOutcome::Sent),
+            Err(e)
+                if e.is_permanent()
+                    && e.status()
+                        .is_some_and(|code| MAILBOX_REFUSALS.contains(&u16::from(code))) =>
+            {
+                tracing::info!(error = %e, "recipient rejected");
+                Ok(EmailOutcome::Rejected)
+            }
+            Err(e) => Err(EmailError(format!("SMTP: {e}"))),
+        }
+    }
+}
+
+/// `Name <addr@host>` → `addr@host`; a bare address is returned as is.
+fn bare_address(value: &str) -> &str {
+    match (value.rfind('<'), value.rfind('>')) {
+        (Some(open), Some(close)) if open < close => &value[open + 1..close],
+        _ => value.trim(),
+    }
+}
+
+/// Just enough to refuse obvious garbage before connecting (and before the
+/// API stores an address): one `@` with something either side, a dotted
+/// domain, and nothing that would break the envelope.
+pub fn is_plausible_address(value: &str) -> bool {
+    let addr = bare_address(value);
+    let Some((local, domain)) = addr.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.contains('@')
+        && !addr
+            .chars()
+            .any(|c| c.is_whitespace() || c == '<' || c == '>')
+}
+
+/// Header values mustn't carry line breaks (header injection).
+fn validate_header(what: &str, value: &str) -> EmailResult<()> {
+    if value.contains(['\r', '\n']) {
+        return Err(EmailError(format!("{what} contains a line break")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::prelude::BASE64_STANDARD;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// A scripted SMTP sink: accepts one connection, answers each command
+    /// with the canned reply for its verb (or the one `overrides` gives it),
+    /// and returns everything the client sent.
+    async fn sink(
+        overrides: &'static [(&'static str, &'static str)],
+    ) -> (u16, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut seen = Vec::new();
+            let mut in_data = false;
+            stream
+                .get_mut()
+                .write_all(b"220 sink ready\r\n")
+                .await
+                .unwrap();
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    return seen;
+                }
+                let line = line.trim_end().to_string();
+                seen.push(line.clone());
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if let Some((_, reply)) =
+                    overrides.iter().find(|(verb, _)| line.starts_with(verb))
+                {
+                    reply.as_bytes()
+                } else {
+                    match line.split(' ').next().unwrap() {
+                        "EHLO" => b"250-sink\r\n250-AUTH PLAIN\r\n250 8BITMIME\r\n",
+                        "AUTH" => b"235 ok\r\n",
+                        "MAIL" => b"250 ok\r\n",
+                        "RCPT" => b"250 ok\r\n",
+                        "DATA" => {
+                            in_data = true;
+                            b"354 go ahead\r\n"
+                        }
+                        "QUIT" => {
+                            stream.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                            return seen;
+                        }
+                        _ => b"502 unknown\r\n",
+                    }
+                };
+                stream.get_mut().write_all(reply).await.unwrap();
+            }
+        });
+        (port, handle)
+    }
+
+    fn client(port: u16) -> EmailClient {
+        EmailClient::new(SmtpConfig {
+            host: "127.0.0.1".into(),
+            port,
+            security: SmtpSecurity::None,
+            credentials: Some(("agon".into(), "secret".into())),
+            from: "Agon <no-reply@agon.app>".into(),
+        })
+        .unwrap()
+    }
+
+    fn message() -> EmailMessage {
+        EmailMessage {
+            to: "sam@example.com".into(),
+            subject: "You're invited — Sunday Derby".into(),
+            text: "Hi Sam,\n.\nSee you there.".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_a_message_to_a_sink() {
+        let (port, sink) = sink(&[]).await;
+        let outcome = client(port).send(&message()).await.unwrap();
+        assert_eq!(outcome, EmailOutcome::Sent);
+
+        let seen = sink.await.unwrap();
+        let plain = BASE64_STANDARD.encode("\0agon\0secret");
+        assert!(seen.contains(&"EHLO agon.local".to_string()));
+        assert!(seen.contains(&format!("AUTH PLAIN {plain}")));
+        assert!(seen.contains(&"MAIL FROM:<no-reply@agon.app>".to_string()));
+        assert!(seen.contains(&"RCPT TO:<sam@example.com>".to_string()));
+        // The non-ASCII subject goes out as an encoded word, not raw UTF-8.
+        let subject = seen.iter().find(|l| l.starts_with("Subject: ")).unwrap();
+        assert!(subject.is_ascii() && subject.contains("=?"));
+
+        // The body follows the headers' blank line, its lone dot stuffed.
+        let data = seen.iter().position(|l| l == "DATA").unwrap();
+        let start = data + seen[data..].iter().position(|l| l.is_empty()).unwrap() + 1;
+        let end = seen.iter().position(|l| l == ".").unwrap();
+        assert_eq!(seen[start..end], ["Hi Sam,", "..", "See you there."]);
+        assert_eq!(seen.last().map(String::as_str), Some("QUIT"));
+    }
+
+    #[tokio::test]
+    async fn permanent_recipient_refusal_is_rejected_not_an_error() {
+        let (port, sink) = sink(&[("RCPT", "550 no such user\r\n")]).await;
+        let outcome = client(port).send(&message()).await.unwrap();
+        assert_eq!(outcome, EmailOutcome::Rejected);
+        assert!(!sink.await.unwrap().contains(&"DATA".to_string()));
+    }
+
+    #[tokio::test]
+    async fn transient_recipient_refusal_is_an_error() {
+        let (port, _sink) = sink(&[("RCPT", "451 try later\r\n")]).await;
+        assert!(client(port).send(&message()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn a_refused_login_is_an_error_not_a_rejection() {
+        let (port, sink) = sink(&[("AUTH", "535 bad credentials\r\n")]).await;
+        assert!(client(port).send(&message()).await.is_err());
+        assert!(!sink.await.unwrap().iter().any(|l| l.starts_with("RCPT")));
+    }
+
+    #[test]
+    fn addresses_and_headers() {
+        assert_eq!(
+            bare_address("Agon <no-reply@agon.app>"),
+            "no-reply@agon.app"
+        );
+        assert_eq!(bare_address(" sam@example.com "), "sam@example.com");
+        assert!(is_plausible_address("sam@example.com"));
+        assert!(!is_plausible_address("sam"));
+        assert!(!is_plausible_address("sam@localhost"));
+        assert!(!is_plausible_address("s m@example.com"));
+        assert!(validate_header("subject", "Hi\r\nBcc: x@example.com").is_err());
+    }
+}
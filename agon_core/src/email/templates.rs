@@ -0,0 +1,441 @@
+//! Plain-text email copy: one message per notification kind, the invitation
+//! sent to an external invitee, and the daily/weekly digest.
+//!
+//! Pure functions of their inputs — the worker looks up names and links and
+//! hands them in. `app_url` is the web app's base URL (no trailing slash),
+//! which every message links back into.
+
+use chrono::{DateTime, Utc};
+
+use super::EmailMessage;
+use crate::dao::records::{InvitationContextRecord, NotificationKindRecord};
+
+/// A rendered subject and body, not yet addressed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailContent {
+    pub subject: String,
+    pub text: String,
+}
+
+impl EmailContent {
+    pub fn to(self, to: impl Into<String>) -> EmailMessage {
+        EmailMessage {
+            to: to.into(),
+            subject: self.subject,
+            text: self.text,
+        }
+    }
+}
+
+/// Signature on every message, with where to change what gets sent.
+fn footer(app_url: &str) -> String {
+    format!("\n\n— Agon\n\nYou can choose which emails you get from {app_url}/notifications.")
+}
+
+/// The email for one in-app notification. `actor_name` is the display name of
+/// whoever triggered it.
+pub fn notification_email(
+    kind: &NotificationKindRecord,
+    actor_name: &str,
+    app_url: &str,
+) -> EmailContent {
+    let match_link = |id: &str| format!("{app_url}/matches/{id}");
+    let team_link = |id: &str| format!("{app_url}/teams/{id}");
+    let (subject, line, link) = match kind {
+        NotificationKindRecord::MatchInvitation {
+            match_id,
+            match_name,
+            ..
+        } => (
+            format!("{actor_name} invited you to {match_name}"),
+            format!("{actor_name} has invited you to play in {match_name}."),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::TeamInvitation {
+            team_id, team_name, ..
+        } => (
+            format!("{actor_name} invited you to join {team_name}"),
+            format!("{actor_name} has invited you to join the team {team_name}."),
+            team_link(team_id),
+        ),
+        NotificationKindRecord::InvitationAccepted { context, .. } => {
+            let (name, link) = match context {
+                InvitationContextRecord::Match {
+                    match_id,
+                    match_name,
+                } => (match_name, match_link(match_id)),
+                InvitationContextRecord::Team { team_id, team_name } => {
+                    (team_name, team_link(team_id))
+                }
+            };
+            (
+                format!("{actor_name} accepted your invitation"),
+                format!("{actor_name} accepted your invitation to {name}."),
+                link,
+            )
+        }
+        NotificationKindRecord::Follow { actor_user_id } => (
+            format!("{actor_name} started following you"),
+            format!("{actor_name} is now following you on Agon."),
+            format!("{app_url}/users/{actor_user_id}"),
+        ),
+        NotificationKindRecord::FollowRequest { actor_user_id } => (
+            format!("{actor_name} asked to follow you"),
+            format!("{actor_name} would like to follow your private profile."),
+            format!("{app_url}/users/{actor_user_id}"),
+        ),
+        NotificationKindRecord::FollowRequestApproved { actor_user_id } => (
+            format!("{actor_name} approved your follow request"),
+            format!("You're now following {actor_name}."),
+            format!("{app_url}/users/{actor_user_id}"),
+        ),
+        NotificationKindRecord::Like {
+            match_id,
+            match_name,
+            ..
+        } => (
+            format!("{actor_name} liked {match_name}"),
+            format!("{actor_name} liked {match_name}."),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::Comment {
+            match_id, preview, ..
+        } => (
+            format!("{actor_name} commented on your match"),
+            format!("{actor_name} wrote:\n\n  {preview}"),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::Reply {
+            match_id, preview, ..
+        } => (
+            format!("{actor_name} replied to a comment"),
+            format!("{actor_name} replied:\n\n  {preview}"),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::ScoreSubmitted {
+            match_id,
+            match_name,
+            needs_confirmation,
+            ..
+        } => {
+            let line = if *needs_confirmation {
+                format!("{actor_name} submitted a score for {match_name}. Please confirm it.")
+            } else {
+                format!("{actor_name} submitted a score for {match_name}.")
+            };
+            (
+                format!("Score submitted for {match_name}"),
+                line,
+                match_link(match_id),
+            )
+        }
+        NotificationKindRecord::ScoreConfirmed {
+            match_id,
+            match_name,
+            ..
+        } => (
+            format!("Your score for {match_name} was confirmed"),
+            format!("{actor_name} confirmed the score you submitted for {match_name}."),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::TeamJoinRequest {
+            team_id, team_name, ..
+        } => (
+            format!("{actor_name} asked to join {team_name}"),
+            format!("{actor_name} would like to join {team_name}."),
+            team_link(team_id),
+        ),
+        NotificationKindRecord::TeamJoinApproved {
+            team_id, team_name, ..
+        } => (
+            format!("You're now a member of {team_name}"),
+            format!("{actor_name} approved your request to join {team_name}."),
+            team_link(team_id),
+        ),
+        NotificationKindRecord::MatchCancelled {
+            match_id,
+            match_name,
+            reason,
+            ..
+        } => {
+            let mut line = format!("{actor_name} cancelled {match_name}.");
+            if !reason.is_empty() {
+                line.push_str(&format!("\n\nReason: {reason}"));
+            }
+            (
+                format!("{match_name} has been cancelled"),
+                line,
+                match_link(match_id),
+            )
+        }
+        NotificationKindRecord::MatchPostponed {
+            match_id,
+            match_name,
+            reason,
+            starts_at,
+            ..
+        } => {
+            let mut line = format!(
+                "{actor_name} moved {match_name} to {}.",
+                format_time(starts_at)
+            );
+            if !reason.is_empty() {
+                line.push_str(&format!("\n\nReason: {reason}"));
+            }
+            (
+                format!("{match_name} has been moved"),
+                line,
+                match_link(match_id),
+            )
+        }
+        NotificationKindRecord::Mention {
+            match_id, preview, ..
+        } => (
+            format!("{actor_name} mentioned you"),
+            format!("{actor_name} mentioned you in a comment:\n\n  {preview}"),
+            match_link(match_id),
+        ),
+        NotificationKindRecord::Reaction {
+            match_id,
+            match_name,
+            reaction,
+            comment_id,
+            ..
+        } => {
+            let target = match comment_id {
+                Some(_) => format!("your comment on {match_name}"),
+                None => match_name.clone(),
+            };
+            (
+                format!("{actor_name} reacted to {target}"),
+                format!("{actor_name} reacted {} to {target}.", reaction.emoji()),
+                match_link(match_id),
+            )
+        }
+    };
+    EmailContent {
+        subject,
+        text: format!("{line}\n\n{link}{}", footer(app_url)),
+    }
+}
+
+/// The email inviting someone without an account. `accept_url` carries the
+/// invitation's token; whoever follows it can accept.
+pub fn token_invitation_email(
+    inviter_name: &str,
+    context: &InvitationContextRecord,
+    accept_url: &str,
+) -> EmailContent {
+    let (subject, line) = match context {
+        InvitationContextRecord::Match { match_name, .. } => (
+            format!("{inviter_name} invited you to {match_name} on Agon"),
+            format!("{inviter_name} has invited you to play in {match_name}."),
+        ),
+        InvitationContextRecord::Team { team_name, .. } => (
+            format!("{inviter_name} invited you to join {team_name} on Agon"),
+            format!("{inviter_name} has invited you to join the team {team_name}."),
+        ),
+    };
+    EmailContent {
+        subject,
+        text: format!(
+            "{line}\n\nAccept or decline here:\n\n{accept_url}\n\n\
+             If you weren't expecting this, you can ignore it.\n\n— Agon"
+        ),
+    }
+}
+
+/// Which digest a message is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+/// One match line in a digest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestMatch {
+    pub match_id: String,
+    pub name: String,
+    /// RFC3339.
+    pub starts_at: String,
+}
+
+/// What a digest summarises, already fetched.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DigestContent {
+    /// Matches starting in the coming period, soonest first.
+    pub upcoming: Vec<DigestMatch>,
+    /// Matches completed in the past period, latest first.
+    pub results: Vec<DigestMatch>,
+    /// Notifications received in the past period (follows, comments,
+    /// reactions, …).
+    pub activity_count: usize,
+}
+
+impl DigestContent {
+    pub fn is_empty(&self) -> bool {
+        self.upcoming.is_empty() && self.results.is_empty() && self.activity_count == 0
+    }
+}
+
+/// The digest email, or None when there's nothing to say (we don't send an
+/// empty digest).
+pub fn digest_email(
+    period: DigestPeriod,
+    recipient_name: &str,
+    content: &DigestContent,
+    app_url: &str,
+) -> Option<EmailContent> {
+    if content.is_empty() {
+        return None;
+    }
+    let (subject, upcoming_heading, results_heading, since) = match period {
+        DigestPeriod::Daily => (
+            "Your day on Agon",
+            "Coming up in the next day",
+            "Results from the last day",
+            "since yesterday",
+        ),
+        DigestPeriod::Weekly => (
+            "Your week on Agon",
+            "Coming up this week",
+            "Results from the last week",
+            "in the last week",
+        ),
+    };
+    let section = |heading: &str, matches: &[DigestMatch]| {
+        let mut out = format!("{heading}\n");
+        for m in matches {
+            out.push_str(&format!(
+                "  • {} — {}\n    {app_url}/matches/{}\n",
+                m.name,
+                format_time(&m.starts_at),
+                m.match_id
+            ));
+        }
+        out
+    };
+
+    let mut text = format!("Hi {recipient_name},\n\n");
+    if !content.upcoming.is_empty() {
+        text.push_str(&section(upcoming_heading, &content.upcoming));
+        text.push('\n');
+    }
+    if !content.results.is_empty() {
+        text.push_str(&section(results_heading, &content.results));
+        text.push('\n');
+    }
+    match content.activity_count {
+        0 => {}
+        1 => text.push_str(&format!(
+            "You have 1 new notification {since}: {app_url}/notifications\n"
+        )),
+        n => text.push_str(&format!(
+            "You have {n} new notifications {since}: {app_url}/notifications\n"
+        )),
+    }
+    text.push_str(footer(app_url).trim_end_matches('\n'));
+    Some(EmailContent {
+        subject: subject.to_string(),
+        text,
+    })
+}
+
+/// `2026-10-18T14:00:00Z` → `Sun 18 Oct, 14:00 UTC`. Left as is if it doesn't
+/// parse.
+fn format_time(rfc3339: &str) -> String {
+    DateTime::parse_from_rfc3339(rfc3339)
+        .map(|t| {
+            t.with_timezone(&Utc)
+                .format("%a %-d %b, %H:%M UTC")
+                .to_string()
+        })
+        .unwrap_or_else(|_| rfc3339.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::records::ReactionRecord;
+
+    const APP: &str = "https://agon.test";
+
+    #[test]
+    fn notification_email_names_the_actor_and_links_the_match() {
+        let email = notification_email(
+            &NotificationKindRecord::MatchPostponed {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                match_name: "Sunday Derby".into(),
+                reason: "rain".into(),
+                starts_at: "2026-10-25T14:00:00Z".into(),
+            },
+            "Alex",
+            APP,
+        );
+        assert_eq!(email.subject, "Sunday Derby has been moved");
+        assert!(email.text.starts_with(
+            "Alex moved Sunday Derby to Sun 25 Oct, 14:00 UTC.\n\nReason: rain\n\nhttps://agon.test/matches/m1"
+        ));
+
+        let email = notification_email(
+            &NotificationKindRecord::Reaction {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                match_name: "Sunday Derby".into(),
+                reaction: ReactionRecord::Fire,
+                comment_id: Some("c1".into()),
+            },
+            "Alex",
+            APP,
+        );
+        assert_eq!(
+            email.subject,
+            "Alex reacted to your comment on Sunday Derby"
+        );
+    }
+
+    #[test]
+    fn token_invitation_links_the_accept_url() {
+        let email = token_invitation_email(
+            "Alex",
+            &InvitationContextRecord::Team {
+                team_id: "t1".into(),
+                team_name: "The Aces".into(),
+            },
+            "https://agon.test/invite/tok",
+        );
+        assert_eq!(email.subject, "Alex invited you to join The Aces on Agon");
+        assert!(email.text.contains("\n\nhttps://agon.test/invite/tok\n\n"));
+    }
+
+    #[test]
+    fn digest_skips_empty_sections_and_empty_digests() {
+        assert_eq!(
+            digest_email(DigestPeriod::Daily, "Sam", &DigestContent::default(), APP),
+            None
+        );
+
+        let content = DigestContent {
+            upcoming: vec![DigestMatch {
+                match_id: "m1".into(),
+                name: "Sunday Derby".into(),
+                starts_at: "2026-10-25T14:00:00Z".into(),
+            }],
+            results: vec![],
+            activity_count: 3,
+        };
+        let email = digest_email(DigestPeriod::Weekly, "Sam", &content, APP).unwrap();
+        assert_eq!(email.subject, "Your week on Agon");
+        assert!(email.text.contains(
+            "Coming up this week\n  • Sunday Derby — Sun 25 Oct, 14:00 UTC\n    https://agon.test/matches/m1\n"
+        ));
+        assert!(!email.text.contains("Results"));
+        assert!(
+            email
+                .text
+                .contains("You have 3 new notifications in the last week")
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Crate-level error types shared across modules.
 //!
 //! The `dao` module has its own `DaoError`; this holds errors for the other
-//! shared clients (currently search and push).
+//! shared clients (currently search, push and email).
 
 use thiserror::Error;
 
@@ -18,3 +18,10 @@ pub struct SearchError(pub String);
 #[derive(Debug, Error)]
 #[error("push error: {0}")]
 pub struct PushError(pub String);
+
+/// An SMTP send failed — connection, TLS, or an unexpected reply. A permanent
+/// refusal of the recipient isn't an error (see `email::EmailOutcome`);
+/// anything here is treated as transient and retried.
+#[derive(Debug, Error)]
+#[error("email error: {0}")]
+pub struct EmailError(pub String);
@@ -0,0 +1,145 @@
+//! Scoring for the ranked ("For you") feed. The API scores a viewer's newest
+//! feed entries here and stores the resulting order (see
+//! `Dao::put_ranked_feed`), paging through that snapshot so scores moving
+//! between requests never reshuffle pages already served.
+//!
+//! One additive score per entry, from:
+//! - recency: how near the match's start is to now, past or future, halving
+//!   every [`RECENCY_HALF_LIFE_HOURS`];
+//! - whether the viewer plays in it;
+//! - how many of its players the viewer follows (capped, so one big squad of
+//!   friends doesn't bury everything else);
+//! - reactions and comments, on a log scale;
+//! - whether it's in progress right now, which outweighs everything else.
+//!
+//! Entries that aren't matches — results, milestones, announcements, joins —
+//! have only recency, from their own time (see [`FeedSignals::at`]).
+
+use chrono::{DateTime, Utc};
+
+/// Score of a match starting right now, before the other signals.
+const RECENCY_WEIGHT: f64 = 4.0;
+
+/// Hours after (or before) which a match's recency score has halved.
+pub const RECENCY_HALF_LIFE_HOURS: f64 = 48.0;
+
+/// Bonus for a match the viewer plays in.
+const OWN_MATCH_WEIGHT: f64 = 1.5;
+
+/// Bonus per followed player, up to `KNOWN_PLAYER_CAP` of them.
+const KNOWN_PLAYER_WEIGHT: f64 = 0.3;
+const KNOWN_PLAYER_CAP: u32 = 5;
+
+/// Bonus per unit of `ln(1 + engagement)`, where a comment counts as
+/// `COMMENT_ENGAGEMENT` reactions.
+const ENGAGEMENT_WEIGHT: f64 = 0.5;
+const COMMENT_ENGAGEMENT: u64 = 2;
+
+/// Bonus for a match in progress.
+const LIVE_WEIGHT: f64 = 6.0;
+
+/// What a feed entry is ranked on.
+#[derive(Debug, Clone, Copy)]
+pub struct FeedSignals {
+    pub starts_at: DateTime<Utc>,
+    /// The viewer plays in the match.
+    pub is_own: bool,
+    /// How many of its players the viewer follows.
+    pub known_player_count: u32,
+    /// Reactions of every kind.
+    pub reactions: u64,
+    pub comments: u64,
+    /// The match is in progress.
+    pub live: bool,
+}
+
+impl FeedSignals {
+    /// Recency alone: an entry at `at` with nothing else going for it.
+    pub fn at(at: DateTime<Utc>) -> Self {
+        Self {
+            starts_at: at,
+            is_own: false,
+            known_player_count: 0,
+            reactions: 0,
+            comments: 0,
+            live: false,
+        }
+    }
+
+    pub fn score(&self, now: DateTime<Utc>) -> f64 {
+        let hours = (now - self.starts_at).num_minutes().abs() as f64 / 60.0;
+        let recency = RECENCY_WEIGHT * 0.5f64.powf(hours / RECENCY_HALF_LIFE_HOURS);
+        let own = if self.is_own { OWN_MATCH_WEIGHT } else { 0.0 };
+        let known = KNOWN_PLAYER_WEIGHT * self.known_player_count.min(KNOWN_PLAYER_CAP) as f64;
+        let engagement = self.reactions + self.comments * COMMENT_ENGAGEMENT;
+        let engagement = ENGAGEMENT_WEIGHT * (engagement as f64).ln_1p();
+        let live = if self.live { LIVE_WEIGHT } else { 0.0 };
+        recency + own + known + engagement + live
+    }
+}
+
+/// Order `entries` best first by their signals' score at `now`. Equal
+/// scores keep their input order.
+pub fn rank<T>(entries: Vec<(T, FeedSignals)>, now: DateTime<Utc>) -> Vec<T> {
+    let mut scored: Vec<(f64, T)> = entries
+        .into_iter()
+        .map(|(entry, signals)| (signals.score(now), entry))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn signals(now: DateTime<Utc>, hours_ago: i64) -> FeedSignals {
+        FeedSignals::at(now - Duration::hours(hours_ago))
+    }
+
+    #[test]
+    fn newer_first_all_else_equal_and_ties_keep_order() {
+        let now = Utc::now();
+        let ranked = rank(
+            vec![
+                ("old", signals(now, 100)),
+                ("a", signals(now, 1)),
+                ("b", signals(now, 1)),
+                ("upcoming", signals(now, -30)),
+            ],
+            now,
+        );
+        assert_eq!(ranked, ["a", "b", "upcoming", "old"]);
+    }
+
+    #[test]
+    fn live_matches_lead_and_social_signals_beat_a_little_recency() {
+        let now = Utc::now();
+        let live = FeedSignals {
+            live: true,
+            ..signals(now, 3)
+        };
+        let own = FeedSignals {
+            is_own: true,
+            ..signals(now, 24)
+        };
+        let popular = FeedSignals {
+            known_player_count: 20,
+            reactions: 30,
+            comments: 10,
+            ..signals(now, 24)
+        };
+        let ranked = rank(
+            vec![
+                ("fresh", signals(now, 12)),
+                ("own", own),
+                ("popular", popular),
+                ("live", live),
+            ],
+            now,
+        );
+        assert_eq!(ranked, ["live", "popular", "own", "fresh"]);
+    }
+}
@@ -1,11 +1,18 @@
 //! Agon shared domain crate.
 //!
 //! Holds the DynamoDB single-table data access layer (`dao`), the Meilisearch
-//! client (`search`), and the FCM push client (`push`), all used by the API
-//! service and the async worker. No web-framework dependencies.
+//! client (`search`), the FCM push client (`push`), the SMTP email client
+//! (`email`), the ranking behind follow suggestions (`suggestions`) and the
+//! ranked feed (`feed_rank`), and stats milestone detection (`milestones`),
+//! all used by the API service and the async worker. No web-framework
+//! dependencies.
 
 pub mod dao;
+pub mod email;
 pub mod error;
+pub mod feed_rank;
+pub mod milestones;
 pub mod push;
 pub mod search;
+pub mod suggestions;
 pub mod telemetry;
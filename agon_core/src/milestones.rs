@@ -0,0 +1,134 @@
+//! Stats milestones: the round numbers of matches played and won, per sport,
+//! that earn a feed card ("Sam played their 100th tennis match"). Detected by
+//! the worker from the stats reconciler's writes — it diffs the old and new
+//! `stats` map on a user's profile item — and recorded once each (see
+//! `MilestoneRecord`).
+//!
+//! Only thresholds crossed *upwards* count: a re-score that takes a win back
+//! doesn't retract the milestone, and the record's first write wins, so
+//! winning it again doesn't repeat it.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dao::records::{MilestoneRecord, MilestoneStat, UserSportStatsRecord};
+
+/// The counts that make a milestone, for either stat.
+pub const THRESHOLDS: [u64; 9] = [1, 10, 25, 50, 100, 250, 500, 1000, 2500];
+
+/// One milestone a user has crossed: `count` of `stat` in `sport`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Milestone {
+    pub sport: String,
+    pub stat: MilestoneStat,
+    pub count: u64,
+}
+
+impl Milestone {
+    /// The record of `user_id` reaching this milestone at `reached_at`.
+    pub fn record(&self, user_id: &str, reached_at: &str) -> MilestoneRecord {
+        MilestoneRecord {
+            id: MilestoneRecord::id_for(user_id, &self.sport, self.stat, self.count),
+            user_id: user_id.into(),
+            sport: self.sport.clone(),
+            stat: self.stat,
+            count: self.count,
+            reached_at: reached_at.into(),
+        }
+    }
+}
+
+/// Every milestone crossed going from `old` to `new` stats, ordered by sport
+/// then stat then count. Only the first match doesn't count as a milestone
+/// (everyone plays one); the first win does.
+pub fn crossed(
+    old: &HashMap<String, UserSportStatsRecord>,
+    new: &HashMap<String, UserSportStatsRecord>,
+) -> Vec<Milestone> {
+    let mut sports: Vec<&String> = new.keys().collect();
+    sports.sort();
+    let mut out = Vec::new();
+    for sport in sports {
+        let after = &new[sport];
+        let before = old.get(sport);
+        let stats = [
+            (
+                MilestoneStat::MatchesPlayed,
+                before.map_or(0, |s| s.matches_played),
+                after.matches_played,
+            ),
+            (
+                MilestoneStat::Wins,
+                before.map_or(0, |s| s.wins),
+                after.wins,
+            ),
+        ];
+        for (stat, before, after) in stats {
+            for count in THRESHOLDS {
+                if stat == MilestoneStat::MatchesPlayed && count == 1 {
+                    continue;
+                }
+                if before < count && count <= after {
+                    out.push(Milestone {
+                        sport: sport.clone(),
+                        stat,
+                        count,
+                    });
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(entries: &[(&str, u64, u64)]) -> HashMap<String, UserSportStatsRecord> {
+        entries
+            .iter()
+            .map(|(sport, matches_played, wins)| {
+                (
+                    sport.to_string(),
+                    UserSportStatsRecord {
+                        matches_played: *matches_played,
+                        wins: *wins,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn crossing_thresholds_upwards_is_a_milestone() {
+        let old = stats(&[("tennis", 9, 0), ("football", 99, 24)]);
+        let new = stats(&[("tennis", 10, 1), ("football", 100, 25), ("cricket", 1, 0)]);
+        let crossed = crossed(&old, &new);
+        let got: Vec<(&str, MilestoneStat, u64)> = crossed
+            .iter()
+            .map(|m| (m.sport.as_str(), m.stat, m.count))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                ("football", MilestoneStat::MatchesPlayed, 100),
+                ("football", MilestoneStat::Wins, 25),
+                ("tennis", MilestoneStat::MatchesPlayed, 10),
+                ("tennis", MilestoneStat::Wins, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn unchanged_or_falling_stats_cross_nothing() {
+        let old = stats(&[("tennis", 10, 10)]);
+        assert!(crossed(&old, &old).is_empty());
+        let lower = stats(&[("tennis", 9, 9)]);
+        assert!(crossed(&old, &lower).is_empty());
+        // Climbing back over a threshold crosses it again; recording it is
+        // first-write-wins, so it isn't fanned out twice.
+        assert_eq!(crossed(&lower, &old).len(), 2);
+    }
+}
@@ -4,25 +4,41 @@
 //! upsert/delete off the DynamoDB stream) and the API (which queries the indexes
 //! to serve discovery endpoints). This module exposes only the slice of the SDK
 //! we actually use — upsert a document, delete one by id, run a filtered
-//! search, and declare index settings — so callers don't reach into the SDK
-//! directly.
+//! search (or several at once), declare index settings, and rebuild an index
+//! behind a shadow copy —
+//! so callers don't reach into the SDK directly.
+//!
+//! Filters are built as a typed [`Filter`] tree, never as strings: values are
+//! quoted and escaped when rendered, and every attribute is checked against
+//! the index's filterable attributes, so a query parameter can only ever be a
+//! value, not part of the expression.
 //!
 //! Write operations are idempotent (a replayed upsert/delete has no visible
 //! effect), which is exactly what the worker's at-least-once delivery needs.
 //! Search returns only document ids: the API hydrates full entities from
 //! DynamoDB, since the indexes store only what's needed to match and rank.
 
-use meilisearch_sdk::client::Client;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use meilisearch_sdk::client::{Client, SwapIndexes};
+use meilisearch_sdk::errors::ErrorCode;
 use meilisearch_sdk::search::Selectors;
-use meilisearch_sdk::settings::Settings;
+use meilisearch_sdk::settings::{MinWordSizeForTypos, Settings, TypoToleranceSettings};
+use meilisearch_sdk::tasks::Task;
 use serde::{Deserialize, Serialize};
 
 use crate::error::SearchError;
 
 pub type SearchResult<T> = Result<T, SearchError>;
 
+/// How long to wait on a Meilisearch task the caller needs finished (a
+/// shadow index's setup, a rebuild batch, the swap) before giving up.
+const TASK_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// The Meilisearch indexes maintained by the worker (see docs/async-design.md §7).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Index {
     Users,
     Teams,
@@ -30,7 +46,7 @@ pub enum Index {
 }
 
 impl Index {
-    fn name(self) -> &'static str {
+    pub fn name(self) -> &'static str {
         match self {
             Index::Users => "users",
             Index::Teams => "teams",
@@ -38,18 +54,40 @@ impl Index {
         }
     }
 
+    /// The index [`name`](Self::name) names, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Index::ALL.into_iter().find(|i| i.name() == name)
+    }
+
+    /// Where a rebuild of this index is built before being swapped in.
+    fn shadow_name(self) -> String {
+        format!("{}__reindex", self.name())
+    }
+
     /// Every index the worker maintains, for bootstrap iteration.
     pub const ALL: [Index; 3] = [Index::Users, Index::Teams, Index::Matches];
 
     /// Attributes that must be declared *filterable* before they can be used in
-    /// a search `filter` expression. Only the matches index is filtered (by
-    /// sport / participant / date range in `GET /matches`).
+    /// a search `filter` expression or asked for as a facet. The matches index
+    /// is filtered by sport / status / team / league / participant / date
+    /// range / distance in `GET /matches`; every index is filtered by viewer
+    /// (see [`Filter::visible_to`]).
     fn filterable_attributes(self) -> &'static [&'static str] {
         match self {
             // `starts_at_ts` (numeric epoch) — not `starts_at` (ISO string) —
             // because Meilisearch range filters are numeric-only.
-            Index::Matches => &["sport", "participant_ids", "starts_at_ts", "status"],
-            Index::Users | Index::Teams => &[],
+            Index::Matches => &[
+                "sport",
+                "participant_ids",
+                "team_ids",
+                "league",
+                "starts_at_ts",
+                "status",
+                "visibility",
+                "visible_to",
+                "_geo",
+            ],
+            Index::Users | Index::Teams => &["visibility", "visible_to"],
         }
     }
 
@@ -63,6 +101,291 @@ impl Index {
             Index::Users | Index::Teams => &[],
         }
     }
+
+    /// The attributes free text is matched against, most important first:
+    /// Meilisearch's `attribute` ranking rule ranks a hit in an earlier
+    /// attribute above one in a later attribute, so for matches a hit in the
+    /// name beats one in a player's name, which beats one in the venue or
+    /// league, which beats one in the description.
+    fn searchable_attributes(self) -> &'static [&'static str] {
+        match self {
+            Index::Matches => &[
+                "name",
+                "participant_names",
+                "side_names",
+                "venue",
+                "league",
+                "description",
+            ],
+            Index::Users | Index::Teams => &["name"],
+        }
+    }
+
+    /// Everything [`SearchClient::configure_index`] declares for this index.
+    ///
+    /// Typo tolerance is loosened a little from Meilisearch's defaults (one
+    /// typo from 5 letters, two from 9): people and team names are short, and
+    /// "Jonh" should still find "John". Numbers get no typos, so "2024" never
+    /// matches "2025".
+    fn settings(self) -> Settings {
+        Settings::new()
+            .with_filterable_attributes(self.filterable_attributes().iter().copied())
+            .with_sortable_attributes(self.sortable_attributes().iter().copied())
+            .with_searchable_attributes(self.searchable_attributes().iter().copied())
+            .with_typo_tolerance(TypoToleranceSettings {
+                enabled: Some(true),
+                disable_on_attributes: None,
+                disable_on_words: None,
+                min_word_size_for_typos: Some(MinWordSizeForTypos {
+                    one_typo: Some(4),
+                    two_typos: Some(8),
+                }),
+                disable_on_numbers: Some(true),
+            })
+    }
+}
+
+/// A value compared against an attribute in a [`Filter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Str(String),
+    Int(i64),
+}
+
+impl From<&str> for FilterValue {
+    fn from(v: &str) -> Self {
+        FilterValue::Str(v.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(v: String) -> Self {
+        FilterValue::Str(v)
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(v: i64) -> Self {
+        FilterValue::Int(v)
+    }
+}
+
+/// A Meilisearch filter expression, rendered (and checked against the index
+/// it's used on) by [`Filter::render`]. Attribute names are `'static` — they
+/// come from code, never from a request — and values are always rendered as
+/// quoted, escaped literals.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// `attr = value`
+    Eq(&'static str, FilterValue),
+    /// `attr IN [values...]`
+    In(&'static str, Vec<FilterValue>),
+    /// An inclusive numeric range; at least one bound must be set.
+    Range {
+        attr: &'static str,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    /// Documents within `meters` of a point. Needs `_geo` filterable.
+    GeoRadius {
+        lat: f64,
+        lng: f64,
+        meters: u32,
+    },
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn eq(attr: &'static str, value: impl Into<FilterValue>) -> Self {
+        Filter::Eq(attr, value.into())
+    }
+
+    pub fn any_of<V: Into<FilterValue>>(
+        attr: &'static str,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        Filter::In(attr, values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn range(attr: &'static str, min: Option<i64>, max: Option<i64>) -> Self {
+        Filter::Range { attr, min, max }
+    }
+
+    /// Limit a search to documents `viewer_id` may see: public ones, plus
+    /// any listing the viewer in `visible_to` (a match's creator and linked
+    /// players, a team's members, a profile's owner).
+    ///
+    /// Every indexed document carries `visibility` and `visible_to`. A
+    /// followers-only document is deliberately only findable by its
+    /// participants — whether the viewer follows someone isn't in the index —
+    /// and reachable by everyone else through the feed or a direct link.
+    pub fn visible_to(viewer_id: &str) -> Self {
+        Filter::Or(vec![
+            Filter::eq("visibility", "public"),
+            Filter::eq("visible_to", viewer_id),
+        ])
+    }
+
+    /// AND these clauses together; `None` when there are none (no filter).
+    pub fn all(clauses: impl IntoIterator<Item = Filter>) -> Option<Self> {
+        let mut clauses: Vec<Filter> = clauses.into_iter().collect();
+        match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => Some(Filter::And(clauses)),
+        }
+    }
+
+    /// `self AND other`, flattening into an existing AND.
+    pub fn and(self, other: Filter) -> Self {
+        match self {
+            Filter::And(mut clauses) => {
+                clauses.push(other);
+                Filter::And(clauses)
+            }
+            this => Filter::And(vec![this, other]),
+        }
+    }
+
+    /// Render the expression for `index`, failing on an attribute `index`
+    /// doesn't declare filterable (Meilisearch would reject the search
+    /// anyway, less legibly) or a malformed clause.
+    pub fn render(&self, index: Index) -> SearchResult<String> {
+        let mut out = String::new();
+        self.render_into(index, &mut out)?;
+        Ok(out)
+    }
+
+    fn render_into(&self, index: Index, out: &mut String) -> SearchResult<()> {
+        use std::fmt::Write;
+
+        match self {
+            Filter::Eq(attr, value) => {
+                check_attr(index, attr)?;
+                let _ = write!(out, "{attr} = ");
+                push_value(out, value);
+            }
+            Filter::In(attr, values) => {
+                check_attr(index, attr)?;
+                if values.is_empty() {
+                    return Err(SearchError(format!("empty IN filter on `{attr}`")));
+                }
+                let _ = write!(out, "{attr} IN [");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    push_value(out, value);
+                }
+                out.push(']');
+            }
+            Filter::Range { attr, min, max } => {
+                check_attr(index, attr)?;
+                match (min, max) {
+                    (Some(min), Some(max)) => {
+                        let _ = write!(out, "{attr} {min} TO {max}");
+                    }
+                    (Some(min), None) => {
+                        let _ = write!(out, "{attr} >= {min}");
+                    }
+                    (None, Some(max)) => {
+                        let _ = write!(out, "{attr} <= {max}");
+                    }
+                    (None, None) => {
+                        return Err(SearchError(format!("unbounded range filter on `{attr}`")));
+                    }
+                }
+            }
+            Filter::GeoRadius { lat, lng, meters } => {
+                check_attr(index, "_geo")?;
+                if !(-90.0..=90.0).contains(lat) || !(-180.0..=180.0).contains(lng) {
+                    return Err(SearchError(format!(
+                        "geo filter point out of range: {lat}, {lng}"
+                    )));
+                }
+                let _ = write!(out, "_geoRadius({lat}, {lng}, {meters})");
+            }
+            Filter::And(clauses) | Filter::Or(clauses) => {
+                let (op, wrap) = match self {
+                    Filter::And(_) => (" AND ", false),
+                    _ => (" OR ", true),
+                };
+                if clauses.is_empty() {
+                    return Err(SearchError("empty filter group".into()));
+                }
+                if wrap {
+                    out.push('(');
+                }
+                for (i, clause) in clauses.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(op);
+                    }
+                    // An OR already brackets itself; only a nested AND needs
+                    // it, and only under an OR.
+                    let bracket = wrap && matches!(clause, Filter::And(_));
+                    if bracket {
+                        out.push('(');
+                    }
+                    clause.render_into(index, out)?;
+                    if bracket {
+                        out.push(')');
+                    }
+                }
+                if wrap {
+                    out.push(')');
+                }
+            }
+            Filter::Not(inner) => {
+                out.push_str("NOT (");
+                inner.render_into(index, out)?;
+                out.push(')');
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_attr(index: Index, attr: &str) -> SearchResult<()> {
+    if index.filterable_attributes().contains(&attr) {
+        Ok(())
+    } else {
+        Err(SearchError(format!(
+            "`{attr}` is not filterable on the {} index",
+            index.name()
+        )))
+    }
+}
+
+/// Facets are counted over filterable attributes only; Meilisearch would
+/// reject the search otherwise. `_geo` has no values to count.
+fn check_facets(index: Index, facets: &[&str]) -> SearchResult<()> {
+    for &attr in facets {
+        if attr == "_geo" {
+            return Err(SearchError(format!("`{attr}` cannot be a facet")));
+        }
+        check_attr(index, attr)?;
+    }
+    Ok(())
+}
+
+/// A value as a filter literal: a string double-quoted, with backslashes and
+/// quotes escaped so nothing in it can end the literal; a number bare.
+fn push_value(out: &mut String, value: &FilterValue) {
+    match value {
+        FilterValue::Str(s) => {
+            out.push('"');
+            for c in s.chars() {
+                if matches!(c, '"' | '\\') {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            out.push('"');
+        }
+        FilterValue::Int(n) => out.push_str(&n.to_string()),
+    }
 }
 
 /// A page of matching document ids, with an offset to fetch the next page.
@@ -72,21 +395,30 @@ pub struct SearchHits {
     pub ids: Vec<String>,
     /// Offset for the next page, or `None` if this was the last page.
     pub next_offset: Option<u32>,
+    /// Per requested facet, how many hits carry each value.
+    pub facets: FacetCounts,
 }
 
+/// Facet attribute → value → how many hits across the whole result set (not
+/// just the returned page) carry that value. Values with no hits are absent.
+pub type FacetCounts = HashMap<String, HashMap<String, usize>>;
+
 /// Parameters for a search query.
 #[derive(Debug, Default, Clone)]
 pub struct SearchQuery {
     /// Free-text query. Empty matches everything (filtered browse).
     pub q: String,
-    /// Meilisearch filter expression (e.g. `sport = tennis AND starts_at >= ...`).
-    pub filter: Option<String>,
+    /// Which documents to consider, checked against the searched index.
+    pub filter: Option<Filter>,
     /// Sort expressions (e.g. `["starts_at:desc"]`).
     pub sort: Vec<String>,
     /// Zero-based offset into the result set.
     pub offset: u32,
     /// Max hits to return.
     pub limit: u32,
+    /// Attributes to count hits per value of (see [`FacetCounts`]). Each
+    /// must be filterable on the searched index.
+    pub facets: Vec<&'static str>,
 }
 
 /// A queried participant's result in a match, resolved from the match search
@@ -113,6 +445,26 @@ pub struct MatchSearchHit {
 pub struct MatchSearchHits {
     pub items: Vec<MatchSearchHit>,
     pub next_offset: Option<u32>,
+    pub facets: FacetCounts,
+}
+
+/// One hit from [`SearchClient::multi_search`].
+#[derive(Debug, Clone)]
+pub struct RankedHit {
+    pub id: String,
+    /// Meilisearch's relevancy score, from 0.0 to 1.0. Comparable across
+    /// indexes, so it can order one index's hits against another's.
+    pub score: f64,
+    /// Whether the viewer is in the document's `visible_to`: a team they're
+    /// on, a match they made or played in — or, for a user, themselves.
+    pub involves_viewer: bool,
+}
+
+/// The hits of one query in a [`SearchClient::multi_search`], best first.
+#[derive(Debug, Clone)]
+pub struct RankedHits {
+    pub index: Index,
+    pub hits: Vec<RankedHit>,
 }
 
 /// A Meilisearch client wrapping the official SDK. Cheap to clone (the SDK's
@@ -122,6 +474,15 @@ pub struct SearchClient {
     client: Client,
 }
 
+/// A bare task uid, in the form the SDK's task lookups take.
+struct TaskUid(u32);
+
+impl AsRef<u32> for TaskUid {
+    fn as_ref(&self) -> &u32 {
+        &self.0
+    }
+}
+
 /// Only the `id` field is read back from search hits; everything else is
 /// hydrated from DynamoDB, so documents must carry a string `id`.
 #[derive(Deserialize)]
@@ -129,6 +490,14 @@ struct IdOnly {
     id: String,
 }
 
+/// What [`SearchClient::multi_search`] retrieves from a hit.
+#[derive(Deserialize)]
+struct RankedDoc {
+    id: String,
+    #[serde(default)]
+    visible_to: Vec<String>,
+}
+
 /// What [`SearchClient::search_matches`] retrieves from a match hit — enough
 /// to resolve a participant's outcome without a second lookup.
 #[derive(Deserialize)]
@@ -179,9 +548,36 @@ impl SearchClient {
         Ok(())
     }
 
+    /// [`upsert`](Self::upsert) into the shadow of an index being rebuilt, so
+    /// a write made mid-rebuild survives the swap.
+    pub async fn upsert_shadow<T: Serialize + Send + Sync>(
+        &self,
+        index: Index,
+        doc: &T,
+    ) -> SearchResult<()> {
+        self.client
+            .index(index.shadow_name())
+            .add_documents(std::slice::from_ref(doc), Some("id"))
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// [`delete`](Self::delete) from the shadow of an index being rebuilt.
+    pub async fn delete_shadow(&self, index: Index, id: &str) -> SearchResult<()> {
+        self.client
+            .index(index.shadow_name())
+            .delete_document(id)
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        Ok(())
+    }
+
     /// Run a search against an index, returning ranked document ids and a
     /// next-page offset. The caller hydrates full entities from DynamoDB.
     pub async fn search(&self, index: Index, query: &SearchQuery) -> SearchResult<SearchHits> {
+        let filter = query.filter.as_ref().map(|f| f.render(index)).transpose()?;
+        check_facets(index, &query.facets)?;
         let idx = self.client.index(index.name());
         let mut builder = idx.search();
         builder
@@ -190,9 +586,12 @@ impl SearchClient {
             .with_limit(query.limit as usize)
             // Only the primary key comes back; entities are hydrated from Dynamo.
             .with_attributes_to_retrieve(Selectors::Some(&["id"]));
-        if let Some(filter) = &query.filter {
+        if let Some(filter) = &filter {
             builder.with_filter(filter);
         }
+        if !query.facets.is_empty() {
+            builder.with_facets(Selectors::Some(&query.facets));
+        }
         let sort_refs: Vec<&str> = query.sort.iter().map(String::as_str).collect();
         if !sort_refs.is_empty() {
             builder.with_sort(&sort_refs);
@@ -209,7 +608,11 @@ impl SearchClient {
         let total = results.estimated_total_hits.unwrap_or(consumed as usize) as u32;
         let next_offset = (consumed < total).then_some(consumed);
 
-        Ok(SearchHits { ids, next_offset })
+        Ok(SearchHits {
+            ids,
+            next_offset,
+            facets: results.facet_distribution.unwrap_or_default(),
+        })
     }
 
     /// Search the matches index, resolving each hit's outcome for
@@ -220,15 +623,21 @@ impl SearchClient {
     /// `participant` filter already retrieves.
     ///
     /// `participant_id` only makes sense paired with a `query.filter` that
-    /// already scopes results to that participant (e.g. `participant_ids =
-    /// "<id>"`) — this method doesn't add that filter itself, callers already
-    /// building one for the `participant` discovery param should reuse it as
-    /// the outcome subject.
+    /// already scopes results to that participant (e.g.
+    /// `Filter::eq("participant_ids", id)`) — this method doesn't add that
+    /// filter itself, callers already building one for the `participant`
+    /// discovery param should reuse it as the outcome subject.
     pub async fn search_matches(
         &self,
         query: &SearchQuery,
         participant_id: Option<&str>,
     ) -> SearchResult<MatchSearchHits> {
+        let filter = query
+            .filter
+            .as_ref()
+            .map(|f| f.render(Index::Matches))
+            .transpose()?;
+        check_facets(Index::Matches, &query.facets)?;
         let idx = self.client.index(Index::Matches.name());
         let mut builder = idx.search();
         builder
@@ -241,9 +650,12 @@ impl SearchClient {
                 "losing_participant_ids",
                 "drawing_participant_ids",
             ]));
-        if let Some(filter) = &query.filter {
+        if let Some(filter) = &filter {
             builder.with_filter(filter);
         }
+        if !query.facets.is_empty() {
+            builder.with_facets(Selectors::Some(&query.facets));
+        }
         let sort_refs: Vec<&str> = query.sort.iter().map(String::as_str).collect();
         if !sort_refs.is_empty() {
             builder.with_sort(&sort_refs);
@@ -281,23 +693,88 @@ impl SearchClient {
         let total = results.estimated_total_hits.unwrap_or(consumed as usize) as u32;
         let next_offset = (consumed < total).then_some(consumed);
 
-        Ok(MatchSearchHits { items, next_offset })
+        Ok(MatchSearchHits {
+            items,
+            next_offset,
+            facets: results.facet_distribution.unwrap_or_default(),
+        })
+    }
+
+    /// Run several searches in one request, e.g. the same text against every
+    /// index. Returns one [`RankedHits`] per query, in order, with each hit's
+    /// score and whether it [involves](RankedHit::involves_viewer)
+    /// `viewer_id`. `facets` are not counted here, and there's no next-page
+    /// offset: this is for a handful of top hits, not paging.
+    pub async fn multi_search(
+        &self,
+        queries: &[(Index, SearchQuery)],
+        viewer_id: &str,
+    ) -> SearchResult<Vec<RankedHits>> {
+        let filters = queries
+            .iter()
+            .map(|(index, query)| query.filter.as_ref().map(|f| f.render(*index)).transpose())
+            .collect::<SearchResult<Vec<_>>>()?;
+        let sorts: Vec<Vec<&str>> = queries
+            .iter()
+            .map(|(_, query)| query.sort.iter().map(String::as_str).collect())
+            .collect();
+        let indexes: Vec<_> = queries
+            .iter()
+            .map(|(index, _)| self.client.index(index.name()))
+            .collect();
+
+        let mut multi = self.client.multi_search();
+        for (i, (_, query)) in queries.iter().enumerate() {
+            let mut builder = indexes[i].search();
+            builder
+                .with_query(&query.q)
+                .with_offset(query.offset as usize)
+                .with_limit(query.limit as usize)
+                .with_show_ranking_score(true)
+                .with_attributes_to_retrieve(Selectors::Some(&["id", "visible_to"]));
+            if let Some(filter) = &filters[i] {
+                builder.with_filter(filter);
+            }
+            if !sorts[i].is_empty() {
+                builder.with_sort(&sorts[i]);
+            }
+            multi.with_search_query(builder);
+        }
+        let response = multi
+            .execute::<RankedDoc>()
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+
+        Ok(queries
+            .iter()
+            .zip(response.results)
+            .map(|((index, _), results)| RankedHits {
+                index: *index,
+                hits: results
+                    .hits
+                    .into_iter()
+                    .map(|h| RankedHit {
+                        involves_viewer: h.result.visible_to.iter().any(|v| v == viewer_id),
+                        id: h.result.id,
+                        score: h.ranking_score.unwrap_or(0.0),
+                    })
+                    .collect(),
+            })
+            .collect())
     }
 
     /// Configure one index's settings (creating the index if absent) so its
-    /// filterable / sortable attributes match what the API queries. Meilisearch
+    /// filterable / sortable / searchable attributes and typo tolerance match
+    /// what the API queries (see `Index::settings`). Meilisearch
     /// treats the settings update as idempotent — re-applying the same settings
     /// is a no-op — so this is safe to run on every worker start.
     ///
     /// Uses `id` as the primary key implicitly: documents are always upserted
     /// with `id` as the explicit primary key (see `upsert`).
     pub async fn configure_index(&self, index: Index) -> SearchResult<()> {
-        let settings = Settings::new()
-            .with_filterable_attributes(index.filterable_attributes().iter().copied())
-            .with_sortable_attributes(index.sortable_attributes().iter().copied());
         self.client
             .index(index.name())
-            .set_settings(&settings)
+            .set_settings(&index.settings())
             .await
             .map_err(|e| SearchError(e.to_string()))?;
         Ok(())
@@ -312,4 +789,272 @@ impl SearchClient {
         }
         Ok(())
     }
+
+    // --- Rebuilds -----------------------------------------------------------
+    //
+    // A rebuild fills `<index>__reindex` from scratch while `<index>` keeps
+    // serving searches, then swaps the two in one Meilisearch task — searches
+    // never see a half-built index. The old documents end up in the shadow,
+    // which is then dropped.
+
+    /// Start a fresh shadow for `index`: drop any leftover from an abandoned
+    /// rebuild, then create it with the current settings (so a newly
+    /// filterable field is declared before any documents go in).
+    pub async fn create_shadow(&self, index: Index) -> SearchResult<()> {
+        self.drop_shadow(index).await?;
+        let task = self
+            .client
+            .index(index.shadow_name())
+            .set_settings(&index.settings())
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        self.wait(task).await?;
+        Ok(())
+    }
+
+    /// Add a batch of documents to the shadow of `index`, waiting until
+    /// Meilisearch has indexed them so a rejected batch fails the caller
+    /// rather than going missing.
+    pub async fn fill_shadow<T: Serialize + Send + Sync>(
+        &self,
+        index: Index,
+        docs: &[T],
+    ) -> SearchResult<()> {
+        if docs.is_empty() {
+            return Ok(());
+        }
+        let task = self
+            .client
+            .index(index.shadow_name())
+            .add_documents(docs, Some("id"))
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        self.wait(task).await?;
+        Ok(())
+    }
+
+    /// Enqueue the swap of the built shadow in for `index`, returning its
+    /// task uid for [`wait_for_task`](Self::wait_for_task). The swap itself is
+    /// atomic on Meilisearch's side; the live index is configured first since
+    /// a swap needs both to exist.
+    ///
+    /// Not idempotent — a second swap swaps back — so callers record the uid
+    /// and wait on it rather than retrying this.
+    pub async fn start_swap(&self, index: Index) -> SearchResult<u32> {
+        self.configure_index(index).await?;
+        let task = self
+            .client
+            .swap_indexes([&SwapIndexes {
+                indexes: (index.name().to_string(), index.shadow_name()),
+                rename: None,
+            }])
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        Ok(task.task_uid)
+    }
+
+    /// Wait for an enqueued task (by uid) to succeed.
+    pub async fn wait_for_task(&self, task_uid: u32) -> SearchResult<()> {
+        self.wait(TaskUid(task_uid)).await
+    }
+
+    /// Delete the shadow of `index`. Idempotent: a missing shadow is fine.
+    pub async fn drop_shadow(&self, index: Index) -> SearchResult<()> {
+        let task = self
+            .client
+            .delete_index(index.shadow_name())
+            .await
+            .map_err(|e| SearchError(e.to_string()))?;
+        match self.finished(task).await? {
+            Task::Failed { content } if content.error.error_code != ErrorCode::IndexNotFound => {
+                Err(SearchError(content.error.error_message))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Wait for an enqueued task to succeed.
+    async fn wait(&self, task: impl AsRef<u32>) -> SearchResult<()> {
+        match self.finished(task).await? {
+            Task::Failed { content } => Err(SearchError(content.error.error_message)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wait for an enqueued task to finish, however it went.
+    async fn finished(&self, task: impl AsRef<u32>) -> SearchResult<Task> {
+        self.client
+            .wait_for_task(task, None, Some(TASK_TIMEOUT))
+            .await
+            .map_err(|e| SearchError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_names_roundtrip() {
+        for index in Index::ALL {
+            assert_eq!(Index::from_name(index.name()), Some(index));
+            assert_eq!(
+                serde_json::to_value(index).unwrap(),
+                serde_json::json!(index.name())
+            );
+        }
+        assert_eq!(Index::from_name("matches__reindex"), None);
+        assert_eq!(Index::Matches.shadow_name(), "matches__reindex");
+    }
+
+    fn render(filter: &Filter) -> String {
+        filter.render(Index::Matches).unwrap()
+    }
+
+    #[test]
+    fn visible_to_admits_public_or_listed_viewer() {
+        assert_eq!(
+            render(&Filter::visible_to("u1")),
+            r#"(visibility = "public" OR visible_to = "u1")"#
+        );
+    }
+
+    #[test]
+    fn visible_to_escapes_quotes() {
+        assert_eq!(
+            render(&Filter::visible_to(r#"u1" OR visibility = "followers"#)),
+            r#"(visibility = "public" OR visible_to = "u1\" OR visibility = \"followers")"#
+        );
+    }
+
+    #[test]
+    fn and_brackets_nested_or() {
+        let filter = Filter::eq("sport", "tennis").and(Filter::visible_to("u1"));
+        assert_eq!(
+            render(&filter),
+            r#"sport = "tennis" AND (visibility = "public" OR visible_to = "u1")"#
+        );
+        // Flattens rather than nesting ANDs.
+        let filter = filter.and(Filter::range("starts_at_ts", Some(1), None));
+        assert_eq!(
+            render(&filter),
+            r#"sport = "tennis" AND (visibility = "public" OR visible_to = "u1") AND starts_at_ts >= 1"#
+        );
+        assert_eq!(
+            render(&Filter::Or(vec![
+                Filter::And(vec![Filter::eq("sport", "a"), Filter::eq("status", "b")]),
+                Filter::Not(Box::new(Filter::eq("sport", "c"))),
+            ])),
+            r#"((sport = "a" AND status = "b") OR NOT (sport = "c"))"#
+        );
+    }
+
+    #[test]
+    fn all_collapses_zero_and_one_clause() {
+        assert_eq!(Filter::all([]), None);
+        assert_eq!(
+            Filter::all([Filter::eq("sport", "a")]),
+            Some(Filter::eq("sport", "a"))
+        );
+        assert!(matches!(
+            Filter::all([Filter::eq("sport", "a"), Filter::eq("status", "b")]),
+            Some(Filter::And(_))
+        ));
+    }
+
+    #[test]
+    fn renders_in_and_ranges() {
+        assert_eq!(
+            render(&Filter::any_of("sport", ["tennis", "padel"])),
+            r#"sport IN ["tennis", "padel"]"#
+        );
+        assert_eq!(
+            render(&Filter::range("starts_at_ts", Some(10), Some(20))),
+            "starts_at_ts 10 TO 20"
+        );
+        assert_eq!(
+            render(&Filter::range("starts_at_ts", None, Some(-5))),
+            "starts_at_ts <= -5"
+        );
+        assert_eq!(render(&Filter::eq("starts_at_ts", 42)), "starts_at_ts = 42");
+        assert!(
+            Filter::range("starts_at_ts", None, None)
+                .render(Index::Matches)
+                .is_err()
+        );
+        assert!(Filter::In("sport", vec![]).render(Index::Matches).is_err());
+        assert!(Filter::And(vec![]).render(Index::Matches).is_err());
+    }
+
+    /// Whatever a value holds, the rendered literal must end exactly where it
+    /// started: unescaping it gives back the input, and nothing follows it.
+    #[test]
+    fn adversarial_values_stay_inside_their_literal() {
+        let hostile = [
+            r#"u1" OR visibility = "followers"#,
+            r#"u1\"#,
+            r#"u1\" OR 1 = 1 OR x = "\"#,
+            r#"") OR (visibility = "private"#,
+            "u1\n) OR visible_to EXISTS",
+            r#"\\\""#,
+            "'u1' OR visibility = 'private'",
+            "u1 ] OR sport IN [ x",
+            "_geoRadius(0, 0, 1)",
+            "NOT",
+            "ü\u{202e}\0",
+            "",
+        ];
+        for value in hostile {
+            let rendered = render(&Filter::eq("participant_ids", value));
+            let literal = rendered
+                .strip_prefix("participant_ids = \"")
+                .and_then(|r| r.strip_suffix('"'))
+                .unwrap_or_else(|| panic!("bad shape: {rendered}"));
+            // Unescape, failing on any quote that isn't escaped — one would
+            // end the literal early.
+            let mut unescaped = String::new();
+            let mut chars = literal.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unescaped.push(chars.next().expect("dangling escape")),
+                    '"' => panic!("unescaped quote in {rendered}"),
+                    c => unescaped.push(c),
+                }
+            }
+            assert_eq!(unescaped, value, "{rendered}");
+        }
+    }
+
+    #[test]
+    fn rejects_attributes_the_index_cannot_filter() {
+        // `participant_ids` is only filterable on matches.
+        let filter = Filter::eq("participant_ids", "u1");
+        assert!(filter.render(Index::Matches).is_ok());
+        assert!(filter.render(Index::Users).is_err());
+        // Checked however deeply nested.
+        let nested = Filter::visible_to("u1").and(Filter::Not(Box::new(Filter::Or(vec![
+            Filter::eq("visibility", "public"),
+            Filter::eq("name", "x"),
+        ]))));
+        assert!(nested.render(Index::Teams).is_err());
+        // Only matches have a location.
+        let geo = Filter::GeoRadius {
+            lat: 51.5,
+            lng: -0.1,
+            meters: 1000,
+        };
+        assert_eq!(
+            geo.render(Index::Matches).unwrap(),
+            "_geoRadius(51.5, -0.1, 1000)"
+        );
+        assert!(geo.render(Index::Users).is_err());
+    }
+
+    #[test]
+    fn facets_must_be_filterable() {
+        assert!(check_facets(Index::Matches, &["sport", "status", "team_ids", "league"]).is_ok());
+        assert!(check_facets(Index::Matches, &["name"]).is_err());
+        assert!(check_facets(Index::Matches, &["_geo"]).is_err());
+        assert!(check_facets(Index::Teams, &["team_ids"]).is_err());
+    }
 }
@@ -0,0 +1,165 @@
+//! Who, and which teams, to suggest a user follow. Two signals:
+//! - co-participation: people they've played matches with, and teams that
+//!   played in those matches (on their side or the other);
+//! - the follow graph: people and teams followed by the people they follow
+//!   (friends of friends).
+//!
+//! The worker gathers the signals in bulk (see its `RefreshSuggestions`
+//! workflow); [`SuggestionTally`] weighs and ranks them into the
+//! [`SuggestionsRecord`] the API serves. Playing together is the stronger
+//! signal, so one shared match outweighs a few mutual follows.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::dao::records::{SuggestedTeamRecord, SuggestedUserRecord, SuggestionsRecord};
+
+/// What one shared match is worth, in mutual follows.
+const SHARED_MATCH_WEIGHT: u32 = 3;
+
+/// Most users, and most teams, kept per user.
+pub const MAX_SUGGESTIONS: usize = 20;
+
+/// Signals collected for one user, keyed by the candidate's id.
+#[derive(Debug, Default)]
+pub struct SuggestionTally {
+    users: HashMap<String, Counts>,
+    teams: HashMap<String, Counts>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    shared_matches: u32,
+    mutual_follows: u32,
+}
+
+impl Counts {
+    fn score(self) -> u32 {
+        self.shared_matches * SHARED_MATCH_WEIGHT + self.mutual_follows
+    }
+}
+
+impl SuggestionTally {
+    /// `user_id` played in one of the user's matches.
+    pub fn played_with_user(&mut self, user_id: &str) {
+        self.users
+            .entry(user_id.to_string())
+            .or_default()
+            .shared_matches += 1;
+    }
+
+    /// `team_id` played in one of the user's matches.
+    pub fn played_with_team(&mut self, team_id: &str) {
+        self.teams
+            .entry(team_id.to_string())
+            .or_default()
+            .shared_matches += 1;
+    }
+
+    /// Someone the user follows follows `user_id`.
+    pub fn followed_user(&mut self, user_id: &str) {
+        self.users
+            .entry(user_id.to_string())
+            .or_default()
+            .mutual_follows += 1;
+    }
+
+    /// Someone the user follows follows `team_id`.
+    pub fn followed_team(&mut self, team_id: &str) {
+        self.teams
+            .entry(team_id.to_string())
+            .or_default()
+            .mutual_follows += 1;
+    }
+
+    /// Rank the candidates for `user_id`, best first, keeping at most `max`
+    /// of each kind. `user_id` themselves and everything in `skip_users` /
+    /// `skip_teams` (already followed, blocked, their own teams) are left
+    /// out. Ties go to the lower id, so a rerun ranks the same way.
+    pub fn rank(
+        self,
+        user_id: &str,
+        computed_at: &str,
+        skip_users: &HashSet<String>,
+        skip_teams: &HashSet<String>,
+        max: usize,
+    ) -> SuggestionsRecord {
+        let users = ranked(
+            self.users,
+            |id| id == user_id || skip_users.contains(id),
+            max,
+        )
+        .into_iter()
+        .map(|(user_id, c)| SuggestedUserRecord {
+            user_id,
+            shared_matches: c.shared_matches,
+            mutual_follows: c.mutual_follows,
+        })
+        .collect();
+        let teams = ranked(self.teams, |id| skip_teams.contains(id), max)
+            .into_iter()
+            .map(|(team_id, c)| SuggestedTeamRecord {
+                team_id,
+                shared_matches: c.shared_matches,
+                mutual_follows: c.mutual_follows,
+            })
+            .collect();
+        SuggestionsRecord {
+            user_id: user_id.to_string(),
+            computed_at: computed_at.to_string(),
+            users,
+            teams,
+        }
+    }
+}
+
+fn ranked(
+    candidates: HashMap<String, Counts>,
+    skip: impl Fn(&str) -> bool,
+    max: usize,
+) -> Vec<(String, Counts)> {
+    let mut ranked: Vec<(String, Counts)> =
+        candidates.into_iter().filter(|(id, _)| !skip(id)).collect();
+    ranked.sort_by(|a, b| b.1.score().cmp(&a.1.score()).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(max);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(users: &[SuggestedUserRecord]) -> Vec<&str> {
+        users.iter().map(|u| u.user_id.as_str()).collect()
+    }
+
+    #[test]
+    fn a_shared_match_outranks_a_couple_of_mutual_follows() {
+        let mut tally = SuggestionTally::default();
+        tally.played_with_user("teammate");
+        tally.followed_user("popular");
+        tally.followed_user("popular");
+        tally.followed_user("known");
+        let record = tally.rank("me", "t", &HashSet::new(), &HashSet::new(), 10);
+        assert_eq!(ids(&record.users), ["teammate", "popular", "known"]);
+        assert_eq!(record.users[1].mutual_follows, 2);
+        assert_eq!(record.users[0].shared_matches, 1);
+    }
+
+    #[test]
+    fn skips_self_and_excluded_and_caps() {
+        let mut tally = SuggestionTally::default();
+        for id in ["me", "followed", "blocked", "a", "b", "c"] {
+            tally.followed_user(id);
+        }
+        tally.played_with_team("mine");
+        tally.played_with_team("rival");
+        let skip_users = HashSet::from(["followed".to_string(), "blocked".to_string()]);
+        let skip_teams = HashSet::from(["mine".to_string()]);
+        let record = tally.rank("me", "t", &skip_users, &skip_teams, 2);
+        // Equal scores fall back to id order.
+        assert_eq!(ids(&record.users), ["a", "b"]);
+        assert_eq!(record.teams.len(), 1);
+        assert_eq!(record.teams[0].team_id, "rival");
+        assert_eq!(record.user_id, "me");
+    }
+}
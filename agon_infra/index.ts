@@ -369,8 +369,9 @@ const dynamoTable = new aws.dynamodb.Table("agon", {
 	// Streams feed the async fan-out / search-indexing pipeline.
 	streamEnabled: true,
 	streamViewType: "NEW_AND_OLD_IMAGES",
-	// Optional auto-expiry for ephemeral items (e.g. pending assets); items opt
-	// in by setting a numeric `ttl` attribute (epoch seconds).
+	// Optional auto-expiry for ephemeral items (e.g. pending assets, feed
+	// entries past their retention); items opt in by setting a numeric `ttl`
+	// attribute (epoch seconds).
 	ttl: { attributeName: "ttl", enabled: true },
 	pointInTimeRecovery: { enabled: true },
 });
@@ -400,6 +401,9 @@ new aws.iam.UserPolicy("agon-app-dynamodb", {
 					"dynamodb:UpdateItem",
 					"dynamodb:DeleteItem",
 					"dynamodb:Query",
+					// Only the worker's search reindex job scans (scan_*_ids); without
+					// it the ReindexSearch workflow's pages fail AccessDenied.
+					"dynamodb:Scan",
 					"dynamodb:TransactWriteItems",
 					"dynamodb:TransactGetItems",
 					"dynamodb:ConditionCheckItem",
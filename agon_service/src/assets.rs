@@ -9,8 +9,9 @@
 //!     it stores the object's canonical CDN URL. Profile/team images are served
 //!     through a *public* CloudFront behaviour (returned verbatim); match-header
 //!     images are served through a *signed* behaviour, so the URL is minted with
-//!     a short expiry at read time — this is where future per-match visibility
-//!     (e.g. followers-only) is enforced, without changing the upload path.
+//!     a short expiry at read time, and only for a caller allowed to see the
+//!     match (see `sign_match_headers` and `Dao::can_view_match`) — a private
+//!     match's photos can't be fetched by anyone else.
 //!
 //! The bucket itself stays fully private (Origin Access Control): CloudFront is
 //! the only reader. See `agon_infra/index.ts`.
@@ -0,0 +1,42 @@
+use poem_openapi::{Enum, Object};
+
+use crate::team::TeamListItem;
+
+/// A club grouping several teams (e.g. a 1st XI, 2nd XI, juniors and a
+/// women's side). Club admins are admins of every team in the club.
+#[derive(Object)]
+pub struct Club {
+    pub id: String,
+    pub name: String,
+    pub teams: Vec<TeamListItem>,
+    /// Users following the club itself.
+    pub follower_count: u32,
+    /// Followers summed across the club's teams. A user following two of the
+    /// teams counts twice.
+    pub team_follower_count: u32,
+    /// Whether the requesting user follows the club.
+    pub is_followed_by_me: bool,
+    /// Whether the requesting user is a club admin.
+    pub is_admin: bool,
+}
+
+#[derive(Object)]
+pub struct CreateClubInput {
+    pub name: String,
+}
+
+#[derive(Object)]
+pub struct AddClubAdminInput {
+    pub user_id: String,
+}
+
+/// Which of a club's matches to list.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum ClubMatchesView {
+    /// Matches without a confirmed result that haven't started yet, soonest
+    /// first.
+    Fixtures,
+    /// Matches with a confirmed result, most recent first.
+    Results,
+}
@@ -0,0 +1,195 @@
+//! `@`-mentions and `#tags` in comment text.
+//!
+//! Pure text scanning only: [`mention_tokens`] finds the `@handle`s and
+//! [`hashtags`] the `#tags`; resolving a handle to a user (against the match's
+//! participants and the author's follows, by [`name_key`]) is the handler's
+//! job, since it needs the DAO.
+//!
+//! A marker only counts at the start of the text or after a character that
+//! can't be part of a word, so `sofia@example.com` isn't a mention and `C#`
+//! isn't a tag.
+
+/// At most this many distinct tags are kept per comment; the rest are left
+/// as plain text. Bounds the tag-index writes in a comment's transaction.
+pub const MAX_COMMENT_TAGS: usize = 10;
+
+/// At most this many mentions are resolved per comment.
+pub const MAX_COMMENT_MENTIONS: usize = 10;
+
+/// Longest tag kept; a longer run after `#` isn't treated as a tag at all.
+const MAX_TAG_LEN: usize = 50;
+
+/// An `@handle` in comment text: the handle without its `@`, and the byte
+/// range of the whole token (including the `@`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionToken<'a> {
+    pub handle: &'a str,
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Every `@handle` in `text`, in order. A handle is a run of letters, digits,
+/// `_`, `.` and `-`, without trailing punctuation — so `@Sofia.` at the end of
+/// a sentence is `Sofia`.
+pub fn mention_tokens(text: &str) -> Vec<MentionToken<'_>> {
+    markers(text, '@', |c| {
+        c.is_alphanumeric() || matches!(c, '_' | '.' | '-')
+    })
+    .into_iter()
+    .filter_map(|(start, body)| {
+        let handle = body.trim_end_matches(['.', '-']);
+        (!handle.is_empty()).then_some(MentionToken {
+            handle,
+            start,
+            len: handle.len() + 1,
+        })
+    })
+    .collect()
+}
+
+/// The distinct `#tags` in `text`, lowercased and without the `#`, in order of
+/// first use and capped at [`MAX_COMMENT_TAGS`]. A tag is a run of letters,
+/// digits and `_` with at least one letter, so `#1` (a shirt number, a rank)
+/// isn't one.
+pub fn hashtags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for (_, body) in markers(text, '#', |c| c.is_alphanumeric() || c == '_') {
+        if body.chars().count() > MAX_TAG_LEN || !body.chars().any(char::is_alphabetic) {
+            continue;
+        }
+        let tag = body.to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+            if tags.len() == MAX_COMMENT_TAGS {
+                break;
+            }
+        }
+    }
+    tags
+}
+
+/// Normalize a tag given in a URL the way [`hashtags`] stores it. `None` if it
+/// couldn't be a tag.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.strip_prefix('#').unwrap_or(tag);
+    let is_tag = !tag.is_empty()
+        && tag.chars().count() <= MAX_TAG_LEN
+        && tag.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && tag.chars().any(char::is_alphabetic);
+    is_tag.then(|| tag.to_lowercase())
+}
+
+/// The form a handle and a display name are compared in: lowercase letters and
+/// digits only. `@sofia_ramos`, `@SofiaRamos` and `@sofia.ramos` all match
+/// "Sofia Ramos".
+pub fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Each `marker` at a word boundary, with the run of `body` characters after
+/// it: `(byte offset of the marker, body)`. Empty bodies are skipped.
+fn markers(text: &str, marker: char, body: impl Fn(char) -> bool) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut prev: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        let at_boundary = prev.is_none_or(|p| !(p.is_alphanumeric() || p == '_'));
+        prev = Some(c);
+        if c != marker || !at_boundary {
+            continue;
+        }
+        let rest = &text[i + c.len_utf8()..];
+        let end = rest.find(|c: char| !body(c)).unwrap_or(rest.len());
+        if end > 0 {
+            out.push((i, &rest[..end]));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentions_carry_their_byte_range() {
+        let text = "gg @sofia_ramos and @Leo.";
+        let tokens = mention_tokens(text);
+        assert_eq!(
+            tokens,
+            vec![
+                MentionToken {
+                    handle: "sofia_ramos",
+                    start: 3,
+                    len: 12,
+                },
+                MentionToken {
+                    handle: "Leo",
+                    start: 20,
+                    len: 4,
+                },
+            ]
+        );
+        for t in &tokens {
+            assert_eq!(&text[t.start..t.start + t.len], format!("@{}", t.handle));
+        }
+    }
+
+    #[test]
+    fn mention_offsets_are_bytes_after_multibyte_text() {
+        let text = "¡olé! @José";
+        let tokens = mention_tokens(text);
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            &text[tokens[0].start..tokens[0].start + tokens[0].len],
+            "@José"
+        );
+    }
+
+    #[test]
+    fn markers_inside_words_are_ignored() {
+        assert!(mention_tokens("mail sofia@example.com").is_empty());
+        assert!(mention_tokens("just an @ sign").is_empty());
+        assert!(hashtags("we play C# and F#").is_empty());
+    }
+
+    #[test]
+    fn hashtags_are_lowercased_distinct_and_need_a_letter() {
+        assert_eq!(
+            hashtags("#Derby day! #derby #1 #top_4 (#finals)"),
+            vec!["derby", "top_4", "finals"]
+        );
+    }
+
+    #[test]
+    fn hashtags_are_capped() {
+        let text: String = (0..MAX_COMMENT_TAGS + 5)
+            .map(|i| format!("#t{i} "))
+            .collect();
+        assert_eq!(hashtags(&text).len(), MAX_COMMENT_TAGS);
+    }
+
+    #[test]
+    fn overlong_tags_are_skipped() {
+        let long = "a".repeat(MAX_TAG_LEN + 1);
+        assert!(hashtags(&format!("#{long}")).is_empty());
+    }
+
+    #[test]
+    fn normalize_tag_accepts_what_hashtags_stores() {
+        assert_eq!(normalize_tag("#Derby"), Some("derby".into()));
+        assert_eq!(normalize_tag("top_4"), Some("top_4".into()));
+        assert_eq!(normalize_tag("42"), None);
+        assert_eq!(normalize_tag("two words"), None);
+        assert_eq!(normalize_tag(""), None);
+    }
+
+    #[test]
+    fn name_key_ignores_case_and_punctuation() {
+        assert_eq!(name_key("Sofia Ramos"), "sofiaramos");
+        assert_eq!(name_key("sofia.ramos"), "sofiaramos");
+        assert_eq!(name_key("SOFIA_RAMOS"), "sofiaramos");
+    }
+}
@@ -40,13 +40,18 @@ use auth::{JwtClaims, JwtVerifier};
 mod mapping;
 use mapping::{
     comment_from_record, dao_internal, derive_live_score, device_platform_to_record,
-    feed_match_from_records, invitation_detail_from_record, invitation_from_record,
-    invitation_status_from_str, invitation_status_str, live_event_from_record,
-    match_format_sport_tag, match_format_to_record, match_from_records, match_score_from_record,
-    match_score_to_record, match_status_str, match_type_tag, new_live_event_to_dao,
-    notification_actor_id, notification_from_record, roster_preview_player,
-    score_submission_from_record, score_to_record, search_match_from_records, team_from_records,
-    team_list_item_from_record, user_profile_from_record,
+    digest_frequency_to_record, embedded_user_profile, feed_match_from_records,
+    invitation_detail_from_record, invitation_from_record, invitation_status_from_str,
+    invitation_status_str, invite_link_from_record, join_request_from_record,
+    live_event_from_record, match_format_sport_tag, match_format_to_record, match_from_records,
+    match_score_from_record, match_score_to_record, match_status_str, match_type_tag,
+    new_live_event_to_dao, notification_actor_id, notification_from_record,
+    notification_preferences_from_record, notification_type_to_record, quiet_hours_to_record,
+    reaction_to_record, reactor_from_record, report_case_from_record, report_from_record,
+    report_reason_str, roster_preview_player, score_submission_from_record, score_to_record,
+    search_match_from_records, season_squad_member_from_record, team_from_records,
+    team_list_item_from_record, team_role_str, team_season_from_record, user_profile_from_record,
+    visibility_to_record,
 };
 
 // Object-storage integration: S3 presigned uploads + CloudFront serving URLs.
@@ -81,17 +86,41 @@ use membership::{
     RespondToInvitationInput, TokenInvitation, UserInvitation, UserMember,
 };
 
+mod club;
+use club::{AddClubAdminInput, Club, ClubMatchesView, CreateClubInput};
+
 mod team;
 use team::{
-    AddTeamMembersInput, CreateTeamInput, Team, TeamListItem, TeamMember, TeamRole, UpdateTeamInput,
+    AddTeamMembersInput, CreateInviteLinkInput, CreateTeamInput, CreateTeamSeasonInput, InviteLink,
+    InviteLinkPage, JoinRequest, JoinRequestPage, JoinRequestResponse, PostTeamAnnouncementInput,
+    RedeemInviteLinkInput, RespondToJoinRequestInput, SeasonPlayerStats, SeasonSquadMember, Team,
+    TeamAnnouncement, TeamListItem, TeamMember, TeamRole, TeamSeason, TeamSeasonStats,
+    TransferTeamAdminInput, UpdateTeamInput,
 };
 
+// Who may mutate which team, club or match.
+mod policy;
+use policy::{ClubAction, ClubStanding, MatchAction, MatchStanding, TeamAction, TeamStanding};
+
 mod notification;
 use notification::{
     CommentNotification, FollowNotification, InvitationAcceptedNotification, LikeNotification,
-    MatchInvitationNotification, Notification, NotificationKind, NotificationPage,
-    ReplyNotification, ScoreConfirmedNotification, ScoreSubmittedNotification,
-    TeamInvitationNotification, UnreadCount,
+    MatchCancelledNotification, MatchInvitationNotification, MatchPostponedNotification,
+    Notification, NotificationKind, NotificationPage, NotificationPreferences, ReplyNotification,
+    ScoreConfirmedNotification, ScoreSubmittedNotification, TeamInvitationNotification,
+    TeamJoinApprovedNotification, TeamJoinRequestNotification, UnreadCount,
+};
+
+// `@`-mention and `#tag` scanning for comment text.
+mod comment_text;
+
+mod reaction;
+use reaction::{ReactInput, Reaction, ReactionCount, ReactorPage};
+
+mod moderation;
+use moderation::{
+    CreateReportInput, ModerationAction, ReportCaseDetail, ReportCasePage, ReportTargetType,
+    ResolveReportInput,
 };
 
 #[derive(SecurityScheme)]
@@ -139,6 +168,14 @@ pub struct UserProfile {
     pub following_count: u32,
     /// Whether the requesting user follows this profile. False for your own.
     pub is_followed_by_me: bool,
+    /// Whether the requesting user has asked to follow this (private) profile
+    /// and is waiting on approval. Only set by `GET /users/:user_id`; false
+    /// wherever a profile is embedded.
+    pub is_follow_requested_by_me: bool,
+    /// Who may see the profile's stats and follow lists. Where a restricted
+    /// profile is embedded (a roster, a comment), `stats` is left empty for
+    /// everyone but its owner.
+    pub visibility: Visibility,
 }
 
 /// The authenticated user's own view: their public `profile` plus private fields
@@ -169,6 +206,7 @@ struct CreateUserInput {
 struct UpdateUserInput {
     name: Option<String>,
     profile_image_asset_id: Option<String>,
+    visibility: Option<Visibility>,
     // Note: no `email` field. Email is owned by the identity provider (the JWT
     // `email` claim), not user-editable through the API — accepting it here would
     // be both a no-op (the DAO doesn't update email) and misleading.
@@ -569,6 +607,21 @@ pub enum MatchStatus {
     Cancelled,
 }
 
+/// Who may see a match, team or profile. Restricted records read as not
+/// found to everyone else, and only their participants find them in search.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Anyone.
+    Public,
+    /// The participants (a match's organizer and players, a team's members,
+    /// a profile's owner) and anyone following one of them — for a match,
+    /// also followers of its teams and their clubs.
+    Followers,
+    /// The participants only.
+    Participants,
+}
+
 /// A geographic location. Optional on a match.
 #[derive(Object)]
 struct Location {
@@ -588,6 +641,10 @@ struct Match {
     starts_at: chrono::DateTime<chrono::Utc>,
     /// Where the match is / was played. Optional.
     location: Option<Location>,
+    /// The venue's name, if given.
+    venue: Option<String>,
+    /// The league or competition the match is part of, if any.
+    league: Option<String>,
     header_photos: Vec<Photo>,
     /// The opposing sides (always present — score needs them).
     sides: Vec<MatchSide>,
@@ -608,16 +665,58 @@ struct Match {
     /// ...), if configured. `None` means the creator didn't set one — clients
     /// should fall back to their own sensible per-sport defaults.
     format: Option<MatchFormat>,
+    /// Why the match was called off. Present once cancelled through
+    /// `POST /matches/:match_id/cancel`.
+    cancellation: Option<MatchCancellation>,
+    /// Every time the match was moved to a new start time, oldest first.
+    postponements: Vec<MatchPostponement>,
+    visibility: Visibility,
+}
+
+#[derive(Object)]
+struct MatchCancellation {
+    reason: String,
+    /// The organizer who cancelled the match.
+    cancelled_by_user_id: String,
+    cancelled_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+struct MatchPostponement {
+    from_starts_at: chrono::DateTime<chrono::Utc>,
+    to_starts_at: chrono::DateTime<chrono::Utc>,
+    reason: String,
+    /// The organizer who postponed the match.
+    postponed_by_user_id: String,
+    postponed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+struct CancelMatchInput {
+    reason: String,
+}
+
+#[derive(Object)]
+struct PostponeMatchInput {
+    reason: String,
+    /// The new start time. Must be in the future and differ from the current
+    /// one.
+    starts_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// Social engagement summary for a match. Counts plus whether the requesting
-/// user has liked it, for rendering feed/detail cards in one fetch.
+/// Social engagement summary for a match. Counts plus the requesting user's
+/// own reaction, for rendering feed/detail cards in one fetch.
 #[derive(Object)]
 struct MatchSocial {
+    /// How many reacted with 👍 (`like`) — what a like is.
     like_count: u32,
     comment_count: u32,
-    /// Whether the requesting user has liked this match.
+    /// Whether the requesting user's reaction is 👍.
     i_liked: bool,
+    /// Per-reaction counts, omitting reactions nobody used.
+    reactions: Vec<ReactionCount>,
+    /// The requesting user's reaction, if any.
+    my_reaction: Option<Reaction>,
 }
 
 /// A comment on a match. Threads are two levels: a top-level comment
@@ -626,6 +725,8 @@ struct MatchSocial {
 #[derive(Object)]
 struct Comment {
     id: String,
+    /// The match it's on.
+    match_id: String,
     /// The top-level comment this is a reply to. None = a top-level comment.
     parent_id: Option<String>,
     /// The author's profile, for rendering name/avatar inline. None on a
@@ -639,15 +740,39 @@ struct Comment {
     edited_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Number of replies. Always 0 for a reply (replies can't be replied to).
     reply_count: u32,
+    /// Per-reaction counts, omitting reactions nobody used.
+    reactions: Vec<ReactionCount>,
+    /// The requesting user's reaction, if any.
+    my_reaction: Option<Reaction>,
     /// When the comment was deleted. When set, this is a tombstone: `author` and
     /// `text` are null and the client shows "[deleted]", but the row is kept so
     /// its replies remain visible. A deleted comment with no replies is removed
     /// entirely rather than tombstoned.
     deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The `@`-mentions in `text` that resolved to a user. Empty on a
+    /// tombstone.
+    mentions: Vec<CommentMention>,
+    /// The `#tags` in `text`, lowercased and without the `#` — each browsable
+    /// via `GET /tags/:tag/comments`. Empty on a tombstone.
+    tags: Vec<String>,
+}
+
+/// An `@`-mention in a comment. `start`/`len` are the byte range of the
+/// `@handle` token in `text`; clients render `user`'s current name over it, so
+/// a rename after the comment was written still shows correctly.
+#[derive(Object)]
+struct CommentMention {
+    user: UserProfile,
+    start: u32,
+    len: u32,
 }
 
 #[derive(Object)]
 struct CreateCommentInput {
+    /// The comment body. `@handle` mentions a match participant or someone
+    /// the author follows — the handle is their name in any case, with or
+    /// without spaces, `_` or `.` (`@sofia_ramos` for "Sofia Ramos") — and
+    /// notifies them. `#tag` files the comment under that tag.
     text: String,
     /// To reply, set this to a top-level comment's id. Omit for a top-level
     /// comment. Replying to a reply is rejected.
@@ -816,6 +941,8 @@ struct CreateMatchInviteInput {
 struct CreateMatchExternalInviteInput {
     client_id: String,
     name: String,
+    /// Where to email the guest their invitation link, if anywhere.
+    email: Option<String>,
 }
 
 #[derive(Object)]
@@ -828,6 +955,11 @@ struct CreateMatchInput {
     /// supplied (an already-played, Completed match).
     starts_at: chrono::DateTime<chrono::Utc>,
     location: Option<Location>,
+    /// The venue's name. Blank is the same as absent.
+    venue: Option<String>,
+    /// The league or competition the match is part of. Blank is the same as
+    /// absent.
+    league: Option<String>,
     /// The opposing sides. At least two are required.
     sides: Vec<CreateMatchSideInput>,
     /// Players to invite up front. Optional — more can be added later.
@@ -850,6 +982,9 @@ struct CreateMatchInput {
     /// Sport-specific format/rules. Optional — omit for the app's own
     /// defaults; must match `match_type`'s sport if supplied.
     format: Option<MatchFormat>,
+    /// Who may see the match. Defaults to the most restrictive visibility
+    /// among the teams playing in it (public for a match without teams).
+    visibility: Option<Visibility>,
 }
 
 /// The organiser's one-stop update for a match: edit metadata, reconcile the
@@ -871,6 +1006,10 @@ struct UpdateMatchInput {
     description: Option<String>,
     starts_at: Option<chrono::DateTime<chrono::Utc>>,
     location: Option<Location>,
+    /// Rename the venue; blank clears it.
+    venue: Option<String>,
+    /// Change the league; blank clears it.
+    league: Option<String>,
     /// Move the match through its lifecycle (e.g. cancel).
     status: Option<MatchStatus>,
     /// Ad-hoc players who actually played but weren't invited (e.g. ringers).
@@ -916,14 +1055,75 @@ struct UpdateMatchInput {
     /// Replace the match's format/rules. `None` leaves it unchanged; must
     /// match the match's sport if supplied.
     format: Option<MatchFormat>,
+    /// Change who may see the match. Narrowing it removes the match from the
+    /// feeds of anyone no longer allowed to see it on the next fan-out.
+    visibility: Option<Visibility>,
 }
 
-/// A single entry in the feed. Modelled as a union so new item types
-/// (member joined, achievements, etc.) can be added without breaking clients.
+/// A single entry in the feed. Modelled as a union so new item types can be
+/// added without breaking clients; a client should skip types it doesn't know.
 #[derive(Union)]
 #[oai(one_of, discriminator_name = "type")]
 enum FeedItem {
+    /// A match, placed by when it starts.
     Match(FeedMatch),
+    /// A match's confirmed result, placed by when it was confirmed.
+    Result(FeedResult),
+    /// Someone reaching a round number of matches played or won.
+    Milestone(FeedMilestone),
+    /// A post by a team's admin.
+    TeamAnnouncement(FeedTeamAnnouncement),
+    /// Someone joining a team.
+    MemberJoined(FeedMemberJoined),
+}
+
+/// A match's confirmed result: the match card again (its `confirmed_score`
+/// set), sitting in the feed at the time the result was confirmed rather
+/// than when the match started. Replaced by a new card if the result is
+/// rescored, and gone if the match is cancelled.
+#[derive(Object)]
+struct FeedResult {
+    #[oai(rename = "match")]
+    match_: FeedMatch,
+    confirmed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Which stat a milestone counts.
+#[derive(Enum)]
+#[oai(rename_all = "snake_case")]
+enum MilestoneStat {
+    MatchesPlayed,
+    Wins,
+}
+
+/// A user reaching `count` matches played (or won) in a sport — their first
+/// win, their 100th match, ... (see `agon_core::milestones::THRESHOLDS`).
+#[derive(Object)]
+struct FeedMilestone {
+    id: String,
+    user: UserProfile,
+    match_type: MatchType,
+    stat: MilestoneStat,
+    count: u64,
+    reached_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A team admin's announcement, shown to the team's members and followers.
+#[derive(Object)]
+struct FeedTeamAnnouncement {
+    id: String,
+    team: TeamListItem,
+    author: UserProfile,
+    body: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Someone joining a team, shown to the team's members and followers.
+#[derive(Object)]
+struct FeedMemberJoined {
+    team: TeamListItem,
+    user: UserProfile,
+    joined_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// A match as it appears in the feed — everything [`Match`] has except the
@@ -942,6 +1142,8 @@ struct FeedMatch {
     /// When the match starts / started.
     starts_at: chrono::DateTime<chrono::Utc>,
     location: Option<Location>,
+    venue: Option<String>,
+    league: Option<String>,
     header_photos: Vec<Photo>,
     /// The opposing sides (always present — score needs them).
     sides: Vec<MatchSide>,
@@ -975,6 +1177,17 @@ struct FeedPage {
     next_cursor: Option<String>,
 }
 
+/// How `GET /feed` orders its items.
+#[derive(Enum)]
+#[oai(rename_all = "snake_case")]
+enum FeedMode {
+    /// Newest match start first (the default).
+    Chronological,
+    /// "For you": matches only, best first, by who's playing, engagement,
+    /// recency and whether it's live (see `agon_core::feed_rank`).
+    Ranked,
+}
+
 /// A queried `participant`'s result in a match — resolved from the search
 /// index's outcome buckets (see `agon_core::search::SearchClient::search_matches`),
 /// no extra lookup.
@@ -1002,6 +1215,8 @@ struct SearchMatch {
     status: MatchStatus,
     starts_at: chrono::DateTime<chrono::Utc>,
     location: Option<Location>,
+    venue: Option<String>,
+    league: Option<String>,
     header_photos: Vec<Photo>,
     sides: Vec<MatchSide>,
     /// The `participant` query parameter's result in this match — `None` if
@@ -1019,6 +1234,90 @@ struct SearchMatch {
 struct MatchPage {
     items: Vec<SearchMatch>,
     next_cursor: Option<String>,
+    /// Counts for filter chips. Only `GET /matches` returns these, and only
+    /// on the first page.
+    facets: Option<MatchFacets>,
+}
+
+/// How many matches in the whole result set — not just this page — fall
+/// under each sport / status / league / team, with the request's other
+/// filters applied. Most common first; values with no matches are left out.
+#[derive(Object)]
+struct MatchFacets {
+    sports: Vec<FacetCount>,
+    statuses: Vec<FacetCount>,
+    leagues: Vec<FacetCount>,
+    /// The `MATCH_TEAM_FACET_CAP` most common teams.
+    teams: Vec<FacetCount>,
+}
+
+/// `GET /search`: the best few users, teams and matches for a query, one
+/// group per kind that had any hits, best group first.
+#[derive(Object)]
+struct GlobalSearchResults {
+    groups: Vec<SearchGroup>,
+}
+
+/// One kind's hits in a [`GlobalSearchResults`], best first.
+#[derive(Union)]
+#[oai(one_of, discriminator_name = "type")]
+enum SearchGroup {
+    Users(UserSearchGroup),
+    Teams(TeamSearchGroup),
+    Matches(MatchSearchGroup),
+}
+
+#[derive(Object)]
+struct UserSearchGroup {
+    items: Vec<UserProfile>,
+}
+
+#[derive(Object)]
+struct TeamSearchGroup {
+    items: Vec<TeamListItem>,
+}
+
+#[derive(Object)]
+struct MatchSearchGroup {
+    items: Vec<SearchMatch>,
+}
+
+/// `GET /suggestions`: people and teams the caller might want to follow,
+/// best first. Empty until suggestions are first computed for them.
+#[derive(Object)]
+struct Suggestions {
+    users: Vec<SuggestedUser>,
+    teams: Vec<SuggestedTeam>,
+}
+
+/// A suggested user, with why.
+#[derive(Object)]
+struct SuggestedUser {
+    user: UserProfile,
+    /// Recent matches you both played in.
+    shared_matches: u32,
+    /// People you follow who follow them.
+    mutual_follows: u32,
+}
+
+/// A suggested team, with why.
+#[derive(Object)]
+struct SuggestedTeam {
+    team: TeamListItem,
+    /// Recent matches of yours this team played in.
+    shared_matches: u32,
+    /// People you follow who follow it.
+    mutual_follows: u32,
+}
+
+/// One facet value and how many matches have it.
+#[derive(Object)]
+struct FacetCount {
+    /// What to pass back as the filter (`match_type`, `status` or `team`).
+    value: String,
+    /// The team's name, for a team facet.
+    label: Option<String>,
+    count: u64,
 }
 
 /// One page of users (e.g. followers / following). `next_cursor` absent => end.
@@ -1105,6 +1404,10 @@ enum UpdateTeamResponse {
     #[oai(status = 400)]
     ValidationError(PlainText<String>),
 
+    /// The caller isn't a team admin.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
@@ -1114,6 +1417,50 @@ enum RemoveTeamMemberResponse {
     #[oai(status = 200)]
     Team(Json<Team>),
 
+    /// The caller isn't a team admin, and the membership isn't their own.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The member is the team's last admin — hand admin over first.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum TransferTeamAdminResponse {
+    /// The team after the handover.
+    #[oai(status = 200)]
+    Team(Json<Team>),
+
+    /// The target can't take over (not a linked user, or the caller).
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// The caller is not an admin member of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The team's roles changed during the handover; retry.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ArchiveTeamResponse {
+    /// The team is archived and its followers detached.
+    #[oai(status = 204)]
+    Ok,
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
@@ -1141,6 +1488,21 @@ enum SearchUsersResponse {
     ValidationError(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum GlobalSearchResponse {
+    #[oai(status = 200)]
+    Results(Json<GlobalSearchResults>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum GetSuggestionsResponse {
+    #[oai(status = 200)]
+    Suggestions(Json<Suggestions>),
+}
+
 #[derive(ApiResponse)]
 enum GetFeedResponse {
     #[oai(status = 200)]
@@ -1177,6 +1539,46 @@ enum CreateMatchResponse {
     ValidationError(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum CancelMatchResponse {
+    #[oai(status = 200)]
+    Match(Json<Match>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// The caller may not manage this match.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The match is already cancelled.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum PostponeMatchResponse {
+    #[oai(status = 200)]
+    Match(Json<Match>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// The caller may not manage this match.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The match is cancelled, or its start time changed concurrently.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
 #[derive(ApiResponse)]
 enum UpdateMatchResponse {
     #[oai(status = 200)]
@@ -1243,6 +1645,34 @@ enum LikeResponse {
     #[oai(status = 204)]
     Ok,
 
+    /// The caller has a block with someone in the match (like only).
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ReactResponse {
+    /// The caller's reaction is now set (put) or gone (delete).
+    #[oai(status = 204)]
+    Ok,
+
+    /// The caller has a block with someone in the match, or with the
+    /// comment's author (put only).
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListReactionsResponse {
+    #[oai(status = 200)]
+    Reactors(Json<ReactorPage>),
+
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
@@ -1265,6 +1695,24 @@ enum ListCommentsResponse {
     NotFound(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum ListTagCommentsResponse {
+    #[oai(status = 200)]
+    Comments(Json<CommentPage>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListTagMatchesResponse {
+    #[oai(status = 200)]
+    Matches(Json<MatchPage>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
 #[derive(ApiResponse)]
 enum CreateCommentResponse {
     #[oai(status = 200)]
@@ -1273,6 +1721,11 @@ enum CreateCommentResponse {
     #[oai(status = 400)]
     ValidationError(PlainText<String>),
 
+    /// The caller has a block with someone in the match, or with the author
+    /// of the comment they're replying to.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
@@ -1327,6 +1780,15 @@ enum MarkNotificationReadResponse {
     NotFound(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum NotificationPreferencesResponse {
+    #[oai(status = 200)]
+    Preferences(Json<NotificationPreferences>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
 /// The client platform a registered push token belongs to.
 #[derive(Enum)]
 #[oai(rename_all = "snake_case")]
@@ -1425,6 +1887,10 @@ enum DeleteLiveEventResponse {
     #[oai(status = 400)]
     ValidationError(PlainText<String>),
 
+    /// The caller may not manage this match.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
     /// Either the match or that specific seq doesn't exist.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
@@ -1470,63 +1936,311 @@ enum GetTeamResponse {
 }
 
 #[derive(ApiResponse)]
-enum AddTeamMembersResponse {
+enum CreateTeamSeasonResponse {
     #[oai(status = 200)]
-    Team(Json<Team>),
+    Season(Json<TeamSeason>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
 
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
 
 #[derive(ApiResponse)]
-enum AddInvitationsResponse {
+enum PostTeamAnnouncementResponse {
     #[oai(status = 200)]
-    Invitations(Json<Vec<Invitation>>),
+    Announcement(Json<TeamAnnouncement>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
 
-    /// The caller is not a participant in this match, so may not invite others.
+    /// The caller is not an admin of the team.
     #[oai(status = 403)]
     Forbidden(PlainText<String>),
 
-    /// The team or match being invited to was not found.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
 
 #[derive(ApiResponse)]
-enum ListInvitationsResponse {
+enum ListTeamSeasonsResponse {
+    /// Most recent first.
     #[oai(status = 200)]
-    Invitations(Json<InvitationPage>),
+    Seasons(Json<Vec<TeamSeason>>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
 }
 
 #[derive(ApiResponse)]
-enum GetInvitationResponse {
+enum GetSeasonSquadResponse {
     #[oai(status = 200)]
-    Invitation(Json<InvitationDetail>),
+    Squad(Json<Vec<SeasonSquadMember>>),
 
+    /// No such team or season.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
 
 #[derive(ApiResponse)]
-enum RespondToInvitationResponse {
+enum GetSeasonStatsResponse {
     #[oai(status = 200)]
-    Invitation(Json<Invitation>),
+    Stats(Json<TeamSeasonStats>),
 
-    /// The invitation does not exist.
+    /// No such team or season.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
+}
 
-    /// The caller is not the user this invitation targets.
+#[derive(ApiResponse)]
+enum CreateInviteLinkResponse {
+    #[oai(status = 200)]
+    InviteLink(Json<InviteLink>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// The caller is not an admin of the team.
     #[oai(status = 403)]
     Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
 }
 
 #[derive(ApiResponse)]
-enum RespondByTokenResponse {
+enum ListInviteLinksResponse {
     #[oai(status = 200)]
-    Invitation(Json<Invitation>),
+    InviteLinks(Json<InviteLinkPage>),
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum RevokeInviteLinkResponse {
+    /// The link was revoked; its token no longer resolves.
+    #[oai(status = 204)]
+    Ok,
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum RedeemInviteLinkResponse {
+    /// The caller joined the team.
+    #[oai(status = 200)]
+    Team(Json<Team>),
+
+    /// The link requires approval: a join request was filed for the team's
+    /// admins.
+    #[oai(status = 202)]
+    JoinRequest(Json<JoinRequest>),
+
+    /// The link is expired, used up, or the caller is already a member or has
+    /// a pending request.
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// No invite link matches the supplied token (or it was revoked).
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum CreateJoinRequestResponse {
+    #[oai(status = 200)]
+    JoinRequest(Json<JoinRequest>),
+
+    /// The caller is already a member or already has a pending request.
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListJoinRequestsResponse {
+    #[oai(status = 200)]
+    JoinRequests(Json<JoinRequestPage>),
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum RespondToJoinRequestResponse {
+    #[oai(status = 200)]
+    JoinRequest(Json<JoinRequest>),
+
+    /// The caller is not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    /// No pending request from that user (or another admin already answered).
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The user has become a member some other way since asking.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum AddTeamMembersResponse {
+    #[oai(status = 200)]
+    Team(Json<Team>),
+
+    /// The caller isn't a team admin, or has a block with one of the users.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum AddInvitationsResponse {
+    #[oai(status = 200)]
+    Invitations(Json<Vec<Invitation>>),
+
+    /// The caller may not invite others: not a participant in the match, or
+    /// not an admin of the team.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    /// The team or match being invited to was not found.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// An external invitee's email address isn't one we can send to.
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListInvitationsResponse {
+    #[oai(status = 200)]
+    Invitations(Json<InvitationPage>),
+}
+
+#[derive(ApiResponse)]
+enum GetInvitationResponse {
+    #[oai(status = 200)]
+    Invitation(Json<InvitationDetail>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum RespondToInvitationResponse {
+    #[oai(status = 200)]
+    Invitation(Json<Invitation>),
+
+    /// The invitation does not exist.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The caller is not the user this invitation targets.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum RespondByTokenResponse {
+    #[oai(status = 200)]
+    Invitation(Json<Invitation>),
+
+    /// The token belongs to a team invite link, which is redeemed via
+    /// `POST /invite-links/redeem` rather than accepted/declined.
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// No invitation matches the supplied token.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum CreateClubResponse {
+    #[oai(status = 200)]
+    Club(Json<Club>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum GetClubResponse {
+    #[oai(status = 200)]
+    Club(Json<Club>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+/// Result of adding a team to, or removing it from, a club.
+#[derive(ApiResponse)]
+enum ClubTeamResponse {
+    /// The club as it now stands.
+    #[oai(status = 200)]
+    Club(Json<Club>),
+
+    /// Adding needs a club admin who is also an admin of the team; removing
+    /// needs either.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The team already belongs to a club.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum AddClubAdminResponse {
+    #[oai(status = 204)]
+    Ok,
+
+    /// The caller is not a club admin.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    /// No such club or user.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListClubMatchesResponse {
+    #[oai(status = 200)]
+    Matches(Json<MatchPage>),
+
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
 
-    /// No invitation matches the supplied token.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
@@ -1538,11 +2252,116 @@ enum FollowResponse {
     #[oai(status = 204)]
     Ok,
 
+    /// The user's profile is private: a follow request is now pending their
+    /// approval (follow only).
+    #[oai(status = 202)]
+    Requested,
+
     /// The user or team being followed was not found.
     #[oai(status = 404)]
     NotFound(PlainText<String>),
 }
 
+/// A user's answer to a request to follow them.
+#[derive(Enum, Debug)]
+#[oai(rename_all = "snake_case")]
+enum FollowRequestResponse {
+    Approved,
+    Denied,
+}
+
+#[derive(Object)]
+struct RespondToFollowRequestInput {
+    response: FollowRequestResponse,
+}
+
+#[derive(ApiResponse)]
+enum RespondToFollowRequestResponse {
+    /// The request is answered: the requester now follows you (approved) or
+    /// the request is gone (denied).
+    #[oai(status = 204)]
+    Ok,
+
+    /// No pending request from that user.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+/// Result of a block/unblock or mute/unmute action.
+#[derive(ApiResponse)]
+enum BlockResponse {
+    /// The block or mute now exists (block/mute) or no longer exists
+    /// (unblock/unmute).
+    #[oai(status = 204)]
+    Ok,
+
+    /// The user was not found.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+/// Result of reporting a comment, match, user or asset.
+#[derive(ApiResponse)]
+enum CreateReportResponse {
+    /// The report is in the moderation queue.
+    #[oai(status = 202)]
+    Accepted,
+
+    /// Reporting your own content, or a comment or header photo without its
+    /// `match_id`.
+    #[oai(status = 400)]
+    ValidationError(PlainText<String>),
+
+    /// Nothing to report: it doesn't exist or you can't see it.
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// You already reported it.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ListReportCasesResponse {
+    #[oai(status = 200)]
+    Cases(Json<ReportCasePage>),
+
+    /// The caller isn't a moderator.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum GetReportCaseResponse {
+    #[oai(status = 200)]
+    Case(Json<ReportCaseDetail>),
+
+    /// The caller isn't a moderator.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum ResolveReportResponse {
+    /// The case is closed and the action applied.
+    #[oai(status = 204)]
+    Ok,
+
+    /// The caller isn't a moderator.
+    #[oai(status = 403)]
+    Forbidden(PlainText<String>),
+
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+
+    /// The case was already resolved.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
+}
+
 #[derive(ApiResponse)]
 enum ListFollowsResponse {
     #[oai(status = 200)]
@@ -1571,6 +2390,20 @@ impl Api {
             .ok_or_else(|| Error::from_string("user not found", StatusCode::UNAUTHORIZED))
     }
 
+    /// `require_uid` for actions a suspended account may no longer take —
+    /// posting, commenting, liking, following, inviting. Costs a profile read.
+    async fn require_active_uid(&self, dao: &dao::Dao, jwt: &JwtClaims) -> Result<String> {
+        let uid = self.require_uid(dao, jwt).await?;
+        let user = dao.get_user(&uid).await.map_err(dao_internal)?;
+        if user.is_some_and(|u| u.suspended_at.is_some()) {
+            return Err(Error::from_string(
+                "your account is suspended",
+                StatusCode::FORBIDDEN,
+            ));
+        }
+        Ok(uid)
+    }
+
     #[oai(path = "/users/me", method = "get")]
     async fn get_current_user(
         &self,
@@ -1617,7 +2450,7 @@ impl Api {
     ) -> Result<UpdateUserResponse> {
         info!("Updating current user profile");
         let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
 
         // Resolve an attached asset id to its stored URL (must be Uploaded, owned
         // by the caller, and of `profile_image` purpose). Some(Some(url)) = set
@@ -1647,6 +2480,11 @@ impl Api {
             }
             other => dao_internal(other),
         })?;
+        if let Some(visibility) = input.visibility {
+            dao.set_user_visibility(&uid, visibility_to_record(visibility))
+                .await
+                .map_err(dao_internal)?;
+        }
 
         // Return the updated profile.
         let record = dao
@@ -1671,7 +2509,7 @@ impl Api {
     ) -> Result<CreateAssetResponse> {
         info!("Creating asset for content type {}", input.content_type);
         let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
 
         // Validate the content type against the purpose (images only for now).
         if !input.content_type.starts_with("image/") {
@@ -1715,21 +2553,53 @@ impl Api {
         &self,
         Data(dao): Data<&dao::Dao>,
         Data(assets): Data<&Assets>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(asset_id): Path<String>,
+        /// The match a header photo is attached to. Required to read someone
+        /// else's `match_header` asset: the caller must be able to see that
+        /// match, and the photo must be on it.
+        Query(match_id): Query<Option<String>>,
     ) -> Result<GetAssetResponse> {
         info!("Getting asset {asset_id}");
-        match dao.get_asset(&asset_id).await.map_err(dao_internal)? {
-            // Pending assets get a fresh presigned upload target on each read
-            // (the previous one may have expired) — that is the retry mechanism.
-            Some(record) => Ok(GetAssetResponse::Asset(Json(
-                asset_from_record(assets, &record).await,
-            ))),
-            None => Ok(GetAssetResponse::NotFound(PlainText(
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let not_found = || {
+            Ok(GetAssetResponse::NotFound(PlainText(
                 "asset not found".into(),
-            ))),
-        }
-    }
+            )))
+        };
+        let Some(record) = dao.get_asset(&asset_id).await.map_err(dao_internal)? else {
+            return not_found();
+        };
+        // Pending assets get a fresh presigned upload target on each read
+        // (the previous one may have expired) — that is the retry mechanism.
+        // Only their owner gets one, and only the owner or someone who can
+        // see the match gets a match header's signed URL; to anyone else the
+        // asset reads as missing.
+        if record.owner_user_id != uid {
+            if record.status != "uploaded" {
+                return not_found();
+            }
+            if record.purpose == "match_header" {
+                let Some(match_id) = &match_id else {
+                    return not_found();
+                };
+                let Some(agg) = self.visible_match(dao, match_id, &uid).await? else {
+                    return not_found();
+                };
+                let attached = agg
+                    .match_
+                    .header_photos
+                    .iter()
+                    .any(|photo| photo.asset_id == asset_id && photo.hidden_at.is_none());
+                if !attached {
+                    return not_found();
+                }
+            }
+        }
+        Ok(GetAssetResponse::Asset(Json(
+            asset_from_record(assets, &record).await,
+        )))
+    }
 
     #[oai(path = "/users/:user_id", method = "get")]
     async fn get_user(
@@ -1739,25 +2609,35 @@ impl Api {
         Path(user_id): Path<String>,
     ) -> Result<GetUserProfileResponse> {
         info!("Getting user {user_id}");
-        let record = match dao.get_user(&user_id).await.map_err(dao_internal)? {
-            Some(r) => r,
-            None => {
-                return Ok(GetUserProfileResponse::NotFound(PlainText(
-                    "user not found".into(),
-                )));
-            }
-        };
         let caller_uid = self.require_uid(dao, &jwt_data).await?;
-        let is_followed = if caller_uid == user_id {
-            false
+        let Some(record) = dao.get_user(&user_id).await.map_err(dao_internal)? else {
+            return Ok(GetUserProfileResponse::NotFound(PlainText(
+                "user not found".into(),
+            )));
+        };
+        // A profile the caller may not see still resolves, as it would embedded
+        // anywhere else (name and image, no stats) — that's what they need to
+        // ask to follow it, and to see that they already have.
+        let visible = dao
+            .can_view_user(&record, Some(&caller_uid))
+            .await
+            .map_err(dao_internal)?;
+        let (is_followed, is_requested) = if caller_uid == user_id {
+            (false, false)
         } else {
-            dao.is_following_user(&caller_uid, &user_id)
-                .await
-                .map_err(dao_internal)?
+            tokio::try_join!(
+                dao.is_following_user(&caller_uid, &user_id),
+                dao.is_follow_requested(&caller_uid, &user_id),
+            )
+            .map_err(dao_internal)?
         };
-        Ok(GetUserProfileResponse::User(Json(
-            user_profile_from_record(&record, is_followed),
-        )))
+        let mut profile = if visible {
+            user_profile_from_record(&record, is_followed)
+        } else {
+            embedded_user_profile(&record, is_followed, Some(&caller_uid))
+        };
+        profile.is_follow_requested_by_me = is_requested;
+        Ok(GetUserProfileResponse::User(Json(profile)))
     }
 
     #[oai(path = "/users", method = "post")]
@@ -1792,6 +2672,11 @@ impl Api {
             following_count: 0,
             unread_count: 0,
             stats: std::collections::HashMap::new(),
+            visibility: Default::default(),
+            is_moderator: false,
+            suspended_at: None,
+            hidden_at: None,
+            profile_image_hidden_at: None,
             created_at: now_iso(),
         };
         match dao.create_user(&jwt_data.sub, &record).await {
@@ -1826,6 +2711,7 @@ impl Api {
         // list (no cursor), so we return one page at the default limit.
         let q = agon_core::search::SearchQuery {
             q: query,
+            filter: Some(agon_core::search::Filter::visible_to(&uid)),
             limit: page_limit(None),
             ..Default::default()
         };
@@ -1840,6 +2726,212 @@ impl Api {
         Ok(SearchUsersResponse::Users(Json(profiles)))
     }
 
+    /// Search users, teams and matches at once, for a single search box.
+    /// Within each kind, people the caller follows, and teams and matches
+    /// they follow or play in, rank a little higher than relevance alone
+    /// would put them.
+    #[oai(path = "/search", method = "get")]
+    async fn search_everything(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(search): Data<&agon_core::search::SearchClient>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        #[oai(name = "q")] Query(query): Query<String>,
+        /// Hits per kind (defaults to 5, capped at 10).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<GlobalSearchResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Searching everything with query: {}", query);
+        if query.trim().is_empty() {
+            return Ok(GlobalSearchResponse::ValidationError(PlainText(
+                "`q` must not be empty".to_string(),
+            )));
+        }
+
+        // One Meilisearch round-trip for all three indexes. Each fetches
+        // more than it returns, so a boosted hit just below the cut can
+        // still make it in.
+        use agon_core::search::{Filter, Index, SearchQuery};
+        let per_kind = limit
+            .unwrap_or(GLOBAL_SEARCH_DEFAULT_PER_KIND)
+            .clamp(1, GLOBAL_SEARCH_MAX_PER_KIND);
+        let query_for = |index| {
+            (
+                index,
+                SearchQuery {
+                    q: query.clone(),
+                    filter: Some(Filter::visible_to(&uid)),
+                    limit: per_kind * GLOBAL_SEARCH_OVERFETCH,
+                    ..Default::default()
+                },
+            )
+        };
+        let results = search
+            .multi_search(
+                &[
+                    query_for(Index::Users),
+                    query_for(Index::Teams),
+                    query_for(Index::Matches),
+                ],
+                &uid,
+            )
+            .await
+            .map_err(search_internal)?;
+        let mut results = results.into_iter().map(|r| r.hits);
+        let (user_hits, team_hits, match_hits) = (
+            results.next().unwrap_or_default(),
+            results.next().unwrap_or_default(),
+            results.next().unwrap_or_default(),
+        );
+        let per_kind = per_kind as usize;
+
+        let users = async {
+            // Hydrating every candidate is what tells us who the caller
+            // follows; it's only a few more than we keep.
+            let ids: Vec<String> = user_hits.iter().map(|h| h.id.clone()).collect();
+            let mut profiles: std::collections::HashMap<String, UserProfile> = self
+                .hydrate_user_profiles(dao, &ids, Some(&uid))
+                .await?
+                .into_iter()
+                .map(|p| (p.id.clone(), p))
+                .collect();
+            let kept = boost_hits(
+                user_hits,
+                |h| profiles.get(&h.id).is_some_and(|p| p.is_followed_by_me),
+                per_kind,
+            );
+            let score = kept.first().map(|(_, score)| *score);
+            let items: Vec<UserProfile> = kept
+                .into_iter()
+                .filter_map(|(id, _)| profiles.remove(&id))
+                .collect();
+            Ok::<_, poem::Error>((score, SearchGroup::Users(UserSearchGroup { items })))
+        };
+        let teams = async {
+            let ids: Vec<String> = team_hits.iter().map(|h| h.id.clone()).collect();
+            let followed = dao
+                .batch_is_following_teams(&uid, &ids)
+                .await
+                .map_err(dao_internal)?;
+            let kept = boost_hits(
+                team_hits,
+                |h| h.involves_viewer || followed.contains(&h.id),
+                per_kind,
+            );
+            let kept_ids: Vec<String> = kept.iter().map(|(id, _)| id.clone()).collect();
+            let teams = dao
+                .batch_get_team_metas(&kept_ids)
+                .await
+                .map_err(dao_internal)?;
+            let items: Vec<TeamListItem> = kept_ids
+                .iter()
+                .filter_map(|id| teams.get(id))
+                .map(|t| team_list_item_from_record(t, followed.contains(&t.id)))
+                .collect();
+            let score = kept.first().map(|(_, score)| *score);
+            Ok((score, SearchGroup::Teams(TeamSearchGroup { items })))
+        };
+        let matches = async {
+            let kept = boost_hits(match_hits, |h| h.involves_viewer, per_kind);
+            let hits: Vec<agon_core::search::MatchSearchHit> = kept
+                .iter()
+                .map(|(id, _)| agon_core::search::MatchSearchHit {
+                    id: id.clone(),
+                    outcome: None,
+                })
+                .collect();
+            let items = self
+                .hydrate_search_matches(dao, assets, &uid, &hits)
+                .await?;
+            let score = kept.first().map(|(_, score)| *score);
+            Ok((score, SearchGroup::Matches(MatchSearchGroup { items })))
+        };
+        let (users, teams, matches) = tokio::try_join!(users, teams, matches)?;
+
+        // Best group first, by its top (boosted) hit; kinds with no hits left
+        // out.
+        let mut groups: Vec<(f64, SearchGroup)> = [users, teams, matches]
+            .into_iter()
+            .filter_map(|(score, group)| Some((score?, group)))
+            .collect();
+        groups.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(GlobalSearchResponse::Results(Json(GlobalSearchResults {
+            groups: groups.into_iter().map(|(_, group)| group).collect(),
+        })))
+    }
+
+    /// People and teams to suggest the caller follow, from who they play with
+    /// and who the people they follow follow. Computed nightly by the worker
+    /// and read as one item; anyone followed or blocked since is dropped here.
+    #[oai(path = "/suggestions", method = "get")]
+    async fn get_suggestions(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+    ) -> Result<GetSuggestionsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let record = dao
+            .get_suggestions(&uid)
+            .await
+            .map_err(dao_internal)?
+            .unwrap_or_default();
+
+        let user_ids: Vec<String> = record.users.iter().map(|u| u.user_id.clone()).collect();
+        let team_ids: Vec<String> = record.teams.iter().map(|t| t.team_id.clone()).collect();
+        let (profiles, blocked, teams, followed_teams) = tokio::try_join!(
+            self.hydrate_user_profiles(dao, &user_ids, Some(&uid)),
+            async {
+                dao.blocked_among(&uid, &user_ids)
+                    .await
+                    .map_err(dao_internal)
+            },
+            async {
+                dao.batch_get_team_metas(&team_ids)
+                    .await
+                    .map_err(dao_internal)
+            },
+            async {
+                dao.batch_is_following_teams(&uid, &team_ids)
+                    .await
+                    .map_err(dao_internal)
+            },
+        )?;
+
+        let mut profiles: std::collections::HashMap<String, UserProfile> =
+            profiles.into_iter().map(|p| (p.id.clone(), p)).collect();
+        let users = record
+            .users
+            .into_iter()
+            .filter(|s| !blocked.contains(&s.user_id))
+            .filter_map(|s| {
+                let user = profiles.remove(&s.user_id)?;
+                (!user.is_followed_by_me).then_some(SuggestedUser {
+                    user,
+                    shared_matches: s.shared_matches,
+                    mutual_follows: s.mutual_follows,
+                })
+            })
+            .collect();
+        let teams = record
+            .teams
+            .into_iter()
+            .filter(|s| !followed_teams.contains(&s.team_id))
+            .filter_map(|s| {
+                let team = teams.get(&s.team_id)?;
+                team.archived_at.is_none().then(|| SuggestedTeam {
+                    team: team_list_item_from_record(team, false),
+                    shared_matches: s.shared_matches,
+                    mutual_follows: s.mutual_follows,
+                })
+            })
+            .collect();
+        Ok(GetSuggestionsResponse::Suggestions(Json(Suggestions {
+            users,
+            teams,
+        })))
+    }
+
     #[oai(path = "/feed", method = "get")]
     async fn get_user_feed(
         &self,
@@ -1855,6 +2947,9 @@ impl Api {
         Query(from): Query<Option<chrono::DateTime<chrono::Utc>>>,
         /// Only include items at or before this time (inclusive).
         Query(to): Query<Option<chrono::DateTime<chrono::Utc>>>,
+        /// Item order (defaults to chronological). A cursor only continues
+        /// the mode it came from.
+        Query(mode): Query<Option<FeedMode>>,
     ) -> Result<GetFeedResponse> {
         info!("Getting caller's social feed");
         let uid = self.require_uid(dao, &jwt_data).await?;
@@ -1874,113 +2969,404 @@ impl Api {
             )));
         }
 
-        // Read the caller's fan-out feed partition (UFEED#<caller>), newest
-        // first. The cursor is the DAO's opaque LastEvaluatedKey (400 if
-        // malformed). Feed entries are thin pointers, so hydrate each referenced
-        // match from DynamoDB — entries never carry stale copies.
-        let page = match dao.list_feed(&uid, cursor.as_deref(), limit).await {
-            Ok(p) => p,
-            Err(dao::DaoError::Malformed(_)) => {
-                return Ok(GetFeedResponse::ValidationError(PlainText(
-                    "Invalid cursor".to_string(),
-                )));
+        let (entries, next_cursor) = match mode {
+            Some(FeedMode::Ranked) => {
+                // Pages come from one stored ranking (see `rank_feed`), so
+                // re-scoring between requests can't repeat or skip entries.
+                // Asking for a first page again replaces the ranking, after
+                // which an older cursor is rejected rather than served from
+                // the new order.
+                let (ranking, offset) = match cursor.as_deref() {
+                    None => (self.rank_feed(dao, &uid, from, to).await?, 0),
+                    Some(raw) => {
+                        let ranking = dao.get_ranked_feed(&uid).await.map_err(dao_internal)?;
+                        let Some(found) = ranking.and_then(|r| {
+                            ranked_feed_offset(raw, &r.ranking_id).map(|offset| (r, offset))
+                        }) else {
+                            return Ok(GetFeedResponse::ValidationError(PlainText(
+                                "Invalid or expired cursor; start again from the first page"
+                                    .to_string(),
+                            )));
+                        };
+                        found
+                    }
+                };
+                let end = (offset + limit as usize).min(ranking.entries.len());
+                let slice = &ranking.entries[offset.min(end)..end];
+                let mut found = dao
+                    .batch_get_feed_items(&uid, slice)
+                    .await
+                    .map_err(dao_internal)?;
+                let entries = slice.iter().filter_map(|e| found.remove(e)).collect();
+                let next_cursor = (end < ranking.entries.len())
+                    .then(|| ranked_feed_cursor(end, &ranking.ranking_id));
+                (entries, next_cursor)
             }
-            Err(e) => return Err(dao_internal(e)),
+            Some(FeedMode::Chronological) | None => {
+                // Read the caller's fan-out feed partition (UFEED#<caller>), newest
+                // first. The cursor is the DAO's opaque LastEvaluatedKey (400 if
+                // malformed). Feed entries are thin pointers, so hydrate each referenced
+                // match from DynamoDB — entries never carry stale copies.
+                let page = match dao.list_feed(&uid, cursor.as_deref(), limit).await {
+                    Ok(p) => p,
+                    Err(dao::DaoError::Malformed(_)) => {
+                        return Ok(GetFeedResponse::ValidationError(PlainText(
+                            "Invalid cursor".to_string(),
+                        )));
+                    }
+                    Err(e) => return Err(dao_internal(e)),
+                };
+                (page.items, page.next_cursor)
+            }
+        };
+
+        let items = self
+            .hydrate_feed_entries(dao, assets, &uid, &entries, from, to)
+            .await?;
+        Ok(GetFeedResponse::Feed(Json(FeedPage { items, next_cursor })))
+    }
+
+    /// Rank the caller's newest `RANKED_FEED_WINDOW` feed entries starting
+    /// in `[from, to]` (see `agon_core::feed_rank`), and store the order as
+    /// their ranked feed for later pages to read. Matches are scored on their
+    /// live engagement; results, milestones, announcements and joins on their
+    /// own time alone.
+    async fn rank_feed(
+        &self,
+        dao: &dao::Dao,
+        uid: &str,
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<dao::records::RankedFeedRecord> {
+        let page = dao
+            .list_feed(uid, None, RANKED_FEED_WINDOW)
+            .await
+            .map_err(dao_internal)?;
+        let window: Vec<_> = page
+            .items
+            .into_iter()
+            .filter(|e| within_range(&e.starts_at, from, to))
+            .collect();
+        // Engagement and status are live on the match, not on the entry.
+        let match_ids: Vec<String> = window
+            .iter()
+            .filter(|e| e.ref_type == dao::records::FeedRefType::Match)
+            .map(|e| e.ref_id.clone())
+            .collect();
+        let metas = dao
+            .batch_get_match_metas(&match_ids)
+            .await
+            .map_err(dao_internal)?;
+
+        let now = chrono::Utc::now();
+        let scored = window
+            .into_iter()
+            .filter_map(|entry| {
+                let signals = if entry.ref_type == dao::records::FeedRefType::Match {
+                    let m = metas.get(&entry.ref_id)?;
+                    agon_core::feed_rank::FeedSignals {
+                        starts_at: m.starts_at.parse().ok()?,
+                        is_own: entry.viewer_side_id.is_some(),
+                        known_player_count: entry.known_player_count,
+                        reactions: m.like_count
+                            + m.fire_count
+                            + m.clap_count
+                            + m.laugh_count
+                            + m.wow_count,
+                        comments: m.comment_count,
+                        live: m.status == "in_progress",
+                    }
+                } else {
+                    agon_core::feed_rank::FeedSignals::at(entry.starts_at.parse().ok()?)
+                };
+                let key = dao::records::RankedFeedEntryRecord {
+                    starts_at: entry.starts_at,
+                    ref_id: entry.ref_id,
+                };
+                Some((key, signals))
+            })
+            .collect();
+        let ranking = dao::records::RankedFeedRecord {
+            viewer_id: uid.to_string(),
+            ranking_id: new_id(),
+            ranked_at: now_iso(),
+            entries: agon_core::feed_rank::rank(scored, now),
         };
+        dao.put_ranked_feed(&ranking).await.map_err(dao_internal)?;
+        Ok(ranking)
+    }
 
-        // (match_id, known_player_ids) for entries that pass the date filter —
-        // `known_player_ids`/`viewer_side_id` are already on the feed entry
-        // (denormalized at fan-out time), never fetched here.
-        struct EligibleEntry {
-            match_id: String,
+    /// Hydrate a page of the caller's feed entries into feed items, in order,
+    /// dropping any outside `[from, to]`, involving someone hidden from the
+    /// caller, or whose match (or milestone, announcement, team) is gone. A
+    /// result card whose match no longer has a confirmed result drops too —
+    /// its rows are pruned on the match's next fan-out. Shared by both feed
+    /// modes.
+    async fn hydrate_feed_entries(
+        &self,
+        dao: &dao::Dao,
+        assets: &Assets,
+        uid: &str,
+        entries: &[dao::records::FeedItemRecord],
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<FeedItem>> {
+        use dao::records::FeedRefType;
+
+        // Entries that pass the date filter, with `known_player_ids` already
+        // stripped of hidden users — `known_player_ids`/`viewer_side_id` are
+        // on the feed entry (denormalized at fan-out time), never fetched
+        // here. Only meaningful for match and result entries.
+        struct EligibleEntry<'a> {
+            entry: &'a dao::records::FeedItemRecord,
             known_player_ids: Vec<String>,
             known_player_count: u32,
-            viewer_side_id: Option<String>,
         }
-        let mut eligible: Vec<EligibleEntry> = Vec::with_capacity(page.items.len());
-        for entry in &page.items {
-            // Apply the optional date range on the entry's start time (cheap;
-            // avoids hydrating matches outside the window).
+        // Users the caller muted or has a block with: matches they organized
+        // drop out (unless the caller plays in them), as do their milestones,
+        // announcements and joins, and they don't count as known
+        // participants. `known_player_count` is only corrected for the ids on
+        // the entry; any beyond `MAX_KNOWN_PLAYERS` can't be told apart.
+        let hidden = dao.hidden_user_ids(uid).await.map_err(dao_internal)?;
+        let mut eligible: Vec<EligibleEntry> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            // Apply the optional date range on the entry's sort time (cheap;
+            // avoids hydrating anything outside the window).
             if !within_range(&entry.starts_at, from, to) {
                 continue;
             }
-            // Currently every feed entry references a match.
-            if entry.ref_type != "match" {
-                continue;
-            }
+            let known_player_ids: Vec<String> = entry
+                .known_player_ids
+                .iter()
+                .filter(|id| !hidden.contains(*id))
+                .cloned()
+                .collect();
+            let dropped = (entry.known_player_ids.len() - known_player_ids.len()) as u32;
             eligible.push(EligibleEntry {
-                match_id: entry.ref_id.clone(),
-                known_player_ids: entry.known_player_ids.clone(),
-                known_player_count: entry.known_player_count,
-                viewer_side_id: entry.viewer_side_id.clone(),
+                entry,
+                known_player_ids,
+                known_player_count: entry.known_player_count.saturating_sub(dropped),
             });
         }
-        let match_ids: Vec<String> = eligible.iter().map(|e| e.match_id.clone()).collect();
+
+        // What each kind of entry points at. A match and its result card
+        // share the one match lookup.
+        let mut match_ids: Vec<String> = Vec::new();
+        let mut milestone_ids: Vec<String> = Vec::new();
+        let mut announcement_keys: Vec<(String, String)> = Vec::new();
+        let mut membership_keys: Vec<(String, String)> = Vec::new();
+        for e in &eligible {
+            let entry = e.entry;
+            match entry.ref_type {
+                FeedRefType::Match | FeedRefType::Result => match_ids.push(entry.ref_id.clone()),
+                FeedRefType::Milestone => milestone_ids.push(entry.ref_id.clone()),
+                FeedRefType::TeamAnnouncement => {
+                    if let Some(team_id) = &entry.subject_id {
+                        announcement_keys.push((team_id.clone(), entry.ref_id.clone()));
+                    }
+                }
+                FeedRefType::MemberJoined => {
+                    if let Some(team_id) = &entry.subject_id {
+                        membership_keys.push((team_id.clone(), entry.ref_id.clone()));
+                    }
+                }
+            }
+        }
 
         // Hydrate every referenced match's meta + sides (never players — the
-        // feed doesn't render the full roster, see `FeedMatch`) and this
-        // viewer's likes, in two round-trips total, run concurrently. Neither
-        // call's DB request count grows with page size.
-        let (summaries, liked) = tokio::try_join!(
+        // feed doesn't render the full roster, see `FeedMatch`), this viewer's
+        // reactions, and the page's milestones, announcements and
+        // memberships, in one concurrent round of batch reads. None of them
+        // grows its request count with page size.
+        let (summaries, reactions, milestones, announcements, memberships) = tokio::try_join!(
             dao.batch_get_match_summaries(&match_ids),
-            dao.batch_has_liked_matches(&match_ids, &uid),
+            dao.batch_get_match_reactions(&match_ids, uid),
+            dao.batch_get_milestones(&milestone_ids),
+            dao.batch_get_team_announcements(&announcement_keys),
+            dao.batch_get_team_members(&membership_keys),
         )
         .map_err(dao_internal)?;
 
         // One more round-trip: every user this page needs to hydrate by name/
-        // avatar — the union of each entry's `known_participants` and each
+        // avatar — the union of each entry's `known_participants`, each
         // match's small per-side `roster_preview`s (both id-only; see
         // `MatchSideRecord::roster_preview`'s doc comment on why a live name/
-        // avatar beats a possibly-stale cached one). `batch_get_users` chunks
-        // internally if the union happens to exceed one `BatchGetItem`, but
-        // page size is capped specifically so it normally won't need to.
+        // avatar beats a possibly-stale cached one), and whoever reached a
+        // milestone, posted an announcement or joined a team.
+        // `batch_get_users` chunks internally if the union happens to exceed
+        // one `BatchGetItem`, but page size is capped specifically so it
+        // normally won't need to.
         let mut user_ids: Vec<String> = Vec::new();
-        for entry in &eligible {
-            user_ids.extend(entry.known_player_ids.iter().cloned());
+        for e in &eligible {
+            user_ids.extend(e.known_player_ids.iter().cloned());
         }
         for summary in summaries.values() {
             for side in &summary.sides {
                 user_ids.extend(side.roster_preview.iter().filter_map(|p| p.user_id.clone()));
             }
         }
+        let card_user_ids: Vec<String> = milestones
+            .values()
+            .map(|m| m.user_id.clone())
+            .chain(announcements.values().map(|a| a.author_id.clone()))
+            .chain(memberships.values().filter_map(|m| m.user_id.clone()))
+            .collect();
+        user_ids.extend(card_user_ids.iter().cloned());
         let users = dao.batch_get_users(&user_ids).await.map_err(dao_internal)?;
 
-        // Side names need the same team-name fallback `Match` gets (via
-        // `hydrate_matches`) — without it a team-linked side with no custom
-        // name would render blank. `roster_preview` already covers the
-        // "sole player's name" case, so only team names need a batch fetch.
-        let team_ids: Vec<String> = summaries
+        // Teams: for side names (the same team-name fallback `Match` gets via
+        // `hydrate_matches` — without it a team-linked side with no custom
+        // name would render blank; `roster_preview` already covers the "sole
+        // player's name" case) and for the announcement and member-joined
+        // cards, with whether the caller follows them — and the same for the
+        // people on the non-match cards.
+        let mut team_ids: Vec<String> = summaries
             .values()
             .flat_map(|s| s.sides.iter().filter_map(|s| s.team_id.clone()))
             .collect();
-        let team_names = self.batch_team_names(dao, &team_ids).await?;
+        team_ids.extend(announcements.values().map(|a| a.team_id.clone()));
+        team_ids.extend(memberships.values().map(|m| m.team_id.clone()));
+        let (teams, followed_teams, followed_users) = tokio::try_join!(
+            dao.batch_get_team_metas(&team_ids),
+            dao.batch_is_following_teams(uid, &team_ids),
+            dao.batch_is_following_users(uid, &card_user_ids),
+        )
+        .map_err(dao_internal)?;
+        let team_names: std::collections::HashMap<String, String> = teams
+            .iter()
+            .map(|(id, t)| (id.clone(), t.name.clone()))
+            .collect();
+        // A live, unarchived team's list item, for the team cards.
+        let team_item = |team_id: &str| {
+            teams
+                .get(team_id)
+                .filter(|t| t.archived_at.is_none())
+                .map(|t| team_list_item_from_record(t, followed_teams.contains(&t.id)))
+        };
+        // A user the caller may see, as shown on the non-match cards.
+        let card_user = |user_id: &str| {
+            users
+                .get(user_id)
+                .filter(|u| u.suspended_at.is_none() && !hidden.contains(user_id))
+                .map(|u| embedded_user_profile(u, followed_users.contains(&u.id), Some(uid)))
+        };
 
-        let mut built: Vec<FeedMatch> = Vec::with_capacity(eligible.len());
-        for entry in &eligible {
-            if let Some(summary) = summaries.get(&entry.match_id) {
-                let i_liked = liked.contains(&entry.match_id);
-                let known_participants = entry
-                    .known_player_ids
-                    .iter()
-                    .filter_map(|uid| users.get(uid))
-                    .map(|record| user_profile_from_record(record, true))
-                    .collect();
-                let mut m = feed_match_from_records(
-                    &summary.match_,
-                    &summary.sides,
-                    &users,
-                    known_participants,
-                    entry.known_player_count,
-                    entry.viewer_side_id.clone(),
-                    i_liked,
-                );
-                Self::resolve_side_names_from_cache(
-                    &mut m.sides,
-                    entry.viewer_side_id.as_deref(),
-                    &team_names,
-                );
-                sign_feed_match_headers(assets, &mut m);
-                built.push(m);
+        let mut built: Vec<FeedItem> = Vec::with_capacity(eligible.len());
+        for e in &eligible {
+            let entry = e.entry;
+            match entry.ref_type {
+                FeedRefType::Match | FeedRefType::Result => {
+                    let Some(summary) = summaries.get(&entry.ref_id) else {
+                        continue;
+                    };
+                    if entry.viewer_side_id.is_none()
+                        && hidden.contains(&summary.match_.created_by_user_id)
+                    {
+                        continue;
+                    }
+                    let is_result = entry.ref_type == FeedRefType::Result;
+                    // The result this card was fanned out for — gone if the
+                    // match was since cancelled, or rescored (its new result
+                    // has its own card).
+                    let confirmed_at = summary
+                        .match_
+                        .confirmed_score
+                        .as_ref()
+                        .and_then(|cs| cs.confirmed_at.as_deref())
+                        .filter(|_| summary.match_.status != "cancelled");
+                    if is_result && confirmed_at != Some(entry.starts_at.as_str()) {
+                        continue;
+                    }
+                    let my_reaction = reactions.get(&entry.ref_id).copied();
+                    let known_participants = e
+                        .known_player_ids
+                        .iter()
+                        .filter_map(|id| users.get(id))
+                        .map(|record| embedded_user_profile(record, true, Some(uid)))
+                        .collect();
+                    let mut m = feed_match_from_records(
+                        &summary.match_,
+                        &summary.sides,
+                        &users,
+                        known_participants,
+                        e.known_player_count,
+                        entry.viewer_side_id.clone(),
+                        my_reaction,
+                    );
+                    Self::resolve_side_names_from_cache(
+                        &mut m.sides,
+                        entry.viewer_side_id.as_deref(),
+                        &team_names,
+                    );
+                    sign_feed_match_headers(assets, &mut m);
+                    built.push(if is_result {
+                        FeedItem::Result(FeedResult {
+                            match_: m,
+                            confirmed_at: mapping::parse_ts(&entry.starts_at),
+                        })
+                    } else {
+                        FeedItem::Match(m)
+                    });
+                }
+                FeedRefType::Milestone => {
+                    let Some(milestone) = milestones.get(&entry.ref_id) else {
+                        continue;
+                    };
+                    let Some(user) = card_user(&milestone.user_id) else {
+                        continue;
+                    };
+                    built.push(FeedItem::Milestone(FeedMilestone {
+                        id: milestone.id.clone(),
+                        user,
+                        match_type: mapping::match_type_from_tag(&milestone.sport),
+                        stat: match milestone.stat {
+                            dao::records::MilestoneStat::MatchesPlayed => {
+                                MilestoneStat::MatchesPlayed
+                            }
+                            dao::records::MilestoneStat::Wins => MilestoneStat::Wins,
+                        },
+                        count: milestone.count,
+                        reached_at: mapping::parse_ts(&milestone.reached_at),
+                    }));
+                }
+                FeedRefType::TeamAnnouncement => {
+                    let Some(announcement) = announcements.get(&entry.ref_id) else {
+                        continue;
+                    };
+                    let (Some(team), Some(author)) = (
+                        team_item(&announcement.team_id),
+                        card_user(&announcement.author_id),
+                    ) else {
+                        continue;
+                    };
+                    built.push(FeedItem::TeamAnnouncement(FeedTeamAnnouncement {
+                        id: announcement.id.clone(),
+                        team,
+                        author,
+                        body: announcement.body.clone(),
+                        created_at: mapping::parse_ts(&announcement.created_at),
+                    }));
+                }
+                FeedRefType::MemberJoined => {
+                    // A membership since ended no longer makes news.
+                    let Some(membership) = memberships
+                        .get(&entry.ref_id)
+                        .filter(|m| m.left_at.is_none())
+                    else {
+                        continue;
+                    };
+                    let Some(user) = membership.user_id.as_deref().and_then(card_user) else {
+                        continue;
+                    };
+                    let Some(team) = team_item(&membership.team_id) else {
+                        continue;
+                    };
+                    built.push(FeedItem::MemberJoined(FeedMemberJoined {
+                        team,
+                        user,
+                        joined_at: mapping::parse_ts(&entry.starts_at),
+                    }));
+                }
             }
         }
 
@@ -1990,17 +3376,16 @@ impl Api {
         // `hydrate_confirmed_pending_score_players`'s doc comment).
         let mut score_refs: Vec<_> = built
             .iter_mut()
+            .filter_map(|item| match item {
+                FeedItem::Match(m) | FeedItem::Result(FeedResult { match_: m, .. }) => Some(m),
+                _ => None,
+            })
             .map(|m| (m.id.as_str(), &mut m.confirmed_score, &mut m.pending_score))
             .collect();
         self.hydrate_confirmed_pending_score_players(dao, &mut score_refs)
             .await?;
 
-        let items = built.into_iter().map(FeedItem::Match).collect();
-
-        Ok(GetFeedResponse::Feed(Json(FeedPage {
-            items,
-            next_cursor: page.next_cursor,
-        })))
+        Ok(built)
     }
 
     #[oai(path = "/matches", method = "get")]
@@ -2025,6 +3410,17 @@ impl Api {
         Query(cursor): Query<Option<String>>,
         /// Maximum number of items to return (defaults to 20, capped at 50).
         Query(limit): Query<Option<u32>>,
+        /// Only matches in this state.
+        Query(status): Query<Option<MatchStatus>>,
+        /// Only matches this team played in.
+        Query(team): Query<Option<String>>,
+        /// Only matches in this league.
+        Query(league): Query<Option<String>>,
+        /// With `near_lng`: only matches within `within_m` of this point.
+        Query(near_lat): Query<Option<f64>>,
+        Query(near_lng): Query<Option<f64>>,
+        /// Radius around `near_lat`/`near_lng`, in meters (defaults to 25 km).
+        Query(within_m): Query<Option<u32>>,
     ) -> Result<ListMatchesResponse> {
         info!("Searching matches");
         let caller_uid = self.require_uid(dao, &jwt_data).await?;
@@ -2042,6 +3438,25 @@ impl Api {
             )));
         }
 
+        let near = match (near_lat, near_lng) {
+            (None, None) => None,
+            (Some(lat), Some(lng))
+                if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) =>
+            {
+                Some((lat, lng))
+            }
+            (Some(_), Some(_)) => {
+                return Ok(ListMatchesResponse::ValidationError(PlainText(
+                    "`near_lat`/`near_lng` out of range".to_string(),
+                )));
+            }
+            _ => {
+                return Ok(ListMatchesResponse::ValidationError(PlainText(
+                    "`near_lat` and `near_lng` go together".to_string(),
+                )));
+            }
+        };
+
         let offset = match search_offset(cursor.as_deref()) {
             Ok(o) => o,
             Err(()) => {
@@ -2053,30 +3468,53 @@ impl Api {
 
         // Build the Meilisearch filter from the supplied facets. The date range
         // filters on `starts_at_ts` (a numeric Unix timestamp), because
-        // Meilisearch's `>=` / `<=` operators are numeric-only — they can't
-        // compare the ISO-8601 `starts_at` string (that raised
+        // Meilisearch's range operators are numeric-only — they can't compare
+        // the ISO-8601 `starts_at` string (that raised
         // `invalid_search_filter: invalid float literal`).
-        let mut clauses: Vec<String> = Vec::new();
+        use agon_core::search::Filter;
+        let mut filter = Filter::visible_to(&caller_uid);
         if let Some(mt) = &match_type {
-            clauses.push(format!("sport = \"{}\"", match_type_tag(mt)));
+            filter = filter.and(Filter::eq("sport", match_type_tag(mt)));
         }
         if let Some(p) = &participant {
-            clauses.push(format!("participant_ids = \"{p}\""));
+            filter = filter.and(Filter::eq("participant_ids", p.as_str()));
         }
-        if let Some(from) = from {
-            clauses.push(format!("starts_at_ts >= {}", from.timestamp()));
+        if let Some(s) = &status {
+            filter = filter.and(Filter::eq("status", match_status_str(s)));
+        }
+        if let Some(t) = &team {
+            filter = filter.and(Filter::eq("team_ids", t.as_str()));
+        }
+        if let Some(l) = &league {
+            filter = filter.and(Filter::eq("league", l.as_str()));
+        }
+        if let Some((lat, lng)) = near {
+            filter = filter.and(Filter::GeoRadius {
+                lat,
+                lng,
+                meters: within_m.unwrap_or(DEFAULT_NEAR_RADIUS_M),
+            });
         }
-        if let Some(to) = to {
-            clauses.push(format!("starts_at_ts <= {}", to.timestamp()));
+        if from.is_some() || to.is_some() {
+            filter = filter.and(Filter::range(
+                "starts_at_ts",
+                from.map(|t| t.timestamp()),
+                to.map(|t| t.timestamp()),
+            ));
         }
-        let filter = (!clauses.is_empty()).then(|| clauses.join(" AND "));
 
         let q = agon_core::search::SearchQuery {
             q: query.unwrap_or_default(),
-            filter,
+            filter: Some(filter),
             sort: vec!["starts_at_ts:desc".to_string()],
             offset,
             limit: page_limit(limit),
+            // Later pages reuse the chips the first page returned.
+            facets: if offset == 0 {
+                vec!["sport", "status", "team_ids", "league"]
+            } else {
+                vec![]
+            },
         };
         // `participant` doubles as the outcome subject: the same id already
         // scoping the filter is who `search_matches` resolves won/lost/draw
@@ -2085,70 +3523,50 @@ impl Api {
             .search_matches(&q, participant.as_deref())
             .await
             .map_err(search_internal)?;
-        let match_ids: Vec<String> = hits.items.iter().map(|h| h.id.clone()).collect();
-
-        // Hydrate each match's meta + sides (never players — search results
-        // don't render the full roster, see `SearchMatch`) and this caller's
-        // like state, in two round-trips total instead of a `get_match` +
-        // `has_liked_match` per hit.
-        let (summaries, liked) = tokio::try_join!(
-            dao.batch_get_match_summaries(&match_ids),
-            dao.batch_has_liked_matches(&match_ids, &caller_uid),
-        )
-        .map_err(dao_internal)?;
-
-        // Side `roster_preview` entries' live name/avatar, and team names for
-        // the side-name fallback chain (same two batch reads the feed makes;
-        // no per-viewer `known_participants` here, so no user-id union from
-        // that source).
-        let mut user_ids: Vec<String> = Vec::new();
-        for summary in summaries.values() {
-            for side in &summary.sides {
-                user_ids.extend(side.roster_preview.iter().filter_map(|p| p.user_id.clone()));
-            }
-        }
-        let team_ids: Vec<String> = summaries
-            .values()
-            .flat_map(|s| s.sides.iter().filter_map(|s| s.team_id.clone()))
-            .collect();
-        let (users, team_names) = tokio::try_join!(
-            async { dao.batch_get_users(&user_ids).await.map_err(dao_internal) },
-            async { self.batch_team_names(dao, &team_ids).await },
+        let facet_teams = hits
+            .facets
+            .get("team_ids")
+            .map(|counts| top_facet_values(counts, MATCH_TEAM_FACET_CAP))
+            .unwrap_or_default();
+        let facet_team_ids: Vec<String> = facet_teams.iter().map(|(id, _)| id.clone()).collect();
+        let (items, team_names) = tokio::try_join!(
+            self.hydrate_search_matches(dao, assets, &caller_uid, &hits.items),
+            self.batch_team_names(dao, &facet_team_ids),
         )?;
 
-        let mut items: Vec<SearchMatch> = Vec::with_capacity(hits.items.len());
-        for hit in &hits.items {
-            if let Some(summary) = summaries.get(&hit.id) {
-                let i_liked = liked.contains(&hit.id);
-                let mut m = search_match_from_records(
-                    &summary.match_,
-                    &summary.sides,
-                    &users,
-                    hit.outcome,
-                    i_liked,
-                );
-                // No per-viewer `viewer_side_id` for a search hit, so no
-                // "Your side"/"Opposition" — falls to team name / Team A/B.
-                Self::resolve_side_names_from_cache(&mut m.sides, None, &team_names);
-                sign_search_match_headers(assets, &mut m);
-                items.push(m);
+        let facets = (offset == 0).then(|| {
+            let counts = |attr: &str| {
+                hits.facets
+                    .get(attr)
+                    .map(|c| top_facet_values(c, usize::MAX))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(value, count)| FacetCount {
+                        value,
+                        label: None,
+                        count,
+                    })
+                    .collect()
+            };
+            MatchFacets {
+                sports: counts("sport"),
+                statuses: counts("status"),
+                leagues: counts("league"),
+                teams: facet_teams
+                    .into_iter()
+                    .map(|(value, count)| FacetCount {
+                        label: team_names.get(&value).cloned(),
+                        value,
+                        count,
+                    })
+                    .collect(),
             }
-        }
-
-        // `confirmed_score`/`pending_score`'s scorer/batter names — same
-        // batched-across-the-page treatment as everything else here, not a
-        // per-match live-score fetch (see
-        // `hydrate_confirmed_pending_score_players`'s doc comment).
-        let mut score_refs: Vec<_> = items
-            .iter_mut()
-            .map(|m| (m.id.as_str(), &mut m.confirmed_score, &mut m.pending_score))
-            .collect();
-        self.hydrate_confirmed_pending_score_players(dao, &mut score_refs)
-            .await?;
+        });
 
         Ok(ListMatchesResponse::Matches(Json(MatchPage {
             items,
             next_cursor: search_cursor(hits.next_offset),
+            facets,
         })))
     }
 
@@ -2162,7 +3580,7 @@ impl Api {
     ) -> Result<CreateMatchResponse> {
         info!("Creating match {}", input.name);
         let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
 
         // Resolve any header images to asset id + stored URL (must be
         // uploaded, owned by the caller, and of `match_header` purpose).
@@ -2171,7 +3589,11 @@ impl Api {
             match resolve_asset_urls(dao, &uid, "match_header", &header_asset_ids).await? {
                 Ok(resolved) => resolved
                     .into_iter()
-                    .map(|(asset_id, url)| dao::records::HeaderPhotoRecord { asset_id, url })
+                    .map(|(asset_id, url)| dao::records::HeaderPhotoRecord {
+                        asset_id,
+                        url,
+                        hidden_at: None,
+                    })
                     .collect::<Vec<_>>(),
                 Err(msg) => return Ok(CreateMatchResponse::ValidationError(PlainText(msg))),
             };
@@ -2204,6 +3626,34 @@ impl Api {
             }
         }
 
+        // Archived teams keep their past matches but can't take on new ones.
+        // Unless the caller picks one, the match is as visible as its most
+        // restrictive team, so a junior side's fixtures stay private.
+        let side_team_ids: Vec<String> = input
+            .sides
+            .iter()
+            .filter_map(|s| s.team_id.clone())
+            .collect();
+        let mut visibility = dao::records::Visibility::Public;
+        if !side_team_ids.is_empty() {
+            let teams = dao
+                .batch_get_team_metas(&side_team_ids)
+                .await
+                .map_err(dao_internal)?;
+            if let Some(team) = teams.values().find(|t| t.archived_at.is_some()) {
+                return Ok(CreateMatchResponse::ValidationError(PlainText(format!(
+                    "team `{}` is archived",
+                    team.name
+                ))));
+            }
+            if let Some(strictest) = teams.values().map(|t| t.visibility).max() {
+                visibility = strictest;
+            }
+        }
+        if let Some(chosen) = input.visibility {
+            visibility = visibility_to_record(chosen);
+        }
+
         // A supplied format must be for this match's own sport — a football
         // match can't carry cricket's overs-per-innings setting, say.
         if let Some(fmt) = &input.format {
@@ -2234,6 +3684,19 @@ impl Api {
             )));
         }
 
+        // A guest's email has to be one we can send their link to.
+        if let Some(bad) = input
+            .invites
+            .iter()
+            .flat_map(|i| &i.invited_externals)
+            .filter_map(|e| e.email.as_deref())
+            .find(|email| !agon_core::email::is_plausible_address(email))
+        {
+            return Ok(CreateMatchResponse::ValidationError(PlainText(format!(
+                "{bad:?} is not a valid email address"
+            ))));
+        }
+
         let now = now_iso();
         let match_id = new_id();
 
@@ -2301,6 +3764,16 @@ impl Api {
                 invitation: None,
             });
         }
+        let invited_user_ids: Vec<String> = input
+            .invites
+            .iter()
+            .flat_map(|invite| invite.invited_user_ids.iter().cloned())
+            .collect();
+        if self.any_blocked(dao, &uid, &invited_user_ids).await? {
+            return Ok(CreateMatchResponse::ValidationError(PlainText(
+                BLOCKED_INVITEE.into(),
+            )));
+        }
         for invite in &input.invites {
             let side_id = invite
                 .side_client_id
@@ -2321,7 +3794,7 @@ impl Api {
                 invitation_records.push(inv);
             }
             for external in &invite.invited_externals {
-                let (player, inv) = build_invited_player(
+                let (player, mut inv) = build_invited_player(
                     &match_id,
                     &input.name,
                     &uid,
@@ -2330,6 +3803,7 @@ impl Api {
                     Some(external.name.clone()),
                     &now,
                 );
+                inv.invitee_email = external.email.clone();
                 player_ids.insert(external.client_id.clone(), player.player_id.clone());
                 player_records.push(player);
                 invitation_records.push(inv);
@@ -2452,14 +3926,24 @@ impl Api {
                 latitude: l.latitude,
                 longitude: l.longitude,
             }),
+            venue: input.venue.as_deref().and_then(non_blank),
+            league: input.league.as_deref().and_then(non_blank),
             header_photos,
             sides,
             confirmed_score: None,
             pending_score,
             like_count: 0,
+            fire_count: 0,
+            clap_count: 0,
+            laugh_count: 0,
+            wow_count: 0,
             comment_count: 0,
             live_seq: 0,
             format: input.format.as_ref().map(match_format_to_record),
+            cancellation: None,
+            postponements: Vec::new(),
+            visibility,
+            hidden_at: None,
             created_at: now.clone(),
         };
 
@@ -2492,7 +3976,7 @@ impl Api {
             match_record.sides.values().cloned().collect();
         sides_for_response.sort_by(|a, b| a.side_id.cmp(&b.side_id));
 
-        let mut m = match_from_records(&match_record, &sides_for_response, &player_records, false);
+        let mut m = match_from_records(&match_record, &sides_for_response, &player_records, None);
         sign_match_headers(assets, &mut m);
         let m = self.hydrate_match(dao, m, &uid).await?;
         Ok(CreateMatchResponse::Match(Json(m)))
@@ -2508,7 +3992,7 @@ impl Api {
     ) -> Result<GetMatchResponse> {
         info!("Getting match {match_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
+        let agg = match self.visible_match(dao, &match_id, &uid).await? {
             Some(a) => a,
             None => {
                 return Ok(GetMatchResponse::NotFound(PlainText(
@@ -2516,11 +4000,12 @@ impl Api {
                 )));
             }
         };
-        let i_liked = dao
-            .has_liked_match(&match_id, &uid)
+        let my_reaction = dao
+            .get_match_reaction(&match_id, &uid)
             .await
-            .map_err(dao_internal)?;
-        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, i_liked);
+            .map_err(dao_internal)?
+            .map(|r| r.reaction);
+        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, my_reaction);
         sign_match_headers(assets, &mut m);
         let m = self.hydrate_match(dao, m, &uid).await?;
         Ok(GetMatchResponse::Match(Json(m)))
@@ -2537,7 +4022,7 @@ impl Api {
     ) -> Result<UpdateMatchResponse> {
         info!("Updating match {match_id}");
         let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
 
         // Load the current state (404 if missing).
         let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
@@ -2550,10 +4035,10 @@ impl Api {
         };
 
         // The creator (organizer) or a participant may edit the match.
-        if !caller_can_manage_match(&agg, &uid) {
-            return Ok(UpdateMatchResponse::Forbidden(PlainText(
-                "only a participant can edit this match".into(),
-            )));
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::Update)
+        {
+            return Ok(UpdateMatchResponse::Forbidden(denied.into()));
         }
 
         if let Some(fmt) = &input.format {
@@ -2583,20 +4068,33 @@ impl Api {
         // set each time (there's no append/remove-by-id on the server), so a
         // client that wants to keep existing photos re-sends their asset ids
         // (from `Match.header_photos[].asset_id`) alongside any new ones.
-        let header_photos: Option<Vec<dao::records::HeaderPhotoRecord>> = match &input
-            .header_photo_asset_ids
-        {
-            Some(ids) => match resolve_asset_urls(dao, &uid, "match_header", ids).await? {
-                Ok(resolved) => Some(
-                    resolved
-                        .into_iter()
-                        .map(|(asset_id, url)| dao::records::HeaderPhotoRecord { asset_id, url })
-                        .collect(),
-                ),
-                Err(msg) => return Ok(UpdateMatchResponse::ValidationError(PlainText(msg))),
-            },
-            None => None,
-        };
+        let header_photos: Option<Vec<dao::records::HeaderPhotoRecord>> =
+            match &input.header_photo_asset_ids {
+                Some(ids) => match resolve_asset_urls(dao, &uid, "match_header", ids).await? {
+                    Ok(resolved) => Some(
+                        resolved
+                            .into_iter()
+                            .map(|(asset_id, url)| {
+                                // A photo hidden pending review stays hidden if
+                                // it's kept.
+                                let hidden_at = agg
+                                    .match_
+                                    .header_photos
+                                    .iter()
+                                    .find(|photo| photo.asset_id == asset_id)
+                                    .and_then(|photo| photo.hidden_at.clone());
+                                dao::records::HeaderPhotoRecord {
+                                    asset_id,
+                                    url,
+                                    hidden_at,
+                                }
+                            })
+                            .collect(),
+                    ),
+                    Err(msg) => return Ok(UpdateMatchResponse::ValidationError(PlainText(msg))),
+                },
+                None => None,
+            };
 
         // A cancelled match can't be scored.
         let resulting_status = input
@@ -2887,6 +4385,10 @@ impl Api {
             .map(|r| (r.side_id.clone(), r.name.clone()))
             .collect();
 
+        // Blank clears: `Some(None)`.
+        let venue = input.venue.as_deref().map(non_blank);
+        let league = input.league.as_deref().map(non_blank);
+
         // Apply metadata + resolved score + side renames in one update.
         dao.update_match_meta(
             &match_id,
@@ -2897,6 +4399,8 @@ impl Api {
                 .starts_at
                 .map(|d| d.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
                 .as_deref(),
+            venue.as_ref().map(Option::as_deref),
+            league.as_ref().map(Option::as_deref),
             None,
             pending_score.map(Some),
             header_photos,
@@ -2910,6 +4414,13 @@ impl Api {
             }
             other => dao_internal(other),
         })?;
+        // Its own write: the meta update above is already a long expression,
+        // and a visibility change re-runs fan-out (pruning feeds) either way.
+        if let Some(visibility) = input.visibility {
+            dao.set_match_visibility(&match_id, visibility_to_record(visibility))
+                .await
+                .map_err(dao_internal)?;
+        }
 
         // Roster: add ad-hoc players (no invitation) then apply side reassigns.
         if let Some(added) = &input.added_players {
@@ -2970,16 +4481,183 @@ impl Api {
                 )));
             }
         };
-        let i_liked = dao
-            .has_liked_match(&match_id, &uid)
+        let my_reaction = dao
+            .get_match_reaction(&match_id, &uid)
             .await
-            .map_err(dao_internal)?;
-        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, i_liked);
+            .map_err(dao_internal)?
+            .map(|r| r.reaction);
+        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, my_reaction);
         sign_match_headers(assets, &mut m);
         let m = self.hydrate_match(dao, m, &uid).await?;
         Ok(UpdateMatchResponse::Match(Json(m)))
     }
 
+    /// Cancel a match, with a reason. The match stays readable (and keeps any
+    /// score for the record) but stops counting towards anyone's stats, and
+    /// everyone who plays in or follows it is notified.
+    #[oai(path = "/matches/:match_id/cancel", method = "post")]
+    async fn cancel_match(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        input: Json<CancelMatchInput>,
+    ) -> Result<CancelMatchResponse> {
+        info!("Cancelling match {match_id}");
+        let input = input.0;
+        let uid = self.require_uid(dao, &jwt_data).await?;
+
+        let reason = input.reason.trim();
+        if reason.is_empty() {
+            return Ok(CancelMatchResponse::ValidationError(PlainText(
+                "a reason is required".into(),
+            )));
+        }
+
+        let Some(agg) = dao.get_match(&match_id).await.map_err(dao_internal)? else {
+            return Ok(CancelMatchResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        };
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::Cancel)
+        {
+            return Ok(CancelMatchResponse::Forbidden(denied.into()));
+        }
+
+        let cancellation = dao::records::MatchCancellationRecord {
+            reason: reason.to_string(),
+            by_user_id: uid.clone(),
+            at: now_iso(),
+        };
+        match dao.cancel_match(&match_id, &cancellation).await {
+            Ok(()) => {}
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(CancelMatchResponse::NotFound(PlainText(
+                    "match not found".into(),
+                )));
+            }
+            Err(dao::DaoError::Conflict(_)) => {
+                return Ok(CancelMatchResponse::Conflict(PlainText(
+                    "match is already cancelled".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+
+        let Some(agg) = dao.get_match(&match_id).await.map_err(dao_internal)? else {
+            return Ok(CancelMatchResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        };
+        let my_reaction = dao
+            .get_match_reaction(&match_id, &uid)
+            .await
+            .map_err(dao_internal)?
+            .map(|r| r.reaction);
+        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, my_reaction);
+        sign_match_headers(assets, &mut m);
+        let m = self.hydrate_match(dao, m, &uid).await?;
+        Ok(CancelMatchResponse::Match(Json(m)))
+    }
+
+    /// Move a match to a new start time, with a reason. The roster and
+    /// invitations carry over unchanged; any pending score is dropped and the
+    /// match goes back to `scheduled`. Feed entries are re-sorted under the new
+    /// time and everyone who plays in or follows the match is notified.
+    #[oai(path = "/matches/:match_id/postpone", method = "post")]
+    async fn postpone_match(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        input: Json<PostponeMatchInput>,
+    ) -> Result<PostponeMatchResponse> {
+        info!("Postponing match {match_id}");
+        let input = input.0;
+        let uid = self.require_uid(dao, &jwt_data).await?;
+
+        let reason = input.reason.trim();
+        if reason.is_empty() {
+            return Ok(PostponeMatchResponse::ValidationError(PlainText(
+                "a reason is required".into(),
+            )));
+        }
+        if input.starts_at <= chrono::Utc::now() {
+            return Ok(PostponeMatchResponse::ValidationError(PlainText(
+                "the new start time must be in the future".into(),
+            )));
+        }
+
+        let Some(agg) = dao.get_match(&match_id).await.map_err(dao_internal)? else {
+            return Ok(PostponeMatchResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        };
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::Postpone)
+        {
+            return Ok(PostponeMatchResponse::Forbidden(denied.into()));
+        }
+        if agg.match_.status == "cancelled" {
+            return Ok(PostponeMatchResponse::Conflict(PlainText(
+                "a cancelled match can't be postponed".into(),
+            )));
+        }
+        if agg.match_.confirmed_score.is_some() {
+            return Ok(PostponeMatchResponse::ValidationError(PlainText(
+                "a match with a confirmed result can't be postponed".into(),
+            )));
+        }
+        let to_starts_at = input
+            .starts_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        if mapping::parse_ts(&agg.match_.starts_at) == input.starts_at {
+            return Ok(PostponeMatchResponse::ValidationError(PlainText(
+                "the new start time must differ from the current one".into(),
+            )));
+        }
+
+        let postponement = dao::records::MatchPostponementRecord {
+            from_starts_at: agg.match_.starts_at.clone(),
+            to_starts_at,
+            reason: reason.to_string(),
+            by_user_id: uid.clone(),
+            at: now_iso(),
+        };
+        match dao.postpone_match(&match_id, &postponement).await {
+            Ok(()) => {}
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(PostponeMatchResponse::NotFound(PlainText(
+                    "match not found".into(),
+                )));
+            }
+            Err(dao::DaoError::Conflict(_)) => {
+                return Ok(PostponeMatchResponse::Conflict(PlainText(
+                    "the match changed while postponing it; refresh and retry".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+
+        let Some(agg) = dao.get_match(&match_id).await.map_err(dao_internal)? else {
+            return Ok(PostponeMatchResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        };
+        let my_reaction = dao
+            .get_match_reaction(&match_id, &uid)
+            .await
+            .map_err(dao_internal)?
+            .map(|r| r.reaction);
+        let mut m = match_from_records(&agg.match_, &agg.sides, &agg.players, my_reaction);
+        sign_match_headers(assets, &mut m);
+        let m = self.hydrate_match(dao, m, &uid).await?;
+        Ok(PostponeMatchResponse::Match(Json(m)))
+    }
+
     /// Both sports' persisted records are kept incrementally correct by
     /// every live-scoring append (see `apply_live_events_incrementally`), so
     /// this trusts the persisted record directly, with a full refold only as
@@ -2991,12 +4669,13 @@ impl Api {
     async fn get_match_score(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
     ) -> Result<GetMatchScoreResponse> {
         info!("Getting score for match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
 
-        let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
+        let agg = match self.visible_match(dao, &match_id, &uid).await? {
             Some(a) => a,
             None => {
                 return Ok(GetMatchScoreResponse::NotFound(PlainText(
@@ -3201,10 +4880,10 @@ impl Api {
         // the match (`update_match`). Confirmation now covers the detail
         // these events fold into, so a non-participant writing them isn't
         // just noise, it's data someone else's confirmation would vouch for.
-        if !caller_can_manage_match(&agg, &uid) {
-            return Ok(AppendLiveEventsResponse::Forbidden(PlainText(
-                "only a participant can record live events for this match".into(),
-            )));
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::RecordLiveEvents)
+        {
+            return Ok(AppendLiveEventsResponse::Forbidden(denied.into()));
         }
 
         let sport = agg.match_.match_type.clone();
@@ -3254,6 +4933,8 @@ impl Api {
                 None,
                 None,
                 None,
+                None,
+                None,
                 &[],
             )
             .await
@@ -3385,19 +5066,15 @@ impl Api {
     async fn list_live_events(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
         /// Opaque cursor from the previous page's `next_cursor`. Omit for the first page.
         Query(cursor): Query<Option<String>>,
         /// Maximum number of items to return (defaults to 20, capped at 50).
         Query(limit): Query<Option<u32>>,
     ) -> Result<ListLiveEventsResponse> {
-        if dao
-            .get_match(&match_id)
-            .await
-            .map_err(dao_internal)?
-            .is_none()
-        {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
             return Ok(ListLiveEventsResponse::NotFound(PlainText(
                 "match not found".into(),
             )));
@@ -3436,7 +5113,7 @@ impl Api {
         Path(match_id): Path<String>,
         Path(seq): Path<u32>,
     ) -> Result<DeleteLiveEventResponse> {
-        self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_uid(dao, &jwt_data).await?;
         info!("Deleting live event {seq} on match {match_id}");
 
         let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
@@ -3447,6 +5124,11 @@ impl Api {
                 )));
             }
         };
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::UndoLiveEvent)
+        {
+            return Ok(DeleteLiveEventResponse::Forbidden(denied.into()));
+        }
 
         let new_tip = match dao.delete_live_event(&match_id, seq).await {
             Ok(new_tip) => new_tip,
@@ -3574,18 +5256,14 @@ impl Api {
     async fn list_score_submissions(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
     ) -> Result<ListScoreSubmissionsResponse> {
         info!("Listing score submissions for match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
 
-        // 404 if the match itself is missing.
-        if dao
-            .get_match(&match_id)
-            .await
-            .map_err(dao_internal)?
-            .is_none()
-        {
+        // 404 if the match itself is missing (or hidden from the caller).
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
             return Ok(ListScoreSubmissionsResponse::NotFound(PlainText(
                 "match not found".into(),
             )));
@@ -3638,6 +5316,11 @@ impl Api {
                 )));
             }
         };
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::RespondToScore)
+        {
+            return Ok(RespondToScoreResponse::Forbidden(denied.into()));
+        }
         // Caller's player row (by linked user id), and the side they're on.
         let caller_player = agg
             .players
@@ -3698,6 +5381,8 @@ impl Api {
                     None,
                     None,
                     None,
+                    None,
+                    None,
                     Some(None),
                     None,
                     None,
@@ -3747,6 +5432,7 @@ impl Api {
                     let confirmed = dao::records::ConfirmedScoreRecord {
                         score: submission.score.clone(),
                         winner_side_id: submission.winner_side_id.clone(),
+                        confirmed_at: Some(now.clone()),
                     };
                     dao.update_match_meta(
                         &match_id,
@@ -3754,6 +5440,8 @@ impl Api {
                         None,
                         None,
                         None,
+                        None,
+                        None,
                         Some(confirmed),
                         Some(None),
                         None,
@@ -3779,8 +5467,15 @@ impl Api {
         Path(match_id): Path<String>,
     ) -> Result<LikeResponse> {
         info!("Liking match {match_id}");
-        let uid = self.require_uid(dao, &jwt_data).await?;
-        // Idempotent create of the caller -> match like edge (bumps like_count).
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let Some(agg) = self.visible_match(dao, &match_id, &uid).await? else {
+            return Ok(LikeResponse::NotFound(PlainText("match not found".into())));
+        };
+        let standing = self.match_social_standing(dao, &agg, &uid, None).await?;
+        if let Err(denied) = policy::authorize_match(standing, MatchAction::React) {
+            return Ok(LikeResponse::Forbidden(denied.into()));
+        }
+        // Idempotent: sets the caller's reaction to 👍 (bumps like_count).
         dao.like_match(&match_id, &uid, &now_iso())
             .await
             .map_err(dao_internal)?;
@@ -3796,7 +5491,7 @@ impl Api {
     ) -> Result<LikeResponse> {
         info!("Unliking match {match_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        // Idempotent removal of the caller -> match like edge.
+        // Idempotent; a reaction other than 👍 is left alone.
         dao.unlike_match(&match_id, &uid)
             .await
             .map_err(dao_internal)?;
@@ -3807,7 +5502,7 @@ impl Api {
     async fn list_match_likes(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
         /// Opaque cursor from the previous page's `next_cursor`.
         Query(cursor): Query<Option<String>>,
@@ -3815,97 +5510,344 @@ impl Api {
         Query(limit): Query<Option<u32>>,
     ) -> Result<ListLikesResponse> {
         info!("Listing likes for match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
+            return Ok(ListLikesResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        }
         let page = dao
-            .list_match_likes(&match_id, cursor.as_deref(), page_limit(limit))
+            .list_match_reactions(
+                &match_id,
+                Some(dao::records::ReactionRecord::Like),
+                cursor.as_deref(),
+                page_limit(limit),
+            )
             .await
             .map_err(dao_internal)?;
         let ids: Vec<String> = page.items.into_iter().map(|l| l.user_id).collect();
-        let items = self.hydrate_user_profiles(dao, &ids, None).await?;
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
         Ok(ListLikesResponse::Users(Json(UserPage {
             items,
             next_cursor: page.next_cursor,
         })))
     }
 
-    #[oai(path = "/matches/:match_id/comments", method = "get")]
-    async fn list_match_comments(
+    #[oai(path = "/matches/:match_id/reactions", method = "put")]
+    async fn react_to_match(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        input: Json<ReactInput>,
+    ) -> Result<ReactResponse> {
+        info!("Reacting to match {match_id}");
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let Some(agg) = self.visible_match(dao, &match_id, &uid).await? else {
+            return Ok(ReactResponse::NotFound(PlainText("match not found".into())));
+        };
+        let standing = self.match_social_standing(dao, &agg, &uid, None).await?;
+        if let Err(denied) = policy::authorize_match(standing, MatchAction::React) {
+            return Ok(ReactResponse::Forbidden(denied.into()));
+        }
+        dao.react_to_match(
+            &match_id,
+            &uid,
+            reaction_to_record(input.0.reaction),
+            &now_iso(),
+        )
+        .await
+        .map_err(dao_internal)?;
+        Ok(ReactResponse::Ok)
+    }
+
+    #[oai(path = "/matches/:match_id/reactions", method = "delete")]
+    async fn unreact_to_match(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+    ) -> Result<ReactResponse> {
+        info!("Removing reaction on match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        dao.unreact_to_match(&match_id, &uid, None)
+            .await
+            .map_err(dao_internal)?;
+        Ok(ReactResponse::Ok)
+    }
+
+    #[oai(path = "/matches/:match_id/reactions", method = "get")]
+    async fn list_match_reactions(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
+        /// Only list people who reacted with this.
+        Query(reaction): Query<Option<Reaction>>,
         /// Opaque cursor from the previous page's `next_cursor`.
         Query(cursor): Query<Option<String>>,
         /// Maximum number of items to return (defaults to 20, capped at 50).
         Query(limit): Query<Option<u32>>,
-    ) -> Result<ListCommentsResponse> {
-        info!("Listing comments for match {match_id}");
+    ) -> Result<ListReactionsResponse> {
+        info!("Listing reactions for match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
+            return Ok(ListReactionsResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        }
         let page = dao
-            .list_comments(&match_id, cursor.as_deref(), page_limit(limit))
+            .list_match_reactions(
+                &match_id,
+                reaction.map(reaction_to_record),
+                cursor.as_deref(),
+                page_limit(limit),
+            )
             .await
             .map_err(dao_internal)?;
-        let items = self.hydrate_comments(dao, page.items).await?;
-        Ok(ListCommentsResponse::Comments(Json(CommentPage {
-            items,
+        let rows = page
+            .items
+            .into_iter()
+            .map(|r| (r.user_id, r.reaction, r.created_at))
+            .collect();
+        Ok(ListReactionsResponse::Reactors(Json(ReactorPage {
+            items: self.hydrate_reactors(dao, rows, &uid).await?,
             next_cursor: page.next_cursor,
         })))
     }
 
-    #[oai(path = "/matches/:match_id/comments", method = "post")]
-    async fn create_match_comment(
+    #[oai(
+        path = "/matches/:match_id/comments/:comment_id/reactions",
+        method = "put"
+    )]
+    async fn react_to_comment(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
-        input: Json<CreateCommentInput>,
-    ) -> Result<CreateCommentResponse> {
-        info!("Creating comment on match {match_id}");
-        let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
-        if input.text.trim().is_empty() {
-            return Ok(CreateCommentResponse::ValidationError(PlainText(
-                "comment text must not be empty".into(),
+        Path(comment_id): Path<String>,
+        input: Json<ReactInput>,
+    ) -> Result<ReactResponse> {
+        info!("Reacting to comment {comment_id} on match {match_id}");
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let Some(agg) = self.visible_match(dao, &match_id, &uid).await? else {
+            return Ok(ReactResponse::NotFound(PlainText("match not found".into())));
+        };
+        let Some(comment) = self
+            .visible_comment(dao, &match_id, &comment_id, &uid)
+            .await?
+        else {
+            return Ok(ReactResponse::NotFound(PlainText(
+                "comment not found".into(),
             )));
+        };
+        // A deleted comment has no author; there's no one to be blocked from.
+        let author = comment.author_user_id.as_deref().unwrap_or(uid.as_str());
+        let standing = self
+            .match_social_standing(dao, &agg, &uid, Some(author))
+            .await?;
+        if let Err(denied) = policy::authorize_match(standing, MatchAction::React) {
+            return Ok(ReactResponse::Forbidden(denied.into()));
         }
+        dao.react_to_comment(
+            &comment,
+            &uid,
+            reaction_to_record(input.0.reaction),
+            &now_iso(),
+        )
+        .await
+        .map_err(dao_internal)?;
+        Ok(ReactResponse::Ok)
+    }
 
-        let record = dao::records::CommentRecord {
-            comment_id: new_id(),
-            match_id: match_id.clone(),
-            parent_id: input.parent_id.clone(),
-            author_user_id: Some(uid.clone()),
-            text: Some(input.text),
-            created_at: now_iso(),
-            edited_at: None,
-            deleted_at: None,
-            reply_count: 0,
-        };
+    #[oai(
+        path = "/matches/:match_id/comments/:comment_id/reactions",
+        method = "delete"
+    )]
+    async fn unreact_to_comment(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        Path(comment_id): Path<String>,
+    ) -> Result<ReactResponse> {
+        info!("Removing reaction on comment {comment_id} on match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        // A tombstoned comment still carries counts, so this looks the comment
+        // up as stored rather than as the caller would see it.
+        if let Some(comment) = self.comment_or_reply(dao, &match_id, &comment_id).await? {
+            dao.unreact_to_comment(&comment, &uid)
+                .await
+                .map_err(dao_internal)?;
+        }
+        Ok(ReactResponse::Ok)
+    }
 
+    #[oai(
+        path = "/matches/:match_id/comments/:comment_id/reactions",
+        method = "get"
+    )]
+    async fn list_comment_reactions(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        Path(comment_id): Path<String>,
+        /// Only list people who reacted with this.
+        Query(reaction): Query<Option<Reaction>>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListReactionsResponse> {
+        info!("Listing reactions for comment {comment_id} on match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none()
+            || self
+                .visible_comment(dao, &match_id, &comment_id, &uid)
+                .await?
+                .is_none()
+        {
+            return Ok(ListReactionsResponse::NotFound(PlainText(
+                "comment not found".into(),
+            )));
+        }
+        let page = dao
+            .list_comment_reactions(
+                &match_id,
+                &comment_id,
+                reaction.map(reaction_to_record),
+                cursor.as_deref(),
+                page_limit(limit),
+            )
+            .await
+            .map_err(dao_internal)?;
+        let rows = page
+            .items
+            .into_iter()
+            .map(|r| (r.user_id, r.reaction, r.created_at))
+            .collect();
+        Ok(ListReactionsResponse::Reactors(Json(ReactorPage {
+            items: self.hydrate_reactors(dao, rows, &uid).await?,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    #[oai(path = "/matches/:match_id/comments", method = "get")]
+    async fn list_match_comments(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListCommentsResponse> {
+        info!("Listing comments for match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
+            return Ok(ListCommentsResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        }
+        let page = dao
+            .list_comments(&match_id, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let items = self.hydrate_comments(dao, page.items, &uid).await?;
+        Ok(ListCommentsResponse::Comments(Json(CommentPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    #[oai(path = "/matches/:match_id/comments", method = "post")]
+    async fn create_match_comment(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(match_id): Path<String>,
+        input: Json<CreateCommentInput>,
+    ) -> Result<CreateCommentResponse> {
+        info!("Creating comment on match {match_id}");
+        let input = input.0;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let Some(agg) = self.visible_match(dao, &match_id, &uid).await? else {
+            return Ok(CreateCommentResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        };
         // A reply targets a top-level comment: validate the parent exists and is
         // itself top-level (no replying to a reply). A top-level comment lives at
         // `COMMENT#<id>`; a reply at `REPLY#<id>`. If the parent id resolves to a
         // reply, it's a (rejected) second-level reply; if it resolves to neither,
         // the parent doesn't exist.
-        if let Some(parent_id) = &input.parent_id {
-            if dao
-                .get_comment(&match_id, parent_id)
-                .await
-                .map_err(dao_internal)?
-                .is_none()
-            {
-                // Not a top-level comment. Is it a reply (→ 400) or absent (→ 404)?
-                if dao
-                    .get_reply(&match_id, parent_id)
+        let parent = match &input.parent_id {
+            Some(parent_id) => {
+                let parent = dao
+                    .get_comment(&match_id, parent_id)
                     .await
-                    .map_err(dao_internal)?
-                    .is_some()
-                {
-                    return Ok(CreateCommentResponse::ValidationError(PlainText(
-                        "cannot reply to a reply".into(),
+                    .map_err(dao_internal)?;
+                if parent.is_none() {
+                    // Not a top-level comment. Is it a reply (→ 400) or absent (→ 404)?
+                    if dao
+                        .get_reply(&match_id, parent_id)
+                        .await
+                        .map_err(dao_internal)?
+                        .is_some()
+                    {
+                        return Ok(CreateCommentResponse::ValidationError(PlainText(
+                            "cannot reply to a reply".into(),
+                        )));
+                    }
+                    return Ok(CreateCommentResponse::NotFound(PlainText(
+                        "parent comment not found".into(),
                     )));
                 }
-                return Ok(CreateCommentResponse::NotFound(PlainText(
-                    "parent comment not found".into(),
-                )));
+                parent
             }
+            None => None,
+        };
+        // A reply answers the parent's author (none, if it's been deleted);
+        // a top-level comment, the match's creator.
+        let counterpart = parent
+            .as_ref()
+            .map(|p| p.author_user_id.as_deref().unwrap_or(uid.as_str()));
+        let standing = self
+            .match_social_standing(dao, &agg, &uid, counterpart)
+            .await?;
+        if let Err(denied) = policy::authorize_match(standing, MatchAction::Comment) {
+            return Ok(CreateCommentResponse::Forbidden(denied.into()));
+        }
+        if input.text.trim().is_empty() {
+            return Ok(CreateCommentResponse::ValidationError(PlainText(
+                "comment text must not be empty".into(),
+            )));
+        }
+
+        let (mentions, mentioned) = self.resolve_mentions(dao, &agg, &uid, &input.text).await?;
+        let record = dao::records::CommentRecord {
+            comment_id: new_id(),
+            match_id: match_id.clone(),
+            parent_id: input.parent_id.clone(),
+            author_user_id: Some(uid.clone()),
+            tags: comment_text::hashtags(&input.text),
+            text: Some(input.text),
+            created_at: now_iso(),
+            edited_at: None,
+            deleted_at: None,
+            hidden_at: None,
+            reply_count: 0,
+            like_count: 0,
+            fire_count: 0,
+            clap_count: 0,
+            laugh_count: 0,
+            wow_count: 0,
+            mentions,
+        };
+
+        if parent.is_some() {
             dao.create_reply(&record).await.map_err(dao_internal)?;
         } else {
             dao.create_comment(&record).await.map_err(dao_internal)?;
@@ -3914,7 +5856,7 @@ impl Api {
         // Author is the caller.
         let author = self.try_user_profile(dao, &uid).await?;
         Ok(CreateCommentResponse::Comment(Json(comment_from_record(
-            &record, author,
+            &record, author, &mentioned, None,
         ))))
     }
 
@@ -3925,7 +5867,7 @@ impl Api {
     async fn list_comment_replies(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(match_id): Path<String>,
         Path(comment_id): Path<String>,
         /// Opaque cursor from the previous page's `next_cursor`.
@@ -3934,11 +5876,17 @@ impl Api {
         Query(limit): Query<Option<u32>>,
     ) -> Result<ListCommentsResponse> {
         info!("Listing replies to comment {comment_id} on match {match_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        if self.visible_match(dao, &match_id, &uid).await?.is_none() {
+            return Ok(ListCommentsResponse::NotFound(PlainText(
+                "match not found".into(),
+            )));
+        }
         let page = dao
             .list_replies(&comment_id, cursor.as_deref(), page_limit(limit))
             .await
             .map_err(dao_internal)?;
-        let items = self.hydrate_comments(dao, page.items).await?;
+        let items = self.hydrate_comments(dao, page.items, &uid).await?;
         Ok(ListCommentsResponse::Comments(Json(CommentPage {
             items,
             next_cursor: page.next_cursor,
@@ -3956,7 +5904,7 @@ impl Api {
     ) -> Result<UpdateCommentResponse> {
         info!("Updating comment {comment_id} on match {match_id}");
         let input = input.0;
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
         if input.text.trim().is_empty() {
             return Ok(UpdateCommentResponse::ValidationError(PlainText(
                 "comment text must not be empty".into(),
@@ -3980,18 +5928,48 @@ impl Api {
                 "only the author can edit this comment".into(),
             )));
         }
+        // Mentions resolve against the match as it is now. The stream handler
+        // diffs the stored mentions, so only people newly mentioned by this
+        // edit are notified.
+        let Some(agg) = dao.get_match(&match_id).await.map_err(dao_internal)? else {
+            return Ok(UpdateCommentResponse::NotFound(PlainText(
+                "comment not found".into(),
+            )));
+        };
+        let (mentions, mentioned) = self.resolve_mentions(dao, &agg, &uid, &input.text).await?;
+        let tags = comment_text::hashtags(&input.text);
 
         let edited_at = now_iso();
-        dao.edit_comment(&match_id, &comment_id, &input.text, &edited_at)
+        match dao
+            .edit_comment(&existing, &input.text, &mentions, &tags, &edited_at)
             .await
-            .map_err(dao_internal)?;
+        {
+            Ok(()) => {}
+            // Deleted by a concurrent request since it was read above.
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(UpdateCommentResponse::NotFound(PlainText(
+                    "comment not found".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
 
         let mut updated = existing;
         updated.text = Some(input.text);
         updated.edited_at = Some(edited_at);
+        updated.mentions = mentions;
+        updated.tags = tags;
         let author = self.try_user_profile(dao, &uid).await?;
+        let my_reaction = dao
+            .get_comment_reaction(&match_id, &comment_id, &uid)
+            .await
+            .map_err(dao_internal)?
+            .map(|r| r.reaction);
         Ok(UpdateCommentResponse::Comment(Json(comment_from_record(
-            &updated, author,
+            &updated,
+            author,
+            &mentioned,
+            my_reaction,
         ))))
     }
 
@@ -4025,11 +6003,14 @@ impl Api {
 
         // Tombstone if it has replies (keep the thread); hard-delete otherwise.
         if existing.reply_count > 0 {
-            dao.tombstone_comment(&match_id, &comment_id, &now_iso())
+            dao.tombstone_comment(&match_id, &comment_id, &existing.tags, &now_iso())
                 .await
                 .map_err(dao_internal)?;
         } else {
-            match dao.delete_comment_hard(&match_id, &comment_id).await {
+            match dao
+                .delete_comment_hard(&match_id, &comment_id, &existing.tags)
+                .await
+            {
                 Ok(()) => {}
                 // Deleted by a concurrent request between the check above and
                 // here.
@@ -4044,6 +6025,150 @@ impl Api {
         Ok(DeleteCommentResponse::Ok)
     }
 
+    /// Comments and replies using a `#tag`, newest first. Comments on matches
+    /// the caller can't see, deleted or hidden ones, and those by users they
+    /// have a block with are left out, so a page may come back short.
+    #[oai(path = "/tags/:tag/comments", method = "get")]
+    async fn list_tag_comments(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// The tag, with or without its `#`; matched case-insensitively.
+        Path(tag): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListTagCommentsResponse> {
+        info!("Listing comments tagged {tag}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(tag) = comment_text::normalize_tag(&tag) else {
+            return Ok(ListTagCommentsResponse::ValidationError(PlainText(
+                "not a valid tag".into(),
+            )));
+        };
+        let page = dao
+            .list_tag_comments(&tag, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let mut comments = dao
+            .batch_get_tagged_comments(&page.items)
+            .await
+            .map_err(dao_internal)?;
+
+        let mut match_ids: Vec<String> = page.items.iter().map(|r| r.match_id.clone()).collect();
+        match_ids.sort();
+        match_ids.dedup();
+        let mut visible = dao
+            .batch_get_match_summaries(&match_ids)
+            .await
+            .map_err(dao_internal)?;
+        dao.retain_visible_matches(&mut visible, Some(&uid))
+            .await
+            .map_err(dao_internal)?;
+
+        let records: Vec<_> = page
+            .items
+            .iter()
+            .filter(|row| visible.contains_key(&row.match_id))
+            .filter_map(|row| comments.remove(&row.comment_id))
+            .filter(|c| c.deleted_at.is_none() && c.hidden_at.is_none())
+            .collect();
+        let items = self.hydrate_comments(dao, records, &uid).await?;
+        Ok(ListTagCommentsResponse::Comments(Json(CommentPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// Matches whose comments use a `#tag`, most recently tagged first.
+    /// Matches the caller can't see are left out, so a page may come back
+    /// short.
+    #[oai(path = "/tags/:tag/matches", method = "get")]
+    async fn list_tag_matches(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// The tag, with or without its `#`; matched case-insensitively.
+        Path(tag): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListTagMatchesResponse> {
+        info!("Listing matches tagged {tag}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(tag) = comment_text::normalize_tag(&tag) else {
+            return Ok(ListTagMatchesResponse::ValidationError(PlainText(
+                "not a valid tag".into(),
+            )));
+        };
+        let page = dao
+            .list_tag_matches(&tag, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        // A match whose tagged comments have all since been edited or deleted
+        // keeps its row at zero.
+        let match_ids: Vec<String> = page
+            .items
+            .iter()
+            .filter(|row| row.comment_count > 0)
+            .map(|row| row.match_id.clone())
+            .collect();
+        let (mut summaries, reactions) = tokio::try_join!(
+            dao.batch_get_match_summaries(&match_ids),
+            dao.batch_get_match_reactions(&match_ids, &uid),
+        )
+        .map_err(dao_internal)?;
+        dao.retain_visible_matches(&mut summaries, Some(&uid))
+            .await
+            .map_err(dao_internal)?;
+
+        let mut user_ids: Vec<String> = Vec::new();
+        for summary in summaries.values() {
+            for side in &summary.sides {
+                user_ids.extend(side.roster_preview.iter().filter_map(|p| p.user_id.clone()));
+            }
+        }
+        let team_ids: Vec<String> = summaries
+            .values()
+            .flat_map(|s| s.sides.iter().filter_map(|s| s.team_id.clone()))
+            .collect();
+        let (users, team_names) = tokio::try_join!(
+            async { dao.batch_get_users(&user_ids).await.map_err(dao_internal) },
+            async { self.batch_team_names(dao, &team_ids).await },
+        )?;
+
+        let mut items: Vec<SearchMatch> = Vec::with_capacity(match_ids.len());
+        for match_id in &match_ids {
+            if let Some(summary) = summaries.get(match_id) {
+                let mut m = search_match_from_records(
+                    &summary.match_,
+                    &summary.sides,
+                    &users,
+                    None,
+                    reactions.get(match_id).copied(),
+                );
+                Self::resolve_side_names_from_cache(&mut m.sides, None, &team_names);
+                sign_search_match_headers(assets, &mut m);
+                items.push(m);
+            }
+        }
+        let mut score_refs: Vec<_> = items
+            .iter_mut()
+            .map(|m| (m.id.as_str(), &mut m.confirmed_score, &mut m.pending_score))
+            .collect();
+        self.hydrate_confirmed_pending_score_players(dao, &mut score_refs)
+            .await?;
+
+        Ok(ListTagMatchesResponse::Matches(Json(MatchPage {
+            items,
+            next_cursor: page.next_cursor,
+            facets: None,
+        })))
+    }
+
     #[oai(path = "/users/me/teams", method = "get")]
     async fn list_my_teams(
         &self,
@@ -4070,6 +6195,7 @@ impl Api {
                 .get_team_meta(&membership.team_id)
                 .await
                 .map_err(dao_internal)?
+                && team.archived_at.is_none()
             {
                 items.push(team_list_item_from_record(&team, is_following));
             }
@@ -4085,7 +6211,7 @@ impl Api {
         &self,
         Data(dao): Data<&dao::Dao>,
         Data(search): Data<&agon_core::search::SearchClient>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         #[oai(name = "q")] Query(query): Query<String>,
         /// Opaque cursor from the previous page's `next_cursor`.
         Query(cursor): Query<Option<String>>,
@@ -4093,6 +6219,7 @@ impl Api {
         Query(limit): Query<Option<u32>>,
     ) -> Result<ListTeamsResponse> {
         info!("Searching teams with query: {query}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
 
         let offset = match search_offset(cursor.as_deref()) {
             Ok(o) => o,
@@ -4105,6 +6232,7 @@ impl Api {
         };
         let q = agon_core::search::SearchQuery {
             q: query,
+            filter: Some(agon_core::search::Filter::visible_to(&uid)),
             offset,
             limit: page_limit(limit),
             ..Default::default()
@@ -4137,13 +6265,20 @@ impl Api {
         input: Json<CreateTeamInput>,
     ) -> Result<CreateTeamResponse> {
         info!("Creating team {}", input.name);
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
         let now = now_iso();
+        let input = input.0;
         let team = dao::records::TeamRecord {
             id: new_id(),
-            name: input.0.name,
+            name: input.name,
             invite_token: Some(new_id()),
             follower_count: 0,
+            club_id: None,
+            archived_at: None,
+            visibility: input
+                .visibility
+                .map(visibility_to_record)
+                .unwrap_or_default(),
             created_at: now.clone(),
         };
         // The creator becomes the first member with the Admin role (already an
@@ -4156,6 +6291,8 @@ impl Api {
             role: String::from("admin"),
             invitation: None,
             created_at: now,
+            left_at: None,
+            previous_roles: Vec::new(),
         };
         match dao.create_team(&team, &creator).await {
             Ok(()) => {}
@@ -4180,7 +6317,7 @@ impl Api {
     ) -> Result<GetTeamResponse> {
         info!("Getting team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        match dao.get_team(&team_id).await.map_err(dao_internal)? {
+        match self.visible_team(dao, &team_id, &uid).await? {
             Some(agg) => {
                 let is_followed_by_me = dao
                     .is_following_team(&uid, &team_id)
@@ -4202,22 +6339,27 @@ impl Api {
     async fn add_team_members(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(team_id): Path<String>,
         input: Json<AddTeamMembersInput>,
     ) -> Result<AddTeamMembersResponse> {
         info!("Adding {} members to team {team_id}", input.user_ids.len());
+        let uid = self.require_uid(dao, &jwt_data).await?;
 
         // Team must exist.
-        if dao
-            .get_team_meta(&team_id)
-            .await
-            .map_err(dao_internal)?
-            .is_none()
-        {
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
             return Ok(AddTeamMembersResponse::NotFound(PlainText(
                 "team not found".into(),
             )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::AddMembers) {
+            return Ok(AddTeamMembersResponse::Forbidden(denied.into()));
+        }
+        if self.any_blocked(dao, &uid, &input.user_ids).await? {
+            return Ok(AddTeamMembersResponse::Forbidden(PlainText(
+                BLOCKED_MEMBER.into(),
+            )));
         }
 
         // Add each user as a Member (no invitation — ad-hoc add).
@@ -4231,10 +6373,14 @@ impl Api {
                 role: String::from("member"),
                 invitation: None,
                 created_at: now.clone(),
+                left_at: None,
+                previous_roles: Vec::new(),
             };
-            dao.put_team_member(&team_id, &member)
-                .await
-                .map_err(dao_internal)?;
+            match dao.put_team_member(&team_id, &member).await {
+                // Already a member: nothing to add.
+                Ok(()) | Err(dao::DaoError::Conflict(_)) => {}
+                Err(e) => return Err(dao_internal(e)),
+            }
         }
 
         // Return the updated team aggregate.
@@ -4254,12 +6400,23 @@ impl Api {
     async fn update_team(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(team_id): Path<String>,
         input: Json<UpdateTeamInput>,
     ) -> Result<UpdateTeamResponse> {
         info!("Updating team {team_id}");
-        match dao.update_team(&team_id, input.0.name.as_deref()).await {
+        let input = input.0;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let Some(agg) = self.visible_team(dao, &team_id, &uid).await? else {
+            return Ok(UpdateTeamResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::Update) {
+            return Ok(UpdateTeamResponse::Forbidden(denied.into()));
+        }
+        match dao.update_team(&team_id, input.name.as_deref()).await {
             Ok(()) => {}
             Err(dao::DaoError::NotFound(_)) => {
                 return Ok(UpdateTeamResponse::NotFound(PlainText(
@@ -4268,6 +6425,11 @@ impl Api {
             }
             Err(e) => return Err(dao_internal(e)),
         }
+        if let Some(visibility) = input.visibility {
+            dao.set_team_visibility(&team_id, visibility_to_record(visibility))
+                .await
+                .map_err(dao_internal)?;
+        }
         match dao.get_team(&team_id).await.map_err(dao_internal)? {
             Some(agg) => Ok(UpdateTeamResponse::Team(Json(team_from_records(
                 &agg.team,
@@ -4284,28 +6446,38 @@ impl Api {
     async fn remove_team_member(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
+        AuthSchema(jwt_data): AuthSchema,
         Path(team_id): Path<String>,
         Path(member_id): Path<String>,
     ) -> Result<RemoveTeamMemberResponse> {
         info!("Removing member {member_id} from team {team_id}");
-        if dao
-            .get_team_meta(&team_id)
-            .await
-            .map_err(dao_internal)?
-            .is_none()
-        {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
             return Ok(RemoveTeamMemberResponse::NotFound(PlainText(
                 "team not found".into(),
             )));
-        }
-        match dao.remove_team_member(&team_id, &member_id).await {
+        };
+        let own = agg
+            .members
+            .iter()
+            .any(|m| m.membership_id == member_id && m.user_id.as_deref() == Some(uid.as_str()));
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::RemoveMember { own }) {
+            return Ok(RemoveTeamMemberResponse::Forbidden(denied.into()));
+        }
+        match dao
+            .remove_team_member(&team_id, &member_id, &now_iso())
+            .await
+        {
             Ok(()) => {}
             Err(dao::DaoError::NotFound(_)) => {
                 return Ok(RemoveTeamMemberResponse::NotFound(PlainText(
                     "member not found".into(),
                 )));
             }
+            Err(dao::DaoError::Conflict(msg)) => {
+                return Ok(RemoveTeamMemberResponse::Conflict(PlainText(msg)));
+            }
             Err(e) => return Err(dao_internal(e)),
         }
         match dao.get_team(&team_id).await.map_err(dao_internal)? {
@@ -4320,665 +6492,2502 @@ impl Api {
         }
     }
 
-    #[oai(path = "/matches/:match_id/invitations", method = "post")]
-    async fn add_match_invitations(
+    /// Hand admin over to another member: they become an admin and the
+    /// caller steps down to a plain member.
+    #[oai(path = "/teams/:team_id/transfer-admin", method = "post")]
+    async fn transfer_team_admin(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(match_id): Path<String>,
-        input: Json<AddInvitationsInput>,
-    ) -> Result<AddInvitationsResponse> {
-        info!("Inviting to match {match_id}");
+        Path(team_id): Path<String>,
+        input: Json<TransferTeamAdminInput>,
+    ) -> Result<TransferTeamAdminResponse> {
+        info!(
+            "Transferring admin of team {team_id} to {}",
+            input.member_id
+        );
         let uid = self.require_uid(dao, &jwt_data).await?;
-        let input = input.0;
-
-        // Match must exist.
-        let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
-            Some(a) => a,
-            None => {
-                return Ok(AddInvitationsResponse::NotFound(PlainText(
-                    "match not found".into(),
-                )));
-            }
-        };
-
-        // The creator (organizer) or a participant may invite others.
-        if !caller_can_manage_match(&agg, &uid) {
-            return Ok(AddInvitationsResponse::Forbidden(PlainText(
-                "only a participant can invite people to this match".into(),
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(TransferTeamAdminResponse::NotFound(PlainText(
+                "team not found".into(),
             )));
+        };
+        // Club admins aren't members, so they have no role to hand over.
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::TransferAdmin) {
+            return Ok(TransferTeamAdminResponse::Forbidden(denied.into()));
         }
-
-        // If a side was named, it must be one of this match's sides.
-        if let Some(side_id) = &input.side_id
-            && !agg.sides.iter().any(|s| &s.side_id == side_id)
-        {
-            return Ok(AddInvitationsResponse::NotFound(PlainText(
-                "side is not part of this match".into(),
+        let Some(caller) = agg.members.iter().find(|m| {
+            m.role == "admin" && m.left_at.is_none() && m.user_id.as_deref() == Some(uid.as_str())
+        }) else {
+            return Ok(TransferTeamAdminResponse::Forbidden(PlainText(
+                "only an admin member can hand over the team".into(),
             )));
-        }
-
-        // Each invitee gets both a roster slot (with an embedded invitation) and
-        // a standalone invitation, exactly like a create-time invite — so they
-        // show up on the match roster immediately, pre-assigned to `side_id` if
-        // given. `build_invited_player` mints the ids and tokens.
-        let now = now_iso();
-        let invitees = input
-            .invited_user_ids
+        };
+        match agg
+            .members
             .iter()
-            .map(|u| (Some(u.clone()), None))
-            .chain(
-                input
-                    .invited_external_names
-                    .iter()
-                    .map(|n| (None, Some(n.clone()))),
-            );
-
-        let mut created = Vec::new();
-        for (user_id, display_name) in invitees {
-            let (player, invitation) = build_invited_player(
-                &match_id,
-                &agg.match_.name,
-                &uid,
-                input.side_id.clone(),
-                user_id,
-                display_name,
-                &now,
-            );
-            dao.put_match_player(&match_id, &player)
-                .await
-                .map_err(dao_internal)?;
-            dao.create_invitation(&invitation)
-                .await
-                .map_err(dao_internal)?;
-            created.push(invitation_from_record(&invitation));
-        }
-        // New roster slots move players onto sides — refresh each side's
-        // cached roster preview (see `PATCH /matches/:match_id`'s roster
-        // block for the same call).
-        if !created.is_empty() {
-            dao.refresh_side_roster_previews(&match_id)
-                .await
-                .map_err(dao_internal)?;
+            .find(|m| m.membership_id == input.member_id)
+        {
+            None => {
+                return Ok(TransferTeamAdminResponse::NotFound(PlainText(
+                    "member not found".into(),
+                )));
+            }
+            Some(target)
+                if target.user_id.is_none() || target.membership_id == caller.membership_id =>
+            {
+                return Ok(TransferTeamAdminResponse::ValidationError(PlainText(
+                    "admin can only be handed to another member with an account".into(),
+                )));
+            }
+            Some(_) => {}
         }
-
-        // Roster/player writes don't trigger the stream (only `#META` does), so
-        // touch the match meta to re-run fan-out / search indexing with the new
-        // roster. Re-writing `name` to its current value is a real write to the
-        // item (an all-`None` update would no-op), so it emits a stream record.
-        if !created.is_empty() {
-            dao.update_match_meta(
-                &match_id,
-                Some(&agg.match_.name),
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                &[],
+        match dao
+            .transfer_team_admin(
+                &team_id,
+                &caller.membership_id,
+                &input.member_id,
+                &now_iso(),
             )
             .await
-            .map_err(dao_internal)?;
+        {
+            Ok(()) => {}
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(TransferTeamAdminResponse::NotFound(PlainText(
+                    "member not found".into(),
+                )));
+            }
+            Err(dao::DaoError::Conflict(msg)) => {
+                return Ok(TransferTeamAdminResponse::Conflict(PlainText(msg)));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+        match dao.get_team(&team_id).await.map_err(dao_internal)? {
+            Some(agg) => {
+                let is_followed_by_me = dao
+                    .is_following_team(&uid, &team_id)
+                    .await
+                    .map_err(dao_internal)?;
+                Ok(TransferTeamAdminResponse::Team(Json(team_from_records(
+                    &agg.team,
+                    &agg.members,
+                    is_followed_by_me,
+                ))))
+            }
+            None => Ok(TransferTeamAdminResponse::NotFound(PlainText(
+                "team not found".into(),
+            ))),
         }
-
-        Ok(AddInvitationsResponse::Invitations(Json(created)))
     }
 
-    #[oai(path = "/teams/:team_id/invitations", method = "post")]
-    async fn add_team_invitations(
+    /// Archive a team. It drops out of search, "my teams" and its club, and
+    /// its followers are detached; its past matches keep showing its name.
+    /// Re-archiving re-runs the follower detach, so an interrupted archive can
+    /// be finished by repeating the call.
+    #[oai(path = "/teams/:team_id", method = "delete")]
+    async fn archive_team(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
         Path(team_id): Path<String>,
-        input: Json<AddInvitationsInput>,
-    ) -> Result<AddInvitationsResponse> {
-        info!("Inviting to team {team_id}");
+    ) -> Result<ArchiveTeamResponse> {
+        info!("Archiving team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        let team = match dao.get_team_meta(&team_id).await.map_err(dao_internal)? {
-            Some(t) => t,
-            None => {
-                return Ok(AddInvitationsResponse::NotFound(PlainText(
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(ArchiveTeamResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::Archive) {
+            return Ok(ArchiveTeamResponse::Forbidden(denied.into()));
+        }
+        match dao.archive_team(&team_id, &now_iso()).await {
+            // Already archived: carry on and finish detaching followers.
+            Ok(()) | Err(dao::DaoError::Conflict(_)) => {}
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(ArchiveTeamResponse::NotFound(PlainText(
                     "team not found".into(),
                 )));
             }
+            Err(e) => return Err(dao_internal(e)),
+        }
+        let detached = dao
+            .remove_all_team_followers(&team_id)
+            .await
+            .map_err(dao_internal)?;
+        info!("Detached {detached} followers from archived team {team_id}");
+        Ok(ArchiveTeamResponse::Ok)
+    }
+
+    #[oai(path = "/teams/:team_id/seasons", method = "post")]
+    async fn create_team_season(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(team_id): Path<String>,
+        input: Json<CreateTeamSeasonInput>,
+    ) -> Result<CreateTeamSeasonResponse> {
+        info!("Creating season {} on team {team_id}", input.name);
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let input = input.0;
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(CreateTeamSeasonResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
         };
-        let ctx = dao::records::InvitationContextRecord::Team {
-            team_id: team_id.clone(),
-            team_name: team.name,
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageSeasons) {
+            return Ok(CreateTeamSeasonResponse::Forbidden(denied.into()));
+        }
+        if input.name.trim().is_empty() {
+            return Ok(CreateTeamSeasonResponse::ValidationError(PlainText(
+                "name must not be empty".into(),
+            )));
+        }
+        if input.ends_at.is_some_and(|end| end <= input.starts_at) {
+            return Ok(CreateTeamSeasonResponse::ValidationError(PlainText(
+                "ends_at must be after starts_at".into(),
+            )));
+        }
+        // Same fixed-width form as `now_iso()`, so membership/match timestamps
+        // compare against the season's bounds as strings.
+        let ts = |at: chrono::DateTime<chrono::Utc>| {
+            at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
         };
-        let created = self.create_invitations(dao, &uid, ctx, &input.0).await?;
-        // TODO: also create the TeamMember slot per invitee — deferred.
-        Ok(AddInvitationsResponse::Invitations(Json(created)))
+        let season = dao::records::TeamSeasonRecord {
+            id: new_id(),
+            team_id,
+            name: input.name,
+            starts_at: ts(input.starts_at),
+            ends_at: input.ends_at.map(ts),
+            created_at: now_iso(),
+        };
+        match dao.create_team_season(&season).await {
+            Ok(()) => {}
+            Err(dao::DaoError::Conflict(msg)) => {
+                return Ok(CreateTeamSeasonResponse::ValidationError(PlainText(msg)));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+        Ok(CreateTeamSeasonResponse::Season(Json(
+            team_season_from_record(&season),
+        )))
     }
 
-    /// Create one invitation per invitee (users by id → user-kind; external
-    /// names → token-kind), persist them, and return the API models. Shared by
-    /// the match and team invite endpoints.
-    async fn create_invitations(
+    /// Post an announcement to the team. It reaches the feeds of the team's
+    /// members and (unless the team is participants-only) its followers
+    /// shortly after, via the worker.
+    #[oai(path = "/teams/:team_id/announcements", method = "post")]
+    async fn post_team_announcement(
         &self,
-        dao: &dao::Dao,
-        inviter_id: &str,
-        context: dao::records::InvitationContextRecord,
-        input: &AddInvitationsInput,
-    ) -> Result<Vec<Invitation>> {
-        let now = now_iso();
-        let mut created = Vec::new();
-
-        for user_id in &input.invited_user_ids {
-            let rec = dao::records::InvitationRecord {
-                id: new_id(),
-                status: String::from("pending"),
-                invited_by_user_id: inviter_id.to_string(),
-                invited_user_id: Some(user_id.clone()),
-                invite_token: None,
-                kind: dao::records::InvitationKindRecord::User {
-                    invited_user_id: user_id.clone(),
-                },
-                context: context.clone(),
-                invited_at: now.clone(),
-                responded_at: None,
-            };
-            dao.create_invitation(&rec).await.map_err(dao_internal)?;
-            created.push(invitation_from_record(&rec));
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(team_id): Path<String>,
+        input: Json<PostTeamAnnouncementInput>,
+    ) -> Result<PostTeamAnnouncementResponse> {
+        info!("Posting announcement on team {team_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(PostTeamAnnouncementResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::PostAnnouncement) {
+            return Ok(PostTeamAnnouncementResponse::Forbidden(denied.into()));
         }
-
-        for _name in &input.invited_external_names {
-            let token = new_id();
-            let rec = dao::records::InvitationRecord {
-                id: new_id(),
-                status: String::from("pending"),
-                invited_by_user_id: inviter_id.to_string(),
-                invited_user_id: None,
-                invite_token: Some(token.clone()),
-                kind: dao::records::InvitationKindRecord::Token {
-                    invite_token: token,
-                },
-                context: context.clone(),
-                invited_at: now.clone(),
-                responded_at: None,
-            };
-            dao.create_invitation(&rec).await.map_err(dao_internal)?;
-            created.push(invitation_from_record(&rec));
+        if agg.team.archived_at.is_some() {
+            return Ok(PostTeamAnnouncementResponse::ValidationError(PlainText(
+                "team is archived".into(),
+            )));
         }
-
-        Ok(created)
+        let body = input.0.body.trim().to_string();
+        if body.is_empty() {
+            return Ok(PostTeamAnnouncementResponse::ValidationError(PlainText(
+                "body must not be empty".into(),
+            )));
+        }
+        if body.chars().count() > ANNOUNCEMENT_MAX_CHARS {
+            return Ok(PostTeamAnnouncementResponse::ValidationError(PlainText(
+                format!("body must be at most {ANNOUNCEMENT_MAX_CHARS} characters"),
+            )));
+        }
+        let announcement = dao::records::TeamAnnouncementRecord {
+            id: new_id(),
+            team_id,
+            author_id: uid,
+            body,
+            created_at: now_iso(),
+        };
+        dao.put_team_announcement(&announcement)
+            .await
+            .map_err(dao_internal)?;
+        Ok(PostTeamAnnouncementResponse::Announcement(Json(
+            TeamAnnouncement {
+                id: announcement.id,
+                team_id: announcement.team_id,
+                author_user_id: announcement.author_id,
+                body: announcement.body,
+                created_at: mapping::parse_ts(&announcement.created_at),
+            },
+        )))
     }
 
-    #[oai(path = "/users/me/invitations", method = "get")]
-    async fn list_my_invitations(
+    #[oai(path = "/teams/:team_id/seasons", method = "get")]
+    async fn list_team_seasons(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        /// Optionally filter to a single status (e.g. only `pending`).
-        Query(status): Query<Option<InvitationStatus>>,
-        /// Opaque cursor from the previous page's `next_cursor`.
-        Query(cursor): Query<Option<String>>,
-        /// Maximum number of items to return (defaults to 20, capped at 50).
-        Query(limit): Query<Option<u32>>,
-    ) -> Result<ListInvitationsResponse> {
-        info!("Listing current user's invitations");
+        Path(team_id): Path<String>,
+    ) -> Result<ListTeamSeasonsResponse> {
+        info!("Listing seasons for team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        let status_str = status.as_ref().map(invitation_status_str);
-        let page = dao
-            .list_user_invitations(&uid, status_str, cursor.as_deref(), page_limit(limit))
+        if self.visible_team(dao, &team_id, &uid).await?.is_none() {
+            return Ok(ListTeamSeasonsResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        }
+        let seasons = dao
+            .list_team_seasons(&team_id)
             .await
             .map_err(dao_internal)?;
-        let items = page
-            .items
-            .iter()
-            .map(invitation_detail_from_record)
-            .collect();
-        Ok(ListInvitationsResponse::Invitations(Json(InvitationPage {
-            items,
-            next_cursor: page.next_cursor,
-        })))
+        Ok(ListTeamSeasonsResponse::Seasons(Json(
+            seasons.iter().map(team_season_from_record).collect(),
+        )))
     }
 
-    #[oai(path = "/notifications", method = "get")]
-    async fn list_notifications(
+    #[oai(path = "/teams/:team_id/seasons/:season_id/squad", method = "get")]
+    async fn get_season_squad(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        /// Opaque cursor from the previous page's `next_cursor`.
-        Query(cursor): Query<Option<String>>,
-        /// Maximum number of items to return (defaults to 20, capped at 50).
-        Query(limit): Query<Option<u32>>,
-    ) -> Result<ListNotificationsResponse> {
+        Path(team_id): Path<String>,
+        Path(season_id): Path<String>,
+    ) -> Result<GetSeasonSquadResponse> {
+        info!("Getting squad for season {season_id} of team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Listing notifications for {uid}");
-        let page = dao
-            .list_notifications(&uid, cursor.as_deref(), page_limit(limit))
+        let Some(season) = dao
+            .get_team_season(&team_id, &season_id)
             .await
-            .map_err(dao_internal)?;
-
-        let mut items = Vec::with_capacity(page.items.len());
-        for rec in page.items {
-            // Hydrate the actor profile (every current kind has one).
-            let actor = self
-                .try_user_profile(dao, notification_actor_id(&rec.kind))
-                .await?
-                .unwrap_or_else(|| {
-                    user_profile_from_record(
-                        &dao::records::UserRecord {
-                            id: notification_actor_id(&rec.kind).to_string(),
-                            email: String::new(),
-                            name: String::new(),
-                            profile_image_url: None,
-                            follower_count: 0,
-                            following_count: 0,
-                            unread_count: 0,
-                            stats: std::collections::HashMap::new(),
-                            created_at: String::new(),
-                        },
-                        false,
-                    )
-                });
-            items.push(notification_from_record(&rec, actor));
-        }
-
-        Ok(ListNotificationsResponse::Notifications(Json(
-            NotificationPage {
-                items,
-                next_cursor: page.next_cursor,
-            },
-        )))
+            .map_err(dao_internal)?
+        else {
+            return Ok(GetSeasonSquadResponse::NotFound(PlainText(
+                "season not found".into(),
+            )));
+        };
+        let Some(agg) = self.visible_team(dao, &team_id, &uid).await? else {
+            return Ok(GetSeasonSquadResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let mut squad: Vec<SeasonSquadMember> = agg
+            .members
+            .iter()
+            .chain(&agg.former_members)
+            .filter_map(|m| season_squad_member_from_record(m, &season))
+            .collect();
+        squad.sort_by_key(|m| m.joined_at);
+        Ok(GetSeasonSquadResponse::Squad(Json(squad)))
     }
 
-    #[oai(path = "/notifications/unread-count", method = "get")]
-    async fn notifications_unread_count(
+    #[oai(path = "/teams/:team_id/seasons/:season_id/stats", method = "get")]
+    async fn get_season_stats(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-    ) -> Result<UnreadCountResponse> {
+        Path(team_id): Path<String>,
+        Path(season_id): Path<String>,
+    ) -> Result<GetSeasonStatsResponse> {
+        info!("Getting stats for season {season_id} of team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Getting unread notification count for {uid}");
-        let count = dao
-            .unread_notification_count(&uid)
+        if self.visible_team(dao, &team_id, &uid).await?.is_none() {
+            return Ok(GetSeasonStatsResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        }
+        let Some(season) = dao
+            .get_team_season(&team_id, &season_id)
+            .await
+            .map_err(dao_internal)?
+        else {
+            return Ok(GetSeasonStatsResponse::NotFound(PlainText(
+                "season not found".into(),
+            )));
+        };
+
+        let matches = dao
+            .list_season_matches(&team_id, &season)
             .await
             .map_err(dao_internal)?;
-        Ok(UnreadCountResponse::Count(Json(UnreadCount {
-            unread_count: count as u32,
+        let refs: Vec<(&str, &dao::match_ops::MatchAggregate)> = matches
+            .iter()
+            .map(|(side, agg)| (side.as_str(), agg))
+            .collect();
+        let stats = dao::season::season_stats(&season, &refs);
+
+        let mut players = Vec::with_capacity(stats.players.len());
+        for line in stats.players {
+            let (user, display_name) = match line.player {
+                dao::season::SeasonPlayerKey::User(user_id) => (
+                    Some(self.user_profile_or_placeholder(dao, &user_id).await?),
+                    None,
+                ),
+                dao::season::SeasonPlayerKey::External(name) => (None, Some(name)),
+            };
+            players.push(SeasonPlayerStats {
+                user,
+                display_name,
+                appearances: line.appearances,
+                scored: line.scored,
+            });
+        }
+        Ok(GetSeasonStatsResponse::Stats(Json(TeamSeasonStats {
+            season: team_season_from_record(&season),
+            played: stats.played,
+            won: stats.won,
+            drawn: stats.drawn,
+            lost: stats.lost,
+            players,
         })))
     }
 
-    #[oai(path = "/notifications/read", method = "post")]
-    async fn mark_all_notifications_read(
+    #[oai(path = "/teams/:team_id/invite-links", method = "post")]
+    async fn create_invite_link(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-    ) -> Result<MarkNotificationReadResponse> {
+        Path(team_id): Path<String>,
+        input: Json<CreateInviteLinkInput>,
+    ) -> Result<CreateInviteLinkResponse> {
+        info!("Creating invite link for team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Marking all notifications read for {uid}");
-        dao.mark_all_notifications_read(&uid)
-            .await
-            .map_err(dao_internal)?;
-        Ok(MarkNotificationReadResponse::Ok)
+        let input = input.0;
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(CreateInviteLinkResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageInviteLinks) {
+            return Ok(CreateInviteLinkResponse::Forbidden(denied.into()));
+        }
+        if input.name.trim().is_empty() {
+            return Ok(CreateInviteLinkResponse::ValidationError(PlainText(
+                "name must not be empty".into(),
+            )));
+        }
+        if input.max_uses == Some(0) {
+            return Ok(CreateInviteLinkResponse::ValidationError(PlainText(
+                "max_uses must be at least 1".into(),
+            )));
+        }
+        if input.expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+            return Ok(CreateInviteLinkResponse::ValidationError(PlainText(
+                "expires_at must be in the future".into(),
+            )));
+        }
+
+        let now = now_iso();
+        let token = new_id();
+        let rec = dao::records::InvitationRecord {
+            id: new_id(),
+            status: String::from("pending"),
+            invited_by_user_id: uid,
+            invited_user_id: None,
+            invite_token: Some(token.clone()),
+            invitee_email: None,
+            kind: dao::records::InvitationKindRecord::Token {
+                invite_token: token,
+            },
+            context: dao::records::InvitationContextRecord::Team {
+                team_id: agg.team.id.clone(),
+                team_name: agg.team.name.clone(),
+            },
+            invited_at: now.clone(),
+            responded_at: None,
+            link: Some(dao::records::InviteLinkRecord {
+                name: input.name,
+                // Same fixed-width form as `now_iso()`, so the DAO's string
+                // comparison against `now` orders correctly.
+                expires_at: input
+                    .expires_at
+                    .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+                max_uses: input.max_uses,
+                use_count: 0,
+                default_role: team_role_str(&input.default_role.unwrap_or(TeamRole::Member))
+                    .to_string(),
+                requires_approval: input.requires_approval,
+            }),
+        };
+        dao.create_invitation(&rec).await.map_err(dao_internal)?;
+        match invite_link_from_record(&rec, &now) {
+            Some(link) => Ok(CreateInviteLinkResponse::InviteLink(Json(link))),
+            None => Err(Error::from_string(
+                "invite link record did not map",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
     }
 
-    #[oai(path = "/notifications/:notification_id/read", method = "post")]
-    async fn mark_notification_read(
+    #[oai(path = "/teams/:team_id/invite-links", method = "get")]
+    async fn list_invite_links(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(notification_id): Path<String>,
-    ) -> Result<MarkNotificationReadResponse> {
-        info!("Marking notification {notification_id} read");
+        Path(team_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListInviteLinksResponse> {
+        info!("Listing invite links for team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        dao.mark_notification_read(&uid, &notification_id)
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(ListInviteLinksResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageInviteLinks) {
+            return Ok(ListInviteLinksResponse::Forbidden(denied.into()));
+        }
+        let page = dao
+            .list_team_invite_links(&team_id, cursor.as_deref(), page_limit(limit))
             .await
             .map_err(dao_internal)?;
-        Ok(MarkNotificationReadResponse::Ok)
+        let now = now_iso();
+        Ok(ListInviteLinksResponse::InviteLinks(Json(InviteLinkPage {
+            items: page
+                .items
+                .iter()
+                .filter_map(|rec| invite_link_from_record(rec, &now))
+                .collect(),
+            next_cursor: page.next_cursor,
+        })))
     }
 
-    #[oai(path = "/devices", method = "post")]
-    async fn register_device(
+    #[oai(
+        path = "/teams/:team_id/invite-links/:invite_link_id",
+        method = "delete"
+    )]
+    async fn revoke_invite_link(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        input: Json<RegisterDeviceInput>,
-    ) -> Result<RegisterDeviceResponse> {
+        Path(team_id): Path<String>,
+        Path(invite_link_id): Path<String>,
+    ) -> Result<RevokeInviteLinkResponse> {
+        info!("Revoking invite link {invite_link_id} on team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Registering device for {uid}");
-        let now = chrono::Utc::now().to_rfc3339();
-        match dao
-            .register_device(
-                &uid,
-                &input.push_token,
-                device_platform_to_record(&input.platform),
-                &now,
-            )
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(RevokeInviteLinkResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageInviteLinks) {
+            return Ok(RevokeInviteLinkResponse::Forbidden(denied.into()));
+        }
+        // Must be a link on *this* team — not any invitation id the caller knows.
+        let is_team_link = dao
+            .get_invitation(&invite_link_id)
             .await
-        {
-            Ok(()) => Ok(RegisterDeviceResponse::Ok),
-            Err(dao::DaoError::Conflict(msg)) => {
-                Ok(RegisterDeviceResponse::ValidationError(PlainText(msg)))
-            }
-            Err(e) => Err(dao_internal(e)),
+            .map_err(dao_internal)?
+            .is_some_and(|rec| {
+                rec.link.is_some()
+                    && matches!(
+                        &rec.context,
+                        dao::records::InvitationContextRecord::Team { team_id: t, .. } if *t == team_id
+                    )
+            });
+        if !is_team_link {
+            return Ok(RevokeInviteLinkResponse::NotFound(PlainText(
+                "invite link not found".into(),
+            )));
         }
-    }
-
-    #[oai(path = "/devices/unregister", method = "post")]
-    async fn unregister_device(
-        &self,
-        Data(dao): Data<&dao::Dao>,
-        AuthSchema(jwt_data): AuthSchema,
-        input: Json<UnregisterDeviceInput>,
-    ) -> Result<UnregisterDeviceResponse> {
-        let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Unregistering device for {uid}");
-        match dao.delete_device(&uid, &input.push_token).await {
-            Ok(()) => Ok(UnregisterDeviceResponse::Ok),
-            Err(dao::DaoError::NotFound(_)) => Ok(UnregisterDeviceResponse::NotFound(PlainText(
-                "device not registered".into(),
+        match dao.delete_invitation(&invite_link_id).await {
+            Ok(()) => Ok(RevokeInviteLinkResponse::Ok),
+            Err(dao::DaoError::NotFound(_)) => Ok(RevokeInviteLinkResponse::NotFound(PlainText(
+                "invite link not found".into(),
             ))),
             Err(e) => Err(dao_internal(e)),
         }
     }
 
-    #[oai(path = "/invitations/:invitation_id", method = "get")]
-    async fn get_invitation(
+    #[oai(path = "/invite-links/redeem", method = "post")]
+    async fn redeem_invite_link(
         &self,
         Data(dao): Data<&dao::Dao>,
-        AuthSchema(_jwt_data): AuthSchema,
-        Path(invitation_id): Path<String>,
-    ) -> Result<GetInvitationResponse> {
-        info!("Getting invitation {invitation_id}");
-        match dao
-            .get_invitation(&invitation_id)
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<RedeemInviteLinkInput>,
+    ) -> Result<RedeemInviteLinkResponse> {
+        info!("Redeeming invite link");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let rec = match dao
+            .get_invitation_by_token(&input.invite_token)
             .await
             .map_err(dao_internal)?
         {
-            Some(rec) => Ok(GetInvitationResponse::Invitation(Json(
-                invitation_detail_from_record(&rec),
-            ))),
-            None => Ok(GetInvitationResponse::NotFound(PlainText(
-                "invitation not found".into(),
-            ))),
+            Some(rec) if rec.link.is_some() => rec,
+            _ => {
+                return Ok(RedeemInviteLinkResponse::NotFound(PlainText(
+                    "no invite link matches that token".into(),
+                )));
+            }
+        };
+        let (Some(link), dao::records::InvitationContextRecord::Team { team_id, .. }) =
+            (&rec.link, &rec.context)
+        else {
+            return Ok(RedeemInviteLinkResponse::NotFound(PlainText(
+                "no invite link matches that token".into(),
+            )));
+        };
+        let Some(agg) = dao
+            .get_team(team_id)
+            .await
+            .map_err(dao_internal)?
+            .filter(|agg| agg.team.archived_at.is_none())
+        else {
+            return Ok(RedeemInviteLinkResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        if agg
+            .members
+            .iter()
+            .any(|m| m.user_id.as_deref() == Some(uid.as_str()))
+        {
+            return Ok(RedeemInviteLinkResponse::ValidationError(PlainText(
+                "you are already a member of this team".into(),
+            )));
         }
-    }
 
-    /// Preview an invitation by its bearer token. Public (no auth): the token is
-    /// itself the credential, so anyone holding an invite link can see what it is
-    /// an invite *to* before signing in. Used by the invite-link landing/accept
-    /// flow to render context and to know which match/team to open on acceptance.
-    #[oai(path = "/invitations/by-token/:token", method = "get")]
-    async fn get_invitation_by_token(
-        &self,
-        Data(dao): Data<&dao::Dao>,
-        Path(token): Path<String>,
-    ) -> Result<GetInvitationResponse> {
-        info!("Getting invitation by token");
-        match dao
-            .get_invitation_by_token(&token)
+        let now = now_iso();
+        let conflict = |e: dao::DaoError| match e {
+            dao::DaoError::Conflict(msg) => Ok(msg),
+            other => Err(dao_internal(other)),
+        };
+        if link.requires_approval {
+            let request = dao::records::JoinRequestRecord {
+                id: new_id(),
+                team_id: team_id.clone(),
+                user_id: uid.clone(),
+                status: String::from("pending"),
+                role: link.default_role.clone(),
+                via_invitation_id: Some(rec.id.clone()),
+                requested_at: now.clone(),
+                responded_at: None,
+                responded_by_user_id: None,
+            };
+            if let Err(e) = dao
+                .redeem_invite_link(
+                    &rec.id,
+                    &now,
+                    dao::team_join::LinkRedemption::Request(&request),
+                )
+                .await
+            {
+                return Ok(RedeemInviteLinkResponse::ValidationError(PlainText(
+                    conflict(e)?,
+                )));
+            }
+            let requester = self.user_profile_or_placeholder(dao, &uid).await?;
+            return Ok(RedeemInviteLinkResponse::JoinRequest(Json(
+                join_request_from_record(&request, requester),
+            )));
+        }
+
+        let member = dao::records::TeamMemberRecord {
+            team_id: team_id.clone(),
+            membership_id: new_id(),
+            user_id: Some(uid.clone()),
+            display_name: None,
+            role: link.default_role.clone(),
+            invitation: None,
+            created_at: now.clone(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        };
+        if let Err(e) = dao
+            .redeem_invite_link(&rec.id, &now, dao::team_join::LinkRedemption::Join(&member))
             .await
-            .map_err(dao_internal)?
         {
-            Some(rec) => Ok(GetInvitationResponse::Invitation(Json(
-                invitation_detail_from_record(&rec),
-            ))),
-            None => Ok(GetInvitationResponse::NotFound(PlainText(
-                "invitation not found".into(),
-            ))),
+            return Ok(RedeemInviteLinkResponse::ValidationError(PlainText(
+                conflict(e)?,
+            )));
         }
+        let mut members = agg.members;
+        members.push(member);
+        let is_followed_by_me = dao
+            .is_following_team(&uid, team_id)
+            .await
+            .map_err(dao_internal)?;
+        Ok(RedeemInviteLinkResponse::Team(Json(team_from_records(
+            &agg.team,
+            &members,
+            is_followed_by_me,
+        ))))
     }
 
-    #[oai(path = "/invitations/:invitation_id", method = "delete")]
-    async fn revoke_invitation(
+    #[oai(path = "/teams/:team_id/join-requests", method = "post")]
+    async fn create_join_request(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(invitation_id): Path<String>,
-    ) -> Result<RevokeInvitationResponse> {
-        info!("Revoking invitation {invitation_id}");
+        Path(team_id): Path<String>,
+    ) -> Result<CreateJoinRequestResponse> {
+        info!("Requesting to join team {team_id}");
         let uid = self.require_uid(dao, &jwt_data).await?;
-        match dao
-            .get_invitation(&invitation_id)
+        let Some(agg) = dao
+            .get_team(&team_id)
             .await
             .map_err(dao_internal)?
+            .filter(|agg| agg.team.archived_at.is_none())
+        else {
+            return Ok(CreateJoinRequestResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        if agg
+            .members
+            .iter()
+            .any(|m| m.user_id.as_deref() == Some(uid.as_str()))
         {
-            None => {
-                return Ok(RevokeInvitationResponse::NotFound(PlainText(
-                    "invitation not found".into(),
-                )));
-            }
-            Some(rec) if rec.invited_by_user_id != uid => {
-                return Ok(RevokeInvitationResponse::Forbidden(PlainText(
-                    "only the inviter can revoke this invitation".into(),
-                )));
-            }
-            Some(_) => {}
+            return Ok(CreateJoinRequestResponse::ValidationError(PlainText(
+                "you are already a member of this team".into(),
+            )));
         }
-        match dao.delete_invitation(&invitation_id).await {
-            Ok(()) => Ok(RevokeInvitationResponse::Ok),
-            // Revoked by a concurrent request between the check above and here.
-            Err(dao::DaoError::NotFound(_)) => Ok(RevokeInvitationResponse::NotFound(PlainText(
-                "invitation not found".into(),
-            ))),
-            Err(e) => Err(dao_internal(e)),
+        let request = dao::records::JoinRequestRecord {
+            id: new_id(),
+            team_id: team_id.clone(),
+            user_id: uid.clone(),
+            status: String::from("pending"),
+            role: String::from("member"),
+            via_invitation_id: None,
+            requested_at: now_iso(),
+            responded_at: None,
+            responded_by_user_id: None,
+        };
+        match dao.create_join_request(&request).await {
+            Ok(()) => {}
+            Err(dao::DaoError::Conflict(msg)) => {
+                return Ok(CreateJoinRequestResponse::ValidationError(PlainText(msg)));
+            }
+            Err(e) => return Err(dao_internal(e)),
         }
+        let requester = self.user_profile_or_placeholder(dao, &uid).await?;
+        Ok(CreateJoinRequestResponse::JoinRequest(Json(
+            join_request_from_record(&request, requester),
+        )))
     }
 
-    #[oai(path = "/invitations/:invitation_id/respond", method = "post")]
-    async fn respond_to_invitation(
+    #[oai(path = "/teams/:team_id/join-requests", method = "get")]
+    async fn list_join_requests(
         &self,
+        Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(invitation_id): Path<String>,
+        Path(team_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListJoinRequestsResponse> {
+        info!("Listing join requests for team {team_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(ListJoinRequestsResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageJoinRequests) {
+            return Ok(ListJoinRequestsResponse::Forbidden(denied.into()));
+        }
+        let page = dao
+            .list_pending_join_requests(&team_id, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        // N+1 requester hydration; batch later.
+        let mut items = Vec::with_capacity(page.items.len());
+        for rec in &page.items {
+            let requester = self.user_profile_or_placeholder(dao, &rec.user_id).await?;
+            items.push(join_request_from_record(rec, requester));
+        }
+        Ok(ListJoinRequestsResponse::JoinRequests(Json(
+            JoinRequestPage {
+                items,
+                next_cursor: page.next_cursor,
+            },
+        )))
+    }
+
+    #[oai(
+        path = "/teams/:team_id/join-requests/:user_id/respond",
+        method = "post"
+    )]
+    async fn respond_to_join_request(
+        &self,
         Data(dao): Data<&dao::Dao>,
-        input: Json<RespondToInvitationInput>,
-    ) -> Result<RespondToInvitationResponse> {
+        AuthSchema(jwt_data): AuthSchema,
+        Path(team_id): Path<String>,
+        Path(user_id): Path<String>,
+        input: Json<RespondToJoinRequestInput>,
+    ) -> Result<RespondToJoinRequestResponse> {
         info!(
-            "Responding to invitation {invitation_id}: {:?}",
+            "Responding to {user_id}'s request to join team {team_id}: {:?}",
             input.response
         );
-
         let uid = self.require_uid(dao, &jwt_data).await?;
-        let rec = match dao
-            .get_invitation(&invitation_id)
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(RespondToJoinRequestResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::ManageJoinRequests) {
+            return Ok(RespondToJoinRequestResponse::Forbidden(denied.into()));
+        }
+        let mut request = match dao
+            .get_join_request(&team_id, &user_id)
             .await
             .map_err(dao_internal)?
         {
-            Some(r) => r,
-            None => {
-                return Ok(RespondToInvitationResponse::NotFound(PlainText(
-                    "invitation not found".into(),
+            Some(r) if r.status == "pending" => r,
+            _ => {
+                return Ok(RespondToJoinRequestResponse::NotFound(PlainText(
+                    "no pending join request from that user".into(),
                 )));
             }
         };
-        // Only the targeted user may respond (user-kind invitation).
-        if rec.invited_user_id.as_deref() != Some(uid.as_str()) {
-            return Ok(RespondToInvitationResponse::Forbidden(PlainText(
-                "this invitation is not addressed to you".into(),
-            )));
-        }
 
-        let responded_at = now_iso();
-        let status = match input.0.response {
-            // Accept synchronously and atomically: bind the accepter to the
-            // invitation, link the roster entry, and (match) write the accepter's
-            // own feed row so the game is on their feed immediately. Follower
-            // fan-out + notification happen async off the resulting stream event.
-            membership::InvitationResponse::Accepted => {
-                dao.accept_invitation_tx(&invitation_id, &uid, &responded_at, &responded_at)
-                    .await
-                    .map_err(|e| match e {
-                        dao::DaoError::NotFound(_) => {
-                            Error::from_string("not found", StatusCode::NOT_FOUND)
-                        }
-                        other => dao_internal(other),
-                    })?;
-                "accepted"
+        let now = now_iso();
+        let member = match input.0.response {
+            JoinRequestResponse::Approved => Some(dao::records::TeamMemberRecord {
+                team_id: team_id.clone(),
+                membership_id: new_id(),
+                user_id: Some(user_id.clone()),
+                display_name: None,
+                role: request.role.clone(),
+                invitation: None,
+                created_at: now.clone(),
+                left_at: None,
+                previous_roles: Vec::new(),
+            }),
+            JoinRequestResponse::Denied => None,
+        };
+        match dao
+            .respond_to_join_request(&team_id, &user_id, &uid, &now, member.as_ref())
+            .await
+        {
+            Ok(()) => {}
+            Err(dao::DaoError::NotFound(_)) => {
+                return Ok(RespondToJoinRequestResponse::NotFound(PlainText(
+                    "no pending join request from that user".into(),
+                )));
             }
-            membership::InvitationResponse::Declined => {
-                dao.respond_to_invitation(
-                    &invitation_id,
-                    "declined",
-                    &responded_at,
-                    &rec.invited_at,
-                    true, // has a user inbox (GSI1) to realign
-                )
-                .await
-                .map_err(|e| match e {
-                    dao::DaoError::NotFound(_) => {
-                        Error::from_string("not found", StatusCode::NOT_FOUND)
-                    }
-                    other => dao_internal(other),
-                })?;
-                "declined"
+            Err(dao::DaoError::Conflict(_)) => {
+                return Ok(RespondToJoinRequestResponse::Conflict(PlainText(
+                    "that user is already a member of this team".into(),
+                )));
             }
-        };
+            Err(e) => return Err(dao_internal(e)),
+        }
 
-        let mut invitation = invitation_from_record(&rec);
-        invitation.status = invitation_status_from_str(status);
-        invitation.responded_at = Some(mapping::parse_ts(&responded_at));
-        Ok(RespondToInvitationResponse::Invitation(Json(invitation)))
+        request.status = String::from(if member.is_some() {
+            "approved"
+        } else {
+            "denied"
+        });
+        request.responded_at = Some(now);
+        request.responded_by_user_id = Some(uid);
+        let requester = self.user_profile_or_placeholder(dao, &user_id).await?;
+        Ok(RespondToJoinRequestResponse::JoinRequest(Json(
+            join_request_from_record(&request, requester),
+        )))
     }
 
-    #[oai(path = "/invitations/respond-by-token", method = "post")]
-    async fn respond_to_invitation_by_token(
+    #[oai(path = "/matches/:match_id/invitations", method = "post")]
+    async fn add_match_invitations(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        input: Json<RespondByTokenInput>,
-    ) -> Result<RespondByTokenResponse> {
-        info!("Responding to invitation by token: {:?}", input.response);
+        Path(match_id): Path<String>,
+        input: Json<AddInvitationsInput>,
+    ) -> Result<AddInvitationsResponse> {
+        info!("Inviting to match {match_id}");
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
         let input = input.0;
 
-        // The caller (holder of the invite link) becomes the accepting account.
-        // Resolving their uid is what lets a bare-token invite be linked to a
-        // real user — without it the acceptance can't be attributed and never
-        // reaches the roster / feed / stats.
-        let uid = self.require_uid(dao, &jwt_data).await?;
-
-        let rec = match dao
-            .get_invitation_by_token(&input.invite_token)
-            .await
-            .map_err(dao_internal)?
-        {
-            Some(r) => r,
+        // Match must exist.
+        let agg = match dao.get_match(&match_id).await.map_err(dao_internal)? {
+            Some(a) => a,
             None => {
-                return Ok(RespondByTokenResponse::NotFound(PlainText(
-                    "no invitation matches that token".into(),
+                return Ok(AddInvitationsResponse::NotFound(PlainText(
+                    "match not found".into(),
                 )));
             }
         };
 
-        let responded_at = now_iso();
-        let status = match input.response {
-            // Accept synchronously: bind this account onto the (previously
-            // userless) token invitation, link the roster entry, and write the
-            // accepter's own feed row. Follower fan-out follows async.
-            membership::InvitationResponse::Accepted => {
-                dao.accept_invitation_tx(&rec.id, &uid, &responded_at, &responded_at)
-                    .await
-                    .map_err(|e| match e {
-                        dao::DaoError::NotFound(_) => {
-                            Error::from_string("not found", StatusCode::NOT_FOUND)
-                        }
-                        other => dao_internal(other),
-                    })?;
-                "accepted"
-            }
-            membership::InvitationResponse::Declined => {
-                dao.respond_to_invitation(
-                    &rec.id,
-                    "declined",
-                    &responded_at,
-                    &rec.invited_at,
-                    false,
-                )
-                .await
-                .map_err(|e| match e {
-                    dao::DaoError::NotFound(_) => {
-                        Error::from_string("not found", StatusCode::NOT_FOUND)
-                    }
-                    other => dao_internal(other),
-                })?;
-                "declined"
-            }
+        // The creator (organizer) or a participant may invite others.
+        if let Err(denied) =
+            policy::authorize_match(MatchStanding::of(&agg, &uid), MatchAction::Invite)
+        {
+            return Ok(AddInvitationsResponse::Forbidden(denied.into()));
+        }
+
+        // If a side was named, it must be one of this match's sides.
+        if let Some(side_id) = &input.side_id
+            && !agg.sides.iter().any(|s| &s.side_id == side_id)
+        {
+            return Ok(AddInvitationsResponse::NotFound(PlainText(
+                "side is not part of this match".into(),
+            )));
+        }
+        if self.any_blocked(dao, &uid, &input.invited_user_ids).await? {
+            return Ok(AddInvitationsResponse::Forbidden(PlainText(
+                BLOCKED_INVITEE.into(),
+            )));
+        }
+        let externals = match external_invitees(&input) {
+            Ok(externals) => externals,
+            Err(msg) => return Ok(AddInvitationsResponse::ValidationError(PlainText(msg))),
         };
 
-        let mut invitation = invitation_from_record(&rec);
-        invitation.status = invitation_status_from_str(status);
-        invitation.responded_at = Some(mapping::parse_ts(&responded_at));
-        Ok(RespondByTokenResponse::Invitation(Json(invitation)))
-    }
+        // Each invitee gets both a roster slot (with an embedded invitation) and
+        // a standalone invitation, exactly like a create-time invite — so they
+        // show up on the match roster immediately, pre-assigned to `side_id` if
+        // given. `build_invited_player` mints the ids and tokens.
+        let now = now_iso();
+        let invitees = input
+            .invited_user_ids
+            .iter()
+            .map(|u| (Some(u.clone()), None, None))
+            .chain(
+                externals
+                    .into_iter()
+                    .map(|(name, email)| (None, Some(name), email)),
+            );
+
+        let mut created = Vec::new();
+        for (user_id, display_name, invitee_email) in invitees {
+            let (player, mut invitation) = build_invited_player(
+                &match_id,
+                &agg.match_.name,
+                &uid,
+                input.side_id.clone(),
+                user_id,
+                display_name,
+                &now,
+            );
+            invitation.invitee_email = invitee_email;
+            dao.put_match_player(&match_id, &player)
+                .await
+                .map_err(dao_internal)?;
+            dao.create_invitation(&invitation)
+                .await
+                .map_err(dao_internal)?;
+            created.push(invitation_from_record(&invitation));
+        }
+        // New roster slots move players onto sides — refresh each side's
+        // cached roster preview (see `PATCH /matches/:match_id`'s roster
+        // block for the same call).
+        if !created.is_empty() {
+            dao.refresh_side_roster_previews(&match_id)
+                .await
+                .map_err(dao_internal)?;
+        }
+
+        // Roster/player writes don't trigger the stream (only `#META` does), so
+        // touch the match meta to re-run fan-out / search indexing with the new
+        // roster. Re-writing `name` to its current value is a real write to the
+        // item (an all-`None` update would no-op), so it emits a stream record.
+        if !created.is_empty() {
+            dao.update_match_meta(
+                &match_id,
+                Some(&agg.match_.name),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .map_err(dao_internal)?;
+        }
+
+        Ok(AddInvitationsResponse::Invitations(Json(created)))
+    }
+
+    #[oai(path = "/teams/:team_id/invitations", method = "post")]
+    async fn add_team_invitations(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(team_id): Path<String>,
+        input: Json<AddInvitationsInput>,
+    ) -> Result<AddInvitationsResponse> {
+        info!("Inviting to team {team_id}");
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let agg = match dao.get_team(&team_id).await.map_err(dao_internal)? {
+            Some(agg) => agg,
+            None => {
+                return Ok(AddInvitationsResponse::NotFound(PlainText(
+                    "team not found".into(),
+                )));
+            }
+        };
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::Invite) {
+            return Ok(AddInvitationsResponse::Forbidden(denied.into()));
+        }
+        if self.any_blocked(dao, &uid, &input.invited_user_ids).await? {
+            return Ok(AddInvitationsResponse::Forbidden(PlainText(
+                BLOCKED_INVITEE.into(),
+            )));
+        }
+        let externals = match external_invitees(&input) {
+            Ok(externals) => externals,
+            Err(msg) => return Ok(AddInvitationsResponse::ValidationError(PlainText(msg))),
+        };
+        let ctx = dao::records::InvitationContextRecord::Team {
+            team_id: team_id.clone(),
+            team_name: agg.team.name,
+        };
+        let created = self
+            .create_invitations(dao, &uid, ctx, &input.invited_user_ids, &externals)
+            .await?;
+        // TODO: also create the TeamMember slot per invitee — deferred.
+        Ok(AddInvitationsResponse::Invitations(Json(created)))
+    }
+
+    /// Create one invitation per invitee (users by id → user-kind; external
+    /// people, with the email to send their link to if given → token-kind),
+    /// persist them, and return the API models.
+    async fn create_invitations(
+        &self,
+        dao: &dao::Dao,
+        inviter_id: &str,
+        context: dao::records::InvitationContextRecord,
+        invited_user_ids: &[String],
+        externals: &[(String, Option<String>)],
+    ) -> Result<Vec<Invitation>> {
+        let now = now_iso();
+        let mut created = Vec::new();
+
+        for user_id in invited_user_ids {
+            let rec = dao::records::InvitationRecord {
+                id: new_id(),
+                status: String::from("pending"),
+                invited_by_user_id: inviter_id.to_string(),
+                invited_user_id: Some(user_id.clone()),
+                invite_token: None,
+                invitee_email: None,
+                kind: dao::records::InvitationKindRecord::User {
+                    invited_user_id: user_id.clone(),
+                },
+                context: context.clone(),
+                invited_at: now.clone(),
+                responded_at: None,
+                link: None,
+            };
+            dao.create_invitation(&rec).await.map_err(dao_internal)?;
+            created.push(invitation_from_record(&rec));
+        }
+
+        for (_name, email) in externals {
+            let token = new_id();
+            let rec = dao::records::InvitationRecord {
+                id: new_id(),
+                status: String::from("pending"),
+                invited_by_user_id: inviter_id.to_string(),
+                invited_user_id: None,
+                invite_token: Some(token.clone()),
+                invitee_email: email.clone(),
+                kind: dao::records::InvitationKindRecord::Token {
+                    invite_token: token,
+                },
+                context: context.clone(),
+                invited_at: now.clone(),
+                responded_at: None,
+                link: None,
+            };
+            dao.create_invitation(&rec).await.map_err(dao_internal)?;
+            created.push(invitation_from_record(&rec));
+        }
+
+        Ok(created)
+    }
+
+    #[oai(path = "/users/me/invitations", method = "get")]
+    async fn list_my_invitations(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Optionally filter to a single status (e.g. only `pending`).
+        Query(status): Query<Option<InvitationStatus>>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListInvitationsResponse> {
+        info!("Listing current user's invitations");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let status_str = status.as_ref().map(invitation_status_str);
+        let page = dao
+            .list_user_invitations(&uid, status_str, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let items = page
+            .items
+            .iter()
+            .map(invitation_detail_from_record)
+            .collect();
+        Ok(ListInvitationsResponse::Invitations(Json(InvitationPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    #[oai(path = "/notifications", method = "get")]
+    async fn list_notifications(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListNotificationsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing notifications for {uid}");
+        let page = dao
+            .list_notifications(&uid, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+
+        let mut items = Vec::with_capacity(page.items.len());
+        for rec in page.items {
+            // Hydrate the actor profile (every current kind has one).
+            let actor = self
+                .user_profile_or_placeholder(dao, notification_actor_id(&rec.kind))
+                .await?;
+            items.push(notification_from_record(&rec, actor));
+        }
+
+        Ok(ListNotificationsResponse::Notifications(Json(
+            NotificationPage {
+                items,
+                next_cursor: page.next_cursor,
+            },
+        )))
+    }
+
+    #[oai(path = "/notifications/unread-count", method = "get")]
+    async fn notifications_unread_count(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+    ) -> Result<UnreadCountResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Getting unread notification count for {uid}");
+        let count = dao
+            .unread_notification_count(&uid)
+            .await
+            .map_err(dao_internal)?;
+        Ok(UnreadCountResponse::Count(Json(UnreadCount {
+            unread_count: count as u32,
+        })))
+    }
+
+    #[oai(path = "/notifications/read", method = "post")]
+    async fn mark_all_notifications_read(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+    ) -> Result<MarkNotificationReadResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Marking all notifications read for {uid}");
+        dao.mark_all_notifications_read(&uid)
+            .await
+            .map_err(dao_internal)?;
+        Ok(MarkNotificationReadResponse::Ok)
+    }
+
+    #[oai(path = "/notifications/:notification_id/read", method = "post")]
+    async fn mark_notification_read(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(notification_id): Path<String>,
+    ) -> Result<MarkNotificationReadResponse> {
+        info!("Marking notification {notification_id} read");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        dao.mark_notification_read(&uid, &notification_id)
+            .await
+            .map_err(dao_internal)?;
+        Ok(MarkNotificationReadResponse::Ok)
+    }
+
+    /// The caller's notification preferences (the defaults if never saved).
+    #[oai(path = "/users/me/notification-preferences", method = "get")]
+    async fn get_notification_preferences(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+    ) -> Result<NotificationPreferencesResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Getting notification preferences for {uid}");
+        let prefs = dao
+            .get_notification_preferences(&uid)
+            .await
+            .map_err(dao_internal)?;
+        Ok(NotificationPreferencesResponse::Preferences(Json(
+            notification_preferences_from_record(&prefs),
+        )))
+    }
+
+    /// Replace the caller's notification preferences.
+    #[oai(path = "/users/me/notification-preferences", method = "put")]
+    async fn put_notification_preferences(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<NotificationPreferences>,
+    ) -> Result<NotificationPreferencesResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Saving notification preferences for {uid}");
+        let input = input.0;
+
+        let mut channels = Vec::with_capacity(input.channels.len());
+        for c in &input.channels {
+            let kind = notification_type_to_record(c.kind);
+            if channels
+                .iter()
+                .any(|seen: &dao::records::NotificationChannelsRecord| seen.kind == kind)
+            {
+                return Ok(NotificationPreferencesResponse::ValidationError(PlainText(
+                    format!("{:?} is listed more than once", c.kind),
+                )));
+            }
+            channels.push(dao::records::NotificationChannelsRecord {
+                kind,
+                in_app: c.in_app,
+                push: c.push,
+                email: c.email,
+            });
+        }
+        let dedup = |ids: Vec<String>| {
+            let mut ids: Vec<String> = ids.into_iter().filter(|id| !id.is_empty()).collect();
+            ids.sort();
+            ids.dedup();
+            ids
+        };
+        let muted_team_ids = dedup(input.muted_team_ids);
+        let muted_match_ids = dedup(input.muted_match_ids);
+        if muted_team_ids.len() > MAX_NOTIFICATION_MUTES
+            || muted_match_ids.len() > MAX_NOTIFICATION_MUTES
+        {
+            return Ok(NotificationPreferencesResponse::ValidationError(PlainText(
+                format!(
+                    "you can mute at most {MAX_NOTIFICATION_MUTES} teams and {MAX_NOTIFICATION_MUTES} matches"
+                ),
+            )));
+        }
+        let quiet_hours = match input.quiet_hours.as_ref().map(quiet_hours_to_record) {
+            Some(Err(msg)) => {
+                return Ok(NotificationPreferencesResponse::ValidationError(PlainText(
+                    msg,
+                )));
+            }
+            Some(Ok(q)) => Some(q),
+            None => None,
+        };
+
+        let prefs = dao::records::NotificationPreferencesRecord {
+            user_id: uid,
+            channels,
+            muted_team_ids,
+            muted_match_ids,
+            quiet_hours,
+            digest: digest_frequency_to_record(input.digest),
+            updated_at: now_iso(),
+        };
+        dao.put_notification_preferences(&prefs)
+            .await
+            .map_err(dao_internal)?;
+        Ok(NotificationPreferencesResponse::Preferences(Json(
+            notification_preferences_from_record(&prefs),
+        )))
+    }
+
+    #[oai(path = "/devices", method = "post")]
+    async fn register_device(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<RegisterDeviceInput>,
+    ) -> Result<RegisterDeviceResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Registering device for {uid}");
+        let now = chrono::Utc::now().to_rfc3339();
+        match dao
+            .register_device(
+                &uid,
+                &input.push_token,
+                device_platform_to_record(&input.platform),
+                &now,
+            )
+            .await
+        {
+            Ok(()) => Ok(RegisterDeviceResponse::Ok),
+            Err(dao::DaoError::Conflict(msg)) => {
+                Ok(RegisterDeviceResponse::ValidationError(PlainText(msg)))
+            }
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/devices/unregister", method = "post")]
+    async fn unregister_device(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<UnregisterDeviceInput>,
+    ) -> Result<UnregisterDeviceResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Unregistering device for {uid}");
+        match dao.delete_device(&uid, &input.push_token).await {
+            Ok(()) => Ok(UnregisterDeviceResponse::Ok),
+            Err(dao::DaoError::NotFound(_)) => Ok(UnregisterDeviceResponse::NotFound(PlainText(
+                "device not registered".into(),
+            ))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    /// An invitation with its match/team context. Only its invitee, its
+    /// inviter and whoever may invite to the same match or team (its admins)
+    /// can see it; to anyone else it reads as not found.
+    #[oai(path = "/invitations/:invitation_id", method = "get")]
+    async fn get_invitation(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(invitation_id): Path<String>,
+    ) -> Result<GetInvitationResponse> {
+        info!("Getting invitation {invitation_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let not_found = || {
+            Ok(GetInvitationResponse::NotFound(PlainText(
+                "invitation not found".into(),
+            )))
+        };
+        let Some(rec) = dao
+            .get_invitation(&invitation_id)
+            .await
+            .map_err(dao_internal)?
+        else {
+            return not_found();
+        };
+        let own =
+            rec.invited_user_id.as_deref() == Some(uid.as_str()) || rec.invited_by_user_id == uid;
+        let allowed = own
+            || match &rec.context {
+                dao::records::InvitationContextRecord::Match { match_id, .. } => {
+                    match dao.get_match(match_id).await.map_err(dao_internal)? {
+                        Some(agg) => policy::authorize_match(
+                            MatchStanding::of(&agg, &uid),
+                            MatchAction::Invite,
+                        )
+                        .is_ok(),
+                        None => false,
+                    }
+                }
+                dao::records::InvitationContextRecord::Team { team_id, .. } => {
+                    match dao.get_team(team_id).await.map_err(dao_internal)? {
+                        Some(agg) => {
+                            let standing = self.team_standing(dao, &agg, &uid).await?;
+                            policy::authorize_team(standing, TeamAction::Invite).is_ok()
+                        }
+                        None => false,
+                    }
+                }
+            };
+        if !allowed {
+            return not_found();
+        }
+        Ok(GetInvitationResponse::Invitation(Json(
+            invitation_detail_from_record(&rec),
+        )))
+    }
+
+    /// Preview an invitation by its bearer token. Public (no auth): the token is
+    /// itself the credential, so anyone holding an invite link can see what it is
+    /// an invite *to* before signing in. Used by the invite-link landing/accept
+    /// flow to render context and to know which match/team to open on acceptance.
+    #[oai(path = "/invitations/by-token/:token", method = "get")]
+    async fn get_invitation_by_token(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Path(token): Path<String>,
+    ) -> Result<GetInvitationResponse> {
+        info!("Getting invitation by token");
+        match dao
+            .get_invitation_by_token(&token)
+            .await
+            .map_err(dao_internal)?
+        {
+            Some(rec) => Ok(GetInvitationResponse::Invitation(Json(
+                invitation_detail_from_record(&rec),
+            ))),
+            None => Ok(GetInvitationResponse::NotFound(PlainText(
+                "invitation not found".into(),
+            ))),
+        }
+    }
+
+    #[oai(path = "/invitations/:invitation_id", method = "delete")]
+    async fn revoke_invitation(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(invitation_id): Path<String>,
+    ) -> Result<RevokeInvitationResponse> {
+        info!("Revoking invitation {invitation_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        match dao
+            .get_invitation(&invitation_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            None => {
+                return Ok(RevokeInvitationResponse::NotFound(PlainText(
+                    "invitation not found".into(),
+                )));
+            }
+            Some(rec) if rec.invited_by_user_id != uid => {
+                return Ok(RevokeInvitationResponse::Forbidden(PlainText(
+                    "only the inviter can revoke this invitation".into(),
+                )));
+            }
+            Some(_) => {}
+        }
+        match dao.delete_invitation(&invitation_id).await {
+            Ok(()) => Ok(RevokeInvitationResponse::Ok),
+            // Revoked by a concurrent request between the check above and here.
+            Err(dao::DaoError::NotFound(_)) => Ok(RevokeInvitationResponse::NotFound(PlainText(
+                "invitation not found".into(),
+            ))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/invitations/:invitation_id/respond", method = "post")]
+    async fn respond_to_invitation(
+        &self,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(invitation_id): Path<String>,
+        Data(dao): Data<&dao::Dao>,
+        input: Json<RespondToInvitationInput>,
+    ) -> Result<RespondToInvitationResponse> {
+        info!(
+            "Responding to invitation {invitation_id}: {:?}",
+            input.response
+        );
+
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let rec = match dao
+            .get_invitation(&invitation_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            Some(r) => r,
+            None => {
+                return Ok(RespondToInvitationResponse::NotFound(PlainText(
+                    "invitation not found".into(),
+                )));
+            }
+        };
+        // Only the targeted user may respond (user-kind invitation).
+        if rec.invited_user_id.as_deref() != Some(uid.as_str()) {
+            return Ok(RespondToInvitationResponse::Forbidden(PlainText(
+                "this invitation is not addressed to you".into(),
+            )));
+        }
+        // Blocking revokes pending invitations either way; one that raced the
+        // block reads as revoked too.
+        if dao
+            .is_blocked_between(&uid, &rec.invited_by_user_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            return Ok(RespondToInvitationResponse::NotFound(PlainText(
+                "invitation not found".into(),
+            )));
+        }
+
+        let responded_at = now_iso();
+        let status = match input.0.response {
+            // Accept synchronously and atomically: bind the accepter to the
+            // invitation, link the roster entry, and (match) write the accepter's
+            // own feed row so the game is on their feed immediately. Follower
+            // fan-out + notification happen async off the resulting stream event.
+            membership::InvitationResponse::Accepted => {
+                dao.accept_invitation_tx(&invitation_id, &uid, &responded_at, &responded_at)
+                    .await
+                    .map_err(|e| match e {
+                        dao::DaoError::NotFound(_) => {
+                            Error::from_string("not found", StatusCode::NOT_FOUND)
+                        }
+                        dao::DaoError::Conflict(_) => Error::from_string(
+                            "you are already a member of this team",
+                            StatusCode::CONFLICT,
+                        ),
+                        other => dao_internal(other),
+                    })?;
+                "accepted"
+            }
+            membership::InvitationResponse::Declined => {
+                dao.respond_to_invitation(
+                    &invitation_id,
+                    "declined",
+                    &responded_at,
+                    &rec.invited_at,
+                    true, // has a user inbox (GSI1) to realign
+                )
+                .await
+                .map_err(|e| match e {
+                    dao::DaoError::NotFound(_) => {
+                        Error::from_string("not found", StatusCode::NOT_FOUND)
+                    }
+                    other => dao_internal(other),
+                })?;
+                "declined"
+            }
+        };
+
+        let mut invitation = invitation_from_record(&rec);
+        invitation.status = invitation_status_from_str(status);
+        invitation.responded_at = Some(mapping::parse_ts(&responded_at));
+        Ok(RespondToInvitationResponse::Invitation(Json(invitation)))
+    }
+
+    #[oai(path = "/invitations/respond-by-token", method = "post")]
+    async fn respond_to_invitation_by_token(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<RespondByTokenInput>,
+    ) -> Result<RespondByTokenResponse> {
+        info!("Responding to invitation by token: {:?}", input.response);
+        let input = input.0;
+
+        // The caller (holder of the invite link) becomes the accepting account.
+        // Resolving their uid is what lets a bare-token invite be linked to a
+        // real user — without it the acceptance can't be attributed and never
+        // reaches the roster / feed / stats.
+        let uid = self.require_uid(dao, &jwt_data).await?;
+
+        let rec = match dao
+            .get_invitation_by_token(&input.invite_token)
+            .await
+            .map_err(dao_internal)?
+        {
+            Some(r) => r,
+            None => {
+                return Ok(RespondByTokenResponse::NotFound(PlainText(
+                    "no invitation matches that token".into(),
+                )));
+            }
+        };
+
+        // Invite links are multi-use and never move out of `pending`; accepting
+        // one here would bind the whole link to this caller.
+        if rec.link.is_some() {
+            return Ok(RespondByTokenResponse::ValidationError(PlainText(
+                "this token is a team invite link; redeem it via /invite-links/redeem".into(),
+            )));
+        }
+        // A token invitation isn't in an inbox for a block to revoke, so it's
+        // refused here instead, as if it had been.
+        if dao
+            .is_blocked_between(&uid, &rec.invited_by_user_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            return Ok(RespondByTokenResponse::NotFound(PlainText(
+                "no invitation matches that token".into(),
+            )));
+        }
+
+        let responded_at = now_iso();
+        let status = match input.response {
+            // Accept synchronously: bind this account onto the (previously
+            // userless) token invitation, link the roster entry, and write the
+            // accepter's own feed row. Follower fan-out follows async.
+            membership::InvitationResponse::Accepted => {
+                dao.accept_invitation_tx(&rec.id, &uid, &responded_at, &responded_at)
+                    .await
+                    .map_err(|e| match e {
+                        dao::DaoError::NotFound(_) => {
+                            Error::from_string("not found", StatusCode::NOT_FOUND)
+                        }
+                        dao::DaoError::Conflict(_) => Error::from_string(
+                            "you are already a member of this team",
+                            StatusCode::CONFLICT,
+                        ),
+                        other => dao_internal(other),
+                    })?;
+                "accepted"
+            }
+            membership::InvitationResponse::Declined => {
+                dao.respond_to_invitation(
+                    &rec.id,
+                    "declined",
+                    &responded_at,
+                    &rec.invited_at,
+                    false,
+                )
+                .await
+                .map_err(|e| match e {
+                    dao::DaoError::NotFound(_) => {
+                        Error::from_string("not found", StatusCode::NOT_FOUND)
+                    }
+                    other => dao_internal(other),
+                })?;
+                "declined"
+            }
+        };
+
+        let mut invitation = invitation_from_record(&rec);
+        invitation.status = invitation_status_from_str(status);
+        invitation.responded_at = Some(mapping::parse_ts(&responded_at));
+        Ok(RespondByTokenResponse::Invitation(Json(invitation)))
+    }
+
+    #[oai(path = "/users/:user_id/follow", method = "post")]
+    async fn follow_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<FollowResponse> {
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        info!("User {uid} following user {user_id}");
+        let Some(followee) = dao.get_user(&user_id).await.map_err(dao_internal)? else {
+            return Ok(FollowResponse::NotFound(PlainText("user not found".into())));
+        };
+        // A block either way reads as the user not existing.
+        if dao
+            .is_blocked_between(&uid, &user_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            return Ok(FollowResponse::NotFound(PlainText("user not found".into())));
+        }
+        // A private profile gets a follow request instead, unless the caller
+        // already follows it (from before it went private, or approved).
+        if followee.visibility != dao::records::Visibility::Public
+            && !dao
+                .is_following_user(&uid, &user_id)
+                .await
+                .map_err(dao_internal)?
+        {
+            return match dao.request_follow_user(&uid, &user_id, &now_iso()).await {
+                Ok(()) => Ok(FollowResponse::Requested),
+                Err(dao::DaoError::Conflict(msg)) => Ok(FollowResponse::NotFound(PlainText(msg))),
+                Err(e) => Err(dao_internal(e)),
+            };
+        }
+        match dao.follow_user(&uid, &user_id, &now_iso()).await {
+            Ok(()) => Ok(FollowResponse::Ok),
+            Err(dao::DaoError::Conflict(msg)) => Ok(FollowResponse::NotFound(PlainText(msg))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/users/:user_id/follow", method = "delete")]
+    async fn unfollow_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<FollowResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("User {uid} unfollowing user {user_id}");
+        dao.unfollow_user(&uid, &user_id)
+            .await
+            .map_err(dao_internal)?;
+        // Also withdraws a pending follow request, if that's all there was.
+        match dao.delete_follow_request(&user_id, &uid).await {
+            Ok(()) | Err(dao::DaoError::NotFound(_)) => {}
+            Err(e) => return Err(dao_internal(e)),
+        }
+        Ok(FollowResponse::Ok)
+    }
+
+    /// Pending requests to follow the caller's private profile, ordered by
+    /// requester id.
+    #[oai(path = "/users/me/follow-requests", method = "get")]
+    async fn list_follow_requests(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListFollowsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing follow requests for user {uid}");
+        let page = dao
+            .list_follow_requests(&uid, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let ids: Vec<String> = page.items.into_iter().map(|r| r.follower_id).collect();
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
+        Ok(ListFollowsResponse::Users(Json(UserPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// Approve or deny a request to follow the caller. Approving makes the
+    /// requester a follower (and only then moves both follow counts).
+    #[oai(path = "/users/me/follow-requests/:user_id/respond", method = "post")]
+    async fn respond_to_follow_request(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+        input: Json<RespondToFollowRequestInput>,
+    ) -> Result<RespondToFollowRequestResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!(
+            "User {uid} responding to {user_id}'s follow request: {:?}",
+            input.response
+        );
+        let result = match input.0.response {
+            FollowRequestResponse::Approved => {
+                dao.approve_follow_request(&uid, &user_id, &now_iso()).await
+            }
+            FollowRequestResponse::Denied => dao.delete_follow_request(&uid, &user_id).await,
+        };
+        match result {
+            Ok(()) => Ok(RespondToFollowRequestResponse::Ok),
+            Err(dao::DaoError::NotFound(_)) => Ok(RespondToFollowRequestResponse::NotFound(
+                PlainText("no pending follow request from that user".into()),
+            )),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    /// Block a user: removes follows, follow requests and pending invitations
+    /// both ways, and stops either of you following, inviting or adding, or
+    /// commenting on or liking the other's matches. The blocked user isn't
+    /// told.
+    #[oai(path = "/users/:user_id/block", method = "post")]
+    async fn block_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<BlockResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("User {uid} blocking user {user_id}");
+        if dao
+            .get_user(&user_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(BlockResponse::NotFound(PlainText("user not found".into())));
+        }
+        match dao.block_user(&uid, &user_id, &now_iso()).await {
+            Ok(()) => Ok(BlockResponse::Ok),
+            Err(dao::DaoError::Conflict(msg)) => Ok(BlockResponse::NotFound(PlainText(msg))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/users/:user_id/block", method = "delete")]
+    async fn unblock_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<BlockResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("User {uid} unblocking user {user_id}");
+        dao.unblock_user(&uid, &user_id)
+            .await
+            .map_err(dao_internal)?;
+        Ok(BlockResponse::Ok)
+    }
+
+    /// Users the caller has blocked.
+    #[oai(path = "/users/me/blocks", method = "get")]
+    async fn list_blocked_users(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListFollowsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing users blocked by {uid}");
+        let page = dao
+            .list_blocked_users(&uid, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let ids: Vec<String> = page.items.into_iter().map(|b| b.blocked_user_id).collect();
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
+        Ok(ListFollowsResponse::Users(Json(UserPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// Mute a user: their matches leave the caller's feed and their actions
+    /// stop notifying the caller. Silent — nothing changes for them.
+    #[oai(path = "/users/:user_id/mute", method = "post")]
+    async fn mute_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<BlockResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("User {uid} muting user {user_id}");
+        if dao
+            .get_user(&user_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(BlockResponse::NotFound(PlainText("user not found".into())));
+        }
+        match dao.mute_user(&uid, &user_id, &now_iso()).await {
+            Ok(()) => Ok(BlockResponse::Ok),
+            Err(dao::DaoError::Conflict(msg)) => Ok(BlockResponse::NotFound(PlainText(msg))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/users/:user_id/mute", method = "delete")]
+    async fn unmute_user(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+    ) -> Result<BlockResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("User {uid} unmuting user {user_id}");
+        dao.unmute_user(&uid, &user_id)
+            .await
+            .map_err(dao_internal)?;
+        Ok(BlockResponse::Ok)
+    }
+
+    /// Users the caller has muted.
+    #[oai(path = "/users/me/mutes", method = "get")]
+    async fn list_muted_users(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListFollowsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing users muted by {uid}");
+        let page = dao
+            .list_muted_users(&uid, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let ids: Vec<String> = page.items.into_iter().map(|m| m.muted_user_id).collect();
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
+        Ok(ListFollowsResponse::Users(Json(UserPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// Report a comment, match, user, header photo or profile image to the
+    /// moderators. Once enough different users report the same thing it's
+    /// hidden until a moderator reviews it.
+    #[oai(path = "/reports", method = "post")]
+    async fn create_report(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<CreateReportInput>,
+    ) -> Result<CreateReportResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let input = input.0;
+        info!(
+            "User {uid} reporting {:?} {}",
+            input.target_type, input.target_id
+        );
+        let target = match self.reportable_target(dao, &uid, &input).await? {
+            Ok(target) => target,
+            Err(response) => return Ok(response),
+        };
+
+        let now = now_iso();
+        let report = dao::records::ReportRecord {
+            case_id: target.case_id(),
+            reporter_user_id: uid,
+            reason: report_reason_str(input.reason).to_string(),
+            details: input.details.filter(|d| !d.trim().is_empty()),
+            created_at: now.clone(),
+        };
+        let case = match dao.file_report(&target, &report).await {
+            Ok(case) => case,
+            Err(dao::DaoError::Conflict(_)) => {
+                return Ok(CreateReportResponse::Conflict(PlainText(
+                    "you already reported this".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        };
+
+        if case.report_count >= AUTO_HIDE_REPORT_THRESHOLD && case.hidden_at.is_none() {
+            info!("Hiding {} pending review", case.id);
+            set_report_target_hidden(dao, &case.target, Some(&now)).await?;
+            dao.mark_report_case_hidden(&case.id, &now)
+                .await
+                .map_err(dao_internal)?;
+        }
+        Ok(CreateReportResponse::Accepted)
+    }
+
+    /// The moderation queue: reported content awaiting review, oldest first.
+    /// Moderators only.
+    #[oai(path = "/moderation/reports", method = "get")]
+    async fn list_report_cases(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListReportCasesResponse> {
+        let Some(uid) = self.moderator_uid(dao, &jwt_data).await? else {
+            return Ok(ListReportCasesResponse::Forbidden(PlainText(
+                MODERATORS_ONLY.into(),
+            )));
+        };
+        info!("Moderator {uid} listing the report queue");
+        let page = dao
+            .list_pending_report_cases(cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let mut items = Vec::with_capacity(page.items.len());
+        for case in &page.items {
+            let preview = report_target_preview(dao, assets, &case.target).await?;
+            items.push(report_case_from_record(case, preview));
+        }
+        Ok(ListReportCasesResponse::Cases(Json(ReportCasePage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// One case with its reports. Moderators only.
+    #[oai(path = "/moderation/reports/:case_id", method = "get")]
+    async fn get_report_case(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(case_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of reports to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<GetReportCaseResponse> {
+        let Some(uid) = self.moderator_uid(dao, &jwt_data).await? else {
+            return Ok(GetReportCaseResponse::Forbidden(PlainText(
+                MODERATORS_ONLY.into(),
+            )));
+        };
+        info!("Moderator {uid} reading report case {case_id}");
+        let Some(case) = dao.get_report_case(&case_id).await.map_err(dao_internal)? else {
+            return Ok(GetReportCaseResponse::NotFound(PlainText(
+                "report case not found".into(),
+            )));
+        };
+        let page = dao
+            .list_case_reports(&case_id, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let mut reports = Vec::with_capacity(page.items.len());
+        for report in &page.items {
+            let reporter = self
+                .user_profile_or_placeholder(dao, &report.reporter_user_id)
+                .await?;
+            reports.push(report_from_record(report, reporter));
+        }
+        let preview = report_target_preview(dao, assets, &case.target).await?;
+        Ok(GetReportCaseResponse::Case(Json(ReportCaseDetail {
+            case: report_case_from_record(&case, preview),
+            reports,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    /// Close a case: dismiss it (restoring anything hidden automatically),
+    /// remove the content, or remove it and suspend whoever posted it.
+    /// Moderators only.
+    #[oai(path = "/moderation/reports/:case_id/resolve", method = "post")]
+    async fn resolve_report_case(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(case_id): Path<String>,
+        input: Json<ResolveReportInput>,
+    ) -> Result<ResolveReportResponse> {
+        let Some(uid) = self.moderator_uid(dao, &jwt_data).await? else {
+            return Ok(ResolveReportResponse::Forbidden(PlainText(
+                MODERATORS_ONLY.into(),
+            )));
+        };
+        let action = input.0.action;
+        info!("Moderator {uid} resolving report case {case_id}: {action:?}");
+        let Some(case) = dao.get_report_case(&case_id).await.map_err(dao_internal)? else {
+            return Ok(ResolveReportResponse::NotFound(PlainText(
+                "report case not found".into(),
+            )));
+        };
+        if case.status != "pending" {
+            return Ok(ResolveReportResponse::Conflict(PlainText(
+                "this case was already resolved".into(),
+            )));
+        }
+
+        let now = now_iso();
+        let resolution = match action {
+            ModerationAction::Dismiss => {
+                set_report_target_hidden(dao, &case.target, None).await?;
+                "dismiss"
+            }
+            ModerationAction::Remove => {
+                remove_report_target(dao, &case.target, &now).await?;
+                "remove"
+            }
+            ModerationAction::Suspend => {
+                // Read the author before the removal clears it.
+                let responsible = report_target_owner(dao, &case.target).await?;
+                remove_report_target(dao, &case.target, &now).await?;
+                if let Some(user_id) = responsible {
+                    match dao.suspend_user(&user_id, &now).await {
+                        Ok(()) | Err(dao::DaoError::NotFound(_)) => {}
+                        Err(e) => return Err(dao_internal(e)),
+                    }
+                }
+                "suspend"
+            }
+        };
+        match dao
+            .resolve_report_case(&case_id, resolution, &uid, &now)
+            .await
+        {
+            Ok(()) => Ok(ResolveReportResponse::Ok),
+            Err(dao::DaoError::Conflict(_)) => Ok(ResolveReportResponse::Conflict(PlainText(
+                "this case was already resolved".into(),
+            ))),
+            Err(e) => Err(dao_internal(e)),
+        }
+    }
+
+    #[oai(path = "/users/:user_id/followers", method = "get")]
+    async fn list_user_followers(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListFollowsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing followers of user {user_id}");
+        if self.visible_user(dao, &user_id, &uid).await?.is_none() {
+            return Ok(ListFollowsResponse::NotFound(PlainText(
+                "user not found".into(),
+            )));
+        }
+        let page = dao
+            .list_user_followers(&user_id, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let ids: Vec<String> = page.items.into_iter().map(|e| e.follower_id).collect();
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
+        Ok(ListFollowsResponse::Users(Json(UserPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    #[oai(path = "/users/:user_id/following", method = "get")]
+    async fn list_user_following(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(user_id): Path<String>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListFollowsResponse> {
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        info!("Listing users that user {user_id} follows");
+        if self.visible_user(dao, &user_id, &uid).await?.is_none() {
+            return Ok(ListFollowsResponse::NotFound(PlainText(
+                "user not found".into(),
+            )));
+        }
+        let page = dao
+            .list_user_following(&user_id, cursor.as_deref(), page_limit(limit))
+            .await
+            .map_err(dao_internal)?;
+        let ids: Vec<String> = page.items.into_iter().map(|e| e.followee_id).collect();
+        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
+        Ok(ListFollowsResponse::Users(Json(UserPage {
+            items,
+            next_cursor: page.next_cursor,
+        })))
+    }
+
+    #[oai(path = "/clubs", method = "post")]
+    async fn create_club(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        input: Json<CreateClubInput>,
+    ) -> Result<CreateClubResponse> {
+        info!("Creating club {}", input.name);
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        let name = input.0.name.trim().to_string();
+        if name.is_empty() {
+            return Ok(CreateClubResponse::ValidationError(PlainText(
+                "name must not be empty".into(),
+            )));
+        }
+        let now = now_iso();
+        let club = dao::records::ClubRecord {
+            id: new_id(),
+            name,
+            follower_count: 0,
+            created_at: now.clone(),
+        };
+        // The creator becomes the first club admin.
+        let admin = dao::records::ClubAdminRecord {
+            club_id: club.id.clone(),
+            user_id: uid.clone(),
+            created_at: now,
+        };
+        dao.create_club(&club, &admin).await.map_err(dao_internal)?;
+        Ok(CreateClubResponse::Club(Json(
+            self.build_club(dao, &club, &uid).await?,
+        )))
+    }
+
+    #[oai(path = "/clubs/:club_id", method = "get")]
+    async fn get_club(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(club_id): Path<String>,
+    ) -> Result<GetClubResponse> {
+        info!("Getting club {club_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(club) = dao.get_club(&club_id).await.map_err(dao_internal)? else {
+            return Ok(GetClubResponse::NotFound(PlainText(
+                "club not found".into(),
+            )));
+        };
+        Ok(GetClubResponse::Club(Json(
+            self.build_club(dao, &club, &uid).await?,
+        )))
+    }
+
+    /// Add a team to a club. The caller must be both a club admin and an admin
+    /// of the team, so nobody can annex a team they don't run.
+    #[oai(path = "/clubs/:club_id/teams/:team_id", method = "put")]
+    async fn add_club_team(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(club_id): Path<String>,
+        Path(team_id): Path<String>,
+    ) -> Result<ClubTeamResponse> {
+        info!("Adding team {team_id} to club {club_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(club) = dao.get_club(&club_id).await.map_err(dao_internal)? else {
+            return Ok(ClubTeamResponse::NotFound(PlainText(
+                "club not found".into(),
+            )));
+        };
+        let Some(agg) = dao
+            .get_team(&team_id)
+            .await
+            .map_err(dao_internal)?
+            .filter(|agg| agg.team.archived_at.is_none())
+        else {
+            return Ok(ClubTeamResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        let target_club_admin = dao
+            .is_club_admin(&club_id, &uid)
+            .await
+            .map_err(dao_internal)?;
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) =
+            policy::authorize_team(standing, TeamAction::JoinClub { target_club_admin })
+        {
+            return Ok(ClubTeamResponse::Forbidden(denied.into()));
+        }
+        match dao.attach_team_to_club(&team_id, &club_id).await {
+            Ok(()) => {}
+            Err(dao::error::DaoError::Conflict(msg)) => {
+                return Ok(ClubTeamResponse::Conflict(PlainText(msg)));
+            }
+            Err(dao::error::DaoError::NotFound(_)) => {
+                return Ok(ClubTeamResponse::NotFound(PlainText(
+                    "team not found".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+        Ok(ClubTeamResponse::Club(Json(
+            self.build_club(dao, &club, &uid).await?,
+        )))
+    }
+
+    /// Take a team out of a club. Either a club admin or one of the team's own
+    /// admins can do this.
+    #[oai(path = "/clubs/:club_id/teams/:team_id", method = "delete")]
+    async fn remove_club_team(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(club_id): Path<String>,
+        Path(team_id): Path<String>,
+    ) -> Result<ClubTeamResponse> {
+        info!("Removing team {team_id} from club {club_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Some(club) = dao.get_club(&club_id).await.map_err(dao_internal)? else {
+            return Ok(ClubTeamResponse::NotFound(PlainText(
+                "club not found".into(),
+            )));
+        };
+        let Some(agg) = dao.get_team(&team_id).await.map_err(dao_internal)? else {
+            return Ok(ClubTeamResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        };
+        if agg.team.club_id.as_deref() != Some(club_id.as_str()) {
+            return Ok(ClubTeamResponse::NotFound(PlainText(
+                "team is not in this club".into(),
+            )));
+        }
+        let standing = self.team_standing(dao, &agg, &uid).await?;
+        if let Err(denied) = policy::authorize_team(standing, TeamAction::LeaveClub) {
+            return Ok(ClubTeamResponse::Forbidden(denied.into()));
+        }
+        match dao.detach_team_from_club(&team_id, &club_id).await {
+            Ok(()) => {}
+            // Raced with another removal.
+            Err(dao::error::DaoError::NotFound(_)) => {
+                return Ok(ClubTeamResponse::NotFound(PlainText(
+                    "team is not in this club".into(),
+                )));
+            }
+            Err(e) => return Err(dao_internal(e)),
+        }
+        Ok(ClubTeamResponse::Club(Json(
+            self.build_club(dao, &club, &uid).await?,
+        )))
+    }
+
+    #[oai(path = "/clubs/:club_id/admins", method = "post")]
+    async fn add_club_admin(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(club_id): Path<String>,
+        input: Json<AddClubAdminInput>,
+    ) -> Result<AddClubAdminResponse> {
+        info!("Adding {} as an admin of club {club_id}", input.user_id);
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let user_id = input.0.user_id;
+        if dao
+            .get_club(&club_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(AddClubAdminResponse::NotFound(PlainText(
+                "club not found".into(),
+            )));
+        }
+        let standing = ClubStanding {
+            admin: dao
+                .is_club_admin(&club_id, &uid)
+                .await
+                .map_err(dao_internal)?,
+        };
+        if let Err(denied) = policy::authorize_club(standing, ClubAction::AddAdmin) {
+            return Ok(AddClubAdminResponse::Forbidden(denied.into()));
+        }
+        if dao
+            .get_user(&user_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(AddClubAdminResponse::NotFound(PlainText(
+                "user not found".into(),
+            )));
+        }
+        dao.put_club_admin(&dao::records::ClubAdminRecord {
+            club_id,
+            user_id,
+            created_at: now_iso(),
+        })
+        .await
+        .map_err(dao_internal)?;
+        Ok(AddClubAdminResponse::Ok)
+    }
+
+    /// The club's fixtures or results, across all of its teams. A derby
+    /// between two of the club's teams appears once.
+    #[oai(path = "/clubs/:club_id/matches", method = "get")]
+    async fn list_club_matches(
+        &self,
+        Data(dao): Data<&dao::Dao>,
+        Data(assets): Data<&Assets>,
+        AuthSchema(jwt_data): AuthSchema,
+        Path(club_id): Path<String>,
+        /// Fixtures (upcoming) or results (confirmed).
+        Query(view): Query<ClubMatchesView>,
+        /// Opaque cursor from the previous page's `next_cursor`.
+        Query(cursor): Query<Option<String>>,
+        /// Maximum number of items to return (defaults to 20, capped at 50).
+        Query(limit): Query<Option<u32>>,
+    ) -> Result<ListClubMatchesResponse> {
+        info!("Listing {view:?} for club {club_id}");
+        let uid = self.require_uid(dao, &jwt_data).await?;
+        let Ok(offset) = search_offset(cursor.as_deref()) else {
+            return Ok(ListClubMatchesResponse::ValidationError(PlainText(
+                "Invalid cursor".to_string(),
+            )));
+        };
+        if dao
+            .get_club(&club_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(ListClubMatchesResponse::NotFound(PlainText(
+                "club not found".into(),
+            )));
+        }
+
+        // Every match any of the club's teams has played in, with the club's
+        // side for the outcome — `None` for a derby between two of its teams,
+        // which the club neither wins nor loses.
+        let teams = dao.list_club_teams(&club_id).await.map_err(dao_internal)?;
+        let mut club_sides: HashMap<String, Option<String>> = HashMap::new();
+        for team in &teams {
+            for pointer in dao
+                .list_team_matches(&team.id)
+                .await
+                .map_err(dao_internal)?
+            {
+                club_sides
+                    .entry(pointer.match_id)
+                    .and_modify(|side| {
+                        if side.as_deref() != Some(pointer.side_id.as_str()) {
+                            *side = None;
+                        }
+                    })
+                    .or_insert(Some(pointer.side_id.clone()));
+            }
+        }
+        let match_ids: Vec<String> = club_sides.keys().cloned().collect();
+        let mut summaries = HashMap::with_capacity(match_ids.len());
+        for chunk in match_ids.chunks(dao::batch::BATCH_GET_MAX) {
+            summaries.extend(
+                dao.batch_get_match_summaries(chunk)
+                    .await
+                    .map_err(dao_internal)?,
+            );
+        }
+        dao.retain_visible_matches(&mut summaries, Some(&uid))
+            .await
+            .map_err(dao_internal)?;
+
+        let now = now_iso();
+        let mut selected: Vec<&dao::match_ops::MatchSummary> = summaries
+            .values()
+            .filter(|s| match view {
+                ClubMatchesView::Fixtures => {
+                    s.match_.confirmed_score.is_none()
+                        && s.match_.status != "cancelled"
+                        && s.match_.starts_at >= now
+                }
+                ClubMatchesView::Results => s.match_.confirmed_score.is_some(),
+            })
+            .collect();
+        match view {
+            ClubMatchesView::Fixtures => {
+                selected.sort_by(|a, b| a.match_.starts_at.cmp(&b.match_.starts_at))
+            }
+            ClubMatchesView::Results => {
+                selected.sort_by(|a, b| b.match_.starts_at.cmp(&a.match_.starts_at))
+            }
+        }
+        let limit = page_limit(limit) as usize;
+        let page: Vec<&dao::match_ops::MatchSummary> = selected
+            .iter()
+            .skip(offset as usize)
+            .take(limit)
+            .copied()
+            .collect();
+        let next_offset = (offset as usize + page.len() < selected.len())
+            .then(|| (offset as usize + page.len()) as u32);
+
+        let page_ids: Vec<String> = page.iter().map(|s| s.match_.id.clone()).collect();
+        let reactions = dao
+            .batch_get_match_reactions(&page_ids, &uid)
+            .await
+            .map_err(dao_internal)?;
+        let mut user_ids: Vec<String> = Vec::new();
+        for summary in &page {
+            for side in &summary.sides {
+                user_ids.extend(side.roster_preview.iter().filter_map(|p| p.user_id.clone()));
+            }
+        }
+        let team_ids: Vec<String> = page
+            .iter()
+            .flat_map(|s| s.sides.iter().filter_map(|s| s.team_id.clone()))
+            .collect();
+        let (users, team_names) = tokio::try_join!(
+            async { dao.batch_get_users(&user_ids).await.map_err(dao_internal) },
+            async { self.batch_team_names(dao, &team_ids).await },
+        )?;
 
-    #[oai(path = "/users/:user_id/follow", method = "post")]
-    async fn follow_user(
-        &self,
-        Data(dao): Data<&dao::Dao>,
-        AuthSchema(jwt_data): AuthSchema,
-        Path(user_id): Path<String>,
-    ) -> Result<FollowResponse> {
-        let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("User {uid} following user {user_id}");
-        match dao.follow_user(&uid, &user_id, &now_iso()).await {
-            Ok(()) => Ok(FollowResponse::Ok),
-            Err(dao::DaoError::Conflict(msg)) => Ok(FollowResponse::NotFound(PlainText(msg))),
-            Err(e) => Err(dao_internal(e)),
+        let mut items: Vec<SearchMatch> = Vec::with_capacity(page.len());
+        for summary in page {
+            let ours = club_sides.get(&summary.match_.id).cloned().flatten();
+            let outcome = match (&summary.match_.confirmed_score, ours) {
+                (Some(confirmed), Some(ours)) => Some(match confirmed.winner_side_id.as_deref() {
+                    None => agon_core::search::MatchOutcome::Draw,
+                    Some(winner) if winner == ours => agon_core::search::MatchOutcome::Won,
+                    Some(_) => agon_core::search::MatchOutcome::Lost,
+                }),
+                _ => None,
+            };
+            let mut m = search_match_from_records(
+                &summary.match_,
+                &summary.sides,
+                &users,
+                outcome,
+                reactions.get(&summary.match_.id).copied(),
+            );
+            Self::resolve_side_names_from_cache(&mut m.sides, None, &team_names);
+            sign_search_match_headers(assets, &mut m);
+            items.push(m);
         }
+        let mut score_refs: Vec<_> = items
+            .iter_mut()
+            .map(|m| (m.id.as_str(), &mut m.confirmed_score, &mut m.pending_score))
+            .collect();
+        self.hydrate_confirmed_pending_score_players(dao, &mut score_refs)
+            .await?;
+
+        Ok(ListClubMatchesResponse::Matches(Json(MatchPage {
+            items,
+            next_cursor: search_cursor(next_offset),
+            facets: None,
+        })))
     }
 
-    #[oai(path = "/users/:user_id/follow", method = "delete")]
-    async fn unfollow_user(
+    /// Follow a club: matches of every team in the club reach the follower's
+    /// feed.
+    #[oai(path = "/clubs/:club_id/follow", method = "post")]
+    async fn follow_club(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(user_id): Path<String>,
+        Path(club_id): Path<String>,
     ) -> Result<FollowResponse> {
-        let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("User {uid} unfollowing user {user_id}");
-        dao.unfollow_user(&uid, &user_id)
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
+        info!("User {uid} following club {club_id}");
+        if dao
+            .get_club(&club_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none()
+        {
+            return Ok(FollowResponse::NotFound(PlainText("club not found".into())));
+        }
+        dao.follow_club(&uid, &club_id, &now_iso())
             .await
             .map_err(dao_internal)?;
         Ok(FollowResponse::Ok)
     }
 
-    #[oai(path = "/users/:user_id/followers", method = "get")]
-    async fn list_user_followers(
+    #[oai(path = "/clubs/:club_id/follow", method = "delete")]
+    async fn unfollow_club(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(user_id): Path<String>,
-        /// Opaque cursor from the previous page's `next_cursor`.
-        Query(cursor): Query<Option<String>>,
-        /// Maximum number of items to return (defaults to 20, capped at 50).
-        Query(limit): Query<Option<u32>>,
-    ) -> Result<ListFollowsResponse> {
+        Path(club_id): Path<String>,
+    ) -> Result<FollowResponse> {
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Listing followers of user {user_id}");
-        let page = dao
-            .list_user_followers(&user_id, cursor.as_deref(), page_limit(limit))
+        info!("User {uid} unfollowing club {club_id}");
+        dao.unfollow_club(&uid, &club_id)
             .await
             .map_err(dao_internal)?;
-        let ids: Vec<String> = page.items.into_iter().map(|e| e.follower_id).collect();
-        let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
-        Ok(ListFollowsResponse::Users(Json(UserPage {
-            items,
-            next_cursor: page.next_cursor,
-        })))
+        Ok(FollowResponse::Ok)
     }
 
-    #[oai(path = "/users/:user_id/following", method = "get")]
-    async fn list_user_following(
+    #[oai(path = "/clubs/:club_id/followers", method = "get")]
+    async fn list_club_followers(
         &self,
         Data(dao): Data<&dao::Dao>,
         AuthSchema(jwt_data): AuthSchema,
-        Path(user_id): Path<String>,
+        Path(club_id): Path<String>,
         /// Opaque cursor from the previous page's `next_cursor`.
         Query(cursor): Query<Option<String>>,
         /// Maximum number of items to return (defaults to 20, capped at 50).
         Query(limit): Query<Option<u32>>,
     ) -> Result<ListFollowsResponse> {
         let uid = self.require_uid(dao, &jwt_data).await?;
-        info!("Listing users that user {user_id} follows");
+        info!("Listing followers of club {club_id}");
         let page = dao
-            .list_user_following(&user_id, cursor.as_deref(), page_limit(limit))
+            .list_club_followers(&club_id, cursor.as_deref(), page_limit(limit))
             .await
             .map_err(dao_internal)?;
-        let ids: Vec<String> = page.items.into_iter().map(|e| e.followee_id).collect();
+        let ids: Vec<String> = page.items.into_iter().map(|e| e.follower_id).collect();
         let items = self.hydrate_user_profiles(dao, &ids, Some(&uid)).await?;
         Ok(ListFollowsResponse::Users(Json(UserPage {
             items,
@@ -4993,8 +9002,16 @@ impl Api {
         AuthSchema(jwt_data): AuthSchema,
         Path(team_id): Path<String>,
     ) -> Result<FollowResponse> {
-        let uid = self.require_uid(dao, &jwt_data).await?;
+        let uid = self.require_active_uid(dao, &jwt_data).await?;
         info!("User {uid} following team {team_id}");
+        if dao
+            .get_team_meta(&team_id)
+            .await
+            .map_err(dao_internal)?
+            .is_none_or(|team| team.archived_at.is_some())
+        {
+            return Ok(FollowResponse::NotFound(PlainText("team not found".into())));
+        }
         dao.follow_team(&uid, &team_id, &now_iso())
             .await
             .map_err(dao_internal)?;
@@ -5029,6 +9046,11 @@ impl Api {
     ) -> Result<ListFollowsResponse> {
         let uid = self.require_uid(dao, &jwt_data).await?;
         info!("Listing followers of team {team_id}");
+        if self.visible_team(dao, &team_id, &uid).await?.is_none() {
+            return Ok(ListFollowsResponse::NotFound(PlainText(
+                "team not found".into(),
+            )));
+        }
         let page = dao
             .list_team_followers(&team_id, cursor.as_deref(), page_limit(limit))
             .await
@@ -5041,6 +9063,78 @@ impl Api {
         })))
     }
 
+    /// Hydrate search hits into `SearchMatch` cards, in hit order, skipping
+    /// any match deleted since it was indexed. Shared by `GET /matches` and
+    /// `GET /search`.
+    async fn hydrate_search_matches(
+        &self,
+        dao: &dao::Dao,
+        assets: &Assets,
+        viewer_uid: &str,
+        hits: &[agon_core::search::MatchSearchHit],
+    ) -> Result<Vec<SearchMatch>> {
+        let match_ids: Vec<String> = hits.iter().map(|h| h.id.clone()).collect();
+
+        // Hydrate each match's meta + sides (never players — search results
+        // don't render the full roster, see `SearchMatch`) and this caller's
+        // reactions, in two round-trips total instead of a `get_match` +
+        // `get_match_reaction` per hit.
+        let (summaries, reactions) = tokio::try_join!(
+            dao.batch_get_match_summaries(&match_ids),
+            dao.batch_get_match_reactions(&match_ids, viewer_uid),
+        )
+        .map_err(dao_internal)?;
+
+        // Side `roster_preview` entries' live name/avatar, and team names for
+        // the side-name fallback chain (same two batch reads the feed makes;
+        // no per-viewer `known_participants` here, so no user-id union from
+        // that source).
+        let mut user_ids: Vec<String> = Vec::new();
+        for summary in summaries.values() {
+            for side in &summary.sides {
+                user_ids.extend(side.roster_preview.iter().filter_map(|p| p.user_id.clone()));
+            }
+        }
+        let team_ids: Vec<String> = summaries
+            .values()
+            .flat_map(|s| s.sides.iter().filter_map(|s| s.team_id.clone()))
+            .collect();
+        let (users, team_names) = tokio::try_join!(
+            async { dao.batch_get_users(&user_ids).await.map_err(dao_internal) },
+            async { self.batch_team_names(dao, &team_ids).await },
+        )?;
+
+        let mut items: Vec<SearchMatch> = Vec::with_capacity(hits.len());
+        for hit in hits {
+            if let Some(summary) = summaries.get(&hit.id) {
+                let mut m = search_match_from_records(
+                    &summary.match_,
+                    &summary.sides,
+                    &users,
+                    hit.outcome,
+                    reactions.get(&hit.id).copied(),
+                );
+                // No per-viewer `viewer_side_id` for a search hit, so no
+                // "Your side"/"Opposition" — falls to team name / Team A/B.
+                Self::resolve_side_names_from_cache(&mut m.sides, None, &team_names);
+                sign_search_match_headers(assets, &mut m);
+                items.push(m);
+            }
+        }
+
+        // `confirmed_score`/`pending_score`'s scorer/batter names — same
+        // batched-across-the-page treatment as everything else here, not a
+        // per-match live-score fetch (see
+        // `hydrate_confirmed_pending_score_players`'s doc comment).
+        let mut score_refs: Vec<_> = items
+            .iter_mut()
+            .map(|m| (m.id.as_str(), &mut m.confirmed_score, &mut m.pending_score))
+            .collect();
+        self.hydrate_confirmed_pending_score_players(dao, &mut score_refs)
+            .await?;
+        Ok(items)
+    }
+
     /// Hydrate a list of user ids into `UserProfile`s via two batched
     /// exact-key reads across the whole page: the profile items, and — for a
     /// signed-in viewer — which of them the viewer follows. Missing users are
@@ -5067,7 +9161,11 @@ impl Api {
         let mut profiles = Vec::with_capacity(ids.len());
         for id in ids {
             if let Some(record) = records.get(id) {
-                profiles.push(user_profile_from_record(record, followed.contains(id)));
+                profiles.push(embedded_user_profile(
+                    record,
+                    followed.contains(id),
+                    viewer_uid,
+                ));
             }
         }
         Ok(profiles)
@@ -5139,7 +9237,10 @@ impl Api {
                 && let Some(record) = records.get(&u.user_id)
             {
                 u.name = record.name.clone();
-                u.avatar_url = record.profile_image_url.clone();
+                u.avatar_url = record
+                    .profile_image_url
+                    .clone()
+                    .filter(|_| record.profile_image_hidden_at.is_none());
             }
         }
     }
@@ -5289,34 +9390,516 @@ impl Api {
                             None => "Team B".to_string(),
                         },
                     },
-                },
-            });
+                },
+            });
+        }
+    }
+
+    /// The caller's id if they're a moderator.
+    async fn moderator_uid(&self, dao: &dao::Dao, jwt: &JwtClaims) -> Result<Option<String>> {
+        let uid = self.require_uid(dao, jwt).await?;
+        let user = dao.get_user(&uid).await.map_err(dao_internal)?;
+        Ok(user.is_some_and(|u| u.is_moderator).then_some(uid))
+    }
+
+    /// Validate what `uid` is reporting: it exists, they can see it, and it
+    /// isn't theirs. A comment or header photo must come with the match it's
+    /// on.
+    async fn reportable_target(
+        &self,
+        dao: &dao::Dao,
+        uid: &str,
+        input: &CreateReportInput,
+    ) -> Result<std::result::Result<dao::records::ReportTargetRecord, CreateReportResponse>> {
+        let not_found = |what: &str| {
+            Ok(Err(CreateReportResponse::NotFound(PlainText(format!(
+                "{what} not found"
+            )))))
+        };
+        let own = || {
+            Ok(Err(CreateReportResponse::ValidationError(PlainText(
+                "you can't report your own content".into(),
+            ))))
+        };
+        let missing_match = |what: &str| {
+            Ok(Err(CreateReportResponse::ValidationError(PlainText(
+                format!("match_id is required to report a {what}"),
+            ))))
+        };
+        let target_id = input.target_id.clone();
+
+        match input.target_type {
+            ReportTargetType::Comment => {
+                let Some(match_id) = &input.match_id else {
+                    return missing_match("comment");
+                };
+                let Some(agg) = self.visible_match(dao, match_id, uid).await? else {
+                    return not_found("match");
+                };
+                let match_id = agg.match_.id;
+                let comment = match dao
+                    .get_comment(&match_id, &target_id)
+                    .await
+                    .map_err(dao_internal)?
+                {
+                    Some(c) => Some(c),
+                    None => dao
+                        .get_reply(&match_id, &target_id)
+                        .await
+                        .map_err(dao_internal)?,
+                };
+                let Some(comment) = comment.filter(|c| c.deleted_at.is_none()) else {
+                    return not_found("comment");
+                };
+                if comment.author_user_id.as_deref() == Some(uid) {
+                    return own();
+                }
+                Ok(Ok(dao::records::ReportTargetRecord::Comment {
+                    match_id,
+                    comment_id: target_id,
+                    parent_id: comment.parent_id,
+                }))
+            }
+            ReportTargetType::Match => {
+                let Some(agg) = self.visible_match(dao, &target_id, uid).await? else {
+                    return not_found("match");
+                };
+                if agg.match_.created_by_user_id == uid {
+                    return own();
+                }
+                Ok(Ok(dao::records::ReportTargetRecord::Match {
+                    match_id: target_id,
+                }))
+            }
+            ReportTargetType::User => {
+                if target_id == uid {
+                    return own();
+                }
+                if dao
+                    .get_user(&target_id)
+                    .await
+                    .map_err(dao_internal)?
+                    .is_none()
+                {
+                    return not_found("user");
+                }
+                Ok(Ok(dao::records::ReportTargetRecord::User {
+                    user_id: target_id,
+                }))
+            }
+            ReportTargetType::Asset => {
+                let Some(asset) = dao
+                    .get_asset(&target_id)
+                    .await
+                    .map_err(dao_internal)?
+                    .filter(|a| a.status == "uploaded")
+                else {
+                    return not_found("asset");
+                };
+                if asset.owner_user_id == uid {
+                    return own();
+                }
+                let match_id = match asset.purpose.as_str() {
+                    "match_header" => {
+                        let Some(match_id) = &input.match_id else {
+                            return missing_match("header photo");
+                        };
+                        let Some(agg) = self.visible_match(dao, match_id, uid).await? else {
+                            return not_found("match");
+                        };
+                        if !agg
+                            .match_
+                            .header_photos
+                            .iter()
+                            .any(|photo| photo.asset_id == target_id)
+                        {
+                            return not_found("asset");
+                        }
+                        Some(agg.match_.id)
+                    }
+                    "profile_image" => {
+                        let owner = dao
+                            .get_user(&asset.owner_user_id)
+                            .await
+                            .map_err(dao_internal)?;
+                        if owner.and_then(|u| u.profile_image_url) != asset.url {
+                            return not_found("asset");
+                        }
+                        None
+                    }
+                    // Team images aren't shown anywhere yet.
+                    _ => return not_found("asset"),
+                };
+                Ok(Ok(dao::records::ReportTargetRecord::Asset {
+                    asset_id: target_id,
+                    match_id,
+                }))
+            }
+        }
+    }
+
+    /// Fetch a single user's public profile, or `None` if absent. Used to embed
+    /// an author/actor inline. (N+1 in list contexts — batch later.)
+    async fn try_user_profile(&self, dao: &dao::Dao, user_id: &str) -> Result<Option<UserProfile>> {
+        match dao.get_user(user_id).await.map_err(dao_internal)? {
+            Some(record) => Ok(Some(embedded_user_profile(&record, false, None))),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve the `@handle`s in comment text, returning the mentions to store
+    /// and the mentioned accounts (for the response). A handle resolves when
+    /// exactly one of the match's participants or the author's follows has a
+    /// matching name (see `comment_text::name_key`); an ambiguous or unknown
+    /// handle, or one naming the author, stays plain text.
+    async fn resolve_mentions(
+        &self,
+        dao: &dao::Dao,
+        agg: &dao::match_ops::MatchAggregate,
+        author_uid: &str,
+        text: &str,
+    ) -> Result<(
+        Vec<dao::records::CommentMentionRecord>,
+        HashMap<String, dao::records::UserRecord>,
+    )> {
+        let tokens = comment_text::mention_tokens(text);
+        if tokens.is_empty() {
+            return Ok(Default::default());
+        }
+        let mut candidates: Vec<String> = dao::visibility::match_participant_ids(agg)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let following = dao
+            .list_user_following(author_uid, None, MENTION_FOLLOWING_LIMIT)
+            .await
+            .map_err(dao_internal)?;
+        candidates.extend(following.items.into_iter().map(|f| f.followee_id));
+        candidates.retain(|id| id != author_uid);
+        let mut users = dao
+            .batch_get_users(&candidates)
+            .await
+            .map_err(dao_internal)?;
+
+        let mut by_key: HashMap<String, Vec<&str>> = HashMap::new();
+        for user in users.values() {
+            by_key
+                .entry(comment_text::name_key(&user.name))
+                .or_default()
+                .push(&user.id);
+        }
+        let mut mentions = Vec::new();
+        for token in tokens.iter().take(comment_text::MAX_COMMENT_MENTIONS) {
+            if let Some([user_id]) = by_key
+                .get(&comment_text::name_key(token.handle))
+                .map(Vec::as_slice)
+            {
+                mentions.push(dao::records::CommentMentionRecord {
+                    user_id: (*user_id).to_string(),
+                    start: token.start as u32,
+                    len: token.len as u32,
+                });
+            }
+        }
+        users.retain(|id, _| mentions.iter().any(|m| &m.user_id == id));
+        Ok((mentions, users))
+    }
+
+    /// The caller's standing on a match for likes and comments: as
+    /// `MatchStanding::of`, plus whether they have a block with whoever's on
+    /// the other end — `counterpart`, the author of the comment they're
+    /// replying or reacting to, or else the match's creator. Never with
+    /// themselves: a creator who has blocked a player can still comment on
+    /// their own match.
+    async fn match_social_standing(
+        &self,
+        dao: &dao::Dao,
+        agg: &dao::match_ops::MatchAggregate,
+        uid: &str,
+        counterpart: Option<&str>,
+    ) -> Result<MatchStanding> {
+        let counterpart = counterpart.unwrap_or(&agg.match_.created_by_user_id);
+        let blocked = counterpart != uid
+            && dao
+                .is_blocked_between(uid, counterpart)
+                .await
+                .map_err(dao_internal)?;
+        Ok(MatchStanding::of(agg, uid).blocked(blocked))
+    }
+
+    /// Whether the caller has a block, either way, with any of `user_ids`.
+    async fn any_blocked(&self, dao: &dao::Dao, uid: &str, user_ids: &[String]) -> Result<bool> {
+        let blocked = dao
+            .blocked_among(uid, user_ids)
+            .await
+            .map_err(dao_internal)?;
+        Ok(!blocked.is_empty())
+    }
+
+    /// The match, if it exists and the caller may see it. A match hidden from
+    /// the caller reads as missing, so its existence doesn't leak.
+    async fn visible_match(
+        &self,
+        dao: &dao::Dao,
+        match_id: &str,
+        uid: &str,
+    ) -> Result<Option<dao::match_ops::MatchAggregate>> {
+        let Some(agg) = dao.get_match(match_id).await.map_err(dao_internal)? else {
+            return Ok(None);
+        };
+        let visible = dao
+            .can_view_match(&agg, Some(uid))
+            .await
+            .map_err(dao_internal)?;
+        Ok(visible.then_some(agg))
+    }
+
+    /// The team, if it exists and the caller may see it — see
+    /// `visible_match`.
+    async fn visible_team(
+        &self,
+        dao: &dao::Dao,
+        team_id: &str,
+        uid: &str,
+    ) -> Result<Option<dao::team::TeamAggregate>> {
+        let Some(agg) = dao.get_team(team_id).await.map_err(dao_internal)? else {
+            return Ok(None);
+        };
+        let visible = dao
+            .can_view_team(&agg, Some(uid))
+            .await
+            .map_err(dao_internal)?;
+        Ok(visible.then_some(agg))
+    }
+
+    /// The user, if they exist and the caller may see their profile — see
+    /// `visible_match`.
+    async fn visible_user(
+        &self,
+        dao: &dao::Dao,
+        user_id: &str,
+        uid: &str,
+    ) -> Result<Option<dao::records::UserRecord>> {
+        let Some(user) = dao.get_user(user_id).await.map_err(dao_internal)? else {
+            return Ok(None);
+        };
+        let visible = dao
+            .can_view_user(&user, Some(uid))
+            .await
+            .map_err(dao_internal)?;
+        Ok(visible.then_some(user))
+    }
+
+    /// The caller's standing on a team, for `policy::authorize_team`: their
+    /// membership and role, and whether they admin the club that owns it (one
+    /// lookup, only for a team in a club).
+    async fn team_standing(
+        &self,
+        dao: &dao::Dao,
+        team: &dao::team::TeamAggregate,
+        uid: &str,
+    ) -> Result<TeamStanding> {
+        let club_admin = match &team.team.club_id {
+            Some(club_id) => dao
+                .is_club_admin(club_id, uid)
+                .await
+                .map_err(dao_internal)?,
+            None => false,
+        };
+        Ok(TeamStanding::of(&team.members, uid, club_admin))
+    }
+
+    /// Assemble the API `Club`: its teams, with follower totals and the
+    /// caller's follow/admin state. One follow check per team — clubs run a
+    /// handful.
+    async fn build_club(
+        &self,
+        dao: &dao::Dao,
+        club: &dao::records::ClubRecord,
+        uid: &str,
+    ) -> Result<Club> {
+        let (teams, is_followed_by_me, is_admin) = tokio::try_join!(
+            dao.list_club_teams(&club.id),
+            dao.is_following_club(uid, &club.id),
+            dao.is_club_admin(&club.id, uid),
+        )
+        .map_err(dao_internal)?;
+        let mut items = Vec::with_capacity(teams.len());
+        for team in &teams {
+            // Club admins see every team; anyone else only those visible to
+            // them.
+            if !is_admin
+                && team.visibility != dao::records::Visibility::Public
+                && self.visible_team(dao, &team.id, uid).await?.is_none()
+            {
+                continue;
+            }
+            let followed = dao
+                .is_following_team(uid, &team.id)
+                .await
+                .map_err(dao_internal)?;
+            items.push(team_list_item_from_record(team, followed));
+        }
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Club {
+            id: club.id.clone(),
+            name: club.name.clone(),
+            team_follower_count: teams.iter().map(|t| t.follower_count as u32).sum(),
+            teams: items,
+            follower_count: club.follower_count as u32,
+            is_followed_by_me,
+            is_admin,
+        })
+    }
+
+    /// Like `try_user_profile`, but a missing user becomes a bare profile
+    /// carrying just the id, for rows that must always embed someone.
+    async fn user_profile_or_placeholder(
+        &self,
+        dao: &dao::Dao,
+        user_id: &str,
+    ) -> Result<UserProfile> {
+        Ok(self
+            .try_user_profile(dao, user_id)
+            .await?
+            .unwrap_or_else(|| {
+                user_profile_from_record(
+                    &dao::records::UserRecord {
+                        id: user_id.to_string(),
+                        email: String::new(),
+                        name: String::new(),
+                        profile_image_url: None,
+                        follower_count: 0,
+                        following_count: 0,
+                        unread_count: 0,
+                        stats: std::collections::HashMap::new(),
+                        visibility: Default::default(),
+                        is_moderator: false,
+                        suspended_at: None,
+                        hidden_at: None,
+                        profile_image_hidden_at: None,
+                        created_at: String::new(),
+                    },
+                    false,
+                )
+            }))
+    }
+
+    /// Map comment records to API `Comment`s, hydrating each author profile
+    /// (tombstoned comments have no author; N+1, batch later) and, in one
+    /// batch, everyone they mention.
+    /// Comments by users the viewer has a block with are left out, and one
+    /// hidden pending moderation reads as a tombstone to all but its author.
+    /// A comment or reply by id, as stored — tombstones included.
+    async fn comment_or_reply(
+        &self,
+        dao: &dao::Dao,
+        match_id: &str,
+        comment_id: &str,
+    ) -> Result<Option<dao::records::CommentRecord>> {
+        if let Some(comment) = dao
+            .get_comment(match_id, comment_id)
+            .await
+            .map_err(dao_internal)?
+        {
+            return Ok(Some(comment));
         }
+        dao.get_reply(match_id, comment_id)
+            .await
+            .map_err(dao_internal)
     }
 
-    /// Fetch a single user's public profile, or `None` if absent. Used to embed
-    /// an author/actor inline. (N+1 in list contexts — batch later.)
-    async fn try_user_profile(&self, dao: &dao::Dao, user_id: &str) -> Result<Option<UserProfile>> {
-        match dao.get_user(user_id).await.map_err(dao_internal)? {
-            Some(record) => Ok(Some(user_profile_from_record(&record, false))),
-            None => Ok(None),
+    /// A comment or reply the caller can see: not a tombstone, not hidden by
+    /// moderation (unless they wrote it), and not by someone they have a
+    /// block with. Anything else reads as missing.
+    async fn visible_comment(
+        &self,
+        dao: &dao::Dao,
+        match_id: &str,
+        comment_id: &str,
+        uid: &str,
+    ) -> Result<Option<dao::records::CommentRecord>> {
+        let Some(comment) = self.comment_or_reply(dao, match_id, comment_id).await? else {
+            return Ok(None);
+        };
+        let Some(author_id) = comment.author_user_id.as_deref() else {
+            return Ok(None);
+        };
+        if comment.deleted_at.is_some() || (comment.hidden_at.is_some() && author_id != uid) {
+            return Ok(None);
         }
+        Ok(Some(comment))
+    }
+
+    /// "Who reacted" rows from `(user id, reaction, reacted at)`, in order,
+    /// skipping users without a profile.
+    async fn hydrate_reactors(
+        &self,
+        dao: &dao::Dao,
+        rows: Vec<(String, dao::records::ReactionRecord, String)>,
+        viewer_uid: &str,
+    ) -> Result<Vec<reaction::Reactor>> {
+        let ids: Vec<String> = rows.iter().map(|(id, _, _)| id.clone()).collect();
+        let (users, followed) = tokio::try_join!(
+            dao.batch_get_users(&ids),
+            dao.batch_is_following_users(viewer_uid, &ids),
+        )
+        .map_err(dao_internal)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, reaction, reacted_at)| {
+                let user = users.get(&id)?;
+                let profile = embedded_user_profile(user, followed.contains(&id), Some(viewer_uid));
+                Some(reactor_from_record(profile, reaction, &reacted_at))
+            })
+            .collect())
     }
 
-    /// Map comment records to API `Comment`s, hydrating each author profile
-    /// (tombstoned comments have no author). N+1; batch later.
     async fn hydrate_comments(
         &self,
         dao: &dao::Dao,
         records: Vec<dao::records::CommentRecord>,
+        viewer_uid: &str,
     ) -> Result<Vec<Comment>> {
+        let authors: Vec<String> = records
+            .iter()
+            .filter_map(|rec| rec.author_user_id.clone())
+            .collect();
+        let blocked = dao
+            .blocked_among(viewer_uid, &authors)
+            .await
+            .map_err(dao_internal)?;
+        let mentioned_ids: Vec<String> = records
+            .iter()
+            .flat_map(|rec| rec.mentions.iter().map(|m| m.user_id.clone()))
+            .collect();
+        let (mentioned, my_reactions) = tokio::try_join!(
+            dao.batch_get_users(&mentioned_ids),
+            dao.batch_get_comment_reactions(&records, viewer_uid),
+        )
+        .map_err(dao_internal)?;
         let mut out = Vec::with_capacity(records.len());
-        for rec in records {
+        for mut rec in records {
+            if rec
+                .author_user_id
+                .as_ref()
+                .is_some_and(|id| blocked.contains(id))
+            {
+                continue;
+            }
+            if rec.hidden_at.is_some() && rec.author_user_id.as_deref() != Some(viewer_uid) {
+                rec.author_user_id = None;
+                rec.text = None;
+                rec.deleted_at = rec.hidden_at.clone();
+                rec.mentions.clear();
+                rec.tags.clear();
+            }
             let author = match &rec.author_user_id {
                 Some(uid) => self.try_user_profile(dao, uid).await?,
                 None => None,
             };
-            out.push(comment_from_record(&rec, author));
+            let my_reaction = my_reactions.get(&rec.comment_id).copied();
+            out.push(comment_from_record(&rec, author, &mentioned, my_reaction));
         }
         Ok(out)
     }
@@ -5824,25 +10407,26 @@ fn two_side_winner(side_ids: &[String], score_for: impl Fn(&str) -> i64) -> Opti
 /// Build a match player record plus the standalone invitation entity for one
 /// invitee (an Agon user or an external). The player and invitation share the
 /// invitation id/status; externals get a minted token.
-/// Whether `uid` is a participant in the match — a player linked to this Agon
-/// user who has either been added ad-hoc (no invitation) or accepted their
-/// invitation. Pending/declined invitees are not participants. Drives who may
-/// edit a match (its metadata, roster, and result).
-fn caller_is_participant(players: &[dao::records::MatchPlayerRecord], uid: &str) -> bool {
-    players.iter().any(|p| {
-        p.user_id.as_deref() == Some(uid)
-            && match &p.invitation {
-                None => true,
-                Some(inv) => inv.status == "accepted",
-            }
-    })
-}
-
-/// Whether the caller may manage the match (edit, invite, record the result):
-/// the creator who organized it, or any participant. The creator can manage a
-/// match they set up between other people without playing in it themselves.
-fn caller_can_manage_match(agg: &dao::match_ops::MatchAggregate, uid: &str) -> bool {
-    agg.match_.created_by_user_id == uid || caller_is_participant(&agg.players, uid)
+/// Every external invitee in an `AddInvitationsInput` as `(name, email)`: the
+/// bare names, then the ones given with an email. Errs (with the message for
+/// a 400) on an email we couldn't send to.
+fn external_invitees(
+    input: &AddInvitationsInput,
+) -> std::result::Result<Vec<(String, Option<String>)>, String> {
+    let mut externals: Vec<(String, Option<String>)> = input
+        .invited_external_names
+        .iter()
+        .map(|name| (name.clone(), None))
+        .collect();
+    for invitee in &input.invited_externals {
+        let email = match invitee.email.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(email) if agon_core::email::is_plausible_address(email) => Some(email.to_string()),
+            Some(email) => return Err(format!("{email:?} is not a valid email address")),
+        };
+        externals.push((invitee.name.clone(), email));
+    }
+    Ok(externals)
 }
 
 fn build_invited_player(
@@ -5904,6 +10488,7 @@ fn build_invited_player(
         invited_by_user_id: invited_by_user_id.to_string(),
         invited_user_id,
         invite_token,
+        invitee_email: None,
         kind,
         context: dao::records::InvitationContextRecord::Match {
             match_id: match_id.to_string(),
@@ -5911,6 +10496,7 @@ fn build_invited_player(
         },
         invited_at: now.to_string(),
         responded_at: None,
+        link: None,
     };
 
     (player, invitation)
@@ -5943,7 +10529,9 @@ fn asset_status_from_str(s: &str) -> AssetStatus {
 /// public purposes (profile/team) it is returned as-is; a match-header asset's
 /// url is signed here so the `Asset` view is directly usable. (Match headers are
 /// also signed when attached to a `Match`; signing both keeps `GET /assets/:id`
-/// consistent.)
+/// consistent.) So, like `sign_match_headers`, only call this once the caller
+/// is known to be allowed the asset: its owner, or someone who can see the
+/// match it's attached to (see `get_asset`).
 async fn asset_from_record(assets: &Assets, record: &dao::records::AssetRecord) -> Asset {
     let status = asset_status_from_str(&record.status);
     let upload = match status {
@@ -6022,10 +10610,12 @@ async fn resolve_asset_urls(
 
 /// Sign a `Match`'s header-photo URLs for private serving. The mapping layer
 /// stores each photo's canonical (unsigned) CloudFront URL; match headers are a
-/// signed CloudFront behaviour, so we mint a short-lived signed URL at read time
-/// (and, in future, only after a per-match visibility check). A no-op in dev,
-/// where signing isn't configured. Call this on every `Match` returned to a
-/// client.
+/// signed CloudFront behaviour, so we mint a short-lived signed URL at read time.
+/// A no-op in dev, where signing isn't configured. Call this on every `Match`
+/// returned to a client — and only once the caller is known to be allowed to
+/// see it (`Dao::can_view_match`, or a route that only reaches visible
+/// matches: the caller's feed, a viewer-filtered search), since the signed
+/// URL is the only thing guarding the photo.
 fn sign_match_headers(assets: &Assets, m: &mut Match) {
     for photo in &mut m.header_photos {
         photo.image_url = assets.sign_get(&photo.image_url);
@@ -6044,6 +10634,214 @@ fn sign_search_match_headers(assets: &Assets, m: &mut SearchMatch) {
     }
 }
 
+/// A reported comment or reply, if it still exists.
+async fn reported_comment(
+    dao: &dao::Dao,
+    match_id: &str,
+    comment_id: &str,
+    is_reply: bool,
+) -> Result<Option<dao::records::CommentRecord>> {
+    let comment = if is_reply {
+        dao.get_reply(match_id, comment_id).await
+    } else {
+        dao.get_comment(match_id, comment_id).await
+    };
+    comment.map_err(dao_internal)
+}
+
+/// Treat a `NotFound` from a moderation write as done: the content went away
+/// on its own since it was reported.
+fn ignore_not_found(result: dao::DaoResult<()>) -> Result<()> {
+    match result {
+        Ok(()) | Err(dao::DaoError::NotFound(_)) => Ok(()),
+        Err(e) => Err(dao_internal(e)),
+    }
+}
+
+/// Hide (`Some`) or restore (`None`) a reported target.
+async fn set_report_target_hidden(
+    dao: &dao::Dao,
+    target: &dao::records::ReportTargetRecord,
+    hidden_at: Option<&str>,
+) -> Result<()> {
+    use dao::records::ReportTargetRecord as Target;
+    let result = match target {
+        Target::Comment {
+            match_id,
+            comment_id,
+            parent_id,
+        } => {
+            dao.set_comment_hidden(match_id, comment_id, parent_id.is_some(), hidden_at)
+                .await
+        }
+        Target::Match { match_id } => dao.set_match_hidden(match_id, hidden_at).await,
+        Target::User { user_id } => dao.set_user_hidden(user_id, hidden_at).await,
+        Target::Asset {
+            asset_id,
+            match_id: Some(match_id),
+        } => {
+            dao.set_header_photo_hidden(match_id, asset_id, hidden_at)
+                .await
+        }
+        Target::Asset {
+            asset_id,
+            match_id: None,
+        } => {
+            let Some(asset) = dao.get_asset(asset_id).await.map_err(dao_internal)? else {
+                return Ok(());
+            };
+            // Only hide the image if it's still the one reported.
+            if hidden_at.is_some() {
+                let owner = dao
+                    .get_user(&asset.owner_user_id)
+                    .await
+                    .map_err(dao_internal)?;
+                if owner.and_then(|u| u.profile_image_url) != asset.url {
+                    return Ok(());
+                }
+            }
+            dao.set_profile_image_hidden(&asset.owner_user_id, hidden_at)
+                .await
+        }
+    };
+    ignore_not_found(result)
+}
+
+/// Take a reported target down for good: tombstone a comment, hide a match
+/// or profile from all but its participants, or detach and retire an image.
+async fn remove_report_target(
+    dao: &dao::Dao,
+    target: &dao::records::ReportTargetRecord,
+    now: &str,
+) -> Result<()> {
+    use dao::records::ReportTargetRecord as Target;
+    match target {
+        Target::Comment {
+            match_id,
+            comment_id,
+            parent_id,
+        } => {
+            let is_reply = parent_id.is_some();
+            // The tombstone writes are unconditional; don't resurrect a
+            // comment its author has since deleted.
+            let Some(comment) = reported_comment(dao, match_id, comment_id, is_reply).await? else {
+                return Ok(());
+            };
+            let tombstone = if is_reply {
+                dao.tombstone_reply(match_id, comment_id, &comment.tags, now)
+                    .await
+            } else {
+                dao.tombstone_comment(match_id, comment_id, &comment.tags, now)
+                    .await
+            };
+            tombstone.map_err(dao_internal)?;
+            ignore_not_found(
+                dao.set_comment_hidden(match_id, comment_id, is_reply, None)
+                    .await,
+            )
+        }
+        Target::Match { match_id } => {
+            ignore_not_found(dao.set_match_hidden(match_id, Some(now)).await)
+        }
+        Target::User { user_id } => ignore_not_found(dao.set_user_hidden(user_id, Some(now)).await),
+        Target::Asset { asset_id, match_id } => {
+            let Some(asset) = dao.get_asset(asset_id).await.map_err(dao_internal)? else {
+                return Ok(());
+            };
+            match match_id {
+                Some(match_id) => {
+                    ignore_not_found(dao.remove_header_photo(match_id, asset_id).await)?;
+                }
+                None => {
+                    let owner = dao
+                        .get_user(&asset.owner_user_id)
+                        .await
+                        .map_err(dao_internal)?;
+                    if owner.is_some_and(|u| u.profile_image_url == asset.url) {
+                        ignore_not_found(
+                            dao.update_user_profile(&asset.owner_user_id, None, Some(None))
+                                .await,
+                        )?;
+                    }
+                }
+            }
+            ignore_not_found(dao.mark_asset_removed(asset_id).await)
+        }
+    }
+}
+
+/// Who's answerable for a reported target: a comment's author, a match's
+/// creator, an image's uploader, or the reported user.
+async fn report_target_owner(
+    dao: &dao::Dao,
+    target: &dao::records::ReportTargetRecord,
+) -> Result<Option<String>> {
+    use dao::records::ReportTargetRecord as Target;
+    Ok(match target {
+        Target::Comment {
+            match_id,
+            comment_id,
+            parent_id,
+        } => reported_comment(dao, match_id, comment_id, parent_id.is_some())
+            .await?
+            .and_then(|c| c.author_user_id),
+        Target::Match { match_id } => dao
+            .get_match(match_id)
+            .await
+            .map_err(dao_internal)?
+            .map(|agg| agg.match_.created_by_user_id)
+            .filter(|id| !id.is_empty()),
+        Target::User { user_id } => Some(user_id.clone()),
+        Target::Asset { asset_id, .. } => dao
+            .get_asset(asset_id)
+            .await
+            .map_err(dao_internal)?
+            .map(|a| a.owner_user_id),
+    })
+}
+
+/// What a moderator sees of a reported target in the queue: a comment's
+/// text, a match's or user's name, or an image URL (signed, for a header
+/// photo). `None` once it's gone.
+async fn report_target_preview(
+    dao: &dao::Dao,
+    assets: &Assets,
+    target: &dao::records::ReportTargetRecord,
+) -> Result<Option<String>> {
+    use dao::records::ReportTargetRecord as Target;
+    Ok(match target {
+        Target::Comment {
+            match_id,
+            comment_id,
+            parent_id,
+        } => reported_comment(dao, match_id, comment_id, parent_id.is_some())
+            .await?
+            .and_then(|c| c.text),
+        Target::Match { match_id } => dao
+            .get_match(match_id)
+            .await
+            .map_err(dao_internal)?
+            .map(|agg| agg.match_.name),
+        Target::User { user_id } => dao
+            .get_user(user_id)
+            .await
+            .map_err(dao_internal)?
+            .map(|u| u.name),
+        Target::Asset { asset_id, match_id } => dao
+            .get_asset(asset_id)
+            .await
+            .map_err(dao_internal)?
+            .and_then(|a| a.url)
+            .map(|url| {
+                if match_id.is_some() {
+                    assets.sign_get(&url)
+                } else {
+                    url
+                }
+            }),
+    })
+}
+
 /// Builds a mock `Pending` asset with a presigned upload target.
 fn mock_pending_asset(id: String, content_type: String) -> Asset {
     Asset {
@@ -6074,6 +10872,7 @@ fn mock_user() -> User {
 fn mock_comment() -> Comment {
     Comment {
         id: String::from("comment_1"),
+        match_id: String::from("match_1"),
         author: Some(mock_user_profile(
             String::from("user_2"),
             String::from("Raj Patel"),
@@ -6083,7 +10882,11 @@ fn mock_comment() -> Comment {
         edited_at: None,
         parent_id: None,
         reply_count: 2,
+        reactions: Vec::new(),
+        my_reaction: None,
         deleted_at: None,
+        mentions: Vec::new(),
+        tags: Vec::new(),
     }
 }
 
@@ -6105,6 +10908,8 @@ fn mock_user_profile(id: String, name: String) -> UserProfile {
         follower_count: 42,
         following_count: 17,
         is_followed_by_me: false,
+        is_follow_requested_by_me: false,
+        visibility: Visibility::Public,
     }
 }
 
@@ -6122,6 +10927,8 @@ fn mock_match(id: String) -> Match {
             latitude: 51.5074,
             longitude: -0.1278,
         }),
+        venue: Some(String::from("Hackney Marshes")),
+        league: Some(String::from("Sunday League")),
         header_photos: vec![Photo {
             image_url: String::from("https://cdn.example.com/matches/match_123/header.jpg"),
             asset_id: Some(String::from("asset_123")),
@@ -6189,8 +10996,16 @@ fn mock_match(id: String) -> Match {
             like_count: 3,
             comment_count: 2,
             i_liked: false,
+            reactions: vec![ReactionCount {
+                reaction: Reaction::Like,
+                count: 3,
+            }],
+            my_reaction: None,
         },
         format: None,
+        cancellation: None,
+        postponements: Vec::new(),
+        visibility: Visibility::Public,
     }
 }
 
@@ -6202,6 +11017,12 @@ fn mock_timestamp() -> chrono::DateTime<chrono::Utc> {
         .unwrap()
 }
 
+/// A free-text field trimmed, or `None` if that leaves nothing.
+fn non_blank(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
 /// Current time as an RFC-3339 / ISO-8601 UTC string (sortable; used in keys).
 fn now_iso() -> String {
     chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
@@ -6262,7 +11083,11 @@ fn mock_invitations_for_input(
             }),
         });
     }
-    for (i, _name) in input.invited_external_names.iter().enumerate() {
+    let external_names = input
+        .invited_external_names
+        .iter()
+        .chain(input.invited_externals.iter().map(|e| &e.name));
+    for (i, _name) in external_names.enumerate() {
         invitations.push(Invitation {
             id: format!("inv_external_{i}"),
             status: InvitationStatus::Pending,
@@ -6309,6 +11134,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_1"),
             is_read: false,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::MatchInvitation(MatchInvitationNotification {
                 inviter: actor("user_2", "Raj Patel"),
@@ -6320,6 +11146,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_team_inv"),
             is_read: false,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::TeamInvitation(TeamInvitationNotification {
                 inviter: actor("user_5", "Tom Brennan"),
@@ -6331,6 +11158,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_accepted"),
             is_read: false,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::InvitationAccepted(InvitationAcceptedNotification {
                 accepted_by: actor("user_3", "Alex Morgan"),
@@ -6344,6 +11172,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_2"),
             is_read: false,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::Follow(FollowNotification {
                 follower: actor("user_1", "Sofia Lindqvist"),
@@ -6352,6 +11181,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_3"),
             is_read: true,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::Like(LikeNotification {
                 liked_by: actor("user_3", "Alex Morgan"),
@@ -6362,6 +11192,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_4"),
             is_read: true,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::Comment(CommentNotification {
                 commenter: actor("user_4", "Priya Shah"),
@@ -6373,6 +11204,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_reply"),
             is_read: true,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::Reply(ReplyNotification {
                 replier: actor("user_2", "Raj Patel"),
@@ -6385,6 +11217,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_score_submitted"),
             is_read: false,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::ScoreSubmitted(ScoreSubmittedNotification {
                 submitted_by: actor("user_2", "Raj Patel"),
@@ -6397,6 +11230,7 @@ fn mock_notifications() -> Vec<Notification> {
         Notification {
             id: String::from("notif_score_confirmed"),
             is_read: true,
+            actor_count: 1,
             created_at: mock_timestamp(),
             kind: NotificationKind::ScoreConfirmed(ScoreConfirmedNotification {
                 confirmed_by: actor("user_3", "Alex Morgan"),
@@ -6405,6 +11239,53 @@ fn mock_notifications() -> Vec<Notification> {
                 submission_id: String::from("sub_abc"),
             }),
         },
+        Notification {
+            id: String::from("notif_join_request"),
+            is_read: false,
+            actor_count: 1,
+            created_at: mock_timestamp(),
+            kind: NotificationKind::TeamJoinRequest(TeamJoinRequestNotification {
+                requester: actor("user_4", "Sam Lee"),
+                team_id: String::from("team_1"),
+                team_name: String::from("Sunday Strikers"),
+            }),
+        },
+        Notification {
+            id: String::from("notif_join_approved"),
+            is_read: true,
+            actor_count: 1,
+            created_at: mock_timestamp(),
+            kind: NotificationKind::TeamJoinApproved(TeamJoinApprovedNotification {
+                approved_by: actor("user_2", "Raj Patel"),
+                team_id: String::from("team_1"),
+                team_name: String::from("Sunday Strikers"),
+            }),
+        },
+        Notification {
+            id: String::from("notif_match_cancelled"),
+            is_read: false,
+            actor_count: 1,
+            created_at: mock_timestamp(),
+            kind: NotificationKind::MatchCancelled(MatchCancelledNotification {
+                cancelled_by: actor("user_2", "Raj Patel"),
+                match_id: String::from("match_123"),
+                match_name: String::from("Tennis vs Raj"),
+                reason: String::from("Courts flooded"),
+            }),
+        },
+        Notification {
+            id: String::from("notif_match_postponed"),
+            is_read: false,
+            actor_count: 1,
+            created_at: mock_timestamp(),
+            kind: NotificationKind::MatchPostponed(MatchPostponedNotification {
+                postponed_by: actor("user_2", "Raj Patel"),
+                match_id: String::from("match_123"),
+                match_name: String::from("Tennis vs Raj"),
+                reason: String::from("Courts flooded"),
+                starts_at: mock_timestamp(),
+            }),
+        },
     ]
 }
 
@@ -6468,6 +11349,9 @@ fn mock_team(id: String, name: String) -> Team {
         invite_token: Some(String::from("team_invite_abc123")),
         follower_count: 128,
         is_followed_by_me: false,
+        club_id: None,
+        archived_at: None,
+        visibility: Visibility::Public,
     }
 }
 
@@ -6503,6 +11387,21 @@ fn within_range(
 const DEFAULT_PAGE_LIMIT: u32 = 20;
 /// Hard cap so a client cannot request an unbounded page.
 const MAX_PAGE_LIMIT: u32 = 50;
+/// `GET /matches`' radius around `near_lat`/`near_lng` when no `within_m`.
+const DEFAULT_NEAR_RADIUS_M: u32 = 25_000;
+/// `GET /search`'s hits per kind when no `limit`, and its cap.
+const GLOBAL_SEARCH_DEFAULT_PER_KIND: u32 = 5;
+const GLOBAL_SEARCH_MAX_PER_KIND: u32 = 10;
+/// How many candidates `GET /search` fetches per hit it returns, for boosting
+/// to pick from.
+const GLOBAL_SEARCH_OVERFETCH: u32 = 2;
+/// What `GET /search` adds to a followed / played-with hit's relevance score
+/// (0.0–1.0): enough to lift it over a slightly better match, not over a much
+/// better one.
+const GLOBAL_SEARCH_BOOST: f64 = 0.1;
+/// How many teams `GET /matches` returns in its team facet — each one's name
+/// is read to label it.
+const MATCH_TEAM_FACET_CAP: usize = 20;
 /// Hard cap on a feed page, tighter than `MAX_PAGE_LIMIT`: at
 /// `agon_core::dao::audience::MAX_KNOWN_PLAYERS` (5) known participants per
 /// match, 20 matches is exactly `BATCH_GET_MAX` (100) — the largest page that
@@ -6510,10 +11409,79 @@ const MAX_PAGE_LIMIT: u32 = 50;
 /// of how full every match's list happens to be.
 const FEED_MAX_PAGE_LIMIT: u32 = 20;
 
+/// How many of a viewer's newest feed entries the ranked feed orders. Its
+/// pages run out after these; older entries are only in the chronological
+/// feed. At most `BATCH_GET_MAX`, so scoring hydrates in one read.
+const RANKED_FEED_WINDOW: u32 = 100;
+
+/// Longest team announcement accepted, in characters.
+const ANNOUNCEMENT_MAX_CHARS: usize = 2000;
+
+/// Distinct pending reports after which reported content is hidden until a
+/// moderator reviews it.
+const AUTO_HIDE_REPORT_THRESHOLD: u32 = 3;
+
+const MODERATORS_ONLY: &str = "only moderators can do that";
+
+/// How many of the author's follows an `@handle` is matched against, besides
+/// the match's participants.
+const MENTION_FOLLOWING_LIMIT: u32 = 100;
+
+/// Refusal for an invitation to someone the inviter has a block with. Doesn't
+/// say which side blocked.
+const BLOCKED_INVITEE: &str = "you can't invite one or more of these users";
+
+/// Refusal to add someone to a team directly, on the same terms as
+/// [`BLOCKED_INVITEE`].
+const BLOCKED_MEMBER: &str = "you can't add one or more of these users";
+
 /// Maximum size of an uploaded asset, in bytes (10 MB). Enforced at asset
 /// creation and baked into the presigned PUT so S3 rejects a mismatch too.
 const MAX_UPLOAD_BYTES: i64 = 10 * 1024 * 1024;
 
+/// Most teams, and separately most matches, one user can mute. Keeps the
+/// preferences item (read on every notification for them) small.
+const MAX_NOTIFICATION_MUTES: usize = 200;
+
+/// A facet's values, most common first (ties by value, so the order is
+/// stable), keeping at most `cap`.
+fn top_facet_values(
+    counts: &std::collections::HashMap<String, usize>,
+    cap: usize,
+) -> Vec<(String, u64)> {
+    let mut values: Vec<(String, u64)> = counts
+        .iter()
+        .map(|(value, &count)| (value.clone(), count as u64))
+        .collect();
+    values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    values.truncate(cap);
+    values
+}
+
+/// Reorder search hits by score, lifting each one `boosted` picks out by
+/// `GLOBAL_SEARCH_BOOST`, and keep the best `n` as `(id, boosted score)`.
+/// Ties keep Meilisearch's order.
+fn boost_hits(
+    hits: Vec<agon_core::search::RankedHit>,
+    boosted: impl Fn(&agon_core::search::RankedHit) -> bool,
+    n: usize,
+) -> Vec<(String, f64)> {
+    let mut scored: Vec<(String, f64)> = hits
+        .into_iter()
+        .map(|h| {
+            let score = if boosted(&h) {
+                h.score + GLOBAL_SEARCH_BOOST
+            } else {
+                h.score
+            };
+            (h.id, score)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(n);
+    scored
+}
+
 /// Clamps a client-supplied limit to `[_, MAX_PAGE_LIMIT]`, defaulting when absent.
 fn page_limit(limit: Option<u32>) -> u32 {
     limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT)
@@ -6524,6 +11492,21 @@ fn feed_page_limit(limit: Option<u32>) -> u32 {
     limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(FEED_MAX_PAGE_LIMIT)
 }
 
+/// Decode a ranked-feed cursor, `<offset>@<ranking_id>`, into its offset —
+/// `None` if it's malformed or from a ranking other than `ranking_id`.
+fn ranked_feed_offset(cursor: &str, ranking_id: &str) -> Option<usize> {
+    let (offset, id) = cursor.split_once('@')?;
+    if ranking_id.is_empty() || id != ranking_id {
+        return None;
+    }
+    offset.parse().ok()
+}
+
+/// The cursor for the ranked-feed page starting at `offset`.
+fn ranked_feed_cursor(offset: usize, ranking_id: &str) -> String {
+    format!("{offset}@{ranking_id}")
+}
+
 /// Decode a search-endpoint cursor into a zero-based offset. Search pagination
 /// is offset-based (Meilisearch), so unlike the DynamoDB `LastEvaluatedKey`
 /// cursors elsewhere, the cursor is simply the stringified next offset. An
@@ -6678,7 +11661,32 @@ async fn main() {
             let ui = api_service.scalar();
 
             let table = std::env::var("AGON_TABLE_NAME").unwrap_or_else(|_| "agon".to_string());
-            let dao = dao::Dao::from_env(table).await;
+            // `AGON_DAO_BACKEND=memory` runs against an empty in-process table
+            // instead of DynamoDB — offline local development, nothing kept
+            // across restarts. See agon_core::dao::memory.
+            let mut dao = match std::env::var("AGON_DAO_BACKEND").as_deref() {
+                Ok("memory") => {
+                    info!("Using the in-memory DAO backend; nothing is persisted");
+                    dao::Dao::in_memory(&dao::memory::MemoryTable::new(table))
+                }
+                Ok("dynamodb") | Err(_) => dao::Dao::from_env(table).await,
+                Ok(other) => {
+                    panic!("AGON_DAO_BACKEND must be `dynamodb` or `memory`, not `{other}`")
+                }
+            };
+            // The participant's own feed rows are written here; fan-out's by
+            // the worker, which reads the same setting. A bad value is logged
+            // and the default kept rather than failing startup.
+            if let Ok(raw) = std::env::var("AGON_FEED_RETENTION_DAYS") {
+                match raw.parse() {
+                    Ok(days) => dao = dao.with_feed_retention_days(days),
+                    Err(e) => error!(
+                        "AGON_FEED_RETENTION_DAYS must be a whole number of days, not `{raw}` \
+                         ({e}); keeping the default of {}",
+                        dao::feed::DEFAULT_FEED_RETENTION_DAYS
+                    ),
+                }
+            }
 
             // Object storage: presigned S3 uploads + CloudFront serving URLs.
             let assets = Assets::from_env().await;
@@ -6773,3 +11781,265 @@ async fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dao::memory::MemoryTable;
+
+    const NOW: &str = "2026-04-01T10:00:00Z";
+
+    fn claims(user_id: &str) -> AuthSchema {
+        AuthSchema(JwtClaims {
+            sub: format!("sub-{user_id}"),
+            exp: 0,
+            iss: None,
+            aud: None,
+            role: None,
+            email: None,
+        })
+    }
+
+    async fn users(dao: &dao::Dao, ids: &[&str]) {
+        for id in ids {
+            let user: dao::records::UserRecord = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "email": format!("{id}@example.com"),
+                "name": id,
+                "created_at": NOW,
+            }))
+            .unwrap();
+            dao.create_user(&format!("sub-{id}"), &user).await.unwrap();
+        }
+    }
+
+    /// A public match `m1` created by `c`, with `p` playing in it.
+    async fn match_with_player(dao: &dao::Dao) {
+        let match_: dao::records::MatchRecord = serde_json::from_value(serde_json::json!({
+            "id": "m1",
+            "name": "Friendly",
+            "description": "",
+            "match_type": "football",
+            "status": "scheduled",
+            "starts_at": NOW,
+            "sides": { "home": { "side_id": "home" } },
+            "created_by_user_id": "c",
+            "created_at": NOW,
+        }))
+        .unwrap();
+        let player = dao::records::MatchPlayerRecord {
+            player_id: "p1".into(),
+            user_id: Some("p".into()),
+            display_name: None,
+            side_id: Some("home".into()),
+            is_member_of_team: None,
+            invitation: None,
+        };
+        dao.create_match(&match_, &[player]).await.unwrap();
+    }
+
+    async fn comment(dao: &dao::Dao, by: &str, parent_id: Option<&str>) -> CreateCommentResponse {
+        let input = CreateCommentInput {
+            text: format!("well played, from {by}"),
+            parent_id: parent_id.map(String::from),
+        };
+        Api.create_match_comment(Data(dao), claims(by), Path("m1".into()), Json(input))
+            .await
+            .unwrap()
+    }
+
+    fn comment_id(response: CreateCommentResponse) -> String {
+        match response {
+            CreateCommentResponse::Comment(Json(c)) => c.id,
+            _ => panic!("comment not created"),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_block_stops_social_actions_only_with_the_counterpart() {
+        let table = MemoryTable::new("agon");
+        let dao = dao::Dao::in_memory(&table);
+        users(&dao, &["c", "p", "f", "x"]).await;
+        match_with_player(&dao).await;
+        dao.block_user("c", "p", NOW).await.unwrap();
+        dao.block_user("x", "f", NOW).await.unwrap();
+
+        // The creator's block on one player doesn't shut them out of their
+        // own match...
+        comment_id(comment(&dao, "c", None).await);
+        let liked = Api
+            .like_match(Data(&dao), claims("c"), Path("m1".into()))
+            .await
+            .unwrap();
+        assert!(matches!(liked, LikeResponse::Ok));
+        // ...but does keep that player off it.
+        assert!(matches!(
+            comment(&dao, "p", None).await,
+            CreateCommentResponse::Forbidden(_)
+        ));
+        let liked = Api
+            .like_match(Data(&dao), claims("p"), Path("m1".into()))
+            .await
+            .unwrap();
+        assert!(matches!(liked, LikeResponse::Forbidden(_)));
+
+        // A block between two spectators only matters when one answers the
+        // other.
+        let by_f = comment_id(comment(&dao, "f", None).await);
+        comment_id(comment(&dao, "x", None).await);
+        assert!(matches!(
+            comment(&dao, "x", Some(&by_f)).await,
+            CreateCommentResponse::Forbidden(_)
+        ));
+        comment_id(comment(&dao, "c", Some(&by_f)).await);
+        let reacted = Api
+            .react_to_comment(
+                Data(&dao),
+                claims("x"),
+                Path("m1".into()),
+                Path(by_f.clone()),
+                Json(ReactInput {
+                    reaction: Reaction::Like,
+                }),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(reacted, ReactResponse::Forbidden(_)));
+    }
+
+    #[tokio::test]
+    async fn a_blocked_user_cant_be_added_to_a_team() {
+        let table = MemoryTable::new("agon");
+        let dao = dao::Dao::in_memory(&table);
+        users(&dao, &["a", "b", "d"]).await;
+        let team: dao::records::TeamRecord = serde_json::from_value(serde_json::json!({
+            "id": "t1",
+            "name": "Rovers",
+            "created_at": NOW,
+        }))
+        .unwrap();
+        let admin = dao::records::TeamMemberRecord {
+            team_id: "t1".into(),
+            membership_id: "ma".into(),
+            user_id: Some("a".into()),
+            display_name: None,
+            role: dao::team::ROLE_ADMIN.into(),
+            invitation: None,
+            created_at: NOW.into(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        };
+        dao.create_team(&team, &admin).await.unwrap();
+        dao.block_user("b", "a", NOW).await.unwrap();
+
+        let add = |user_ids: &[&str]| {
+            let input = AddTeamMembersInput {
+                user_ids: user_ids.iter().map(|id| id.to_string()).collect(),
+            };
+            Api.add_team_members(Data(&dao), claims("a"), Path("t1".into()), Json(input))
+        };
+        assert!(matches!(
+            add(&["d", "b"]).await.unwrap(),
+            AddTeamMembersResponse::Forbidden(_)
+        ));
+        assert!(matches!(
+            add(&["d"]).await.unwrap(),
+            AddTeamMembersResponse::Team(_)
+        ));
+        let agg = dao.get_team("t1").await.unwrap().unwrap();
+        let mut members: Vec<_> = agg
+            .members
+            .iter()
+            .filter_map(|m| m.user_id.clone())
+            .collect();
+        members.sort();
+        assert_eq!(members, ["a", "d"]);
+    }
+
+    #[tokio::test]
+    async fn an_invitation_across_a_block_reads_as_revoked() {
+        let table = MemoryTable::new("agon");
+        let dao = dao::Dao::in_memory(&table);
+        users(&dao, &["a", "b"]).await;
+        let invitation = |id: &str| dao::records::InvitationRecord {
+            id: id.into(),
+            status: "pending".into(),
+            invited_by_user_id: "a".into(),
+            invited_user_id: Some("b".into()),
+            invite_token: None,
+            invitee_email: None,
+            kind: dao::records::InvitationKindRecord::User {
+                invited_user_id: "b".into(),
+            },
+            context: dao::records::InvitationContextRecord::Team {
+                team_id: "t1".into(),
+                team_name: "Rovers".into(),
+            },
+            invited_at: NOW.into(),
+            responded_at: None,
+            link: None,
+        };
+        dao.create_invitation(&invitation("before")).await.unwrap();
+        dao.block_user("b", "a", NOW).await.unwrap();
+        assert!(dao.get_invitation("before").await.unwrap().is_none());
+
+        // One written as the block landed is refused when answered.
+        dao.create_invitation(&invitation("raced")).await.unwrap();
+        let responded = Api
+            .respond_to_invitation(
+                claims("b"),
+                Path("raced".into()),
+                Data(&dao),
+                Json(RespondToInvitationInput {
+                    response: membership::InvitationResponse::Accepted,
+                    side_id: None,
+                }),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            responded,
+            RespondToInvitationResponse::NotFound(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn an_invitation_is_only_shown_to_its_invitee_and_the_matchs_managers() {
+        let table = MemoryTable::new("agon");
+        let dao = dao::Dao::in_memory(&table);
+        users(&dao, &["c", "p", "i", "s"]).await;
+        match_with_player(&dao).await;
+        dao.create_invitation(&dao::records::InvitationRecord {
+            id: "inv1".into(),
+            status: "pending".into(),
+            invited_by_user_id: "c".into(),
+            invited_user_id: Some("i".into()),
+            invite_token: None,
+            invitee_email: None,
+            kind: dao::records::InvitationKindRecord::User {
+                invited_user_id: "i".into(),
+            },
+            context: dao::records::InvitationContextRecord::Match {
+                match_id: "m1".into(),
+                match_name: "Friendly".into(),
+            },
+            invited_at: NOW.into(),
+            responded_at: None,
+            link: None,
+        })
+        .await
+        .unwrap();
+
+        for (viewer, shown) in [("i", true), ("c", true), ("p", true), ("s", false)] {
+            let got = Api
+                .get_invitation(Data(&dao), claims(viewer), Path("inv1".into()))
+                .await
+                .unwrap();
+            assert_eq!(
+                matches!(got, GetInvitationResponse::Invitation(_)),
+                shown,
+                "{viewer}"
+            );
+        }
+    }
+}
@@ -33,18 +33,28 @@ use crate::membership::{
     ExternalMember, Invitation, InvitationContext, InvitationKind, InvitationMatchContext,
     InvitationStatus, InvitationTeamContext, Member, TokenInvitation, UserInvitation, UserMember,
 };
+use crate::moderation::{Report, ReportCase, ReportCaseStatus, ReportReason, ReportTargetType};
 use crate::notification::{
-    CommentNotification, FollowNotification, InvitationAcceptedNotification, LikeNotification,
-    MatchInvitationNotification, Notification, NotificationKind, ReplyNotification,
+    CommentNotification, DigestFrequency, FollowNotification, FollowRequestApprovedNotification,
+    FollowRequestNotification, InvitationAcceptedNotification, LikeNotification,
+    MatchCancelledNotification, MatchInvitationNotification, MatchPostponedNotification,
+    MentionNotification, Notification, NotificationChannels, NotificationKind,
+    NotificationPreferences, NotificationType, QuietHours, ReactionNotification, ReplyNotification,
     ScoreConfirmedNotification, ScoreSubmittedNotification, TeamInvitationNotification,
+    TeamJoinApprovedNotification, TeamJoinRequestNotification,
+};
+use crate::reaction::{Reaction, ReactionCount, Reactor};
+use crate::team::{
+    InviteLink, JoinRequest, JoinRequestStatus, SeasonSquadMember, Team, TeamListItem, TeamMember,
+    TeamRole, TeamSeason,
 };
-use crate::team::{Team, TeamListItem, TeamMember, TeamRole};
 use crate::{
-    Comment, ConfirmedScore, CricketScore, CricketScoreInnings, DevicePlatform, FeedMatch,
-    FootballScore, Location, Match, MatchOutcome, MatchPlayer, MatchSide, MatchSocial, MatchStatus,
-    MatchType, NetballScore, PendingScore, Photo, RosterPreviewPlayer, Score, ScoreConfirmation,
-    ScoreResponseKind, ScoreSubmission, ScoreSubmissionResponse, ScoreSubmissionStatus,
-    SearchMatch, SetsScore, SimpleScore, UserProfile, UserSportStats,
+    Comment, CommentMention, ConfirmedScore, CricketScore, CricketScoreInnings, DevicePlatform,
+    FeedMatch, FootballScore, Location, Match, MatchCancellation, MatchOutcome, MatchPlayer,
+    MatchPostponement, MatchSide, MatchSocial, MatchStatus, MatchType, NetballScore, PendingScore,
+    Photo, RosterPreviewPlayer, Score, ScoreConfirmation, ScoreResponseKind, ScoreSubmission,
+    ScoreSubmissionResponse, ScoreSubmissionStatus, SearchMatch, SetsScore, SimpleScore,
+    UserProfile, UserSportStats, Visibility,
 };
 use agon_core::dao::error::DaoError;
 use agon_core::dao::live_score_ops::NewLiveEvent;
@@ -55,18 +65,22 @@ use agon_core::dao::records::{
     CricketExtrasRecord, CricketFallOfWicketRecord, CricketFormatRecord,
     CricketInningsEndEventRecord, CricketInningsStartEventRecord, CricketLiveEventRecord,
     CricketRetireEventRecord, CricketScoreInningsRecord, DevicePlatform as DevicePlatformRecord,
-    EmbeddedInvitationRecord, FootballCardColorRecord, FootballCardEventRecord,
-    FootballFormatRecord, FootballGoalEventRecord, FootballLiveEventRecord,
-    FootballPenaltyShootoutKickRecord, FootballPeriodEventRecord, FootballPeriodRecord,
-    FootballSubstitutionEventRecord, InningsEndReasonRecord, InvitationContextRecord,
-    InvitationKindRecord, InvitationRecord, LiveEventPayloadRecord, LiveEventRecord,
-    MatchFormatRecord, MatchLikeRecord, MatchPlayerRecord, MatchRecord, MatchScoreRecord,
-    MatchSideRecord, NetballFormatRecord, NetballFoulEventRecord, NetballFoulKindRecord,
-    NetballGoalEventRecord, NetballLiveEventRecord, NetballPeriodEventRecord, NetballPeriodRecord,
-    NetballPositionRecord, NextBallContextRecord, NotificationKindRecord, NotificationRecord,
-    OversRecord, PendingScoreRecord, ScoreConfirmationRecord, ScoreRecord, ScoreResponseRecord,
-    ScoreSubmissionRecord, TeamMemberRecord, TeamRecord, UserRecord, UserSportStatsRecord,
+    DigestFrequencyRecord, EmbeddedInvitationRecord, FootballCardColorRecord,
+    FootballCardEventRecord, FootballFormatRecord, FootballGoalEventRecord,
+    FootballLiveEventRecord, FootballPenaltyShootoutKickRecord, FootballPeriodEventRecord,
+    FootballPeriodRecord, FootballSubstitutionEventRecord, InningsEndReasonRecord,
+    InvitationContextRecord, InvitationKindRecord, InvitationRecord, JoinRequestRecord,
+    LiveEventPayloadRecord, LiveEventRecord, MatchFormatRecord, MatchPlayerRecord, MatchRecord,
+    MatchScoreRecord, MatchSideRecord, NetballFormatRecord, NetballFoulEventRecord,
+    NetballFoulKindRecord, NetballGoalEventRecord, NetballLiveEventRecord,
+    NetballPeriodEventRecord, NetballPeriodRecord, NetballPositionRecord, NextBallContextRecord,
+    NotificationKindRecord, NotificationPreferencesRecord, NotificationRecord,
+    NotificationTypeRecord, OversRecord, PendingScoreRecord, QuietHoursRecord, ReactionRecord,
+    ReportCaseRecord, ReportRecord, ReportTargetRecord, ScoreConfirmationRecord, ScoreRecord,
+    ScoreResponseRecord, ScoreSubmissionRecord, TeamMemberRecord, TeamRecord, TeamSeasonRecord,
+    UserRecord, UserSportStatsRecord, Visibility as VisibilityRecord,
 };
+use agon_core::dao::season;
 
 /// Parse an RFC-3339 timestamp string stored by the DAO into a UTC datetime,
 /// defaulting to the epoch on a malformed value (reads never fail on bad data).
@@ -128,6 +142,22 @@ pub fn device_platform_to_record(p: &DevicePlatform) -> DevicePlatformRecord {
     }
 }
 
+pub fn visibility_from_record(v: VisibilityRecord) -> Visibility {
+    match v {
+        VisibilityRecord::Public => Visibility::Public,
+        VisibilityRecord::Followers => Visibility::Followers,
+        VisibilityRecord::Participants => Visibility::Participants,
+    }
+}
+
+pub fn visibility_to_record(v: Visibility) -> VisibilityRecord {
+    match v {
+        Visibility::Public => VisibilityRecord::Public,
+        Visibility::Followers => VisibilityRecord::Followers,
+        Visibility::Participants => VisibilityRecord::Participants,
+    }
+}
+
 /// Build the public `UserProfile` from a stored user record (its inline
 /// per-sport `stats` map) and the viewer-relative follow flag.
 pub fn user_profile_from_record(user: &UserRecord, is_followed_by_me: bool) -> UserProfile {
@@ -139,10 +169,14 @@ pub fn user_profile_from_record(user: &UserRecord, is_followed_by_me: bool) -> U
     UserProfile {
         id: user.id.clone(),
         name: user.name.clone(),
-        profile_image: user.profile_image_url.as_ref().map(|url| Photo {
-            image_url: url.clone(),
-            asset_id: None,
-        }),
+        profile_image: user
+            .profile_image_url
+            .as_ref()
+            .filter(|_| user.profile_image_hidden_at.is_none())
+            .map(|url| Photo {
+                image_url: url.clone(),
+                asset_id: None,
+            }),
         stats: entries
             .into_iter()
             .map(|(sport, rec)| sport_stats_from_record(sport, rec))
@@ -150,9 +184,28 @@ pub fn user_profile_from_record(user: &UserRecord, is_followed_by_me: bool) -> U
         follower_count: user.follower_count as u32,
         following_count: user.following_count as u32,
         is_followed_by_me,
+        is_follow_requested_by_me: false,
+        visibility: visibility_from_record(user.visibility),
     }
 }
 
+/// A profile as embedded in another resource (a roster, a comment, a follow
+/// list), where its own visibility isn't checked: a restricted (or
+/// moderation-hidden) profile keeps its name and image but drops its stats
+/// for anyone but `viewer_uid` being its owner.
+pub fn embedded_user_profile(
+    user: &UserRecord,
+    is_followed_by_me: bool,
+    viewer_uid: Option<&str>,
+) -> UserProfile {
+    let mut profile = user_profile_from_record(user, is_followed_by_me);
+    let restricted = user.visibility != VisibilityRecord::Public || user.hidden_at.is_some();
+    if restricted && viewer_uid != Some(user.id.as_str()) {
+        profile.stats.clear();
+    }
+    profile
+}
+
 /// Map a stored per-sport stats record to the API model, deriving win %.
 pub fn sport_stats_from_record(sport: &str, rec: &UserSportStatsRecord) -> UserSportStats {
     let win_percentage = if rec.matches_played == 0 {
@@ -793,7 +846,43 @@ pub fn team_from_records(
         invite_token: team.invite_token.clone(),
         follower_count: team.follower_count as u32,
         is_followed_by_me,
+        club_id: team.club_id.clone(),
+        archived_at: parse_ts_opt(&team.archived_at),
+        visibility: visibility_from_record(team.visibility),
+    }
+}
+
+pub fn team_season_from_record(rec: &TeamSeasonRecord) -> TeamSeason {
+    TeamSeason {
+        id: rec.id.clone(),
+        team_id: rec.team_id.clone(),
+        name: rec.name.clone(),
+        starts_at: parse_ts(&rec.starts_at),
+        ends_at: parse_ts_opt(&rec.ends_at),
+    }
+}
+
+/// A season squad entry, or `None` if the membership doesn't overlap the
+/// season (or never started — a still-pending invitee).
+pub fn season_squad_member_from_record(
+    rec: &TeamMemberRecord,
+    season: &TeamSeasonRecord,
+) -> Option<SeasonSquadMember> {
+    if !season::member_in_season(rec, season) {
+        return None;
     }
+    let joined_at = season::member_joined_at(rec)?;
+    Some(SeasonSquadMember {
+        member: member_from_parts(
+            &rec.membership_id,
+            rec.user_id.as_deref(),
+            rec.display_name.as_deref(),
+            rec.invitation.as_ref(),
+        ),
+        role: team_role_from_str(season::role_in_season(rec, season)),
+        joined_at: parse_ts(joined_at),
+        left_at: parse_ts_opt(&rec.left_at),
+    })
 }
 
 pub fn team_list_item_from_record(team: &TeamRecord, is_followed_by_me: bool) -> TeamListItem {
@@ -805,6 +894,56 @@ pub fn team_list_item_from_record(team: &TeamRecord, is_followed_by_me: bool) ->
     }
 }
 
+/// Build the API `InviteLink` from a link invitation. `None` if the record
+/// isn't a team invite link (no `link`, or not a token/team invitation).
+/// `now` decides `is_active`.
+pub fn invite_link_from_record(rec: &InvitationRecord, now: &str) -> Option<InviteLink> {
+    let link = rec.link.as_ref()?;
+    let InvitationContextRecord::Team { team_id, .. } = &rec.context else {
+        return None;
+    };
+    let InvitationKindRecord::Token { invite_token } = &rec.kind else {
+        return None;
+    };
+    let expired = link.expires_at.as_deref().is_some_and(|at| at <= now);
+    let used_up = link.max_uses.is_some_and(|max| link.use_count >= max);
+    Some(InviteLink {
+        id: rec.id.clone(),
+        team_id: team_id.clone(),
+        name: link.name.clone(),
+        invite_token: invite_token.clone(),
+        created_by_user_id: rec.invited_by_user_id.clone(),
+        created_at: parse_ts(&rec.invited_at),
+        expires_at: parse_ts_opt(&link.expires_at),
+        max_uses: link.max_uses,
+        use_count: link.use_count,
+        default_role: team_role_from_str(&link.default_role),
+        requires_approval: link.requires_approval,
+        is_active: rec.status == "pending" && !expired && !used_up,
+    })
+}
+
+pub fn join_request_status_from_str(s: &str) -> JoinRequestStatus {
+    match s {
+        "approved" => JoinRequestStatus::Approved,
+        "denied" => JoinRequestStatus::Denied,
+        _ => JoinRequestStatus::Pending,
+    }
+}
+
+/// Build the API `JoinRequest`, given the requester's hydrated profile.
+pub fn join_request_from_record(rec: &JoinRequestRecord, requester: UserProfile) -> JoinRequest {
+    JoinRequest {
+        team_id: rec.team_id.clone(),
+        requester,
+        status: join_request_status_from_str(&rec.status),
+        role: team_role_from_str(&rec.role),
+        invite_link_id: rec.via_invitation_id.clone(),
+        requested_at: parse_ts(&rec.requested_at),
+        responded_at: parse_ts_opt(&rec.responded_at),
+    }
+}
+
 pub fn match_player_from_record(rec: &MatchPlayerRecord) -> MatchPlayer {
     MatchPlayer {
         member: member_from_parts(
@@ -874,7 +1013,9 @@ pub fn roster_preview_player(
             RosterPreviewPlayer {
                 user_id: Some(uid.to_string()),
                 name: record.map(|u| u.name.clone()).unwrap_or_default(),
-                avatar_url: record.and_then(|u| u.profile_image_url.clone()),
+                avatar_url: record
+                    .filter(|u| u.profile_image_hidden_at.is_none())
+                    .and_then(|u| u.profile_image_url.clone()),
             }
         }
         None => RosterPreviewPlayer {
@@ -890,12 +1031,12 @@ pub fn roster_preview_player(
 // ===========================================================================
 
 /// Build the API `Match` from a match record plus its sides and players.
-/// `i_liked` is a viewer-relative flag the caller resolves separately.
+/// `my_reaction` is the viewer's own, which the caller resolves separately.
 pub fn match_from_records(
     rec: &MatchRecord,
     sides: &[MatchSideRecord],
     players: &[MatchPlayerRecord],
-    i_liked: bool,
+    my_reaction: Option<ReactionRecord>,
 ) -> Match {
     Match {
         id: rec.id.clone(),
@@ -908,9 +1049,12 @@ pub fn match_from_records(
             latitude: l.latitude,
             longitude: l.longitude,
         }),
+        venue: rec.venue.clone(),
+        league: rec.league.clone(),
         header_photos: rec
             .header_photos
             .iter()
+            .filter(|p| p.hidden_at.is_none())
             .map(|p| Photo {
                 image_url: p.url.clone(),
                 asset_id: Some(p.asset_id.clone()),
@@ -923,12 +1067,25 @@ pub fn match_from_records(
             .as_ref()
             .map(confirmed_score_from_record),
         pending_score: rec.pending_score.as_ref().map(pending_score_from_record),
-        social: MatchSocial {
-            like_count: rec.like_count as u32,
-            comment_count: rec.comment_count as u32,
-            i_liked,
-        },
+        social: match_social(rec, my_reaction),
         format: rec.format.as_ref().map(match_format_from_record),
+        cancellation: rec.cancellation.as_ref().map(|c| MatchCancellation {
+            reason: c.reason.clone(),
+            cancelled_by_user_id: c.by_user_id.clone(),
+            cancelled_at: parse_ts(&c.at),
+        }),
+        postponements: rec
+            .postponements
+            .iter()
+            .map(|p| MatchPostponement {
+                from_starts_at: parse_ts(&p.from_starts_at),
+                to_starts_at: parse_ts(&p.to_starts_at),
+                reason: p.reason.clone(),
+                postponed_by_user_id: p.by_user_id.clone(),
+                postponed_at: parse_ts(&p.at),
+            })
+            .collect(),
+        visibility: visibility_from_record(rec.visibility),
     }
 }
 
@@ -943,7 +1100,7 @@ pub fn feed_match_from_records(
     known_participants: Vec<UserProfile>,
     known_participants_count: u32,
     viewer_side_id: Option<String>,
-    i_liked: bool,
+    my_reaction: Option<ReactionRecord>,
 ) -> FeedMatch {
     FeedMatch {
         id: rec.id.clone(),
@@ -956,9 +1113,12 @@ pub fn feed_match_from_records(
             latitude: l.latitude,
             longitude: l.longitude,
         }),
+        venue: rec.venue.clone(),
+        league: rec.league.clone(),
         header_photos: rec
             .header_photos
             .iter()
+            .filter(|p| p.hidden_at.is_none())
             .map(|p| Photo {
                 image_url: p.url.clone(),
                 asset_id: Some(p.asset_id.clone()),
@@ -980,11 +1140,7 @@ pub fn feed_match_from_records(
             .as_ref()
             .map(confirmed_score_from_record),
         pending_score: rec.pending_score.as_ref().map(pending_score_from_record),
-        social: MatchSocial {
-            like_count: rec.like_count as u32,
-            comment_count: rec.comment_count as u32,
-            i_liked,
-        },
+        social: match_social(rec, my_reaction),
         format: rec.format.as_ref().map(match_format_from_record),
     }
 }
@@ -999,7 +1155,7 @@ pub fn search_match_from_records(
     sides: &[MatchSideRecord],
     users: &std::collections::HashMap<String, UserRecord>,
     outcome: Option<agon_core::search::MatchOutcome>,
-    i_liked: bool,
+    my_reaction: Option<ReactionRecord>,
 ) -> SearchMatch {
     SearchMatch {
         id: rec.id.clone(),
@@ -1012,9 +1168,12 @@ pub fn search_match_from_records(
             latitude: l.latitude,
             longitude: l.longitude,
         }),
+        venue: rec.venue.clone(),
+        league: rec.league.clone(),
         header_photos: rec
             .header_photos
             .iter()
+            .filter(|p| p.hidden_at.is_none())
             .map(|p| Photo {
                 image_url: p.url.clone(),
                 asset_id: Some(p.asset_id.clone()),
@@ -1034,15 +1193,22 @@ pub fn search_match_from_records(
             .as_ref()
             .map(confirmed_score_from_record),
         pending_score: rec.pending_score.as_ref().map(pending_score_from_record),
-        social: MatchSocial {
-            like_count: rec.like_count as u32,
-            comment_count: rec.comment_count as u32,
-            i_liked,
-        },
+        social: match_social(rec, my_reaction),
         format: rec.format.as_ref().map(match_format_from_record),
     }
 }
 
+/// A match's social counts, with the viewer's own reaction.
+fn match_social(rec: &MatchRecord, my_reaction: Option<ReactionRecord>) -> MatchSocial {
+    MatchSocial {
+        like_count: rec.like_count as u32,
+        comment_count: rec.comment_count as u32,
+        i_liked: my_reaction == Some(ReactionRecord::Like),
+        reactions: reaction_counts(|r| rec.reaction_count(r)),
+        my_reaction: my_reaction.map(reaction_from_record),
+    }
+}
+
 /// Map the search client's outcome enum to the API's.
 fn match_outcome_from_search(outcome: agon_core::search::MatchOutcome) -> MatchOutcome {
     match outcome {
@@ -1057,17 +1223,40 @@ fn match_outcome_from_search(outcome: agon_core::search::MatchOutcome) -> MatchO
 // ===========================================================================
 
 /// Build the API `Comment` from a comment record. `author` is resolved by the
-/// caller (None on a tombstone) and passed in.
-pub fn comment_from_record(rec: &CommentRecord, author: Option<UserProfile>) -> Comment {
+/// caller (None on a tombstone) and passed in, as are the accounts of
+/// everyone mentioned — a mention of a user missing from `mentioned` is left
+/// out, and its `@handle` reads as plain text.
+pub fn comment_from_record(
+    rec: &CommentRecord,
+    author: Option<UserProfile>,
+    mentioned: &std::collections::HashMap<String, UserRecord>,
+    my_reaction: Option<ReactionRecord>,
+) -> Comment {
+    let mentions = rec
+        .mentions
+        .iter()
+        .filter_map(|m| {
+            mentioned.get(&m.user_id).map(|user| CommentMention {
+                user: embedded_user_profile(user, false, None),
+                start: m.start,
+                len: m.len,
+            })
+        })
+        .collect();
     Comment {
         id: rec.comment_id.clone(),
+        match_id: rec.match_id.clone(),
         parent_id: rec.parent_id.clone(),
         author,
         text: rec.text.clone(),
         created_at: parse_ts(&rec.created_at),
         edited_at: parse_ts_opt(&rec.edited_at),
         reply_count: rec.reply_count as u32,
+        reactions: reaction_counts(|r| rec.reaction_count(r)),
+        my_reaction: my_reaction.map(reaction_from_record),
         deleted_at: parse_ts_opt(&rec.deleted_at),
+        mentions,
+        tags: rec.tags.clone(),
     }
 }
 
@@ -1077,12 +1266,55 @@ pub fn comment_is_tombstone(rec: &CommentRecord) -> bool {
 }
 
 // ===========================================================================
-// Likes
+// Reactions
 // ===========================================================================
 
-/// The liking user's id from a like record.
-pub fn like_user_id(rec: &MatchLikeRecord) -> String {
-    rec.user_id.clone()
+pub fn reaction_from_record(rec: ReactionRecord) -> Reaction {
+    match rec {
+        ReactionRecord::Like => Reaction::Like,
+        ReactionRecord::Fire => Reaction::Fire,
+        ReactionRecord::Clap => Reaction::Clap,
+        ReactionRecord::Laugh => Reaction::Laugh,
+        ReactionRecord::Wow => Reaction::Wow,
+    }
+}
+
+pub fn reaction_to_record(reaction: Reaction) -> ReactionRecord {
+    match reaction {
+        Reaction::Like => ReactionRecord::Like,
+        Reaction::Fire => ReactionRecord::Fire,
+        Reaction::Clap => ReactionRecord::Clap,
+        Reaction::Laugh => ReactionRecord::Laugh,
+        Reaction::Wow => ReactionRecord::Wow,
+    }
+}
+
+/// The non-zero per-reaction counts, in the fixed reaction order.
+fn reaction_counts(count: impl Fn(ReactionRecord) -> u64) -> Vec<ReactionCount> {
+    ReactionRecord::ALL
+        .into_iter()
+        .filter_map(|r| {
+            let n = count(r);
+            (n > 0).then(|| ReactionCount {
+                reaction: reaction_from_record(r),
+                count: n as u32,
+            })
+        })
+        .collect()
+}
+
+/// A "who reacted" row: the reacting user's already-built profile, with how
+/// and when they reacted.
+pub fn reactor_from_record(
+    user: UserProfile,
+    reaction: ReactionRecord,
+    reacted_at: &str,
+) -> Reactor {
+    Reactor {
+        user,
+        reaction: reaction_from_record(reaction),
+        reacted_at: parse_ts(reacted_at),
+    }
 }
 
 // ===========================================================================
@@ -1883,17 +2115,7 @@ pub fn cricket_format_args(format: Option<&MatchFormatRecord>) -> (u32, bool, bo
 /// Collect every actor user id referenced by a notification kind, so the caller
 /// can hydrate the actor `UserProfile`s in one pass.
 pub fn notification_actor_id(kind: &NotificationKindRecord) -> &str {
-    match kind {
-        NotificationKindRecord::MatchInvitation { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::TeamInvitation { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::InvitationAccepted { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::Follow { actor_user_id } => actor_user_id,
-        NotificationKindRecord::Like { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::Comment { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::Reply { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::ScoreSubmitted { actor_user_id, .. } => actor_user_id,
-        NotificationKindRecord::ScoreConfirmed { actor_user_id, .. } => actor_user_id,
-    }
+    kind.actor_user_id()
 }
 
 /// Build the API `Notification` from a record, given the resolved actor profile
@@ -1934,6 +2156,14 @@ pub fn notification_from_record(rec: &NotificationRecord, actor: UserProfile) ->
         NotificationKindRecord::Follow { .. } => {
             NotificationKind::Follow(FollowNotification { follower: actor })
         }
+        NotificationKindRecord::FollowRequest { .. } => {
+            NotificationKind::FollowRequest(FollowRequestNotification { requester: actor })
+        }
+        NotificationKindRecord::FollowRequestApproved { .. } => {
+            NotificationKind::FollowRequestApproved(FollowRequestApprovedNotification {
+                approved_by: actor,
+            })
+        }
         NotificationKindRecord::Like {
             match_id,
             match_name,
@@ -1991,12 +2221,261 @@ pub fn notification_from_record(rec: &NotificationRecord, actor: UserProfile) ->
             match_name: match_name.clone(),
             submission_id: submission_id.clone(),
         }),
+        NotificationKindRecord::TeamJoinRequest {
+            team_id, team_name, ..
+        } => NotificationKind::TeamJoinRequest(TeamJoinRequestNotification {
+            requester: actor,
+            team_id: team_id.clone(),
+            team_name: team_name.clone(),
+        }),
+        NotificationKindRecord::TeamJoinApproved {
+            team_id, team_name, ..
+        } => NotificationKind::TeamJoinApproved(TeamJoinApprovedNotification {
+            approved_by: actor,
+            team_id: team_id.clone(),
+            team_name: team_name.clone(),
+        }),
+        NotificationKindRecord::MatchCancelled {
+            match_id,
+            match_name,
+            reason,
+            ..
+        } => NotificationKind::MatchCancelled(MatchCancelledNotification {
+            cancelled_by: actor,
+            match_id: match_id.clone(),
+            match_name: match_name.clone(),
+            reason: reason.clone(),
+        }),
+        NotificationKindRecord::MatchPostponed {
+            match_id,
+            match_name,
+            reason,
+            starts_at,
+            ..
+        } => NotificationKind::MatchPostponed(MatchPostponedNotification {
+            postponed_by: actor,
+            match_id: match_id.clone(),
+            match_name: match_name.clone(),
+            reason: reason.clone(),
+            starts_at: parse_ts(starts_at),
+        }),
+        NotificationKindRecord::Mention {
+            match_id,
+            comment_id,
+            parent_comment_id,
+            preview,
+            ..
+        } => NotificationKind::Mention(MentionNotification {
+            mentioned_by: actor,
+            match_id: match_id.clone(),
+            comment_id: comment_id.clone(),
+            parent_comment_id: parent_comment_id.clone(),
+            preview: preview.clone(),
+        }),
+        NotificationKindRecord::Reaction {
+            match_id,
+            match_name,
+            reaction,
+            comment_id,
+            ..
+        } => NotificationKind::Reaction(ReactionNotification {
+            reacted_by: actor,
+            reaction: reaction_from_record(*reaction),
+            match_id: match_id.clone(),
+            match_name: match_name.clone(),
+            comment_id: comment_id.clone(),
+        }),
     };
     Notification {
         id: rec.id.clone(),
         is_read: rec.is_read,
         created_at: parse_ts(&rec.created_at),
         kind,
+        actor_count: rec.actor_count() as u32,
+    }
+}
+
+pub fn notification_type_from_record(t: NotificationTypeRecord) -> NotificationType {
+    match t {
+        NotificationTypeRecord::MatchInvitation => NotificationType::MatchInvitation,
+        NotificationTypeRecord::TeamInvitation => NotificationType::TeamInvitation,
+        NotificationTypeRecord::InvitationAccepted => NotificationType::InvitationAccepted,
+        NotificationTypeRecord::Follow => NotificationType::Follow,
+        NotificationTypeRecord::FollowRequest => NotificationType::FollowRequest,
+        NotificationTypeRecord::FollowRequestApproved => NotificationType::FollowRequestApproved,
+        NotificationTypeRecord::Like => NotificationType::Like,
+        NotificationTypeRecord::Comment => NotificationType::Comment,
+        NotificationTypeRecord::Reply => NotificationType::Reply,
+        NotificationTypeRecord::ScoreSubmitted => NotificationType::ScoreSubmitted,
+        NotificationTypeRecord::ScoreConfirmed => NotificationType::ScoreConfirmed,
+        NotificationTypeRecord::TeamJoinRequest => NotificationType::TeamJoinRequest,
+        NotificationTypeRecord::TeamJoinApproved => NotificationType::TeamJoinApproved,
+        NotificationTypeRecord::MatchCancelled => NotificationType::MatchCancelled,
+        NotificationTypeRecord::MatchPostponed => NotificationType::MatchPostponed,
+        NotificationTypeRecord::Mention => NotificationType::Mention,
+        NotificationTypeRecord::Reaction => NotificationType::Reaction,
+    }
+}
+
+pub fn notification_type_to_record(t: NotificationType) -> NotificationTypeRecord {
+    match t {
+        NotificationType::MatchInvitation => NotificationTypeRecord::MatchInvitation,
+        NotificationType::TeamInvitation => NotificationTypeRecord::TeamInvitation,
+        NotificationType::InvitationAccepted => NotificationTypeRecord::InvitationAccepted,
+        NotificationType::Follow => NotificationTypeRecord::Follow,
+        NotificationType::FollowRequest => NotificationTypeRecord::FollowRequest,
+        NotificationType::FollowRequestApproved => NotificationTypeRecord::FollowRequestApproved,
+        NotificationType::Like => NotificationTypeRecord::Like,
+        NotificationType::Comment => NotificationTypeRecord::Comment,
+        NotificationType::Reply => NotificationTypeRecord::Reply,
+        NotificationType::ScoreSubmitted => NotificationTypeRecord::ScoreSubmitted,
+        NotificationType::ScoreConfirmed => NotificationTypeRecord::ScoreConfirmed,
+        NotificationType::TeamJoinRequest => NotificationTypeRecord::TeamJoinRequest,
+        NotificationType::TeamJoinApproved => NotificationTypeRecord::TeamJoinApproved,
+        NotificationType::MatchCancelled => NotificationTypeRecord::MatchCancelled,
+        NotificationType::MatchPostponed => NotificationTypeRecord::MatchPostponed,
+        NotificationType::Mention => NotificationTypeRecord::Mention,
+        NotificationType::Reaction => NotificationTypeRecord::Reaction,
+    }
+}
+
+pub fn notification_preferences_from_record(
+    rec: &NotificationPreferencesRecord,
+) -> NotificationPreferences {
+    NotificationPreferences {
+        channels: rec
+            .channels
+            .iter()
+            .map(|c| NotificationChannels {
+                kind: notification_type_from_record(c.kind),
+                in_app: c.in_app,
+                push: c.push,
+                email: c.email,
+            })
+            .collect(),
+        muted_team_ids: rec.muted_team_ids.clone(),
+        muted_match_ids: rec.muted_match_ids.clone(),
+        quiet_hours: rec.quiet_hours.map(|q| QuietHours {
+            start: format_minute_of_day(q.start_minute),
+            end: format_minute_of_day(q.end_minute),
+            utc_offset_minutes: q.utc_offset_minutes,
+        }),
+        digest: digest_frequency_from_record(rec.digest),
+    }
+}
+
+pub fn digest_frequency_from_record(f: DigestFrequencyRecord) -> DigestFrequency {
+    match f {
+        DigestFrequencyRecord::Off => DigestFrequency::Off,
+        DigestFrequencyRecord::Daily => DigestFrequency::Daily,
+        DigestFrequencyRecord::Weekly => DigestFrequency::Weekly,
+    }
+}
+
+pub fn digest_frequency_to_record(f: DigestFrequency) -> DigestFrequencyRecord {
+    match f {
+        DigestFrequency::Off => DigestFrequencyRecord::Off,
+        DigestFrequency::Daily => DigestFrequencyRecord::Daily,
+        DigestFrequency::Weekly => DigestFrequencyRecord::Weekly,
+    }
+}
+
+/// Validate and convert quiet hours; the error is the message for a 400.
+pub fn quiet_hours_to_record(q: &QuietHours) -> Result<QuietHoursRecord, String> {
+    // Real-world offsets run from UTC-12 to UTC+14.
+    if !(-12 * 60..=14 * 60).contains(&q.utc_offset_minutes) {
+        return Err(format!(
+            "utc_offset_minutes must be between -720 and 840, got {}",
+            q.utc_offset_minutes
+        ));
+    }
+    Ok(QuietHoursRecord {
+        start_minute: parse_minute_of_day(&q.start)?,
+        end_minute: parse_minute_of_day(&q.end)?,
+        utc_offset_minutes: q.utc_offset_minutes,
+    })
+}
+
+fn format_minute_of_day(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+fn parse_minute_of_day(raw: &str) -> Result<u32, String> {
+    use chrono::Timelike;
+
+    chrono::NaiveTime::parse_from_str(raw, "%H:%M")
+        .map(|t| t.hour() * 60 + t.minute())
+        .map_err(|_| format!("{raw:?} is not a time of day (HH:MM)"))
+}
+
+// ===========================================================================
+// Reports
+// ===========================================================================
+
+/// The stored string tag for a report reason.
+pub fn report_reason_str(reason: ReportReason) -> &'static str {
+    match reason {
+        ReportReason::Spam => "spam",
+        ReportReason::Harassment => "harassment",
+        ReportReason::Inappropriate => "inappropriate",
+        ReportReason::Other => "other",
+    }
+}
+
+fn report_reason_from_str(s: &str) -> ReportReason {
+    match s {
+        "spam" => ReportReason::Spam,
+        "harassment" => ReportReason::Harassment,
+        "inappropriate" => ReportReason::Inappropriate,
+        _ => ReportReason::Other,
+    }
+}
+
+/// Build the API `ReportCase` from a record, with the `preview` the caller
+/// read off the target.
+pub fn report_case_from_record(rec: &ReportCaseRecord, preview: Option<String>) -> ReportCase {
+    let (target_type, target_id, match_id) = match &rec.target {
+        ReportTargetRecord::Comment {
+            match_id,
+            comment_id,
+            ..
+        } => (
+            ReportTargetType::Comment,
+            comment_id.clone(),
+            Some(match_id.clone()),
+        ),
+        ReportTargetRecord::Match { match_id } => (ReportTargetType::Match, match_id.clone(), None),
+        ReportTargetRecord::User { user_id } => (ReportTargetType::User, user_id.clone(), None),
+        ReportTargetRecord::Asset { asset_id, match_id } => {
+            (ReportTargetType::Asset, asset_id.clone(), match_id.clone())
+        }
+    };
+    let status = match rec.status.as_str() {
+        "dismissed" => ReportCaseStatus::Dismissed,
+        "actioned" => ReportCaseStatus::Actioned,
+        _ => ReportCaseStatus::Pending,
+    };
+    ReportCase {
+        id: rec.id.clone(),
+        status,
+        target_type,
+        target_id,
+        match_id,
+        preview,
+        report_count: rec.report_count,
+        is_hidden: rec.hidden_at.is_some(),
+        pending_since: parse_ts_opt(&rec.pending_since),
+        last_reported_at: parse_ts(&rec.last_reported_at),
+    }
+}
+
+/// Build the API `Report` from a record and the hydrated reporter.
+pub fn report_from_record(rec: &ReportRecord, reporter: UserProfile) -> Report {
+    Report {
+        reporter,
+        reason: report_reason_from_str(&rec.reason),
+        details: rec.details.clone(),
+        created_at: parse_ts(&rec.created_at),
     }
 }
 
@@ -2425,4 +2904,74 @@ mod tests {
             assert_eq!(original_json, round_tripped.to_json());
         }
     }
+
+    #[test]
+    fn embedded_restricted_profile_hides_stats_from_others() {
+        let user = UserRecord {
+            id: "u1".into(),
+            email: "u1@example.com".into(),
+            name: "Junior".into(),
+            profile_image_url: None,
+            follower_count: 0,
+            following_count: 0,
+            unread_count: 0,
+            stats: HashMap::from([(
+                "football".to_string(),
+                UserSportStatsRecord {
+                    matches_played: 3,
+                    wins: 2,
+                },
+            )]),
+            visibility: VisibilityRecord::Followers,
+            is_moderator: false,
+            suspended_at: None,
+            hidden_at: None,
+            profile_image_hidden_at: None,
+            created_at: String::new(),
+        };
+
+        assert!(
+            embedded_user_profile(&user, false, Some("u2"))
+                .stats
+                .is_empty()
+        );
+        assert!(embedded_user_profile(&user, false, None).stats.is_empty());
+        assert_eq!(
+            embedded_user_profile(&user, false, Some("u1")).stats.len(),
+            1
+        );
+
+        let public = UserRecord {
+            visibility: VisibilityRecord::Public,
+            ..user
+        };
+        assert_eq!(
+            embedded_user_profile(&public, false, Some("u2"))
+                .stats
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn quiet_hours_parse_and_format() {
+        let input = QuietHours {
+            start: "22:30".into(),
+            end: "07:00".into(),
+            utc_offset_minutes: 60,
+        };
+        let rec = quiet_hours_to_record(&input).unwrap();
+        assert_eq!((rec.start_minute, rec.end_minute), (22 * 60 + 30, 7 * 60));
+        assert_eq!(format_minute_of_day(rec.start_minute), "22:30");
+        assert_eq!(format_minute_of_day(rec.end_minute), "07:00");
+
+        for (start, offset) in [("24:00", 0), ("7pm", 0), ("22:00", 15 * 60)] {
+            let bad = QuietHours {
+                start: start.into(),
+                end: "07:00".into(),
+                utc_offset_minutes: offset,
+            };
+            assert!(quiet_hours_to_record(&bad).is_err(), "{start} {offset}");
+        }
+    }
 }
@@ -119,17 +119,28 @@ pub struct InvitationTeamContext {
 }
 
 /// Invite people to a team or match. Agon users by id; external people by name
-/// (the server mints a token invitation for each).
+/// (the server mints a token invitation for each), or by name and email to
+/// also email them their invitation link.
 #[derive(Object)]
 pub struct AddInvitationsInput {
     pub invited_user_ids: Vec<String>,
     pub invited_external_names: Vec<String>,
+    #[oai(default)]
+    pub invited_externals: Vec<ExternalInvitee>,
     /// (Match invitations only) the side to invite these people to. None invites
     /// them to the match without a side, to be chosen on acceptance. Ignored for
     /// team invitations.
     pub side_id: Option<String>,
 }
 
+/// Someone without an account, invited by name.
+#[derive(Object)]
+pub struct ExternalInvitee {
+    pub name: String,
+    /// Where to email the invitation link. Not shown back on the invitation.
+    pub email: Option<String>,
+}
+
 #[derive(Object)]
 pub struct RespondToInvitationInput {
     pub response: InvitationResponse,
@@ -0,0 +1,114 @@
+use poem_openapi::{Enum, Object};
+
+use crate::UserProfile;
+
+/// What kind of thing a report is about.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum ReportTargetType {
+    /// A comment or reply on a match.
+    Comment,
+    Match,
+    User,
+    /// A match header photo or a profile image.
+    Asset,
+}
+
+/// Why something was reported.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Inappropriate,
+    Other,
+}
+
+#[derive(Object)]
+pub struct CreateReportInput {
+    pub target_type: ReportTargetType,
+    /// The comment, match, user or asset id.
+    pub target_id: String,
+    /// The match a reported comment or header photo is on. Required for
+    /// those; ignored otherwise.
+    pub match_id: Option<String>,
+    pub reason: ReportReason,
+    /// Anything the moderators should know.
+    pub details: Option<String>,
+}
+
+/// Where a moderation case stands.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum ReportCaseStatus {
+    /// Awaiting review.
+    Pending,
+    /// A moderator found nothing wrong.
+    Dismissed,
+    /// A moderator removed the content or suspended its author.
+    Actioned,
+}
+
+/// Every report against one comment, match, user or asset.
+#[derive(Object)]
+pub struct ReportCase {
+    pub id: String,
+    pub status: ReportCaseStatus,
+    pub target_type: ReportTargetType,
+    pub target_id: String,
+    /// The match a reported comment or header photo is on.
+    pub match_id: Option<String>,
+    /// The comment's text, the match's or user's name, or the image URL —
+    /// whatever a moderator needs to judge it without opening it. `None` once
+    /// the content is gone.
+    pub preview: Option<String>,
+    /// Distinct users who reported it since it was last reviewed.
+    pub report_count: u32,
+    /// True while it's hidden automatically, pending review.
+    pub is_hidden: bool,
+    /// When it entered the queue.
+    pub pending_since: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_reported_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+pub struct ReportCasePage {
+    pub items: Vec<ReportCase>,
+    pub next_cursor: Option<String>,
+}
+
+/// One user's report.
+#[derive(Object)]
+pub struct Report {
+    pub reporter: UserProfile,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A case with a page of its reports.
+#[derive(Object)]
+pub struct ReportCaseDetail {
+    pub case: ReportCase,
+    pub reports: Vec<Report>,
+    /// Cursor for the next page of `reports`.
+    pub next_cursor: Option<String>,
+}
+
+/// What a moderator does with a case.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Nothing wrong: restore anything hidden automatically.
+    Dismiss,
+    /// Take the content down: tombstone a comment, hide a match or profile
+    /// from everyone but its participants, or remove a photo.
+    Remove,
+    /// `Remove`, and suspend whoever posted it (or the reported user).
+    Suspend,
+}
+
+#[derive(Object)]
+pub struct ResolveReportInput {
+    pub action: ModerationAction,
+}
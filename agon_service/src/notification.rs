@@ -1,7 +1,8 @@
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 
 use crate::UserProfile;
 use crate::membership::InvitationContext;
+use crate::reaction::Reaction;
 
 /// A notification feed entry. A thin, read-only record of "something happened"
 /// that references the underlying entity rather than owning its state — the
@@ -13,10 +14,17 @@ use crate::membership::InvitationContext;
 pub struct Notification {
     pub id: String,
     pub is_read: bool,
+    /// When it happened — for a grouped notification, when its latest actor
+    /// joined.
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// The event. Person-triggered kinds carry an `actor`; system-generated
     /// kinds (e.g. a match completing because time passed) simply omit it.
     pub kind: NotificationKind,
+    /// How many people this row stands for. Reactions and comments on a match
+    /// are grouped into one row per few hours, which later actors join in
+    /// place; `kind`'s actor is the latest, so a client renders "Sam and
+    /// `actor_count - 1` others". 1 for everything else.
+    pub actor_count: u32,
 }
 
 /// The kind of notification. Each variant carries only the references and small
@@ -36,7 +44,13 @@ pub enum NotificationKind {
     InvitationAccepted(InvitationAcceptedNotification),
     /// Someone followed you.
     Follow(FollowNotification),
-    /// Someone liked your match.
+    /// Someone asked to follow your private profile. Approve/Deny act on the
+    /// referenced follow request.
+    FollowRequest(FollowRequestNotification),
+    /// Your request to follow a private profile was approved.
+    FollowRequestApproved(FollowRequestApprovedNotification),
+    /// Someone liked your match. Only on rows from before reactions; a like
+    /// now arrives as a `Reaction`.
     Like(LikeNotification),
     /// Someone commented on a match.
     Comment(CommentNotification),
@@ -48,6 +62,20 @@ pub enum NotificationKind {
     ScoreSubmitted(ScoreSubmittedNotification),
     /// A score you submitted was confirmed by the other side(s).
     ScoreConfirmed(ScoreConfirmedNotification),
+    /// Someone asked to join a team you admin. Approve/Deny act on the
+    /// referenced join request.
+    TeamJoinRequest(TeamJoinRequestNotification),
+    /// Your request to join a team was approved.
+    TeamJoinApproved(TeamJoinApprovedNotification),
+    /// A match you play in or follow was cancelled.
+    MatchCancelled(MatchCancelledNotification),
+    /// A match you play in or follow was moved to a new start time.
+    MatchPostponed(MatchPostponedNotification),
+    /// Someone `@`-mentioned you in a comment or reply.
+    Mention(MentionNotification),
+    /// Someone reacted to your match or to your comment on it. Grouped: see
+    /// `Notification::actor_count`.
+    Reaction(ReactionNotification),
 }
 
 #[derive(Object)]
@@ -88,6 +116,19 @@ pub struct FollowNotification {
     pub follower: UserProfile,
 }
 
+#[derive(Object)]
+pub struct FollowRequestNotification {
+    /// The user asking to follow you. Approve/Deny →
+    /// `POST /users/me/follow-requests/:user_id/respond` with their id.
+    pub requester: UserProfile,
+}
+
+#[derive(Object)]
+pub struct FollowRequestApprovedNotification {
+    /// The user who approved the request, and whom you now follow.
+    pub approved_by: UserProfile,
+}
+
 #[derive(Object)]
 pub struct LikeNotification {
     /// The user who liked the match.
@@ -145,6 +186,71 @@ pub struct ScoreConfirmedNotification {
     pub submission_id: String,
 }
 
+#[derive(Object)]
+pub struct TeamJoinRequestNotification {
+    /// The user asking to join. Approve/Deny →
+    /// `POST /teams/:team_id/join-requests/:user_id/respond` with their id.
+    pub requester: UserProfile,
+    pub team_id: String,
+    /// Display label so the row renders without fetching the team.
+    pub team_name: String,
+}
+
+#[derive(Object)]
+pub struct TeamJoinApprovedNotification {
+    /// The admin who approved the request.
+    pub approved_by: UserProfile,
+    pub team_id: String,
+    /// Display label so the row renders without fetching the team.
+    pub team_name: String,
+}
+
+#[derive(Object)]
+pub struct MatchCancelledNotification {
+    /// The organizer who cancelled the match.
+    pub cancelled_by: UserProfile,
+    pub match_id: String,
+    /// Display label so the row renders without fetching the match.
+    pub match_name: String,
+    pub reason: String,
+}
+
+#[derive(Object)]
+pub struct MatchPostponedNotification {
+    /// The organizer who postponed the match.
+    pub postponed_by: UserProfile,
+    pub match_id: String,
+    /// Display label so the row renders without fetching the match.
+    pub match_name: String,
+    pub reason: String,
+    /// The new start time, as of the postponement.
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+pub struct MentionNotification {
+    /// The user who mentioned you.
+    pub mentioned_by: UserProfile,
+    pub match_id: String,
+    /// The mentioning comment's own id.
+    pub comment_id: String,
+    /// The top-level comment whose thread it belongs to, when it's a reply.
+    pub parent_comment_id: Option<String>,
+    /// A short snapshot of the comment text for the row.
+    pub preview: String,
+}
+
+#[derive(Object)]
+pub struct ReactionNotification {
+    /// The latest user to react.
+    pub reacted_by: UserProfile,
+    pub reaction: Reaction,
+    pub match_id: String,
+    pub match_name: String,
+    /// The comment reacted to. None when it was the match itself.
+    pub comment_id: Option<String>,
+}
+
 /// One page of notifications. `next_cursor` absent => end.
 #[derive(Object)]
 pub struct NotificationPage {
@@ -157,3 +263,91 @@ pub struct NotificationPage {
 pub struct UnreadCount {
     pub unread_count: u32,
 }
+
+/// A kind of notification, without its payload — what preferences are set
+/// per. Mirrors `NotificationKind`'s variants.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum NotificationType {
+    MatchInvitation,
+    TeamInvitation,
+    InvitationAccepted,
+    Follow,
+    FollowRequest,
+    FollowRequestApproved,
+    Like,
+    Comment,
+    Reply,
+    ScoreSubmitted,
+    ScoreConfirmed,
+    TeamJoinRequest,
+    TeamJoinApproved,
+    MatchCancelled,
+    MatchPostponed,
+    Mention,
+    Reaction,
+}
+
+/// What the caller wants to be notified about, and how. Saved wholesale: a
+/// `PUT` replaces every field.
+#[derive(Object)]
+pub struct NotificationPreferences {
+    /// Per-kind toggles. A kind not listed is in-app and pushed, and emailed
+    /// if it's an invitation, cancellation or postponement. Pushes and emails
+    /// go out for an in-app notification, so a kind switched off in-app gets
+    /// neither.
+    #[oai(default)]
+    pub channels: Vec<NotificationChannels>,
+    /// Teams to hear nothing about: neither the team's own notifications nor
+    /// those about matches it plays in.
+    #[oai(default)]
+    pub muted_team_ids: Vec<String>,
+    /// Matches to hear nothing about.
+    #[oai(default)]
+    pub muted_match_ids: Vec<String>,
+    /// When set, pushes that arrive in this window are held and sent when it
+    /// ends. In-app notifications are unaffected.
+    pub quiet_hours: Option<QuietHours>,
+    /// How often to email a summary of upcoming matches, recent results and
+    /// activity. Off unless chosen.
+    #[oai(default)]
+    pub digest: DigestFrequency,
+}
+
+/// How often the caller gets a digest email.
+#[derive(Enum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    /// Every morning (08:00 UTC).
+    Daily,
+    /// Monday mornings (08:00 UTC).
+    Weekly,
+}
+
+/// One kind's toggles.
+#[derive(Object)]
+pub struct NotificationChannels {
+    pub kind: NotificationType,
+    /// Whether it shows in the notification list.
+    pub in_app: bool,
+    /// Whether it's also pushed to the caller's devices.
+    pub push: bool,
+    /// Whether it's also emailed. Omitted = the kind's default (see
+    /// `NotificationPreferences.channels`).
+    pub email: Option<bool>,
+}
+
+/// A daily window in local time. `start` after `end` wraps past midnight
+/// (`22:00`–`07:00`).
+#[derive(Object)]
+pub struct QuietHours {
+    /// `HH:MM`, local time.
+    pub start: String,
+    /// `HH:MM`, local time; the window ends as this minute begins.
+    pub end: String,
+    /// The caller's current offset from UTC in minutes (e.g. `-300` for
+    /// UTC-5). Clients re-save when it changes, e.g. for daylight saving.
+    pub utc_offset_minutes: i32,
+}
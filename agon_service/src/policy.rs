@@ -0,0 +1,456 @@
+//! Authorization policy for team, club and match mutations: "may this caller
+//! do this action on this resource?".
+//!
+//! Handlers resolve the caller's standing on the resource once — from the
+//! aggregate they've already loaded (plus a club-admin lookup for teams in a
+//! club) — then ask [`authorize_team`], [`authorize_club`] or
+//! [`authorize_match`]. The rules live here, as pure functions over those
+//! standings, so the whole permission matrix is unit-tested in one place
+//! rather than scattered across handlers. A refusal is a [`Denied`] carrying
+//! the 403 message, which every response enum's `Forbidden` variant takes.
+//!
+//! Resources that are owned by a single user rather than governed by roles
+//! (a comment's author, an invitation's inviter, the caller's own follows)
+//! keep their ownership checks inline.
+
+use agon_core::dao::match_ops::MatchAggregate;
+use agon_core::dao::records::TeamMemberRecord;
+use poem_openapi::payload::PlainText;
+
+/// A refused action. The message is what the 403 body says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Denied(&'static str);
+
+impl Denied {
+    pub fn message(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<Denied> for PlainText<String> {
+    fn from(denied: Denied) -> Self {
+        PlainText(denied.0.to_string())
+    }
+}
+
+// ===========================================================================
+// Teams
+// ===========================================================================
+
+/// The caller's standing on a team.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TeamStanding {
+    /// A current (not departed) member of the team, in any role.
+    pub member: bool,
+    /// A current member with the admin role.
+    pub member_admin: bool,
+    /// An admin of the club the team belongs to. Club admins act as admins
+    /// of every team in the club without being members of them.
+    pub club_admin: bool,
+}
+
+impl TeamStanding {
+    /// The caller's standing from the team's member list, given whether they
+    /// admin the team's club (looked up separately, only when it has one).
+    pub fn of(members: &[TeamMemberRecord], uid: &str, club_admin: bool) -> Self {
+        let mine = || {
+            members
+                .iter()
+                .filter(|m| m.left_at.is_none() && m.user_id.as_deref() == Some(uid))
+        };
+        TeamStanding {
+            member: mine().next().is_some(),
+            member_admin: mine().any(|m| m.role == "admin"),
+            club_admin,
+        }
+    }
+
+    fn admin(&self) -> bool {
+        self.member_admin || self.club_admin
+    }
+}
+
+/// A mutation on a team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamAction {
+    /// Rename the team.
+    Update,
+    /// Add members directly, without an invitation.
+    AddMembers,
+    /// Remove a membership. `own` is true when it's the caller's own (leaving
+    /// the team), which any member may do.
+    RemoveMember { own: bool },
+    /// Invite people to the team.
+    Invite,
+    /// View, create or revoke invite links.
+    ManageInviteLinks,
+    /// View, approve or deny join requests.
+    ManageJoinRequests,
+    /// Create seasons.
+    ManageSeasons,
+    /// Post an announcement to the team's members and followers.
+    PostAnnouncement,
+    /// Archive the team.
+    Archive,
+    /// Hand the admin role to another member. Needs an admin *member*: club
+    /// admins have no role on the team to hand over.
+    TransferAdmin,
+    /// Put the team in a club. Needs an admin member of the team who also
+    /// admins the target club (`target_club_admin`).
+    JoinClub { target_club_admin: bool },
+    /// Take the team out of its club.
+    LeaveClub,
+}
+
+/// Whether a caller with `standing` may perform `action` on the team.
+pub fn authorize_team(standing: TeamStanding, action: TeamAction) -> Result<(), Denied> {
+    let allowed = match action {
+        TeamAction::RemoveMember { own: true } => standing.member || standing.admin(),
+        TeamAction::TransferAdmin => standing.member_admin,
+        TeamAction::JoinClub { target_club_admin } => standing.member_admin && target_club_admin,
+        TeamAction::Update
+        | TeamAction::AddMembers
+        | TeamAction::RemoveMember { own: false }
+        | TeamAction::Invite
+        | TeamAction::ManageInviteLinks
+        | TeamAction::ManageJoinRequests
+        | TeamAction::ManageSeasons
+        | TeamAction::PostAnnouncement
+        | TeamAction::Archive
+        | TeamAction::LeaveClub => standing.admin(),
+    };
+    if allowed {
+        return Ok(());
+    }
+    Err(Denied(match action {
+        TeamAction::Update => "only team admins can edit this team",
+        TeamAction::AddMembers => "only team admins can add members",
+        TeamAction::RemoveMember { own: true } => "only a member can leave this team",
+        TeamAction::RemoveMember { own: false } => "only team admins can remove other members",
+        TeamAction::Invite => "only team admins can invite people to this team",
+        TeamAction::ManageInviteLinks => "only team admins can manage invite links",
+        TeamAction::ManageJoinRequests => "only team admins can manage join requests",
+        TeamAction::ManageSeasons => "only team admins can manage seasons",
+        TeamAction::PostAnnouncement => "only team admins can post announcements",
+        TeamAction::Archive => "only team admins can archive a team",
+        TeamAction::TransferAdmin => "only an admin member can hand over the team",
+        TeamAction::JoinClub { .. } => "only a club admin who also admins the team can add it",
+        TeamAction::LeaveClub => "only club or team admins can remove a team from its club",
+    }))
+}
+
+// ===========================================================================
+// Clubs
+// ===========================================================================
+
+/// The caller's standing on a club.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClubStanding {
+    pub admin: bool,
+}
+
+/// A mutation on a club itself (its teams are governed by [`TeamAction`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClubAction {
+    /// Make another user a club admin.
+    AddAdmin,
+}
+
+/// Whether a caller with `standing` may perform `action` on the club.
+pub fn authorize_club(standing: ClubStanding, action: ClubAction) -> Result<(), Denied> {
+    match action {
+        ClubAction::AddAdmin if standing.admin => Ok(()),
+        ClubAction::AddAdmin => Err(Denied("only club admins can add club admins")),
+    }
+}
+
+// ===========================================================================
+// Matches
+// ===========================================================================
+
+/// The caller's standing on a match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchStanding {
+    /// Created (organized) the match, whether or not they play in it.
+    pub creator: bool,
+    /// A linked player who was added directly or accepted their invitation.
+    /// Pending and declined invitees aren't participants.
+    pub participant: bool,
+    /// A linked player assigned to one of the sides.
+    pub on_side: bool,
+    /// Has a block, either way, with whoever's on the other end of a social
+    /// action: the author of the comment being replied or reacted to, or else
+    /// the match's creator. Not derivable from the aggregate: set by the
+    /// handler with [`MatchStanding::blocked`] before asking about social
+    /// actions.
+    pub blocked: bool,
+}
+
+impl MatchStanding {
+    pub fn of(agg: &MatchAggregate, uid: &str) -> Self {
+        let mine = || {
+            agg.players
+                .iter()
+                .filter(|p| p.user_id.as_deref() == Some(uid))
+        };
+        MatchStanding {
+            creator: agg.match_.created_by_user_id == uid,
+            participant: mine().any(|p| match &p.invitation {
+                None => true,
+                Some(inv) => inv.status == "accepted",
+            }),
+            on_side: mine().any(|p| p.side_id.is_some()),
+            blocked: false,
+        }
+    }
+
+    pub fn blocked(self, blocked: bool) -> Self {
+        MatchStanding { blocked, ..self }
+    }
+
+    fn manager(&self) -> bool {
+        self.creator || self.participant
+    }
+}
+
+/// A mutation on a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchAction {
+    /// Edit details, roster or result.
+    Update,
+    Cancel,
+    Postpone,
+    /// Invite people to the match.
+    Invite,
+    /// Append live-scoring events.
+    RecordLiveEvents,
+    /// Undo the most recent live-scoring event.
+    UndoLiveEvent,
+    /// Confirm or dispute a submitted score, on behalf of the caller's side.
+    RespondToScore,
+    /// Comment on the match, or reply to a comment on it.
+    Comment,
+    /// React to the match or to a comment on it (liking included).
+    React,
+}
+
+/// Whether a caller with `standing` may perform `action` on the match.
+pub fn authorize_match(standing: MatchStanding, action: MatchAction) -> Result<(), Denied> {
+    let allowed = match action {
+        MatchAction::RespondToScore => standing.on_side,
+        MatchAction::Comment | MatchAction::React => !standing.blocked,
+        MatchAction::Update
+        | MatchAction::Cancel
+        | MatchAction::Postpone
+        | MatchAction::Invite
+        | MatchAction::RecordLiveEvents
+        | MatchAction::UndoLiveEvent => standing.manager(),
+    };
+    if allowed {
+        return Ok(());
+    }
+    Err(Denied(match action {
+        MatchAction::Update => "only a participant can edit this match",
+        MatchAction::Cancel => "only a participant can cancel this match",
+        MatchAction::Postpone => "only a participant can postpone this match",
+        MatchAction::Invite => "only a participant can invite people to this match",
+        MatchAction::RecordLiveEvents => "only a participant can record live events for this match",
+        MatchAction::UndoLiveEvent => "only a participant can undo live events for this match",
+        MatchAction::RespondToScore => "only an assigned participant may respond to the score",
+        MatchAction::Comment => "you can't comment on this match",
+        MatchAction::React => "you can't react to this match",
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOBODY: TeamStanding = TeamStanding {
+        member: false,
+        member_admin: false,
+        club_admin: false,
+    };
+    const MEMBER: TeamStanding = TeamStanding {
+        member: true,
+        ..NOBODY
+    };
+    const ADMIN: TeamStanding = TeamStanding {
+        member: true,
+        member_admin: true,
+        club_admin: false,
+    };
+    const CLUB_ADMIN: TeamStanding = TeamStanding {
+        club_admin: true,
+        ..NOBODY
+    };
+
+    /// Every team action against every standing: (action, nobody, member,
+    /// admin member, club admin).
+    #[test]
+    fn team_permission_matrix() {
+        let cases = [
+            (TeamAction::Update, false, false, true, true),
+            (TeamAction::AddMembers, false, false, true, true),
+            (
+                TeamAction::RemoveMember { own: false },
+                false,
+                false,
+                true,
+                true,
+            ),
+            (
+                TeamAction::RemoveMember { own: true },
+                false,
+                true,
+                true,
+                true,
+            ),
+            (TeamAction::Invite, false, false, true, true),
+            (TeamAction::ManageInviteLinks, false, false, true, true),
+            (TeamAction::ManageJoinRequests, false, false, true, true),
+            (TeamAction::ManageSeasons, false, false, true, true),
+            (TeamAction::PostAnnouncement, false, false, true, true),
+            (TeamAction::Archive, false, false, true, true),
+            (TeamAction::TransferAdmin, false, false, true, false),
+            (TeamAction::LeaveClub, false, false, true, true),
+            (
+                TeamAction::JoinClub {
+                    target_club_admin: true,
+                },
+                false,
+                false,
+                true,
+                false,
+            ),
+            (
+                TeamAction::JoinClub {
+                    target_club_admin: false,
+                },
+                false,
+                false,
+                false,
+                false,
+            ),
+        ];
+        for (action, nobody, member, admin, club_admin) in cases {
+            for (standing, expected) in [
+                (NOBODY, nobody),
+                (MEMBER, member),
+                (ADMIN, admin),
+                (CLUB_ADMIN, club_admin),
+            ] {
+                assert_eq!(
+                    authorize_team(standing, action).is_ok(),
+                    expected,
+                    "{action:?} as {standing:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn club_permission_matrix() {
+        assert!(authorize_club(ClubStanding { admin: true }, ClubAction::AddAdmin).is_ok());
+        assert!(authorize_club(ClubStanding { admin: false }, ClubAction::AddAdmin).is_err());
+    }
+
+    /// Every match action against every standing: (action, stranger,
+    /// creator not playing, participant without a side, participant on a
+    /// side, pending invitee assigned to a side).
+    #[test]
+    fn match_permission_matrix() {
+        let stranger = MatchStanding::default();
+        let creator = MatchStanding {
+            creator: true,
+            ..stranger
+        };
+        let unassigned = MatchStanding {
+            participant: true,
+            ..stranger
+        };
+        let on_side = MatchStanding {
+            participant: true,
+            on_side: true,
+            ..stranger
+        };
+        let invitee = MatchStanding {
+            on_side: true,
+            ..stranger
+        };
+        // A player the creator has blocked (or who has blocked them).
+        let blocked = on_side.blocked(true);
+
+        let cases = [
+            (MatchAction::Update, false, true, true, true, false),
+            (MatchAction::Cancel, false, true, true, true, false),
+            (MatchAction::Postpone, false, true, true, true, false),
+            (MatchAction::Invite, false, true, true, true, false),
+            (
+                MatchAction::RecordLiveEvents,
+                false,
+                true,
+                true,
+                true,
+                false,
+            ),
+            (MatchAction::UndoLiveEvent, false, true, true, true, false),
+            (MatchAction::RespondToScore, false, false, false, true, true),
+            (MatchAction::Comment, true, true, true, true, true),
+            (MatchAction::React, true, true, true, true, true),
+        ];
+        for (action, s, c, u, o, i) in cases {
+            // A block only ever takes away the social actions.
+            let b = o && !matches!(action, MatchAction::Comment | MatchAction::React);
+            for (standing, expected) in [
+                (stranger, s),
+                (creator, c),
+                (unassigned, u),
+                (on_side, o),
+                (invitee, i),
+                (blocked, b),
+            ] {
+                assert_eq!(
+                    authorize_match(standing, action).is_ok(),
+                    expected,
+                    "{action:?} as {standing:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn denials_carry_a_message() {
+        let denied = authorize_team(NOBODY, TeamAction::Update).unwrap_err();
+        assert_eq!(denied.message(), "only team admins can edit this team");
+        let body: PlainText<String> = denied.into();
+        assert_eq!(body.0, "only team admins can edit this team");
+    }
+
+    fn member(user_id: &str, role: &str, left: bool) -> TeamMemberRecord {
+        TeamMemberRecord {
+            team_id: "t".into(),
+            membership_id: format!("m-{user_id}"),
+            user_id: Some(user_id.into()),
+            display_name: None,
+            role: role.into(),
+            invitation: None,
+            created_at: "2026-01-01T00:00:00Z".into(),
+            left_at: left.then(|| "2026-02-01T00:00:00Z".into()),
+            previous_roles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn team_standing_ignores_departed_memberships() {
+        let members = [
+            member("admin", "admin", false),
+            member("player", "member", false),
+            member("former", "admin", true),
+        ];
+        assert_eq!(TeamStanding::of(&members, "admin", false), ADMIN);
+        assert_eq!(TeamStanding::of(&members, "player", false), MEMBER);
+        assert_eq!(TeamStanding::of(&members, "former", false), NOBODY);
+        assert_eq!(TeamStanding::of(&members, "stranger", true), CLUB_ADMIN);
+    }
+}
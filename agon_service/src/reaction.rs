@@ -0,0 +1,50 @@
+use poem_openapi::{Enum, Object};
+
+use crate::UserProfile;
+
+/// One of the fixed set of reactions on a match or comment. `like` is 👍 —
+/// liking a match is reacting with it.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[oai(rename_all = "snake_case")]
+pub enum Reaction {
+    /// 👍
+    Like,
+    /// 🔥
+    Fire,
+    /// 👏
+    Clap,
+    /// 😂
+    Laugh,
+    /// 😮
+    Wow,
+}
+
+/// How many people reacted with one reaction.
+#[derive(Object)]
+pub struct ReactionCount {
+    pub reaction: Reaction,
+    pub count: u32,
+}
+
+#[derive(Object)]
+pub struct ReactInput {
+    /// Replaces any reaction the caller already has on the target.
+    pub reaction: Reaction,
+}
+
+/// Someone who reacted, and how.
+#[derive(Object)]
+pub struct Reactor {
+    pub user: UserProfile,
+    pub reaction: Reaction,
+    /// When they reacted, or last changed their reaction.
+    pub reacted_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+pub struct ReactorPage {
+    pub items: Vec<Reactor>,
+    /// Set when more may follow. When filtering by reaction a page can be
+    /// short, or even empty, with this still set.
+    pub next_cursor: Option<String>,
+}
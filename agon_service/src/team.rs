@@ -1,6 +1,7 @@
 use poem_openapi::{Enum, Object};
 
 use crate::membership::Member;
+use crate::{UserProfile, Visibility};
 
 /// A persistent team/squad. The pool of people a match side can be drawn from;
 /// a match never derives its roster live from this (see MatchSide), it snapshots
@@ -10,12 +11,21 @@ pub struct Team {
     pub id: String,
     pub name: String,
     pub members: Vec<TeamMember>,
-    /// Shareable token for inviting people to join the team. None if no active
-    /// invite link.
+    /// Legacy single invite token, superseded by the team's invite links
+    /// (`GET /teams/:team_id/invite-links`). Not redeemable.
     pub invite_token: Option<String>,
     pub follower_count: u32,
     /// Whether the requesting user follows this team.
     pub is_followed_by_me: bool,
+    /// The club this team belongs to, if any. Its club admins are also admins
+    /// of the team.
+    pub club_id: Option<String>,
+    /// Set once the team is archived. Archived teams still resolve so their
+    /// past matches render, but can't be followed, joined or picked for new
+    /// matches.
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Who may see the team, its squad, seasons and followers.
+    pub visibility: Visibility,
 }
 
 /// A person's membership of a team: the shared `Member` (user or external,
@@ -48,15 +58,191 @@ pub struct TeamListItem {
 #[derive(Object)]
 pub struct CreateTeamInput {
     pub name: String,
+    /// Defaults to public.
+    pub visibility: Option<Visibility>,
 }
 
 /// Editable fields on a team. All optional — only supplied fields change.
 #[derive(Object)]
 pub struct UpdateTeamInput {
     pub name: Option<String>,
+    pub visibility: Option<Visibility>,
 }
 
 #[derive(Object)]
 pub struct AddTeamMembersInput {
     pub user_ids: Vec<String>,
 }
+
+#[derive(Object)]
+pub struct TransferTeamAdminInput {
+    /// Membership id of the member taking over as admin.
+    pub member_id: String,
+}
+
+/// A reusable, shareable link for joining a team. Redeemed by its
+/// `invite_token` via `POST /invite-links/redeem`; the same token also resolves
+/// through `GET /invitations/by-token/:token` for a landing-page preview.
+#[derive(Object)]
+pub struct InviteLink {
+    /// The backing invitation's id. Revoke via
+    /// `DELETE /teams/:team_id/invite-links/:invite_link_id`.
+    pub id: String,
+    pub team_id: String,
+    pub name: String,
+    pub invite_token: String,
+    pub created_by_user_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// None never expires.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// None is unlimited.
+    pub max_uses: Option<u32>,
+    pub use_count: u32,
+    /// The role people join with.
+    pub default_role: TeamRole,
+    /// True => redeeming files a join request for an admin to approve.
+    pub requires_approval: bool,
+    /// False once expired or used up.
+    pub is_active: bool,
+}
+
+#[derive(Object)]
+pub struct CreateInviteLinkInput {
+    pub name: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Must be at least 1 when given.
+    pub max_uses: Option<u32>,
+    /// Defaults to `member`.
+    pub default_role: Option<TeamRole>,
+    #[oai(default)]
+    pub requires_approval: bool,
+}
+
+/// One page of a team's invite links, newest first.
+#[derive(Object)]
+pub struct InviteLinkPage {
+    pub items: Vec<InviteLink>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Object)]
+pub struct RedeemInviteLinkInput {
+    pub invite_token: String,
+}
+
+/// A request to join a team, awaiting (or answered by) an admin.
+#[derive(Object)]
+pub struct JoinRequest {
+    pub team_id: String,
+    /// The requester. Approve/deny via
+    /// `POST /teams/:team_id/join-requests/:user_id/respond` with their id.
+    pub requester: UserProfile,
+    pub status: JoinRequestStatus,
+    /// The role they'll join with if approved.
+    pub role: TeamRole,
+    /// The invite link they came through, if any.
+    pub invite_link_id: Option<String>,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+    pub responded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Enum)]
+#[oai(rename_all = "snake_case")]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// One page of a team's pending join requests.
+#[derive(Object)]
+pub struct JoinRequestPage {
+    pub items: Vec<JoinRequest>,
+    pub next_cursor: Option<String>,
+}
+
+/// An admin's answer to a join request.
+#[derive(Enum, Debug)]
+#[oai(rename_all = "snake_case")]
+pub enum JoinRequestResponse {
+    Approved,
+    Denied,
+}
+
+#[derive(Object)]
+pub struct RespondToJoinRequestInput {
+    pub response: JoinRequestResponse,
+}
+
+/// A named date range on a team ("2024", "Spring 2025"). The season's squad
+/// and stats are derived from membership dates and the team's matches.
+#[derive(Object)]
+pub struct TeamSeason {
+    pub id: String,
+    pub team_id: String,
+    pub name: String,
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    /// Exclusive. None while the season is open-ended.
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Object)]
+pub struct CreateTeamSeasonInput {
+    pub name: String,
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    /// Must be after `starts_at` when given.
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A post by a team admin, fanned out to the feeds of the team's members
+/// and followers.
+#[derive(Object)]
+pub struct TeamAnnouncement {
+    pub id: String,
+    pub team_id: String,
+    pub author_user_id: String,
+    pub body: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Object)]
+pub struct PostTeamAnnouncementInput {
+    /// At most `ANNOUNCEMENT_MAX_CHARS` characters.
+    pub body: String,
+}
+
+/// Someone who was on the team at some point during a season — including
+/// people who have since left.
+#[derive(Object)]
+pub struct SeasonSquadMember {
+    pub member: Member,
+    /// The role they held during the season (not necessarily today's).
+    pub role: TeamRole,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+    /// None if they're still on the team.
+    pub left_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A team's results and per-player totals for one season, computed from the
+/// matches it played that started in the season and have a confirmed score.
+#[derive(Object)]
+pub struct TeamSeasonStats {
+    pub season: TeamSeason,
+    pub played: u32,
+    pub won: u32,
+    pub drawn: u32,
+    pub lost: u32,
+    /// Top scorers first, then by appearances.
+    pub players: Vec<SeasonPlayerStats>,
+}
+
+#[derive(Object)]
+pub struct SeasonPlayerStats {
+    /// The player's account, if they have one.
+    pub user: Option<UserProfile>,
+    /// Set for a player without an account: the name they were entered under.
+    pub display_name: Option<String>,
+    pub appearances: u32,
+    /// Goals (football), points (netball) or runs (cricket).
+    pub scored: u32,
+}
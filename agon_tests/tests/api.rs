@@ -1192,6 +1192,67 @@ async fn follow_and_unfollow_team() {
     assert!(!fetched.is_followed_by_me);
 }
 
+#[tokio::test]
+async fn team_announcement_reaches_followers_feed() {
+    let (admin_config, _admin) = new_user().await;
+    let team = teams_post(
+        &admin_config,
+        models::CreateTeamInput {
+            name: "Announcers".to_string(),
+        },
+    )
+    .await
+    .expect("create team");
+
+    let (follower_config, _follower) = new_user().await;
+    teams_team_id_follow_post(&follower_config, &team.id)
+        .await
+        .expect("follow team");
+
+    // Only admins post announcements.
+    let err = teams_team_id_announcements_post(
+        &follower_config,
+        &team.id,
+        models::PostTeamAnnouncementInput {
+            body: "Not mine to say".to_string(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        openapi::apis::Error::ResponseError(openapi::apis::ResponseContent {
+            status: reqwest::StatusCode::FORBIDDEN,
+            ..
+        })
+    ));
+
+    let posted = teams_team_id_announcements_post(
+        &admin_config,
+        &team.id,
+        models::PostTeamAnnouncementInput {
+            body: "Nets at 7 on Thursday".to_string(),
+        },
+    )
+    .await
+    .expect("post announcement");
+
+    let found = eventually("announcement to fan out into the follower's feed", || {
+        let config = &follower_config;
+        let announcement_id = &posted.id;
+        async move {
+            let page = feed_get(config, None, None, None, None, None).await.ok()?;
+            page.items.into_iter().find_map(|item| match item {
+                models::FeedItem::TeamAnnouncement(a) if &a.id == announcement_id => Some(a),
+                _ => None,
+            })
+        }
+    })
+    .await;
+    assert_eq!(found.team.id, team.id);
+    assert_eq!(found.body, "Nets at 7 on Thursday");
+}
+
 // ---------------------------------------------------------------------------
 // Invitations
 // ---------------------------------------------------------------------------
@@ -1457,8 +1518,11 @@ async fn creating_a_match_fans_out_to_a_participants_feed() {
         let config = &invitee_config;
         let match_id = &created.id;
         async move {
-            let page = feed_get(config, None, None, None, None).await.ok()?;
-            page.items.into_iter().find(|item| &item.id == match_id)
+            let page = feed_get(config, None, None, None, None, None).await.ok()?;
+            page.items
+                .into_iter()
+                .filter_map(feed_match)
+                .find(|m| &m.id == match_id)
         }
     })
     .await;
@@ -1466,6 +1530,64 @@ async fn creating_a_match_fans_out_to_a_participants_feed() {
     assert_eq!(found.name, "Test Match");
 }
 
+#[tokio::test]
+async fn ranked_feed_pages_through_every_entry_once() {
+    let (owner_config, _owner) = new_user().await;
+    let (invitee_config, invitee) = new_user().await;
+
+    let mut created = Vec::new();
+    for _ in 0..3 {
+        let m = matches_post(&owner_config, create_match_input(&invitee.profile.id))
+            .await
+            .expect("create match");
+        created.push(m.id);
+    }
+    for id in &created {
+        assert_match_reaches_feed(&invitee_config, id, "the invitee").await;
+    }
+
+    // One item per page, so every page after the first reads the stored
+    // ranking rather than re-ranking.
+    let mut seen = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = feed_get(
+            &invitee_config,
+            cursor.as_deref(),
+            Some(1),
+            None,
+            None,
+            Some(models::FeedMode::Ranked),
+        )
+        .await
+        .expect("list ranked feed");
+        seen.extend(page.items.into_iter().filter_map(feed_match).map(|m| m.id));
+        match page.next_cursor {
+            Some(c) => cursor = Some(c),
+            None => break,
+        }
+    }
+    seen.sort();
+    created.sort();
+    assert_eq!(seen, created);
+
+    // A cursor from another ranking is rejected.
+    let response = feed_get(
+        &invitee_config,
+        Some("0@2000-01-01T00:00:00.000Z"),
+        None,
+        None,
+        None,
+        Some(models::FeedMode::Ranked),
+    )
+    .await;
+    assert_status_with_content(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "start again from the first page",
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Feed fan-out scenarios (multi-user end-to-end)
 //
@@ -1718,6 +1840,35 @@ async fn a_created_user_becomes_searchable() {
     assert_eq!(found.id, user.profile.id);
 }
 
+#[tokio::test]
+async fn global_search_groups_hits_by_kind() {
+    let (config, _user) = new_user().await;
+    // Content depends on what's indexed; just check the shape.
+    let results = search_get(&config, "test", Some(3))
+        .await
+        .expect("global search");
+    assert!(results.groups.len() <= 3);
+}
+
+#[tokio::test]
+async fn global_search_rejects_an_empty_query() {
+    let (config, _user) = new_user().await;
+    let response = search_get(&config, "  ", None).await;
+    assert_status_with_content(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "`q` must not be empty",
+    );
+}
+
+#[tokio::test]
+async fn a_new_user_has_no_suggestions_yet() {
+    let (config, _user) = new_user().await;
+    let suggestions = suggestions_get(&config).await.expect("suggestions");
+    assert!(suggestions.users.is_empty());
+    assert!(suggestions.teams.is_empty());
+}
+
 #[tokio::test]
 async fn following_a_user_eventually_notifies_them() {
     let (follower_config, follower) = new_user().await;
@@ -2378,10 +2529,34 @@ async fn list_matches_accepts_filters() {
         Some("2026-12-31T00:00:00Z".to_string()),
         None,
         Some(10),
+        Some(models::MatchStatus::Completed),
+        Some("some-team"),
+        Some(51.5),
+        Some(-0.1),
+        Some(5000),
     )
     .await
     .expect("list matches");
     let _ = page.items.len();
+    // The first page carries the filter-chip counts.
+    assert!(page.facets.is_some());
+}
+
+#[tokio::test]
+async fn list_matches_rejects_half_a_location() {
+    let (config, _user) = new_user().await;
+    let response = matches_get(
+        &config, None, None, None, None, None, None, None, None, None,
+        Some(51.5),
+        None,
+        None,
+    )
+    .await;
+    assert_status_with_content(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "`near_lat` and `near_lng` go together",
+    );
 }
 
 #[tokio::test]
@@ -2397,6 +2572,11 @@ async fn list_matches_rejects_inverted_date_range() {
         Some("2026-01-01T00:00:00Z".to_string()),
         None,
         None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
     .await;
     assert_status_with_content(
@@ -3071,14 +3251,27 @@ where
     panic!("timed out after {ATTEMPTS}s waiting for: {what}");
 }
 
+/// The match card in a feed item, or `None` for the other item types.
+fn feed_match(item: models::FeedItem) -> Option<Box<models::FeedMatch>> {
+    match item {
+        models::FeedItem::Match(m) => Some(m),
+        _ => None,
+    }
+}
+
 /// Whether `match_id` is currently anywhere in the viewer's paged feed.
 async fn feed_contains(config: &Configuration, match_id: &str) -> bool {
     let mut cursor: Option<String> = None;
     loop {
-        let page = feed_get(config, cursor.as_deref(), Some(50), None, None)
+        let page = feed_get(config, cursor.as_deref(), Some(50), None, None, None)
             .await
             .expect("list feed");
-        if page.items.iter().any(|item| item.id == match_id) {
+        let found = page
+            .items
+            .into_iter()
+            .filter_map(feed_match)
+            .any(|m| m.id == match_id);
+        if found {
             return true;
         }
         match page.next_cursor {
@@ -30,6 +30,29 @@ pub struct Config {
     /// not a startup failure. If set, it must parse — a broken credential is a
     /// real misconfiguration, not an intentional opt-out.
     pub fcm_service_account_json: Option<String>,
+    /// SMTP server for notification, invitation and digest emails
+    /// (`AGON_SMTP_HOST`). Optional like the FCM key: unset means "email
+    /// disabled". Locally, `docker compose up mailpit` and point this at
+    /// `localhost` with `AGON_SMTP_PORT=1025 AGON_SMTP_STARTTLS=false`.
+    pub smtp_host: Option<String>,
+    /// SMTP port (`AGON_SMTP_PORT`, default 587 — submission).
+    pub smtp_port: u16,
+    /// Whether to upgrade with STARTTLS before authenticating
+    /// (`AGON_SMTP_STARTTLS`, default true).
+    pub smtp_starttls: bool,
+    /// SMTP credentials (`AGON_SMTP_USERNAME` / `AGON_SMTP_PASSWORD`); unset
+    /// sends unauthenticated.
+    pub smtp_credentials: Option<(String, String)>,
+    /// The `From` header (`AGON_EMAIL_FROM`).
+    pub email_from: String,
+    /// Base URL of the web app that emails link into (`AGON_APP_URL`),
+    /// required when `smtp_host` is set.
+    pub app_url: String,
+    /// How long feed entries are kept past their sort time, in days
+    /// (`AGON_FEED_RETENTION_DAYS`, default
+    /// `agon_core::dao::feed::DEFAULT_FEED_RETENTION_DAYS`). Set the API's to
+    /// match: it writes participants' own entries.
+    pub feed_retention_days: u32,
     /// Max messages to pull per SQS receive (1..=10).
     pub batch_size: i32,
     /// SQS long-poll wait time in seconds (0..=20).
@@ -42,6 +65,14 @@ impl Config {
     /// Load configuration from the environment, failing if a required var is
     /// missing.
     pub fn from_env() -> WorkerResult<Self> {
+        let smtp_host = env::var("AGON_SMTP_HOST").ok();
+        // Only needed to send email, so only required when that's on.
+        let app_url = match &smtp_host {
+            Some(_) => required("AGON_APP_URL")?,
+            None => env::var("AGON_APP_URL").unwrap_or_default(),
+        }
+        .trim_end_matches('/')
+        .to_string();
         Ok(Self {
             table_name: required("AGON_TABLE_NAME")?,
             events_queue_url: required("AGON_EVENTS_QUEUE_URL")?,
@@ -52,6 +83,23 @@ impl Config {
             meili_url: required("MEILI_URL")?,
             meili_key: required("MEILI_MASTER_KEY")?,
             fcm_service_account_json: env::var("AGON_FCM_SERVICE_ACCOUNT_JSON").ok(),
+            smtp_host,
+            smtp_port: optional_parsed("AGON_SMTP_PORT", 587)?,
+            smtp_starttls: optional_parsed("AGON_SMTP_STARTTLS", true)?,
+            smtp_credentials: match (
+                env::var("AGON_SMTP_USERNAME").ok(),
+                env::var("AGON_SMTP_PASSWORD").ok(),
+            ) {
+                (Some(user), Some(password)) => Some((user, password)),
+                _ => None,
+            },
+            email_from: env::var("AGON_EMAIL_FROM")
+                .unwrap_or_else(|_| "Agon <no-reply@agon.app>".to_string()),
+            app_url,
+            feed_retention_days: optional_parsed(
+                "AGON_FEED_RETENTION_DAYS",
+                agon_core::dao::feed::DEFAULT_FEED_RETENTION_DAYS,
+            )?,
             batch_size: optional_parsed("AGON_WORKER_BATCH_SIZE", 10)?,
             wait_time_seconds: optional_parsed("AGON_WORKER_WAIT_SECONDS", 20)?,
             visibility_timeout_seconds: optional_parsed("AGON_WORKER_VISIBILITY_SECONDS", 60)?,
@@ -23,6 +23,7 @@ use crate::config::Config;
 use crate::error::{WorkerError, WorkerResult};
 use crate::event::{ChangeEvent, Envelope};
 use crate::handlers;
+use crate::handlers::email::Mailer;
 use agon_core::push::PushClient;
 use agon_core::search::SearchClient;
 
@@ -35,6 +36,8 @@ pub struct Consumer {
     /// `None` when push isn't configured (e.g. local dev) — handled the same
     /// way as the Temporal client's absence in tests: handlers no-op.
     push: Option<PushClient>,
+    /// `None` when SMTP isn't configured — likewise a no-op.
+    mailer: Option<Mailer>,
     config: Arc<Config>,
     /// Client for starting multi-step workflows. Attached in `main` after the
     /// Temporal connection succeeds; when absent (e.g. in unit tests), multi-step
@@ -48,6 +51,7 @@ impl Consumer {
         dao: Dao,
         search: SearchClient,
         push: Option<PushClient>,
+        mailer: Option<Mailer>,
         config: Config,
     ) -> Self {
         Self {
@@ -55,6 +59,7 @@ impl Consumer {
             dao,
             search,
             push,
+            mailer,
             config: Arc::new(config),
             temporal: None,
         }
@@ -158,7 +163,15 @@ impl Consumer {
         let event = ChangeEvent::from_envelope(&envelope)?;
 
         let now = Utc::now().to_rfc3339();
-        handlers::route(&self.dao, &self.search, self.push.as_ref(), &event, &now).await?;
+        handlers::route(
+            &self.dao,
+            &self.search,
+            self.push.as_ref(),
+            self.mailer.as_ref(),
+            &event,
+            &now,
+        )
+        .await?;
 
         // Multi-step work: start the relevant Temporal workflow (idempotent via
         // deterministic ids). A start failure is transient, so the message is left
@@ -181,10 +194,43 @@ impl Consumer {
                 .start_fanout(&match_id)
                 .await
                 .map_err(|e| WorkerError::Sqs(format!("start fanout: {e}")))?,
+            Some(WorkflowStart::Milestones {
+                user_id,
+                milestones,
+            }) => {
+                for milestone in milestones {
+                    let input = crate::temporal::workflows::FanOutMilestoneInput {
+                        user_id: user_id.clone(),
+                        milestone,
+                    };
+                    temporal
+                        .start_milestone(input)
+                        .await
+                        .map_err(|e| WorkerError::Sqs(format!("start milestone: {e}")))?;
+                }
+            }
+            Some(WorkflowStart::TeamPost(input)) => temporal
+                .start_team_post(input)
+                .await
+                .map_err(|e| WorkerError::Sqs(format!("start team post: {e}")))?,
+            Some(WorkflowStart::FollowBackfill(edge)) => temporal
+                .start_follow_backfill(edge)
+                .await
+                .map_err(|e| WorkerError::Sqs(format!("start follow backfill: {e}")))?,
+            Some(WorkflowStart::UnfollowPrune(edge)) => {
+                temporal
+                    .start_unfollow_prune(edge)
+                    .await
+                    .map_err(|e| WorkerError::Sqs(format!("start unfollow prune: {e}")))?
+            }
             Some(WorkflowStart::Accept(input)) => temporal
                 .start_accept(input)
                 .await
                 .map_err(|e| WorkerError::Sqs(format!("start accept saga: {e}")))?,
+            Some(WorkflowStart::ReleasePushes(input)) => temporal
+                .start_release_pushes(input)
+                .await
+                .map_err(|e| WorkerError::Sqs(format!("start release pushes: {e}")))?,
             None => {}
         }
         Ok(())
@@ -213,31 +259,143 @@ impl Consumer {
 enum WorkflowStart {
     /// (Re)fan a match into feeds.
     FanOut { match_id: String },
+    /// Record and fan out the milestones a user's stats just crossed, one
+    /// workflow each.
+    Milestones {
+        user_id: String,
+        milestones: Vec<agon_core::milestones::Milestone>,
+    },
+    /// Fan a team announcement or new member into feeds.
+    TeamPost(crate::temporal::workflows::FanOutTeamPostInput),
+    /// Add a new follow's recent matches to the follower's feed.
+    FollowBackfill(crate::temporal::activities::FollowEdge),
+    /// Take an ended follow out of the follower's feed.
+    UnfollowPrune(crate::temporal::activities::FollowEdge),
     /// Run the invitation-acceptance saga.
     Accept(crate::temporal::workflows::AcceptInvitationInput),
+    /// Send a user's deferred pushes when their quiet hours end.
+    ReleasePushes(crate::temporal::activities::ReleasePushes),
 }
 
 /// Decide which workflow (if any) a change event should start:
 /// - a match meta write (not a remove) → fan-out;
+/// - a profile write whose stats crossed a milestone → a milestone fan-out
+///   per milestone;
+/// - a new team announcement, or a membership that just became active (the
+///   member accepted, or was added outright) → a team-post fan-out;
+/// - a new follow edge (of a user, team or club) → a feed backfill, and a
+///   removed one → a feed prune;
 /// - an invitation that just transitioned *into* "accepted" → the accept saga.
+/// - a push deferred by quiet hours → the run that releases it.
 ///
 /// For the invitation case both images deserialize straight into
 /// `InvitationRecord`, so we detect the transition and read the full accepted
 /// record with no extra DynamoDB read. Firing on the *transition* means we start
 /// once, not on every subsequent modify of an already-accepted invitation.
 fn workflow_for(event: &ChangeEvent) -> Option<WorkflowStart> {
+    use crate::event::ChangeKind;
+    use crate::temporal::activities::FollowEdge;
+    use crate::temporal::workflows::FanOutTeamPostInput;
+    use agon_core::dao::feed::FeedRef;
     use agon_core::dao::keys::{Pk, Sk};
-    use agon_core::dao::records::{InvitationContextRecord, InvitationRecord};
+    use agon_core::dao::records::{
+        AudienceReason, FeedRefType, InvitationContextRecord, InvitationRecord,
+        TeamAnnouncementRecord, TeamMemberRecord, UserRecord,
+    };
+
+    // A follow edge came or went → backfill or prune the follower's feed.
+    // The only case acting on a remove.
+    if let Sk::Follower(follower_id) = &event.sk {
+        let followed = match &event.pk {
+            Pk::User(id) => AudienceReason::FollowsUser(id.clone()),
+            Pk::Team(id) => AudienceReason::FollowsTeam(id.clone()),
+            Pk::Club(id) => AudienceReason::FollowsClub(id.clone()),
+            _ => return None,
+        };
+        let edge = FollowEdge {
+            follower_id: follower_id.clone(),
+            followed,
+        };
+        return match event.kind {
+            ChangeKind::Insert => Some(WorkflowStart::FollowBackfill(edge)),
+            ChangeKind::Remove => Some(WorkflowStart::UnfollowPrune(edge)),
+            ChangeKind::Modify => None,
+        };
+    }
 
     if event.kind.is_remove() {
         return None;
     }
 
     match (&event.pk, &event.sk) {
+        // A push was held for quiet hours → wait them out, then send it.
+        (Pk::User(user_id), Sk::DeferredPush { release_at, .. }) => Some(
+            WorkflowStart::ReleasePushes(crate::temporal::activities::ReleasePushes {
+                user_id: user_id.clone(),
+                release_at: release_at.clone(),
+            }),
+        ),
         // A match was created or updated → (re)fan it into feeds.
         (Pk::Match(match_id), Sk::Meta) => Some(WorkflowStart::FanOut {
             match_id: match_id.clone(),
         }),
+        // The stats reconciler moved a user's stats → fan out any milestone
+        // they crossed. A new profile has no stats to cross from.
+        (Pk::User(user_id), Sk::Profile) => {
+            let old = event.old_record::<UserRecord>()?;
+            let new = event.new_record::<UserRecord>()?;
+            let milestones = agon_core::milestones::crossed(&old.stats, &new.stats);
+            (!milestones.is_empty()).then(|| WorkflowStart::Milestones {
+                user_id: user_id.clone(),
+                milestones,
+            })
+        }
+        // An admin posted to the team → fan it out. Announcements aren't
+        // edited, so only the insert counts.
+        (Pk::Team(team_id), Sk::Announcement(id)) if event.kind == ChangeKind::Insert => {
+            let announcement = event.new_record::<TeamAnnouncementRecord>()?;
+            Some(WorkflowStart::TeamPost(FanOutTeamPostInput {
+                team_id: team_id.clone(),
+                feed_ref: FeedRef {
+                    ref_type: FeedRefType::TeamAnnouncement,
+                    ref_id: id.clone(),
+                    subject_id: Some(team_id.clone()),
+                    at: announcement.created_at,
+                },
+            }))
+        }
+        // A membership became active → "X joined the team", at when they
+        // accepted (or were added). The creator's own membership, inserted
+        // with the team as its admin, isn't news.
+        (Pk::Team(team_id), Sk::Member(membership_id)) => {
+            let new = event.new_record::<TeamMemberRecord>()?;
+            let old = event.old_record::<TeamMemberRecord>();
+            let is_active = |m: &TeamMemberRecord| {
+                m.user_id.is_some()
+                    && m.left_at.is_none()
+                    && m.invitation.as_ref().is_none_or(|i| i.status == "accepted")
+            };
+            let is_creator = old.is_none()
+                && new.invitation.is_none()
+                && new.role == agon_core::dao::team::ROLE_ADMIN;
+            if !is_active(&new) || old.as_ref().is_some_and(is_active) || is_creator {
+                return None;
+            }
+            let at = new
+                .invitation
+                .as_ref()
+                .and_then(|i| i.responded_at.clone())
+                .unwrap_or(new.created_at);
+            Some(WorkflowStart::TeamPost(FanOutTeamPostInput {
+                team_id: team_id.clone(),
+                feed_ref: FeedRef {
+                    ref_type: FeedRefType::MemberJoined,
+                    ref_id: membership_id.clone(),
+                    subject_id: Some(team_id.clone()),
+                    at,
+                },
+            }))
+        }
         // An invitation meta write → start the accept saga iff this is a
         // pending → accepted transition.
         (Pk::Invitation(_), Sk::Meta) => {
@@ -277,12 +435,15 @@ fn workflow_for(event: &ChangeEvent) -> Option<WorkflowStart> {
 mod tests {
     use super::*;
     use crate::event::{ChangeKind, Image};
+    use agon_core::dao::feed::FeedRef;
     use agon_core::dao::records::{
-        InvitationContextRecord, InvitationKindRecord, InvitationRecord,
+        EmbeddedInvitationRecord, FeedRefType, InvitationContextRecord, InvitationKindRecord,
+        InvitationRecord, MilestoneStat, TeamAnnouncementRecord, TeamMemberRecord, UserRecord,
+        UserSportStatsRecord,
     };
+    use agon_core::milestones::Milestone;
 
-    /// Build a `ChangeEvent` from typed keys and optional old/new records, going
-    /// through the same attribute-value (de)serialization the stream uses.
+    /// Build a `ChangeEvent` from typed keys and optional old/new invitations.
     fn event(
         kind: ChangeKind,
         pk: &str,
@@ -290,7 +451,19 @@ mod tests {
         old: Option<&InvitationRecord>,
         new: Option<&InvitationRecord>,
     ) -> ChangeEvent {
-        let to_image = |r: &InvitationRecord| -> Image { serde_dynamo::to_item(r).unwrap() };
+        record_event(kind, pk, sk, old, new)
+    }
+
+    /// Build a `ChangeEvent` from typed keys and optional old/new records, going
+    /// through the same attribute-value (de)serialization the stream uses.
+    fn record_event<T: serde::Serialize>(
+        kind: ChangeKind,
+        pk: &str,
+        sk: &str,
+        old: Option<&T>,
+        new: Option<&T>,
+    ) -> ChangeEvent {
+        let to_image = |r: &T| -> Image { serde_dynamo::to_item(r).unwrap() };
         ChangeEvent::from_envelope(&Envelope {
             event: kind,
             pk: pk.into(),
@@ -308,12 +481,14 @@ mod tests {
             invited_by_user_id: "u_host".into(),
             invited_user_id: Some("u_guest".into()),
             invite_token: None,
+            invitee_email: None,
             kind: InvitationKindRecord::User {
                 invited_user_id: "u_guest".into(),
             },
             context,
             invited_at: "2026-07-01T10:00:00Z".into(),
             responded_at: Some("2026-07-02T09:00:00Z".into()),
+            link: None,
         }
     }
 
@@ -355,6 +530,26 @@ mod tests {
         assert_eq!(workflow_for(&ev), None);
     }
 
+    #[test]
+    fn deferred_push_starts_release_for_its_night() {
+        let ev = event(
+            ChangeKind::Insert,
+            "USER#u1",
+            "DEFERPUSH#2026-07-02T06:00:00Z#n1",
+            None,
+            None,
+        );
+        assert_eq!(
+            workflow_for(&ev),
+            Some(WorkflowStart::ReleasePushes(
+                crate::temporal::activities::ReleasePushes {
+                    user_id: "u1".into(),
+                    release_at: "2026-07-02T06:00:00Z".into(),
+                }
+            ))
+        );
+    }
+
     #[test]
     fn accept_transition_starts_accept_saga_with_match_id() {
         let old = invitation("pending", match_ctx());
@@ -440,4 +635,196 @@ mod tests {
         );
         assert_eq!(workflow_for(&ev), None);
     }
+
+    fn user(matches_played: u64, wins: u64) -> UserRecord {
+        UserRecord {
+            id: "u1".into(),
+            email: "u1@example.com".into(),
+            name: "Sam".into(),
+            profile_image_url: None,
+            follower_count: 0,
+            following_count: 0,
+            unread_count: 0,
+            stats: [(
+                "tennis".to_string(),
+                UserSportStatsRecord {
+                    matches_played,
+                    wins,
+                },
+            )]
+            .into(),
+            visibility: Default::default(),
+            is_moderator: false,
+            suspended_at: None,
+            hidden_at: None,
+            profile_image_hidden_at: None,
+            created_at: "2026-01-01T00:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn stats_crossing_a_threshold_starts_milestone_fanout() {
+        let ev = record_event(
+            ChangeKind::Modify,
+            "USER#u1",
+            "#PROFILE",
+            Some(&user(9, 3)),
+            Some(&user(10, 3)),
+        );
+        assert_eq!(
+            workflow_for(&ev),
+            Some(WorkflowStart::Milestones {
+                user_id: "u1".into(),
+                milestones: vec![Milestone {
+                    sport: "tennis".into(),
+                    stat: MilestoneStat::MatchesPlayed,
+                    count: 10,
+                }],
+            })
+        );
+
+        // Any other profile write crosses nothing.
+        let ev = record_event(
+            ChangeKind::Modify,
+            "USER#u1",
+            "#PROFILE",
+            Some(&user(10, 3)),
+            Some(&user(10, 3)),
+        );
+        assert_eq!(workflow_for(&ev), None);
+    }
+
+    #[test]
+    fn new_announcement_starts_team_post() {
+        let announcement = TeamAnnouncementRecord {
+            id: "a1".into(),
+            team_id: "t1".into(),
+            author_id: "u1".into(),
+            body: "Training moved to 7pm".into(),
+            created_at: "2026-07-01T10:00:00Z".into(),
+        };
+        let ev = record_event(
+            ChangeKind::Insert,
+            "TEAM#t1",
+            "ANNOUNCE#a1",
+            None,
+            Some(&announcement),
+        );
+        assert_eq!(
+            workflow_for(&ev),
+            Some(WorkflowStart::TeamPost(
+                crate::temporal::workflows::FanOutTeamPostInput {
+                    team_id: "t1".into(),
+                    feed_ref: FeedRef {
+                        ref_type: FeedRefType::TeamAnnouncement,
+                        ref_id: "a1".into(),
+                        subject_id: Some("t1".into()),
+                        at: "2026-07-01T10:00:00Z".into(),
+                    },
+                }
+            ))
+        );
+    }
+
+    fn member(role: &str, invitation_status: Option<&str>) -> TeamMemberRecord {
+        TeamMemberRecord {
+            team_id: "t1".into(),
+            membership_id: "mem1".into(),
+            user_id: Some("u_guest".into()),
+            display_name: None,
+            role: role.into(),
+            invitation: invitation_status.map(|status| EmbeddedInvitationRecord {
+                id: "inv1".into(),
+                status: status.into(),
+                invited_by_user_id: "u_host".into(),
+                invited_at: "2026-07-01T10:00:00Z".into(),
+                responded_at: (status == "accepted").then(|| "2026-07-02T09:00:00Z".into()),
+                kind: InvitationKindRecord::User {
+                    invited_user_id: "u_guest".into(),
+                },
+            }),
+            created_at: "2026-07-01T10:00:00Z".into(),
+            left_at: None,
+            previous_roles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn accepting_a_team_invite_starts_member_joined_post() {
+        let ev = record_event(
+            ChangeKind::Modify,
+            "TEAM#t1",
+            "MEMBER#mem1",
+            Some(&member("member", Some("pending"))),
+            Some(&member("member", Some("accepted"))),
+        );
+        match workflow_for(&ev) {
+            Some(WorkflowStart::TeamPost(input)) => {
+                assert_eq!(input.feed_ref.ref_type, FeedRefType::MemberJoined);
+                assert_eq!(input.feed_ref.ref_id, "mem1");
+                assert_eq!(input.feed_ref.at, "2026-07-02T09:00:00Z");
+            }
+            other => panic!("expected a team post, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pending_invites_the_creator_and_later_edits_start_nothing() {
+        let pending = member("member", Some("pending"));
+        let ev = record_event(
+            ChangeKind::Insert,
+            "TEAM#t1",
+            "MEMBER#mem1",
+            None,
+            Some(&pending),
+        );
+        assert_eq!(workflow_for(&ev), None);
+
+        let creator = member("admin", None);
+        let ev = record_event(
+            ChangeKind::Insert,
+            "TEAM#t1",
+            "MEMBER#mem1",
+            None,
+            Some(&creator),
+        );
+        assert_eq!(workflow_for(&ev), None);
+
+        let accepted = member("member", Some("accepted"));
+        let ev = record_event(
+            ChangeKind::Modify,
+            "TEAM#t1",
+            "MEMBER#mem1",
+            Some(&accepted),
+            Some(&member("admin", Some("accepted"))),
+        );
+        assert_eq!(workflow_for(&ev), None);
+    }
+
+    #[test]
+    fn follow_edges_start_backfill_and_unfollows_start_prune() {
+        use crate::temporal::activities::FollowEdge;
+        use agon_core::dao::records::AudienceReason;
+
+        let ev = event(ChangeKind::Insert, "USER#u2", "FOLLOWER#u1", None, None);
+        assert_eq!(
+            workflow_for(&ev),
+            Some(WorkflowStart::FollowBackfill(FollowEdge {
+                follower_id: "u1".into(),
+                followed: AudienceReason::FollowsUser("u2".into()),
+            }))
+        );
+
+        let ev = event(ChangeKind::Remove, "CLUB#c1", "FOLLOWER#u1", None, None);
+        assert_eq!(
+            workflow_for(&ev),
+            Some(WorkflowStart::UnfollowPrune(FollowEdge {
+                follower_id: "u1".into(),
+                followed: AudienceReason::FollowsClub("c1".into()),
+            }))
+        );
+
+        let ev = event(ChangeKind::Modify, "TEAM#t1", "FOLLOWER#u1", None, None);
+        assert_eq!(workflow_for(&ev), None);
+    }
 }
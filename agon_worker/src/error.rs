@@ -11,7 +11,7 @@
 
 use agon_core::dao::error::DaoError;
 use agon_core::dao::keys::KeyError;
-use agon_core::error::{PushError, SearchError};
+use agon_core::error::{EmailError, PushError, SearchError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -47,6 +47,10 @@ pub enum WorkerError {
     #[error(transparent)]
     Push(#[from] PushError),
 
+    /// An SMTP send failed. Usually transient → retry.
+    #[error(transparent)]
+    Email(#[from] EmailError),
+
     /// An SQS operation failed.
     #[error("sqs error: {0}")]
     Sqs(String),
@@ -28,6 +28,7 @@
 //! indexing always reflects the latest committed state).
 
 use agon_core::dao::keys::{KeyError, Pk, Sk};
+use agon_core::dao::memory::{StreamEventKind, StreamRecord};
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
 
@@ -72,6 +73,25 @@ pub struct Envelope {
     pub new_image: Option<Image>,
 }
 
+/// A change off the in-memory table's stream (`agon_core::dao::memory`), as
+/// the envelope the Pipe would have delivered for it — so tests can drive the
+/// handlers with the changes a DAO call really makes.
+impl From<StreamRecord> for Envelope {
+    fn from(record: StreamRecord) -> Self {
+        Self {
+            event: match record.kind {
+                StreamEventKind::Insert => ChangeKind::Insert,
+                StreamEventKind::Modify => ChangeKind::Modify,
+                StreamEventKind::Remove => ChangeKind::Remove,
+            },
+            pk: record.pk,
+            sk: record.sk,
+            old_image: record.old_image.map(Image::from),
+            new_image: record.new_image.map(Image::from),
+        }
+    }
+}
+
 /// Deserialize an optional DynamoDB image that may arrive as a map, `null`, or
 /// an empty string (the EventBridge Pipe's rendering of an absent path).
 fn deserialize_optional_image<'de, D>(deserializer: D) -> Result<Option<Image>, D::Error>
@@ -153,6 +173,7 @@ mod tests {
             invited_by_user_id: "u_host".into(),
             invited_user_id: Some("u_guest".into()),
             invite_token: None,
+            invitee_email: None,
             kind: InvitationKindRecord::User {
                 invited_user_id: "u_guest".into(),
             },
@@ -162,6 +183,7 @@ mod tests {
             },
             invited_at: "2026-07-01T10:00:00Z".into(),
             responded_at: Some("2026-07-02T09:00:00Z".into()),
+            link: None,
         }
     }
 
@@ -0,0 +1,112 @@
+//! Inline handler: email the events that should reach people outside the app.
+//!
+//! Two sources:
+//! - a newly-created `NotificationRecord` (INSERT only — a grouped
+//!   notification gaining actors isn't worth another email), sent when the
+//!   recipient's preferences allow email for its kind. Like push, this reacts
+//!   to the notification's own stream event rather than re-deriving
+//!   recipients, so mutes and in-app toggles are already applied.
+//! - a newly-created token invitation with an `invitee_email` — someone with
+//!   no account, who gets the link to accept by email since there's no
+//!   notification to send them.
+//!
+//! At-least-once like everything else here: a redelivered event can send the
+//! same email twice. A recipient the server permanently refuses is logged and
+//! dropped, not retried.
+
+use agon_core::dao::Dao;
+use agon_core::dao::keys::{Pk, Sk};
+use agon_core::dao::records::{InvitationRecord, NotificationRecord};
+use agon_core::email::templates::{EmailContent, notification_email, token_invitation_email};
+use agon_core::email::{EmailClient, EmailOutcome};
+
+use crate::error::WorkerResult;
+use crate::event::{ChangeEvent, ChangeKind};
+
+/// The SMTP client plus the web app's base URL the messages link into.
+#[derive(Clone)]
+pub struct Mailer {
+    pub client: EmailClient,
+    /// No trailing slash.
+    pub app_url: String,
+}
+
+impl Mailer {
+    /// The link an external invitee follows to accept.
+    pub fn accept_url(&self, invite_token: &str) -> String {
+        format!("{}/invite/{invite_token}", self.app_url)
+    }
+
+    /// Send `content` to `to`, treating a refused address as done.
+    pub async fn send(&self, to: &str, content: EmailContent) -> WorkerResult<()> {
+        match self.client.send(&content.to(to)).await? {
+            EmailOutcome::Sent => {}
+            EmailOutcome::Rejected => {
+                tracing::info!("email recipient refused; not retrying");
+            }
+        }
+        Ok(())
+    }
+}
+
+pub async fn handle(dao: &Dao, mailer: Option<&Mailer>, ev: &ChangeEvent) -> WorkerResult<()> {
+    // Not configured (e.g. local dev without an SMTP server) — nothing to do.
+    let Some(mailer) = mailer else {
+        return Ok(());
+    };
+    if ev.kind != ChangeKind::Insert {
+        return Ok(());
+    }
+    match (&ev.pk, &ev.sk) {
+        (Pk::User(user_id), Sk::Notification(_)) => {
+            let Some(notif) = ev.new_record::<NotificationRecord>() else {
+                return Ok(());
+            };
+            notification(dao, mailer, user_id, &notif).await
+        }
+        (Pk::Invitation(_), Sk::Meta) => {
+            let Some(inv) = ev.new_record::<InvitationRecord>() else {
+                return Ok(());
+            };
+            token_invitation(dao, mailer, &inv).await
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn notification(
+    dao: &Dao,
+    mailer: &Mailer,
+    user_id: &str,
+    notif: &NotificationRecord,
+) -> WorkerResult<()> {
+    let prefs = dao.get_notification_preferences(user_id).await?;
+    if !prefs.allows_email(&notif.kind) {
+        return Ok(());
+    }
+    let actor_id = notif.kind.actor_user_id().to_string();
+    let users = dao
+        .batch_get_users(&[user_id.to_string(), actor_id.clone()])
+        .await?;
+    let Some(recipient) = users.get(user_id).filter(|u| !u.email.is_empty()) else {
+        return Ok(());
+    };
+    let actor_name = users
+        .get(&actor_id)
+        .map(|u| u.name.as_str())
+        .unwrap_or("Someone");
+    let content = notification_email(&notif.kind, actor_name, &mailer.app_url);
+    mailer.send(&recipient.email, content).await
+}
+
+async fn token_invitation(dao: &Dao, mailer: &Mailer, inv: &InvitationRecord) -> WorkerResult<()> {
+    // Reusable team invite links are shared by hand, never emailed.
+    let (Some(email), Some(token), None) = (&inv.invitee_email, &inv.invite_token, &inv.link)
+    else {
+        return Ok(());
+    };
+    let inviter = dao.get_user(&inv.invited_by_user_id).await?;
+    let inviter_name = inviter.as_ref().map_or("Someone", |u| u.name.as_str());
+    let content = token_invitation_email(inviter_name, &inv.context, &mailer.accept_url(token));
+    mailer.send(email, content).await
+}
@@ -8,31 +8,52 @@
 //! The search documents are intentionally minimal — just what the discovery
 //! endpoints need to match on and render a row. Full hydration happens from
 //! DynamoDB when a result is opened.
+//!
+//! Every document carries the record's `visibility` and `visible_to` — the
+//! user ids that may always find it (see
+//! `agon_core::search::Filter::visible_to`). Those come from the team's
+//! members and the match's players, so a member or player write re-indexes
+//! its team or match too.
+//!
+//! While an index is being rebuilt (see the `ReindexSearch` workflow) every
+//! write here goes to its shadow as well, so nothing written mid-rebuild is
+//! lost when the shadow is swapped in. That costs one marker read per write.
 
 use agon_core::dao::Dao;
 use agon_core::dao::keys::{Pk, Sk};
 use agon_core::dao::match_ops::MatchAggregate;
-use agon_core::dao::records::{TeamRecord, UserRecord};
+use agon_core::dao::records::UserRecord;
+use agon_core::dao::team::TeamAggregate;
+use futures::StreamExt;
 use serde::Serialize;
 
 use crate::error::WorkerResult;
 use crate::event::ChangeEvent;
 use agon_core::search::{Index, SearchClient};
 
+/// How many aggregates [`team_docs`] / [`match_docs`] read at once.
+const DOC_READ_CONCURRENCY: usize = 8;
+
 /// A user search document (index `users`).
 #[derive(Debug, Serialize)]
-struct UserDoc {
+pub struct UserDoc {
     id: String,
     name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     profile_image_url: Option<String>,
+    visibility: &'static str,
+    /// Just the user themselves.
+    visible_to: Vec<String>,
 }
 
 /// A team search document (index `teams`).
 #[derive(Debug, Serialize)]
-struct TeamDoc {
+pub struct TeamDoc {
     id: String,
     name: String,
+    visibility: &'static str,
+    /// The team's current members with a linked account.
+    visible_to: Vec<String>,
 }
 
 /// A match search document (index `matches`). Carries the fields the discovery
@@ -41,6 +62,7 @@ struct TeamDoc {
 pub struct MatchDoc {
     id: String,
     name: String,
+    description: String,
     sport: String,
     status: String,
     /// ISO-8601 start time, for display / hydration.
@@ -53,6 +75,22 @@ pub struct MatchDoc {
     /// the stable player ids — so the `participant` discovery filter matches
     /// either. Deduplicated.
     participant_ids: Vec<String>,
+    /// Players' display names, for free-text search. Deduplicated.
+    participant_names: Vec<String>,
+    /// The teams playing, for the `team` discovery filter and facet.
+    team_ids: Vec<String>,
+    /// Sides' display names (often the team name), for free-text search.
+    side_names: Vec<String>,
+    /// The venue's name, for free-text search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    venue: Option<String>,
+    /// The league, for free-text search and the `league` filter and facet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    league: Option<String>,
+    /// Where the match is played, in Meilisearch's reserved geo field, for
+    /// distance filtering. Absent when the match has no location.
+    #[serde(rename = "_geo", skip_serializing_if = "Option::is_none")]
+    geo: Option<GeoPoint>,
     /// Which of `participant_ids` won / lost / drew, split into three
     /// buckets rather than a single per-participant map — Meilisearch
     /// documents are per-match, not per-viewer, so there's no single
@@ -66,6 +104,16 @@ pub struct MatchDoc {
     /// Populated only when the confirmed score has no single winner (a tie) —
     /// every participant with an assigned side lands here.
     drawing_participant_ids: Vec<String>,
+    visibility: &'static str,
+    /// The match's creator and every player with a linked account.
+    visible_to: Vec<String>,
+}
+
+/// Meilisearch's `_geo` shape.
+#[derive(Debug, Serialize)]
+pub struct GeoPoint {
+    lat: f64,
+    lng: f64,
 }
 
 /// Handle an index-relevant change event. Returns `Ok(())` for events that are
@@ -76,6 +124,10 @@ pub async fn handle(dao: &Dao, search: &SearchClient, ev: &ChangeEvent) -> Worke
         (Pk::User(uid), Sk::Profile) => index_user(dao, search, uid, ev.kind.is_remove()).await,
         (Pk::Team(tid), Sk::Meta) => index_team(dao, search, tid, ev.kind.is_remove()).await,
         (Pk::Match(mid), Sk::Meta) => index_match(dao, search, mid, ev.kind.is_remove()).await,
+        // Membership and roster changes alter `visible_to`; the parent item
+        // itself is still there, so never a delete.
+        (Pk::Team(tid), Sk::Member(_)) => index_team(dao, search, tid, false).await,
+        (Pk::Match(mid), Sk::Player(_)) => index_match(dao, search, mid, false).await,
         // Not an indexable item — nothing to do.
         _ => Ok(()),
     }
@@ -88,15 +140,13 @@ async fn index_user(
     removed: bool,
 ) -> WorkerResult<()> {
     if removed {
-        search.delete(Index::Users, user_id).await?;
-        return Ok(());
+        return remove_doc(dao, search, Index::Users, user_id).await;
     }
     match dao.get_user(user_id).await? {
-        Some(u) => search.upsert(Index::Users, &user_doc(&u)).await?,
+        Some(u) => put_doc(dao, search, Index::Users, &user_doc(&u)).await,
         // Item gone between the stream event and our read → treat as delete.
-        None => search.delete(Index::Users, user_id).await?,
+        None => remove_doc(dao, search, Index::Users, user_id).await,
     }
-    Ok(())
 }
 
 async fn index_team(
@@ -106,14 +156,15 @@ async fn index_team(
     removed: bool,
 ) -> WorkerResult<()> {
     if removed {
-        search.delete(Index::Teams, team_id).await?;
-        return Ok(());
+        return remove_doc(dao, search, Index::Teams, team_id).await;
     }
-    match dao.get_team_meta(team_id).await? {
-        Some(t) => search.upsert(Index::Teams, &team_doc(&t)).await?,
-        None => search.delete(Index::Teams, team_id).await?,
+    match dao.get_team(team_id).await? {
+        // Archived teams keep their meta but drop out of search.
+        Some(agg) if agg.team.archived_at.is_none() => {
+            put_doc(dao, search, Index::Teams, &team_doc(&agg)).await
+        }
+        _ => remove_doc(dao, search, Index::Teams, team_id).await,
     }
-    Ok(())
 }
 
 async fn index_match(
@@ -123,28 +174,104 @@ async fn index_match(
     removed: bool,
 ) -> WorkerResult<()> {
     if removed {
-        search.delete(Index::Matches, match_id).await?;
-        return Ok(());
+        return remove_doc(dao, search, Index::Matches, match_id).await;
     }
     match dao.get_match(match_id).await? {
-        Some(agg) => search.upsert(Index::Matches, &match_doc(&agg)).await?,
-        None => search.delete(Index::Matches, match_id).await?,
+        Some(agg) => put_doc(dao, search, Index::Matches, &match_doc(&agg)).await,
+        None => remove_doc(dao, search, Index::Matches, match_id).await,
+    }
+}
+
+/// Upsert a document into `index`, and into its shadow while it's being
+/// rebuilt.
+pub async fn put_doc<T: Serialize + Send + Sync>(
+    dao: &Dao,
+    search: &SearchClient,
+    index: Index,
+    doc: &T,
+) -> WorkerResult<()> {
+    search.upsert(index, doc).await?;
+    if dao.get_search_reindex(index.name()).await?.is_some() {
+        search.upsert_shadow(index, doc).await?;
     }
     Ok(())
 }
 
+/// Delete a document from `index`, and from its shadow while it's being
+/// rebuilt.
+async fn remove_doc(dao: &Dao, search: &SearchClient, index: Index, id: &str) -> WorkerResult<()> {
+    search.delete(index, id).await?;
+    if dao.get_search_reindex(index.name()).await?.is_some() {
+        search.delete_shadow(index, id).await?;
+    }
+    Ok(())
+}
+
+/// The documents for a page of user ids, skipping any since deleted.
+pub async fn user_docs(dao: &Dao, user_ids: &[String]) -> WorkerResult<Vec<UserDoc>> {
+    let users = dao.batch_get_users(user_ids).await?;
+    Ok(user_ids
+        .iter()
+        .filter_map(|id| users.get(id))
+        .map(user_doc)
+        .collect())
+}
+
+/// The documents for a page of team ids, skipping archived or deleted teams.
+pub async fn team_docs(dao: &Dao, team_ids: &[String]) -> WorkerResult<Vec<TeamDoc>> {
+    let teams: Vec<_> = futures::stream::iter(team_ids)
+        .map(|id| dao.get_team(id))
+        .buffered(DOC_READ_CONCURRENCY)
+        .collect()
+        .await;
+    let mut docs = Vec::with_capacity(teams.len());
+    for team in teams {
+        if let Some(agg) = team?
+            && agg.team.archived_at.is_none()
+        {
+            docs.push(team_doc(&agg));
+        }
+    }
+    Ok(docs)
+}
+
+/// The documents for a page of match ids, skipping any since deleted.
+pub async fn match_docs(dao: &Dao, match_ids: &[String]) -> WorkerResult<Vec<MatchDoc>> {
+    let matches: Vec<_> = futures::stream::iter(match_ids)
+        .map(|id| dao.get_match(id))
+        .buffered(DOC_READ_CONCURRENCY)
+        .collect()
+        .await;
+    let mut docs = Vec::with_capacity(matches.len());
+    for agg in matches {
+        if let Some(agg) = agg? {
+            docs.push(match_doc(&agg));
+        }
+    }
+    Ok(docs)
+}
+
 fn user_doc(u: &UserRecord) -> UserDoc {
     UserDoc {
         id: u.id.clone(),
         name: u.name.clone(),
         profile_image_url: u.profile_image_url.clone(),
+        visibility: u.visibility.as_str(),
+        visible_to: vec![u.id.clone()],
     }
 }
 
-fn team_doc(t: &TeamRecord) -> TeamDoc {
+fn team_doc(agg: &TeamAggregate) -> TeamDoc {
+    let visible_to: std::collections::BTreeSet<String> = agg
+        .members
+        .iter()
+        .filter_map(|m| m.user_id.clone())
+        .collect();
     TeamDoc {
-        id: t.id.clone(),
-        name: t.name.clone(),
+        id: agg.team.id.clone(),
+        name: agg.team.name.clone(),
+        visibility: agg.team.visibility.as_str(),
+        visible_to: visible_to.into_iter().collect(),
     }
 }
 
@@ -159,9 +286,15 @@ fn match_doc(agg: &MatchAggregate) -> MatchDoc {
     // Both linked user ids and stable player ids identify a participant, so the
     // discovery `participant` filter matches whichever the caller supplies.
     let mut participant_ids = std::collections::BTreeSet::new();
+    let mut participant_names = std::collections::BTreeSet::new();
     let mut winning_participant_ids = std::collections::BTreeSet::new();
     let mut losing_participant_ids = std::collections::BTreeSet::new();
     let mut drawing_participant_ids = std::collections::BTreeSet::new();
+    let mut visible_to = std::collections::BTreeSet::new();
+    // Empty on matches created before the creator was recorded.
+    if !m.created_by_user_id.is_empty() {
+        visible_to.insert(m.created_by_user_id.clone());
+    }
     // `None` = no confirmed result yet, so every player's ids stay out of all
     // three outcome buckets. `Some(None)` = confirmed but tied (no single
     // winning side). `Some(Some(side))` = that side won.
@@ -173,8 +306,12 @@ fn match_doc(agg: &MatchAggregate) -> MatchDoc {
         let mut ids = vec![player.player_id.clone()];
         if let Some(uid) = &player.user_id {
             ids.push(uid.clone());
+            visible_to.insert(uid.clone());
         }
         participant_ids.extend(ids.iter().cloned());
+        if let Some(name) = &player.display_name {
+            participant_names.insert(name.clone());
+        }
         // A player who was never assigned a side (shouldn't happen for a
         // finished match, but no roster invariant guarantees it) has no
         // outcome to report — leave them out of every bucket.
@@ -194,16 +331,32 @@ fn match_doc(agg: &MatchAggregate) -> MatchDoc {
     let starts_at_ts = chrono::DateTime::parse_from_rfc3339(&m.starts_at)
         .map(|dt| dt.timestamp())
         .unwrap_or(0);
+    let team_ids: std::collections::BTreeSet<String> =
+        m.sides.values().filter_map(|s| s.team_id.clone()).collect();
+    let side_names: std::collections::BTreeSet<String> =
+        m.sides.values().filter_map(|s| s.name.clone()).collect();
     MatchDoc {
         id: m.id.clone(),
         name: m.name.clone(),
+        description: m.description.clone(),
         sport: m.match_type.clone(),
         status: m.status.clone(),
         starts_at: m.starts_at.clone(),
         starts_at_ts,
         participant_ids: participant_ids.into_iter().collect(),
+        participant_names: participant_names.into_iter().collect(),
+        team_ids: team_ids.into_iter().collect(),
+        side_names: side_names.into_iter().collect(),
+        venue: m.venue.clone(),
+        league: m.league.clone(),
+        geo: m.location.as_ref().map(|l| GeoPoint {
+            lat: l.latitude,
+            lng: l.longitude,
+        }),
         winning_participant_ids: winning_participant_ids.into_iter().collect(),
         losing_participant_ids: losing_participant_ids.into_iter().collect(),
         drawing_participant_ids: drawing_participant_ids.into_iter().collect(),
+        visibility: m.visibility.as_str(),
+        visible_to: visible_to.into_iter().collect(),
     }
 }
@@ -9,6 +9,7 @@
 //! it will be delegated to Temporal in a later pass (see docs/async-design.md
 //! §5). This module is the inline slice only.
 
+pub mod email;
 pub mod index;
 pub mod notify;
 pub mod push;
@@ -16,6 +17,7 @@ pub mod stats;
 
 use agon_core::dao::Dao;
 
+use self::email::Mailer;
 use crate::error::WorkerResult;
 use crate::event::ChangeEvent;
 use agon_core::push::PushClient;
@@ -24,22 +26,24 @@ use agon_core::search::SearchClient;
 /// Run every inline handler applicable to one event. `now` is the processing
 /// timestamp (RFC3339), used where an event carries no timestamp of its own.
 ///
-/// Ordering: indexing, notifications, push, then stats. All are independent
-/// and idempotent, so if a later one fails after an earlier succeeded,
-/// redelivery re-runs them all harmlessly. `push` runs after `notify`
-/// deliberately: a `NotificationRecord` write from `notify::handle` produces
-/// its own stream event, which `push::handle` reacts to on a later call to
-/// `route` — see `handlers/push.rs`'s module docs.
+/// Ordering: indexing, notifications, push, email, then stats. All are
+/// independent and idempotent (email at-least-once), so if a later one fails
+/// after an earlier succeeded, redelivery re-runs them all harmlessly. `push`
+/// and `email` run after `notify` deliberately: a `NotificationRecord` write
+/// from `notify::handle` produces its own stream event, which they react to on
+/// a later call to `route` — see `handlers/push.rs`'s module docs.
 pub async fn route(
     dao: &Dao,
     search: &SearchClient,
     push: Option<&PushClient>,
+    mailer: Option<&Mailer>,
     ev: &ChangeEvent,
     now: &str,
 ) -> WorkerResult<()> {
     index::handle(dao, search, ev).await?;
     notify::handle(dao, ev, now).await?;
-    push::handle(dao, push, ev).await?;
+    push::handle(dao, push, ev, now).await?;
+    email::handle(dao, mailer, ev).await?;
     stats::handle(dao, ev).await?;
     Ok(())
 }
@@ -1,24 +1,43 @@
 //! Inline handler: generate notifications from social events.
 //!
-//! Triggered by newly-created social edges: a follow, a match like, a match
-//! comment, an invitation, or a score submission. For each we synthesise a
-//! `NotificationRecord` for the target user and write it via the DAO (which
-//! bumps the unread badge atomically).
+//! Triggered by newly-created social edges: a follow, a reaction on a match or
+//! comment (likes included), a match comment, an invitation, a score
+//! submission, or a team join request — and by a match being cancelled or
+//! postponed, or a comment edited to `@`-mention someone new. For each we
+//! synthesise a `NotificationRecord` for the target user and write it via the
+//! DAO (which bumps the unread badge atomically).
+//!
+//! Reactions and comments are **grouped**: one row per (kind, match,
+//! recipient) per `GROUP_WINDOW_SECS` window, which each new actor joins in
+//! place ("Sam and 12 others reacted to Sunday Derby") instead of adding a row
+//! of their own. See `Dao::group_notification`.
+//!
+//! A notification whose recipient has muted its actor, or has a block with
+//! them either way, is dropped without a trace — as is one the recipient's
+//! notification preferences turn off (its kind in-app, or a muted match or
+//! team). See `wanted`.
 //!
 //! **Idempotency**: notification ids are **deterministic**, derived from the
 //! source item's keys (`notif-<kind>-<...>`). Combined with the guarded,
 //! id-idempotent `create_notification`, a redelivered stream event re-computes
 //! the same id and the write is a harmless no-op — no duplicate bell entries, no
-//! double-counted badge. Most notifications act only on `INSERT` (the edge's
-//! creation). The one exception is score submissions: the submission item is
-//! overwritten in place when a side responds, so "score confirmed" is detected
-//! as a `pending → confirmed` MODIFY transition (fired once, on the transition).
+//! double-counted badge. A grouped notification's id is the group's, with the
+//! window taken from the source item's own timestamp, so a redelivery lands in
+//! the same group, which already lists its actor. Most notifications act only
+//! on `INSERT` (the edge's creation). The one exception is score submissions:
+//! the submission item is overwritten in place when a side responds, so "score
+//! confirmed" is detected as a `pending → confirmed` MODIFY transition (fired
+//! once, on the transition).
+//! Comment edits are another: the stored mentions are diffed old vs new, and
+//! only the people an edit adds are notified.
 
 use agon_core::dao::Dao;
 use agon_core::dao::keys::{Pk, Sk};
+use agon_core::dao::match_ops::MatchAggregate;
 use agon_core::dao::records::{
-    InvitationContextRecord, InvitationRecord, NotificationKindRecord, NotificationRecord,
-    ScoreSubmissionRecord,
+    CommentRecord, InvitationContextRecord, InvitationRecord, JoinRequestRecord, MatchRecord,
+    NotificationKindRecord, NotificationRecord, ReactionRecord, ScoreSubmissionRecord,
+    UserFollowRecord,
 };
 
 use crate::error::{WorkerError, WorkerResult};
@@ -47,26 +66,59 @@ pub async fn handle(dao: &Dao, ev: &ChangeEvent, now: &str) -> WorkerResult<()>
         return notify_invitation_event(dao, ev, invitation_id, now).await;
     }
 
+    // Join requests are overwritten in place (a re-request after a denial, an
+    // admin's answer), so they react to MODIFY too.
+    if let (Pk::Team(team_id), Sk::JoinRequest(_)) = (&ev.pk, &ev.sk) {
+        return notify_join_request(dao, ev, team_id, now).await;
+    }
+
+    // Cancelling or postponing a match is an in-place `#META` MODIFY.
+    if let (Pk::Match(match_id), Sk::Meta) = (&ev.pk, &ev.sk) {
+        return notify_match_schedule_change(dao, ev, match_id, now).await;
+    }
+
+    // An edited comment or reply may mention someone new.
+    if let (Pk::Match(match_id), Sk::Comment(_) | Sk::Reply(_)) = (&ev.pk, &ev.sk)
+        && ev.kind == ChangeKind::Modify
+    {
+        return notify_comment_edit(dao, ev, match_id).await;
+    }
+
     // Every other notification is generated only on the creation of the edge.
     if ev.kind != ChangeKind::Insert {
         return Ok(());
     }
 
     match (&ev.pk, &ev.sk) {
-        // A user gained a follower: notify the followed user.
+        // A user gained a follower: notify the followed user — or, if they
+        // approved a follow request to get here, the follower.
         (Pk::User(followee_id), Sk::Follower(follower_id)) => {
-            notify_follow(dao, followee_id, follower_id, now).await
+            notify_follow(dao, ev, followee_id, follower_id, now).await
         }
-        // A match was liked: notify the match participants.
-        (Pk::Match(match_id), Sk::Like(liker_id)) => {
-            notify_like(dao, match_id, liker_id, now).await
+        // Someone asked to follow a private profile: notify its owner.
+        (Pk::User(followee_id), Sk::FollowRequest(follower_id)) => {
+            notify_follow_request(dao, followee_id, follower_id, now).await
         }
-        // A top-level comment on a match: notify the participants.
+        // Someone reacted to a match (a like is the 👍 reaction): notify the
+        // match participants.
+        (Pk::Match(match_id), Sk::Like(reactor_id)) => {
+            notify_match_reaction(dao, match_id, reactor_id, now).await
+        }
+        // Someone reacted to a comment or reply: notify its author.
+        (
+            Pk::Match(match_id),
+            Sk::CommentReaction {
+                comment_id,
+                user_id,
+            },
+        ) => notify_comment_reaction(dao, match_id, comment_id, user_id, now).await,
+        // A top-level comment on a match: notify the participants and anyone
+        // it mentions.
         (Pk::Match(match_id), Sk::Comment(comment_id)) => {
             notify_comment(dao, match_id, comment_id).await
         }
-        // A reply to a comment: notify the parent comment's author and the match
-        // participants.
+        // A reply to a comment: notify the parent comment's author, the match
+        // participants and anyone it mentions.
         (Pk::Match(match_id), Sk::Reply(reply_id)) => notify_reply(dao, match_id, reply_id).await,
         _ => Ok(()),
     }
@@ -132,6 +184,7 @@ async fn notify_invitation_accepted(
         id: format!("notif-invitationaccepted-{invitation_id}"),
         user_id: inv.invited_by_user_id.clone(),
         is_read: false,
+        actor_ids: Vec::new(),
         created_at: now.to_string(),
         kind: NotificationKindRecord::InvitationAccepted {
             actor_user_id: accepter_user_id,
@@ -139,7 +192,7 @@ async fn notify_invitation_accepted(
             context: inv.context.clone(),
         },
     };
-    dao.create_notification(&notif).await?;
+    deliver(dao, &notif).await?;
     Ok(())
 }
 
@@ -192,15 +245,17 @@ async fn notify_invitation(
         id: format!("notif-invitation-{invitation_id}"),
         user_id: invited_user_id,
         is_read: false,
+        actor_ids: Vec::new(),
         created_at: now.to_string(),
         kind,
     };
-    dao.create_notification(&notif).await?;
+    deliver(dao, &notif).await?;
     Ok(())
 }
 
 async fn notify_follow(
     dao: &Dao,
+    ev: &ChangeEvent,
     followee_id: &str,
     follower_id: &str,
     now: &str,
@@ -213,44 +268,237 @@ async fn notify_follow(
             "self-follow edge for user {followee_id}"
         )));
     }
+    let approved = ev
+        .new_record::<UserFollowRecord>()
+        .is_some_and(|edge| edge.requested_at.is_some());
+    let notif = if approved {
+        NotificationRecord {
+            id: format!("notif-follow-approved-{follower_id}-{followee_id}"),
+            user_id: follower_id.to_string(),
+            is_read: false,
+            actor_ids: Vec::new(),
+            created_at: now.to_string(),
+            kind: NotificationKindRecord::FollowRequestApproved {
+                actor_user_id: followee_id.to_string(),
+            },
+        }
+    } else {
+        NotificationRecord {
+            id: format!("notif-follow-{followee_id}-{follower_id}"),
+            user_id: followee_id.to_string(),
+            is_read: false,
+            actor_ids: Vec::new(),
+            created_at: now.to_string(),
+            kind: NotificationKindRecord::Follow {
+                actor_user_id: follower_id.to_string(),
+            },
+        }
+    };
+    deliver(dao, &notif).await?;
+    Ok(())
+}
+
+/// A follow request was filed. Like a plain follow, the id is per (followee,
+/// follower), so asking again after a denial doesn't notify twice.
+async fn notify_follow_request(
+    dao: &Dao,
+    followee_id: &str,
+    follower_id: &str,
+    now: &str,
+) -> WorkerResult<()> {
+    if followee_id == follower_id {
+        return Err(WorkerError::Invariant(format!(
+            "self-follow request for user {followee_id}"
+        )));
+    }
     let notif = NotificationRecord {
-        id: format!("notif-follow-{followee_id}-{follower_id}"),
+        id: format!("notif-follow-request-{followee_id}-{follower_id}"),
         user_id: followee_id.to_string(),
         is_read: false,
+        actor_ids: Vec::new(),
         created_at: now.to_string(),
-        kind: NotificationKindRecord::Follow {
+        kind: NotificationKindRecord::FollowRequest {
             actor_user_id: follower_id.to_string(),
         },
     };
-    dao.create_notification(&notif).await?;
+    deliver(dao, &notif).await?;
+    Ok(())
+}
+
+/// Write `notif` unless its recipient doesn't want it (see `wanted`) — then
+/// it's dropped silently (and so is its push).
+async fn deliver(dao: &Dao, notif: &NotificationRecord) -> WorkerResult<()> {
+    if !wanted(dao, notif).await? {
+        return Ok(());
+    }
+    dao.create_notification(notif).await?;
+    Ok(())
+}
+
+/// `deliver` for a grouped notification: `notif` (with a `group_id` id) is
+/// folded into its group rather than written as a row of its own.
+async fn deliver_grouped(dao: &Dao, notif: &NotificationRecord) -> WorkerResult<()> {
+    if !wanted(dao, notif).await? {
+        return Ok(());
+    }
+    dao.group_notification(notif).await?;
     Ok(())
 }
 
-async fn notify_like(dao: &Dao, match_id: &str, liker_id: &str, now: &str) -> WorkerResult<()> {
+/// Whether the recipient wants `notif` in their list: they haven't silenced
+/// its actor, switched its kind off in-app, or muted the match or team it's
+/// about — including, for a match, a team playing in it (the one case that
+/// costs a read of the match, and only for someone who mutes teams).
+async fn wanted(dao: &Dao, notif: &NotificationRecord) -> WorkerResult<bool> {
+    if dao
+        .is_silenced(&notif.user_id, notif.kind.actor_user_id())
+        .await?
+    {
+        return Ok(false);
+    }
+    let prefs = dao.get_notification_preferences(&notif.user_id).await?;
+    if !prefs.allows_in_app(&notif.kind) {
+        return Ok(false);
+    }
+    if let Some(match_id) = notif.kind.match_id()
+        && !prefs.muted_team_ids.is_empty()
+    {
+        let summaries = dao
+            .batch_get_match_summaries(&[match_id.to_string()])
+            .await?;
+        let muted = summaries.get(match_id).is_some_and(|summary| {
+            summary
+                .sides
+                .iter()
+                .filter_map(|side| side.team_id.as_deref())
+                .any(|team_id| prefs.mutes_team(team_id))
+        });
+        if muted {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// How long a notification group stays open: events further apart than this
+/// start a new row. Windows are fixed (aligned to the epoch), so a group can
+/// close early if it opened near a window's end.
+const GROUP_WINDOW_SECS: i64 = 6 * 60 * 60;
+
+/// The id of the group for `kind` events on `match_id` for `user_id` in the
+/// window containing `at` (the source item's own timestamp, so redelivery maps
+/// to the same group).
+fn group_id(kind: &str, match_id: &str, user_id: &str, at: &str) -> String {
+    let window = chrono::DateTime::parse_from_rfc3339(at)
+        .map(|t| t.timestamp().div_euclid(GROUP_WINDOW_SECS))
+        .unwrap_or_default();
+    format!("notif-{kind}-{match_id}-{user_id}-{window}")
+}
+
+async fn notify_match_reaction(
+    dao: &Dao,
+    match_id: &str,
+    reactor_id: &str,
+    now: &str,
+) -> WorkerResult<()> {
+    let Some(reaction) = dao.get_match_reaction(match_id, reactor_id).await? else {
+        // Already taken back; nothing to notify about.
+        return Ok(());
+    };
     let Some(agg) = dao.get_match(match_id).await? else {
         // Match gone; nothing to notify about.
         return Ok(());
     };
     // Recipients: the match participants (players with a linked user), minus the
-    // liker themselves. Deduplicated across sides.
-    let recipients = participant_user_ids(&agg, liker_id);
-    for user_id in recipients {
-        let notif = NotificationRecord {
-            id: format!("notif-like-{match_id}-{liker_id}-{user_id}"),
+    // reactor themselves. Deduplicated across sides.
+    for user_id in participant_user_ids(&agg, reactor_id) {
+        let notif = reaction_notification(
+            &agg.match_,
             user_id,
-            is_read: false,
-            created_at: now.to_string(),
-            kind: NotificationKindRecord::Like {
-                actor_user_id: liker_id.to_string(),
-                match_id: match_id.to_string(),
-                match_name: agg.match_.name.clone(),
-            },
-        };
-        dao.create_notification(&notif).await?;
+            reactor_id,
+            reaction.reaction,
+            None,
+            &reaction.created_at,
+            now,
+        );
+        deliver_grouped(dao, &notif).await?;
     }
     Ok(())
 }
 
+async fn notify_comment_reaction(
+    dao: &Dao,
+    match_id: &str,
+    comment_id: &str,
+    reactor_id: &str,
+    now: &str,
+) -> WorkerResult<()> {
+    let Some(reaction) = dao
+        .get_comment_reaction(match_id, comment_id, reactor_id)
+        .await?
+    else {
+        return Ok(());
+    };
+    let comment = match dao.get_comment(match_id, comment_id).await? {
+        Some(comment) => comment,
+        None => match dao.get_reply(match_id, comment_id).await? {
+            Some(reply) => reply,
+            None => return Ok(()),
+        },
+    };
+    // Tombstones have no author to tell.
+    let Some(author_id) = comment.author_user_id else {
+        return Ok(());
+    };
+    if author_id == reactor_id {
+        return Ok(());
+    }
+    let Some(agg) = dao.get_match(match_id).await? else {
+        return Ok(());
+    };
+    if !dao.can_view_match(&agg, Some(&author_id)).await? {
+        return Ok(());
+    }
+    let notif = reaction_notification(
+        &agg.match_,
+        author_id,
+        reactor_id,
+        reaction.reaction,
+        Some(comment.comment_id),
+        &reaction.created_at,
+        now,
+    );
+    deliver_grouped(dao, &notif).await
+}
+
+/// The reaction notification for `user_id` on `match_`, in the match's
+/// reaction group for the window `reacted_at` falls in. Reactions on the
+/// match itself and on its comments share the group.
+fn reaction_notification(
+    match_: &MatchRecord,
+    user_id: String,
+    reactor_id: &str,
+    reaction: ReactionRecord,
+    comment_id: Option<String>,
+    reacted_at: &str,
+    now: &str,
+) -> NotificationRecord {
+    NotificationRecord {
+        id: group_id("reaction", &match_.id, &user_id, reacted_at),
+        user_id,
+        is_read: false,
+        actor_ids: Vec::new(),
+        created_at: now.to_string(),
+        kind: NotificationKindRecord::Reaction {
+            actor_user_id: reactor_id.to_string(),
+            match_id: match_.id.clone(),
+            match_name: match_.name.clone(),
+            reaction,
+            comment_id,
+        },
+    }
+}
+
 async fn notify_comment(dao: &Dao, match_id: &str, comment_id: &str) -> WorkerResult<()> {
     let Some(comment) = dao.get_comment(match_id, comment_id).await? else {
         return Ok(());
@@ -264,14 +512,18 @@ async fn notify_comment(dao: &Dao, match_id: &str, comment_id: &str) -> WorkerRe
 
     let preview = comment.text.as_deref().map(preview_of).unwrap_or_default();
 
-    // Notify match participants (except the comment author). The comment carries
-    // its own created_at, so the notification uses it directly.
+    // Notify match participants (except the comment author, and anyone it
+    // mentions — they get the more specific `Mention` below), grouped per
+    // match. The comment carries its own created_at, so the notification uses
+    // it directly.
+    let mentioned = mentioned_user_ids(&comment);
     let recipients = participant_user_ids(&agg, &author_id);
-    for user_id in recipients {
+    for user_id in recipients.into_iter().filter(|id| !mentioned.contains(id)) {
         let notif = NotificationRecord {
-            id: format!("notif-comment-{match_id}-{comment_id}-{user_id}"),
+            id: group_id("comment", match_id, &user_id, &comment.created_at),
             user_id,
             is_read: false,
+            actor_ids: Vec::new(),
             created_at: comment.created_at.clone(),
             kind: NotificationKindRecord::Comment {
                 actor_user_id: author_id.clone(),
@@ -280,9 +532,9 @@ async fn notify_comment(dao: &Dao, match_id: &str, comment_id: &str) -> WorkerRe
                 preview: preview.clone(),
             },
         };
-        dao.create_notification(&notif).await?;
+        deliver_grouped(dao, &notif).await?;
     }
-    Ok(())
+    notify_mentions(dao, &agg, &comment, &author_id, &[], &comment.created_at).await
 }
 
 /// A reply to a comment: notify the parent comment's author plus the match
@@ -321,12 +573,17 @@ async fn notify_reply(dao: &Dao, match_id: &str, reply_id: &str) -> WorkerResult
     {
         recipients.insert(parent_author);
     }
+    // Anyone the reply mentions gets a `Mention` instead.
+    for user_id in mentioned_user_ids(&reply) {
+        recipients.remove(&user_id);
+    }
 
     for user_id in recipients {
         let notif = NotificationRecord {
             id: format!("notif-reply-{match_id}-{reply_id}-{user_id}"),
             user_id,
             is_read: false,
+            actor_ids: Vec::new(),
             created_at: reply.created_at.clone(),
             kind: NotificationKindRecord::Reply {
                 actor_user_id: author_id.clone(),
@@ -336,7 +593,79 @@ async fn notify_reply(dao: &Dao, match_id: &str, reply_id: &str) -> WorkerResult
                 preview: preview.clone(),
             },
         };
-        dao.create_notification(&notif).await?;
+        deliver(dao, &notif).await?;
+    }
+    notify_mentions(dao, &agg, &reply, &author_id, &[], &reply.created_at).await
+}
+
+/// A comment or reply was modified. Only a text edit can add mentions — a
+/// `reply_count` bump, moderation or a tombstone can't — so anything else is
+/// ignored, as is an edit that only kept or dropped mentions.
+async fn notify_comment_edit(dao: &Dao, ev: &ChangeEvent, match_id: &str) -> WorkerResult<()> {
+    let (Some(old), Some(new)) = (
+        ev.old_record::<CommentRecord>(),
+        ev.new_record::<CommentRecord>(),
+    ) else {
+        return Ok(());
+    };
+    if new.edited_at == old.edited_at || new.deleted_at.is_some() || new.hidden_at.is_some() {
+        return Ok(());
+    }
+    let (Some(author_id), Some(edited_at)) = (new.author_user_id.clone(), new.edited_at.clone())
+    else {
+        return Ok(());
+    };
+    let before = mentioned_user_ids(&old);
+    if mentioned_user_ids(&new)
+        .iter()
+        .all(|id| before.contains(id))
+    {
+        return Ok(());
+    }
+    let Some(agg) = dao.get_match(match_id).await? else {
+        return Ok(());
+    };
+    notify_mentions(dao, &agg, &new, &author_id, &before, &edited_at).await
+}
+
+/// Notify each user `comment` mentions who wasn't already mentioned in
+/// `before`, can see the match, and isn't its author. The id is per (comment,
+/// user), so mentioning someone again after an edit removed them doesn't
+/// re-notify.
+async fn notify_mentions(
+    dao: &Dao,
+    agg: &MatchAggregate,
+    comment: &CommentRecord,
+    author_id: &str,
+    before: &[String],
+    created_at: &str,
+) -> WorkerResult<()> {
+    let preview = comment.text.as_deref().map(preview_of).unwrap_or_default();
+    for user_id in mentioned_user_ids(comment) {
+        if user_id == author_id
+            || before.contains(&user_id)
+            || !dao.can_view_match(agg, Some(&user_id)).await?
+        {
+            continue;
+        }
+        let notif = NotificationRecord {
+            id: format!(
+                "notif-mention-{}-{}-{user_id}",
+                comment.match_id, comment.comment_id
+            ),
+            user_id,
+            is_read: false,
+            actor_ids: Vec::new(),
+            created_at: created_at.to_string(),
+            kind: NotificationKindRecord::Mention {
+                actor_user_id: author_id.to_string(),
+                match_id: comment.match_id.clone(),
+                comment_id: comment.comment_id.clone(),
+                parent_comment_id: comment.parent_id.clone(),
+                preview: preview.clone(),
+            },
+        };
+        deliver(dao, &notif).await?;
     }
     Ok(())
 }
@@ -430,6 +759,7 @@ async fn notify_score_submitted(
             id: format!("notif-scoresubmitted-{match_id}-{submission_id}-{user_id}"),
             user_id,
             is_read: false,
+            actor_ids: Vec::new(),
             created_at: now.to_string(),
             kind: NotificationKindRecord::ScoreSubmitted {
                 actor_user_id: submitter_user_id.clone(),
@@ -439,7 +769,7 @@ async fn notify_score_submitted(
                 needs_confirmation,
             },
         };
-        dao.create_notification(&notif).await?;
+        deliver(dao, &notif).await?;
     }
     Ok(())
 }
@@ -512,6 +842,7 @@ async fn notify_score_confirmed(
         id: format!("notif-scoreconfirmed-{match_id}-{submission_id}"),
         user_id: submitter_user_id,
         is_read: false,
+        actor_ids: Vec::new(),
         created_at: now.to_string(),
         kind: NotificationKindRecord::ScoreConfirmed {
             actor_user_id: confirmer_user_id,
@@ -520,17 +851,171 @@ async fn notify_score_confirmed(
             submission_id: submission_id.to_string(),
         },
     };
-    dao.create_notification(&notif).await?;
+    deliver(dao, &notif).await?;
+    Ok(())
+}
+
+/// Dispatch a join-request change event. A request that's newly pending — a
+/// first request, or a re-request replacing an answered one (a new `id`) —
+/// notifies every team admin; a transition into `approved` notifies the
+/// requester. A denial is deliberately silent, as is a REMOVE.
+async fn notify_join_request(
+    dao: &Dao,
+    ev: &ChangeEvent,
+    team_id: &str,
+    now: &str,
+) -> WorkerResult<()> {
+    let Some(req) = ev.new_record::<JoinRequestRecord>() else {
+        return Ok(());
+    };
+    let old = ev.old_record::<JoinRequestRecord>();
+
+    match req.status.as_str() {
+        "pending" => {
+            if old.as_ref().is_some_and(|o| o.id == req.id) {
+                return Ok(());
+            }
+            let Some(agg) = dao.get_team(team_id).await? else {
+                return Ok(());
+            };
+            let admins: std::collections::BTreeSet<String> = agg
+                .members
+                .iter()
+                .filter(|m| m.role == "admin")
+                .filter_map(|m| m.user_id.clone())
+                .filter(|uid| *uid != req.user_id)
+                .collect();
+            for admin_id in admins {
+                let notif = NotificationRecord {
+                    id: format!("notif-joinrequest-{}-{admin_id}", req.id),
+                    user_id: admin_id,
+                    is_read: false,
+                    actor_ids: Vec::new(),
+                    created_at: now.to_string(),
+                    kind: NotificationKindRecord::TeamJoinRequest {
+                        actor_user_id: req.user_id.clone(),
+                        team_id: team_id.to_string(),
+                        team_name: agg.team.name.clone(),
+                        request_id: req.id.clone(),
+                    },
+                };
+                deliver(dao, &notif).await?;
+            }
+            Ok(())
+        }
+        "approved" => {
+            let was_approved = old
+                .as_ref()
+                .is_some_and(|o| o.id == req.id && o.status == "approved");
+            if was_approved {
+                return Ok(());
+            }
+            let Some(admin_id) = req.responded_by_user_id.clone() else {
+                return Ok(());
+            };
+            let Some(team) = dao.get_team_meta(team_id).await? else {
+                return Ok(());
+            };
+            let notif = NotificationRecord {
+                id: format!("notif-joinapproved-{}", req.id),
+                user_id: req.user_id.clone(),
+                is_read: false,
+                actor_ids: Vec::new(),
+                created_at: now.to_string(),
+                kind: NotificationKindRecord::TeamJoinApproved {
+                    actor_user_id: admin_id,
+                    team_id: team_id.to_string(),
+                    team_name: team.name,
+                },
+            };
+            deliver(dao, &notif).await?;
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Dispatch a match `#META` change. A cancellation newly recorded on the
+/// match, or a new entry appended to its postponements, notifies the match's
+/// whole fan-out audience — participants and followers of the players, teams
+/// and clubs — except the organizer who made the change. Every other meta
+/// write (counter bumps, edits, scores) compares equal and is ignored.
+async fn notify_match_schedule_change(
+    dao: &Dao,
+    ev: &ChangeEvent,
+    match_id: &str,
+    now: &str,
+) -> WorkerResult<()> {
+    if ev.kind != ChangeKind::Modify {
+        return Ok(());
+    }
+    let (Some(old), Some(new)) = (
+        ev.old_record::<MatchRecord>(),
+        ev.new_record::<MatchRecord>(),
+    ) else {
+        return Ok(());
+    };
+
+    let (id_prefix, actor_user_id, kind) = if old.cancellation.is_none()
+        && let Some(c) = &new.cancellation
+    {
+        (
+            format!("notif-match-cancelled-{match_id}"),
+            c.by_user_id.clone(),
+            NotificationKindRecord::MatchCancelled {
+                actor_user_id: c.by_user_id.clone(),
+                match_id: match_id.to_string(),
+                match_name: new.name.clone(),
+                reason: c.reason.clone(),
+            },
+        )
+    } else if new.postponements.len() > old.postponements.len()
+        && let Some(p) = new.postponements.last()
+    {
+        (
+            // The postponement's index keeps a second postponement from
+            // colliding with the first's ids.
+            format!(
+                "notif-match-postponed-{match_id}-{}",
+                new.postponements.len()
+            ),
+            p.by_user_id.clone(),
+            NotificationKindRecord::MatchPostponed {
+                actor_user_id: p.by_user_id.clone(),
+                match_id: match_id.to_string(),
+                match_name: new.name.clone(),
+                reason: p.reason.clone(),
+                starts_at: p.to_starts_at.clone(),
+            },
+        )
+    } else {
+        return Ok(());
+    };
+
+    let audience = dao.resolve_fanout_audience(match_id).await?;
+    let mut recipients: Vec<String> = audience
+        .into_keys()
+        .filter(|uid| *uid != actor_user_id)
+        .collect();
+    recipients.sort();
+    for user_id in recipients {
+        let notif = NotificationRecord {
+            id: format!("{id_prefix}-{user_id}"),
+            user_id,
+            is_read: false,
+            actor_ids: Vec::new(),
+            created_at: now.to_string(),
+            kind: kind.clone(),
+        };
+        deliver(dao, &notif).await?;
+    }
     Ok(())
 }
 
 /// The deduplicated set of linked user ids among a match's players, excluding
 /// `exclude` (typically the actor, who shouldn't be notified about their own
 /// action).
-fn participant_user_ids(
-    agg: &agon_core::dao::match_ops::MatchAggregate,
-    exclude: &str,
-) -> Vec<String> {
+fn participant_user_ids(agg: &MatchAggregate, exclude: &str) -> Vec<String> {
     let mut seen = std::collections::BTreeSet::new();
     for player in &agg.players {
         if let Some(uid) = &player.user_id
@@ -542,6 +1027,17 @@ fn participant_user_ids(
     seen.into_iter().collect()
 }
 
+/// The distinct users a comment mentions, in order of first mention.
+fn mentioned_user_ids(comment: &CommentRecord) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for mention in &comment.mentions {
+        if !ids.contains(&mention.user_id) {
+            ids.push(mention.user_id.clone());
+        }
+    }
+    ids
+}
+
 /// A short preview of comment text for the notification body.
 fn preview_of(text: &str) -> String {
     const MAX: usize = 80;
@@ -552,3 +1048,53 @@ fn preview_of(text: &str) -> String {
         format!("{truncated}…")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agon_core::dao::memory::MemoryTable;
+    use agon_core::dao::records::UserRecord;
+
+    use crate::event::Envelope;
+
+    fn user(id: &str) -> UserRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "email": format!("{id}@example.com"),
+            "name": id,
+            "created_at": "2026-07-01T10:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    /// A follow, end to end: the DAO writes the edge to an in-memory table,
+    /// every change it made goes through `handle` the way the consumer would
+    /// see it — twice, as a redelivery would — and the followee has exactly
+    /// one notification and one unread.
+    #[tokio::test]
+    async fn follow_notifies_the_followee_once() {
+        let table = MemoryTable::new("agon");
+        let dao = Dao::in_memory(&table);
+        let now = "2026-07-01T10:00:00Z";
+        for id in ["u1", "u2"] {
+            dao.create_user(&format!("sub-{id}"), &user(id))
+                .await
+                .unwrap();
+        }
+        dao.follow_user("u2", "u1", now).await.unwrap();
+
+        let records = table.take_stream();
+        for record in records.iter().chain(&records) {
+            let event = ChangeEvent::from_envelope(&Envelope::from(record.clone())).unwrap();
+            handle(&dao, &event, now).await.unwrap();
+        }
+
+        let page = dao.list_notifications("u1", None, 10).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert!(matches!(
+            &page.items[0].kind,
+            NotificationKindRecord::Follow { actor_user_id } if actor_user_id == "u2"
+        ));
+        assert_eq!(dao.get_user("u1").await.unwrap().unwrap().unread_count, 1);
+    }
+}
@@ -9,38 +9,95 @@
 //! means zero duplication of `notify.rs`'s nine recipient-computing branches,
 //! and the same at-least-once/idempotent handling every other handler gets.
 //!
-//! Future: a per-user (or per-kind / per-followed-team) push-preference check
-//! — the YouTube-bell "mute this kind of notification" idea — belongs right
-//! here, before the send loop: it only changes whether a push goes out, never
-//! whether the in-app `NotificationRecord` (the bell entry) gets created.
+//! Grouped notifications (see `Dao::group_notification`) are the exception to
+//! INSERT-only: the first actor creates the row (an INSERT, pushed as usual),
+//! and later actors join it in place (MODIFYs). Those push only when the
+//! group's size reaches one of `GROUP_PUSH_THRESHOLDS`, so a busy match buzzes
+//! a few times rather than once per like.
+//!
+//! The recipient's `NotificationPreferencesRecord` is checked before the send
+//! loop: a kind with push switched off sends nothing, and a push that arrives
+//! in their quiet hours is stored as a `DeferredPushRecord` instead, which
+//! starts a `ReleaseDeferredPushes` workflow that sends it when they end.
+//! (Mutes and in-app toggles were already applied by `notify.rs` — a
+//! notification they drop never reaches here.)
 
 use agon_core::dao::Dao;
 use agon_core::dao::error::DaoError;
 use agon_core::dao::keys::{Pk, Sk};
-use agon_core::dao::records::{NotificationKindRecord, NotificationRecord};
+use agon_core::dao::records::{DeferredPushRecord, NotificationKindRecord, NotificationRecord};
 use agon_core::push::{PushClient, PushOutcome};
 
 use crate::error::WorkerResult;
 use crate::event::{ChangeEvent, ChangeKind};
 
-pub async fn handle(dao: &Dao, push: Option<&PushClient>, ev: &ChangeEvent) -> WorkerResult<()> {
+/// `now` is the processing timestamp (RFC3339), which quiet hours are judged
+/// against.
+pub async fn handle(
+    dao: &Dao,
+    push: Option<&PushClient>,
+    ev: &ChangeEvent,
+    now: &str,
+) -> WorkerResult<()> {
     // Not configured (e.g. local dev without a GCP project) — nothing to do.
     let Some(push) = push else {
         return Ok(());
     };
-    if ev.kind != ChangeKind::Insert {
-        return Ok(());
-    }
     let (Pk::User(user_id), Sk::Notification(_)) = (&ev.pk, &ev.sk) else {
         return Ok(());
     };
     let Some(notif) = ev.new_record::<NotificationRecord>() else {
         return Ok(());
     };
+    let (title, body) = match ev.kind {
+        ChangeKind::Insert => push_text(&notif.kind),
+        // A grouped notification gaining actors; anything else that modifies
+        // a notification (marking it read) has the same `actor_ids` both sides.
+        ChangeKind::Modify => {
+            let Some(old) = ev.old_record::<NotificationRecord>() else {
+                return Ok(());
+            };
+            if !crosses_push_threshold(old.actor_ids.len(), notif.actor_ids.len()) {
+                return Ok(());
+            }
+            grouped_push_text(&notif.kind, notif.actor_ids.len())
+        }
+        ChangeKind::Remove => return Ok(()),
+    };
+
+    let prefs = dao.get_notification_preferences(user_id).await?;
+    if !prefs.allows_push(&notif.kind) {
+        return Ok(());
+    }
+    let now = chrono::DateTime::parse_from_rfc3339(now)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|_| chrono::Utc::now());
+    if let Some(release_at) = prefs.quiet_hours.and_then(|q| q.ends_after(now)) {
+        dao.defer_push(&DeferredPushRecord {
+            user_id: user_id.clone(),
+            notification_id: notif.id,
+            title,
+            body,
+            // Whole seconds, `Z`: the key sorts by this, so keep one format.
+            release_at: release_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        })
+        .await?;
+        return Ok(());
+    }
+    send_to_devices(dao, push, user_id, &title, &body).await
+}
 
-    let (title, body) = push_text(&notif.kind);
+/// Send one push to every device `user_id` has registered, unregistering any
+/// FCM reports as gone.
+pub async fn send_to_devices(
+    dao: &Dao,
+    push: &PushClient,
+    user_id: &str,
+    title: &str,
+    body: &str,
+) -> WorkerResult<()> {
     for device in dao.list_devices(user_id).await? {
-        match push.send(&device.push_token, &title, &body).await? {
+        match push.send(&device.push_token, title, body).await? {
             PushOutcome::Sent => {}
             // FCM rejected the token itself (unregistered/not found) — the
             // device is gone (app uninstalled, service worker replaced, etc.);
@@ -56,6 +113,34 @@ pub async fn handle(dao: &Dao, push: Option<&PushClient>, ev: &ChangeEvent) -> W
     Ok(())
 }
 
+/// Group sizes at which a grouped notification pushes again, after the push
+/// for its first actor.
+const GROUP_PUSH_THRESHOLDS: [usize; 6] = [5, 10, 25, 50, 100, 500];
+
+/// Whether a group growing from `before` to `after` actors reached a new
+/// threshold.
+fn crosses_push_threshold(before: usize, after: usize) -> bool {
+    GROUP_PUSH_THRESHOLDS
+        .iter()
+        .any(|&threshold| before < threshold && threshold <= after)
+}
+
+/// Push copy for a group of `count` actors. Kinds that are never grouped fall
+/// back to `push_text`.
+fn grouped_push_text(kind: &NotificationKindRecord, count: usize) -> (String, String) {
+    match kind {
+        NotificationKindRecord::Reaction { match_name, .. } => (
+            "New reactions".to_string(),
+            format!("{count} people reacted to {match_name}"),
+        ),
+        NotificationKindRecord::Comment { .. } => (
+            "New comments".to_string(),
+            format!("{count} people commented on your match"),
+        ),
+        other => push_text(other),
+    }
+}
+
 /// Generic push copy for one notification. Built only from fields already
 /// denormalized onto `NotificationKindRecord` — no extra DAO reads, matching
 /// the "kind carries display fields so the feed renders without extra reads"
@@ -78,6 +163,14 @@ fn push_text(kind: &NotificationKindRecord) -> (String, String) {
             "New follower".to_string(),
             "Someone started following you".to_string(),
         ),
+        NotificationKindRecord::FollowRequest { .. } => (
+            "Follow request".to_string(),
+            "Someone asked to follow you".to_string(),
+        ),
+        NotificationKindRecord::FollowRequestApproved { .. } => (
+            "Follow request approved".to_string(),
+            "Your follow request was approved".to_string(),
+        ),
         NotificationKindRecord::Like { match_name, .. } => (
             "New like".to_string(),
             format!("Someone liked {match_name}"),
@@ -102,13 +195,47 @@ fn push_text(kind: &NotificationKindRecord) -> (String, String) {
             "Score confirmed".to_string(),
             format!("Your score for {match_name} was confirmed"),
         ),
+        NotificationKindRecord::TeamJoinRequest { team_name, .. } => (
+            "Join request".to_string(),
+            format!("Someone asked to join {team_name}"),
+        ),
+        NotificationKindRecord::TeamJoinApproved { team_name, .. } => (
+            "Request approved".to_string(),
+            format!("You're now a member of {team_name}"),
+        ),
+        NotificationKindRecord::MatchCancelled { match_name, .. } => (
+            "Match cancelled".to_string(),
+            format!("{match_name} has been cancelled"),
+        ),
+        NotificationKindRecord::MatchPostponed { match_name, .. } => (
+            "Match postponed".to_string(),
+            format!("{match_name} has been moved to a new time"),
+        ),
+        NotificationKindRecord::Mention { preview, .. } => {
+            ("You were mentioned".to_string(), preview.clone())
+        }
+        NotificationKindRecord::Reaction {
+            match_name,
+            reaction,
+            comment_id,
+            ..
+        } => {
+            let target = match comment_id {
+                Some(_) => format!("your comment on {match_name}"),
+                None => match_name.clone(),
+            };
+            (
+                "New reaction".to_string(),
+                format!("Someone reacted {} to {target}", reaction.emoji()),
+            )
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use agon_core::dao::records::InvitationContextRecord;
+    use agon_core::dao::records::{InvitationContextRecord, ReactionRecord};
 
     /// `push_text` produces non-empty copy for every current notification kind
     /// (mirrors every `NotificationKindRecord` variant, so a new variant that
@@ -140,6 +267,12 @@ mod tests {
             NotificationKindRecord::Follow {
                 actor_user_id: "u1".into(),
             },
+            NotificationKindRecord::FollowRequest {
+                actor_user_id: "u1".into(),
+            },
+            NotificationKindRecord::FollowRequestApproved {
+                actor_user_id: "u1".into(),
+            },
             NotificationKindRecord::Like {
                 actor_user_id: "u1".into(),
                 match_id: "m1".into(),
@@ -178,6 +311,44 @@ mod tests {
                 match_name: "Sunday Tennis".into(),
                 submission_id: "s1".into(),
             },
+            NotificationKindRecord::TeamJoinRequest {
+                actor_user_id: "u1".into(),
+                team_id: "t1".into(),
+                team_name: "The Aces".into(),
+                request_id: "jr1".into(),
+            },
+            NotificationKindRecord::TeamJoinApproved {
+                actor_user_id: "u1".into(),
+                team_id: "t1".into(),
+                team_name: "The Aces".into(),
+            },
+            NotificationKindRecord::MatchCancelled {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                match_name: "Sunday Tennis".into(),
+                reason: "Rain".into(),
+            },
+            NotificationKindRecord::MatchPostponed {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                match_name: "Sunday Tennis".into(),
+                reason: "Rain".into(),
+                starts_at: "2026-06-01T10:00:00.000Z".into(),
+            },
+            NotificationKindRecord::Mention {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                comment_id: "c1".into(),
+                parent_comment_id: None,
+                preview: "@Sofia what a rally".into(),
+            },
+            NotificationKindRecord::Reaction {
+                actor_user_id: "u1".into(),
+                match_id: "m1".into(),
+                match_name: "Sunday Tennis".into(),
+                reaction: ReactionRecord::Fire,
+                comment_id: Some("c1".into()),
+            },
         ];
 
         for kind in &kinds {
@@ -188,8 +359,22 @@ mod tests {
 
         // The two ScoreSubmitted variants (needs_confirmation true/false) must
         // produce different copy — that's the whole point of the flag.
-        let (_, needs_confirm_body) = push_text(&kinds[7]);
-        let (_, informational_body) = push_text(&kinds[8]);
+        let (_, needs_confirm_body) = push_text(&kinds[9]);
+        let (_, informational_body) = push_text(&kinds[10]);
         assert_ne!(needs_confirm_body, informational_body);
     }
+
+    /// A group pushes when it reaches a threshold, once per threshold, however
+    /// many actors one write adds — and never for a modify that adds none
+    /// (marking it read).
+    #[test]
+    fn grouped_pushes_fire_at_thresholds() {
+        assert!(!crosses_push_threshold(1, 2));
+        assert!(!crosses_push_threshold(3, 4));
+        assert!(crosses_push_threshold(4, 5));
+        assert!(!crosses_push_threshold(5, 6));
+        assert!(crosses_push_threshold(8, 12));
+        assert!(!crosses_push_threshold(10, 10));
+        assert!(!crosses_push_threshold(500, 900));
+    }
 }
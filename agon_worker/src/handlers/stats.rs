@@ -53,8 +53,14 @@ pub async fn reconcile_match_stats(dao: &Dao, match_id: &str) -> WorkerResult<()
     // A match only contributes to stats once its score is confirmed — a
     // completed-but-pending (or disputed) score isn't agreed yet, and counting
     // it would inflate `matches_played` with no corresponding win, dragging
-    // down (or zeroing) the win rate for a game nobody has actually lost.
-    let confirmed_score = agg.match_.confirmed_score.as_ref();
+    // down (or zeroing) the win rate for a game nobody has actually lost. A
+    // cancelled match keeps any confirmed score for the record but contributes
+    // nothing, so cancelling backs its contribution out.
+    let confirmed_score = agg
+        .match_
+        .confirmed_score
+        .as_ref()
+        .filter(|_| agg.match_.status != "cancelled");
     let sport = agg.match_.match_type.clone();
     let winner_side_id = confirmed_score.and_then(|cs| cs.winner_side_id.clone());
 
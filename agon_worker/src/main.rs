@@ -1,12 +1,29 @@
 //! agon_worker — the async processing worker.
 //!
 //! Long-polls the SQS events queue (fed by DynamoDB Streams via an EventBridge
-//! Pipe) and runs inline handlers: search indexing (Meilisearch) and
-//! notification generation. See docs/async-design.md.
+//! Pipe) and runs inline handlers: search indexing (Meilisearch),
+//! notification generation, and push/email delivery. See docs/async-design.md.
 //!
 //! The inline slice always runs. Multi-step orchestration (feed fan-out, the
 //! accept-invitation saga) lives in the `temporal` module and runs a Temporal
 //! worker alongside the consumer loop in this same binary.
+//!
+//! `agon_worker reindex [users|teams|matches]...` instead rebuilds the named
+//! search indexes (all of them by default) from the table and exits — see the
+//! `ReindexSearch` workflow. The work runs on the regular workers; this just
+//! starts it and reports progress.
+//!
+//! `agon_worker stamp-feed-ttl` stamps a TTL on the feed entries written
+//! before feed retention existed, then exits. A one-off, run in-process:
+//! stopping it part-way and running it again picks up where it left off.
+//!
+//! `agon_worker backfill-team-matches` writes the `TMATCH#` pointers season
+//! stats read for matches created before they existed, then exits. Also
+//! in-process and safe to re-run from the start.
+//!
+//! `agon_worker backfill-match-feed` projects the match and result feed
+//! entries written before the `MFEED#` index existed onto it, so a
+//! postponement prunes them too, then exits. Also safe to re-run.
 
 mod asset_consumer;
 mod config;
@@ -21,8 +38,10 @@ use aws_sdk_sqs::Client as SqsClient;
 
 use crate::config::Config;
 use crate::consumer::Consumer;
+use crate::handlers::email::Mailer;
+use agon_core::email::{EmailClient, SmtpConfig, SmtpSecurity};
 use agon_core::push::PushClient;
-use agon_core::search::SearchClient;
+use agon_core::search::{Index, SearchClient};
 
 #[tokio::main]
 async fn main() {
@@ -30,6 +49,43 @@ async fn main() {
     // flushes the OTLP batch exporters. See agon_core::telemetry.
     let _telemetry = agon_core::telemetry::init("agon-worker");
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.split_first() {
+        None => {}
+        Some((command, rest)) if command == "reindex" => {
+            if let Err(e) = reindex(rest).await {
+                tracing::error!(error = %e, "reindex failed");
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some((command, _)) if command == "stamp-feed-ttl" => {
+            if let Err(e) = stamp_feed_ttl().await {
+                tracing::error!(error = %e, "feed TTL backfill failed");
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some((command, _)) if command == "backfill-team-matches" => {
+            if let Err(e) = backfill_team_matches().await {
+                tracing::error!(error = %e, "team match backfill failed");
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some((command, _)) if command == "backfill-match-feed" => {
+            if let Err(e) = backfill_match_feed().await {
+                tracing::error!(error = %e, "match feed backfill failed");
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some((command, _)) => {
+            tracing::error!(command = %command, "unknown command; exiting");
+            std::process::exit(2);
+        }
+    }
+
     let config = match Config::from_env() {
         Ok(c) => c,
         Err(e) => {
@@ -40,7 +96,9 @@ async fn main() {
 
     let aws_config = aws_config::load_from_env().await;
     let sqs = SqsClient::new(&aws_config);
-    let dao = Dao::from_env(config.table_name.clone()).await;
+    let dao = Dao::from_env(config.table_name.clone())
+        .await
+        .with_feed_retention_days(config.feed_retention_days);
     let search = SearchClient::new(config.meili_url.clone(), config.meili_key.clone());
 
     // Ensure the search indexes exist with the right filterable / sortable
@@ -69,6 +127,37 @@ async fn main() {
         },
     };
 
+    // Email is opt-in the same way.
+    let mailer = match &config.smtp_host {
+        None => {
+            tracing::info!("AGON_SMTP_HOST unset; email disabled");
+            None
+        }
+        Some(host) => {
+            let smtp = SmtpConfig {
+                host: host.clone(),
+                port: config.smtp_port,
+                security: if config.smtp_starttls {
+                    SmtpSecurity::StartTls
+                } else {
+                    SmtpSecurity::None
+                },
+                credentials: config.smtp_credentials.clone(),
+                from: config.email_from.clone(),
+            };
+            match EmailClient::new(smtp) {
+                Ok(client) => Some(Mailer {
+                    client,
+                    app_url: config.app_url.clone(),
+                }),
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to build SMTP client; exiting");
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+
     // The asset consumer shares the SQS client, DAO and config; build it before
     // moving `config` into the main consumer.
     let asset_consumer = asset_consumer::AssetConsumer::new(
@@ -77,12 +166,32 @@ async fn main() {
         std::sync::Arc::new(config.clone()),
     );
 
-    let consumer = Consumer::new(sqs, dao.clone(), search.clone(), push, config);
+    let consumer = Consumer::new(
+        sqs,
+        dao.clone(),
+        search.clone(),
+        push.clone(),
+        mailer.clone(),
+        config,
+    );
 
     // Attach a client so multi-step stream events start workflows. A connection
     // failure here is fatal — Temporal is a required dependency of the worker.
     let consumer = match temporal::client::TemporalClient::connect().await {
-        Ok(client) => consumer.with_temporal(client),
+        Ok(client) => {
+            // Digests are emailed, so their schedules only run with email on.
+            // Neither schedule is fatal: the stream work doesn't depend on
+            // them, and the next boot tries again.
+            if mailer.is_some()
+                && let Err(e) = client.ensure_digest_schedules().await
+            {
+                tracing::error!(error = %e, "failed to start digest schedules");
+            }
+            if let Err(e) = client.ensure_suggestions_schedule().await {
+                tracing::error!(error = %e, "failed to start suggestions schedule");
+            }
+            consumer.with_temporal(client)
+        }
         Err(e) => {
             tracing::error!(error = %e, "failed to connect Temporal client; exiting");
             std::process::exit(1);
@@ -106,7 +215,7 @@ async fn main() {
     let consumer_fut = consumer.run(Box::pin(events_shutdown));
     let asset_fut = asset_consumer.run(Box::pin(asset_shutdown));
     let temporal_fut = async {
-        if let Err(e) = temporal::worker::run(dao, search).await {
+        if let Err(e) = temporal::worker::run(dao, search, push, mailer).await {
             tracing::error!(error = %e, "temporal worker exited with error");
         }
     };
@@ -115,6 +224,119 @@ async fn main() {
     tracing::info!("worker stopped");
 }
 
+/// The `reindex` command: rebuild each named index (every index when none
+/// are named) in turn, waiting for each to finish.
+async fn reindex(names: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let indexes = if names.is_empty() {
+        Index::ALL.to_vec()
+    } else {
+        names
+            .iter()
+            .map(|n| Index::from_name(n).ok_or_else(|| format!("unknown index {n:?}")))
+            .collect::<Result<_, _>>()?
+    };
+    let client = temporal::client::TemporalClient::connect().await?;
+    for index in indexes {
+        tracing::info!(index = index.name(), "reindexing");
+        let done = client.reindex(index).await?;
+        tracing::info!(
+            index = index.name(),
+            pages = done.pages,
+            indexed = done.indexed,
+            "reindex complete"
+        );
+    }
+    Ok(())
+}
+
+/// Items read per page of the feed TTL backfill's table scan.
+const STAMP_FEED_TTL_PAGE: u32 = 1000;
+
+/// The `stamp-feed-ttl` command: page through the table stamping a TTL on
+/// every feed entry without one (see `Dao::stamp_feed_ttl_page`).
+async fn stamp_feed_ttl() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let dao = Dao::from_env(config.table_name.clone())
+        .await
+        .with_feed_retention_days(config.feed_retention_days);
+    let (mut pages, mut stamped) = (0u64, 0u64);
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = dao
+            .stamp_feed_ttl_page(cursor.as_deref(), STAMP_FEED_TTL_PAGE)
+            .await?;
+        pages += 1;
+        stamped += page.items.len() as u64;
+        if pages % 100 == 0 {
+            tracing::info!(pages, stamped, "stamping feed TTLs");
+        }
+        match page.next_cursor {
+            Some(c) => cursor = Some(c),
+            None => break,
+        }
+    }
+    tracing::info!(pages, stamped, "feed TTL backfill complete");
+    Ok(())
+}
+
+/// Items read per page of the team match backfill's table scan.
+const BACKFILL_TEAM_MATCHES_PAGE: u32 = 1000;
+
+/// The `backfill-team-matches` command: page through the table writing the
+/// `TMATCH#` pointers of every match's team sides (see
+/// `Dao::backfill_team_match_pointers_page`).
+async fn backfill_team_matches() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let dao = Dao::from_env(config.table_name.clone()).await;
+    let (mut pages, mut written) = (0u64, 0u64);
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = dao
+            .backfill_team_match_pointers_page(cursor.as_deref(), BACKFILL_TEAM_MATCHES_PAGE)
+            .await?;
+        pages += 1;
+        written += page.items.len() as u64;
+        if pages % 100 == 0 {
+            tracing::info!(pages, written, "backfilling team match pointers");
+        }
+        match page.next_cursor {
+            Some(c) => cursor = Some(c),
+            None => break,
+        }
+    }
+    tracing::info!(pages, written, "team match backfill complete");
+    Ok(())
+}
+
+/// Items read per page of the match feed backfill's table scan.
+const BACKFILL_MATCH_FEED_PAGE: u32 = 1000;
+
+/// The `backfill-match-feed` command: page through the table projecting every
+/// match and result feed entry onto `MFEED#` (see
+/// `Dao::project_match_feed_page`).
+async fn backfill_match_feed() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let dao = Dao::from_env(config.table_name.clone()).await;
+    let (mut pages, mut projected) = (0u64, 0u64);
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = dao
+            .project_match_feed_page(cursor.as_deref(), BACKFILL_MATCH_FEED_PAGE)
+            .await?;
+        pages += 1;
+        projected += page.items.len() as u64;
+        if pages % 100 == 0 {
+            tracing::info!(pages, projected, "projecting match feed entries");
+        }
+        match page.next_cursor {
+            Some(c) => cursor = Some(c),
+            None => break,
+        }
+    }
+    tracing::info!(pages, projected, "match feed backfill complete");
+    Ok(())
+}
+
 /// A future that resolves when the shutdown broadcast fires (or the sender is
 /// dropped / the receiver lags), used to stop each consumer loop cleanly.
 fn subscribe_shutdown(
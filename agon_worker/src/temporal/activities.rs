@@ -4,19 +4,76 @@
 //! Activities take a single (de)serializable argument, so multi-field inputs are
 //! passed as structs.
 
+use std::collections::HashSet;
+
 use agon_core::dao::Dao;
+use agon_core::dao::audience::AudienceMember;
+use agon_core::dao::feed::FeedRef;
+use agon_core::dao::records::{
+    AudienceReason, DigestFrequencyRecord, FeedRefType, MilestoneRecord, SearchReindexRecord,
+    SuggestionsRecord, Visibility,
+};
+use agon_core::email::templates::{DigestContent, DigestMatch, DigestPeriod, digest_email};
+use agon_core::push::PushClient;
 use agon_core::search::{Index, SearchClient};
+use agon_core::suggestions::{self, SuggestionTally};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use temporalio_macros::activities;
 use temporalio_sdk::activities::{ActivityContext, ActivityError};
 
+use crate::handlers::index;
+
 /// Shared dependencies available to every activity. Registered once with the
-/// worker; activities read `dao` / `search` off it.
+/// worker; activities read `dao` / `search` / `push` / `mailer` off it.
 pub struct AgonActivities {
     pub dao: Dao,
     pub search: SearchClient,
+    /// `None` when push isn't configured, as for the inline push handler.
+    pub push: Option<PushClient>,
+    /// `None` when email isn't configured, likewise.
+    pub mailer: Option<crate::handlers::email::Mailer>,
 }
 
+/// Most matches listed in each digest section.
+const DIGEST_SECTION_MAX: usize = 10;
+
+/// Feed rows read per digest section, before dropping cancelled (or not yet
+/// completed) matches. Together the two sections stay within one
+/// `batch_get_match_metas` call.
+const DIGEST_FEED_SCAN: u32 = 50;
+
+/// Digest recipients per page. Also the batch size for sending: one
+/// `batch_get_users` call per page.
+pub const DIGEST_PAGE: u32 = 50;
+
+/// How far back a new follow reaches into the followed user's, team's or
+/// club's matches, and how many of them (latest first) it adds to the
+/// follower's feed. Upcoming matches count too, within the same cap.
+const FOLLOW_BACKFILL_DAYS: i64 = 30;
+const FOLLOW_BACKFILL_MAX: usize = 50;
+
+/// Users per suggestions refresh page (one activity each).
+const SUGGESTIONS_PAGE: u32 = 25;
+
+/// How much of the follow graph a user's suggestions read: their follows
+/// (all of which are excluded from the suggestions), the first
+/// `SUGGEST_FOLLOWEES` of those people, and that many of each one's own
+/// follows.
+const SUGGEST_FOLLOWING_SCAN: u32 = 500;
+const SUGGEST_FOLLOWEES: usize = 50;
+const SUGGEST_FOLLOWEE_FOLLOWS: u32 = 50;
+
+/// How many of a user's matches their suggestions look through.
+const SUGGEST_MATCHES: u32 = 50;
+
+/// Reads in flight at once while gathering one user's suggestions.
+const SUGGEST_READ_CONCURRENCY: usize = 8;
+
+/// Entity ids read per search rebuild page. At most `BATCH_GET_MAX`, so a
+/// page of users hydrates in one `batch_get_users` call.
+const REINDEX_PAGE: u32 = 100;
+
 /// One audience member: the viewer plus their capped "people you follow in
 /// this match" list and their own side (if they're a participant) — see
 /// `agon_core::dao::audience::AudienceMember`.
@@ -26,6 +83,36 @@ pub struct FeedViewer {
     pub known_player_ids: Vec<String>,
     pub known_player_count: u32,
     pub viewer_side_id: Option<String>,
+    #[serde(default)]
+    pub reasons: Vec<AudienceReason>,
+}
+
+impl FeedViewer {
+    fn from_audience((viewer_id, member): (String, AudienceMember)) -> Self {
+        Self {
+            viewer_id,
+            known_player_ids: member.known_player_ids,
+            known_player_count: member.known_player_count,
+            viewer_side_id: member.viewer_side_id,
+            reasons: member.reasons,
+        }
+    }
+}
+
+/// The DAO's shape of a chunk of viewers, for `Dao::write_feed_items`.
+fn dao_viewers(viewers: Vec<FeedViewer>) -> Vec<agon_core::dao::feed::FeedViewer> {
+    viewers
+        .into_iter()
+        .map(|v| agon_core::dao::feed::FeedViewer {
+            viewer_id: v.viewer_id,
+            audience: AudienceMember {
+                known_player_ids: v.known_player_ids,
+                known_player_count: v.known_player_count,
+                viewer_side_id: v.viewer_side_id,
+                reasons: v.reasons,
+            },
+        })
+        .collect()
 }
 
 /// The result of resolving a match's fan-out: who should see it (with their
@@ -34,8 +121,16 @@ pub struct FeedViewer {
 pub struct FanoutAudience {
     pub viewers: Vec<FeedViewer>,
     pub starts_at: String,
+    /// When the match's current result was confirmed — the sort time of its
+    /// result entries. `None` with no confirmed result, or once cancelled.
+    #[serde(default)]
+    pub confirmed_at: Option<String>,
     /// False if the match no longer exists (workflow should stop).
     pub match_exists: bool,
+    /// True for a participants-only match: `viewers` is then just the
+    /// participants, and every other viewer's row is pruned.
+    #[serde(default)]
+    pub participants_only: bool,
 }
 
 /// One chunk of feed writes: the viewers in this batch for a given match.
@@ -43,9 +138,125 @@ pub struct FanoutAudience {
 pub struct WriteFeedChunk {
     pub viewers: Vec<FeedViewer>,
     pub match_id: String,
+    /// The entries' sort time: the match's start, or for a result, its
+    /// confirmation.
     pub starts_at: String,
     /// Processing timestamp, stamped by the workflow (deterministic per run).
     pub now: String,
+    /// `Match` or `Result`; both point at `match_id`.
+    #[serde(default)]
+    pub ref_type: FeedRefType,
+}
+
+/// One chunk of feed writes for anything but a match: a milestone, a team
+/// announcement, a new member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteFeedPosts {
+    pub viewers: Vec<FeedViewer>,
+    pub feed_ref: FeedRef,
+    /// Processing timestamp, stamped by the workflow (deterministic per run).
+    pub now: String,
+}
+
+/// Inputs for pruning a match's feed rows left under an old start time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneFeed {
+    pub match_id: String,
+    /// The match's current start time; rows under any other are deleted.
+    pub starts_at: String,
+    /// Likewise for result rows and the current result's confirmation. With
+    /// none, every result row goes.
+    #[serde(default)]
+    pub confirmed_at: Option<String>,
+    /// When set, rows of viewers outside it are deleted too. Only passed for
+    /// a participants-only match, whose audience is small.
+    #[serde(default)]
+    pub audience: Option<Vec<String>>,
+}
+
+/// A follow edge: `follower_id` follows the user, team or club `followed`
+/// names (one of the `Follows*` reasons).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowEdge {
+    pub follower_id: String,
+    pub followed: AudienceReason,
+}
+
+/// A new follow to backfill, as of `now` (stamped by the workflow).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowBackfillRefs {
+    pub edge: FollowEdge,
+    pub now: String,
+}
+
+/// The entries a new follow adds to the follower's feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteFollowBackfill {
+    pub edge: FollowEdge,
+    pub refs: Vec<FeedRef>,
+    pub now: String,
+}
+
+/// Which deferred pushes to send: `user_id`'s, released by `release_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleasePushes {
+    pub user_id: String,
+    pub release_at: String,
+}
+
+/// One page of a digest's recipients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestRecipientsPage {
+    pub frequency: DigestFrequencyRecord,
+    pub cursor: Option<String>,
+}
+
+/// One page of user ids — a digest's recipients, or every user — and where
+/// the next page starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserIdPage {
+    pub user_ids: Vec<String>,
+    pub next_cursor: Option<String>,
+}
+
+/// One batch of digests to send: `frequency`'s, to `user_ids`, summarising
+/// the period around `now` (stamped by the workflow).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendDigestChunk {
+    pub frequency: DigestFrequencyRecord,
+    pub user_ids: Vec<String>,
+    pub now: String,
+}
+
+/// One batch of users whose follow suggestions to recompute, stamped with
+/// the refresh's start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshSuggestionsChunk {
+    pub user_ids: Vec<String>,
+    pub now: String,
+}
+
+/// Start a rebuild of `index`, stamped with the workflow's start time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeginReindex {
+    pub index: Index,
+    pub started_at: String,
+}
+
+/// One page of a search rebuild: `index`'s entities from `cursor` on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexPage {
+    pub index: Index,
+    pub cursor: Option<String>,
+}
+
+/// What one rebuild page covered, and where the next one starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexedPage {
+    /// Documents written to the shadow — fewer than the ids read when some
+    /// were deleted or archived meanwhile.
+    pub indexed: u64,
+    pub next_cursor: Option<String>,
 }
 
 /// Inputs for linking an accepted invitation to its roster entry.
@@ -66,16 +277,22 @@ impl AgonActivities {
         _ctx: ActivityContext,
         match_id: String,
     ) -> Result<FanoutAudience, ActivityError> {
-        let starts_at = match self.dao.get_match(&match_id).await.map_err(activity_err)? {
-            Some(agg) => agg.match_.starts_at,
-            None => {
-                return Ok(FanoutAudience {
-                    viewers: Vec::new(),
-                    starts_at: String::new(),
-                    match_exists: false,
-                });
-            }
+        let Some(agg) = self.dao.get_match(&match_id).await.map_err(activity_err)? else {
+            return Ok(FanoutAudience {
+                viewers: Vec::new(),
+                starts_at: String::new(),
+                confirmed_at: None,
+                match_exists: false,
+                participants_only: false,
+            });
         };
+        let match_ = agg.match_;
+        // A cancelled match keeps its confirmed score for the record, but
+        // its result no longer makes news.
+        let confirmed_at = match_
+            .confirmed_score
+            .and_then(|cs| cs.confirmed_at)
+            .filter(|_| match_.status != "cancelled");
         let audience = self
             .dao
             .resolve_fanout_audience(&match_id)
@@ -83,43 +300,195 @@ impl AgonActivities {
             .map_err(activity_err)?;
         let viewers = audience
             .into_iter()
-            .map(|(viewer_id, member)| FeedViewer {
-                viewer_id,
-                known_player_ids: member.known_player_ids,
-                known_player_count: member.known_player_count,
-                viewer_side_id: member.viewer_side_id,
-            })
+            .map(FeedViewer::from_audience)
             .collect();
         Ok(FanoutAudience {
             viewers,
-            starts_at,
+            starts_at: match_.starts_at,
+            confirmed_at,
             match_exists: true,
+            participants_only: match_.visibility == Visibility::Participants,
         })
     }
 
-    /// Write one chunk of feed entries. Idempotent on `<starts_at>#<matchId>`, so
-    /// a retried chunk is harmless.
+    /// Who should see a user's own news (their milestones): them and their
+    /// followers, unless their profile is participants-only.
+    #[activity]
+    pub async fn resolve_user_feed_audience(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        user_id: String,
+    ) -> Result<Vec<FeedViewer>, ActivityError> {
+        let audience = self
+            .dao
+            .resolve_user_feed_audience(&user_id)
+            .await
+            .map_err(activity_err)?;
+        Ok(audience
+            .into_iter()
+            .map(FeedViewer::from_audience)
+            .collect())
+    }
+
+    /// Who should see a team's news (announcements, new members): its current
+    /// members and its followers, unless it's participants-only.
+    #[activity]
+    pub async fn resolve_team_feed_audience(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        team_id: String,
+    ) -> Result<Vec<FeedViewer>, ActivityError> {
+        let audience = self
+            .dao
+            .resolve_team_feed_audience(&team_id)
+            .await
+            .map_err(activity_err)?;
+        Ok(audience
+            .into_iter()
+            .map(FeedViewer::from_audience)
+            .collect())
+    }
+
+    /// Write one chunk of a match's (or its result's) feed entries.
+    /// Idempotent on `<starts_at>#<matchId>`, so a retried chunk is harmless.
     #[activity]
     pub async fn write_feed_chunk(
         self: std::sync::Arc<Self>,
         _ctx: ActivityContext,
         chunk: WriteFeedChunk,
     ) -> Result<(), ActivityError> {
-        let viewers: Vec<agon_core::dao::feed::FeedViewer> = chunk
-            .viewers
-            .into_iter()
-            .map(|v| agon_core::dao::feed::FeedViewer {
-                viewer_id: v.viewer_id,
-                audience: agon_core::dao::audience::AudienceMember {
-                    known_player_ids: v.known_player_ids,
-                    known_player_count: v.known_player_count,
-                    viewer_side_id: v.viewer_side_id,
-                },
-            })
-            .collect();
+        let feed_ref = FeedRef {
+            ref_type: chunk.ref_type,
+            ..FeedRef::match_at(&chunk.match_id, &chunk.starts_at)
+        };
+        self.dao
+            .write_feed_items(&dao_viewers(chunk.viewers), &feed_ref, &chunk.now)
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Write one chunk of feed entries for a milestone, announcement or new
+    /// member. Idempotent on `<at>#<refId>`, likewise.
+    #[activity]
+    pub async fn write_feed_posts(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        chunk: WriteFeedPosts,
+    ) -> Result<(), ActivityError> {
+        self.dao
+            .write_feed_items(&dao_viewers(chunk.viewers), &chunk.feed_ref, &chunk.now)
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Record a milestone, or read back the one already recorded — so its
+    /// `reached_at`, the sort time of its feed entries, is whichever
+    /// detection came first.
+    #[activity]
+    pub async fn record_milestone(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        milestone: MilestoneRecord,
+    ) -> Result<MilestoneRecord, ActivityError> {
+        self.dao
+            .record_milestone(&milestone)
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Delete the match's feed rows keyed under a start time other than its
+    /// current one (left behind by a postponement), its result rows not under
+    /// the current result's confirmation, and those of viewers outside
+    /// `audience` when given. Idempotent.
+    #[activity]
+    pub async fn prune_stale_feed_items(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: PruneFeed,
+    ) -> Result<(), ActivityError> {
+        let audience: Option<HashSet<String>> = input.audience.map(|a| a.into_iter().collect());
+        self.dao
+            .prune_stale_feed_items(
+                &input.match_id,
+                &input.starts_at,
+                input.confirmed_at.as_deref(),
+                audience.as_ref(),
+            )
+            .await
+            .map(|_| ())
+            .map_err(activity_err)
+    }
+
+    /// The recent and upcoming matches a new follow adds to the follower's
+    /// feed (see `Dao::follow_backfill_refs`) — none if they've unfollowed
+    /// again since.
+    #[activity]
+    pub async fn resolve_follow_backfill(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: FollowBackfillRefs,
+    ) -> Result<Vec<FeedRef>, ActivityError> {
+        let edge = &input.edge;
+        let following = self
+            .dao
+            .is_following(&edge.follower_id, &edge.followed)
+            .await
+            .map_err(activity_err)?;
+        if !following {
+            return Ok(Vec::new());
+        }
+        let now = chrono::DateTime::parse_from_rfc3339(&input.now)
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now());
+        let since = (now - chrono::Duration::days(FOLLOW_BACKFILL_DAYS))
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        self.dao
+            .follow_backfill_refs(&edge.followed, &since, FOLLOW_BACKFILL_MAX)
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Add a new follow's matches to the follower's feed, or the follow to
+    /// the entries already there. Idempotent.
+    #[activity]
+    pub async fn write_follow_backfill(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: WriteFollowBackfill,
+    ) -> Result<(), ActivityError> {
+        self.dao
+            .add_feed_reason(
+                &input.edge.follower_id,
+                &input.edge.followed,
+                &input.refs,
+                &input.now,
+            )
+            .await
+            .map(|_| ())
+            .map_err(activity_err)
+    }
+
+    /// Take an ended follow out of the follower's feed: the entries it alone
+    /// put there go, and the rest stop listing it. A no-op if they've
+    /// followed again since. Idempotent.
+    #[activity]
+    pub async fn prune_unfollowed_feed(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        edge: FollowEdge,
+    ) -> Result<(), ActivityError> {
+        let following = self
+            .dao
+            .is_following(&edge.follower_id, &edge.followed)
+            .await
+            .map_err(activity_err)?;
+        if following {
+            return Ok(());
+        }
         self.dao
-            .write_feed_items(&viewers, &chunk.match_id, &chunk.starts_at, &chunk.now)
+            .remove_feed_reason(&edge.follower_id, &edge.followed)
             .await
+            .map(|_| ())
             .map_err(activity_err)
     }
 
@@ -135,10 +504,9 @@ impl AgonActivities {
         match self.dao.get_match(&match_id).await.map_err(activity_err)? {
             Some(agg) => {
                 let doc = crate::handlers::index::match_search_doc(&agg);
-                self.search
-                    .upsert(Index::Matches, &doc)
+                crate::handlers::index::put_doc(&self.dao, &self.search, Index::Matches, &doc)
                     .await
-                    .map_err(search_err)
+                    .map_err(worker_err)
             }
             None => Ok(()),
         }
@@ -192,6 +560,455 @@ impl AgonActivities {
             .await
             .map_err(activity_err)
     }
+
+    /// Send a user's deferred pushes that are due, then delete them. One is
+    /// sent as it was written; several collapse into a single summary rather
+    /// than buzzing once each the moment quiet hours end. A retry after a
+    /// partial failure may repeat the push, never lose it.
+    #[activity]
+    pub async fn send_deferred_pushes(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: ReleasePushes,
+    ) -> Result<(), ActivityError> {
+        let Some(push) = &self.push else {
+            return Ok(());
+        };
+        let due = self
+            .dao
+            .list_due_deferred_pushes(&input.user_id, &input.release_at)
+            .await
+            .map_err(activity_err)?;
+        let (title, body) = match due.as_slice() {
+            [] => return Ok(()),
+            [one] => (one.title.clone(), one.body.clone()),
+            many => (
+                "While you were away".to_string(),
+                format!("You have {} new notifications", many.len()),
+            ),
+        };
+        crate::handlers::push::send_to_devices(&self.dao, push, &input.user_id, &title, &body)
+            .await
+            .map_err(worker_err)?;
+        for deferred in &due {
+            self.dao
+                .delete_deferred_push(deferred)
+                .await
+                .map_err(activity_err)?;
+        }
+        Ok(())
+    }
+
+    /// One page of the users subscribed to a digest.
+    #[activity]
+    pub async fn list_digest_recipients(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: DigestRecipientsPage,
+    ) -> Result<UserIdPage, ActivityError> {
+        let page = self
+            .dao
+            .list_digest_subscribers(input.frequency, input.cursor.as_deref(), DIGEST_PAGE)
+            .await
+            .map_err(activity_err)?;
+        Ok(UserIdPage {
+            user_ids: page.items.into_iter().map(|p| p.user_id).collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// Email one batch of digests: for each user, the matches in their feed
+    /// starting in the coming period, those completed in the past one, and
+    /// how many notifications they've had since. Nothing is sent to a user
+    /// with nothing to report. A retry after a partial failure may send some
+    /// of the batch twice.
+    #[activity]
+    pub async fn send_digest_chunk(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: SendDigestChunk,
+    ) -> Result<(), ActivityError> {
+        let Some(mailer) = &self.mailer else {
+            return Ok(());
+        };
+        let (period, days) = match input.frequency {
+            DigestFrequencyRecord::Off => return Ok(()),
+            DigestFrequencyRecord::Daily => (DigestPeriod::Daily, 1),
+            DigestFrequencyRecord::Weekly => (DigestPeriod::Weekly, 7),
+        };
+        let now = chrono::DateTime::parse_from_rfc3339(&input.now)
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now());
+        let stamp = |t: chrono::DateTime<chrono::Utc>| {
+            t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        };
+        let window = chrono::Duration::days(days);
+        let (since, now_s, until) = (stamp(now - window), stamp(now), stamp(now + window));
+
+        let users = self
+            .dao
+            .batch_get_users(&input.user_ids)
+            .await
+            .map_err(activity_err)?;
+        for user_id in &input.user_ids {
+            let Some(user) = users.get(user_id).filter(|u| !u.email.is_empty()) else {
+                continue;
+            };
+            let content = self
+                .digest_content(user_id, &since, &now_s, &until)
+                .await
+                .map_err(activity_err)?;
+            if let Some(email) = digest_email(period, &user.name, &content, &mailer.app_url) {
+                mailer.send(&user.email, email).await.map_err(worker_err)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// One page of every user's id, for the suggestions refresh.
+    #[activity]
+    pub async fn list_suggestion_users(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        cursor: Option<String>,
+    ) -> Result<UserIdPage, ActivityError> {
+        let page = self
+            .dao
+            .scan_user_ids(cursor.as_deref(), SUGGESTIONS_PAGE)
+            .await
+            .map_err(activity_err)?;
+        Ok(UserIdPage {
+            user_ids: page.items,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// Recompute and cache the follow suggestions of a batch of users. Each
+    /// user's record is replaced whole, so a retried batch is harmless.
+    #[activity]
+    pub async fn refresh_suggestions(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: RefreshSuggestionsChunk,
+    ) -> Result<(), ActivityError> {
+        for user_id in &input.user_ids {
+            let record = self
+                .suggestions_for(user_id, &input.now)
+                .await
+                .map_err(activity_err)?;
+            self.dao
+                .put_suggestions(&record)
+                .await
+                .map_err(activity_err)?;
+        }
+        Ok(())
+    }
+
+    /// Start rebuilding a search index: a fresh shadow with the current
+    /// settings, then the marker that makes the stream indexer write to it
+    /// too. In that order, so no stream write creates the shadow first.
+    #[activity]
+    pub async fn begin_reindex(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: BeginReindex,
+    ) -> Result<(), ActivityError> {
+        self.search
+            .create_shadow(input.index)
+            .await
+            .map_err(search_err)?;
+        self.dao
+            .put_search_reindex(&SearchReindexRecord {
+                index: input.index.name().to_string(),
+                started_at: input.started_at,
+                swap_task_uid: None,
+            })
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Build and add one page of documents to the index's shadow. Documents
+    /// are upserts by id, so a retried page is harmless.
+    #[activity]
+    pub async fn reindex_page(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        input: ReindexPage,
+    ) -> Result<ReindexedPage, ActivityError> {
+        let cursor = input.cursor.as_deref();
+        let (ids, next_cursor) = match input.index {
+            Index::Users => self.dao.scan_user_ids(cursor, REINDEX_PAGE).await,
+            Index::Teams => self.dao.scan_team_ids(cursor, REINDEX_PAGE).await,
+            Index::Matches => self.dao.scan_match_ids(cursor, REINDEX_PAGE).await,
+        }
+        .map(|page| (page.items, page.next_cursor))
+        .map_err(activity_err)?;
+
+        let indexed = match input.index {
+            Index::Users => {
+                let docs = index::user_docs(&self.dao, &ids)
+                    .await
+                    .map_err(worker_err)?;
+                self.search
+                    .fill_shadow(Index::Users, &docs)
+                    .await
+                    .map(|_| docs.len())
+            }
+            Index::Teams => {
+                let docs = index::team_docs(&self.dao, &ids)
+                    .await
+                    .map_err(worker_err)?;
+                self.search
+                    .fill_shadow(Index::Teams, &docs)
+                    .await
+                    .map(|_| docs.len())
+            }
+            Index::Matches => {
+                let docs = index::match_docs(&self.dao, &ids)
+                    .await
+                    .map_err(worker_err)?;
+                self.search
+                    .fill_shadow(Index::Matches, &docs)
+                    .await
+                    .map(|_| docs.len())
+            }
+        }
+        .map_err(search_err)?;
+
+        tracing::info!(
+            index = input.index.name(),
+            read = ids.len(),
+            indexed,
+            more = next_cursor.is_some(),
+            "reindexed page"
+        );
+        Ok(ReindexedPage {
+            indexed: indexed as u64,
+            next_cursor,
+        })
+    }
+
+    /// Swap a built shadow in and clear the rebuild marker. The swap's task
+    /// uid is recorded before waiting on it, so a retry waits on the same
+    /// swap rather than swapping back; with the marker already gone, there's
+    /// nothing left to do.
+    #[activity]
+    pub async fn swap_reindex(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        index: Index,
+    ) -> Result<(), ActivityError> {
+        let Some(mut marker) = self
+            .dao
+            .get_search_reindex(index.name())
+            .await
+            .map_err(activity_err)?
+        else {
+            return Ok(());
+        };
+        let task_uid = match marker.swap_task_uid {
+            Some(uid) => uid,
+            None => {
+                let uid = self.search.start_swap(index).await.map_err(search_err)?;
+                marker.swap_task_uid = Some(uid);
+                self.dao
+                    .put_search_reindex(&marker)
+                    .await
+                    .map_err(activity_err)?;
+                uid
+            }
+        };
+        self.search
+            .wait_for_task(task_uid)
+            .await
+            .map_err(search_err)?;
+        self.dao
+            .end_search_reindex(index.name())
+            .await
+            .map_err(activity_err)
+    }
+
+    /// Drop a swapped-out shadow (now holding the old documents).
+    #[activity]
+    pub async fn drop_reindex_shadow(
+        self: std::sync::Arc<Self>,
+        _ctx: ActivityContext,
+        index: Index,
+    ) -> Result<(), ActivityError> {
+        self.search.drop_shadow(index).await.map_err(search_err)
+    }
+}
+
+impl AgonActivities {
+    /// Gather and rank one user's follow suggestions (see
+    /// `agon_core::suggestions`).
+    async fn suggestions_for(
+        &self,
+        user_id: &str,
+        now: &str,
+    ) -> agon_core::dao::error::DaoResult<SuggestionsRecord> {
+        let dao = &self.dao;
+        let (following, followed_teams, member_teams, blocked, match_ids) = tokio::try_join!(
+            dao.list_user_following(user_id, None, SUGGEST_FOLLOWING_SCAN),
+            dao.list_followed_teams(user_id, None, SUGGEST_FOLLOWING_SCAN),
+            dao.list_user_teams(user_id, None, SUGGEST_FOLLOWING_SCAN),
+            dao.blocked_user_ids(user_id),
+            dao.list_user_match_ids(user_id, None, SUGGEST_MATCHES),
+        )?;
+        let mut tally = SuggestionTally::default();
+
+        // Friends of friends: who and what the people they follow follow.
+        let mut reads = futures::stream::iter(following.items.iter().take(SUGGEST_FOLLOWEES))
+            .map(|f| async move {
+                tokio::try_join!(
+                    dao.list_user_following(&f.followee_id, None, SUGGEST_FOLLOWEE_FOLLOWS),
+                    dao.list_followed_teams(&f.followee_id, None, SUGGEST_FOLLOWEE_FOLLOWS),
+                )
+            })
+            .buffer_unordered(SUGGEST_READ_CONCURRENCY);
+        while let Some(read) = reads.next().await {
+            let (users, teams) = read?;
+            for f in &users.items {
+                tally.followed_user(&f.followee_id);
+            }
+            for f in &teams.items {
+                tally.followed_team(&f.team_id);
+            }
+        }
+
+        // Co-participation: everyone else in their matches, and the teams.
+        let mut matches = futures::stream::iter(&match_ids.items)
+            .map(|id| dao.get_match(id))
+            .buffer_unordered(SUGGEST_READ_CONCURRENCY);
+        while let Some(agg) = matches.next().await {
+            let Some(agg) = agg? else {
+                continue;
+            };
+            let players: HashSet<&str> = agg
+                .players
+                .iter()
+                .filter_map(|p| p.user_id.as_deref())
+                .collect();
+            for player in players {
+                tally.played_with_user(player);
+            }
+            let teams: HashSet<&str> = agg
+                .match_
+                .sides
+                .values()
+                .filter_map(|s| s.team_id.as_deref())
+                .collect();
+            for team in teams {
+                tally.played_with_team(team);
+            }
+        }
+
+        let mut skip_users: HashSet<String> =
+            following.items.into_iter().map(|f| f.followee_id).collect();
+        skip_users.extend(blocked);
+        let skip_teams: HashSet<String> = followed_teams
+            .items
+            .into_iter()
+            .map(|f| f.team_id)
+            .chain(
+                member_teams
+                    .items
+                    .into_iter()
+                    .filter(|m| m.left_at.is_none())
+                    .map(|m| m.team_id),
+            )
+            .collect();
+        // Twice the cap, since some are dropped below.
+        let mut record = tally.rank(
+            user_id,
+            now,
+            &skip_users,
+            &skip_teams,
+            suggestions::MAX_SUGGESTIONS * 2,
+        );
+
+        // Someone reached only through the follow graph is suggested only if
+        // they're public, as search would show them; people you've played
+        // with you know already. Deleted, suspended and hidden accounts and
+        // archived teams go.
+        let user_ids: Vec<String> = record.users.iter().map(|u| u.user_id.clone()).collect();
+        let team_ids: Vec<String> = record.teams.iter().map(|t| t.team_id.clone()).collect();
+        let (users, teams) = tokio::try_join!(
+            dao.batch_get_users(&user_ids),
+            dao.batch_get_team_metas(&team_ids),
+        )?;
+        record.users.retain(|s| {
+            users.get(&s.user_id).is_some_and(|u| {
+                u.suspended_at.is_none()
+                    && u.hidden_at.is_none()
+                    && (s.shared_matches > 0 || u.visibility == Visibility::Public)
+            })
+        });
+        record.teams.retain(|s| {
+            teams.get(&s.team_id).is_some_and(|t| {
+                t.archived_at.is_none()
+                    && (s.shared_matches > 0 || t.visibility == Visibility::Public)
+            })
+        });
+        record.users.truncate(suggestions::MAX_SUGGESTIONS);
+        record.teams.truncate(suggestions::MAX_SUGGESTIONS);
+        Ok(record)
+    }
+
+    /// What one user's digest covers. Feed rows are keyed by start time, so
+    /// "results" are the matches that started in the past period and have
+    /// since completed.
+    async fn digest_content(
+        &self,
+        user_id: &str,
+        since: &str,
+        now: &str,
+        until: &str,
+    ) -> agon_core::dao::error::DaoResult<DigestContent> {
+        let upcoming = self
+            .dao
+            .list_feed_between(user_id, now, until, DIGEST_FEED_SCAN)
+            .await?;
+        let recent = self
+            .dao
+            .list_feed_between(user_id, since, now, DIGEST_FEED_SCAN)
+            .await?;
+        let ids: Vec<String> = upcoming
+            .iter()
+            .chain(&recent)
+            .map(|f| f.ref_id.clone())
+            .collect();
+        let metas = self.dao.batch_get_match_metas(&ids).await?;
+        let line = |id: &str, status: &str| {
+            metas
+                .get(id)
+                .filter(|m| m.status == status)
+                .map(|m| DigestMatch {
+                    match_id: m.id.clone(),
+                    name: m.name.clone(),
+                    starts_at: m.starts_at.clone(),
+                })
+        };
+        Ok(DigestContent {
+            upcoming: upcoming
+                .iter()
+                .filter_map(|f| line(&f.ref_id, "scheduled"))
+                .take(DIGEST_SECTION_MAX)
+                .collect(),
+            results: recent
+                .iter()
+                .rev()
+                .filter_map(|f| line(&f.ref_id, "completed"))
+                .take(DIGEST_SECTION_MAX)
+                .collect(),
+            activity_count: self
+                .dao
+                .count_notifications_since(user_id, since)
+                .await?
+                .try_into()
+                .unwrap_or(usize::MAX),
+        })
+    }
 }
 
 /// Map a DAO error into a Temporal `ActivityError` (an Application error, so the
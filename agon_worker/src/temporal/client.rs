@@ -5,14 +5,43 @@
 //! of deterministic ids): a redelivered stream event attaches to the existing
 //! run rather than erroring or double-processing.
 
+use std::time::Duration;
+
 use temporalio_client::{
-    Client, ClientOptions, Connection, WorkflowStartOptions,
-    envconfig::LoadClientConfigProfileOptions,
+    Client, ClientOptions, Connection, WorkflowGetResultOptions, WorkflowQueryOptions,
+    WorkflowStartOptions, envconfig::LoadClientConfigProfileOptions,
 };
 use temporalio_common::protos::temporal::api::enums::v1::WorkflowIdConflictPolicy;
 
-use super::workflows::{AcceptInvitation, AcceptInvitationInput, FanOutMatch};
-use super::{TASK_QUEUE, accept_workflow_id, fanout_workflow_id};
+use agon_core::dao::records::{DigestFrequencyRecord, MilestoneRecord};
+use agon_core::search::Index;
+
+use super::activities::{FollowEdge, ReleasePushes};
+use super::workflows::{
+    AcceptInvitation, AcceptInvitationInput, FanOutMatch, FanOutMilestone, FanOutMilestoneInput,
+    FanOutTeamPost, FanOutTeamPostInput, FollowBackfill, RefreshSuggestions,
+    RefreshSuggestionsInput, ReindexProgress, ReindexSearch, ReindexSearchInput,
+    ReleaseDeferredPushes, SendDigests, UnfollowPrune,
+};
+use super::{
+    SUGGESTIONS_WORKFLOW_ID, TASK_QUEUE, accept_workflow_id, digest_workflow_id,
+    fanout_workflow_id, follow_backfill_workflow_id, milestone_workflow_id, reindex_workflow_id,
+    release_pushes_workflow_id, team_post_workflow_id, unfollow_prune_workflow_id,
+};
+
+/// How often [`TemporalClient::reindex`] logs a rebuild's progress.
+const REINDEX_PROGRESS_EVERY: Duration = Duration::from_secs(10);
+
+/// When each digest goes out (cron, UTC): daily every morning, weekly on
+/// Monday mornings.
+const DIGEST_SCHEDULES: [(DigestFrequencyRecord, &str); 2] = [
+    (DigestFrequencyRecord::Daily, "0 8 * * *"),
+    (DigestFrequencyRecord::Weekly, "0 8 * * 1"),
+];
+
+/// When follow suggestions are recomputed (cron, UTC): nightly, ahead of the
+/// morning digests.
+const SUGGESTIONS_SCHEDULE: &str = "0 4 * * *";
 
 /// Thin wrapper over a Temporal client for starting Agon workflows.
 #[derive(Clone)]
@@ -48,6 +77,94 @@ impl TemporalClient {
         Ok(())
     }
 
+    /// Start (or attach to) the fan-out of a user's milestone. Idempotent via
+    /// the deterministic `milestone-<milestone_id>` id + `UseExisting`
+    /// conflict policy.
+    pub async fn start_milestone(
+        &self,
+        input: FanOutMilestoneInput,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let m = &input.milestone;
+        let id = milestone_workflow_id(&MilestoneRecord::id_for(
+            &input.user_id,
+            &m.sport,
+            m.stat,
+            m.count,
+        ));
+        self.client
+            .start_workflow(
+                FanOutMilestone::run,
+                input,
+                WorkflowStartOptions::new(TASK_QUEUE, id)
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Start (or attach to) the fan-out of a team announcement or new member.
+    /// Idempotent via the deterministic `team-post-<ref_id>` id +
+    /// `UseExisting` conflict policy.
+    pub async fn start_team_post(
+        &self,
+        input: FanOutTeamPostInput,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let id = team_post_workflow_id(&input.feed_ref.ref_id);
+        self.client
+            .start_workflow(
+                FanOutTeamPost::run,
+                input,
+                WorkflowStartOptions::new(TASK_QUEUE, id)
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Start (or attach to) the backfill of a new follow into the follower's
+    /// feed. Idempotent via the deterministic
+    /// `follow-backfill-<follower_id>-<followed>` id + `UseExisting` conflict
+    /// policy; following again after it finished starts a new run.
+    pub async fn start_follow_backfill(
+        &self,
+        edge: FollowEdge,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let id = follow_backfill_workflow_id(&edge.follower_id, &edge.followed.to_string());
+        self.client
+            .start_workflow(
+                FollowBackfill::run,
+                edge,
+                WorkflowStartOptions::new(TASK_QUEUE, id)
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Start (or attach to) the pruning of an ended follow from the
+    /// follower's feed. Idempotent via the deterministic
+    /// `unfollow-prune-<follower_id>-<followed>` id + `UseExisting` conflict
+    /// policy.
+    pub async fn start_unfollow_prune(
+        &self,
+        edge: FollowEdge,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let id = unfollow_prune_workflow_id(&edge.follower_id, &edge.followed.to_string());
+        self.client
+            .start_workflow(
+                UnfollowPrune::run,
+                edge,
+                WorkflowStartOptions::new(TASK_QUEUE, id)
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Start (or attach to) the accept-invitation saga. Idempotent via the
     /// deterministic `accept-<invitation_id>` id + `UseExisting` conflict policy.
     pub async fn start_accept(
@@ -66,4 +183,108 @@ impl TemporalClient {
             .await?;
         Ok(())
     }
+
+    /// Start (or attach to) the run that sends a user's deferred pushes when
+    /// their quiet hours end. Idempotent via the deterministic
+    /// `release-pushes-<uid>-<release_at>` id + `UseExisting` conflict policy,
+    /// so a night's pushes share one run.
+    pub async fn start_release_pushes(
+        &self,
+        input: ReleasePushes,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let id = release_pushes_workflow_id(&input.user_id, &input.release_at);
+        self.client
+            .start_workflow(
+                ReleaseDeferredPushes::run,
+                input,
+                WorkflowStartOptions::new(TASK_QUEUE, id)
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Make sure the digest cron workflows exist. Called at boot; the fixed
+    /// `digest-<frequency>` ids + `UseExisting` make every call after the
+    /// first a no-op. (Changing a schedule means terminating the old
+    /// workflow so the next boot starts the new one.)
+    pub async fn ensure_digest_schedules(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for (frequency, cron) in DIGEST_SCHEDULES {
+            self.client
+                .start_workflow(
+                    SendDigests::run,
+                    frequency,
+                    WorkflowStartOptions::new(TASK_QUEUE, digest_workflow_id(frequency.as_str()))
+                        .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                        .cron_schedule(cron.to_string())
+                        .build(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Make sure the follow-suggestions cron workflow exists. Called at boot;
+    /// idempotent like [`ensure_digest_schedules`](Self::ensure_digest_schedules).
+    pub async fn ensure_suggestions_schedule(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.client
+            .start_workflow(
+                RefreshSuggestions::run,
+                RefreshSuggestionsInput::default(),
+                WorkflowStartOptions::new(TASK_QUEUE, SUGGESTIONS_WORKFLOW_ID.to_string())
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .cron_schedule(SUGGESTIONS_SCHEDULE.to_string())
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Start (or attach to) the rebuild of a search index and wait for it,
+    /// logging its progress as it goes. Idempotent via the deterministic
+    /// `reindex-<index>` id + `UseExisting`, so running this again while a
+    /// rebuild is underway (e.g. after the command itself was interrupted)
+    /// just resumes watching it.
+    pub async fn reindex(
+        &self,
+        index: Index,
+    ) -> Result<ReindexProgress, Box<dyn std::error::Error>> {
+        let id = reindex_workflow_id(index.name());
+        self.client
+            .start_workflow(
+                ReindexSearch::run,
+                ReindexSearchInput::new(index),
+                WorkflowStartOptions::new(TASK_QUEUE, id.clone())
+                    .id_conflict_policy(WorkflowIdConflictPolicy::UseExisting)
+                    .build(),
+            )
+            .await?;
+
+        // No run id: follows the rebuild across its continue-as-new runs.
+        let handle = self.client.get_workflow_handle::<ReindexSearch>(id);
+        let result = handle.get_result(WorkflowGetResultOptions::default());
+        tokio::pin!(result);
+        let mut ticks = tokio::time::interval(REINDEX_PROGRESS_EVERY);
+        loop {
+            tokio::select! {
+                done = &mut result => return Ok(done?),
+                _ = ticks.tick() => {
+                    match handle
+                        .query(ReindexSearch::progress, (), WorkflowQueryOptions::default())
+                        .await
+                    {
+                        Ok(p) => tracing::info!(
+                            index = index.name(),
+                            phase = ?p.phase,
+                            pages = p.pages,
+                            indexed = p.indexed,
+                            "reindex progress"
+                        ),
+                        Err(e) => tracing::warn!(error = %e, "reindex progress query failed"),
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,6 +1,9 @@
 //! Temporal integration: durable orchestration for the multi-step async work
-//! (feed fan-out, the accept-invitation saga). Built against the Temporal Rust
-//! SDK (`temporalio-sdk` / `temporalio-client`, crates.io 0.5).
+//! (feed fan-out of matches, results, milestones and team news, feed
+//! backfill and pruning as follows come and go, the accept-invitation saga,
+//! releasing pushes held by quiet hours, the digest emails, follow
+//! suggestions, search rebuilds). Built against the Temporal Rust SDK
+//! (`temporalio-sdk` / `temporalio-client`, crates.io 0.5).
 //!
 //! Split of responsibility (see docs/async-design.md §2/§4):
 //! - The **SQS consumer** owns *capture* — every committed write arrives via the
@@ -16,7 +19,7 @@ pub mod worker;
 pub mod workflows;
 
 /// The task queue both the worker and the client use. A single queue is fine —
-/// the two workflow types are distinguished by name, not queue.
+/// the workflow types are distinguished by name, not queue.
 pub const TASK_QUEUE: &str = "agon-async";
 
 /// Deterministic workflow id for a match fan-out. A duplicate start (e.g. a
@@ -26,7 +29,58 @@ pub fn fanout_workflow_id(match_id: &str) -> String {
     format!("fanout-{match_id}")
 }
 
+/// Deterministic workflow id for fanning out a stats milestone. Recording it
+/// is first-write-wins anyway; this keeps a redelivery from fanning it out
+/// twice at once.
+pub fn milestone_workflow_id(milestone_id: &str) -> String {
+    format!("milestone-{milestone_id}")
+}
+
+/// Deterministic workflow id for fanning out a piece of team news — an
+/// announcement or a membership, by its id.
+pub fn team_post_workflow_id(ref_id: &str) -> String {
+    format!("team-post-{ref_id}")
+}
+
+/// Deterministic workflow id for backfilling a new follow into the
+/// follower's feed. `followed` is the follow's `AudienceReason` string
+/// (`user#<id>`, `team#<id>`, `club#<id>`).
+pub fn follow_backfill_workflow_id(follower_id: &str, followed: &str) -> String {
+    format!("follow-backfill-{follower_id}-{followed}")
+}
+
+/// Deterministic workflow id for pruning an ended follow from the follower's
+/// feed, keyed like [`follow_backfill_workflow_id`].
+pub fn unfollow_prune_workflow_id(follower_id: &str, followed: &str) -> String {
+    format!("unfollow-prune-{follower_id}-{followed}")
+}
+
 /// Deterministic workflow id for an invitation acceptance saga.
 pub fn accept_workflow_id(invitation_id: &str) -> String {
     format!("accept-{invitation_id}")
 }
+
+/// Deterministic workflow id for releasing a user's deferred pushes at one
+/// quiet-hours end. Every push deferred into the same night shares it, so
+/// they go out together from a single run.
+pub fn release_pushes_workflow_id(user_id: &str, release_at: &str) -> String {
+    format!("release-pushes-{user_id}-{release_at}")
+}
+
+/// Deterministic workflow id for one digest frequency's cron workflow. Fixed,
+/// so the boot-time start in every worker replica attaches to the one
+/// schedule.
+pub fn digest_workflow_id(frequency: &str) -> String {
+    format!("digest-{frequency}")
+}
+
+/// Workflow id of the daily follow-suggestions refresh. Fixed, like the
+/// digests', so every replica's boot-time start attaches to one schedule.
+pub const SUGGESTIONS_WORKFLOW_ID: &str = "suggestions";
+
+/// Deterministic workflow id for rebuilding one search index. Fixed, so
+/// re-running the `reindex` command attaches to a rebuild still in progress
+/// rather than starting a second one.
+pub fn reindex_workflow_id(index: &str) -> String {
+    format!("reindex-{index}")
+}
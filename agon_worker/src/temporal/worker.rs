@@ -5,6 +5,7 @@
 //! separate task in `main`), sharing the same `agon_core` clients.
 
 use agon_core::dao::Dao;
+use agon_core::push::PushClient;
 use agon_core::search::SearchClient;
 use temporalio_client::{
     Client, ClientOptions, Connection, envconfig::LoadClientConfigProfileOptions,
@@ -14,12 +15,21 @@ use temporalio_sdk_core::{CoreRuntime, RuntimeOptions};
 
 use super::TASK_QUEUE;
 use super::activities::AgonActivities;
-use super::workflows::{AcceptInvitation, FanOutMatch};
+use super::workflows::{
+    AcceptInvitation, FanOutMatch, FanOutMilestone, FanOutTeamPost, FollowBackfill,
+    RefreshSuggestions, ReindexSearch, ReleaseDeferredPushes, SendDigests, UnfollowPrune,
+};
+use crate::handlers::email::Mailer;
 
 /// Connect to Temporal (config from the standard `TEMPORAL_*` env / profile) and
-/// run the worker until the process exits. Registers both workflows and the
+/// run the worker until the process exits. Registers every workflow and the
 /// shared activities struct.
-pub async fn run(dao: Dao, search: SearchClient) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(
+    dao: Dao,
+    search: SearchClient,
+    push: Option<PushClient>,
+    mailer: Option<Mailer>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let runtime = CoreRuntime::new_assume_tokio(RuntimeOptions::builder().build()?)?;
 
     let (conn_options, client_options) =
@@ -28,9 +38,22 @@ pub async fn run(dao: Dao, search: SearchClient) -> Result<(), Box<dyn std::erro
     let client = Client::new(connection, client_options)?;
 
     let worker_options = WorkerOptions::new(TASK_QUEUE)
-        .register_activities(AgonActivities { dao, search })
+        .register_activities(AgonActivities {
+            dao,
+            search,
+            push,
+            mailer,
+        })
         .register_workflow::<FanOutMatch>()?
+        .register_workflow::<FanOutMilestone>()?
+        .register_workflow::<FanOutTeamPost>()?
+        .register_workflow::<FollowBackfill>()?
+        .register_workflow::<UnfollowPrune>()?
         .register_workflow::<AcceptInvitation>()?
+        .register_workflow::<ReleaseDeferredPushes>()?
+        .register_workflow::<SendDigests>()?
+        .register_workflow::<RefreshSuggestions>()?
+        .register_workflow::<ReindexSearch>()?
         .build();
 
     tracing::info!(task_queue = TASK_QUEUE, "temporal worker starting");
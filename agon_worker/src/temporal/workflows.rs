@@ -1,38 +1,119 @@
 //! Temporal workflows — deterministic orchestration of the multi-step async
-//! work (feed fan-out, the accept-invitation saga). Workflows call activities;
-//! they never touch DynamoDB / the network directly.
+//! work (feed fan-out of matches, results, milestones and team news, feed
+//! backfill and pruning as follows come and go, the accept-invitation saga,
+//! deferred pushes, digest emails, follow suggestions, search rebuilds).
+//! Workflows call activities; they never touch DynamoDB / the network
+//! directly.
 //!
 //! Built against the Temporal Rust SDK (crates.io 0.5) — the workflow/activity
 //! macros, `WorkflowContext::start_activity` and `workflow_time`, and the
 //! unit-struct + `#[run(ctx, input)]` shape all match the SDK's own examples.
 //!
 //! Idempotency / determinism:
-//! - Workflow ids are deterministic (`fanout-<match_id>`, `accept-<inv_id>`) and
-//!   started with `UseExisting`, so a duplicate start attaches to the running
-//!   run (see docs/async-design.md §3).
-//! - Every activity's effects are idempotent (feed writes keyed by match id,
-//!   link is a fixed-point update), so activity retries are safe.
+//! - Workflow ids are deterministic (`fanout-<match_id>`,
+//!   `milestone-<milestone_id>`, `team-post-<ref_id>`,
+//!   `follow-backfill-<follower_id>-<followed>`,
+//!   `unfollow-prune-<follower_id>-<followed>`, `accept-<inv_id>`,
+//!   `release-pushes-<uid>-<release_at>`, `digest-<frequency>`,
+//!   `suggestions`, `reindex-<index>`) and started with `UseExisting`, so a
+//!   duplicate start attaches to the running run (see docs/async-design.md
+//!   §3).
+//! - Every activity's effects are idempotent (feed writes keyed by the
+//!   referenced id, a milestone is recorded once, link is a fixed-point
+//!   update), so activity retries are safe.
 //! - Timestamps come from `ctx.workflow_time()` (deterministic on replay), never
 //!   the wall clock.
+//! - A step added to a workflow that already has runs in flight goes behind a
+//!   `ctx.patched(..)` marker, so those runs replay the history they recorded.
 
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use temporalio_macros::{workflow, workflow_methods};
-use temporalio_sdk::{ActivityOptions, WorkflowContext, WorkflowResult};
+use temporalio_sdk::{
+    ActivityOptions, ContinueAsNewOptions, WorkflowContext, WorkflowContextView, WorkflowResult,
+};
 
-use super::activities::{AgonActivities, LinkAccepted, WriteFeedChunk};
+use agon_core::dao::feed::FeedRef;
+use agon_core::dao::records::{DigestFrequencyRecord, FeedRefType};
+use agon_core::milestones::Milestone;
+use agon_core::search::Index;
+
+use super::activities::{
+    AgonActivities, BeginReindex, DigestRecipientsPage, FanoutAudience, FeedViewer,
+    FollowBackfillRefs, FollowEdge, LinkAccepted, PruneFeed, RefreshSuggestionsChunk, ReindexPage,
+    ReleasePushes, SendDigestChunk, WriteFeedChunk, WriteFeedPosts, WriteFollowBackfill,
+};
 
 /// How many feed rows to write per activity invocation. Each chunk is a
 /// separately-retryable, checkpointed step — the whole point of running fan-out
 /// on Temporal (a mid-way failure resumes, not restarts).
 const FEED_CHUNK: usize = 500;
 
+/// Patch marker for `FanOutMatch`'s prune step, which was added after runs
+/// were already in flight; see [`WorkflowContext::patched`].
+const PRUNE_STALE_FEED_PATCH: &str = "fanout-prune-stale-feed";
+
 /// Default activity timeout. Fan-out chunks and single DAO ops are all quick.
 fn activity_opts() -> ActivityOptions {
     ActivityOptions::start_to_close_timeout(Duration::from_secs(30))
 }
 
+/// Write a match's feed entries for its whole audience — and, once it has a
+/// confirmed result, the result's — in checkpointed chunks. Each chunk is its
+/// own activity, so a failure resumes at the failed chunk on replay.
+async fn write_match_feed<W>(
+    ctx: &WorkflowContext<W>,
+    match_id: &str,
+    audience: &FanoutAudience,
+    now: &str,
+) -> WorkflowResult<()> {
+    let mut refs = vec![(FeedRefType::Match, &audience.starts_at)];
+    if let Some(confirmed_at) = &audience.confirmed_at {
+        refs.push((FeedRefType::Result, confirmed_at));
+    }
+    for (ref_type, at) in refs {
+        for chunk in audience.viewers.chunks(FEED_CHUNK) {
+            ctx.start_activity(
+                AgonActivities::write_feed_chunk,
+                WriteFeedChunk {
+                    viewers: chunk.to_vec(),
+                    match_id: match_id.to_string(),
+                    starts_at: at.clone(),
+                    now: now.to_string(),
+                    ref_type,
+                },
+                activity_opts(),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Write one feed entry per viewer for a milestone, announcement or new
+/// member, in checkpointed chunks like [`write_match_feed`].
+async fn write_feed_posts<W>(
+    ctx: &WorkflowContext<W>,
+    viewers: &[FeedViewer],
+    feed_ref: &FeedRef,
+    now: &str,
+) -> WorkflowResult<()> {
+    for chunk in viewers.chunks(FEED_CHUNK) {
+        ctx.start_activity(
+            AgonActivities::write_feed_posts,
+            WriteFeedPosts {
+                viewers: chunk.to_vec(),
+                feed_ref: feed_ref.clone(),
+                now: now.to_string(),
+            },
+            activity_opts(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
 /// An RFC3339 timestamp from the workflow's deterministic clock. Falls back to
 /// empty if the context has no time yet (shouldn't happen inside `run`).
 fn workflow_now(ctx: &WorkflowContext<impl Sized>) -> String {
@@ -45,8 +126,9 @@ fn workflow_now(ctx: &WorkflowContext<impl Sized>) -> String {
 // FanOutMatch — fan a match into its audience's feeds.
 // ===========================================================================
 
-/// Fan a match into the feeds of everyone who should see it. Started when a
-/// match is created / completed. Workflow id: `fanout-<match_id>`.
+/// Fan a match into the feeds of everyone who should see it, along with its
+/// result once confirmed. Started when a match is created / completed /
+/// postponed / scored. Workflow id: `fanout-<match_id>`.
 #[workflow]
 #[derive(Default)]
 pub struct FanOutMatch;
@@ -68,24 +150,40 @@ impl FanOutMatch {
             return Ok(());
         }
 
-        // 2. Write feed entries in checkpointed chunks. Each chunk is its own
-        //    activity, so a failure resumes at the failed chunk on replay.
+        // 2. Write the match's (and its result's) feed entries.
         let now = workflow_now(ctx);
-        for chunk in audience.viewers.chunks(FEED_CHUNK) {
+        write_match_feed(ctx, &match_id, &audience, &now).await?;
+
+        // 3. Drop rows keyed under a previous start time — a postponement
+        //    changes every row's sort key, so step 2 wrote new rows rather
+        //    than overwriting the old ones — and likewise result rows under a
+        //    previous confirmation (or any, with no result now), and, for a
+        //    participants-only match, the rows of anyone who isn't a
+        //    participant (left from before it was made private). A no-op
+        //    otherwise. Patched in: runs started before this step existed
+        //    replay straight on to step 4.
+        if ctx.patched(PRUNE_STALE_FEED_PATCH) {
+            let keep = audience.participants_only.then(|| {
+                audience
+                    .viewers
+                    .iter()
+                    .map(|v| v.viewer_id.clone())
+                    .collect()
+            });
             ctx.start_activity(
-                AgonActivities::write_feed_chunk,
-                WriteFeedChunk {
-                    viewers: chunk.to_vec(),
+                AgonActivities::prune_stale_feed_items,
+                PruneFeed {
                     match_id: match_id.clone(),
                     starts_at: audience.starts_at.clone(),
-                    now: now.clone(),
+                    confirmed_at: audience.confirmed_at.clone(),
+                    audience: keep,
                 },
                 activity_opts(),
             )
             .await?;
         }
 
-        // 3. Ensure the match is searchable (idempotent; complements the inline
+        // 4. Ensure the match is searchable (idempotent; complements the inline
         //    indexing handler).
         ctx.start_activity(AgonActivities::index_match, match_id, activity_opts())
             .await?;
@@ -94,6 +192,162 @@ impl FanOutMatch {
     }
 }
 
+// ===========================================================================
+// FanOutMilestone — fan a stats milestone into feeds.
+// ===========================================================================
+
+/// A milestone `user_id` has just crossed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FanOutMilestoneInput {
+    pub user_id: String,
+    pub milestone: Milestone,
+}
+
+/// Record a user's milestone and fan it into their feed and their followers'.
+/// Started when the stats reconciler's write to a profile crosses a
+/// threshold (see `agon_core::milestones`). Workflow id:
+/// `milestone-<milestone_id>`.
+#[workflow]
+#[derive(Default)]
+pub struct FanOutMilestone;
+
+#[workflow_methods]
+impl FanOutMilestone {
+    #[run]
+    pub async fn run(
+        ctx: &mut WorkflowContext<Self>,
+        input: FanOutMilestoneInput,
+    ) -> WorkflowResult<()> {
+        // 1. Record it (first write wins), so a milestone detected again
+        //    keeps its first `reached_at` — the entries' sort key.
+        let now = workflow_now(ctx);
+        let milestone = ctx
+            .start_activity(
+                AgonActivities::record_milestone,
+                input.milestone.record(&input.user_id, &now),
+                activity_opts(),
+            )
+            .await?;
+
+        // 2. Write it to the user's feed audience.
+        let viewers = ctx
+            .start_activity(
+                AgonActivities::resolve_user_feed_audience,
+                input.user_id,
+                activity_opts(),
+            )
+            .await?;
+        let feed_ref = FeedRef {
+            ref_type: FeedRefType::Milestone,
+            ref_id: milestone.id,
+            subject_id: None,
+            at: milestone.reached_at,
+        };
+        write_feed_posts(ctx, &viewers, &feed_ref, &now).await
+    }
+}
+
+// ===========================================================================
+// FanOutTeamPost — fan team news into feeds.
+// ===========================================================================
+
+/// A piece of team news: an announcement or a new member, with `team_id` as
+/// its `subject_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FanOutTeamPostInput {
+    pub team_id: String,
+    pub feed_ref: FeedRef,
+}
+
+/// Fan a team announcement or new member into the feeds of the team's members
+/// and followers. Started when an announcement is posted or someone joins.
+/// Workflow id: `team-post-<ref_id>`.
+#[workflow]
+#[derive(Default)]
+pub struct FanOutTeamPost;
+
+#[workflow_methods]
+impl FanOutTeamPost {
+    #[run]
+    pub async fn run(
+        ctx: &mut WorkflowContext<Self>,
+        input: FanOutTeamPostInput,
+    ) -> WorkflowResult<()> {
+        let viewers = ctx
+            .start_activity(
+                AgonActivities::resolve_team_feed_audience,
+                input.team_id,
+                activity_opts(),
+            )
+            .await?;
+        let now = workflow_now(ctx);
+        write_feed_posts(ctx, &viewers, &input.feed_ref, &now).await
+    }
+}
+
+// ===========================================================================
+// FollowBackfill — add a new follow's recent matches to the follower's feed.
+// ===========================================================================
+
+/// Give a new follower the feed they'd have had if the follow had always
+/// been there: the followed user's, team's or club's recent and upcoming
+/// matches (fan-out only reaches them from the next match on). Started when
+/// a follow edge is written — a follow, or an approved follow request.
+/// Workflow id: `follow-backfill-<follower_id>-<followed>`.
+#[workflow]
+#[derive(Default)]
+pub struct FollowBackfill;
+
+#[workflow_methods]
+impl FollowBackfill {
+    #[run]
+    pub async fn run(ctx: &mut WorkflowContext<Self>, edge: FollowEdge) -> WorkflowResult<()> {
+        let now = workflow_now(ctx);
+        let refs = ctx
+            .start_activity(
+                AgonActivities::resolve_follow_backfill,
+                FollowBackfillRefs {
+                    edge: edge.clone(),
+                    now: now.clone(),
+                },
+                activity_opts(),
+            )
+            .await?;
+        if refs.is_empty() {
+            return Ok(());
+        }
+        ctx.start_activity(
+            AgonActivities::write_follow_backfill,
+            WriteFollowBackfill { edge, refs, now },
+            activity_opts(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+// ===========================================================================
+// UnfollowPrune — take an ended follow out of the follower's feed.
+// ===========================================================================
+
+/// Remove the feed entries an ended follow alone put in the follower's feed,
+/// and the follow from the entries something else (another follow, playing)
+/// keeps there. Started when a follow edge is deleted. Workflow id:
+/// `unfollow-prune-<follower_id>-<followed>`.
+#[workflow]
+#[derive(Default)]
+pub struct UnfollowPrune;
+
+#[workflow_methods]
+impl UnfollowPrune {
+    #[run]
+    pub async fn run(ctx: &mut WorkflowContext<Self>, edge: FollowEdge) -> WorkflowResult<()> {
+        ctx.start_activity(AgonActivities::prune_unfollowed_feed, edge, activity_opts())
+            .await?;
+        Ok(())
+    }
+}
+
 // ===========================================================================
 // AcceptInvitation — the acceptance saga.
 // ===========================================================================
@@ -150,19 +404,7 @@ impl AcceptInvitation {
 
             if audience.match_exists {
                 let now = workflow_now(ctx);
-                for chunk in audience.viewers.chunks(FEED_CHUNK) {
-                    ctx.start_activity(
-                        AgonActivities::write_feed_chunk,
-                        WriteFeedChunk {
-                            viewers: chunk.to_vec(),
-                            match_id: match_id.clone(),
-                            starts_at: audience.starts_at.clone(),
-                            now: now.clone(),
-                        },
-                        activity_opts(),
-                    )
-                    .await?;
-                }
+                write_match_feed(ctx, &match_id, &audience, &now).await?;
 
                 // 3. Reconcile the newly-linked player's stat contribution
                 //    (idempotent; a no-op unless the match is completed).
@@ -188,3 +430,307 @@ impl AcceptInvitation {
         Ok(())
     }
 }
+
+// ===========================================================================
+// ReleaseDeferredPushes — send pushes held back by quiet hours.
+// ===========================================================================
+
+/// Wait until a user's quiet hours end, then send the pushes deferred into
+/// them. Started when a deferred push is written. Workflow id:
+/// `release-pushes-<uid>-<release_at>`.
+#[workflow]
+#[derive(Default)]
+pub struct ReleaseDeferredPushes;
+
+#[workflow_methods]
+impl ReleaseDeferredPushes {
+    #[run]
+    pub async fn run(ctx: &mut WorkflowContext<Self>, input: ReleasePushes) -> WorkflowResult<()> {
+        // A durable timer: survives worker restarts, and a start that's
+        // already past `release_at` (a late redelivery) doesn't wait at all.
+        let release_at = chrono::DateTime::parse_from_rfc3339(&input.release_at)
+            .map(|t| t.with_timezone(&chrono::Utc));
+        let now = ctx
+            .workflow_time()
+            .map(chrono::DateTime::<chrono::Utc>::from);
+        if let (Ok(release_at), Some(now)) = (release_at, now)
+            && let Ok(wait) = (release_at - now).to_std()
+        {
+            ctx.timer(wait).await;
+        }
+
+        ctx.start_activity(AgonActivities::send_deferred_pushes, input, activity_opts())
+            .await?;
+        Ok(())
+    }
+}
+
+// ===========================================================================
+// SendDigests — the daily / weekly digest emails.
+// ===========================================================================
+
+/// Email every subscriber of one digest frequency their summary. Runs on a
+/// cron schedule (see `TemporalClient::ensure_digest_schedules`), one
+/// workflow per frequency. Workflow id: `digest-<frequency>`.
+///
+/// Each page of recipients is sent by its own activity, so a failure resumes
+/// at that page; the period summarised is pinned to the run's start.
+#[workflow]
+#[derive(Default)]
+pub struct SendDigests;
+
+#[workflow_methods]
+impl SendDigests {
+    #[run]
+    pub async fn run(
+        ctx: &mut WorkflowContext<Self>,
+        frequency: DigestFrequencyRecord,
+    ) -> WorkflowResult<()> {
+        let now = workflow_now(ctx);
+        let mut cursor = None;
+        loop {
+            let page = ctx
+                .start_activity(
+                    AgonActivities::list_digest_recipients,
+                    DigestRecipientsPage { frequency, cursor },
+                    activity_opts(),
+                )
+                .await?;
+            if !page.user_ids.is_empty() {
+                ctx.start_activity(
+                    AgonActivities::send_digest_chunk,
+                    SendDigestChunk {
+                        frequency,
+                        user_ids: page.user_ids,
+                        now: now.clone(),
+                    },
+                    long_activity_opts(),
+                )
+                .await?;
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+// ===========================================================================
+// RefreshSuggestions — recompute everyone's follow suggestions.
+// ===========================================================================
+
+/// Pages of users per run before continuing as new, keeping each run's
+/// history bounded however many users there are.
+const SUGGESTION_PAGES_PER_RUN: u32 = 500;
+
+/// Where a suggestions refresh has got to, when continuing as new. A fresh
+/// run starts from the defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RefreshSuggestionsInput {
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// The refresh's start, kept across continued runs.
+    #[serde(default)]
+    pub now: Option<String>,
+}
+
+/// Recompute and cache every user's follow suggestions, a page of users per
+/// activity. Runs daily on a cron schedule (see
+/// `TemporalClient::ensure_suggestions_schedule`). Workflow id: `suggestions`.
+#[workflow]
+#[derive(Default)]
+pub struct RefreshSuggestions;
+
+#[workflow_methods]
+impl RefreshSuggestions {
+    #[run]
+    pub async fn run(
+        ctx: &mut WorkflowContext<Self>,
+        input: RefreshSuggestionsInput,
+    ) -> WorkflowResult<()> {
+        let now = input.now.unwrap_or_else(|| workflow_now(ctx));
+        let mut cursor = input.cursor;
+        for _ in 0..SUGGESTION_PAGES_PER_RUN {
+            let page = ctx
+                .start_activity(
+                    AgonActivities::list_suggestion_users,
+                    cursor.clone(),
+                    activity_opts(),
+                )
+                .await?;
+            if !page.user_ids.is_empty() {
+                ctx.start_activity(
+                    AgonActivities::refresh_suggestions,
+                    RefreshSuggestionsChunk {
+                        user_ids: page.user_ids,
+                        now: now.clone(),
+                    },
+                    long_activity_opts(),
+                )
+                .await?;
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(()),
+            }
+        }
+        let next = RefreshSuggestionsInput {
+            cursor,
+            now: Some(now),
+        };
+        ctx.continue_as_new(&next, ContinueAsNewOptions::default())?;
+        Ok(())
+    }
+}
+
+/// For activities doing a batch of slower work — a digest chunk's SMTP round
+/// trip per recipient, a page of users' suggestions, a rebuild page waiting
+/// on Meilisearch.
+fn long_activity_opts() -> ActivityOptions {
+    ActivityOptions::start_to_close_timeout(Duration::from_secs(300))
+}
+
+// ===========================================================================
+// ReindexSearch — rebuild a search index from the table.
+// ===========================================================================
+
+/// Rebuild pages per run before continuing as new, keeping each run's
+/// history bounded however large the table.
+const REINDEX_PAGES_PER_RUN: u64 = 500;
+
+/// How long to keep the swapped-out shadow before dropping it, so a stream
+/// write that read the rebuild marker just before it was cleared lands in an
+/// index that still exists rather than recreating a deleted one.
+const REINDEX_DROP_GRACE: Duration = Duration::from_secs(60);
+
+/// Where a rebuild has got to — returned by the `progress` query and as the
+/// workflow's result.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReindexProgress {
+    pub phase: ReindexPhase,
+    /// Pages read from the table so far.
+    pub pages: u64,
+    /// Documents written to the shadow so far.
+    pub indexed: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexPhase {
+    #[default]
+    Building,
+    Swapping,
+    Done,
+}
+
+/// A rebuild's input: the index, and — when continuing as new — where the
+/// previous run left off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexSearchInput {
+    pub index: Index,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub progress: ReindexProgress,
+}
+
+impl ReindexSearchInput {
+    /// A rebuild of `index` from the start.
+    pub fn new(index: Index) -> Self {
+        Self {
+            index,
+            cursor: None,
+            progress: ReindexProgress::default(),
+        }
+    }
+}
+
+/// Rebuild a search index from the table without taking it offline: fill a
+/// shadow index page by page while the live one keeps serving (and the stream
+/// indexer writes to both), then swap the shadow in. Started by the worker's
+/// `reindex` command. Workflow id: `reindex-<index>`.
+///
+/// Resumable: each page is its own activity, so a failure resumes at that
+/// page, and re-running the command attaches to the running rebuild.
+#[workflow]
+#[derive(Default)]
+pub struct ReindexSearch {
+    progress: ReindexProgress,
+}
+
+#[workflow_methods]
+impl ReindexSearch {
+    #[run]
+    pub async fn run(
+        ctx: &mut WorkflowContext<Self>,
+        input: ReindexSearchInput,
+    ) -> WorkflowResult<ReindexProgress> {
+        let ReindexSearchInput {
+            index,
+            mut cursor,
+            mut progress,
+        } = input;
+        ctx.state_mut(|s| s.progress = progress.clone());
+
+        // A continued run picks up mid-build; only a fresh one starts the
+        // shadow over.
+        if progress.pages == 0 {
+            let started_at = workflow_now(ctx);
+            ctx.start_activity(
+                AgonActivities::begin_reindex,
+                BeginReindex { index, started_at },
+                activity_opts(),
+            )
+            .await?;
+        }
+
+        while progress.phase == ReindexPhase::Building {
+            let page = ctx
+                .start_activity(
+                    AgonActivities::reindex_page,
+                    ReindexPage {
+                        index,
+                        cursor: cursor.clone(),
+                    },
+                    long_activity_opts(),
+                )
+                .await?;
+            progress.pages += 1;
+            progress.indexed += page.indexed;
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                progress.phase = ReindexPhase::Swapping;
+            }
+            ctx.state_mut(|s| s.progress = progress.clone());
+
+            if cursor.is_some() && progress.pages % REINDEX_PAGES_PER_RUN == 0 {
+                let next = ReindexSearchInput {
+                    index,
+                    cursor: cursor.clone(),
+                    progress: progress.clone(),
+                };
+                ctx.continue_as_new(&next, ContinueAsNewOptions::default())?;
+            }
+        }
+
+        ctx.start_activity(AgonActivities::swap_reindex, index, long_activity_opts())
+            .await?;
+        ctx.timer(REINDEX_DROP_GRACE).await;
+        ctx.start_activity(
+            AgonActivities::drop_reindex_shadow,
+            index,
+            long_activity_opts(),
+        )
+        .await?;
+
+        progress.phase = ReindexPhase::Done;
+        ctx.state_mut(|s| s.progress = progress.clone());
+        Ok(progress)
+    }
+
+    #[query]
+    pub fn progress(&self, _ctx: &WorkflowContextView) -> ReindexProgress {
+        self.progress.clone()
+    }
+}